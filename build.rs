@@ -0,0 +1,21 @@
+use std::error::Error;
+use vergen::EmitBuilder;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // Embed the git commit, build date and enabled features so a running build can be identified
+    EmitBuilder::builder()
+        .build_timestamp()
+        .cargo_features()
+        .git_sha(true)
+        .emit()?;
+
+    // The gRPC server is generated from the committed proto, clients generate their own from it.
+    // Replies are filled straight from the JSON the GvCLI handlers answer with.
+    #[cfg(feature = "grpc")]
+    tonic_build::configure()
+        .build_client(false)
+        .type_attribute(".", "#[derive(serde::Deserialize)] #[serde(default)]")
+        .compile(&["proto/ghostvault.proto"], &["proto"])?;
+
+    Ok(())
+}
@@ -1,36 +1,103 @@
 #![allow(dead_code)]
-use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use crate::logging;
+use chrono::{DateTime, Datelike, Days, NaiveDate, TimeZone, Timelike, Utc};
 use chrono_tz::Tz;
 use futures::{future, prelude::*};
 use humantime::{format_duration, FormattedDuration};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use service::{
-    config::GVConfig,
-    constants::{GV_PID_FILE, MIN_TX_VALUE, TMP_PATH, VERSION},
-    daemon_helper::{listen_for_events, listen_zmq, DaemonHelper, DaemonState, TxidAndWallet},
+    amount::Amount,
+    announce::{self, AnnounceDestination, TemplateValues},
+    api_schema::{self, ApiMethod},
+    backup::{self, BackupInfo, MigrateInfo, WalletMeta},
+    cli_tls,
+    config::{
+        self, AnonPolicy, BootstrapSource, ConfigReload, DaemonSource, FeePolicy, FeeSettings,
+        GVConfig, HookSettings, PruneChange, UpdateChannel,
+    },
+    constants::{
+        ADDRESS_LABEL_MAX_LEN, ANON_MATURITY_CONFIRMATIONS, ANON_PAYOUT_TX_SIZE, API_VERSION,
+        AUDIT_LOG_DEFAULT, AUDIT_ORIGINS, BLOCK_REPLAY_LIMIT, BLOCK_TARGET_SPACING, BUILD_DATE,
+        CARGO_FEATURES, CHAIN_EMERGENCY_ALERT_INTERVAL, CHAIN_EMERGENCY_BAN_TIME,
+        CHAIN_EMERGENCY_SNOOZE, CHAIN_PEER_TIMEOUT, CHART_PAGE_DEFAULT, CHART_PAGE_MAX,
+        DAEMON_UPGRADE_TIMEOUT, DEFAULT_DEPOSIT_WINDOW_HOURS, DEFAULT_HOT_WALLET,
+        DEFAULT_LOG_TAIL_LINES, DEFAULT_SIMULATION_DAYS, DOCKER_RESTART_BACKOFF,
+        DOCKER_RESTART_BACKOFF_MAX, DOCKER_RESTART_TIMEOUT, EVENT_LONG_POLL_MAX, EVENT_PAGE_MAX,
+        EXPECTED_DEPOSIT_KEEP, GIT_COMMIT, GV_LOG_FILE, GV_PID_FILE, IMPORT_BIRTHDAY_MARGIN,
+        IMPORT_MESSAGE_INTERVAL, JOBS_DEFAULT, LOG_TAIL_MAX_BYTES, LOG_TAIL_MAX_LINES,
+        MAX_RING_SIZE, MIN_DAEMON_PRUNE, MIN_RING_SIZE, MIN_TX_VALUE, MISSED_STAKE_CHECK_INTERVAL,
+        NETWORK_SAMPLE_INTERVAL, NETWORK_STATS_MAX_POINTS, OPERATIONS, PAYOUT_UNLOCK_TIMEOUT,
+        PENDING_SPLIT_TTL, PENDING_UPDATE_TTL, QUIET_HOURS_CHECK_INTERVAL, RESTART_HISTORY_MAX,
+        SCHEDULED_TASKS, SIMULATION_MAX_DAYS, STAKE_FORECAST_WINDOW_DAYS, STAKE_MESSAGE_STEP,
+        STAKING_ONLY_UNLOCK, STREAK_MILESTONES, SYNC_MESSAGE_INTERVAL, SYNC_MESSAGE_MIN_BLOCKS,
+        SYNC_RATE_WINDOW, SYSTEM_SAMPLE_INTERVAL, TENANT_EXT_KEY_LOOKAHEAD, TX_LABEL_MAX_LEN,
+        VERSION, ZAP_REQUEST_KEYS, ZAP_REQUEST_TTL,
+    },
+    daemon_helper::{
+        listen_for_events, listen_zmq, DaemonHelper, DaemonState, LocalChainCheck, SyncProgress,
+        TxidAndWallet,
+    },
+    event_bus::{self, EventBus, GvEvent},
+    explorer::{ExplorerHealth, ExplorerProvider},
     file_ops,
     gv_client_methods::{
-        AllTimeEarnigns, BarChart, GVStatus, PendingRewards, StakeTotals, StakingData,
-        StakingDataOverview,
+        format_bytes, labeled_address, AddressBookEntry, AddressStakes, AllTimeEarnigns,
+        AnnounceRoute, AnnounceTemplate, AnonConversion, AnonymizationStatus, ApiCapabilities,
+        BalanceHistory, BarChart, BlockSummary, CLICaller, ChainEmergency, ChainTip, ChartPage,
+        ColdWalletCreated, ColdWalletInfo, ColdWallets, Consolidation, DaemonOnline, DaemonUpdate,
+        Delegation, Delegations, Digest, DigestSettings, EventPage, ExpectedDeposit, FleetVault,
+        GVStatus, HeatmapDay, HotWalletCreated, HotWalletInfo, HotWalletScript, MaintenanceWindow,
+        NetworkStats, OperationState, OperationStatus, ParquetExportSummary, PayoutEntry,
+        PayoutHistory, PendingRewards, PendingSplit, PendingUpdate, QuietHours, RecentLogs,
+        RewardOptions, RewardSimulation, RoiWindow, ScheduledTask, SplitUtxo, StakeForecast,
+        StakeHeatmap, StakeStreak, StakeTotals, StakesByAddress, StakingData, StakingDataOverview,
+        StakingRoi, SystemStats, Tenant, TenantStatement, TenantStatementEntry, TxLabel, TxSummary,
+        Utxo, VersionInfo, ZapRequest,
     },
     gv_methods::{self, PathAndDigest},
     gvdb::{
-        AddressInfo, DaemonStatusDB, NewStakeStatusDB, RewardsDB, ServerReadyDB, TgBotQueueDB,
-        ZapStatusDB, GVDB,
+        AddressBookDB, AddressInfo, AnnounceTemplateDB, AnonConversionDB, AuditDB, BalanceSampleDB,
+        BootstrapDB, ChainEmergencyDB, ColdWalletDB, DaemonStatusDB, DbMaintenanceDB, DigestDB,
+        DiskAlertDB, ExpectedDepositDB, FrozenUtxoDB, HeldNoticeDB, JobDB, MaintenanceDB,
+        MissedStakeDB, NetworkSampleDB, NewStakeStatusDB, PayoutDB, PendingRewardChangeDB,
+        PendingSplitDB, PendingUpdateDB, QuietHoursDB, RestartDB, RewardsDB, ServerReadyDB,
+        SharedVaultDB, StakeRollupDB, StakeStreakDB, SystemSampleDB, Task, TenantDB,
+        TenantLedgerDB, TgBotQueueDB, TxLabelDB, WalletRescanDB, ZapRequestDB, ZapStatusDB, GVDB,
     },
-    task_runner,
+    remote_backup,
+    response::{self, ErrorCode, GvError, GvReply, GvResult, OrCode},
+    stats, task_runner,
     task_runner::task_runner,
+    time_ranges::{self, Division},
     GvCLI,
 };
-use std::{env, net::IpAddr, path::PathBuf, sync::Arc, time::Duration};
+use std::{
+    any::Any,
+    collections::{BTreeMap, HashMap, VecDeque},
+    env,
+    net::IpAddr,
+    panic::AssertUnwindSafe,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use systemstat::{LoadAverage, Platform, System};
 use tarpc::{
     context,
     server::{incoming::Incoming, BaseChannel, Channel},
     tokio_serde::formats::Json,
 };
-use tokio::sync::{Mutex as async_Mutex, RwLock as async_RwLock};
+use tokio::{
+    net::TcpListener,
+    sync::{Mutex as async_Mutex, RwLock as async_RwLock},
+};
+use tokio_rustls::TlsAcceptor;
+use uuid::Uuid;
 
 pub struct CpuLoad {
     pub one: f32,
@@ -49,28 +116,225 @@ pub struct NewStake {
     pub staking_data: StakingData,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct VersionInfo {
-    pub gv_version: String,
-    pub ghostd_version: String,
-    pub latest_release: String,
+#[derive(Clone, Debug)]
+pub(crate) struct GvCLIServer {
+    daemon: DaemonHelper,
+    db: Arc<GVDB>,
+    gv_config: Arc<async_RwLock<GVConfig>>,
+    daemon_state: Arc<async_Mutex<DaemonState>>,
+    tg_bot_started: bool,
+    events: EventBus,
+    connection: Arc<Connection>,
+    operations: OperationRegistry,
+    jobs: JobRecorder,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-struct RewardOptions {
-    pub reward_mode: String,
-    pub reward_interval: String,
-    pub reward_address: String,
-    pub reward_min: f64,
+// The client on the other end of a connection, for the audit log. gv-cli doesn't call
+// set_origin so "cli" is assumed, the server's own instance is "internal".
+#[derive(Debug)]
+struct Connection {
+    peer: Option<String>,
+    origin: std::sync::Mutex<String>,
+}
+
+// A GvCLI call on its way into the audit log, see GvCLIServer::audit.
+struct Audit {
+    db: Arc<GVDB>,
+    method: &'static str,
+    origin: String,
+    peer: Option<String>,
+    params: Value,
 }
 
+// Single flight for the long operations in OPERATIONS, a second run of one is turned away while
+// the first is going. Shared by every connection's copy of the server. Every run is also recorded
+// as a job.
 #[derive(Clone, Debug)]
-struct GvCLIServer {
-    daemon: DaemonHelper,
+struct OperationRegistry {
+    operations: Arc<std::sync::Mutex<HashMap<&'static str, OperationStatus>>>,
+    jobs: JobRecorder,
+}
+
+// Held by the task doing the operation. Dropped without finish, like when the task panics, the
+// operation is marked failed so it can be run again.
+struct OperationGuard {
+    registry: OperationRegistry,
+    op_id: &'static str,
+    job: Option<JobGuard>,
+    finished: bool,
+}
+
+// Jobs go into GVDB, so whether a payout or resync ran is still known after a restart.
+#[derive(Clone, Debug)]
+struct JobRecorder {
     db: Arc<GVDB>,
-    gv_config: Arc<async_RwLock<GVConfig>>,
-    daemon_state: Arc<async_Mutex<DaemonState>>,
-    tg_bot_active: bool,
+}
+
+// Held by the task doing the job, dropped without finish the job is marked failed.
+struct JobGuard {
+    db: Arc<GVDB>,
+    job: JobDB,
+    finished: bool,
+}
+
+impl OperationRegistry {
+    fn new(jobs: JobRecorder) -> Self {
+        OperationRegistry {
+            operations: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            jobs,
+        }
+    }
+
+    async fn start(&self, op_id: &'static str) -> Result<OperationGuard, GvError> {
+        {
+            let mut operations = self.operations.lock().unwrap();
+
+            if operations
+                .get(op_id)
+                .is_some_and(|status| status.state == OperationState::Running)
+            {
+                return Err(GvError::new(
+                    ErrorCode::AlreadyRunning,
+                    format!(
+                        "{} is already running, follow it with operationstatus.",
+                        op_id
+                    ),
+                ));
+            }
+
+            operations.insert(
+                op_id,
+                OperationStatus {
+                    op_id: op_id.to_string(),
+                    state: OperationState::Running,
+                    started: Some(Utc::now().timestamp() as u64),
+                    finished: None,
+                    error: None,
+                    progress: None,
+                },
+            );
+        }
+
+        let kind: &str = match op_id {
+            "force_resync" => "resync",
+            "daemon_update" => "update",
+            "import_wallet" => "import",
+            _ => op_id,
+        };
+
+        Ok(OperationGuard {
+            registry: self.clone(),
+            op_id,
+            job: Some(self.jobs.start(kind).await),
+            finished: false,
+        })
+    }
+
+    fn status(&self, op_id: &str) -> OperationStatus {
+        self.operations
+            .lock()
+            .unwrap()
+            .get(op_id)
+            .cloned()
+            .unwrap_or(OperationStatus {
+                op_id: op_id.to_string(),
+                state: OperationState::Idle,
+                started: None,
+                finished: None,
+                error: None,
+                progress: None,
+            })
+    }
+
+    fn finish(&self, op_id: &str, error: Option<String>) {
+        if let Some(status) = self.operations.lock().unwrap().get_mut(op_id) {
+            status.state = match error {
+                Some(_) => OperationState::Failed,
+                None => OperationState::Idle,
+            };
+            status.finished = Some(Utc::now().timestamp() as u64);
+            status.error = error;
+        }
+    }
+}
+
+impl OperationGuard {
+    async fn finish(mut self, result: Result<(), String>) {
+        self.finished = true;
+        self.registry.finish(self.op_id, result.clone().err());
+
+        if let Some(job) = self.job.take() {
+            job.finish(result.map(|_| None)).await;
+        }
+    }
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.registry
+                .finish(self.op_id, Some("Stopped before it finished".to_string()));
+        }
+    }
+}
+
+impl JobRecorder {
+    async fn start(&self, kind: &str) -> JobGuard {
+        let job: JobDB = JobDB {
+            id: self.db.next_job_id(),
+            kind: kind.to_string(),
+            state: "running".to_string(),
+            started: Utc::now().timestamp() as u64,
+            finished: None,
+            progress: None,
+            outcome: None,
+            error: None,
+        };
+        self.db.set_job(&job).await.unwrap();
+
+        JobGuard {
+            db: Arc::clone(&self.db),
+            job,
+            finished: false,
+        }
+    }
+}
+
+impl JobGuard {
+    // Ok carries what the job did, if there's anything to say about it.
+    async fn finish(mut self, result: Result<Option<String>, String>) {
+        self.finished = true;
+        self.job.finished = Some(Utc::now().timestamp() as u64);
+
+        match result {
+            Ok(outcome) => {
+                self.job.state = "complete".to_string();
+                self.job.outcome = outcome;
+            }
+            Err(err) => {
+                self.job.state = "failed".to_string();
+                self.job.error = Some(err);
+            }
+        }
+
+        self.db.set_job(&self.job).await.unwrap();
+    }
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        if !self.finished {
+            let db: Arc<GVDB> = Arc::clone(&self.db);
+            let mut job: JobDB = self.job.clone();
+            job.state = "failed".to_string();
+            job.finished = Some(Utc::now().timestamp() as u64);
+            job.error = Some("Stopped before it finished".to_string());
+
+            tokio::spawn(async move {
+                let _ = db.set_job(&job).await;
+            });
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -90,7 +354,13 @@ impl GvCLIServer {
         }
 
         let cli_address: String = conf.cli_address.clone();
-        let tg_bot_active: bool = conf.bot_token.is_some() && conf.tg_user.is_some();
+        // The bot only starts with GhostVault, see tg_bot_active.
+        let tg_bot_started: bool = conf.bot_token.is_some() && conf.tg_user.is_some();
+        // Both of these stand in for the explorers, so the socket.io feed isn't needed
+        let remote_feed: bool = !conf.local_chain_check && conf.chain_peers.is_empty();
+        let events_address: Option<String> = conf.events_address.clone();
+        let hooks: HookSettings = conf.hooks.clone();
+        let update_channel: UpdateChannel = conf.update_channel.clone();
 
         drop(conf);
 
@@ -100,28 +370,28 @@ impl GvCLIServer {
 
         let daemon: DaemonHelper = DaemonHelper::new(&gv_config, "cold").await;
 
-        let blockchain_info: Value = daemon.call_status(true).await.unwrap();
         let online: bool = true;
 
-        let synced: bool = !daemon.is_syncing().await.unwrap();
-
-        let best_block: u32 = blockchain_info["blocks"].as_u64().unwrap() as u32;
-        let best_block_hash: String = blockchain_info["bestblockhash"]
-            .as_str()
-            .unwrap()
-            .to_string();
+        let (synced, best_block, best_block_hash, version) = loop {
+            match startup_chain_state(&daemon).await {
+                Ok(chain_state) => break chain_state,
+                Err(e) => {
+                    error!("Error reading the ghostd chain state: {}", e);
+                    error!("Retrying in 30 seconds...");
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                    continue;
+                }
+            }
+        };
 
-        let (remote_bc_info, remote_block_hash, latest_release) = loop {
+        let (remote_chain, latest_release) = loop {
             let res = tokio::try_join!(
-                gv_methods::get_remote_block_chain_info(),
-                gv_methods::get_remote_block_hash(best_block),
-                gv_methods::get_latest_release()
+                check_remote_chain(gv_config, &daemon, best_block, &best_block_hash),
+                gv_methods::get_latest_release(&update_channel)
             );
 
             match res {
-                Ok((bc_info, block_hash, latest_release)) => {
-                    break (bc_info, block_hash, latest_release)
-                }
+                Ok((remote_chain, latest_release)) => break (remote_chain, latest_release),
                 Err(e) => {
                     error!("Error fetching remote blockchain info: {}", e);
                     error!("Retrying in 30 seconds...");
@@ -131,15 +401,9 @@ impl GvCLIServer {
             }
         };
 
-        let remote_best_block: u32 = remote_bc_info["blocks"].as_u64().unwrap() as u32;
-        let remote_best_block_hash: String = remote_bc_info["bestblockhash"]
-            .as_str()
-            .unwrap()
-            .to_string();
-
-        let good_chain: bool = remote_block_hash == best_block_hash;
-
-        let version: String = daemon.get_daemon_version().await.unwrap();
+        let remote_best_block: u32 = remote_chain.best_block;
+        let remote_best_block_hash: String = remote_chain.best_block_hash;
+        let good_chain: bool = remote_chain.good_chain;
 
         let daemon_state: Arc<async_Mutex<DaemonState>> = Arc::new(async_Mutex::new(DaemonState {
             online,
@@ -153,19 +417,56 @@ impl GvCLIServer {
             remote_best_block,
             remote_best_block_hash,
             cycle: 0,
+            sync_progress: None,
+            payout_deferred: None,
         }));
 
         let cloned_db: Arc<GVDB> = Arc::clone(&db);
         let zmq_db = Arc::clone(&db);
         let sio_db = Arc::clone(&db);
 
+        // cleanup_missing_tx moves the daemon status to the tip, the blocks in between are
+        // replayed once the server is up.
+        let replay_from: Option<u32> = db
+            .get_daemon_status()
+            .map(|status| status.height)
+            .filter(|height| *height < best_block);
+
         daemon.cleanup_missing_tx(&db).await;
 
-        let zmq_listen_addr: Vec<String> = get_zmq_listen_addr(gv_config_clone_zmq).await;
+        // A rescan cut short by a restart goes back in the queue for the next window.
+        if let Some(mut rescan) = db.get_wallet_rescan() {
+            if rescan.status == "running" {
+                warn!("Wallet rescan was interrupted, rescheduling...");
+                rescan.status = "scheduled".to_string();
+                rescan.started = None;
+                rescan.progress = 0.0;
+                db.set_wallet_rescan(&rescan).await.unwrap();
+            }
+        }
+
+        // Whatever was running when ghostvaultd stopped is left for get_jobs to report.
+        for job in db.interrupt_running_jobs().await.unwrap() {
+            warn!(
+                "The {} job started at {} was interrupted by a restart.",
+                job.kind, job.started
+            );
+        }
+
+        // A bootstrap isn't resumed, the download is picked up again if it's rerun.
+        if let Some(mut bootstrap) = db.get_bootstrap() {
+            if bootstrap.status == "downloading" || bootstrap.status == "installing" {
+                warn!("Blockchain bootstrap was interrupted by a restart.");
+                bootstrap.status = "failed".to_string();
+                bootstrap.finished = Some(chrono::Utc::now().timestamp() as u64);
+                bootstrap.error = Some("Interrupted by a restart".to_string());
+                db.set_bootstrap(&bootstrap).await.unwrap();
+            }
+        }
 
         // Start the ZMQ listener on another thread.
         tokio::spawn(async move {
-            let _ = listen_zmq(&zmq_listen_addr, &cli_address, zmq_db).await;
+            let _ = listen_zmq(gv_config_clone_zmq, &cli_address, zmq_db).await;
         });
 
         // Start the task runner thread.
@@ -173,16 +474,87 @@ impl GvCLIServer {
             let _ = task_runner(&cloned_db, &gv_config_clone_task).await;
         });
 
-        tokio::spawn(async move {
-            let _ = listen_for_events(gv_config_clone_sio, sio_db).await;
-        });
+        if remote_feed {
+            tokio::spawn(async move {
+                let _ = listen_for_events(gv_config_clone_sio, sio_db).await;
+            });
+        } else {
+            info!("Chain checked without the explorers, not listening for remote blocks.");
+        }
+
+        let events: EventBus = EventBus::new(db, hooks);
 
-        GvCLIServer {
+        // The outbound side of the socket.io feed, GUIs get pushed events instead of polling
+        if let Some(events_address) = events_address {
+            let events_clone: EventBus = events.clone();
+
+            tokio::spawn(async move {
+                if let Err(e) = event_bus::serve_events(events_clone, events_address).await {
+                    error!("Event stream stopped: {}", e);
+                }
+            });
+        }
+
+        let jobs: JobRecorder = JobRecorder {
+            db: Arc::clone(db),
+        };
+
+        let server: GvCLIServer = GvCLIServer {
             daemon,
             db: db.to_owned(),
             gv_config: Arc::clone(&gv_config),
             daemon_state: Arc::clone(&daemon_state),
-            tg_bot_active,
+            tg_bot_started,
+            events,
+            connection: Arc::new(Connection {
+                peer: None,
+                origin: std::sync::Mutex::new("internal".to_string()),
+            }),
+            operations: OperationRegistry::new(jobs.clone()),
+            jobs,
+        };
+
+        let quiet_server: GvCLIServer = server.clone();
+
+        tokio::spawn(async move {
+            quiet_server.send_held_notices().await;
+        });
+
+        if let Some(last_height) = replay_from {
+            let replay_server: GvCLIServer = server.clone();
+
+            tokio::spawn(async move {
+                replay_server
+                    .replay_missed_blocks(last_height, best_block)
+                    .await;
+            });
+        }
+
+        server
+    }
+
+    // A copy of the server for one client connection, with its own origin.
+    fn for_connection(&self, peer: Option<String>) -> Self {
+        self.for_origin(peer, "cli")
+    }
+
+    pub(crate) fn for_origin(&self, peer: Option<String>, origin: &str) -> Self {
+        let mut server: GvCLIServer = self.clone();
+        server.connection = Arc::new(Connection {
+            peer,
+            origin: std::sync::Mutex::new(origin.to_string()),
+        });
+        server
+    }
+
+    // Secrets must be left out of params, they're stored as given.
+    fn audit(&self, method: &'static str, params: Value) -> Audit {
+        Audit {
+            db: Arc::clone(&self.db),
+            method,
+            origin: self.connection.origin.lock().unwrap().clone(),
+            peer: self.connection.peer.clone(),
+            params,
         }
     }
 
@@ -208,6 +580,16 @@ impl GvCLIServer {
         guard.cycle = new_cycle;
     }
 
+    async fn payout_deferred(&self) -> Option<String> {
+        self.daemon_state.lock().await.payout_deferred.clone()
+    }
+
+    // Returns the reason already held, so a run of deferrals only notifies once.
+    async fn set_payout_deferred(&self, reason: Option<String>) -> Option<String> {
+        let mut guard = self.daemon_state.lock().await;
+        std::mem::replace(&mut guard.payout_deferred, reason)
+    }
+
     async fn remote_best_block(&self) -> u32 {
         self.daemon_state.lock().await.remote_best_block
     }
@@ -266,6 +648,15 @@ impl GvCLIServer {
         guard.synced = new_state;
     }
 
+    async fn sync_progress(&self) -> Option<SyncProgress> {
+        self.daemon_state.lock().await.sync_progress.clone()
+    }
+
+    async fn set_sync_progress(&self, new_progress: Option<SyncProgress>) {
+        let mut guard = self.daemon_state.lock().await;
+        guard.sync_progress = new_progress;
+    }
+
     async fn good_chain(&self) -> bool {
         self.daemon_state.lock().await.good_chain
     }
@@ -301,10 +692,89 @@ impl GvCLIServer {
             && daemon_state.available
     }
 
+    async fn in_chain_emergency(&self) -> bool {
+        self.db
+            .get_chain_emergency()
+            .is_some_and(|emergency| emergency.active)
+    }
+
+    // Once the chain has been bad for longer than CHAIN_SPLIT_TIMEOUT payouts and anon
+    // conversion stop, and the alert repeats until the chain recovers or it's snoozed.
+    async fn check_chain_emergency(
+        &self,
+        bad_since: u64,
+        best_block: u32,
+        best_block_hash: &str,
+        remote_hash: &str,
+    ) {
+        let conf = self.gv_config.read().await;
+        let chain_split_timeout: u64 = conf.chain_split_timeout;
+        drop(conf);
+
+        let timestamp: u64 = chrono::Utc::now().timestamp() as u64;
+
+        if chain_split_timeout == 0 || timestamp.saturating_sub(bad_since) < chain_split_timeout {
+            return;
+        }
+
+        let mut emergency: ChainEmergencyDB =
+            self.db.get_chain_emergency().unwrap_or(ChainEmergencyDB {
+                since: bad_since,
+                active: false,
+                last_alert: 0,
+                snoozed_until: None,
+            });
+
+        if !emergency.active {
+            warn!("Chain split emergency, suspending payouts and anon conversion.");
+            emergency.active = true;
+        }
+
+        let snoozed: bool = emergency
+            .snoozed_until
+            .is_some_and(|until| timestamp < until);
+
+        if !snoozed
+            && timestamp.saturating_sub(emergency.last_alert) >= CHAIN_EMERGENCY_ALERT_INTERVAL
+        {
+            let bad_for: FormattedDuration = format_duration(Duration::from_secs(
+                timestamp.saturating_sub(emergency.since),
+            ));
+
+            let header = "🚨 Chain Split Emergency! 🚨";
+            let msg = format!("GhostVault has been on a different chain than remote for {}.\nPayouts and anon conversion are suspended until the chain recovers.\nGhostVault best block: {}\nGhostVault best block hash: {}\nRemote hash: {}\n\nResync the daemon, ban the current peers or wait it out.", bad_for, best_block, best_block_hash, remote_hash);
+
+            self.queue_bot_message("emergency", header, msg).await;
+            emergency.last_alert = timestamp;
+        }
+
+        self.db.set_chain_emergency(&emergency).await.unwrap();
+    }
+
+    async fn clear_chain_emergency(&self) {
+        let emergency: Option<ChainEmergencyDB> = self.db.get_chain_emergency();
+        self.db.remove_chain_emergency().await.unwrap();
+
+        if emergency.is_some_and(|emergency| emergency.active) {
+            info!("Chain recovered, resuming payouts and anon conversion.");
+
+            let header = "👻 Chain Recovered! 👻";
+            let msg =
+                "The local blockchain matches remote again.\nPayouts and anon conversion resumed."
+                    .to_string();
+
+            self.queue_bot_message("online", header, msg).await;
+        }
+    }
+
     async fn check_chain_task(&self) {
         info!("Starting the chain check monitor...");
         let check_seconds: u64 = 60 * 5;
         let mut bad_chain_count = 0;
+        let mut bad_since: Option<u64> = self
+            .db
+            .get_chain_emergency()
+            .map(|emergency| emergency.since);
 
         loop {
             let sleep_time = if self.daemon_online().await {
@@ -312,42 +782,53 @@ impl GvCLIServer {
                 let best_block: u32 = blockchain_info.best_block;
                 let best_block_hash: String = blockchain_info.best_block_hash;
 
-                let remote_block_hash: Value = loop {
-                    let remote_hash = gv_methods::get_remote_block_hash(best_block).await;
-
-                    if remote_hash.is_err() {
-                        error!(
-                            "Error fetching remote block hash: {}",
-                            remote_hash.err().unwrap()
-                        );
-                        error!("Retrying in 30 seconds...");
-                        tokio::time::sleep(Duration::from_secs(30)).await;
-                        continue;
+                let remote_chain: RemoteChain = loop {
+                    match check_remote_chain(
+                        &self.gv_config,
+                        &self.daemon,
+                        best_block,
+                        &best_block_hash,
+                    )
+                    .await
+                    {
+                        Ok(remote_chain) => break remote_chain,
+                        Err(err) => {
+                            error!("Error checking the remote chain: {}", err);
+                            error!("Retrying in 30 seconds...");
+                            tokio::time::sleep(Duration::from_secs(30)).await;
+                        }
                     }
-                    break remote_hash.unwrap();
                 };
 
-                let remote_hash: String = remote_block_hash
-                    .get("blockHash")
-                    .unwrap()
-                    .as_str()
-                    .unwrap()
-                    .to_string();
+                // Without the socket.io feed this is the only place the remote tip gets updated
+                self.set_remote_best_block(remote_chain.best_block).await;
+                self.set_remote_best_block_hash(&remote_chain.best_block_hash)
+                    .await;
 
-                let good_chain: bool = remote_hash == best_block_hash;
+                let remote_hash: String = remote_chain.remote_hash;
+                let good_chain: bool = remote_chain.good_chain;
 
                 self.set_good_chain(good_chain).await;
 
                 let sleep_time: u64 = if !good_chain {
                     bad_chain_count += 1;
+                    bad_since.get_or_insert(chrono::Utc::now().timestamp() as u64);
                     60 * 2
                 } else {
                     bad_chain_count = 0;
+                    if bad_since.take().is_some() {
+                        self.clear_chain_emergency().await;
+                    }
                     check_seconds
                 };
 
+                if let Some(since) = bad_since {
+                    self.check_chain_emergency(since, best_block, &best_block_hash, &remote_hash)
+                        .await;
+                }
+
                 if bad_chain_count >= 5 {
-                    if self.tg_bot_active {
+                    if self.announce_active().await {
                         let current_time = chrono::Utc::now();
                         let timestamp: u64 = current_time.timestamp() as u64;
 
@@ -361,15 +842,15 @@ impl GvCLIServer {
                             msg,
                             code_block: None,
                             url: None,
-                            msg_type: "online".to_string(),
+                            msg_type: "bad_chain".to_string(),
                             reward_txid: None,
                             msg_to_delete: None,
+                            action_token: None,
+                            msg_to_edit: None,
                         };
 
-                        self.db
-                            .set_tg_bot_queue(timestamp.to_string().as_bytes(), &tg_queue)
-                            .await
-                            .unwrap();
+                        self.announce(timestamp.to_string().as_bytes(), &tg_queue)
+                            .await;
                     }
                     bad_chain_count = 0;
                 }
@@ -387,20 +868,53 @@ impl GvCLIServer {
         info!("Starting the daemon sync monitor...");
 
         let mut last_state: Option<bool> = None;
+        let mut sync_samples: VecDeque<(u64, u64)> = VecDeque::new();
+        let mut sync_reported: Option<u64> = None;
 
         loop {
             let sleep_time = if self.daemon_online().await {
-                let synced_res = self.daemon.is_syncing().await.map_err(|e| e.to_string());
+                let bc_info: Value = match self.daemon.getblockchaininfo().await {
+                    Ok(bc_info) => bc_info,
+                    Err(_) => {
+                        self.handle_daemon_offline().await;
+                        continue;
+                    }
+                };
 
-                let synced = if synced_res.is_err() {
-                    self.handle_daemon_offline().await;
-                    continue;
-                } else {
-                    !synced_res.unwrap()
+                let synced: bool = match DaemonHelper::syncing_from_info(&bc_info) {
+                    Ok(syncing) => !syncing,
+                    Err(_) => {
+                        self.handle_daemon_offline().await;
+                        continue;
+                    }
                 };
 
                 self.set_daemon_synced(synced).await;
 
+                if synced {
+                    sync_samples.clear();
+                    self.set_sync_progress(None).await;
+
+                    if sync_reported.take().is_some() {
+                        self.queue_bot_message(
+                            "synced",
+                            "👻 Sync Complete! 👻",
+                            format!("ghostd is synced at block {}.", bc_info["blocks"]),
+                        )
+                        .await;
+                    }
+                } else {
+                    sync_reported = self
+                        .update_sync_progress(&bc_info, &mut sync_samples, sync_reported)
+                        .await;
+                }
+
+                if last_state != Some(synced) {
+                    self.events
+                        .publish("sync", serde_json::json!({ "synced": synced }))
+                        .await;
+                }
+
                 let sleep_time: u64 = if !synced {
                     3
                 } else {
@@ -419,6 +933,66 @@ impl GvCLIServer {
         }
     }
 
+    // Keeps the progress get_daemon_state shows and edits the Telegram sync message every
+    // SYNC_MESSAGE_INTERVAL. Syncs that are only a little behind, like after a restart, don't
+    // get one. Returns when the message was last sent.
+    async fn update_sync_progress(
+        &self,
+        bc_info: &Value,
+        samples: &mut VecDeque<(u64, u64)>,
+        reported: Option<u64>,
+    ) -> Option<u64> {
+        let now: u64 = chrono::Utc::now().timestamp() as u64;
+        let blocks: u64 = bc_info
+            .get("blocks")
+            .and_then(|blocks| blocks.as_u64())
+            .unwrap_or_default();
+
+        samples.push_back((now, blocks));
+
+        while samples
+            .front()
+            .is_some_and(|(time, _)| now - time > SYNC_RATE_WINDOW)
+        {
+            samples.pop_front();
+        }
+
+        let progress: SyncProgress = match SyncProgress::from_info(bc_info, samples) {
+            Ok(progress) => progress,
+            Err(_) => return reported,
+        };
+
+        self.set_sync_progress(Some(progress.clone())).await;
+
+        let due: bool = match reported {
+            Some(reported) => now - reported >= SYNC_MESSAGE_INTERVAL,
+            None => {
+                progress.eta_seconds.is_some()
+                    && progress.headers.saturating_sub(progress.blocks) >= SYNC_MESSAGE_MIN_BLOCKS
+            }
+        };
+
+        if !due {
+            return reported;
+        }
+
+        self.queue_bot_message(
+            "sync",
+            "👻 Syncing ghostd 👻",
+            format!(
+                "Block {} of {} ({:.2}% verified)\nRate: {:.0} blocks/min\nETA: {}",
+                progress.blocks,
+                progress.headers,
+                progress.verification_progress * 100.0,
+                progress.blocks_per_minute,
+                progress.eta
+            ),
+        )
+        .await;
+
+        Some(now)
+    }
+
     async fn monitor_daemon_online(&self) {
         let sleep_time: u64 = 1;
         info!("Starting the daemon online monitor...");
@@ -437,8 +1011,17 @@ impl GvCLIServer {
     }
 
     async fn handle_daemon_offline(&self) {
+        {
+            let mut guard = self.daemon_state.lock().await;
+
+            // Both monitors can notice at once, only one of them restarts ghostd
+            if !guard.online {
+                return;
+            }
+            guard.online = false;
+        }
+
         info!("Daemon offline, waiting for restart...");
-        self.set_daemon_online(false).await;
         let mut server_ready: ServerReadyDB = self.db.get_server_ready().unwrap();
         let is_docker: bool = env::vars().any(|(key, _)| key == "DOCKER_RUNNING");
 
@@ -446,12 +1029,14 @@ impl GvCLIServer {
         server_ready.reason = Some("Daemon offline".to_string());
 
         self.db.set_server_ready(&server_ready).await.unwrap();
+        self.events
+            .publish("offline", serde_json::json!({ "reason": "Daemon offline" }))
+            .await;
 
         if is_docker {
-            return;
-        }
-
-        if self.tg_bot_active {
+            // Nothing else brings ghostd back in the container
+            self.supervise_docker_daemon().await;
+        } else if self.announce_active().await {
             let current_time = chrono::Utc::now();
             let timestamp: u64 = current_time.timestamp() as u64;
 
@@ -467,22 +1052,30 @@ impl GvCLIServer {
                 msg_type: "offline".to_string(),
                 reward_txid: None,
                 msg_to_delete: None,
+                action_token: None,
+                msg_to_edit: None,
             };
 
-            self.db
-                .set_tg_bot_queue(timestamp.to_string().as_bytes(), &tg_queue)
-                .await
-                .unwrap();
+            self.announce(timestamp.to_string().as_bytes(), &tg_queue)
+                .await;
         }
 
-        self.daemon.wait_for_daemon_startup().await;
+        if !is_docker {
+            self.daemon.wait_for_daemon_startup().await;
+        }
 
         server_ready.daemon_ready = true;
         server_ready.reason = None;
         self.db.set_server_ready(&server_ready).await.unwrap();
         self.set_daemon_online(true).await;
+        self.events
+            .publish("online", serde_json::json!({ "online": true }))
+            .await;
+
+        // ghostd forgets the unlock on restart.
+        self.check_wallet_locked().await;
 
-        if self.tg_bot_active {
+        if self.announce_active().await {
             let current_time = chrono::Utc::now();
             let timestamp: u64 = current_time.timestamp() as u64;
 
@@ -498,102 +1091,195 @@ impl GvCLIServer {
                 msg_type: "online".to_string(),
                 reward_txid: None,
                 msg_to_delete: None,
+                action_token: None,
+                msg_to_edit: None,
             };
 
-            self.db
-                .set_tg_bot_queue(timestamp.to_string().as_bytes(), &tg_queue)
-                .await
-                .unwrap();
+            self.announce(timestamp.to_string().as_bytes(), &tg_queue)
+                .await;
         }
     }
 
-    async fn get_gv_status(&self) -> Result<GVStatus, Box<dyn std::error::Error>> {
-        let (
-            net_info,
-            bc_info,
-            daemon_is_syncing,
-            staking_info,
-            cold_staking_info,
-            last_stake_details,
-            daemon_up,
-        ) = tokio::try_join!(
-            self.daemon.getnetworkinfo(),
-            self.daemon.getblockchaininfo(),
-            self.daemon.is_syncing(),
-            self.daemon.getstakinginfo(),
-            self.daemon.getcoldstakinginfo(),
-            self.get_last_stake(),
-            self.daemon.getuptime()
-        )
-        .unwrap();
-        let sys: System = System::new();
-        let load_avg: CpuLoad = self.load(&sys);
+    // Restarts ghostd with a backoff that doubles after every failure. After DOCKER_MAX_RESTARTS
+    // failures in a row GhostVault exits, so the container's restart policy starts both over.
+    async fn supervise_docker_daemon(&self) {
+        let max_restarts: u64 = self.gv_config.read().await.docker_max_restarts;
+        let mut backoff: u64 = DOCKER_RESTART_BACKOFF;
+
+        for attempt in 1..=max_restarts {
+            tokio::time::sleep(Duration::from_secs(backoff)).await;
+            warn!(
+                "Restarting ghostd, attempt {} of {}...",
+                attempt, max_restarts
+            );
 
-        let uptime: FormattedDuration = format_duration(sys.uptime().unwrap());
+            let restart_res = self.restart_daemon().await;
 
-        let uptime_load = format!(
-            "{}, {1:.2} {2:.2} {3:.2}",
-            uptime, load_avg.one, load_avg.five, load_avg.fifteen
-        );
+            let restart: RestartDB = RestartDB {
+                timestamp: chrono::Utc::now().timestamp() as u64,
+                attempt,
+                success: restart_res.is_ok(),
+                error: restart_res.err().map(|err| err.to_string()),
+            };
+            self.db.set_restart(&restart).await.unwrap();
 
-        let conf = self.gv_config.read().await;
+            if restart.success {
+                info!("ghostd restarted.");
+                return;
+            }
 
-        let privacy_mode = if conf.anon_mode {
+            error!(
+                "ghostd restart failed: {}",
+                restart.error.unwrap_or_default()
+            );
+            backoff = (backoff * 2).min(DOCKER_RESTART_BACKOFF_MAX);
+        }
+
+        error!(
+            "ghostd failed to restart {} times, exiting so the container restarts...",
+            max_restarts
+        );
+
+        self.queue_bot_message(
+            "offline",
+            "👻 Daemon Restart Failed! 👻",
+            format!(
+                "ghostd failed to restart {} times, GhostVault is exiting so the container restarts.",
+                max_restarts
+            ),
+        )
+        .await;
+
+        // Gives the bot a chance to send the message first
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        std::process::exit(1);
+    }
+
+    async fn restart_daemon(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // A ghostd that hung but is still running holds the data dir lock
+        let _ = self.daemon.stop_daemon().await;
+        self.daemon.start_daemon().await?;
+
+        let deadline: tokio::time::Instant =
+            tokio::time::Instant::now() + Duration::from_secs(DOCKER_RESTART_TIMEOUT);
+
+        while tokio::time::Instant::now() < deadline {
+            if !self.daemon.call_status(false).await?.is_null() {
+                return Ok(());
+            }
+
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+
+        Err("ghostd started but never answered".into())
+    }
+
+    async fn get_gv_status(&self, tz: &Tz) -> Result<GVStatus, Box<dyn std::error::Error>> {
+        // The daemon calls go out as a single batch, alongside the last stake lookup.
+        let status_calls: Vec<String> = [
+            "getnetworkinfo",
+            "getblockchaininfo",
+            "getstakinginfo",
+            "getcoldstakinginfo",
+            "uptime",
+        ]
+        .iter()
+        .map(|call| call.to_string())
+        .collect();
+
+        let (status_results, last_stake_details) =
+            tokio::try_join!(self.daemon.batch(&status_calls), self.get_last_stake(tz))
+                .map_err(|err| err.to_string())?;
+
+        let mut status_results = status_results.into_iter();
+        let mut next_result = || -> Result<Value, Box<dyn std::error::Error>> {
+            Ok(status_results
+                .next()
+                .ok_or("Missing daemon status result")?
+                .map_err(|err| err.to_string())?)
+        };
+
+        let net_info: Value = next_result()?;
+        let bc_info: Value = next_result()?;
+        let staking_info: Value = next_result()?;
+        let cold_staking_info: Value = next_result()?;
+        let daemon_up: Value = next_result()?;
+
+        let daemon_is_syncing: bool =
+            DaemonHelper::syncing_from_info(&bc_info).map_err(|err| err.to_string())?;
+        let sys: System = System::new();
+        let load_avg: CpuLoad = self.load(&sys);
+
+        let uptime: FormattedDuration = format_duration(sys.uptime().unwrap());
+
+        let uptime_load = format!(
+            "{}, {1:.2} {2:.2} {3:.2}",
+            uptime, load_avg.one, load_avg.five, load_avg.fifteen
+        );
+
+        let conf = self.gv_config.read().await;
+
+        let privacy_mode = if conf.watch_only {
+            "WATCH-ONLY".to_string()
+        } else if conf.anon_mode {
             "ANON".to_string()
         } else {
             "STANDARD".to_string()
         };
+        let chain_check = if !conf.chain_peers.is_empty() {
+            "PEERS".to_string()
+        } else if conf.local_chain_check {
+            "LOCAL".to_string()
+        } else {
+            "EXPLORER".to_string()
+        };
 
         drop(conf);
 
-        let daemon_uptime_secs: u64 = daemon_up.as_u64().unwrap();
+        let daemon_uptime_secs: u64 = daemon_up
+            .as_u64()
+            .or_code(ErrorCode::DaemonError, "ghostd uptime isn't a number")?;
         let daemon_uptime: FormattedDuration =
             format_duration(Duration::from_secs(daemon_uptime_secs));
 
         let daemon_synced: String = bool_to_yn(!daemon_is_syncing);
-        let daemon_peers: u16 = net_info.get("connections").unwrap().as_u64().unwrap() as u16;
-        let best_block: u32 = bc_info.get("blocks").unwrap().as_u64().unwrap() as u32;
-        let best_block_hash = bc_info
-            .get("bestblockhash")
-            .unwrap()
-            .as_str()
-            .unwrap()
-            .to_string();
+        let daemon_peers: u16 = reply_field(&net_info, "connections", Value::as_u64)? as u16;
+        let best_block: u32 = reply_field(&bc_info, "blocks", Value::as_u64)? as u32;
+        let best_block_hash: String = reply_str(&bc_info, "bestblockhash")?;
 
         let best_block_extern = self.remote_best_block().await;
         let good_chain: String = bool_to_yn(self.good_chain().await);
 
         let staking_enabled: String =
-            bool_to_yn(staking_info.get("enabled").unwrap().as_bool().unwrap());
+            bool_to_yn(reply_field(&staking_info, "enabled", Value::as_bool)?);
         let active_staking: String =
-            bool_to_yn(staking_info.get("staking").unwrap().as_bool().unwrap());
-        let staking_difficulty: f64 = staking_info.get("difficulty").unwrap().as_f64().unwrap();
-        let network_stake_weight: f64 = self.daemon.convert_from_sat(
-            staking_info
-                .get("netstakeweight")
-                .unwrap()
-                .as_u64()
-                .unwrap(),
-        );
-
-        let currently_staking: f64 = cold_staking_info
-            .get("currently_staking")
-            .unwrap()
-            .as_f64()
-            .unwrap();
-
-        let total_coldstaking: f64 = cold_staking_info
-            .get("coin_in_coldstakeable_script")
-            .unwrap()
-            .as_f64()
-            .unwrap();
+            bool_to_yn(reply_field(&staking_info, "staking", Value::as_bool)?);
+        let staking_difficulty: f64 = reply_field(&staking_info, "difficulty", Value::as_f64)?;
+        let network_stake_weight: Amount =
+            Amount::from_sat(reply_field(&staking_info, "netstakeweight", Value::as_u64)?);
+
+        let currently_staking: Amount = Amount::from_ghost(reply_field(
+            &cold_staking_info,
+            "currently_staking",
+            Value::as_f64,
+        )?);
+
+        let total_coldstaking: Amount = Amount::from_ghost(reply_field(
+            &cold_staking_info,
+            "coin_in_coldstakeable_script",
+            Value::as_f64,
+        )?);
 
         let stakes: StakeTotals = self.get_stakes_days(1).await;
 
-        let stakes_24: u32 = stakes.stakes;
-        let earned_24: f64 = stakes.rewards;
-        let earned_agvr_24: f64 = stakes.agvr;
-        let total_24: f64 = stakes.total;
+        let expected_stake_time: String = match stats::expected_stake_secs(
+            currently_staking,
+            network_stake_weight,
+            staking_difficulty,
+        ) {
+            Some(secs) => format_duration(Duration::from_secs(secs)).to_string(),
+            None => "N/A".to_string(),
+        };
 
         let res: GVStatus = GVStatus {
             uptime: uptime_load,
@@ -603,29 +1289,43 @@ impl GvCLIServer {
             daemon_uptime: daemon_uptime.to_string(),
             daemon_peers,
             daemon_synced,
+            sync_progress: if daemon_is_syncing {
+                self.sync_progress().await
+            } else {
+                None
+            },
             best_block,
             best_block_hash,
             best_block_extern,
             good_chain,
+            chain_check,
             staking_enabled,
             active_staking,
             staking_difficulty,
-            network_stake_weight,
-            currently_staking,
-            total_coldstaking,
+            network_stake_weight: network_stake_weight.to_ghost(),
+            currently_staking: currently_staking.to_ghost(),
+            total_coldstaking: total_coldstaking.to_ghost(),
             last_stake: last_stake_details.last_stake_str,
-            stakes_24,
-            rewards_24: earned_24,
-            agvr_24: earned_agvr_24,
-            total_24,
+            expected_stake_time,
+            stakes_24: stakes.stakes,
+            rewards_24: stakes.rewards,
+            agvr_24: stakes.agvr,
+            total_24: stakes.total,
+            network_stake_weight_amount: network_stake_weight,
+            currently_staking_amount: currently_staking,
+            total_coldstaking_amount: total_coldstaking,
+            rewards_24_amount: stakes.rewards_amount,
+            agvr_24_amount: stakes.agvr_amount,
+            total_24_amount: stakes.total_amount,
         };
 
         Ok(res)
     }
 
-    async fn get_last_stake(&self) -> Result<LastStake, Box<dyn std::error::Error + Send + Sync>> {
-        let conf = self.gv_config.read().await;
-
+    async fn get_last_stake(
+        &self,
+        tz: &Tz,
+    ) -> Result<LastStake, Box<dyn std::error::Error + Send + Sync>> {
         let last_tx = self.db.rewards_ts_index.last().unwrap();
 
         let last_time = match last_tx {
@@ -649,10 +1349,7 @@ impl GvCLIServer {
                 )
                 .unwrap();
 
-                let time_zone: String = conf.timezone.clone();
-                let tz: Tz = Tz::from_str_insensitive(&time_zone).unwrap();
-
-                let tz_time = Tz::from_utc_datetime(&tz, &n_time);
+                let tz_time = Tz::from_utc_datetime(tz, &n_time);
 
                 let last_time_str = tz_time.format("%Y-%m-%d %H:%M:%S %Z").to_string();
 
@@ -670,11 +1367,6 @@ impl GvCLIServer {
     }
 
     async fn get_stakes_days(&self, days_or_start: u64) -> StakeTotals {
-        let mut stakes: u32 = 0;
-        let mut earned_int: u64 = 0;
-        let mut earned_agvr_int: u64 = 0;
-        let mut earned_total_int: u64 = 0;
-
         let current_time = chrono::Utc::now();
 
         let range_end: u64 = current_time.timestamp() as u64;
@@ -698,38 +1390,24 @@ impl GvCLIServer {
             days_or_start
         };
 
-        for result in self
-            .db
-            .rewards_ts_index
-            .range(range_start.to_be_bytes()..=range_end.to_be_bytes())
-        {
-            match result {
-                Ok((_, value)) => {
-                    let value: RewardsDB = serde_json::from_slice(&value).unwrap();
-                    stakes += 1;
-                    earned_int += value.reward;
-                    earned_agvr_int += value.agvr_reward;
-                    earned_total_int += value.reward + value.agvr_reward;
-                }
-                Err(err) => {
-                    eprintln!("Error during iteration: {:?}", err);
-                }
-            }
-        }
+        let totals: StakeRollupDB = self.db.stake_totals(range_start, range_end);
 
-        let earned: f64 = self.daemon.convert_from_sat(earned_int);
-        let earned_agvr: f64 = self.daemon.convert_from_sat(earned_agvr_int);
-        let total: f64 = self.daemon.convert_from_sat(earned_total_int);
+        let earned: Amount = Amount::from_sat(totals.reward);
+        let earned_agvr: Amount = Amount::from_sat(totals.agvr_reward);
+        let total: Amount = earned + earned_agvr;
 
         StakeTotals {
-            stakes,
-            rewards: earned,
-            agvr: earned_agvr,
-            total,
+            stakes: totals.stakes,
+            rewards: earned.to_ghost(),
+            agvr: earned_agvr.to_ghost(),
+            total: total.to_ghost(),
+            rewards_amount: earned,
+            agvr_amount: earned_agvr,
+            total_amount: total,
         }
     }
 
-    async fn get_earnings_chart_vec(&self, start: u64, end: u64) -> AllTimeEarnigns {
+    async fn get_earnings_chart_vec(&self, start: u64, end: u64, tz: &Tz) -> AllTimeEarnigns {
         let range_start = if start == 0 {
             let first_stake_opt = self.db.rewards_ts_index.first().unwrap();
             let first_stake = match first_stake_opt {
@@ -745,43 +1423,61 @@ impl GvCLIServer {
         };
         let range_end = end;
 
-        let mut heatmap: Vec<Vec<f64>> = Vec::new();
+        // Running total at the end of each day, starting from everything earned before the range
+        let mut earned: u64 = if range_start > 0 {
+            let before: StakeRollupDB = self.db.stake_totals(0, range_start - 1);
+            before.reward + before.agvr_reward
+        } else {
+            0
+        };
+
+        // Days are local to the chart, so they're summed from the rewards index rather than the
+        // UTC rollups, the same as the stake bars.
+        let mut daily: BTreeMap<u64, u64> = BTreeMap::new();
 
-        for (_, result) in self
+        for reward in self
             .db
             .rewards_ts_index
-            .range(range_start.to_be_bytes()..range_end.to_be_bytes())
-            .enumerate()
+            .range(range_start.to_be_bytes()..range_end.saturating_add(1).to_be_bytes())
+            .flatten()
+            .filter_map(|(_, value)| serde_json::from_slice::<RewardsDB>(&value).ok())
         {
-            match result {
-                Ok((_, value)) => {
-                    let value: RewardsDB = serde_json::from_slice(&value).unwrap();
+            *daily
+                .entry(Division::Day.period_start(tz, reward.timestamp))
+                .or_insert(0) += reward.reward + reward.agvr_reward;
+        }
 
-                    let total_rewards = value.all_time_reward;
-                    let total_agvr = value.all_time_agvr_reward;
-                    let total_earning = self.daemon.convert_from_sat(total_rewards + total_agvr);
+        let mut heatmap: Vec<Vec<f64>> = Vec::new();
 
-                    heatmap.push(vec![total_earning, value.timestamp as f64]);
-                }
-                Err(err) => {
-                    eprintln!("Error during iteration: {:?}", err);
-                }
-            }
+        for (day, reward) in daily {
+            earned += reward;
+
+            let total_earning = self.daemon.convert_from_sat(earned);
+            heatmap.push(vec![total_earning, day.max(range_start) as f64]);
         }
 
-        let start = self.get_date_str(range_start).await;
-        let end = self.get_date_str(range_end).await;
+        let start = date_str_in(tz, range_start);
+        let end = date_str_in(tz, range_end);
 
         let earings_data = AllTimeEarnigns {
+            total_points: heatmap.len() as u64,
             data: heatmap,
             start,
             end,
+            offset: 0,
+            next_offset: None,
         };
 
         earings_data
     }
 
-    async fn get_stake_barchart_vec(&self, start: u64, end: u64, division: &str) -> BarChart {
+    async fn get_stake_barchart_vec(
+        &self,
+        start: u64,
+        end: u64,
+        division: Division,
+        tz: &Tz,
+    ) -> BarChart {
         let range_start = if start == 0 {
             let first_stake_opt = self.db.rewards_ts_index.first().unwrap();
             let first_stake = match first_stake_opt {
@@ -797,1695 +1493,10286 @@ impl GvCLIServer {
         };
         let range_end = end;
 
-        let mut heatmap: Vec<Vec<u64>> = Vec::new();
-
-        let mut stake_count: u64 = 0;
-        let mut current_divisor: u32 = 0;
-        let mut first_iter = true;
-        let mut ts: u64 = 0;
-
-        let total_stakes = self
-            .db
-            .rewards_ts_index
-            .range(range_start.to_be_bytes()..range_end.to_be_bytes())
-            .count();
-
-        for (index, result) in self
-            .db
-            .rewards_ts_index
-            .range(range_start.to_be_bytes()..range_end.to_be_bytes())
-            .enumerate()
-        {
-            match result {
-                Ok((_, value)) => {
-                    let value: RewardsDB = serde_json::from_slice(&value).unwrap();
-
-                    let date_enum: (u32, u32, u32, u64) = self
-                        .get_enumerated_date(value.timestamp, division)
-                        .await
-                        .unwrap();
-
-                    let divisor = match division {
-                        "day" => date_enum.0,
-                        "week" => date_enum.1,
-                        "month" => date_enum.2,
-                        _ => 0,
-                    };
-
-                    if first_iter {
-                        first_iter = false;
-                        current_divisor = divisor;
-                        ts = date_enum.3;
-                        stake_count += 1;
-                        continue;
-                    }
-
-                    if divisor == current_divisor {
-                        stake_count += 1;
-
-                        if index == total_stakes - 1 {
-                            heatmap.push(vec![ts, stake_count]);
-                        }
-                    } else {
-                        heatmap.push(vec![ts, stake_count]);
-                        stake_count = 1;
-
-                        let conf = self.gv_config.read().await;
-                        let time_zone = conf.timezone.clone();
-
-                        let tz = Tz::from_str_insensitive(&time_zone).unwrap();
-
-                        let start_date = DateTime::from_timestamp(ts as i64, 0)
-                            .unwrap()
-                            .with_timezone(&tz);
-                        let end_date = DateTime::from_timestamp(date_enum.3 as i64, 0)
-                            .unwrap()
-                            .with_timezone(&tz);
-
-                        match division {
-                            "day" => {
-                                let days = end_date.signed_duration_since(start_date).num_days();
-                                if days > 1 {
-                                    for _ in 1..days {
-                                        let current_ts = start_date
-                                            .checked_add_days(Days::new(1))
-                                            .unwrap()
-                                            .timestamp()
-                                            as u64;
-                                        heatmap.push(vec![current_ts, 0]);
-                                    }
-                                }
-                            }
-                            "week" => {
-                                let weeks =
-                                    end_date.signed_duration_since(start_date).num_weeks() as u64;
-                                if weeks > 1 {
-                                    for _ in 1..weeks {
-                                        let current_ts = start_date
-                                            .checked_add_days(Days::new(7))
-                                            .unwrap()
-                                            .timestamp()
-                                            as u64;
-                                        heatmap.push(vec![current_ts, 0]);
-                                    }
-                                }
-                            }
-                            "month" => {
-                                let months =
-                                    end_date.signed_duration_since(start_date).num_days() / 30;
-
-                                if months > 1 {
-                                    for _ in 1..months {
-                                        let current_ts = start_date
-                                            .checked_add_months(Months::new(1))
-                                            .unwrap()
-                                            .timestamp()
-                                            as u64;
-                                        heatmap.push(vec![current_ts, 0]);
-                                    }
-                                }
-                            }
-                            _ => {}
-                        }
+        // Bars are local periods, so they're counted from the rewards index rather than the
+        // UTC rollups. Periods between the first and last stake without any are zero.
+        let counts: BTreeMap<u64, u64> = time_ranges::bucket_counts(
+            division,
+            tz,
+            self.db
+                .rewards_ts_index
+                .range(range_start.to_be_bytes()..range_end.saturating_add(1).to_be_bytes())
+                .flatten()
+                .filter_map(|(key, _)| key.as_ref().try_into().ok().map(u64::from_be_bytes)),
+        );
 
-                        current_divisor = divisor;
-                        ts = date_enum.3;
-                    }
-                }
-                Err(err) => {
-                    eprintln!("Error during iteration: {:?}", err);
-                }
-            }
-        }
+        let heatmap: Vec<Vec<u64>> = match (counts.keys().next(), counts.keys().next_back()) {
+            (Some(first), Some(last)) => time_ranges::buckets(division, tz, *first, *last)
+                .into_iter()
+                .map(|(period_start, _)| {
+                    vec![
+                        period_start,
+                        counts.get(&period_start).copied().unwrap_or(0),
+                    ]
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
 
-        let start = self.get_date_str(range_start).await;
-        let end = self.get_date_str(range_end).await;
+        let start = date_str_in(tz, range_start);
+        let end = date_str_in(tz, range_end);
 
         let barchart_data = BarChart {
+            total_points: heatmap.len() as u64,
             data: heatmap,
-            division: division.to_string(),
+            division: division.as_str().to_string(),
             start,
             end,
+            offset: 0,
+            next_offset: None,
         };
 
         barchart_data
     }
 
-    fn load(&self, sys: &System) -> CpuLoad {
-        let system_load: LoadAverage = sys.load_average().unwrap();
-        let one: f32 = system_load.one;
-        let five: f32 = system_load.five;
-        let fifteen: f32 = system_load.fifteen;
-
-        CpuLoad { one, five, fifteen }
-    }
-
-    async fn get_date_str(&self, timestamp: u64) -> String {
+    // A helper for the primary cold wallet or one of the extra ones, None for any other wallet.
+    async fn cold_wallet_daemon(&self, wallet: &str) -> Option<DaemonHelper> {
         let conf = self.gv_config.read().await;
-        let time_zone = conf.timezone.clone();
-        let tz: Tz = Tz::from_str_insensitive(&time_zone).unwrap();
-
-        let naive_datetime = DateTime::from_timestamp(timestamp as i64, 0);
-        let datetime = naive_datetime.unwrap();
+        let is_primary: bool = conf.rpc_wallet == wallet;
+        drop(conf);
 
-        let date_str = datetime.with_timezone(&tz).format("%d/%m/%y").to_string();
-        date_str
+        if is_primary {
+            Some(self.daemon.clone())
+        } else if self.db.get_cold_wallet(wallet).is_some() {
+            Some(DaemonHelper::for_wallet(&self.gv_config, wallet).await)
+        } else {
+            None
+        }
     }
 
-    async fn get_enumerated_date(
-        &self,
-        timestamp: u64,
-        division: &str,
-    ) -> Option<(u32, u32, u32, u64)> {
-        // Convert the Unix timestamp to a NaiveDateTime
-        let naive_datetime = DateTime::from_timestamp(timestamp as i64, 0);
+    // A zap matches an open request when it pays to the request's stake address, the request is
+    // closed with the txid.
+    async fn match_zap_request(&self, tx_io: &[Value], txid: &str) -> Option<ZapRequestDB> {
+        let addresses: Vec<&str> = tx_io
+            .iter()
+            .flat_map(|tx| ["address", "coldstake_address", "stakeaddress"].map(|key| tx.get(key)))
+            .filter_map(|addr| addr.and_then(|addr| addr.as_str()))
+            .collect();
+
+        let mut request: ZapRequestDB = self.db.get_zap_requests().into_iter().find(|request| {
+            request.txid.is_none() && addresses.contains(&request.stake_addr.as_str())
+        })?;
+
+        info!(
+            "Zap {} fills the request for {} GHOST",
+            txid,
+            Amount::from_sat(request.amount)
+        );
+        request.txid = Some(txid.to_string());
+        let _ = self.db.set_zap_request(&request).await;
 
-        let conf = self.gv_config.read().await;
-        let time_zone = conf.timezone.clone();
-        let tz: Tz = Tz::from_str_insensitive(&time_zone).unwrap();
+        Some(request)
+    }
 
-        let datetime = naive_datetime.unwrap();
+    // A zap of exactly an expected amount closes the oldest open expectation for it. Late
+    // deposits still match, the owner was only told they were missed.
+    async fn match_expected_deposit(&self, amount: u64, txid: &str) -> Option<ExpectedDepositDB> {
+        let mut deposit: ExpectedDepositDB = self
+            .db
+            .get_expected_deposits()
+            .into_iter()
+            .find(|deposit| deposit.txid.is_none() && deposit.amount == amount)?;
+
+        deposit.txid = Some(txid.to_string());
+        let _ = self.db.set_expected_deposit(&deposit).await;
+
+        if !deposit.memo.is_empty() && self.db.get_tx_label(txid).is_none() {
+            let label: TxLabelDB = TxLabelDB {
+                txid: txid.to_string(),
+                label: deposit.memo.clone(),
+                updated: Utc::now().timestamp() as u64,
+            };
+            let _ = self.db.set_tx_label(&label).await;
+        }
 
-        let day_of_week = datetime.with_timezone(&tz).ordinal0();
-        let week_num = datetime.with_timezone(&tz).iso_week().week0();
-        let month_num = datetime.with_timezone(&tz).month0();
+        Some(deposit)
+    }
 
-        let timestamp = match division {
-            "day" => datetime
-                .with_time(NaiveTime::MIN)
-                .unwrap()
-                .with_timezone(&tz)
-                .timestamp() as u64,
-            "week" => {
-                let days_from_sun = datetime.weekday().num_days_from_sunday() as u64;
-                let start_of_week = if days_from_sun == 0 {
-                    datetime
-                        .with_time(NaiveTime::MIN)
-                        .unwrap()
-                        .with_timezone(&tz)
-                        .timestamp() as u64
-                } else {
-                    datetime
-                        .checked_sub_days(Days::new(days_from_sun))
-                        .unwrap()
-                        .with_time(NaiveTime::MIN)
-                        .unwrap()
-                        .with_timezone(&tz)
-                        .timestamp() as u64
-                };
+    // Tells the owner about expected deposits that are past due, once each, and drops the ones
+    // closed longer than EXPECTED_DEPOSIT_KEEP ago.
+    async fn check_expected_deposits(&self) {
+        let now: u64 = Utc::now().timestamp() as u64;
+        let mut missed: Vec<String> = Vec::new();
 
-                start_of_week
+        for mut deposit in self.db.get_expected_deposits() {
+            if now > deposit.due + EXPECTED_DEPOSIT_KEEP {
+                let _ = self.db.remove_expected_deposit(deposit.id).await;
+                continue;
             }
-            "month" => {
-                let start_of_month = datetime
-                    .with_day(1)
-                    .unwrap()
-                    .with_time(NaiveTime::MIN)
-                    .unwrap()
-                    .with_timezone(&tz)
-                    .timestamp() as u64;
-                start_of_month
+
+            if deposit.txid.is_some() || deposit.missed || now <= deposit.due {
+                continue;
             }
-            _ => 0,
-        };
 
-        Some((day_of_week, week_num, month_num, timestamp))
-    }
+            missed.push(if deposit.memo.is_empty() {
+                format!("{} GHOST", Amount::from_sat(deposit.amount))
+            } else {
+                format!(
+                    "{} GHOST ({})",
+                    Amount::from_sat(deposit.amount),
+                    deposit.memo
+                )
+            });
 
-    async fn do_force_resync(&self) {
-        info!("Forcing a resync of the daemon...");
-        self.set_daemon_online(false).await;
-        self.set_daemon_synced(false).await;
+            deposit.missed = true;
+            let _ = self.db.set_expected_deposit(&deposit).await;
+        }
 
-        let mut server_state: ServerReadyDB = self.db.get_server_ready().unwrap();
-        server_state.daemon_ready = false;
-        server_state.reason = Some("Forcing resync".to_string());
-        self.db.set_server_ready(&server_state).await.unwrap();
+        if missed.is_empty() {
+            return;
+        }
+
+        warn!("Expected deposits missed: {}", missed.join(", "));
+        self.queue_bot_message(
+            "deposit_missed",
+            "⚠️ Expected Deposit Missing ⚠️",
+            format!(
+                "These deposits didn't arrive in time:\n{}",
+                missed.join("\n")
+            ),
+        )
+        .await;
+    }
 
-        self.daemon.stop_daemon().await.unwrap();
+    // Wallet names end up in ghostd calls, so they are kept to characters that need no quoting.
+    async fn check_new_cold_wallet(&self, name: &str) -> Result<(), GvError> {
+        if name.is_empty()
+            || name.len() > 64
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(GvError::new(
+                ErrorCode::InvalidArgument,
+                "Wallet names are up to 64 letters, digits, dashes and underscores.",
+            ));
+        }
 
         let conf = self.gv_config.read().await;
-        let daemon_data_dir: PathBuf = conf.daemon_data_dir.clone();
+        let taken: bool = name == conf.rpc_wallet
+            || conf.rpc_wallet_hot == name
+            || self.db.get_cold_wallet(name).is_some();
         drop(conf);
 
-        let blocks_dir: PathBuf = daemon_data_dir.join("blocks/");
-        let chainstate_dir: PathBuf = daemon_data_dir.join("chainstate/");
-        let peers_file: PathBuf = daemon_data_dir.join("peers.dat");
-        let banlist_file: PathBuf = daemon_data_dir.join("banlist.dat");
+        if taken {
+            return Err(GvError::new(
+                ErrorCode::InvalidArgument,
+                format!("Wallet {} is already in use!", name),
+            ));
+        }
 
-        // Remove the blocks and chainstate directories.
-        // This will force a resync of the daemon.
+        Ok(())
+    }
 
-        // Also remove the peers.dat and banlist.dat files.
-        // Get these fresh incase of bad peers.
+    async fn get_stakes_by_address_vec(&self, start: u64, end: u64) -> StakesByAddress {
+        let mut by_address: BTreeMap<String, AddressStakes> = BTreeMap::new();
 
-        file_ops::rm_dir(&blocks_dir).unwrap();
-        file_ops::rm_dir(&chainstate_dir).unwrap();
-        file_ops::rm_file(&peers_file).unwrap();
-        file_ops::rm_file(&banlist_file).unwrap();
+        for (_, value) in self
+            .db
+            .rewards_ts_index
+            .range(start.to_be_bytes()..end.saturating_add(1).to_be_bytes())
+            .flatten()
+        {
+            let stake: RewardsDB = match serde_json::from_slice(&value) {
+                Ok(stake) => stake,
+                Err(_) => continue,
+            };
 
-        self.daemon.wait_for_daemon_startup().await;
-        self.set_daemon_online(true).await;
+            let entry: &mut AddressStakes =
+                by_address
+                    .entry(stake.address.clone())
+                    .or_insert_with(|| AddressStakes {
+                        address: stake.address.clone(),
+                        stakes: 0,
+                        rewards: Amount::ZERO,
+                        agvr: Amount::ZERO,
+                        total: Amount::ZERO,
+                        share: 0.0,
+                        first_stake: stake.timestamp,
+                        last_stake: stake.timestamp,
+                    });
 
-        server_state.daemon_ready = true;
-        server_state.reason = None;
-        self.db.set_server_ready(&server_state).await.unwrap();
+            entry.stakes += 1;
+            entry.rewards += Amount::from_sat(stake.reward);
+            entry.agvr += Amount::from_sat(stake.agvr_reward);
+            entry.total = entry.rewards + entry.agvr;
+            entry.last_stake = stake.timestamp;
+        }
+
+        let total: Amount = by_address.values().map(|entry| entry.total).sum();
+        let mut addresses: Vec<AddressStakes> = by_address.into_values().collect();
+
+        for entry in addresses.iter_mut() {
+            if total > Amount::ZERO {
+                entry.share = entry.total.to_sat() as f64 / total.to_sat() as f64 * 100.0;
+            }
+        }
+        addresses.sort_by_key(|entry| std::cmp::Reverse(entry.total));
+
+        StakesByAddress {
+            start: self.get_date_str(start).await,
+            end: self.get_date_str(end).await,
+            total_stakes: addresses.iter().map(|entry| entry.stakes).sum(),
+            total,
+            addresses,
+        }
     }
 
-    async fn do_update(&self, latest_release: &str) {
-        info!("New daemon verison found, doing upgrade...");
+    // Credits tenants with what stakes from their addresses earned since the last run. Stakes
+    // outside anon mode paid out on their own, so they only move the cursor along.
+    async fn credit_tenant_stakes(&self, anon_mode: bool) {
+        let mut tenants: Vec<TenantDB> = self.db.get_tenants();
 
-        let mut daemon_ready: ServerReadyDB = self.db.get_server_ready().unwrap();
+        if tenants.is_empty() {
+            return;
+        }
 
-        daemon_ready.daemon_ready = false;
-        daemon_ready.reason = Some("Daemon update in progress".to_string());
+        let credited_until: u64 = self
+            .db
+            .get_shared_vault()
+            .map_or(0, |shared_vault| shared_vault.credited_until);
+        let mut last_stake: u64 = credited_until;
 
-        self.db.set_server_ready(&daemon_ready).await.unwrap();
+        for (_, value) in self
+            .db
+            .rewards_ts_index
+            .range(credited_until.saturating_add(1).to_be_bytes()..)
+            .flatten()
+        {
+            let stake: RewardsDB = match serde_json::from_slice(&value) {
+                Ok(stake) => stake,
+                Err(_) => continue,
+            };
+            last_stake = stake.timestamp;
 
-        let dl_path_res = gv_methods::download_daemon().await;
+            if !anon_mode {
+                continue;
+            }
 
-        let dl_path: PathBuf = if let Err(err) = dl_path_res {
-            error!("Error downloading daemon: {}", err);
-            daemon_ready.daemon_ready = true;
-            daemon_ready.reason = None;
-            self.db.set_server_ready(&daemon_ready).await.unwrap();
-            return;
-        } else {
-            dl_path_res.unwrap()
-        };
+            let tenant: &mut TenantDB = match tenants.iter_mut().find(|tenant| {
+                stake.timestamp >= tenant.registered && tenant.addresses.contains(&stake.address)
+            }) {
+                Some(tenant) => tenant,
+                None => continue,
+            };
 
-        if self.tg_bot_active {
-            let current_time = chrono::Utc::now();
-            let timestamp: u64 = current_time.timestamp() as u64;
+            let amount: u64 = stake.reward + stake.agvr_reward;
+            tenant.owed += amount;
+            tenant.earned += amount;
 
-            let header = format!("👻 Daemon update in progress! 👻\n\n");
-            let msg = Some(format!(
-                "New release {} found!\nPlease be patient while the daemon is updated.",
-                latest_release,
-            ));
+            let entry: TenantLedgerDB = TenantLedgerDB {
+                timestamp: stake.timestamp,
+                tenant: tenant.name.clone(),
+                kind: "stake".to_string(),
+                txid: stake.txid,
+                amount,
+            };
+            self.db.add_tenant_ledger(&entry).await.unwrap();
+        }
 
-            let tg_queue: TgBotQueueDB = TgBotQueueDB {
-                timestamp,
-                header,
-                msg,
-                code_block: None,
-                url: None,
-                msg_type: "update".to_string(),
-                reward_txid: None,
-                msg_to_delete: None,
-            };
-
-            self.db
-                .set_tg_bot_queue(timestamp.to_string().as_bytes(), &tg_queue)
-                .await
-                .unwrap();
+        for tenant in &tenants {
+            // Skip anyone removed while their stakes were being credited
+            if self.db.get_tenant(&tenant.name).is_some() {
+                self.db.set_tenant(tenant).await.unwrap();
+            }
         }
 
-        self.set_daemon_online(false).await;
-        self.daemon.stop_daemon().await.unwrap();
-
-        let mut config = self.gv_config.write().await;
-
-        file_ops::rm_dir(&config.gv_home.join("daemon/")).unwrap();
-        file_ops::rm_dir(&PathBuf::from(TMP_PATH)).unwrap();
-
-        let path_and_hash: PathAndDigest =
-            gv_methods::extract_archive(&dl_path, &config.gv_home).unwrap();
-
-        config
-            .update_gv_config("daemon_path", path_and_hash.daemon_path.to_str().unwrap())
+        self.db
+            .set_shared_vault(&SharedVaultDB {
+                credited_until: last_stake,
+            })
+            .await
             .unwrap();
+    }
 
-        config
-            .update_gv_config("daemon_hash", path_and_hash.daemon_hash.as_str())
-            .unwrap();
+    // Pays tenants what they're owed out of the trusted anon balance, scaled down when it
+    // can't cover everyone yet. Returns what's left over for the vault's own payout.
+    async fn pay_tenants(
+        &self,
+        trusted_anon: f64,
+        in_type: &str,
+        min_payout: u64,
+        fee_rate: u64,
+    ) -> f64 {
+        let mut tenants: Vec<TenantDB> = self.db.get_tenants();
+        let available: u64 = self.daemon.convert_to_sat(trusted_anon);
+        let total_owed: u64 = tenants.iter().map(|tenant| tenant.owed).sum();
+
+        if total_owed == 0 {
+            return trusted_anon;
+        }
 
-        drop(config);
+        let timestamp: u64 = chrono::Utc::now().timestamp() as u64;
+        let mut spent: u64 = 0;
 
-        self.daemon.wait_for_daemon_startup().await;
+        for tenant in tenants.iter_mut() {
+            let amount: u64 = if total_owed > available {
+                (tenant.owed as u128 * available as u128 / total_owed as u128) as u64
+            } else {
+                tenant.owed
+            };
 
-        let daemon_version = self.daemon.get_daemon_version().await.unwrap();
-        let latest_release_str: String = gv_methods::get_latest_release().await.unwrap();
+            if amount == 0 || amount < min_payout {
+                continue;
+            }
 
-        self.set_daemon_version(&daemon_version).await;
-        self.set_latest_release(&latest_release_str).await;
-        self.set_daemon_online(true).await;
+            let is_stealth: bool = match self.daemon.get_address_info(&tenant.payout_address).await
+            {
+                Ok(addr_info) => addr_info
+                    .get("isstealthaddress")
+                    .and_then(|stealth| stealth.as_bool())
+                    .unwrap_or(false),
+                Err(err) => {
+                    error!("Error checking payout address for {}: {}", tenant.name, err);
+                    continue;
+                }
+            };
+            let out_type: &str = if is_stealth { "anon" } else { "ghost" };
+            let label: String = format!("Tenant payout to {}", tenant.name);
 
-        daemon_ready.daemon_ready = true;
-        daemon_ready.reason = None;
-        self.db.set_server_ready(&daemon_ready).await.unwrap();
+            let txid: String = match self
+                .daemon
+                .send_ghost_amount(
+                    &tenant.payout_address,
+                    in_type,
+                    out_type,
+                    self.daemon.convert_from_sat(amount),
+                    fee_rate,
+                    &label,
+                )
+                .await
+            {
+                Ok(txid) => txid,
+                Err(err) => {
+                    error!("Error paying tenant {}: {}", tenant.name, err);
+                    continue;
+                }
+            };
 
-        if self.tg_bot_active {
-            let current_time = chrono::Utc::now();
-            let timestamp: u64 = current_time.timestamp() as u64;
+            info!("Payout to tenant {}: {}", tenant.name, txid);
 
-            let header = format!("👻 Daemon update complete! 👻\n\n");
-            let msg = Some(format!(
-                "Update to version {} complete!\nYour GhostVault is now ready.",
-                daemon_version
-            ));
+            tenant.owed -= amount;
+            tenant.paid += amount;
+            spent += amount;
 
-            let tg_queue: TgBotQueueDB = TgBotQueueDB {
+            let entry: TenantLedgerDB = TenantLedgerDB {
                 timestamp,
-                header,
-                msg,
-                code_block: None,
-                url: None,
-                msg_type: "update".to_string(),
-                reward_txid: None,
-                msg_to_delete: None,
+                tenant: tenant.name.clone(),
+                kind: "payout".to_string(),
+                txid: txid.clone(),
+                amount,
             };
-
-            self.db
-                .set_tg_bot_queue(timestamp.to_string().as_bytes(), &tg_queue)
-                .await
-                .unwrap();
+            self.db.add_tenant_ledger(&entry).await.unwrap();
+            self.db.set_tenant(tenant).await.unwrap();
+
+            self.record_payouts(
+                &[Value::String(txid)],
+                &tenant.payout_address,
+                "tenant",
+                fee_rate,
+                &label,
+            )
+            .await;
         }
+
+        self.daemon
+            .convert_from_sat(available.saturating_sub(spent))
     }
 
-    async fn do_flush_rewards_to_anon(&self) {
-        let daemon_ready: bool = self.daemon_ready().await;
+    async fn get_tenant_statement_vec(
+        &self,
+        tenant: &TenantDB,
+        start: u64,
+        end: u64,
+    ) -> TenantStatement {
+        let ledger: Vec<TenantLedgerDB> = self.db.tenant_ledger(&tenant.name, start, end);
+        let mut entries: Vec<TenantStatementEntry> = Vec::with_capacity(ledger.len());
+        let mut stakes: u32 = 0;
+        let mut earned: Amount = Amount::ZERO;
+        let mut paid: Amount = Amount::ZERO;
 
-        if daemon_ready {
-            let balances = self.daemon.get_balances().await.unwrap();
-            let balance_value = balances.get("mine").unwrap().as_object().unwrap();
+        for entry in ledger {
+            let amount: Amount = Amount::from_sat(entry.amount);
 
-            let bal: serde_json::Map<String, Value> = balance_value.to_owned();
+            if entry.kind == "stake" {
+                stakes += 1;
+                earned += amount;
+            } else {
+                paid += amount;
+            }
 
-            let trusted_pub: f64 = bal.get("trusted").unwrap().as_f64().unwrap();
+            entries.push(TenantStatementEntry {
+                date: self.get_date_str(entry.timestamp).await,
+                timestamp: entry.timestamp,
+                kind: entry.kind,
+                txid: entry.txid,
+                amount,
+            });
+        }
 
-            let mut conf = self.gv_config.write().await;
-            let min_tx: f64 = self.daemon.convert_from_sat(MIN_TX_VALUE);
+        TenantStatement {
+            name: tenant.name.clone(),
+            payout_address: tenant.payout_address.clone(),
+            start: self.get_date_str(start).await,
+            end: self.get_date_str(end).await,
+            stakes,
+            earned,
+            paid,
+            owed: Amount::from_sat(tenant.owed),
+            entries,
+        }
+    }
 
-            if trusted_pub >= min_tx {
-                let addr_option: Option<String> = conf.to_owned().internal_anon;
+    async fn get_stake_heatmap_vec(&self, start: u64, end: u64, tz: &Tz) -> StakeHeatmap {
+        let first_stake: Option<u64> = match self.db.rewards_ts_index.first() {
+            Ok(Some((key, _))) => key.as_ref().try_into().ok().map(u64::from_be_bytes),
+            _ => None,
+        };
 
-                let addr: String = if addr_option.is_none() {
-                    let internal_anon = self
-                        .daemon
-                        .getnewstealthaddress()
-                        .await
-                        .unwrap()
-                        .as_str()
-                        .unwrap()
-                        .to_string();
+        // Days before the first stake would only pad the calendar with empty cells
+        let range_start: u64 = start.max(first_stake.unwrap_or(u64::MAX));
+        let range_end: u64 = end.min(Utc::now().timestamp() as u64);
 
-                    conf.update_gv_config("INTERNAL_ANON", &internal_anon)
-                        .unwrap();
+        let local_day = |timestamp: u64| -> NaiveDate {
+            Utc.timestamp_opt(timestamp as i64, 0)
+                .unwrap()
+                .with_timezone(tz)
+                .date_naive()
+        };
 
-                    internal_anon
-                } else {
-                    addr_option.unwrap()
-                };
+        let mut data: Vec<HeatmapDay> = Vec::new();
+
+        if range_start <= range_end {
+            let mut day_stakes: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+
+            for (key, _) in self
+                .db
+                .rewards_ts_index
+                .range(range_start.to_be_bytes()..range_end.saturating_add(1).to_be_bytes())
+                .flatten()
+            {
+                if let Ok(key) = key.as_ref().try_into() {
+                    *day_stakes
+                        .entry(local_day(u64::from_be_bytes(key)))
+                        .or_default() += 1;
+                }
+            }
 
-                let txid_res = self.daemon.send_ghost(&addr, "ghost", "anon").await;
+            let last_day: NaiveDate = local_day(range_end);
+            let mut day: NaiveDate = local_day(range_start);
 
-                println!("txid_res: {:?}", txid_res);
+            while day <= last_day {
+                let timestamp: u64 = time_ranges::local_day_start(tz, day);
 
-                let txid = match txid_res {
-                    Ok(txid) => txid,
-                    Err(err) => {
-                        error!("Error sending to address: {}", err);
-                        return;
-                    }
-                };
+                data.push(HeatmapDay {
+                    date: day.format("%Y-%m-%d").to_string(),
+                    timestamp,
+                    stakes: day_stakes.get(&day).copied().unwrap_or(0),
+                });
 
-                info!("Payout to anon address: {}", txid);
+                day = match day.succ_opt() {
+                    Some(next) => next,
+                    None => break,
+                };
             }
         }
+
+        StakeHeatmap {
+            max_stakes: data.iter().map(|day| day.stakes).max().unwrap_or(0),
+            total_stakes: data.iter().map(|day| day.stakes).sum(),
+            data,
+            timezone: tz.name().to_string(),
+            start: date_str_in(tz, range_start.min(range_end)),
+            end: date_str_in(tz, range_end),
+        }
     }
 
-    async fn do_reward_payout(&self) {
-        let daemon_ready: bool = self.daemon_ready().await;
-        let current_time = chrono::Utc::now();
-        let timestamp: u64 = current_time.timestamp() as u64;
+    fn load(&self, sys: &System) -> CpuLoad {
+        let system_load: LoadAverage = sys.load_average().unwrap();
+        let one: f32 = system_load.one;
+        let five: f32 = system_load.five;
+        let fifteen: f32 = system_load.fifteen;
 
-        if daemon_ready {
-            let balances = self.daemon.get_balances().await.unwrap();
-            let balance_value = balances.get("mine").unwrap().as_object().unwrap();
+        CpuLoad { one, five, fifteen }
+    }
 
-            let bal: serde_json::Map<String, Value> = balance_value.to_owned();
+    async fn vault_tz(&self) -> Tz {
+        let conf = self.gv_config.read().await;
+        Tz::from_str_insensitive(&conf.timezone).unwrap_or(Tz::UTC)
+    }
 
-            let trusted_anon: f64 = bal.get("anon_trusted").unwrap().as_f64().unwrap();
+    async fn get_date_str(&self, timestamp: u64) -> String {
+        date_str_in(&self.vault_tz().await, timestamp)
+    }
 
-            let conf = self.gv_config.read().await;
+    async fn maintenance_window(&self) -> MaintenanceWindow {
+        let conf = self.gv_config.read().await;
+        let start: u32 = conf.maint_window_start;
+        let end: u32 = conf.maint_window_end;
+        let timezone: String = conf.timezone.clone();
+        drop(conf);
 
-            let min_payout: f64 = self.daemon.convert_from_sat(conf.min_reward_payout);
+        let maintenance: MaintenanceDB = self.db.get_maintenance();
 
-            if trusted_anon >= min_payout {
-                let addr_option: Option<String> = conf.anon_reward_address.clone();
+        MaintenanceWindow {
+            start,
+            end,
+            timezone,
+            open: task_runner::in_maint_window(&self.gv_config).await,
+            deferred_update: maintenance.update,
+            deferred_resync: maintenance.resync,
+        }
+    }
 
-                if addr_option.is_some() {
-                    let addr: String = addr_option.unwrap();
-                    let addr_info: Value = self.daemon.get_address_info(&addr).await.unwrap();
-                    let is_stealth: bool = addr_info
-                        .get("isstealthaddress")
-                        .unwrap_or(&Value::Bool(false))
-                        .as_bool()
-                        .unwrap();
+    async fn do_force_resync(&self) {
+        let conf = self.gv_config.read().await;
+        let use_bootstrap: bool = conf.bootstrap_on_resync && conf.bootstrap_source().is_some();
+        drop(conf);
 
-                    let out_type: &str = if is_stealth { "anon" } else { "ghost" };
+        if use_bootstrap && self.do_bootstrap_sync().await {
+            return;
+        }
 
-                    let is_256bit: bool = addr_info
-                        .get("is256bit")
-                        .unwrap_or(&Value::Bool(false))
-                        .as_bool()
-                        .unwrap();
+        info!("Forcing a resync of the daemon...");
+        self.set_daemon_online(false).await;
+        self.set_daemon_synced(false).await;
 
-                    if is_256bit {
-                        let txids_res = self.daemon.zap_ghost(&addr, "anon").await;
+        let mut server_state: ServerReadyDB = self.db.get_server_ready().unwrap();
+        server_state.daemon_ready = false;
+        server_state.reason = Some("Forcing resync".to_string());
+        self.db.set_server_ready(&server_state).await.unwrap();
 
-                        let txids = match txids_res {
-                            Ok(txids) => txids,
-                            Err(err) => {
-                                error!("Error zapping to 256bit address: {}", err);
-                                return;
-                            }
-                        };
+        // The chain files can't go while ghostd still has them open
+        if let Err(err) = self.daemon.stop_daemon().await {
+            error!("Error stopping ghostd, resync cancelled: {}", err);
+            server_state.daemon_ready = true;
+            server_state.reason = None;
+            self.db.set_server_ready(&server_state).await.unwrap();
+            self.set_daemon_online(true).await;
+            return;
+        }
 
-                        let default_txid: Vec<Value> = Vec::new();
-                        let txid_vec = txids.as_array().unwrap_or(&default_txid);
+        let conf = self.gv_config.read().await;
+        let daemon_data_dir: PathBuf = conf.daemon_data_dir.clone();
+        drop(conf);
 
-                        if txid_vec.is_empty() {
-                            return;
-                        }
+        let blocks_dir: PathBuf = daemon_data_dir.join("blocks/");
+        let chainstate_dir: PathBuf = daemon_data_dir.join("chainstate/");
+        let peers_file: PathBuf = daemon_data_dir.join("peers.dat");
+        let banlist_file: PathBuf = daemon_data_dir.join("banlist.dat");
 
-                        for txid_value in txid_vec {
-                            let txid = txid_value.as_str().unwrap().to_string();
-                            info!("Zap to public address: {}", txid);
-                        }
+        // Remove the blocks and chainstate directories.
+        // This will force a resync of the daemon.
 
-                        if self.tg_bot_active {
-                            let header = format!("👻 Rewards coming your way! 👻");
+        // Also remove the peers.dat and banlist.dat files.
+        // Get these fresh incase of bad peers.
 
-                            let msg = Some(format!(
-                            "Anon rewards in the amount of {} GHOST being zapped to PUBLIC address.",
-                            trusted_anon
-                        ));
+        file_ops::rm_dir(&blocks_dir).unwrap();
+        file_ops::rm_dir(&chainstate_dir).unwrap();
+        file_ops::rm_file(&peers_file).unwrap();
+        file_ops::rm_file(&banlist_file).unwrap();
 
-                            let url = {
-                                let mut urls: Vec<String> = Vec::new();
-                                for txid_value in txid_vec {
-                                    urls.push(format!(
-                                        "https://ghostscan.io/tx/{}/",
-                                        txid_value.as_str().unwrap()
-                                    ));
-                                }
-                                Some(urls)
-                            };
+        self.daemon.wait_for_daemon_startup().await;
+        self.set_daemon_online(true).await;
 
-                            let msg_type = "rewards".to_string();
+        server_state.daemon_ready = true;
+        server_state.reason = None;
+        self.db.set_server_ready(&server_state).await.unwrap();
+    }
 
-                            let tg_queue: TgBotQueueDB = TgBotQueueDB {
-                                timestamp,
-                                header,
-                                msg,
-                                code_block: None,
-                                url,
-                                msg_type,
-                                reward_txid: None,
-                                msg_to_delete: None,
-                            };
-                            let txid = txid_vec[0].as_str().unwrap().to_string();
-                            self.db
-                                .set_tg_bot_queue(txid.as_bytes(), &tg_queue)
-                                .await
-                                .unwrap();
-                        }
-                    } else {
-                        let txids_res = self.daemon.send_ghost(&addr, "anon", out_type).await;
+    // The bootstrap downloads while ghostd keeps running, it's only stopped to swap the chain
+    // in. A download that fails or doesn't verify leaves ghostd alone and returns false.
+    async fn do_bootstrap_sync(&self) -> bool {
+        let conf = self.gv_config.read().await;
+        let source: Option<BootstrapSource> = conf.bootstrap_source();
+        let daemon_data_dir: PathBuf = conf.daemon_data_dir.clone();
+        drop(conf);
 
-                        let txids = match txids_res {
-                            Ok(txids) => txids,
-                            Err(err) => {
-                                error!("Error sending to address: {}", err);
-                                return;
-                            }
-                        };
+        let source: BootstrapSource = match source {
+            Some(source) => source,
+            None => return false,
+        };
 
-                        let default_txid: Vec<Value> = Vec::new();
-                        let txid_vec = txids.as_array().unwrap_or(&default_txid);
+        info!(
+            "Downloading the blockchain bootstrap from {}...",
+            source.url
+        );
 
-                        if txid_vec.is_empty() {
-                            return;
-                        }
+        let mut bootstrap: BootstrapDB = BootstrapDB {
+            url: source.url.clone(),
+            status: "downloading".to_string(),
+            started: chrono::Utc::now().timestamp() as u64,
+            finished: None,
+            downloaded: 0,
+            total: 0,
+            error: None,
+        };
+        self.db.set_bootstrap(&bootstrap).await.unwrap();
 
-                        for txid_value in txid_vec {
-                            let txid = txid_value.as_str().unwrap().to_string();
-                            info!("Payout to {} address: {}", out_type.to_uppercase(), txid);
-                        }
+        self.queue_bot_message(
+            "online",
+            "👻 Bootstrap Started! 👻",
+            format!("Downloading the blockchain bootstrap from {}", source.url),
+        )
+        .await;
 
-                        if self.tg_bot_active {
-                            let header = format!("👻 Rewards coming your way! 👻");
+        let downloaded: Arc<AtomicU64> = Arc::new(AtomicU64::new(0));
+        let total: Arc<AtomicU64> = Arc::new(AtomicU64::new(0));
 
-                            let msg = Some(format!(
-                                "Anon rewards in the amount of {} GHOST being sent to {} address.",
-                                trusted_anon,
-                                out_type.to_uppercase()
-                            ));
+        let progress_self = self.clone();
+        let (progress_downloaded, progress_total) = (Arc::clone(&downloaded), Arc::clone(&total));
+        let progress_task = tokio::spawn(async move {
+            progress_self
+                .monitor_bootstrap_progress(progress_downloaded, progress_total)
+                .await;
+        });
 
-                            let url = {
-                                let mut urls: Vec<String> = Vec::new();
-                                for txid_value in txid_vec {
-                                    urls.push(format!(
-                                        "https://ghostscan.io/tx/{}/",
-                                        txid_value.as_str().unwrap()
-                                    ));
-                                }
-                                Some(urls)
-                            };
+        let (chunk_downloaded, chunk_total) = (Arc::clone(&downloaded), Arc::clone(&total));
+        let download_res = gv_methods::download_bootstrap(&source, move |done, size| {
+            chunk_downloaded.store(done, Ordering::Relaxed);
+            chunk_total.store(size, Ordering::Relaxed);
+        })
+        .await;
+        progress_task.abort();
+
+        bootstrap.downloaded = downloaded.load(Ordering::Relaxed);
+        bootstrap.total = total.load(Ordering::Relaxed);
+
+        let archive_path: PathBuf = match download_res {
+            Ok(archive_path) => archive_path,
+            Err(err) => {
+                error!("Blockchain bootstrap download failed: {}", err);
+                bootstrap.status = "failed".to_string();
+                bootstrap.finished = Some(chrono::Utc::now().timestamp() as u64);
+                bootstrap.error = Some(err.to_string());
+                self.db.set_bootstrap(&bootstrap).await.unwrap();
+
+                self.queue_bot_message(
+                    "online",
+                    "👻 Bootstrap Failed! 👻",
+                    format!("Unable to download the blockchain bootstrap: {}", err),
+                )
+                .await;
+                return false;
+            }
+        };
 
-                            let msg_type = "rewards".to_string();
+        info!("Installing the blockchain bootstrap...");
+        bootstrap.status = "installing".to_string();
+        self.db.set_bootstrap(&bootstrap).await.unwrap();
 
-                            let tg_queue: TgBotQueueDB = TgBotQueueDB {
-                                timestamp,
-                                header,
-                                msg,
-                                code_block: None,
-                                url,
-                                msg_type,
-                                reward_txid: None,
-                                msg_to_delete: None,
-                            };
+        self.set_daemon_online(false).await;
+        self.set_daemon_synced(false).await;
 
-                            let txid = txid_vec[0].as_str().unwrap().to_string();
+        let mut server_state: ServerReadyDB = self.db.get_server_ready().unwrap();
+        server_state.daemon_ready = false;
+        server_state.reason = Some("Installing bootstrap".to_string());
+        self.db.set_server_ready(&server_state).await.unwrap();
 
-                            self.db
-                                .set_tg_bot_queue(txid.as_bytes(), &tg_queue)
-                                .await
-                                .unwrap();
+        if let Err(err) = self.daemon.stop_daemon().await {
+            error!("Error stopping ghostd, bootstrap cancelled: {}", err);
+            bootstrap.status = "failed".to_string();
+            bootstrap.finished = Some(chrono::Utc::now().timestamp() as u64);
+            bootstrap.error = Some(err.to_string());
+            self.db.set_bootstrap(&bootstrap).await.unwrap();
+            server_state.daemon_ready = true;
+            server_state.reason = None;
+            self.db.set_server_ready(&server_state).await.unwrap();
+            self.set_daemon_online(true).await;
+            return false;
+        }
+
+        // Peers are fetched fresh, same as a resync
+        file_ops::rm_file(&daemon_data_dir.join("peers.dat")).unwrap();
+        file_ops::rm_file(&daemon_data_dir.join("banlist.dat")).unwrap();
+
+        let install_archive: PathBuf = archive_path.clone();
+        let install_dir: PathBuf = daemon_data_dir.clone();
+        let install_res = tokio::task::spawn_blocking(move || {
+            gv_methods::install_bootstrap(&install_archive, &install_dir)
+        })
+        .await
+        .map_err(|err| err.to_string())
+        .and_then(|res| res.map_err(|err| err.to_string()));
+
+        // The archive is several GB, it isn't kept around once it's unpacked
+        let _ = file_ops::rm_file(&archive_path);
+        bootstrap.finished = Some(chrono::Utc::now().timestamp() as u64);
+
+        let msg: String = match install_res {
+            Ok(_) => {
+                info!("Blockchain bootstrap installed!");
+                bootstrap.status = "complete".to_string();
+                "Blockchain bootstrap installed, ghostd is syncing the remaining blocks."
+                    .to_string()
+            }
+            Err(err) => {
+                // Half a chain is worse than none, ghostd syncs from scratch instead
+                error!("Blockchain bootstrap install failed: {}", err);
+                file_ops::rm_dir(&daemon_data_dir.join("blocks")).unwrap();
+                file_ops::rm_dir(&daemon_data_dir.join("chainstate")).unwrap();
+                bootstrap.status = "failed".to_string();
+                bootstrap.error = Some(err.clone());
+                format!(
+                    "Unable to install the blockchain bootstrap: {}\nghostd is resyncing from the network instead.",
+                    err
+                )
+            }
+        };
+
+        self.db.set_bootstrap(&bootstrap).await.unwrap();
+
+        self.daemon.wait_for_daemon_startup().await;
+        self.set_daemon_online(true).await;
+
+        server_state.daemon_ready = true;
+        server_state.reason = None;
+        self.db.set_server_ready(&server_state).await.unwrap();
+
+        self.queue_bot_message("online", "👻 Bootstrap Finished! 👻", msg)
+            .await;
+
+        true
+    }
+
+    // Saves the download progress and tells the bot at every 25%.
+    async fn monitor_bootstrap_progress(&self, downloaded: Arc<AtomicU64>, total: Arc<AtomicU64>) {
+        let mut last_quarter: u64 = 0;
+
+        loop {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+
+            let done: u64 = downloaded.load(Ordering::Relaxed);
+            let size: u64 = total.load(Ordering::Relaxed);
+
+            if let Some(mut bootstrap) = self.db.get_bootstrap() {
+                bootstrap.downloaded = done;
+                bootstrap.total = size;
+                self.db.set_bootstrap(&bootstrap).await.unwrap();
+            }
+
+            if size == 0 {
+                continue;
+            }
+
+            let quarter: u64 = done * 4 / size;
+
+            if quarter > last_quarter && quarter < 4 {
+                last_quarter = quarter;
+                info!("Blockchain bootstrap download at {}%", quarter * 25);
+
+                self.queue_bot_message(
+                    "online",
+                    "👻 Bootstrap Progress 👻",
+                    format!(
+                        "Downloaded {} of {} ({}%)",
+                        format_bytes(done),
+                        format_bytes(size),
+                        quarter * 25
+                    ),
+                )
+                .await;
+            }
+        }
+    }
+
+    // Pruning only needs a restart, going back to a full node downloads the chain again.
+    async fn do_daemon_prune(&self, prune_change: PruneChange) {
+        let conf = self.gv_config.read().await;
+        let write_res = conf.write_daemon_prune();
+        drop(conf);
+
+        if let Err(err) = write_res {
+            error!("Unable to update ghost.conf: {}", err);
+            return;
+        }
+
+        if prune_change == PruneChange::Unprune {
+            self.do_force_resync().await;
+            return;
+        }
+
+        info!("Restarting ghostd in prune mode...");
+        self.set_daemon_online(false).await;
+
+        let mut server_state: ServerReadyDB = self.db.get_server_ready().unwrap();
+        server_state.daemon_ready = false;
+        server_state.reason = Some("Restarting ghostd".to_string());
+        self.db.set_server_ready(&server_state).await.unwrap();
+
+        if let Err(err) = self.daemon.stop_daemon().await {
+            error!("Error stopping ghostd for the restart: {}", err);
+        }
+        self.daemon.wait_for_daemon_startup().await;
+        self.set_daemon_online(true).await;
+
+        server_state.daemon_ready = true;
+        server_state.reason = None;
+        self.db.set_server_ready(&server_state).await.unwrap();
+    }
+
+    async fn do_wallet_rescan(&self, mut rescan: WalletRescanDB) {
+        let label: &str = if rescan.repair {
+            "Repairing wallet"
+        } else {
+            "Rescanning wallet"
+        };
+        info!("{} from block {}...", label, rescan.start_height);
+
+        let job: JobGuard = self.jobs.start("rescan").await;
+
+        let mut server_state: ServerReadyDB = self.db.get_server_ready().unwrap();
+        server_state.daemon_ready = false;
+        server_state.reason = Some(label.to_string());
+        self.db.set_server_ready(&server_state).await.unwrap();
+        self.set_daemon_available(false).await;
+
+        let (header, msg) = if rescan.repair {
+            (
+                "👻 Wallet Repair Started! 👻",
+                "Clearing the wallet's transactions and rescanning them from genesis.".to_string(),
+            )
+        } else {
+            (
+                "👻 Wallet Rescan Started! 👻",
+                format!("Rescanning the wallet from block {}.", rescan.start_height),
+            )
+        };
+        self.queue_bot_message("online", header, msg).await;
+
+        let progress_self = self.clone();
+        let progress_label: String = label.to_string();
+        let progress_task = tokio::spawn(async move {
+            progress_self.monitor_rescan_progress(&progress_label).await;
+        });
+
+        let rescan_res = if rescan.repair {
+            match self.daemon.clear_wallet_transactions().await {
+                Ok(_) => self.daemon.rescan_blockchain(rescan.start_height).await,
+                Err(err) => Err(err),
+            }
+        } else {
+            self.daemon.rescan_blockchain(rescan.start_height).await
+        };
+        progress_task.abort();
+
+        let current_time: u64 = chrono::Utc::now().timestamp() as u64;
+        rescan.finished = Some(current_time);
+
+        let action: &str = if rescan.repair { "repair" } else { "rescan" };
+        let msg: String = match rescan_res {
+            Ok(_) => {
+                info!("Wallet {} complete!", action);
+                rescan.status = "complete".to_string();
+                rescan.progress = 1.0;
+                rescan.error = None;
+                format!("Wallet {} complete!", action)
+            }
+            Err(err) => {
+                error!("Wallet {} failed: {}", action, err);
+                rescan.status = "failed".to_string();
+                rescan.error = Some(err.to_string());
+                format!("Wallet {} failed: {}", action, err)
+            }
+        };
+
+        self.db.set_wallet_rescan(&rescan).await.unwrap();
+
+        match &rescan.error {
+            Some(err) => job.finish(Err(err.clone())).await,
+            None => job.finish(Ok(Some(msg.clone()))).await,
+        }
+
+        // Pick up anything the rescan found before handing the wallet back.
+        self.daemon.cleanup_missing_tx(&self.db).await;
+
+        self.set_daemon_available(true).await;
+        server_state.daemon_ready = true;
+        server_state.reason = None;
+        self.db.set_server_ready(&server_state).await.unwrap();
+
+        let header: &str = if rescan.repair {
+            "👻 Wallet Repair Finished! 👻"
+        } else {
+            "👻 Wallet Rescan Finished! 👻"
+        };
+        self.queue_bot_message("online", header, msg).await;
+    }
+
+    // How far ghostd is through scanning blocks for the wallet, None while it isn't scanning.
+    async fn wallet_scan_progress(&self) -> Option<f64> {
+        let wallet_info: Value = self.daemon.get_wallet_info().await.ok()?;
+
+        wallet_info
+            .get("scanning")
+            .and_then(|scanning| scanning.get("progress"))
+            .and_then(|progress| progress.as_f64())
+    }
+
+    // The progress also goes into the server's unready reason, so the bot and status show it.
+    async fn monitor_rescan_progress(&self, label: &str) {
+        loop {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+
+            let progress: Option<f64> = self.wallet_scan_progress().await;
+
+            if let (Some(progress), Some(mut rescan)) = (progress, self.db.get_wallet_rescan()) {
+                info!("{} progress: {:.2}%", label, progress * 100.0);
+                rescan.progress = progress;
+                self.db.set_wallet_rescan(&rescan).await.unwrap();
+
+                if let Some(mut server_state) = self.db.get_server_ready() {
+                    server_state.reason = Some(format!("{} ({:.0}%)", label, progress * 100.0));
+                    self.db.set_server_ready(&server_state).await.unwrap();
+                }
+            }
+        }
+    }
+
+    // Like a sync, the Telegram import message is edited every IMPORT_MESSAGE_INTERVAL.
+    async fn monitor_import_progress(&self) {
+        let mut reported: u64 = 0;
+
+        loop {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+
+            let progress: f64 = match self.wallet_scan_progress().await {
+                Some(progress) => progress,
+                None => continue,
+            };
+
+            if let Some(mut server_state) = self.db.get_server_ready() {
+                server_state.reason = Some(format!("Importing wallet ({:.0}%)", progress * 100.0));
+                self.db.set_server_ready(&server_state).await.unwrap();
+            }
+
+            let now: u64 = chrono::Utc::now().timestamp() as u64;
+            if now - reported >= IMPORT_MESSAGE_INTERVAL {
+                self.queue_bot_message(
+                    "import",
+                    "👻 Importing Wallet 👻",
+                    format!(
+                        "Scanned {:.2}% of the blocks for the wallet.",
+                        progress * 100.0
+                    ),
+                )
+                .await;
+                reported = now;
+            }
+        }
+    }
+
+    // The old wallet's stakes are dropped and the imported one's are read back from ghostd.
+    async fn do_import_wallet(
+        &self,
+        name: String,
+        mnemonic: String,
+        scan_from: Option<u64>,
+        mut server_ready: ServerReadyDB,
+    ) -> Result<(), String> {
+        let progress_self = self.clone();
+        let progress_task = tokio::spawn(async move {
+            progress_self.monitor_import_progress().await;
+        });
+
+        let res = self
+            .daemon
+            .import_wallet(&name, &mnemonic, scan_from, &self.db)
+            .await;
+        progress_task.abort();
+
+        let (msg, result): (String, Result<(), String>) = match res {
+            Ok(_) => {
+                // MNEMONIC is now the imported wallet's
+                for mut wallet in self.db.get_cold_wallets() {
+                    if wallet.holds_mnemonic {
+                        wallet.holds_mnemonic = false;
+                        let _ = self.db.set_cold_wallet(&wallet).await;
+                    }
+                }
+
+                server_ready.reason = Some("Rebuilding stake history".to_string());
+                self.db.set_server_ready(&server_ready).await.unwrap();
+
+                self.db.clear_db().await.unwrap();
+                self.daemon.cleanup_missing_tx(&self.db).await;
+
+                info!("Imported wallet {}", name);
+                let msg: String = format!(
+                    "Wallet {} imported with {} stakes. If stakes or coins are missing, run gv-cli rescanwallet or gv-cli repairwallet.",
+                    name,
+                    self.db.rewards_ts_index.len()
+                );
+                (msg, Ok(()))
+            }
+            Err(err) => {
+                error!("Error importing wallet: {}", err);
+                (
+                    format!("Wallet import failed: {}", err),
+                    Err(err.to_string()),
+                )
+            }
+        };
+
+        self.set_daemon_available(true).await;
+        server_ready.daemon_ready = true;
+        server_ready.reason = None;
+        self.db.set_server_ready(&server_ready).await.unwrap();
+
+        self.queue_bot_message("imported", "👻 Wallet Import Finished! 👻", msg)
+            .await;
+
+        result
+    }
+
+    // Every wallet's stakes are read back in turn, the all time totals are worked out once they're
+    // all in as the wallets don't list them in order.
+    async fn do_reindex_history(&self, mut server_ready: ServerReadyDB) {
+        let conf = self.gv_config.read().await;
+        let primary: String = conf.rpc_wallet.clone();
+        drop(conf);
+
+        let wallets: Vec<String> = std::iter::once(primary)
+            .chain(
+                self.db
+                    .get_cold_wallets()
+                    .into_iter()
+                    .map(|wallet| wallet.name),
+            )
+            .collect();
+
+        // Transactions done and the total for the wallet being read, and how many came before it
+        let done: Arc<AtomicU64> = Arc::new(AtomicU64::new(0));
+        let total: Arc<AtomicU64> = Arc::new(AtomicU64::new(0));
+        let wallet_index: Arc<AtomicU64> = Arc::new(AtomicU64::new(0));
+
+        let progress_self = self.clone();
+        let (progress_done, progress_total, progress_wallet) = (
+            Arc::clone(&done),
+            Arc::clone(&total),
+            Arc::clone(&wallet_index),
+        );
+        let wallet_count: f64 = wallets.len() as f64;
+        let progress_task = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+
+                let total: u64 = progress_total.load(Ordering::Relaxed);
+                if total == 0 {
+                    continue;
+                }
+
+                let wallet_progress: f64 =
+                    progress_done.load(Ordering::Relaxed) as f64 / total as f64;
+                let progress: f64 = (progress_wallet.load(Ordering::Relaxed) as f64
+                    + wallet_progress)
+                    / wallet_count;
+
+                if let Some(mut server_state) = progress_self.db.get_server_ready() {
+                    server_state.reason = Some(format!(
+                        "Reindexing stake history ({:.0}%)",
+                        progress * 100.0
+                    ));
+                    if let Err(err) = progress_self.db.set_server_ready(&server_state).await {
+                        warn!("Error saving the reindex progress: {}", err);
+                    }
+                }
+            }
+        });
+
+        let res: Result<usize, Box<dyn std::error::Error + Send + Sync>> = async {
+            self.db.clear_stake_history().await?;
+
+            let mut stakes: usize = 0;
+
+            for (index, wallet) in wallets.iter().enumerate() {
+                let daemon: DaemonHelper = match self.cold_wallet_daemon(wallet).await {
+                    Some(daemon) => daemon,
+                    None => continue,
+                };
+
+                wallet_index.store(index as u64, Ordering::Relaxed);
+                total.store(0, Ordering::Relaxed);
+
+                stakes += daemon
+                    .import_history(&self.db, |wallet_done, wallet_total| {
+                        done.store(wallet_done as u64, Ordering::Relaxed);
+                        total.store(wallet_total as u64, Ordering::Relaxed);
+                    })
+                    .await?;
+            }
+
+            let repaired: u64 = self.db.recompute_reward_totals()?;
+            info!("All time totals recomputed for {} stakes", repaired);
+
+            self.db.gvdb.flush_async().await?;
+
+            Ok::<usize, Box<dyn std::error::Error + Send + Sync>>(stakes)
+        }
+        .await;
+        progress_task.abort();
+
+        let msg: String = match res {
+            Ok(stakes) => {
+                info!("Stake history reindexed with {} stakes", stakes);
+                format!(
+                    "Stake history rebuilt from {} wallets with {} stakes.",
+                    wallets.len(),
+                    stakes
+                )
+            }
+            Err(err) => {
+                error!("Error reindexing stake history: {}", err);
+                format!(
+                    "Stake history reindex failed: {}\nRun gv-cli reindexhistory again once ghostd is answering.",
+                    err
+                )
+            }
+        };
+
+        server_ready.daemon_ready = true;
+        server_ready.reason = None;
+        self.db.set_server_ready(&server_ready).await.unwrap();
+
+        self.queue_bot_message("reindex", "👻 Stake History Reindexed 👻", msg)
+            .await;
+    }
+
+    // A birthday is a block height or a YYYY-MM-DD date in the vault's timezone.
+    async fn birthday_timestamp(&self, birthday: &str) -> Result<u64, GvError> {
+        if let Ok(height) = birthday.parse::<u32>() {
+            let block_hash: String = self
+                .daemon
+                .getblockhash(height)
+                .await
+                .or_code(ErrorCode::InvalidArgument, "Birthday height not found")?;
+            let block: Value = self
+                .daemon
+                .getblock(&block_hash, 1)
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting the birthday block")?;
+
+            return block
+                .get("time")
+                .and_then(|time| time.as_u64())
+                .or_code(ErrorCode::DaemonError, "Malformed getblock response");
+        }
+
+        let date: NaiveDate = NaiveDate::parse_from_str(birthday, "%Y-%m-%d").map_err(|_| {
+            GvError::new(
+                ErrorCode::InvalidArgument,
+                "The birthday must be a block height or a YYYY-MM-DD date.",
+            )
+        })?;
+
+        let conf = self.gv_config.read().await;
+        let tz: Tz = Tz::from_str_insensitive(&conf.timezone).unwrap_or(Tz::UTC);
+        drop(conf);
+
+        Ok(time_ranges::local_day_start(&tz, date))
+    }
+
+    async fn explorer(&self) -> ExplorerProvider {
+        ExplorerProvider::new(&*self.gv_config.read().await)
+    }
+
+    // With BOT_RELAY on, messages are queued for a bot on another vault to pick up. Unlike the
+    // bot token it can be switched by reload_config.
+    fn tg_bot_active(&self, conf: &GVConfig) -> bool {
+        self.tg_bot_started || conf.bot_relay
+    }
+
+    async fn announce_active(&self) -> bool {
+        let conf = self.gv_config.read().await;
+        self.tg_bot_active(&conf)
+            || conf.announce_webhook_url.is_some()
+            || conf.announce_email.is_some()
+    }
+
+    // Webhook and email destinations are sent from here, the Telegram ones are queued for the bot.
+    async fn announce(&self, key: impl AsRef<[u8]>, tg_queue: &TgBotQueueDB) {
+        let destinations: Vec<AnnounceDestination> =
+            self.db.announce_destinations(&tg_queue.msg_type);
+
+        let conf: GVConfig = self.gv_config.read().await.clone();
+        let tg_bot_active: bool = self.tg_bot_active(&conf);
+
+        if !announce::announce_enabled(&conf, &tg_queue.msg_type) {
+            return;
+        }
+
+        if let Some(event) = announce::event_for_msg_type(&tg_queue.msg_type) {
+            let may_hold: bool = !announce::bypasses_quiet_hours(&tg_queue.msg_type);
+            let mut destinations: Vec<AnnounceDestination> = destinations.clone();
+
+            for destination in [AnnounceDestination::Webhook, AnnounceDestination::Email] {
+                if may_hold
+                    && destinations.contains(&destination)
+                    && announce::in_quiet_hours(&self.db, &conf, destination)
+                {
+                    let _ = self
+                        .db
+                        .hold_notice(destination, &announce::held_notice(tg_queue, ""))
+                        .await;
+                    destinations.retain(|dest| *dest != destination);
+                }
+            }
+
+            if destinations.contains(&AnnounceDestination::Webhook) {
+                let webhook_conf: GVConfig = conf.clone();
+                let webhook_msg: TgBotQueueDB = tg_queue.clone();
+                tokio::spawn(async move {
+                    if let Err(err) =
+                        announce::send_webhook(&webhook_conf, event, &webhook_msg).await
+                    {
+                        warn!("Error sending {} webhook: {}", event, err);
+                    }
+                });
+            }
+
+            if destinations.contains(&AnnounceDestination::Email) {
+                let email_msg: TgBotQueueDB = tg_queue.clone();
+                tokio::task::spawn_blocking(move || {
+                    if let Err(err) = announce::send_email(&conf, &email_msg) {
+                        warn!("Error sending {} email: {}", event, err);
+                    }
+                });
+            }
+        }
+
+        if tg_bot_active
+            && destinations
+                .iter()
+                .any(|destination| destination.is_telegram())
+        {
+            self.db.set_tg_bot_queue(key, tg_queue).await.unwrap();
+        }
+    }
+
+    // Stake, zap and payout messages in the operator's own words when a template is set for the
+    // type. The template replaces the text and the details block, the explorer links are kept.
+    async fn announce_templated(
+        &self,
+        key: impl AsRef<[u8]>,
+        mut tg_queue: TgBotQueueDB,
+        mut values: TemplateValues,
+    ) {
+        if let Some(template) = self.db.get_announce_template(&tg_queue.msg_type) {
+            values.total_24h = self.get_stakes_days(1).await.total_amount.to_string();
+
+            if let Some(header) = &template.header {
+                tg_queue.header = announce::render_template(header, &values);
+            }
+            tg_queue.msg = Some(announce::render_template(&template.body, &values));
+            tg_queue.code_block = None;
+        }
+
+        self.announce(key, &tg_queue).await;
+    }
+
+    // Webhook and email notices held during quiet hours go out as one summary once they end, the
+    // bot does the same for the Telegram destinations.
+    async fn send_held_notices(&self) {
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_secs(QUIET_HOURS_CHECK_INTERVAL)).await;
+
+            let conf: GVConfig = self.gv_config.read().await.clone();
+
+            for destination in [AnnounceDestination::Webhook, AnnounceDestination::Email] {
+                if announce::in_quiet_hours(&self.db, &conf, destination)
+                    || self.db.count_held_notices(destination) == 0
+                {
+                    continue;
+                }
+
+                let notices: Vec<HeldNoticeDB> = match self.db.take_held_notices(destination).await
+                {
+                    Ok(notices) => notices,
+                    Err(err) => {
+                        warn!("Error reading held notices: {}", err);
+                        continue;
+                    }
+                };
+
+                let summary: TgBotQueueDB = announce::quiet_hours_summary(&notices);
+
+                let sent: Result<(), Box<dyn std::error::Error + Send + Sync>> = match destination {
+                    AnnounceDestination::Webhook => {
+                        announce::send_webhook(&conf, "quiet_summary", &summary).await
+                    }
+                    _ => {
+                        let email_conf: GVConfig = conf.clone();
+                        tokio::task::spawn_blocking(move || {
+                            announce::send_email(&email_conf, &summary)
+                        })
+                        .await
+                        .unwrap_or_else(|err| Err(err.to_string().into()))
+                    }
+                };
+
+                if let Err(err) = sent {
+                    warn!(
+                        "Error sending quiet hours summary to {}: {}",
+                        destination.as_str(),
+                        err
+                    );
+                }
+            }
+        }
+    }
+
+    // The address a reward mode sends to. It has to be valid, not one of GhostVault's own and on
+    // REWARD_ADDRESS_WHITELIST when that's set.
+    async fn check_reward_address(
+        &self,
+        whitelist: &[String],
+        mode: &str,
+        addr: Option<&String>,
+    ) -> Result<String, GvError> {
+        let addr: &String = addr.ok_or_else(|| {
+            GvError::new(
+                ErrorCode::InvalidArgument,
+                format!("An address is required for {} mode!", mode.to_lowercase()),
+            )
+        })?;
+
+        // An address book label stands in for its address
+        let addr: String = self
+            .db
+            .find_labeled_address(addr)
+            .map_or_else(|| addr.clone(), |entry| entry.address);
+
+        let addr_info: Value = self
+            .daemon
+            .get_address_info(&addr)
+            .await
+            .map_err(|_| GvError::new(ErrorCode::InvalidArgument, "Invalid address!"))?;
+
+        let is_mine: bool = addr_info
+            .get("ismine")
+            .and_then(|is_mine| is_mine.as_bool())
+            .unwrap_or(false);
+
+        if is_mine {
+            return Err(GvError::new(
+                ErrorCode::InvalidArgument,
+                "Cannot use a address owned by GhostVault!",
+            ));
+        }
+
+        if !whitelist.is_empty() && !whitelist.contains(&addr) {
+            return Err(GvError::new(
+                ErrorCode::Unauthorized,
+                "Address is not in REWARD_ADDRESS_WHITELIST!",
+            ));
+        }
+
+        Ok(addr)
+    }
+
+    async fn apply_reward_mode(&self, mode: &str, addr: Option<String>) -> GvResult<String> {
+        let mut conf = self.gv_config.write().await;
+
+        match mode {
+            "ANON" => {
+                let addr: String = self
+                    .check_reward_address(&conf.reward_address_whitelist, mode, addr.as_ref())
+                    .await?;
+
+                let mut internal_anon: String =
+                    conf.internal_anon.clone().unwrap_or("".to_string());
+
+                if internal_anon.is_empty() {
+                    let anon_addr = self
+                        .daemon
+                        .getnewstealthaddress()
+                        .await
+                        .or_code(ErrorCode::DaemonError, "Error getting stealth address")?
+                        .as_str()
+                        .or_code(ErrorCode::DaemonError, "Malformed daemon response")?
+                        .to_string();
+                    conf.update_gv_config("internal_anon", &anon_addr)
+                        .or_code(ErrorCode::Internal, "Error updating config")?;
+                } else {
+                    let addr_is_valid: bool =
+                        match self.daemon.get_address_info(&internal_anon).await {
+                            Ok(addr_info) => {
+                                let is_stealth = addr_info
+                                    .get("isstealthaddress")
+                                    .and_then(Value::as_bool)
+                                    .unwrap_or(false);
+
+                                let is_mine = addr_info
+                                    .get("ismine")
+                                    .and_then(Value::as_bool)
+                                    .unwrap_or(false);
+
+                                is_stealth && is_mine
+                            }
+                            Err(_) => false,
+                        };
+
+                    if !addr_is_valid {
+                        let anon_addr = self
+                            .daemon
+                            .getnewstealthaddress()
+                            .await
+                            .or_code(ErrorCode::DaemonError, "Error getting stealth address")?
+                            .as_str()
+                            .or_code(ErrorCode::DaemonError, "Malformed daemon response")?
+                            .to_string();
+                        conf.update_gv_config("internal_anon", &anon_addr)
+                            .or_code(ErrorCode::Internal, "Error updating config")?;
+                        internal_anon = anon_addr;
+                    }
+                }
+
+                conf.update_gv_config("reward_address", &internal_anon)
+                    .or_code(ErrorCode::Internal, "Error updating config")?;
+                conf.update_gv_config("anon_mode", "true")
+                    .or_code(ErrorCode::Internal, "Error updating config")?;
+                conf.update_gv_config("anon_reward_address", &addr)
+                    .or_code(ErrorCode::Internal, "Error updating config")?;
+                self.daemon
+                    .set_reward_addr_in_wallet(Some(&internal_anon))
+                    .await
+                    .or_code(
+                        ErrorCode::DaemonError,
+                        "Error setting reward address in wallet",
+                    )?;
+                Ok(response::message("Reward mode updated!"))
+            }
+            "STANDARD" => {
+                let addr: String = self
+                    .check_reward_address(&conf.reward_address_whitelist, mode, addr.as_ref())
+                    .await?;
+
+                conf.update_gv_config("reward_address", &addr)
+                    .or_code(ErrorCode::Internal, "Error updating config")?;
+                conf.update_gv_config("anon_mode", "false")
+                    .or_code(ErrorCode::Internal, "Error updating config")?;
+                self.daemon
+                    .set_reward_addr_in_wallet(Some(&addr))
+                    .await
+                    .or_code(
+                        ErrorCode::DaemonError,
+                        "Error setting reward address in wallet",
+                    )?;
+                Ok(response::message("Reward mode updated!"))
+            }
+            "DEFAULT" => {
+                conf.update_gv_config("anon_mode", "false")
+                    .or_code(ErrorCode::Internal, "Error updating config")?;
+                conf.update_gv_config("reward_address", "")
+                    .or_code(ErrorCode::Internal, "Error updating config")?;
+                self.daemon.set_reward_addr_in_wallet(None).await.or_code(
+                    ErrorCode::DaemonError,
+                    "Error setting reward address in wallet",
+                )?;
+                Ok(response::message("Reward mode updated!"))
+            }
+            _ => Err(GvError::new(ErrorCode::InvalidArgument, "Invalid mode!")),
+        }
+    }
+
+    // With REWARD_CHANGE_DELAY set a new reward address waits before it's used, and is announced
+    // so a change nobody asked for can be cancelled in time.
+    async fn schedule_reward_change(
+        &self,
+        mode: String,
+        address: String,
+        delay: u64,
+    ) -> GvResult<String> {
+        let now: u64 = Utc::now().timestamp() as u64;
+
+        let pending: PendingRewardChangeDB = PendingRewardChangeDB {
+            mode,
+            address,
+            requested: now,
+            effective: now + delay,
+        };
+
+        self.db
+            .set_pending_reward_change(&pending)
+            .await
+            .or_code(ErrorCode::Internal, "Database error")?;
+
+        let wait: FormattedDuration = format_duration(Duration::from_secs(delay));
+        info!(
+            "Reward address change to {} ({}) scheduled, taking effect in {}.",
+            pending.address, pending.mode, wait
+        );
+
+        let msg: String = format!(
+            "Rewards will go to {} ({}) in {}.\nIf you didn't ask for this, cancel it with the Cancel button or 'gv-cli cancelrewardchange'.",
+            self.address_name(&pending.address),
+            pending.mode.to_lowercase(),
+            wait
+        );
+        self.queue_bot_message("reward_change", "👻 Reward Address Change 👻", msg)
+            .await;
+
+        Ok(response::message(format!(
+            "Reward mode change scheduled, it takes effect in {}. Cancel it with cancelrewardchange.",
+            wait
+        )))
+    }
+
+    // Applies a scheduled reward change once its delay is up.
+    async fn process_reward_change(&self) {
+        let pending: PendingRewardChangeDB = match self.db.get_pending_reward_change() {
+            Some(pending) => pending,
+            None => return,
+        };
+
+        if pending.effective > Utc::now().timestamp() as u64 {
+            return;
+        }
+
+        if let Err(err) = self.db.remove_pending_reward_change().await {
+            error!("Error removing pending reward change: {}", err);
+            return;
+        }
+
+        let msg: String = match self
+            .apply_reward_mode(&pending.mode, Some(pending.address.clone()))
+            .await
+        {
+            Ok(_) => {
+                info!(
+                    "Scheduled reward change applied, rewards now go to {}.",
+                    pending.address
+                );
+                format!(
+                    "Rewards now go to {} ({}).",
+                    self.address_name(&pending.address),
+                    pending.mode.to_lowercase()
+                )
+            }
+            Err(err) => {
+                error!("Scheduled reward change failed: {}", err);
+                format!(
+                    "The scheduled reward change to {} failed: {}",
+                    self.address_name(&pending.address),
+                    err
+                )
+            }
+        };
+
+        self.queue_bot_message("reward_changed", "👻 Reward Address Changed 👻", msg)
+            .await;
+    }
+
+    // The address with its address book label, for messages.
+    fn address_name(&self, address: &str) -> String {
+        labeled_address(address, self.db.get_address_label(address).as_deref())
+    }
+
+    async fn notify_mnemonic_access(&self, outcome: &str) {
+        let origin: String = self.connection.origin.lock().unwrap().clone();
+        let peer: String = self
+            .connection
+            .peer
+            .clone()
+            .unwrap_or_else(|| "local".to_string());
+
+        let msg: String = format!(
+            "The sealed mnemonic {} ({} from {}).",
+            outcome, origin, peer
+        );
+        warn!("{}", msg);
+
+        self.queue_bot_message("mnemonic_access", "👻 Mnemonic Access 👻", msg)
+            .await;
+    }
+
+    async fn queue_bot_message(&self, msg_type: &str, header: &str, msg: String) {
+        if !self.announce_active().await {
+            return;
+        }
+
+        let current_time = chrono::Utc::now();
+        let timestamp: u64 = current_time.timestamp() as u64;
+
+        let tg_queue: TgBotQueueDB = TgBotQueueDB {
+            timestamp,
+            header: header.to_string(),
+            msg: Some(msg),
+            code_block: None,
+            url: None,
+            msg_type: msg_type.to_string(),
+            reward_txid: None,
+            msg_to_delete: None,
+            action_token: None,
+            msg_to_edit: None,
+        };
+
+        self.announce(timestamp.to_string().as_bytes(), &tg_queue)
+            .await;
+    }
+
+    // Percent of the period ghostd was up, going by the offline and online events in the event
+    // log. Downtime from before the log's retention isn't known and counts as up.
+    fn daemon_uptime(&self, start: u64, end: u64) -> f64 {
+        let overlap = |from: u64, to: u64| -> u64 { to.min(end).saturating_sub(from.max(start)) };
+
+        let mut offline_since: Option<u64> = None;
+        let mut downtime: u64 = 0;
+
+        for event in self.db.get_events_since(0, usize::MAX) {
+            if event.timestamp >= end {
+                break;
+            }
+
+            match event.event.as_str() {
+                "offline" if offline_since.is_none() => offline_since = Some(event.timestamp),
+                "online" => {
+                    if let Some(since) = offline_since.take() {
+                        downtime += overlap(since, event.timestamp);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(since) = offline_since {
+            downtime += overlap(since, end);
+        }
+
+        let period: u64 = end.saturating_sub(start).max(1);
+
+        100.0 * (1.0 - downtime as f64 / period as f64)
+    }
+
+    async fn build_digest(&self, period: &str, start: u64, end: u64) -> Digest {
+        let totals: StakeRollupDB = self.db.stake_totals(start, end - 1);
+        let payouts: PayoutHistory = self.get_payout_history_vec(start, end - 1).await;
+
+        let samples: Vec<BalanceSampleDB> = self.db.balance_history(start, end - 1);
+        let balance = |sample: &BalanceSampleDB| -> i64 {
+            (sample.total_coldstaking + sample.anon_balance) as i64
+        };
+        let balance_change: Option<i64> = match (samples.first(), samples.last()) {
+            (Some(first), Some(last)) if samples.len() > 1 => Some(balance(last) - balance(first)),
+            _ => None,
+        };
+
+        Digest {
+            period: period.to_string(),
+            start,
+            end,
+            stakes: totals.stakes,
+            rewards: Amount::from_sat(totals.reward),
+            agvr: Amount::from_sat(totals.agvr_reward),
+            uptime: self.daemon_uptime(start, end),
+            payouts: payouts.payout_count,
+            paid: Amount::from_ghost(payouts.total_paid),
+            fees: Amount::from_ghost(payouts.total_fees),
+            balance_change,
+        }
+    }
+
+    async fn send_digest(&self, digest: &Digest) {
+        let first_day: String = self.get_date_str(digest.start).await;
+        let last_day: String = self.get_date_str(digest.end - 1).await;
+
+        let covered: String = if first_day == last_day {
+            first_day
+        } else {
+            format!("{} - {}", first_day, last_day)
+        };
+
+        let balance_change: String = match digest.balance_change {
+            Some(change) if change < 0 => {
+                format!("-{} GHOST", Amount::from_sat(change.unsigned_abs()))
+            }
+            Some(change) => format!("+{} GHOST", Amount::from_sat(change as u64)),
+            None => "Not enough balance samples".to_string(),
+        };
+
+        let msg: String = format!(
+            "{}\n\nStakes: {}\nRewards: {} GHOST\nAGVR: {} GHOST\nDaemon uptime: {:.2}%\nPayouts: {} ({} GHOST paid, {} GHOST fees)\nBalance change: {}",
+            covered,
+            digest.stakes,
+            digest.rewards,
+            digest.agvr,
+            digest.uptime,
+            digest.payouts,
+            digest.paid,
+            digest.fees,
+            balance_change
+        );
+
+        self.queue_bot_message("digest", &format!("👻 {} Digest 👻", digest.period), msg)
+            .await;
+    }
+
+    async fn do_update(&self, latest_release: &str) -> Result<(), String> {
+        info!("New daemon verison found, doing upgrade...");
+
+        let mut daemon_ready: ServerReadyDB = self
+            .db
+            .get_server_ready()
+            .ok_or("Server status missing from the database")?;
+
+        daemon_ready.daemon_ready = false;
+        daemon_ready.reason = Some("Daemon update in progress".to_string());
+
+        self.db
+            .set_server_ready(&daemon_ready)
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let source: DaemonSource = self.gv_config.read().await.daemon_source();
+
+        let dl_path: PathBuf = match gv_methods::download_daemon(&source).await {
+            Ok(dl_path) => dl_path,
+            Err(err) => {
+                error!("Error downloading daemon: {}", err);
+                daemon_ready.daemon_ready = true;
+                daemon_ready.reason = None;
+                self.db
+                    .set_server_ready(&daemon_ready)
+                    .await
+                    .map_err(|err| err.to_string())?;
+                return Err(format!("Error downloading daemon: {}", err));
+            }
+        };
+
+        self.install_daemon(&dl_path, latest_release, daemon_ready)
+            .await
+    }
+
+    // Swaps in ghostd from a verified release archive, daemon_ready is already marked as updating.
+    async fn install_daemon(
+        &self,
+        dl_path: &PathBuf,
+        latest_release: &str,
+        mut daemon_ready: ServerReadyDB,
+    ) -> Result<(), String> {
+        if self.announce_active().await {
+            let current_time = chrono::Utc::now();
+            let timestamp: u64 = current_time.timestamp() as u64;
+
+            let header = format!("👻 Daemon update in progress! 👻\n\n");
+            let msg = Some(format!(
+                "New release {} found!\nPlease be patient while the daemon is updated.",
+                latest_release,
+            ));
+
+            let tg_queue: TgBotQueueDB = TgBotQueueDB {
+                timestamp,
+                header,
+                msg,
+                code_block: None,
+                url: None,
+                msg_type: "update".to_string(),
+                reward_txid: None,
+                msg_to_delete: None,
+                action_token: None,
+                msg_to_edit: None,
+            };
+
+            self.announce(timestamp.to_string().as_bytes(), &tg_queue)
+                .await;
+        }
+
+        // The new release goes in next to the running one, nothing is stopped until it's unpacked
+        let conf = self.gv_config.read().await;
+        let gv_home: PathBuf = conf.gv_home.clone();
+        let previous_path: PathBuf = conf.daemon_path.clone();
+        let previous_hash: String = conf.daemon_hash.clone().unwrap_or_default();
+        drop(conf);
+
+        let path_and_hash: PathAndDigest = match gv_methods::extract_archive(dl_path, &gv_home) {
+            Ok(path_and_hash) => path_and_hash,
+            Err(err) => {
+                error!("Error extracting daemon: {}", err);
+                daemon_ready.daemon_ready = true;
+                daemon_ready.reason = None;
+                self.db
+                    .set_server_ready(&daemon_ready)
+                    .await
+                    .map_err(|err| err.to_string())?;
+                return Err(format!("Error extracting daemon: {}", err));
+            }
+        };
+
+        if let Err(err) = gv_methods::clean_download_cache(Duration::ZERO) {
+            warn!("Unable to clean download cache: {}", err);
+        }
+
+        self.set_daemon_online(false).await;
+
+        if let Err(err) = self.daemon.stop_daemon().await {
+            warn!("Unable to stop ghostd before the update: {}", err);
+        }
+
+        let mut config = self.gv_config.write().await;
+
+        config
+            .update_gv_config("daemon_path", &path_and_hash.daemon_path.to_string_lossy())
+            .map_err(|err| err.to_string())?;
+
+        config
+            .update_gv_config("daemon_hash", path_and_hash.daemon_hash.as_str())
+            .map_err(|err| err.to_string())?;
+
+        drop(config);
+
+        let health = self
+            .daemon
+            .wait_for_daemon_health(Duration::from_secs(DAEMON_UPGRADE_TIMEOUT))
+            .await;
+
+        if let Err(err) = health {
+            error!("New ghostd failed its health check: {}", err);
+            self.rollback_daemon(&path_and_hash.daemon_path, &previous_path, &previous_hash)
+                .await;
+
+            daemon_ready.daemon_ready = true;
+            daemon_ready.reason = None;
+            self.db
+                .set_server_ready(&daemon_ready)
+                .await
+                .map_err(|err| err.to_string())?;
+
+            self.queue_bot_message(
+                "update",
+                "👻 Daemon update rolled back! 👻\n\n",
+                format!(
+                    "ghostd {} failed to start and sync: {}\nThe previous version is running again.",
+                    latest_release, err
+                ),
+            )
+            .await;
+            return Err(format!(
+                "ghostd {} failed its health check: {}",
+                latest_release, err
+            ));
+        }
+
+        // Only the new release and the one before it are kept around
+        if let Err(err) = gv_methods::prune_daemon_versions(
+            &gv_home,
+            &[&path_and_hash.daemon_path, &previous_path],
+        ) {
+            warn!("Unable to remove old ghostd versions: {}", err);
+        }
+
+        let daemon_version: String = self
+            .daemon
+            .get_daemon_version()
+            .await
+            .map_err(|err| format!("Unable to get the new ghostd version: {}", err))?;
+
+        self.set_daemon_version(&daemon_version).await;
+
+        // An offline install may not be able to reach GitHub
+        let update_channel: UpdateChannel = self.gv_config.read().await.update_channel.clone();
+        if let Ok(latest_release_str) = gv_methods::get_latest_release(&update_channel).await {
+            self.set_latest_release(&latest_release_str).await;
+        }
+
+        self.set_daemon_online(true).await;
+
+        daemon_ready.daemon_ready = true;
+        daemon_ready.reason = None;
+        self.db
+            .set_server_ready(&daemon_ready)
+            .await
+            .map_err(|err| err.to_string())?;
+
+        self.events
+            .publish("update", serde_json::json!({ "version": daemon_version }))
+            .await;
+
+        if self.announce_active().await {
+            let current_time = chrono::Utc::now();
+            let timestamp: u64 = current_time.timestamp() as u64;
+
+            let header = format!("👻 Daemon update complete! 👻\n\n");
+            let msg = Some(format!(
+                "Update to version {} complete!\nYour GhostVault is now ready.",
+                daemon_version
+            ));
+
+            let tg_queue: TgBotQueueDB = TgBotQueueDB {
+                timestamp,
+                header,
+                msg,
+                code_block: None,
+                url: None,
+                msg_type: "update".to_string(),
+                reward_txid: None,
+                msg_to_delete: None,
+                action_token: None,
+                msg_to_edit: None,
+            };
+
+            self.announce(timestamp.to_string().as_bytes(), &tg_queue)
+                .await;
+        }
+
+        Ok(())
+    }
+
+    // Puts the previous ghostd back after a failed update and drops the broken install.
+    async fn rollback_daemon(
+        &self,
+        failed_path: &PathBuf,
+        previous_path: &PathBuf,
+        previous_hash: &str,
+    ) {
+        warn!("Rolling ghostd back to {}", previous_path.display());
+
+        if let Err(err) = self.daemon.stop_daemon().await {
+            warn!("Unable to stop the new ghostd: {}", err);
+        }
+
+        let mut config = self.gv_config.write().await;
+
+        let restored = config
+            .update_gv_config("daemon_path", &previous_path.to_string_lossy())
+            .and_then(|_| config.update_gv_config("daemon_hash", previous_hash));
+
+        if let Err(err) = restored {
+            error!(
+                "Unable to point the config back at the previous ghostd: {}",
+                err
+            );
+        }
+
+        let gv_home: PathBuf = config.gv_home.clone();
+        drop(config);
+
+        self.daemon.wait_for_daemon_startup().await;
+
+        if let Err(err) = gv_methods::prune_daemon_versions(&gv_home, &[previous_path]) {
+            warn!("Unable to remove {}: {}", failed_path.display(), err);
+        }
+
+        self.set_daemon_online(true).await;
+    }
+
+    async fn do_flush_rewards_to_anon(&self) {
+        if self.watch_only().await {
+            return;
+        }
+
+        let policy: AnonPolicy = self.gv_config.read().await.anon_policy();
+
+        if !policy.enabled {
+            return;
+        }
+
+        if !self.wallet_can_spend().await {
+            info!("Wallet is locked for spending, skipping anon conversion.");
+            return;
+        }
+
+        if self.in_chain_emergency().await {
+            info!("Chain split emergency active, skipping anon conversion.");
+            return;
+        }
+
+        let daemon_ready: bool = self.daemon_ready().await;
+
+        if daemon_ready {
+            self.relock_frozen_utxos(&self.daemon, "cold").await;
+            self.prune_anon_conversions().await;
+
+            // Rewards that come in before ANON_FLUSH_INTERVAL is up wait for the next matured stake
+            let now: u64 = chrono::Utc::now().timestamp() as u64;
+            let last_conversion: u64 = self
+                .db
+                .get_anon_conversions()
+                .last()
+                .map_or(0, |conversion| conversion.timestamp);
+
+            if policy.interval > 0 && now < last_conversion + policy.interval {
+                info!("Last anon conversion was less than ANON_FLUSH_INTERVAL ago, skipping.");
+                return;
+            }
+
+            let trusted_pub: f64 = match self.mine_balance("trusted").await {
+                Ok(balance) => balance,
+                Err(err) => {
+                    error!("Error getting the public balance: {}", err);
+                    return;
+                }
+            };
+
+            let mut conf = self.gv_config.write().await;
+            let threshold: f64 = self.daemon.convert_from_sat(policy.threshold);
+
+            if trusted_pub >= threshold {
+                let addr_option: Option<String> = conf.to_owned().internal_anon;
+
+                let addr: String = match addr_option {
+                    Some(addr) => addr,
+                    None => {
+                        let internal_anon: String = match self.daemon.getnewstealthaddress().await {
+                            Ok(Value::String(addr)) => addr,
+                            Ok(reply) => {
+                                error!("Unexpected getnewstealthaddress reply: {}", reply);
+                                return;
+                            }
+                            Err(err) => {
+                                error!("Error getting a new stealth address: {}", err);
+                                return;
+                            }
+                        };
+
+                        if let Err(err) = conf.update_gv_config("INTERNAL_ANON", &internal_anon) {
+                            error!("Error saving INTERNAL_ANON: {}", err);
+                            return;
+                        }
+
+                        internal_anon
+                    }
+                };
+
+                let fees: FeeSettings = conf.fee_settings();
+                let fee_rate: u64 = self.daemon.fee_rate(&fees).await;
+
+                let txid_res = self
+                    .daemon
+                    .send_ghost(&addr, "ghost", "anon", fee_rate, fees.max_fee, "", 0)
+                    .await;
+
+                println!("txid_res: {:?}", txid_res);
+
+                let txid = match txid_res {
+                    Ok(txid) => txid,
+                    Err(err) => {
+                        error!("Error sending to address: {}", err);
+                        return;
+                    }
+                };
+
+                info!("Payout to anon address: {}", txid);
+
+                let txids: Vec<String> = txid
+                    .as_array()
+                    .map(|txids| {
+                        txids
+                            .iter()
+                            .filter_map(|txid| txid.as_str().map(|txid| txid.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                if !txids.is_empty() {
+                    let conversion: AnonConversionDB = AnonConversionDB {
+                        timestamp: now,
+                        txids,
+                        amount: self.daemon.convert_to_sat(trusted_pub),
+                    };
+                    let _ = self.db.set_anon_conversion(&conversion).await;
+                }
+            }
+        }
+    }
+
+    // The fewest confirmations of the conversion's transactions, None once one of them is gone
+    // from the wallet, like after being abandoned.
+    async fn anon_conversion_confirmations(&self, conversion: &AnonConversionDB) -> Option<u64> {
+        let mut confirmations: Option<u64> = None;
+
+        for txid in &conversion.txids {
+            let tx: Value = self.daemon.get_transaction(txid).await.ok()?;
+            let confs: u64 = tx
+                .get("confirmations")
+                .and_then(|confs| confs.as_i64())
+                .unwrap_or(0)
+                .max(0) as u64;
+
+            confirmations = Some(confirmations.map_or(confs, |min| min.min(confs)));
+        }
+
+        confirmations
+    }
+
+    // The newest run is kept after it matures, ANON_FLUSH_INTERVAL counts from it.
+    async fn prune_anon_conversions(&self) {
+        let mut conversions: Vec<AnonConversionDB> = self.db.get_anon_conversions();
+        conversions.pop();
+
+        for conversion in conversions {
+            let matured: bool = match self.anon_conversion_confirmations(&conversion).await {
+                Some(confirmations) => confirmations >= ANON_MATURITY_CONFIRMATIONS,
+                None => true,
+            };
+
+            if matured {
+                let _ = self.db.remove_anon_conversion(conversion.timestamp).await;
+            }
+        }
+    }
+
+    async fn record_payouts(
+        &self,
+        txid_vec: &[Value],
+        destination: &str,
+        mode: &str,
+        fee_rate: u64,
+        label: &str,
+    ) {
+        let current_time = chrono::Utc::now();
+        let timestamp: u64 = current_time.timestamp() as u64;
+        let wallet: String = self.daemon.wallet_name().await;
+
+        for txid in txid_vec.iter().filter_map(Value::as_str) {
+            let txid: String = txid.to_string();
+
+            if !label.is_empty() {
+                let tx_label: TxLabelDB = TxLabelDB {
+                    txid: txid.clone(),
+                    label: label.to_string(),
+                    updated: timestamp,
+                };
+                let _ = self.db.set_tx_label(&tx_label).await;
+            }
+
+            let (amount, fee) = match self.daemon.get_transaction(&txid).await {
+                Ok(tx) => {
+                    let amount: f64 = tx.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                    let fee: f64 = tx.get("fee").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                    (
+                        self.daemon.convert_to_sat(amount.abs()),
+                        self.daemon.convert_to_sat(fee.abs()),
+                    )
+                }
+                Err(err) => {
+                    warn!("Unable to fetch payout details for {}: {}", txid, err);
+                    (0, 0)
+                }
+            };
+
+            let payout: PayoutDB = PayoutDB {
+                txid,
+                timestamp,
+                amount,
+                fee,
+                destination: destination.to_string(),
+                mode: mode.to_string(),
+                fee_rate,
+                wallet: wallet.clone(),
+            };
+
+            self.db.set_payout(&payout).await.unwrap();
+            self.events.publish("payout", &payout).await;
+        }
+    }
+
+    async fn get_payout_history_vec(&self, start: u64, end: u64) -> PayoutHistory {
+        let range_start = if start == 0 {
+            let first_payout_opt = self.db.payout_history_db.first().unwrap();
+            match first_payout_opt {
+                Some((_, value)) => {
+                    let value: PayoutDB = serde_json::from_slice(&value).unwrap();
+                    value.timestamp
+                }
+                None => 0,
+            }
+        } else {
+            start
+        };
+        let range_end = end;
+
+        let mut payouts: Vec<PayoutEntry> = Vec::new();
+        let mut total_paid_int: u64 = 0;
+        let mut total_fees_int: u64 = 0;
+
+        for result in self
+            .db
+            .payout_history_db
+            .range(range_start.to_be_bytes()..range_end.saturating_add(1).to_be_bytes())
+        {
+            match result {
+                Ok((_, value)) => {
+                    let value: PayoutDB = serde_json::from_slice(&value).unwrap();
+
+                    total_paid_int += value.amount;
+                    total_fees_int += value.fee;
+
+                    let label: String = self.db.get_tx_label(&value.txid).unwrap_or_default();
+                    let destination_label: Option<String> =
+                        self.db.get_address_label(&value.destination);
+
+                    payouts.push(PayoutEntry {
+                        date: self.get_date_str(value.timestamp).await,
+                        txid: value.txid,
+                        timestamp: value.timestamp,
+                        amount: self.daemon.convert_from_sat(value.amount),
+                        fee: self.daemon.convert_from_sat(value.fee),
+                        destination: value.destination,
+                        mode: value.mode,
+                        fee_rate: self.daemon.convert_from_sat(value.fee_rate),
+                        label,
+                        destination_label,
+                    });
+                }
+                Err(err) => {
+                    eprintln!("Error during iteration: {:?}", err);
+                }
+            }
+        }
+
+        let start = self.get_date_str(range_start).await;
+        let end = self.get_date_str(range_end).await;
+
+        PayoutHistory {
+            payout_count: payouts.len() as u32,
+            payouts,
+            total_paid: self.daemon.convert_from_sat(total_paid_int),
+            total_fees: self.daemon.convert_from_sat(total_fees_int),
+            start,
+            end,
+        }
+    }
+
+    async fn expected_stake_secs(&self) -> Option<u64> {
+        self.stake_forecast().await.ok()?.expected_secs
+    }
+
+    async fn stake_forecast(
+        &self,
+    ) -> Result<StakeForecast, Box<dyn std::error::Error + Send + Sync>> {
+        let staking_info: Value = self.daemon.getstakinginfo().await?;
+        let cold_staking_info: Value = self.daemon.getcoldstakinginfo().await?;
+
+        let difficulty: f64 = staking_info
+            .get("difficulty")
+            .and_then(|difficulty| difficulty.as_f64())
+            .unwrap_or_default();
+        let net_stake_weight: Amount = Amount::from_sat(
+            staking_info
+                .get("netstakeweight")
+                .and_then(|weight| weight.as_u64())
+                .unwrap_or_default(),
+        );
+        let currently_staking: Amount = Amount::from_ghost(
+            cold_staking_info
+                .get("currently_staking")
+                .and_then(|staking| staking.as_f64())
+                .unwrap_or_default(),
+        );
+
+        // Days before the first stake ever found aren't held against the vault
+        let now: u64 = chrono::Utc::now().timestamp() as u64;
+        let window_start: u64 = match self.db.rewards_ts_index.first()? {
+            Some((key, _)) => u64::from_be_bytes(key.as_ref().try_into()?)
+                .max(now.saturating_sub(STAKE_FORECAST_WINDOW_DAYS * 86400)),
+            None => now,
+        };
+        let actual_stakes: u32 = self.db.stake_totals(window_start, now).stakes;
+
+        Ok(stats::stake_forecast(
+            currently_staking,
+            net_stake_weight,
+            difficulty,
+            actual_stakes,
+            now - window_start,
+        ))
+    }
+
+    // ANON payouts are charged what past ones cost on average, before the first one the fee
+    // rate is applied to a typical anon transaction.
+    async fn simulated_payout_fee(&self, fees: &FeeSettings) -> (Amount, bool) {
+        let mut total_fees: u64 = 0;
+        let mut count: u64 = 0;
+
+        for (_, value) in self.db.payout_history_db.iter().flatten() {
+            let payout: PayoutDB = serde_json::from_slice(&value).unwrap();
+
+            if matches!(payout.mode.as_str(), "anon" | "ghost" | "zap") && payout.fee > 0 {
+                total_fees += payout.fee;
+                count += 1;
+            }
+        }
+
+        if let Some(average) = total_fees.checked_div(count) {
+            return (Amount::from_sat(average), false);
+        }
+
+        let fee_rate: u64 = self.daemon.fee_rate(fees).await;
+        (
+            Amount::from_sat(fee_rate * ANON_PAYOUT_TX_SIZE / 1000),
+            true,
+        )
+    }
+
+    async fn simulate_rewards_for(&self, days: u64) -> RewardSimulation {
+        let conf = self.gv_config.read().await;
+        let current_mode: &str = if conf.anon_mode {
+            "ANON"
+        } else if conf
+            .reward_address
+            .as_ref()
+            .is_some_and(|addr| !addr.is_empty())
+        {
+            "STANDARD"
+        } else {
+            "DEFAULT"
+        };
+        let current_mode: String = current_mode.to_string();
+        let reward_interval: u64 = conf.reward_interval;
+        let min_payout: Amount = Amount::from_sat(conf.min_reward_payout);
+        let fees: FeeSettings = conf.fee_settings();
+        drop(conf);
+
+        let end: u64 = Utc::now().timestamp() as u64;
+        let start: u64 = end.saturating_sub(days * 86400);
+
+        let stakes: Vec<(u64, Amount)> = self
+            .db
+            .rewards_ts_index
+            .range(start.to_be_bytes()..=end.to_be_bytes())
+            .filter_map(|result| result.ok())
+            .map(|(_, value)| {
+                let reward: RewardsDB = serde_json::from_slice(&value).unwrap();
+                (
+                    reward.timestamp,
+                    Amount::from_sat(reward.reward + reward.agvr_reward),
+                )
+            })
+            .collect();
+
+        let (payout_fee, payout_fee_estimated) = self.simulated_payout_fee(&fees).await;
+
+        RewardSimulation {
+            days,
+            stakes: stakes.len() as u32,
+            rewards: stakes.iter().map(|(_, reward)| *reward).sum(),
+            current_mode,
+            payout_fee,
+            payout_fee_estimated,
+            modes: stats::simulate_reward_modes(
+                &stakes,
+                start,
+                end,
+                reward_interval,
+                min_payout,
+                payout_fee,
+            ),
+        }
+    }
+
+    async fn staking_roi(&self) -> StakingRoi {
+        let now: u64 = Utc::now().timestamp() as u64;
+
+        let window = |days: u64| -> Option<RoiWindow> {
+            let (average_staked, covered_from) = self
+                .db
+                .average_staked_balance(now.saturating_sub(days * 86400), now)?;
+            let rewards: StakeRollupDB = self.db.stake_totals(covered_from, now);
+
+            Some(stats::roi_window(
+                Amount::from_sat(average_staked),
+                Amount::from_sat(rewards.reward + rewards.agvr_reward),
+                now - covered_from,
+            ))
+        };
+
+        StakingRoi {
+            roi_30d: window(30),
+            roi_90d: window(90),
+            roi_1y: window(365),
+        }
+    }
+
+    async fn get_stake_streak(&self) -> StakeStreak {
+        let conf = self.gv_config.read().await;
+        let tz: Tz = Tz::from_str_insensitive(&conf.timezone).unwrap_or(Tz::UTC);
+        drop(conf);
+
+        let current_time = chrono::Utc::now();
+        let today: NaiveDate = current_time.with_timezone(&tz).date_naive();
+
+        let mut streak_days: u32 = 0;
+        let mut last_stake_ts: Option<u64> = None;
+        let mut expected_day: Option<NaiveDate> = None;
+
+        for result in self.db.rewards_ts_index.iter().rev() {
+            let value: RewardsDB = match result {
+                Ok((_, value)) => serde_json::from_slice(&value).unwrap(),
+                Err(_) => continue,
+            };
+
+            let stake_day: NaiveDate = Utc
+                .timestamp_opt(value.timestamp as i64, 0)
+                .unwrap()
+                .with_timezone(&tz)
+                .date_naive();
+
+            let next_day: NaiveDate = match expected_day {
+                // A streak is still alive if the last stake was today or yesterday.
+                None => {
+                    last_stake_ts = Some(value.timestamp);
+
+                    if stake_day < today.pred_opt().unwrap() {
+                        break;
+                    }
+                    stake_day
+                }
+                Some(day) if stake_day > day => continue,
+                Some(day) if stake_day == day => day,
+                Some(_) => break,
+            };
+
+            streak_days += 1;
+            expected_day = next_day.pred_opt();
+        }
+
+        let drought_secs: u64 = match last_stake_ts {
+            Some(timestamp) => (current_time.timestamp() as u64).saturating_sub(timestamp),
+            None => 0,
+        };
+
+        StakeStreak {
+            streak_days,
+            drought: format_duration(Duration::from_secs(drought_secs)).to_string(),
+            drought_secs,
+            expected_secs: self.expected_stake_secs().await,
+        }
+    }
+
+    async fn monitor_stake_streak(&self) {
+        let check_seconds: u64 = 60 * 10;
+        info!("Starting the stake streak monitor...");
+
+        loop {
+            tokio::time::sleep(Duration::from_secs(check_seconds)).await;
+
+            if !self.daemon_ready().await {
+                continue;
+            }
+
+            let streak: StakeStreak = self.get_stake_streak().await;
+            let mut streak_state: StakeStreakDB = self
+                .db
+                .get_stake_streak()
+                .unwrap_or(StakeStreakDB { last_milestone: 0 });
+
+            // The streak was broken, start counting milestones again.
+            if streak.streak_days < streak_state.last_milestone {
+                streak_state.last_milestone = 0;
+            }
+
+            let milestone: Option<u32> = STREAK_MILESTONES
+                .iter()
+                .rev()
+                .find(|milestone| streak.streak_days >= **milestone)
+                .copied();
+
+            if let Some(milestone) = milestone {
+                if milestone > streak_state.last_milestone {
+                    info!("Stake streak milestone reached: {} days", milestone);
+                    self.queue_bot_message(
+                        "streak",
+                        "👻 Stake Streak! 👻",
+                        format!(
+                            "GhostVault has staked every day for {} days in a row!",
+                            milestone
+                        ),
+                    )
+                    .await;
+                    streak_state.last_milestone = milestone;
+                }
+            }
+
+            self.db.set_stake_streak(&streak_state).await.unwrap();
+        }
+    }
+
+    // Staking can stop without any error, e.g. coins left the cold staking script or ghostd
+    // lost its peers. Stakes are random, so only a wait well past the expected interval for
+    // the current weight is treated as missed.
+    async fn monitor_missed_stakes(&self) {
+        info!("Starting the missed stake monitor...");
+
+        loop {
+            tokio::time::sleep(Duration::from_secs(MISSED_STAKE_CHECK_INTERVAL)).await;
+
+            let missed_stake_factor: u64 = self.gv_config.read().await.missed_stake_factor;
+
+            if missed_stake_factor == 0 || !self.daemon_ready().await {
+                continue;
+            }
+
+            let last_stake_ts: u64 = match self.db.rewards_ts_index.last() {
+                Ok(Some((key, _))) => match key.as_ref().try_into() {
+                    Ok(key) => u64::from_be_bytes(key),
+                    Err(_) => continue,
+                },
+                _ => continue,
+            };
+
+            let expected_secs: u64 = match self.expected_stake_secs().await {
+                Some(expected_secs) => expected_secs,
+                None => continue,
+            };
+
+            let now: u64 = Utc::now().timestamp() as u64;
+            let drought_secs: u64 = now.saturating_sub(last_stake_ts);
+
+            if drought_secs <= expected_secs.saturating_mul(missed_stake_factor) {
+                continue;
+            }
+
+            let mut missed: MissedStakeDB = self.db.get_missed_stake().unwrap_or(MissedStakeDB {
+                alerted_after: None,
+            });
+
+            if missed.alerted_after == Some(last_stake_ts) {
+                continue;
+            }
+
+            let drought: String = format_duration(Duration::from_secs(drought_secs)).to_string();
+            let expected: String = format_duration(Duration::from_secs(expected_secs)).to_string();
+            warn!(
+                "No stake for {}, expected about every {}",
+                drought, expected
+            );
+            self.queue_bot_message(
+                "drought",
+                "👻 Stake Drought 👻",
+                format!(
+                    "No stake for {}. At your current weight a stake is expected about every {}.\nDroughts this long do happen by chance, but it may be worth checking that GhostVault is staking.",
+                    drought, expected
+                ),
+            )
+            .await;
+
+            missed.alerted_after = Some(last_stake_ts);
+            self.db.set_missed_stake(&missed).await.unwrap();
+        }
+    }
+
+    // Samples disk and network use for get_system_stats, and warns while there's still time
+    // to act when the disk under ghostd or GhostVault is filling up.
+    async fn monitor_system_stats(&self) {
+        info!("Starting the system stats monitor...");
+
+        loop {
+            let sample: SystemSampleDB = self.system_sample().await;
+
+            if let Err(err) = self.db.set_system_sample(&sample).await {
+                warn!("Unable to save system stats: {}", err);
+            }
+
+            self.check_disk_space(&sample).await;
+
+            tokio::time::sleep(Duration::from_secs(SYSTEM_SAMPLE_INTERVAL)).await;
+        }
+    }
+
+    // ghostd only reports the current staking weights, so they're sampled here for
+    // get_network_stats. Nothing is sampled while ghostd syncs or is on a bad chain.
+    async fn monitor_network_stats(&self) {
+        info!("Starting the network stats monitor...");
+
+        loop {
+            if self.daemon_ready().await {
+                match self.network_sample().await {
+                    Ok(sample) => {
+                        if let Err(err) = self.db.set_network_sample(&sample).await {
+                            warn!("Unable to save network stats: {}", err);
+                        }
+                    }
+                    Err(err) => warn!("Unable to sample network stats: {}", err),
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(NETWORK_SAMPLE_INTERVAL)).await;
+        }
+    }
+
+    async fn network_sample(
+        &self,
+    ) -> Result<NetworkSampleDB, Box<dyn std::error::Error + Send + Sync>> {
+        let staking_info: Value = self.daemon.getstakinginfo().await?;
+        let cold_staking_info: Value = self.daemon.getcoldstakinginfo().await?;
+
+        let difficulty: f64 = staking_info
+            .get("difficulty")
+            .and_then(|difficulty| difficulty.as_f64())
+            .unwrap_or_default();
+        let net_stake_weight: Amount = Amount::from_sat(
+            staking_info
+                .get("netstakeweight")
+                .and_then(|weight| weight.as_u64())
+                .unwrap_or_default(),
+        );
+        let our_weight: Amount = Amount::from_ghost(
+            cold_staking_info
+                .get("currently_staking")
+                .and_then(|staking| staking.as_f64())
+                .unwrap_or_default(),
+        );
+
+        Ok(NetworkSampleDB {
+            timestamp: Utc::now().timestamp() as u64,
+            height: self
+                .db
+                .get_daemon_status()
+                .map_or(0, |status| status.height),
+            network_weight: stats::network_weight(net_stake_weight, difficulty) as u64,
+            difficulty,
+            our_weight: our_weight.to_sat(),
+        })
+    }
+
+    async fn system_sample(&self) -> SystemSampleDB {
+        let conf = self.gv_config.read().await;
+        let daemon_data_dir: PathBuf = conf.daemon_data_dir.clone();
+        let gv_home: PathBuf = conf.gv_home.clone();
+        drop(conf);
+
+        let sys: System = System::new();
+        let (daemon_disk_free, daemon_disk_total) = disk_space(&sys, &daemon_data_dir);
+        let (gv_disk_free, gv_disk_total) = disk_space(&sys, &gv_home);
+
+        // Still wanted while ghostd syncs, that's when the chain and traffic grow the most
+        let daemon_state: DaemonState = self.current_daemon_state().await;
+        let daemon_up: bool = daemon_state.online && daemon_state.available;
+
+        let (chain_size, net_totals) = if daemon_up {
+            let chain_size: Option<u64> = self
+                .daemon
+                .getblockchaininfo()
+                .await
+                .ok()
+                .and_then(|info| info.get("size_on_disk")?.as_u64());
+
+            (chain_size, self.daemon.getnettotals().await.ok())
+        } else {
+            (None, None)
+        };
+
+        let net_total = |key: &str| -> Option<u64> { net_totals.as_ref()?.get(key)?.as_u64() };
+
+        SystemSampleDB {
+            timestamp: Utc::now().timestamp() as u64,
+            daemon_disk_free,
+            daemon_disk_total,
+            gv_disk_free,
+            gv_disk_total,
+            chain_size,
+            net_bytes_recv: net_total("totalbytesrecv"),
+            net_bytes_sent: net_total("totalbytessent"),
+        }
+    }
+
+    // Alerts once per drop below DISK_ALERT_GB, it can go off again after space was freed.
+    async fn check_disk_space(&self, sample: &SystemSampleDB) {
+        let conf = self.gv_config.read().await;
+        let disk_alert_gb: u64 = conf.disk_alert_gb;
+        let daemon_data_dir: PathBuf = conf.daemon_data_dir.clone();
+        let gv_home: PathBuf = conf.gv_home.clone();
+        drop(conf);
+
+        let low_disks: Vec<String> = [
+            (
+                sample.daemon_disk_free,
+                sample.daemon_disk_total,
+                &daemon_data_dir,
+            ),
+            (sample.gv_disk_free, sample.gv_disk_total, &gv_home),
+        ]
+        .into_iter()
+        .filter(|(free, total, _)| low_disk(*free, *total, disk_alert_gb))
+        .map(|(free, _, path)| format!("{} free on {}", format_bytes(free), path.display()))
+        .collect();
+
+        let mut alert: DiskAlertDB = self.db.get_disk_alert();
+
+        if low_disks.is_empty() {
+            if alert.alerted {
+                alert.alerted = false;
+                self.db.set_disk_alert(&alert).await.unwrap();
+            }
+            return;
+        }
+
+        if alert.alerted {
+            return;
+        }
+
+        warn!("Low disk space: {}", low_disks.join(", "));
+        self.queue_bot_message(
+            "disk",
+            "👻 Low Disk Space 👻",
+            format!(
+                "{}.\nghostd stops when the disk fills up, most likely in the middle of a sync. Free up some space before it runs out.",
+                low_disks.join("\n")
+            ),
+        )
+        .await;
+
+        alert.alerted = true;
+        self.db.set_disk_alert(&alert).await.unwrap();
+    }
+
+    // Reuses an unexpired token for the same version, so repeated checks don't re-announce it.
+    async fn get_pending_update(&self, version: &str) -> PendingUpdate {
+        let now: u64 = Utc::now().timestamp() as u64;
+
+        if let Some(pending) = self.db.get_pending_update() {
+            if pending.version == version && pending.created + PENDING_UPDATE_TTL >= now {
+                return PendingUpdate {
+                    version: pending.version,
+                    token: pending.token,
+                    expires: pending.created + PENDING_UPDATE_TTL,
+                };
+            }
+        }
+
+        let pending: PendingUpdateDB = PendingUpdateDB {
+            token: Uuid::new_v4().to_string(),
+            version: version.to_string(),
+            created: now,
+        };
+
+        self.db.set_pending_update(&pending).await.unwrap();
+
+        info!(
+            "ghostd v{} available, waiting for confirmation to update.",
+            version
+        );
+
+        self.queue_bot_message(
+            "update",
+            "👻 Ghostd Update Available 👻",
+            format!(
+                "ghostd v{} is available.\nConfirm it with the Update ghostd button or 'gv-cli confirmupdate {}'.",
+                version, pending.token
+            ),
+        )
+        .await;
+
+        PendingUpdate {
+            version: pending.version,
+            token: pending.token,
+            expires: now + PENDING_UPDATE_TTL,
+        }
+    }
+
+    // Reuses an unexpired plan for the same outputs, so the scheduled task doesn't ask for the
+    // same confirmation every time it runs.
+    async fn get_pending_split(&self, utxos: Vec<(String, u32, u64)>, pieces: u64) -> PendingSplit {
+        let now: u64 = Utc::now().timestamp() as u64;
+
+        let pending: PendingSplitDB = match self.db.get_pending_split() {
+            Some(pending)
+                if pending.utxos == utxos
+                    && pending.pieces == pieces
+                    && pending.created + PENDING_SPLIT_TTL >= now =>
+            {
+                pending
+            }
+            _ => {
+                let pending: PendingSplitDB = PendingSplitDB {
+                    token: Uuid::new_v4().to_string(),
+                    created: now,
+                    pieces,
+                    utxos,
+                };
+
+                self.db.set_pending_split(&pending).await.unwrap();
+
+                info!(
+                    "{} staking output(s) can be split, waiting for confirmation.",
+                    pending.utxos.len()
+                );
+
+                let outputs: Vec<String> = pending
+                    .utxos
+                    .iter()
+                    .map(|(txid, vout, amount)| {
+                        format!(
+                            "{}:{} {} GHOST",
+                            txid,
+                            vout,
+                            self.daemon.convert_from_sat(*amount)
+                        )
+                    })
+                    .collect();
+
+                if self.announce_active().await {
+                    let tg_queue: TgBotQueueDB = TgBotQueueDB {
+                        timestamp: now,
+                        header: "👻 Staking Outputs Ready To Split 👻".to_string(),
+                        msg: Some(format!(
+                            "Split each of these outputs into {} pieces?\nConfirm it with the Split button or 'gv-cli confirmsplit {}'.",
+                            pieces, pending.token
+                        )),
+                        code_block: Some(outputs.join("\n")),
+                        url: None,
+                        msg_type: "split".to_string(),
+                        reward_txid: None,
+                        msg_to_delete: None,
+                        action_token: Some(pending.token.clone()),
+                        msg_to_edit: None,
+                    };
+
+                    self.announce(now.to_string().as_bytes(), &tg_queue).await;
+                }
+
+                pending
+            }
+        };
+
+        PendingSplit {
+            token: pending.token,
+            expires: pending.created + PENDING_SPLIT_TTL,
+            pieces: pending.pieces,
+            utxos: pending
+                .utxos
+                .into_iter()
+                .map(|(txid, vout, amount)| SplitUtxo {
+                    txid,
+                    vout,
+                    amount: Amount::from_sat(amount),
+                })
+                .collect(),
+        }
+    }
+
+    // Coldstake script staked by the vault and spendable by the given wallet, for outputs the
+    // vault moves around itself.
+    async fn own_coldstake_script(&self, daemon: &DaemonHelper) -> Result<String, GvError> {
+        let stake_addr: String = self
+            .daemon
+            .get_stake_addr()
+            .await
+            .or_code(ErrorCode::DaemonError, "Error getting stake address")?;
+        let spend_addr: String = daemon
+            .getnewaddress()
+            .await
+            .or_code(ErrorCode::DaemonError, "Error getting spend address")?
+            .as_str()
+            .or_code(ErrorCode::DaemonError, "Malformed getnewaddress response")?
+            .to_string();
+        let script: Value = self
+            .daemon
+            .build_script(&stake_addr, &spend_addr)
+            .await
+            .or_code(ErrorCode::DaemonError, "Error building script")?;
+
+        Ok(script
+            .get("hex")
+            .and_then(|hex| hex.as_str())
+            .or_code(ErrorCode::DaemonError, "Malformed buildscript response")?
+            .to_string())
+    }
+
+    async fn do_create_backup(
+        &self,
+        path: PathBuf,
+    ) -> Result<BackupInfo, Box<dyn std::error::Error + Send + Sync>> {
+        let gv_home: PathBuf = self.gv_config.read().await.gv_home.clone();
+        let wallet_meta: WalletMeta = self.wallet_meta().await;
+        let db: Arc<GVDB> = Arc::clone(&self.db);
+
+        tokio::task::spawn_blocking(move || {
+            backup::create_backup(&gv_home, &db, &wallet_meta, &path)
+        })
+        .await?
+    }
+
+    fn scheduled_task(&self, name: &str) -> Result<Task, GvError> {
+        if !SCHEDULED_TASKS.contains(&name) {
+            return Err(GvError::new(
+                ErrorCode::InvalidArgument,
+                format!(
+                    "Unknown task {}, use one of: {}",
+                    name,
+                    SCHEDULED_TASKS.join(", ")
+                ),
+            ));
+        }
+
+        self.db.get_task(name.as_bytes()).ok_or(GvError::new(
+            ErrorCode::Unavailable,
+            "The task runner hasn't started yet.",
+        ))
+    }
+
+    // The wallet setup stored with backups and migration bundles.
+    async fn wallet_meta(&self) -> WalletMeta {
+        let conf = self.gv_config.read().await;
+        let mut wallet_meta: WalletMeta = WalletMeta {
+            cold_wallet: conf.rpc_wallet.clone(),
+            hot_wallet: conf.rpc_wallet_hot.clone(),
+            ext_pub_key: conf.ext_pub_key.clone(),
+            ext_pub_key_label: conf.ext_pub_key_label.clone(),
+            reward_address: conf.reward_address.clone(),
+            anon_mode: conf.anon_mode,
+            anon_reward_address: conf.anon_reward_address.clone(),
+            wallet_info: None,
+        };
+        drop(conf);
+
+        wallet_meta.wallet_info = self.daemon.get_wallet_info().await.ok();
+
+        wallet_meta
+    }
+
+    // Push a backup to the configured remote and prune old ones there, None if no remote is set.
+    async fn do_remote_backup(
+        &self,
+        info: &BackupInfo,
+    ) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let conf: GVConfig = self.gv_config.read().await.clone();
+
+        if conf.backup_remote_url.is_none() {
+            return Ok(None);
+        }
+
+        let remote: String =
+            remote_backup::upload_backup(&conf, &PathBuf::from(&info.path)).await?;
+        info!("Backup uploaded to {}", remote);
+
+        match remote_backup::prune_remote_backups(&conf).await {
+            Ok(removed) if removed > 0 => info!("Removed {} old remote backups", removed),
+            Ok(_) => (),
+            Err(err) => error!("Error pruning remote backups: {}", err),
+        }
+
+        Ok(Some(remote))
+    }
+
+    // The daemon for the wallet a UTXO command names, the hot wallet has to be loaded.
+    async fn utxo_wallet(&self, wallet: &str) -> Result<DaemonHelper, GvError> {
+        match wallet {
+            "cold" => Ok(DaemonHelper::new(&self.gv_config, "cold").await),
+            "hot" => {
+                let hot_wallet: String = self.gv_config.read().await.rpc_wallet_hot.clone();
+
+                if !self.hot_wallet_loaded(&hot_wallet).await {
+                    return Err(GvError::new(
+                        ErrorCode::NotFound,
+                        format!("Hot wallet {} is not loaded!", hot_wallet),
+                    ));
+                }
+
+                Ok(DaemonHelper::new(&self.gv_config, "hot").await)
+            }
+            _ => Err(GvError::new(
+                ErrorCode::InvalidArgument,
+                format!("Invalid wallet: {}, use cold or hot.", wallet),
+            )),
+        }
+    }
+
+    // Wallet locks don't survive a ghostd restart, so frozen outputs are locked again before
+    // anything spends from the wallet.
+    async fn relock_frozen_utxos(&self, daemon: &DaemonHelper, wallet: &str) {
+        let frozen: Vec<FrozenUtxoDB> = self.db.get_frozen_utxos(wallet);
+
+        if frozen.is_empty() {
+            return;
+        }
+
+        let locked: Vec<(String, u32)> = match daemon.list_lock_unspent().await {
+            Ok(locked) => locked,
+            Err(err) => {
+                warn!("Unable to list locked outputs: {}", err);
+                return;
+            }
+        };
+
+        for utxo in frozen {
+            let output: (String, u32) = (utxo.txid, utxo.vout);
+
+            if locked.contains(&output) {
+                continue;
+            }
+
+            // Fails once the output has been spent some other way, which is fine to leave be
+            if let Err(err) = daemon
+                .lock_unspent(false, std::slice::from_ref(&output))
+                .await
+            {
+                warn!("Unable to relock {}:{}: {}", output.0, output.1, err);
+            }
+        }
+    }
+
+    async fn watch_only(&self) -> bool {
+        self.gv_config.read().await.watch_only
+    }
+
+    // Anything that spends or needs the wallet's private keys is off on a watch-only vault.
+    async fn require_wallet_control(&self) -> Result<(), GvError> {
+        if self.watch_only().await {
+            return Err(GvError::new(
+                ErrorCode::Disabled,
+                "This vault is watch-only and has no spending keys.",
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Locked and staking only wallets can stake but can't send payouts or convert to anon, they
+    // need a full unlock. A locked wallet is reported by check_wallet_locked, a staking only
+    // unlock holds the payouts back and says so once.
+    async fn wallet_can_spend(&self) -> bool {
+        match self.daemon.get_encryption_status().await {
+            Ok(status) if status == "Unencrypted" || status == "Unlocked" => true,
+            Ok(status) => {
+                if status != "Locked" {
+                    self.defer_payout(STAKING_ONLY_UNLOCK.to_string()).await;
+                }
+                false
+            }
+            Err(_) => false,
+        }
+    }
+
+    async fn check_wallet_locked(&self) {
+        let status: String = match self.daemon.get_encryption_status().await {
+            Ok(status) => status,
+            Err(_) => return,
+        };
+
+        if status != "Locked" {
+            return;
+        }
+
+        warn!(
+            "Wallet is locked, staking is paused until it is unlocked with 'gv-cli unlockwallet'"
+        );
+
+        self.queue_bot_message(
+            "incident",
+            "👻 Wallet Locked! 👻",
+            "The wallet is encrypted and locked, staking is paused.\nUse Unlock Wallet in GhostVault Options to unlock it for staking.".to_string(),
+        )
+        .await;
+    }
+
+    async fn hot_wallet_loaded(&self, wallet: &str) -> bool {
+        match self.daemon.list_wallets().await {
+            Ok(loaded_wallets) => loaded_wallets
+                .as_array()
+                .is_some_and(|wallets| wallets.contains(&Value::String(wallet.to_string()))),
+            Err(_) => false,
+        }
+    }
+
+    async fn get_hot_wallet_data(&self) -> HotWalletInfo {
+        let conf = self.gv_config.read().await;
+        let wallet: String = conf.rpc_wallet_hot.clone();
+        drop(conf);
+
+        let mut hot_wallet_info: HotWalletInfo = HotWalletInfo {
+            wallet,
+            loaded: false,
+            spendable: 0.0,
+            delegated: 0.0,
+        };
+
+        if !self.hot_wallet_loaded(&hot_wallet_info.wallet).await {
+            return hot_wallet_info;
+        }
+
+        hot_wallet_info.loaded = true;
+
+        let hot_daemon: DaemonHelper = DaemonHelper::new(&self.gv_config, "hot").await;
+
+        if let Ok(balances) = hot_daemon.get_balances().await {
+            hot_wallet_info.spendable = balances["mine"]["trusted"].as_f64().unwrap_or(0.0);
+        }
+
+        // On the spending side this is the coin handed to the cold wallet for staking.
+        if let Ok(cs_info) = hot_daemon.getcoldstakinginfo().await {
+            hot_wallet_info.delegated = cs_info["coin_in_coldstakeable_script"]
+                .as_f64()
+                .unwrap_or(0.0);
+        }
+
+        hot_wallet_info
+    }
+
+    // One of the wallet's own getbalances entries, like trusted or anon_trusted.
+    async fn mine_balance(&self, key: &str) -> Result<f64, GvError> {
+        let balances: Value = self
+            .daemon
+            .get_balances()
+            .await
+            .or_code(ErrorCode::DaemonError, "Error getting balances")?;
+        let my_balances: Value = reply_field(&balances, "mine", |mine| Some(mine.clone()))?;
+
+        reply_field(&my_balances, key, Value::as_f64)
+    }
+
+    // Why a payout shouldn't go out yet, None when it can. On a minority fork the vault may end
+    // up paying with coins it doesn't have. Inputs a reorg could still take back aren't a reason,
+    // they're left out of the payout and go in a later one.
+    async fn payout_deferral(&self) -> Option<String> {
+        if !self.good_chain().await {
+            return Some("ghostd isn't on the same chain as the explorers.".to_string());
+        }
+
+        None
+    }
+
+    // Only the first deferral is announced, the next one after a payout has gone out again.
+    async fn defer_payout(&self, reason: String) {
+        warn!("Deferring reward payout: {}", reason);
+
+        if self
+            .set_payout_deferred(Some(reason.clone()))
+            .await
+            .is_none()
+        {
+            self.queue_bot_message(
+                "payout_deferred",
+                "👻 Payout Deferred 👻",
+                format!("{}\nIt will go out once that clears.", reason),
+            )
+            .await;
+        }
+    }
+
+    // A payout round recorded as a job, returns how it went.
+    async fn payout_round(&self) -> String {
+        let job: JobGuard = self.jobs.start("payout").await;
+        let started: u64 = chrono::Utc::now().timestamp() as u64;
+
+        self.do_reward_payout().await;
+
+        let finished: u64 = chrono::Utc::now().timestamp() as u64;
+        let paid: PayoutHistory = self.get_payout_history_vec(started, finished).await;
+
+        let outcome: String = if paid.payout_count == 0 {
+            match self.payout_deferred().await {
+                Some(reason) => format!("Deferred, {}", reason),
+                None => "Nothing paid out".to_string(),
+            }
+        } else {
+            format!(
+                "Paid out {} GHOST in {} transaction(s)",
+                paid.total_paid, paid.payout_count
+            )
+        };
+        job.finish(Ok(Some(outcome.clone()))).await;
+
+        outcome
+    }
+
+    async fn do_reward_payout(&self) {
+        if self.watch_only().await {
+            info!("Watch-only vault, skipping reward payout.");
+            return;
+        }
+
+        if !self.wallet_can_spend().await {
+            info!("Wallet is locked for spending, skipping reward payout.");
+            return;
+        }
+
+        if self.in_chain_emergency().await {
+            info!("Chain split emergency active, skipping reward payout.");
+            return;
+        }
+
+        let daemon_ready: bool = self.daemon_ready().await;
+        let current_time = chrono::Utc::now();
+        let timestamp: u64 = current_time.timestamp() as u64;
+
+        if daemon_ready {
+            self.relock_frozen_utxos(&self.daemon, "cold").await;
+
+            let conf = self.gv_config.read().await;
+
+            // With ANON_FLUSH off the rewards are paid straight from the public balance
+            let (in_type, balance_key, rewards_label) = if conf.anon_flush {
+                ("anon", "anon_trusted", "Anon rewards")
+            } else {
+                ("ghost", "trusted", "Rewards")
+            };
+
+            let trusted_anon: f64 = match self.mine_balance(balance_key).await {
+                Ok(balance) => balance,
+                Err(err) => {
+                    error!("Error getting the rewards balance: {}", err);
+                    return;
+                }
+            };
+
+            let min_payout: f64 = self.daemon.convert_from_sat(conf.min_reward_payout);
+
+            if trusted_anon >= min_payout {
+                if let Some(reason) = self.payout_deferral().await {
+                    self.defer_payout(reason).await;
+                    return;
+                }
+                self.set_payout_deferred(None).await;
+
+                // Only inputs past PAYOUT_SAFE_DEPTH are paid out, newer ones wait for a later round
+                let trusted_anon: f64 = match self
+                    .daemon
+                    .unspent_balance(in_type, conf.payout_safe_depth)
+                    .await
+                {
+                    Ok(balance) => balance,
+                    Err(err) => {
+                        error!("Error checking the payout inputs: {}", err);
+                        return;
+                    }
+                };
+
+                if trusted_anon < min_payout {
+                    info!(
+                        "Only {} GHOST is past {} confirmations, below the payout minimum.",
+                        trusted_anon, conf.payout_safe_depth
+                    );
+                    return;
+                }
+
+                let fees: FeeSettings = conf.fee_settings();
+                let fee_rate: u64 = self.daemon.fee_rate(&fees).await;
+
+                self.credit_tenant_stakes(conf.anon_mode).await;
+
+                // Tenants sharing the vault get their part first, the rest is the vault's own
+                let trusted_anon: f64 = if conf.anon_mode {
+                    self.pay_tenants(trusted_anon, in_type, conf.min_reward_payout, fee_rate)
+                        .await
+                } else {
+                    trusted_anon
+                };
+
+                let addr_option: Option<String> = conf.anon_reward_address.clone();
+                let label: String = conf.payout_label.clone().unwrap_or_default();
+
+                if let Some(addr) = addr_option {
+                    let addr_info: Value = match self.daemon.get_address_info(&addr).await {
+                        Ok(addr_info) => addr_info,
+                        Err(err) => {
+                            error!("Error getting the reward address info: {}", err);
+                            return;
+                        }
+                    };
+                    let is_stealth: bool = addr_info
+                        .get("isstealthaddress")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false);
+
+                    let out_type: &str = if is_stealth { "anon" } else { "ghost" };
+
+                    let is_256bit: bool = addr_info
+                        .get("is256bit")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false);
+
+                    if is_256bit {
+                        let txids_res = self
+                            .daemon
+                            .zap_ghost(
+                                &addr,
+                                in_type,
+                                fee_rate,
+                                fees.max_fee,
+                                &label,
+                                conf.payout_safe_depth,
+                            )
+                            .await;
+
+                        let txids = match txids_res {
+                            Ok(txids) => txids,
+                            Err(err) => {
+                                error!("Error zapping to 256bit address: {}", err);
+                                return;
+                            }
+                        };
+
+                        let default_txid: Vec<Value> = Vec::new();
+                        let txid_vec = txids.as_array().unwrap_or(&default_txid);
+
+                        if txid_vec.is_empty() {
+                            return;
+                        }
+
+                        for txid in txid_vec.iter().filter_map(Value::as_str) {
+                            info!("Zap to public address: {}", txid);
+                        }
+
+                        self.record_payouts(txid_vec, &addr, "zap", fee_rate, &label)
+                            .await;
+
+                        if self.announce_active().await {
+                            let header = format!("👻 Rewards coming your way! 👻");
+
+                            let msg = Some(format!(
+                                "{} in the amount of {} GHOST being zapped to PUBLIC address.",
+                                rewards_label, trusted_anon
+                            ));
+
+                            let url = Some(
+                                txid_vec
+                                    .iter()
+                                    .filter_map(Value::as_str)
+                                    .map(|txid| ExplorerProvider::new(&conf).tx_url(txid))
+                                    .collect(),
+                            );
+
+                            let msg_type = "rewards".to_string();
+
+                            let tg_queue: TgBotQueueDB = TgBotQueueDB {
+                                timestamp,
+                                header,
+                                msg,
+                                code_block: None,
+                                url,
+                                msg_type,
+                                reward_txid: None,
+                                msg_to_delete: None,
+                                action_token: None,
+                                msg_to_edit: None,
+                            };
+                            let txid: String = txid_vec
+                                .iter()
+                                .find_map(Value::as_str)
+                                .unwrap_or_default()
+                                .to_string();
+                            self.announce_templated(
+                                txid.as_bytes(),
+                                tg_queue,
+                                TemplateValues {
+                                    amount: trusted_anon.to_string(),
+                                    txid: txid.clone(),
+                                    ..Default::default()
+                                },
+                            )
+                            .await;
+                        }
+                    } else {
+                        let txids_res = self
+                            .daemon
+                            .send_ghost(
+                                &addr,
+                                in_type,
+                                out_type,
+                                fee_rate,
+                                fees.max_fee,
+                                &label,
+                                conf.payout_safe_depth,
+                            )
+                            .await;
+
+                        let txids = match txids_res {
+                            Ok(txids) => txids,
+                            Err(err) => {
+                                error!("Error sending to address: {}", err);
+                                return;
+                            }
+                        };
+
+                        let default_txid: Vec<Value> = Vec::new();
+                        let txid_vec = txids.as_array().unwrap_or(&default_txid);
+
+                        if txid_vec.is_empty() {
+                            return;
+                        }
+
+                        for txid in txid_vec.iter().filter_map(Value::as_str) {
+                            info!("Payout to {} address: {}", out_type.to_uppercase(), txid);
+                        }
+
+                        self.record_payouts(txid_vec, &addr, out_type, fee_rate, &label)
+                            .await;
+
+                        if self.announce_active().await {
+                            let header = format!("👻 Rewards coming your way! 👻");
+
+                            let msg = Some(format!(
+                                "{} in the amount of {} GHOST being sent to {} address.",
+                                rewards_label,
+                                trusted_anon,
+                                out_type.to_uppercase()
+                            ));
+
+                            let url = Some(
+                                txid_vec
+                                    .iter()
+                                    .filter_map(Value::as_str)
+                                    .map(|txid| ExplorerProvider::new(&conf).tx_url(txid))
+                                    .collect(),
+                            );
+
+                            let msg_type = "rewards".to_string();
+
+                            let tg_queue: TgBotQueueDB = TgBotQueueDB {
+                                timestamp,
+                                header,
+                                msg,
+                                code_block: None,
+                                url,
+                                msg_type,
+                                reward_txid: None,
+                                msg_to_delete: None,
+                                action_token: None,
+                                msg_to_edit: None,
+                            };
+
+                            let txid: String = txid_vec
+                                .iter()
+                                .find_map(Value::as_str)
+                                .unwrap_or_default()
+                                .to_string();
+
+                            self.announce_templated(
+                                txid.as_bytes(),
+                                tg_queue,
+                                TemplateValues {
+                                    amount: trusted_anon.to_string(),
+                                    txid: txid.clone(),
+                                    ..Default::default()
+                                },
+                            )
+                            .await;
+                        }
+                    }
+                }
+            }
+            drop(conf);
+        }
+    }
+
+    // The new_block pipeline. A replayed block is behind the tip the daemon status already points
+    // at, so only the side effects that hang off each block are run for it.
+    async fn process_new_block(&self, new_block: &str, replay: bool) {
+        if replay {
+            if self.daemon_ready().await {
+                self.process_block_effects().await;
+            }
+            return;
+        }
+
+        DaemonHelper::invalidate_cache();
+        let block_height: u32 = match self.daemon.getblock(new_block, 1).await {
+            Ok(block_value) => match reply_field(&block_value, "height", Value::as_u64) {
+                Ok(height) => height as u32,
+                Err(err) => {
+                    error!("Skipping block {}: {}", new_block, err);
+                    return;
+                }
+            },
+            Err(err) => {
+                error!("Error getting block {}: {}", new_block, err);
+                return;
+            }
+        };
+        let cycle: u32 = self.cycle().await + 1;
+
+        let new_status: DaemonStatusDB = DaemonStatusDB {
+            height: block_height,
+            block_hash: new_block.to_string(),
+        };
+
+        let synced: bool = match self.daemon.is_syncing().await {
+            Ok(syncing) => !syncing,
+            Err(err) => {
+                warn!("Unable to check if ghostd is syncing: {}", err);
+                false
+            }
+        };
+
+        if let Err(err) = self.db.set_daemon_status(&new_status).await {
+            error!("Error saving the daemon status: {}", err);
+        }
+
+        if self.daemon_ready().await {
+            self.process_block_effects().await;
+        }
+
+        self.set_best_block(block_height).await;
+        self.set_best_block_hash(new_block).await;
+        self.set_daemon_synced(synced).await;
+        self.set_cycle(cycle).await;
+    }
+
+    // Zap and stake confirmations, expected deposits and scheduled reward changes move on with
+    // every block.
+    async fn process_block_effects(&self) {
+        let _ = self.process_zap_status().await;
+        self.check_expected_deposits().await;
+        let _ = self.process_rewards_status().await;
+        self.process_reward_change().await;
+    }
+
+    // Feed the blocks ghostd found while GhostVault was down through the new_block pipeline,
+    // oldest first, so confirmations and notifications tied to them aren't skipped. Only the
+    // last BLOCK_REPLAY_LIMIT are replayed after a long outage.
+    async fn replay_missed_blocks(&self, last_height: u32, tip: u32) {
+        while !self.db.get_server_ready().is_some_and(|ready| ready.ready) {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+
+        let start: u32 = last_height.max(tip.saturating_sub(BLOCK_REPLAY_LIMIT)) + 1;
+
+        if start > last_height + 1 {
+            info!(
+                "{} blocks were missed, replaying the last {}",
+                tip - last_height,
+                BLOCK_REPLAY_LIMIT
+            );
+        }
+
+        info!("Replaying blocks {} to {}...", start, tip);
+
+        for height in start..=tip {
+            let block_hash: String = match self.daemon.getblockhash(height).await {
+                Ok(block_hash) => block_hash,
+                Err(err) => {
+                    error!("Block replay stopped at {}: {}", height, err);
+                    return;
+                }
+            };
+
+            debug!("Replaying block {} ({})", height, block_hash);
+            self.process_new_block(&block_hash, true).await;
+        }
+
+        info!("Replayed {} missed blocks.", tip - start + 1);
+    }
+
+    async fn process_rewards_status(&self) {
+        let stake_confirmations: u64 = self.gv_config.read().await.stake_confirmations;
+
+        for result in self.db.new_stake_status.iter() {
+            match result {
+                Ok((key, value)) => {
+                    let mut stake_status: NewStakeStatusDB = match serde_json::from_slice(&value) {
+                        Ok(stake_status) => stake_status,
+                        Err(err) => {
+                            error!("Unreadable stake status: {}", err);
+                            continue;
+                        }
+                    };
+                    let txid: String = stake_status.txid.clone();
+
+                    let tx_details: Value = match self.daemon.get_transaction(&txid).await {
+                        Ok(tx_details) => tx_details,
+                        Err(_) => {
+                            self.forget_stake(&key, &stake_status).await;
+                            continue;
+                        }
+                    };
+
+                    let is_stake: bool = tx_details
+                        .get("details")
+                        .and_then(Value::as_array)
+                        .and_then(|details| details.first())
+                        .and_then(|output| output.get("category"))
+                        .and_then(Value::as_str)
+                        == Some("stake");
+
+                    if !is_stake {
+                        self.forget_stake(&key, &stake_status).await;
+
+                        self.queue_stake_update(
+                            &stake_status,
+                            "stake_removal",
+                            "👻 Stake Orphaned 👻",
+                            "The block was orphaned, this stake won't pay a reward.".to_string(),
+                        )
+                        .await;
+
+                        continue;
+                    }
+
+                    let confirms: u64 = tx_details
+                        .get("confirmations")
+                        .and_then(Value::as_u64)
+                        .unwrap_or(0);
+
+                    // Only every STAKE_MESSAGE_STEP confirmations, an edit per block is a lot of
+                    // calls to Telegram for a busy staker.
+                    let step: u64 = STAKE_MESSAGE_STEP;
+                    let stepped: bool = confirms / step != stake_status.confirmations as u64 / step;
+
+                    stake_status.confirmations = confirms as u32;
+
+                    if confirms > stake_confirmations {
+                        self.announce_stake_matured(&stake_status).await;
+                        self.do_flush_rewards_to_anon().await;
+                        if let Err(err) = self.db.remove_new_stake_status(&key).await {
+                            error!("Error removing the stake status of {}: {}", txid, err);
+                        }
+                    } else {
+                        if stepped && stake_status.tg_msg_id.is_some() {
+                            self.queue_stake_update(
+                                &stake_status,
+                                "stake_confirmations",
+                                "👻 New Block Found! 👻",
+                                stake_progress(confirms, stake_confirmations),
+                            )
+                            .await;
+                        }
+
+                        if let Err(err) = self.db.set_new_stake_status(&key, &stake_status).await {
+                            error!("Error saving the stake status of {}: {}", txid, err);
+                        }
+                    }
+                }
+                Err(err) => {
+                    eprintln!("Error during iteration: {:?}", err);
+                }
+            }
+        }
+    }
+
+    // A stake that's gone from the wallet or no longer a stake, like after a reorg, and its reward.
+    async fn forget_stake(&self, key: &[u8], stake_status: &NewStakeStatusDB) {
+        if let Err(err) = self.db.remove_new_stake_status(key).await {
+            error!(
+                "Error removing the stake status of {}: {}",
+                stake_status.txid, err
+            );
+        }
+
+        if let Err(err) = self
+            .db
+            .remove_reward(stake_status.timestamp.to_be_bytes())
+            .await
+        {
+            error!(
+                "Error removing the reward of {}: {}",
+                stake_status.txid, err
+            );
+        }
+    }
+
+    // The reward of a stake can be spent from here on.
+    async fn announce_stake_matured(&self, stake_status: &NewStakeStatusDB) {
+        let msg: String = match self.db.get_reward(stake_status.timestamp.to_be_bytes()) {
+            Some(reward) => format!(
+                "The {} GHOST reward from the stake in block {} is now spendable.",
+                self.daemon
+                    .convert_from_sat(reward.reward + reward.agvr_reward),
+                reward.height
+            ),
+            None => format!(
+                "The reward from stake {} is now spendable.",
+                stake_status.txid
+            ),
+        };
+
+        self.queue_stake_update(stake_status, "stake_matured", "👻 Stake Matured! 👻", msg)
+            .await;
+    }
+
+    // Goes into the stake's first message in place of its text, so a stake keeps one message
+    // from being found to maturing or being orphaned.
+    async fn queue_stake_update(
+        &self,
+        stake_status: &NewStakeStatusDB,
+        msg_type: &str,
+        header: &str,
+        msg: String,
+    ) {
+        if !self.announce_active().await {
+            return;
+        }
+
+        let url: Option<Vec<String>> = Some(vec![self.explorer().await.tx_url(&stake_status.txid)]);
+
+        let tg_queue: TgBotQueueDB = TgBotQueueDB {
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            header: header.to_string(),
+            msg: Some(msg),
+            code_block: stake_status.summary.clone(),
+            url,
+            msg_type: msg_type.to_string(),
+            reward_txid: Some(stake_status.txid.clone()),
+            msg_to_delete: None,
+            action_token: None,
+            msg_to_edit: stake_status.tg_msg_id,
+        };
+
+        // Keyed by stake and kind, a newer count replaces one the bot hasn't sent yet
+        let key: String = format!("{}:{}", stake_status.txid, msg_type);
+        self.announce(key.as_bytes(), &tg_queue).await;
+    }
+
+    async fn process_zap_status(&self) {
+        let zap_confirmations: i64 = self.gv_config.read().await.zap_confirmations as i64;
+
+        for result in self.db.zap_status_db.iter() {
+            match result {
+                Ok((key, value)) => {
+                    let mut zap_status: ZapStatusDB = match serde_json::from_slice(&value) {
+                        Ok(zap_status) => zap_status,
+                        Err(err) => {
+                            error!("Unreadable zap status: {}", err);
+                            continue;
+                        }
+                    };
+                    let txid: String = zap_status.txid.clone();
+                    let tx_details: Value = match self.daemon.get_transaction(&txid).await {
+                        Ok(tx_details) => tx_details,
+                        Err(err) => {
+                            error!("Error getting zap {}: {}", txid, err);
+                            continue;
+                        }
+                    };
+                    let confirms: i64 = tx_details
+                        .get("confirmations")
+                        .and_then(Value::as_i64)
+                        .unwrap_or(0);
+
+                    if confirms < 0 {
+                        if let Err(err) = self.db.remove_zap_status(&key).await {
+                            error!("Error removing the zap status of {}: {}", txid, err);
+                        }
+                        continue;
+                    }
+
+                    let current_time = chrono::Utc::now();
+                    let timestamp: u64 = current_time.timestamp() as u64;
+
+                    if confirms >= zap_confirmations {
+                        if self.announce_active().await {
+                            let header = format!("👻 Zap Now Staking! 👻");
+                            let msg = Some(format!(
+                                "The deposit of {} GHOST in your GhostVault is now staking!",
+                                self.daemon.convert_from_sat(zap_status.amount)
+                            ));
+
+                            let url = None;
+                            let msg_type = "zap".to_string();
+
+                            let tg_queue: TgBotQueueDB = TgBotQueueDB {
+                                timestamp,
+                                header,
+                                msg,
+                                code_block: None,
+                                url,
+                                msg_type,
+                                reward_txid: None,
+                                msg_to_delete: None,
+                                action_token: None,
+                                msg_to_edit: None,
+                            };
+
+                            let in_tg_queue: Option<TgBotQueueDB> = self.db.get_tg_bot_queue(&key);
+                            if in_tg_queue.is_none() {
+                                self.announce_templated(
+                                    &key,
+                                    tg_queue,
+                                    TemplateValues {
+                                        amount: self
+                                            .daemon
+                                            .convert_from_sat(zap_status.amount)
+                                            .to_string(),
+                                        txid: txid.clone(),
+                                        ..Default::default()
+                                    },
+                                )
+                                .await;
+                            }
+                        }
+                        if let Err(err) = self.db.remove_zap_status(&key).await {
+                            error!("Error removing the zap status of {}: {}", txid, err);
+                        }
+                    } else {
+                        zap_status.confirmations = confirms as u32;
+                        if self.announce_active().await {
+                            let amount = self.daemon.convert_from_sat(zap_status.amount);
+
+                            let in_msg_que = self.db.get_tg_bot_queue(&key).is_some();
+
+                            let header = format!("👻 New Zap Detected! 👻");
+
+                            let msg = Some(format!(
+                                "New deposit of {} GHOST is in your GhostVault!",
+                                amount
+                            ));
+
+                            let url = Some(vec![self.explorer().await.tx_url(&txid)]);
+
+                            let msg_type = "zap".to_string();
+
+                            let tg_queue: TgBotQueueDB = TgBotQueueDB {
+                                timestamp,
+                                header,
+                                msg,
+                                code_block: None,
+                                url,
+                                msg_type,
+                                reward_txid: None,
+                                msg_to_delete: None,
+                                action_token: None,
+                                msg_to_edit: None,
+                            };
+
+                            if !zap_status.first_notice && !in_msg_que {
+                                self.announce_templated(
+                                    txid.as_bytes(),
+                                    tg_queue,
+                                    TemplateValues {
+                                        amount: amount.to_string(),
+                                        txid: txid.clone(),
+                                        ..Default::default()
+                                    },
+                                )
+                                .await;
+                                zap_status.first_notice = true;
+                            }
+                        }
+
+                        if let Err(err) = self.db.set_zap_status(&key, &zap_status).await {
+                            error!("Error saving the zap status of {}: {}", txid, err);
+                        }
+                    }
+                }
+                Err(err) => {
+                    eprintln!("Error during iteration: {:?}", err);
+                }
+            }
+        }
+    }
+}
+
+impl GvCLI for GvCLIServer {
+    async fn getblockcount(self, _: context::Context) -> GvReply<u64> {
+        guarded("getblockcount", async move {
+            let blocks: u64 = self
+                .daemon
+                .getblockcount()
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting block count")?
+                .as_u64()
+                .or_code(ErrorCode::DaemonError, "Malformed daemon response")?;
+            Ok(response::ok(blocks))
+        })
+        .await
+    }
+
+    async fn shutdown(self, _: context::Context) -> GvReply<String> {
+        let audit: Audit = self.audit("shutdown", serde_json::json!({}));
+        audited(audit, async move {
+            let conf = self.gv_config.read().await;
+            let gv_data_dir = conf.gv_home.clone();
+            drop(conf);
+            let pid_file: PathBuf = gv_data_dir.join(GV_PID_FILE);
+            file_ops::rm_file(&pid_file).or_code(ErrorCode::Internal, "Error removing pid file")?;
+
+            let is_docker: bool = env::vars().any(|(key, _)| key == "DOCKER_RUNNING");
+
+            if is_docker {
+                let _ = self.daemon.stop_daemon().await;
+            }
+
+            tokio::spawn(async move {
+                do_shutdown().await;
+            });
+            Ok(response::message("GhostVault going down for shutdown..."))
+        })
+        .await
+    }
+
+    async fn get_daemon_state(self, _: context::Context) -> GvReply<GVStatus> {
+        guarded("get_daemon_state", async move {
+            let status = self
+                .get_gv_status(&self.vault_tz().await)
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting daemon state")?;
+            Ok(response::ok(status))
+        })
+        .await
+    }
+
+    async fn get_daemon_state_tz(self, _: context::Context, timezone: String) -> GvReply<GVStatus> {
+        guarded("get_daemon_state_tz", async move {
+            let tz: Tz = parse_timezone(&timezone)?;
+
+            let status = self
+                .get_gv_status(&tz)
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting daemon state")?;
+            Ok(response::ok(status))
+        })
+        .await
+    }
+
+    async fn get_ext_pub_key(self, _: context::Context) -> GvReply<String> {
+        guarded("get_ext_pub_key", async move {
+            let mut conf = self.gv_config.write().await;
+
+            if let Some(ext_pub_key) = conf.ext_pub_key.clone() {
+                return Ok(response::ok(ext_pub_key));
+            }
+
+            let ext_pub_key = self
+                .daemon
+                .getnewextaddress()
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting ext pub key")?
+                .as_str()
+                .or_code(ErrorCode::DaemonError, "Malformed daemon response")?
+                .to_string();
+            conf.update_gv_config("ext_pub_key", &ext_pub_key)
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+
+            Ok(response::ok(ext_pub_key))
+        })
+        .await
+    }
+
+    async fn enable_telegram_bot(
+        self,
+        _: context::Context,
+        token: String,
+        user: String,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit("enable_telegram_bot", serde_json::json!({ "user": &user }));
+        audited(audit, async move {
+            let mut conf = self.gv_config.write().await;
+
+            let plausible_userid = user.parse::<u64>();
+
+            if plausible_userid.is_err() {
+                return Err(GvError::new(ErrorCode::InvalidArgument, "Invalid user ID!"));
+            }
+
+            let token_is_valid: bool = gv_methods::validate_bot_token(&token)
+                .await
+                .or_code(ErrorCode::Unavailable, "Error validating bot token")?;
+
+            if !token_is_valid {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Invalid bot token!",
+                ));
+            }
+
+            conf.update_gv_config("TELOXIDE_TOKEN", &token)
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+            conf.update_gv_config("TELEGRAM_USER", &user)
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+            Ok(response::message("Telegram bot enabled!"))
+        })
+        .await
+    }
+
+    async fn disable_telegram_bot(self, _: context::Context) -> GvReply<String> {
+        let audit: Audit = self.audit("disable_telegram_bot", serde_json::json!({}));
+        audited(audit, async move {
+            let mut conf = self.gv_config.write().await;
+            conf.update_gv_config("TELOXIDE_TOKEN", "")
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+            conf.update_gv_config("TELEGRAM_USER", "")
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+            Ok(response::message("Telegram bot disabled!"))
+        })
+        .await
+    }
+
+    async fn set_reward_interval(self, _: context::Context, interval: String) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_reward_interval",
+            serde_json::json!({ "interval": &interval }),
+        );
+        audited(audit, async move {
+            let second: i64 = 1;
+            let minute: i64 = 60 * second;
+            let hour: i64 = 60 * minute;
+            let day: i64 = 24 * hour;
+            let week: i64 = 7 * day;
+            let month: i64 = 30 * day;
+            let year: i64 = 365 * day;
+
+            let multiplier: char = interval
+                .chars()
+                .last()
+                .or_code(ErrorCode::InvalidArgument, "Invalid interval!")?;
+
+            let striped_str: String = interval.replace(multiplier, "");
+
+            let timeframe: i64 = striped_str
+                .parse::<i64>()
+                .or_code(ErrorCode::InvalidArgument, "Invalid interval!")?;
+
+            let interval: i64 = match multiplier {
+                's' => timeframe * second,
+                'm' => timeframe * minute,
+                'h' => timeframe * hour,
+                'd' => timeframe * day,
+                'w' => timeframe * week,
+                'M' => timeframe * month,
+                'y' => timeframe * year,
+                _ => {
+                    return Err(GvError::new(
+                        ErrorCode::InvalidArgument,
+                        "Invalid interval!",
+                    ))
+                }
+            };
+            let mut conf = self.gv_config.write().await;
+            conf.update_gv_config("reward_interval", &interval.to_string())
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+
+            task_runner::update_payout_interval(&self.db, interval)
+                .await
+                .or_code(ErrorCode::Internal, "Error updating payout task")?;
+
+            Ok(response::message("Reward interval updated!"))
+        })
+        .await
+    }
+
+    async fn set_payout_min(self, _: context::Context, min: f64) -> GvReply<String> {
+        let audit: Audit = self.audit("set_payout_min", serde_json::json!({ "min": min }));
+        audited(audit, async move {
+            let mut conf = self.gv_config.write().await;
+            let min_int: u64 = self.daemon.convert_to_sat(min);
+
+            if min_int < MIN_TX_VALUE {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Minimum payout too low!",
+                ));
+            }
+
+            conf.update_gv_config("min_reward_payout", &min_int.to_string())
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+
+            task_runner::update_payout_min(&self.db, min_int)
+                .await
+                .or_code(ErrorCode::Internal, "Error updating payout task")?;
+
+            Ok(response::message("Minimum payout updated!"))
+        })
+        .await
+    }
+
+    async fn set_reward_mode(
+        self,
+        _: context::Context,
+        mode: String,
+        addr: Option<String>,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_reward_mode",
+            serde_json::json!({ "mode": &mode, "addr": &addr }),
+        );
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+
+            let mode: String = mode.to_uppercase();
+
+            if !["ANON", "STANDARD", "DEFAULT"].contains(&mode.as_str()) {
+                return Err(GvError::new(ErrorCode::InvalidArgument, "Invalid mode!"));
+            }
+
+            let conf = self.gv_config.read().await;
+            let whitelist: Vec<String> = conf.reward_address_whitelist.clone();
+            let delay: u64 = conf.reward_change_delay;
+            drop(conf);
+
+            // DEFAULT sends rewards back to the staking address, so it isn't held back.
+            if delay > 0 && mode != "DEFAULT" {
+                let addr: String = self
+                    .check_reward_address(&whitelist, &mode, addr.as_ref())
+                    .await?;
+                return self.schedule_reward_change(mode, addr, delay).await;
+            }
+
+            self.apply_reward_mode(&mode, addr).await
+        })
+        .await
+    }
+
+    async fn new_block(self, _: context::Context, new_block: String) {
+        isolated("new_block", async move {
+            if new_block != self.best_block_hash().await {
+                info!("New block from daemon: {new_block}");
+                self.process_new_block(&new_block, false).await;
+            }
+        })
+        .await
+    }
+
+    async fn new_remote_block(self, _: context::Context, block_hash: String, height: u32) {
+        isolated("new_remote_block", async move {
+            if block_hash != self.remote_best_block_hash().await {
+                info!("New block from remote: {block_hash}");
+
+                self.set_remote_best_block(height).await;
+                self.set_remote_best_block_hash(&block_hash).await;
+            }
+        })
+        .await
+    }
+
+    async fn new_wallet_tx(self, _: context::Context, txid_and_wal: TxidAndWallet) {
+        isolated("new_wallet_tx", async move {
+            let txid: String = txid_and_wal.txid;
+            let wallet: String = txid_and_wal.wallet;
+
+            let cold_wallet: Option<DaemonHelper> = self.cold_wallet_daemon(&wallet).await;
+            let conf = self.gv_config.read().await;
+
+            if let Some(daemon) = cold_wallet {
+                let tx_details: Value = match daemon.get_transaction(&txid).await {
+                    Ok(tx_details) => tx_details,
+                    Err(err) => {
+                        error!("Error getting wallet tx {}: {}", txid, err);
+                        return;
+                    }
+                };
+                let tx_io: &[Value] = tx_details
+                    .get("details")
+                    .and_then(Value::as_array)
+                    .map_or(&[], Vec::as_slice);
+
+                if tx_io.is_empty() {
+                    return;
+                }
+
+                let tx_category: &str = tx_io[0]
+                    .get("category")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+
+                let current_time = chrono::Utc::now();
+                let timestamp: u64 = current_time.timestamp() as u64;
+
+                let is_stake: bool = match tx_category {
+                    "stake" => true,
+                    _ => false,
+                };
+
+                if is_stake {
+                    let reward: RewardsDB = match daemon
+                        .process_stake_transaction(&tx_details, &self.db)
+                        .await
+                    {
+                        Some(reward) => reward,
+                        None => return,
+                    };
+                    info!("New stake reward: {:?}", reward);
+                    self.events.publish("stake", &reward).await;
+
+                    let mut stake_new_status = NewStakeStatusDB {
+                        txid: txid.clone(),
+                        confirmations: 1,
+                        timestamp: reward.timestamp,
+                        tg_msg_id: None,
+                        summary: None,
+                    };
+
+                    let _ = self
+                        .db
+                        .set_new_stake_status(txid.as_bytes(), &stake_new_status)
+                        .await;
+
+                    if self.announce_active().await {
+                        let cs_info: Value = daemon.getcoldstakinginfo().await.unwrap_or_else(|err| {
+                            warn!("Error getting cold staking info: {}", err);
+                            Value::Null
+                        });
+
+                        let total_staking: f64 = cs_info
+                            .get("currently_staking")
+                            .and_then(Value::as_f64)
+                            .unwrap_or(0.0);
+                        let total_coldstaking: f64 = cs_info
+                            .get("coin_in_coldstakeable_script")
+                            .and_then(Value::as_f64)
+                            .unwrap_or(0.0);
+
+                        let stakes_24h: StakeTotals = self.get_stakes_days(1).await;
+
+                        let time_zone: String = conf.timezone.clone();
+                        let tz: Tz = Tz::from_str_insensitive(&time_zone).unwrap_or(Tz::UTC);
+
+                        let start_year: u64 =
+                            time_ranges::year_start(&tz, current_time.timestamp() as u64);
+                        let stakes_ytd: StakeTotals = self.get_stakes_days(start_year).await;
+
+                        let staking_data: StakingData = StakingData {
+                            total_staking,
+                            total_coldstaking,
+                            stakes_24h,
+                            stakes_ytd,
+                        };
+
+                        let new_stake: NewStake = NewStake {
+                            height: reward.height,
+                            block_hash: reward.block_hash.clone(),
+                            txid: reward.txid.clone(),
+                            reward: self.daemon.convert_from_sat(reward.reward),
+                            agvr_reward: self.daemon.convert_from_sat(reward.agvr_reward),
+                            total_reward: self
+                                .daemon
+                                .convert_from_sat(reward.reward + reward.agvr_reward),
+                            staking_data,
+                        };
+
+                        let msg: Option<String> =
+                            Some(stake_progress(1, conf.stake_confirmations));
+
+                        let code_block: Option<String> =
+                            serde_json::to_string_pretty(&new_stake).ok();
+
+                        stake_new_status.summary = code_block.clone();
+                        let _ = self
+                            .db
+                            .set_new_stake_status(txid.as_bytes(), &stake_new_status)
+                            .await;
+
+                        let header: String = format!("👻 New Block Found! 👻");
+                        let url = Some(vec![ExplorerProvider::new(&conf).tx_url(&txid)]);
+
+                        let msg_type = "stake".to_string();
+
+                        let tg_queue: TgBotQueueDB = TgBotQueueDB {
+                            timestamp,
+                            header,
+                            msg,
+                            code_block,
+                            url,
+                            msg_type,
+                            reward_txid: Some(reward.txid.clone()),
+                            msg_to_delete: None,
+                            action_token: None,
+                            msg_to_edit: None,
+                        };
+
+                        let in_tg_queue = self.db.get_tg_bot_queue(txid.as_bytes());
+                        if in_tg_queue.is_none() {
+                            self.announce_templated(
+                                txid.as_bytes(),
+                                tg_queue,
+                                TemplateValues {
+                                    amount: Amount::from_sat(reward.reward + reward.agvr_reward)
+                                        .to_string(),
+                                    txid: txid.clone(),
+                                    height: Some(reward.height),
+                                    ..Default::default()
+                                },
+                            )
+                            .await;
+                        }
+                    }
+                } else if wallet == conf.rpc_wallet {
+                    // Zaps are only watched for on the primary wallet
+                    info!("wallet tx!");
+
+                    let mut is_incoming_zap = false;
+                    let mut amount_int = 0;
+                    let mut amount: f64 = 0.0;
+
+                    for tx in tx_io {
+                        let is_watchonly = tx
+                            .get("involvesWatchonly")
+                            .and_then(Value::as_bool)
+                            .unwrap_or(false);
+
+                        let is_receive: bool =
+                            tx.get("category").and_then(Value::as_str) == Some("receive");
+
+                        if is_watchonly && is_receive {
+                            is_incoming_zap = true;
+                            amount += tx.get("amount").and_then(Value::as_f64).unwrap_or(0.0);
+                            amount_int += self.daemon.convert_to_sat(amount);
+                        }
+                    }
+
+                    if is_incoming_zap {
+                        let confirms = tx_details
+                            .get("confirmations")
+                            .and_then(Value::as_i64)
+                            .unwrap_or(0);
+
+                        if confirms < 0 {
+                            return;
+                        }
+
+                        if confirms < conf.zap_confirmations as i64 {
+                            let in_queue = self.db.get_zap_status(txid.as_bytes());
+                            let first_notice = false;
+                            let confirmations = confirms as u32;
+
+                            if in_queue.is_none() {
+                                let mut zap_status = ZapStatusDB {
+                                    txid: txid.clone(),
+                                    amount: amount_int,
+                                    confirmations,
+                                    first_notice,
+                                };
+                                if let Err(err) =
+                                    self.db.set_zap_status(txid.as_bytes(), &zap_status).await
+                                {
+                                    error!("Error saving the zap status of {}: {}", txid, err);
+                                }
+                                self.events.publish("zap", &zap_status).await;
+
+                                // Deposits only count as unexpected once the owner lists what's coming
+                                let watching: bool =
+                                    !self.db.get_expected_deposits().is_empty();
+
+                                let zap_request: Option<ZapRequestDB> =
+                                    self.match_zap_request(tx_io, &txid).await;
+                                let expected: Option<ExpectedDepositDB> = match zap_request {
+                                    Some(_) => None,
+                                    None => {
+                                        self.match_expected_deposit(
+                                            self.daemon.convert_to_sat(amount),
+                                            &txid,
+                                        )
+                                        .await
+                                    }
+                                };
+
+                                if self.announce_active().await {
+                                    let header = if watching
+                                        && zap_request.is_none()
+                                        && expected.is_none()
+                                    {
+                                        "⚠️ Unexpected Deposit! ⚠️".to_string()
+                                    } else {
+                                        "👻 New Zap Detected! 👻".to_string()
+                                    };
+
+                                    let msg = Some(match (zap_request, expected) {
+                                        (Some(request), _) => format!(
+                                            "New deposit of {} GHOST is in your GhostVault! It fills your zap request for {} GHOST.",
+                                            amount,
+                                            Amount::from_sat(request.amount)
+                                        ),
+                                        (None, Some(deposit)) if !deposit.memo.is_empty() => format!(
+                                            "New deposit of {} GHOST is in your GhostVault! It's the expected deposit: {}",
+                                            amount, deposit.memo
+                                        ),
+                                        (None, Some(_)) => format!(
+                                            "New deposit of {} GHOST is in your GhostVault! It's the deposit you expected.",
+                                            amount
+                                        ),
+                                        (None, None) if watching => format!(
+                                            "New deposit of {} GHOST is in your GhostVault, it doesn't match any expected deposit or zap request!",
+                                            amount
+                                        ),
+                                        (None, None) => format!(
+                                            "New deposit of {} GHOST is in your GhostVault!",
+                                            amount
+                                        ),
+                                    });
+
+                                    let url =
+                                        Some(vec![ExplorerProvider::new(&conf).tx_url(&txid)]);
+
+                                    let msg_type = "zap".to_string();
+
+                                    let tg_queue: TgBotQueueDB = TgBotQueueDB {
+                                        timestamp,
+                                        header,
+                                        msg,
+                                        code_block: None,
+                                        url,
+                                        msg_type,
+                                        reward_txid: None,
+                                        msg_to_delete: None,
+                                        action_token: None,
+                                        msg_to_edit: None,
+                                    };
+
+                                    let in_tg_queue = self.db.get_tg_bot_queue(txid.as_bytes());
+                                    if in_tg_queue.is_none() && !zap_status.first_notice {
+                                        self.announce_templated(
+                                            txid.as_bytes(),
+                                            tg_queue,
+                                            TemplateValues {
+                                                amount: amount.to_string(),
+                                                txid: txid.clone(),
+                                                ..Default::default()
+                                            },
+                                        )
+                                        .await;
+                                        zap_status.first_notice = true;
+                                        if let Err(err) = self
+                                            .db
+                                            .set_zap_status(txid.as_bytes(), &zap_status)
+                                            .await
+                                        {
+                                            error!(
+                                                "Error saving the zap status of {}: {}",
+                                                txid, err
+                                            );
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            drop(conf);
+        })
+        .await
+    }
+
+    async fn set_bot_announce(
+        self,
+        _: context::Context,
+        msg_type: String,
+        new_value: bool,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_bot_announce",
+            serde_json::json!({ "msg_type": &msg_type, "new_val": new_value }),
+        );
+        audited(audit, async move {
+            let mut conf = self.gv_config.write().await;
+
+            match msg_type.to_uppercase().as_str() {
+                "STAKE" => {
+                    conf.update_gv_config("ANNOUNCE_STAKES", &new_value.to_string())
+                        .or_code(ErrorCode::Internal, "Error updating config")?;
+                }
+                "ZAP" => {
+                    conf.update_gv_config("ANNOUNCE_ZAPS", &new_value.to_string())
+                        .or_code(ErrorCode::Internal, "Error updating config")?;
+                }
+                "REWARD" => {
+                    conf.update_gv_config("ANNOUNCE_REWARDS", &new_value.to_string())
+                        .or_code(ErrorCode::Internal, "Error updating config")?;
+                }
+                "ALL" => {
+                    conf.update_gv_config("ANNOUNCE_STAKES", &new_value.to_string())
+                        .or_code(ErrorCode::Internal, "Error updating config")?;
+                    conf.update_gv_config("ANNOUNCE_ZAPS", &new_value.to_string())
+                        .or_code(ErrorCode::Internal, "Error updating config")?;
+                    conf.update_gv_config("ANNOUNCE_REWARDS", &new_value.to_string())
+                        .or_code(ErrorCode::Internal, "Error updating config")?;
+                }
+                _ => {
+                    return Err(GvError::new(
+                        ErrorCode::InvalidArgument,
+                        "Invalid message type!",
+                    ));
+                }
+            }
+            drop(conf);
+
+            Ok(response::message("Bot announcement updated!"))
+        })
+        .await
+    }
+
+    async fn get_version_info(self, _: context::Context) -> GvReply<VersionInfo> {
+        guarded("get_version_info", async move {
+            let gv_version: String = VERSION.to_string();
+            let daemon_state: DaemonState = self.current_daemon_state().await;
+
+            let ghostd_version: String = daemon_state.version.clone();
+            let latest_release: String = daemon_state.latest_release.clone();
+
+            let cargo_features: String = if CARGO_FEATURES.is_empty() {
+                "none".to_string()
+            } else {
+                CARGO_FEATURES.to_string()
+            };
+
+            let version_info: VersionInfo = VersionInfo {
+                gv_version,
+                git_commit: GIT_COMMIT.to_string(),
+                build_date: BUILD_DATE.to_string(),
+                cargo_features,
+                ghostd_version,
+                latest_release,
+                api_version: API_VERSION,
+            };
+            Ok(response::ok(version_info))
+        })
+        .await
+    }
+
+    async fn get_reward_options(self, _: context::Context) -> GvReply<RewardOptions> {
+        guarded("get_reward_options", async move {
+            let conf = self.gv_config.read().await;
+            let anon_mode = conf.anon_mode;
+            let reward_address: String = if anon_mode {
+                conf.anon_reward_address.clone().unwrap_or_default()
+            } else {
+                conf.reward_address.clone().unwrap_or_default()
+            };
+
+            let reward_mode = if anon_mode {
+                "ANON".to_string()
+            } else if !anon_mode && !reward_address.is_empty() {
+                "STANDARD".to_string()
+            } else {
+                "DEFAULT".to_string()
+            };
+
+            let reward_interval_secs: Duration = Duration::from_secs(conf.reward_interval);
+            let reward_interval: String = format_duration(reward_interval_secs).to_string();
+            let reward_min: f64 = self.daemon.convert_from_sat(conf.min_reward_payout);
+
+            let reward_address_label: Option<String> = self.db.get_address_label(&reward_address);
+
+            let rewards: RewardOptions = RewardOptions {
+                reward_mode,
+                reward_address,
+                reward_address_label,
+                reward_interval,
+                reward_min,
+            };
+
+            Ok(response::ok(rewards))
+        })
+        .await
+    }
+
+    async fn check_chain(self, _: context::Context) -> GvReply<bool> {
+        guarded("check_chain", async move {
+            let daemon_info = self.current_daemon_state().await;
+            Ok(response::ok(daemon_info.good_chain))
+        })
+        .await
+    }
+
+    async fn validate_address(self, _: context::Context, address: String) -> GvReply<AddressInfo> {
+        guarded("validate_address", async move {
+            let addr_info = self.daemon.get_address_info(&address).await;
+
+            let is_valid = !addr_info.is_err();
+
+            let addr_flag = |key: &str| -> bool {
+                addr_info
+                    .as_ref()
+                    .ok()
+                    .and_then(|addr_info| addr_info.get(key))
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false)
+            };
+
+            let is_mine: bool = addr_flag("ismine");
+            let is_256bit: bool = addr_flag("is256bit");
+
+            let addr_info: AddressInfo = AddressInfo {
+                is_mine,
+                is_valid,
+                is_256bit,
+            };
+
+            Ok(response::ok(addr_info))
+        })
+        .await
+    }
+
+    async fn get_pending_rewards(self, _: context::Context) -> GvReply<PendingRewards> {
+        guarded("get_pending_rewards", async move {
+            let balances = self
+                .daemon
+                .get_balances()
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting balances")?;
+            let my_balances = balances
+                .get("mine")
+                .and_then(|mine| mine.as_object())
+                .or_code(ErrorCode::DaemonError, "Malformed getbalances response")?;
+
+            let balance = |key: &str| -> Amount {
+                Amount::from_ghost(
+                    my_balances
+                        .get(key)
+                        .and_then(|amount| amount.as_f64())
+                        .unwrap_or(0.0),
+                )
+            };
+
+            let trusted: Amount = balance("trusted");
+            let untrusted_pending: Amount = balance("untrusted_pending");
+            let immature: Amount = balance("immature");
+            let staked: Amount = balance("staked");
+            let anon_trusted: Amount = balance("anon_trusted");
+            let anon_immature: Amount = balance("anon_immature");
+            let anon_pending: Amount = balance("anon_untrusted_pending");
+
+            let conf = self.gv_config.read().await;
+
+            // With ANON_FLUSH off the trusted public balance is what gets paid out
+            let (pending_anonymization, pending_payout): (Amount, Amount) = if conf.anon_flush {
+                (trusted + untrusted_pending + immature, anon_trusted)
+            } else {
+                (untrusted_pending + immature, trusted)
+            };
+            let pending_anon_confs: Amount = anon_immature + anon_pending;
+            let total_pending: Amount =
+                trusted + untrusted_pending + immature + staked + anon_trusted + pending_anon_confs;
+
+            let next_payout_time: i64 = task_runner::get_next_payout_time(&self.db)
+                .await
+                .or_code(ErrorCode::Internal, "Error updating payout task")?;
+
+            let next_payout_time: DateTime<Utc> = DateTime::from_timestamp(next_payout_time, 0)
+                .or_code(ErrorCode::Internal, "Invalid payout time")?;
+            let time_zone: String = conf.timezone.clone();
+            let tz: Tz = parse_timezone(&time_zone)?;
+            let next_payout_run: String = next_payout_time.with_timezone(&tz).to_string();
+
+            let min_payout: Amount = Amount::from_sat(conf.min_reward_payout);
+
+            let pending_rewards: PendingRewards = PendingRewards {
+                total_pending: total_pending.to_ghost(),
+                staked: staked.to_ghost(),
+                pending_anonymization: pending_anonymization.to_ghost(),
+                pending_anon_confs: pending_anon_confs.to_ghost(),
+                pending_payout: pending_payout.to_ghost(),
+                payout_run_interval: format_duration(Duration::from_secs(conf.reward_interval))
+                    .to_string(),
+                next_payout_run,
+                min_payout: min_payout.to_ghost(),
+                total_pending_amount: total_pending,
+                staked_amount: staked,
+                pending_anonymization_amount: pending_anonymization,
+                pending_anon_confs_amount: pending_anon_confs,
+                pending_payout_amount: pending_payout,
+                min_payout_amount: min_payout,
+            };
+
+            Ok(response::ok(pending_rewards))
+        })
+        .await
+    }
+
+    async fn get_anonymization_status(self, _: context::Context) -> GvReply<AnonymizationStatus> {
+        guarded("get_anonymization_status", async move {
+            let balances = self
+                .daemon
+                .get_balances()
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting balances")?;
+            let my_balances = balances
+                .get("mine")
+                .and_then(|mine| mine.as_object())
+                .or_code(ErrorCode::DaemonError, "Malformed getbalances response")?;
+
+            let balance = |key: &str| -> Amount {
+                Amount::from_ghost(
+                    my_balances
+                        .get(key)
+                        .and_then(|amount| amount.as_f64())
+                        .unwrap_or(0.0),
+                )
+            };
+
+            let now: u64 = Utc::now().timestamp() as u64;
+            let mut in_flight: Vec<AnonConversion> = Vec::new();
+
+            for conversion in self.db.get_anon_conversions() {
+                let confirmations: u64 = match self.anon_conversion_confirmations(&conversion).await
+                {
+                    Some(confirmations) if confirmations < ANON_MATURITY_CONFIRMATIONS => {
+                        confirmations
+                    }
+                    _ => continue,
+                };
+
+                let blocks_left: u64 = ANON_MATURITY_CONFIRMATIONS - confirmations;
+
+                in_flight.push(AnonConversion {
+                    txids: conversion.txids,
+                    amount: Amount::from_sat(conversion.amount),
+                    started: conversion.timestamp,
+                    confirmations,
+                    matures: now + blocks_left * BLOCK_TARGET_SPACING,
+                });
+            }
+
+            Ok(response::ok(AnonymizationStatus {
+                public_trusted: balance("trusted"),
+                public_pending: balance("untrusted_pending") + balance("immature"),
+                converting: balance("anon_untrusted_pending"),
+                anon_immature: balance("anon_immature"),
+                anon_trusted: balance("anon_trusted"),
+                in_flight,
+            }))
+        })
+        .await
+    }
+
+    async fn process_daemon_update(
+        self,
+        _: context::Context,
+        force: bool,
+    ) -> GvReply<DaemonUpdate> {
+        let audit: Audit = self.audit(
+            "process_daemon_update",
+            serde_json::json!({ "force": force }),
+        );
+        audited(audit, async move {
+            let conf = self.gv_config.read().await;
+            let system_daemon: bool = conf.system_daemon;
+            let update_channel: UpdateChannel = conf.update_channel.clone();
+            drop(conf);
+
+            if system_daemon {
+                info!("System ghostd in use, skipping managed update.");
+                return Err(GvError::new(
+                    ErrorCode::Disabled,
+                    "System ghostd in use, update it outside of GhostVault!",
+                ));
+            }
+
+            info!("Checking for new update");
+            let version_str: String = self
+                .daemon
+                .get_daemon_version()
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting daemon version")?;
+            let latest_release_str: String = gv_methods::get_latest_release(&update_channel)
+                .await
+                .or_code(ErrorCode::Unavailable, "Failed to check for updates!")?;
+
+            if gv_methods::parse_version(&version_str).is_none() {
+                return Err(GvError::new(
+                    ErrorCode::DaemonError,
+                    "Invalid daemon version",
+                ));
+            }
+
+            let release_order =
+                gv_methods::compare_versions(&latest_release_str, &version_str).ok_or(
+                    GvError::new(ErrorCode::Unavailable, "Invalid release version"),
+                )?;
+
+            // A pin older than the running ghostd is how a bad release gets rolled back
+            let update: bool = match update_channel {
+                UpdateChannel::Pinned(_) => release_order.is_ne(),
+                _ => release_order.is_gt(),
+            };
+
+            if update {
+                let conf = self.gv_config.read().await;
+                let confirm_daemon_update: bool = conf.confirm_daemon_update;
+                drop(conf);
+
+                if confirm_daemon_update {
+                    let pending: PendingUpdate = self.get_pending_update(&latest_release_str).await;
+                    return Ok(response::ok(DaemonUpdate::Pending(pending)));
+                }
+
+                // Held for the maintenance window, the task runner applies it once it opens
+                if !force && !task_runner::in_maint_window(&self.gv_config).await {
+                    let mut maintenance: MaintenanceDB = self.db.get_maintenance();
+                    maintenance.update = Some(latest_release_str.clone());
+                    self.db
+                        .set_maintenance(&maintenance)
+                        .await
+                        .or_code(ErrorCode::Internal, "Database error")?;
+
+                    info!(
+                        "ghostd v{} deferred to the maintenance window",
+                        latest_release_str
+                    );
+                    return Ok(response::ok(DaemonUpdate::Deferred(
+                        self.maintenance_window().await,
+                    )));
+                }
+
+                // An update already on its way is reported instead of started again
+                let operation: OperationGuard = match self.operations.start("daemon_update").await {
+                    Ok(operation) => operation,
+                    Err(_) => return Ok(response::ok(DaemonUpdate::Updating(latest_release_str))),
+                };
+
+                let release_clone = latest_release_str.clone();
+                tokio::spawn(async move {
+                    let res = self.do_update(&release_clone).await;
+                    operation.finish(res).await;
+                });
+                Ok(response::ok(DaemonUpdate::Updating(latest_release_str)))
+            } else {
+                info!("Daemon is up to date!");
+                Ok(response::ok(DaemonUpdate::UpToDate(false)))
+            }
+        })
+        .await
+    }
+
+    async fn get_daemon_online(self, _: context::Context) -> GvReply<DaemonOnline> {
+        guarded("get_daemon_online", async move {
+            let daemon_online: bool = self.daemon_online().await;
+
+            if !daemon_online {
+                self.db.gvdb.flush_async().await.unwrap();
+                let server_ready: Option<ServerReadyDB> = self.db.get_server_ready();
+                Ok(response::ok(DaemonOnline::Starting(server_ready)))
+            } else {
+                Ok(response::ok(DaemonOnline::Online(daemon_online)))
+            }
+        })
+        .await
+    }
+
+    async fn get_stake_barchart_data(
+        self,
+        _: context::Context,
+        start: u64,
+        end: u64,
+        division: String,
+    ) -> GvReply<BarChart> {
+        guarded("get_stake_barchart_data", async move {
+            let division: Division = Division::from_name(&division).or_code(
+                ErrorCode::InvalidArgument,
+                &format!("Invalid division: {}", division),
+            )?;
+
+            let stake_data: BarChart = self
+                .get_stake_barchart_vec(start, end, division, &self.vault_tz().await)
+                .await;
+            Ok(response::ok(stake_data))
+        })
+        .await
+    }
+
+    async fn get_stake_barchart_data_tz(
+        self,
+        _: context::Context,
+        start: u64,
+        end: u64,
+        division: String,
+        timezone: String,
+    ) -> GvReply<BarChart> {
+        guarded("get_stake_barchart_data_tz", async move {
+            let division: Division = Division::from_name(&division).or_code(
+                ErrorCode::InvalidArgument,
+                &format!("Invalid division: {}", division),
+            )?;
+            let tz: Tz = parse_timezone(&timezone)?;
+
+            let stake_data: BarChart = self.get_stake_barchart_vec(start, end, division, &tz).await;
+            Ok(response::ok(stake_data))
+        })
+        .await
+    }
+
+    async fn get_earnings_chart_data(
+        self,
+        _: context::Context,
+        start: u64,
+        end: u64,
+    ) -> GvReply<AllTimeEarnigns> {
+        guarded("get_earnings_chart_data", async move {
+            let earnings_data: AllTimeEarnigns = self
+                .get_earnings_chart_vec(start, end, &self.vault_tz().await)
+                .await;
+            Ok(response::ok(earnings_data))
+        })
+        .await
+    }
+
+    async fn get_earnings_chart_data_tz(
+        self,
+        _: context::Context,
+        start: u64,
+        end: u64,
+        timezone: String,
+    ) -> GvReply<AllTimeEarnigns> {
+        guarded("get_earnings_chart_data_tz", async move {
+            let tz: Tz = parse_timezone(&timezone)?;
+
+            let earnings_data: AllTimeEarnigns = self.get_earnings_chart_vec(start, end, &tz).await;
+            Ok(response::ok(earnings_data))
+        })
+        .await
+    }
+
+    async fn get_stake_heatmap_data(
+        self,
+        _: context::Context,
+        start: u64,
+        end: u64,
+    ) -> GvReply<StakeHeatmap> {
+        guarded("get_stake_heatmap_data", async move {
+            let heatmap: StakeHeatmap = self
+                .get_stake_heatmap_vec(start, end, &self.vault_tz().await)
+                .await;
+            Ok(response::ok(heatmap))
+        })
+        .await
+    }
+
+    async fn get_stake_heatmap_data_tz(
+        self,
+        _: context::Context,
+        start: u64,
+        end: u64,
+        timezone: String,
+    ) -> GvReply<StakeHeatmap> {
+        guarded("get_stake_heatmap_data_tz", async move {
+            let tz: Tz = parse_timezone(&timezone)?;
+
+            let heatmap: StakeHeatmap = self.get_stake_heatmap_vec(start, end, &tz).await;
+            Ok(response::ok(heatmap))
+        })
+        .await
+    }
+
+    async fn get_stakes_by_address(
+        self,
+        _: context::Context,
+        start: u64,
+        end: u64,
+    ) -> GvReply<StakesByAddress> {
+        guarded("get_stakes_by_address", async move {
+            let stakes: StakesByAddress = self.get_stakes_by_address_vec(start, end).await;
+            Ok(response::ok(stakes))
+        })
+        .await
+    }
+
+    async fn get_stakes_by_address_csv(
+        self,
+        _: context::Context,
+        start: u64,
+        end: u64,
+    ) -> GvReply<String> {
+        guarded("get_stakes_by_address_csv", async move {
+            let stakes: StakesByAddress = self.get_stakes_by_address_vec(start, end).await;
+            Ok(response::ok(stakes.to_csv()))
+        })
+        .await
+    }
+
+    async fn set_fee_policy(
+        self,
+        _: context::Context,
+        policy: String,
+        value: Option<f64>,
+        max_fee: Option<f64>,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_fee_policy",
+            serde_json::json!({ "policy": &policy, "value": value, "max_fee": max_fee }),
+        );
+        audited(audit, async move {
+            let policy: FeePolicy = match FeePolicy::from_name(&policy) {
+                Some(policy) => policy,
+                None => {
+                    return Err(GvError::new(
+                        ErrorCode::InvalidArgument,
+                        format!("Invalid fee policy: {}, use FIXED or ESTIMATE.", policy),
+                    ))
+                }
+            };
+
+            let mut conf = self.gv_config.write().await;
+
+            // FIXED takes a rate in GHOST per kB, ESTIMATE a confirmation target in blocks
+            match (policy, value) {
+                (FeePolicy::Fixed, Some(rate)) => {
+                    let rate: u64 = self.daemon.convert_to_sat(rate);
+                    conf.update_gv_config("fee_rate", &rate.to_string())
+                        .or_code(ErrorCode::InvalidArgument, "Invalid fee rate")?;
+                }
+                (FeePolicy::Estimate, Some(target)) => {
+                    if target.fract() != 0.0 {
+                        return Err(GvError::new(
+                            ErrorCode::InvalidArgument,
+                            "Confirmation target must be a whole number of blocks.",
+                        ));
+                    }
+                    conf.update_gv_config("fee_conf_target", &(target as u64).to_string())
+                        .or_code(ErrorCode::InvalidArgument, "Invalid confirmation target")?;
+                }
+                (_, None) => {}
+            }
+
+            if let Some(max_fee) = max_fee {
+                let max_fee: u64 = self.daemon.convert_to_sat(max_fee);
+                conf.update_gv_config("max_tx_fee", &max_fee.to_string())
+                    .or_code(ErrorCode::InvalidArgument, "Invalid max fee")?;
+            }
+
+            conf.update_gv_config("fee_policy", policy.as_str())
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+
+            let max_fee: f64 = self.daemon.convert_from_sat(conf.max_tx_fee);
+            let msg: String = match policy {
+                FeePolicy::Fixed => format!(
+                    "Fee policy set to FIXED at {:.8} GHOST per kB, max {} GHOST per transaction.",
+                    self.daemon.convert_from_sat(conf.fee_rate),
+                    max_fee
+                ),
+                FeePolicy::Estimate => format!(
+                    "Fee policy set to ESTIMATE for {} blocks, falling back to {:.8} GHOST per kB, max {} GHOST per transaction.",
+                    conf.fee_conf_target,
+                    self.daemon.convert_from_sat(conf.fee_rate),
+                    max_fee
+                ),
+            };
+
+            Ok(response::message(msg))
+        })
+        .await
+    }
+
+    async fn set_anon_policy(
+        self,
+        _: context::Context,
+        enabled: bool,
+        threshold: Option<f64>,
+        interval: Option<u64>,
+        ring_size: Option<u32>,
+        batch_size: Option<u64>,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_anon_policy",
+            serde_json::json!({
+                "enabled": enabled,
+                "threshold": threshold,
+                "interval": interval,
+                "ring_size": ring_size,
+                "batch_size": batch_size,
+            }),
+        );
+        audited(audit, async move {
+            let mut conf = self.gv_config.write().await;
+
+            if let Some(threshold) = threshold {
+                let threshold: u64 = self.daemon.convert_to_sat(threshold);
+                conf.update_gv_config("anon_flush_threshold", &threshold.to_string())
+                    .or_code(
+                        ErrorCode::InvalidArgument,
+                        &format!(
+                            "Threshold must be at least {} GHOST",
+                            self.daemon.convert_from_sat(MIN_TX_VALUE)
+                        ),
+                    )?;
+            }
+
+            if let Some(interval) = interval {
+                conf.update_gv_config("anon_flush_interval", &interval.to_string())
+                    .or_code(ErrorCode::InvalidArgument, "Invalid interval")?;
+            }
+
+            if let Some(ring_size) = ring_size {
+                conf.update_gv_config("anon_ring_size", &ring_size.to_string())
+                    .or_code(
+                        ErrorCode::InvalidArgument,
+                        &format!(
+                            "Ring size must be from {} to {}",
+                            MIN_RING_SIZE, MAX_RING_SIZE
+                        ),
+                    )?;
+            }
+
+            if let Some(batch_size) = batch_size {
+                conf.update_gv_config("anon_batch_size", &batch_size.to_string())
+                    .or_code(ErrorCode::InvalidArgument, "Invalid batch size")?;
+            }
+
+            conf.update_gv_config("anon_flush", &enabled.to_string())
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+
+            let policy: AnonPolicy = conf.anon_policy();
+
+            let msg: String = if !policy.enabled {
+                "Anon conversion disabled, rewards are paid out from the public balance."
+                    .to_string()
+            } else {
+                let frequency: String = if policy.interval == 0 {
+                    "every matured stake".to_string()
+                } else {
+                    format!(
+                        "at most every {}",
+                        format_duration(Duration::from_secs(policy.interval))
+                    )
+                };
+                let batch: String = if policy.batch_size == 0 {
+                    "inputs limited by the max fee".to_string()
+                } else {
+                    format!("at most {} inputs per transaction", policy.batch_size)
+                };
+
+                format!(
+                    "Anon conversion enabled from {} GHOST, {}, ring size {}, {}.",
+                    self.daemon.convert_from_sat(policy.threshold),
+                    frequency,
+                    policy.ring_size,
+                    batch
+                )
+            };
+
+            Ok(response::message(msg))
+        })
+        .await
+    }
+
+    async fn list_utxos(
+        self,
+        _: context::Context,
+        wallet: String,
+        utxo_type: String,
+        min_amount: f64,
+        max_amount: f64,
+        min_confirmations: u64,
+    ) -> GvReply<Vec<Utxo>> {
+        guarded("list_utxos", async move {
+            let wallet: String = wallet.to_lowercase();
+            let daemon: DaemonHelper = self.utxo_wallet(&wallet).await?;
+
+            let types: Vec<&str> = match utxo_type.to_lowercase().as_str() {
+                "ghost" => vec!["ghost"],
+                "anon" => vec!["anon"],
+                "all" | "" => vec!["ghost", "anon"],
+                _ => {
+                    return Err(GvError::new(
+                        ErrorCode::InvalidArgument,
+                        format!("Invalid type: {}, use ghost, anon or all.", utxo_type),
+                    ))
+                }
+            };
+
+            self.relock_frozen_utxos(&daemon, &wallet).await;
+
+            let min_sat: u64 = self.daemon.convert_to_sat(min_amount);
+            let max_sat: Option<u64> = if max_amount > 0.0 {
+                Some(self.daemon.convert_to_sat(max_amount))
+            } else {
+                None
+            };
+            let in_range = |amount: u64, confirmations: u64| {
+                amount >= min_sat
+                    && max_sat.is_none_or(|max_sat| amount <= max_sat)
+                    && confirmations >= min_confirmations
+            };
+
+            let mut utxos: Vec<Utxo> = Vec::new();
+
+            for utxo_type in &types {
+                let unspent: Value = daemon
+                    .list_unspent(utxo_type)
+                    .await
+                    .or_code(ErrorCode::DaemonError, "Error listing unspent outputs")?;
+
+                for item in unspent
+                    .as_array()
+                    .or_code(ErrorCode::DaemonError, "Malformed listunspent response")?
+                {
+                    let amount: u64 = self.daemon.convert_to_sat(
+                        item.get("amount")
+                            .and_then(|amount| amount.as_f64())
+                            .unwrap_or_default(),
+                    );
+                    let confirmations: u64 = item
+                        .get("confirmations")
+                        .and_then(|confirmations| confirmations.as_u64())
+                        .unwrap_or_default();
+
+                    if !in_range(amount, confirmations) {
+                        continue;
+                    }
+
+                    let flag = |key: &str| -> bool {
+                        item.get(key)
+                            .and_then(|flag| flag.as_bool())
+                            .unwrap_or(false)
+                    };
+
+                    // Same rule the sweeps use to pick their inputs
+                    let spendable: bool = if *utxo_type == "ghost" {
+                        flag("safe") && flag("spendable")
+                    } else {
+                        flag("safe")
+                    };
+
+                    utxos.push(Utxo {
+                        txid: item
+                            .get("txid")
+                            .and_then(|txid| txid.as_str())
+                            .unwrap_or_default()
+                            .to_string(),
+                        vout: item
+                            .get("vout")
+                            .and_then(|vout| vout.as_u64())
+                            .unwrap_or_default() as u32,
+                        utxo_type: utxo_type.to_string(),
+                        address: item
+                            .get("address")
+                            .and_then(|address| address.as_str())
+                            .unwrap_or_default()
+                            .to_string(),
+                        amount: Amount::from_sat(amount),
+                        confirmations,
+                        spendable,
+                        frozen: false,
+                    });
+                }
+            }
+
+            // Frozen outputs are locked, so listunspent leaves them out
+            for frozen in self.db.get_frozen_utxos(&wallet) {
+                if !types.contains(&frozen.utxo_type.as_str()) {
+                    continue;
+                }
+
+                let confirmations: u64 = match daemon.get_transaction(&frozen.txid).await {
+                    Ok(tx) => tx
+                        .get("confirmations")
+                        .and_then(|confirmations| confirmations.as_u64())
+                        .unwrap_or_default(),
+                    Err(_) => 0,
+                };
+
+                if !in_range(frozen.amount, confirmations) {
+                    continue;
+                }
+
+                utxos.push(Utxo {
+                    txid: frozen.txid,
+                    vout: frozen.vout,
+                    utxo_type: frozen.utxo_type,
+                    address: frozen.address,
+                    amount: Amount::from_sat(frozen.amount),
+                    confirmations,
+                    spendable: false,
+                    frozen: true,
+                });
+            }
+
+            utxos.sort_by_key(|utxo| utxo.amount);
+
+            Ok(response::ok(utxos))
+        })
+        .await
+    }
+
+    async fn freeze_utxo(
+        self,
+        _: context::Context,
+        wallet: String,
+        txid: String,
+        vout: u32,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "freeze_utxo",
+            serde_json::json!({ "wallet": &wallet, "txid": &txid, "vout": vout }),
+        );
+        audited(audit, async move {
+            let wallet: String = wallet.to_lowercase();
+            let daemon: DaemonHelper = self.utxo_wallet(&wallet).await?;
+
+            if self.db.get_frozen_utxo(&wallet, &txid, vout).is_some() {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Output {}:{} is already frozen.", txid, vout),
+                ));
+            }
+
+            let mut found: Option<(&str, Value)> = None;
+
+            for utxo_type in ["ghost", "anon"] {
+                let unspent: Value = daemon
+                    .list_unspent(utxo_type)
+                    .await
+                    .or_code(ErrorCode::DaemonError, "Error listing unspent outputs")?;
+
+                found = unspent
+                    .as_array()
+                    .or_code(ErrorCode::DaemonError, "Malformed listunspent response")?
+                    .iter()
+                    .find(|item| {
+                        item.get("txid").and_then(|txid| txid.as_str()) == Some(txid.as_str())
+                            && item.get("vout").and_then(|vout| vout.as_u64()) == Some(vout as u64)
+                    })
+                    .map(|item| (utxo_type, item.clone()));
+
+                if found.is_some() {
+                    break;
+                }
+            }
+
+            let (utxo_type, item) = match found {
+                Some(found) => found,
+                None => {
+                    return Err(GvError::new(
+                        ErrorCode::NotFound,
+                        format!(
+                            "No unspent output {}:{} in the {} wallet.",
+                            txid, vout, wallet
+                        ),
+                    ))
+                }
+            };
+
+            daemon
+                .lock_unspent(false, &[(txid.clone(), vout)])
+                .await
+                .or_code(ErrorCode::DaemonError, "Error locking output")?;
+
+            let frozen: FrozenUtxoDB = FrozenUtxoDB {
+                wallet,
+                txid: txid.clone(),
+                vout,
+                utxo_type: utxo_type.to_string(),
+                address: item
+                    .get("address")
+                    .and_then(|address| address.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                amount: self.daemon.convert_to_sat(
+                    item.get("amount")
+                        .and_then(|amount| amount.as_f64())
+                        .unwrap_or_default(),
+                ),
+                frozen_at: Utc::now().timestamp() as u64,
+            };
+
+            self.db
+                .set_frozen_utxo(&frozen)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            Ok(response::message(format!(
+                "Output {}:{} frozen!",
+                txid, vout
+            )))
+        })
+        .await
+    }
+
+    async fn unfreeze_utxo(
+        self,
+        _: context::Context,
+        wallet: String,
+        txid: String,
+        vout: u32,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "unfreeze_utxo",
+            serde_json::json!({ "wallet": &wallet, "txid": &txid, "vout": vout }),
+        );
+        audited(audit, async move {
+            let wallet: String = wallet.to_lowercase();
+            let daemon: DaemonHelper = self.utxo_wallet(&wallet).await?;
+
+            if self.db.get_frozen_utxo(&wallet, &txid, vout).is_none() {
+                return Err(GvError::new(
+                    ErrorCode::NotFound,
+                    format!("Output {}:{} isn't frozen.", txid, vout),
+                ));
+            }
+
+            // Already unlocked if ghostd restarted since it was frozen
+            let locked: Vec<(String, u32)> = daemon
+                .list_lock_unspent()
+                .await
+                .or_code(ErrorCode::DaemonError, "Error listing locked outputs")?;
+
+            if locked.contains(&(txid.clone(), vout)) {
+                daemon
+                    .lock_unspent(true, &[(txid.clone(), vout)])
+                    .await
+                    .or_code(ErrorCode::DaemonError, "Error unlocking output")?;
+            }
+
+            self.db
+                .remove_frozen_utxo(&wallet, &txid, vout)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            Ok(response::message(format!(
+                "Output {}:{} unfrozen!",
+                txid, vout
+            )))
+        })
+        .await
+    }
+
+    async fn consolidate_utxos(
+        self,
+        _: context::Context,
+        wallet: String,
+        max_amount: f64,
+        max_fee_rate: f64,
+    ) -> GvReply<Consolidation> {
+        let audit: Audit = self.audit("consolidate_utxos", serde_json::json!({ "wallet": &wallet, "max_amount": max_amount, "max_fee_rate": max_fee_rate }));
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+
+            let wallet: String = wallet.to_lowercase();
+            let daemon: DaemonHelper = self.utxo_wallet(&wallet).await?;
+
+            if self.in_chain_emergency().await {
+                return Err(GvError::new(
+                    ErrorCode::InvalidState,
+                    "Chain split emergency active, not consolidating.",
+                ));
+            }
+
+            match daemon.get_encryption_status().await {
+                Ok(status) if status == "Unencrypted" || status == "Unlocked" => {}
+                _ => {
+                    return Err(GvError::new(
+                        ErrorCode::InvalidState,
+                        format!("The {} wallet is locked for spending.", wallet),
+                    ))
+                }
+            }
+
+            if max_amount <= 0.0 {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Dust threshold must be more than 0.",
+                ));
+            }
+
+            let fees: FeeSettings = self.gv_config.read().await.fee_settings();
+            let fee_rate: u64 = daemon.fee_rate(&fees).await;
+
+            // Only worth doing while fees are low, a limit of 0 takes whatever the rate is
+            if max_fee_rate > 0.0 && fee_rate > self.daemon.convert_to_sat(max_fee_rate) {
+                return Err(GvError::new(
+                    ErrorCode::InvalidState,
+                    format!(
+                        "Fee rate is {:.8} GHOST per kB, above the {:.8} limit. Try again when fees are lower.",
+                        self.daemon.convert_from_sat(fee_rate),
+                        max_fee_rate
+                    ),
+                ));
+            }
+
+            self.relock_frozen_utxos(&daemon, &wallet).await;
+
+            let max_sat: u64 = self.daemon.convert_to_sat(max_amount);
+            let unspent: Value = daemon
+                .list_unspent("ghost")
+                .await
+                .or_code(ErrorCode::DaemonError, "Error listing unspent outputs")?;
+
+            let dust: Vec<(String, u32, f64)> = unspent
+                .as_array()
+                .or_code(ErrorCode::DaemonError, "Malformed listunspent response")?
+                .iter()
+                .filter(|item| {
+                    item.get("safe").and_then(|safe| safe.as_bool()) == Some(true)
+                        && item.get("spendable").and_then(|spendable| spendable.as_bool())
+                            == Some(true)
+                })
+                .filter_map(|item| {
+                    let txid: String = item.get("txid")?.as_str()?.to_string();
+                    let vout: u32 = item.get("vout")?.as_u64()? as u32;
+                    let amount: f64 = item.get("amount")?.as_f64()?;
+                    Some((txid, vout, amount))
+                })
+                .filter(|(_, _, amount)| self.daemon.convert_to_sat(*amount) <= max_sat)
+                .collect();
+
+            if dust.len() < 2 {
+                return Err(GvError::new(
+                    ErrorCode::InvalidState,
+                    format!(
+                        "Nothing to consolidate, found {} spendable output(s) at or below {} GHOST.",
+                        dust.len(),
+                        max_amount
+                    ),
+                ));
+            }
+
+            // Staked by the vault, spendable by the wallet the dust came from
+            let script_hex: String = self.own_coldstake_script(&daemon).await?;
+
+            let txids: Vec<String> = daemon
+                .consolidate_to_script(&dust, &script_hex, fee_rate)
+                .await
+                .or_code(ErrorCode::DaemonError, "Error consolidating outputs")?;
+
+            for txid in &txids {
+                info!("Consolidated dust in the {} wallet: {}", wallet, txid);
+            }
+
+            let consolidation: Consolidation = Consolidation {
+                wallet,
+                inputs: dust.len() as u32,
+                amount: dust
+                    .iter()
+                    .map(|(_, _, amount)| Amount::from_ghost(*amount))
+                    .sum(),
+                fee_rate: self.daemon.convert_from_sat(fee_rate),
+                txids,
+            };
+
+            Ok(response::ok(consolidation))
+        })
+        .await
+    }
+
+    async fn split_utxos(self, _: context::Context) -> GvReply<Option<PendingSplit>> {
+        let audit: Audit = self.audit("split_utxos", serde_json::json!({}));
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+
+            let daemon: DaemonHelper = self.utxo_wallet("cold").await?;
+
+            if self.in_chain_emergency().await {
+                return Err(GvError::new(
+                    ErrorCode::InvalidState,
+                    "Chain split emergency active, not splitting.",
+                ));
+            }
+
+            let conf = self.gv_config.read().await;
+            let split_threshold: u64 = conf.split_threshold;
+            let split_count: u64 = conf.split_count;
+            drop(conf);
+
+            self.relock_frozen_utxos(&daemon, "cold").await;
+
+            let unspent: Value = daemon
+                .list_unspent("ghost")
+                .await
+                .or_code(ErrorCode::DaemonError, "Error listing unspent outputs")?;
+
+            let oversized: Vec<(String, u32, u64)> = unspent
+                .as_array()
+                .or_code(ErrorCode::DaemonError, "Malformed listunspent response")?
+                .iter()
+                .filter(|item| {
+                    item.get("safe").and_then(|safe| safe.as_bool()) == Some(true)
+                        && item
+                            .get("spendable")
+                            .and_then(|spendable| spendable.as_bool())
+                            == Some(true)
+                })
+                .filter_map(|item| {
+                    let txid: String = item.get("txid")?.as_str()?.to_string();
+                    let vout: u32 = item.get("vout")?.as_u64()? as u32;
+                    let amount: u64 = self.daemon.convert_to_sat(item.get("amount")?.as_f64()?);
+                    Some((txid, vout, amount))
+                })
+                .filter(|(_, _, amount)| *amount > split_threshold)
+                .collect();
+
+            if oversized.is_empty() {
+                return Ok(response::message(format!(
+                    "No staking outputs above {} GHOST to split.",
+                    self.daemon.convert_from_sat(split_threshold)
+                )));
+            }
+
+            let pending: PendingSplit = self.get_pending_split(oversized, split_count).await;
+
+            Ok(response::ok(Some(pending)))
+        })
+        .await
+    }
+
+    async fn confirm_split(self, _: context::Context, token: String) -> GvReply<Vec<String>> {
+        let audit: Audit = self.audit("confirm_split", serde_json::json!({ "token": &token }));
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+
+            let pending: PendingSplitDB = match self.db.get_pending_split() {
+                Some(pending) => pending,
+                None => return Err(GvError::new(ErrorCode::NotFound, "No split is pending!")),
+            };
+
+            let now: u64 = Utc::now().timestamp() as u64;
+
+            if pending.created + PENDING_SPLIT_TTL < now {
+                self.db
+                    .remove_pending_split()
+                    .await
+                    .or_code(ErrorCode::Internal, "Database error")?;
+                return Err(GvError::new(
+                    ErrorCode::Expired,
+                    "Split token expired, run the split again!",
+                ));
+            }
+
+            if pending.token != token {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Invalid split token!",
+                ));
+            }
+
+            self.db
+                .remove_pending_split()
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            if self.in_chain_emergency().await {
+                return Err(GvError::new(
+                    ErrorCode::InvalidState,
+                    "Chain split emergency active, not splitting.",
+                ));
+            }
+
+            let daemon: DaemonHelper = self.utxo_wallet("cold").await?;
+
+            match daemon.get_encryption_status().await {
+                Ok(status) if status == "Unencrypted" || status == "Unlocked" => {}
+                _ => {
+                    return Err(GvError::new(
+                        ErrorCode::InvalidState,
+                        "The cold wallet is locked for spending.",
+                    ))
+                }
+            }
+
+            let fees: FeeSettings = self.gv_config.read().await.fee_settings();
+            let fee_rate: u64 = daemon.fee_rate(&fees).await;
+            let script_hex: String = self.own_coldstake_script(&daemon).await?;
+
+            let mut txids: Vec<String> = Vec::new();
+
+            // An output spent since the plan was made fails on its own, the rest still split
+            for (txid, vout, amount) in &pending.utxos {
+                match daemon
+                    .split_to_script(txid, *vout, *amount, pending.pieces, &script_hex, fee_rate)
+                    .await
+                {
+                    Ok(split_txid) => {
+                        info!(
+                            "Split {}:{} into {} outputs: {}",
+                            txid, vout, pending.pieces, split_txid
+                        );
+                        txids.push(split_txid);
+                    }
+                    Err(err) => warn!("Unable to split {}:{}: {}", txid, vout, err),
+                }
+            }
+
+            if txids.is_empty() {
+                return Err(GvError::new(
+                    ErrorCode::DaemonError,
+                    "Error splitting outputs, none were split.",
+                ));
+            }
+
+            Ok(response::ok(txids))
+        })
+        .await
+    }
+
+    async fn get_events_since(
+        self,
+        _: context::Context,
+        cursor: u64,
+        wait: u64,
+    ) -> GvReply<EventPage> {
+        guarded("get_events_since", async move {
+            // Subscribe before reading the log, an event landing in between is then still seen
+            let mut receiver = self.events.subscribe();
+            let mut events: Vec<GvEvent> =
+                self.db.get_events_since(cursor, EVENT_PAGE_MAX as usize);
+
+            if events.is_empty() && wait > 0 {
+                let wait: Duration = Duration::from_secs(wait.min(EVENT_LONG_POLL_MAX));
+
+                if let Ok(Ok(_)) = tokio::time::timeout(wait, receiver.recv()).await {
+                    events = self.db.get_events_since(cursor, EVENT_PAGE_MAX as usize);
+                }
+            }
+
+            let cursor: u64 = events.last().map_or(cursor, |event| event.id);
+
+            Ok(response::ok(EventPage { cursor, events }))
+        })
+        .await
+    }
+
+    async fn get_digest_settings(self, _: context::Context) -> GvReply<DigestSettings> {
+        guarded("get_digest_settings", async move {
+            let conf = self.gv_config.read().await;
+
+            Ok(response::ok(DigestSettings {
+                daily: conf.digest_daily,
+                weekly: conf.digest_weekly,
+                hour: conf.digest_hour,
+                timezone: conf.timezone.clone(),
+            }))
+        })
+        .await
+    }
+
+    async fn set_digest(
+        self,
+        _: context::Context,
+        period: String,
+        enabled: bool,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_digest",
+            serde_json::json!({ "period": &period, "enabled": enabled }),
+        );
+        audited(audit, async move {
+            let (field, name) = match period.to_lowercase().as_str() {
+                "daily" => ("digest_daily", "Daily"),
+                "weekly" => ("digest_weekly", "Weekly"),
+                _ => {
+                    return Err(GvError::new(
+                        ErrorCode::InvalidArgument,
+                        "Invalid digest, use daily or weekly.",
+                    ))
+                }
+            };
+
+            let mut conf = self.gv_config.write().await;
+            conf.update_gv_config(field, &enabled.to_string())
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+            drop(conf);
+
+            let state: &str = if enabled { "enabled" } else { "disabled" };
+
+            Ok(response::message(format!("{} digest {}!", name, state)))
+        })
+        .await
+    }
+
+    async fn set_digest_time(self, _: context::Context, hour: u32) -> GvReply<String> {
+        let audit: Audit = self.audit("set_digest_time", serde_json::json!({ "hour": hour }));
+        audited(audit, async move {
+            let mut conf = self.gv_config.write().await;
+            conf.update_gv_config("digest_hour", &hour.to_string())
+                .or_code(ErrorCode::InvalidArgument, "Invalid hour, use 0 to 23.")?;
+            let timezone: String = conf.timezone.clone();
+            drop(conf);
+
+            Ok(response::message(format!(
+                "Digests will be sent at {:02}:00 {}.",
+                hour, timezone
+            )))
+        })
+        .await
+    }
+
+    // Each digest covers the last full day or Monday to Sunday week in TIMEZONE, and goes out
+    // on the first check after DIGEST_HOUR. A vault that was down at that hour sends it late.
+    async fn process_digest(self, _: context::Context) -> GvReply<Vec<Digest>> {
+        guarded("process_digest", async move {
+            let conf = self.gv_config.read().await;
+            let daily: bool = conf.digest_daily;
+            let weekly: bool = conf.digest_weekly;
+            let hour: u32 = conf.digest_hour;
+            let tz: Tz = Tz::from_str_insensitive(&conf.timezone).unwrap_or(Tz::UTC);
+            drop(conf);
+
+            if !self.announce_active().await {
+                return Ok(response::ok(Vec::<Digest>::new()));
+            }
+
+            let now: DateTime<Tz> = Utc::now().with_timezone(&tz);
+            let today: NaiveDate = now.date_naive();
+            let past_hour: bool = now.hour() >= hour;
+
+            let mut last_sent: DigestDB = self.db.get_digest();
+            let mut sent: Vec<Digest> = Vec::new();
+
+            let day_start: u64 = time_ranges::local_day_start(&tz, today);
+
+            if daily && past_hour && last_sent.last_daily < day_start {
+                let yesterday: NaiveDate = today.checked_sub_days(Days::new(1)).unwrap();
+                let digest: Digest = self
+                    .build_digest(
+                        "Daily",
+                        time_ranges::local_day_start(&tz, yesterday),
+                        day_start,
+                    )
+                    .await;
+
+                self.send_digest(&digest).await;
+                last_sent.last_daily = day_start;
+                sent.push(digest);
+            }
+
+            let monday: NaiveDate = today
+                .checked_sub_days(Days::new(now.weekday().num_days_from_monday() as u64))
+                .unwrap();
+            let week_start: u64 = time_ranges::local_day_start(&tz, monday);
+
+            if weekly && (today > monday || past_hour) && last_sent.last_weekly < week_start {
+                let last_monday: NaiveDate = monday.checked_sub_days(Days::new(7)).unwrap();
+                let digest: Digest = self
+                    .build_digest(
+                        "Weekly",
+                        time_ranges::local_day_start(&tz, last_monday),
+                        week_start,
+                    )
+                    .await;
+
+                self.send_digest(&digest).await;
+                last_sent.last_weekly = week_start;
+                sent.push(digest);
+            }
+
+            if !sent.is_empty() {
+                self.db
+                    .set_digest(&last_sent)
+                    .await
+                    .or_code(ErrorCode::Internal, "Database error")?;
+            }
+
+            Ok(response::ok(sent))
+        })
+        .await
+    }
+
+    // Only the vault's own wallets are searched, ghostd doesn't keep a transaction index.
+    async fn lookup_tx(self, _: context::Context, txid: String) -> GvReply<TxSummary> {
+        guarded("lookup_tx", async move {
+            let txid: String = txid.trim().to_lowercase();
+
+            if !is_hash(&txid) {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Invalid txid: {}", txid),
+                ));
+            }
+
+            for wallet in ["cold", "hot"] {
+                let daemon: DaemonHelper = match self.utxo_wallet(wallet).await {
+                    Ok(daemon) => daemon,
+                    Err(_) => continue,
+                };
+
+                let tx: Value = match daemon.get_transaction(&txid).await {
+                    Ok(tx) => tx,
+                    Err(_) => continue,
+                };
+
+                let category: String = tx
+                    .get("details")
+                    .and_then(|details| details.get(0))
+                    .and_then(|detail| detail.get("category"))
+                    .and_then(|category| category.as_str())
+                    .unwrap_or("unknown")
+                    .to_string();
+
+                return Ok(response::ok(TxSummary {
+                    url: self.explorer().await.tx_url(&txid),
+                    txid,
+                    wallet: wallet.to_string(),
+                    category,
+                    amount: Amount::from_ghost(
+                        tx.get("amount")
+                            .and_then(|v| v.as_f64())
+                            .unwrap_or(0.0)
+                            .abs(),
+                    ),
+                    fee: Amount::from_ghost(
+                        tx.get("fee").and_then(|v| v.as_f64()).unwrap_or(0.0).abs(),
+                    ),
+                    confirmations: tx
+                        .get("confirmations")
+                        .and_then(|v| v.as_i64())
+                        .unwrap_or_default(),
+                    block_hash: tx
+                        .get("blockhash")
+                        .and_then(|v| v.as_str())
+                        .map(|hash| hash.to_string()),
+                    block_height: tx
+                        .get("blockheight")
+                        .and_then(|v| v.as_u64())
+                        .map(|height| height as u32),
+                    time: tx.get("time").and_then(|v| v.as_u64()).unwrap_or_default(),
+                }));
+            }
+
+            Err(GvError::new(
+                ErrorCode::NotFound,
+                format!("Transaction {} is not in the vault's wallets.", txid),
+            ))
+        })
+        .await
+    }
+
+    // block is a height or a block hash.
+    async fn lookup_block(self, _: context::Context, block: String) -> GvReply<BlockSummary> {
+        guarded("lookup_block", async move {
+            let block: String = block.trim().to_lowercase();
+
+            let block_hash: String = if let Ok(height) = block.parse::<u32>() {
+                self.daemon
+                    .getblockhash(height)
+                    .await
+                    .or_code(ErrorCode::NotFound, "Block height not found")?
+            } else if is_hash(&block) {
+                block
+            } else {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Invalid block height or hash: {}", block),
+                ));
+            };
+
+            let info: Value = self
+                .daemon
+                .getblock(&block_hash, 1)
+                .await
+                .or_code(ErrorCode::NotFound, "Block not found")?;
+
+            Ok(response::ok(BlockSummary {
+                url: self.explorer().await.block_url(&block_hash),
+                hash: block_hash,
+                height: info
+                    .get("height")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or_default() as u32,
+                confirmations: info
+                    .get("confirmations")
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or_default(),
+                time: info
+                    .get("time")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or_default(),
+                tx_count: info.get("nTx").and_then(|v| v.as_u64()).unwrap_or_default() as u32,
+                size: info
+                    .get("size")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or_default(),
+                previous_hash: info
+                    .get("previousblockhash")
+                    .and_then(|v| v.as_str())
+                    .map(|hash| hash.to_string()),
+            }))
+        })
+        .await
+    }
+
+    async fn register_tenant(
+        self,
+        _: context::Context,
+        name: String,
+        key: String,
+        payout_address: String,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "register_tenant",
+            serde_json::json!({ "name": &name, "key": &key, "payout_address": &payout_address }),
+        );
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+
+            let name: String = name.trim().to_string();
+
+            if name.is_empty() {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Tenant name can not be empty.",
+                ));
+            }
+
+            if self.db.get_tenant(&name).is_some() {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Tenant {} is already registered.", name),
+                ));
+            }
+
+            if !self.gv_config.read().await.anon_mode {
+                return Err(GvError::new(
+                    ErrorCode::NotConfigured,
+                    "Shared vaults need anon reward mode, so rewards collect in the vault before they're split.",
+                ));
+            }
+
+            let key_info: Value = self
+                .daemon
+                .validate_address(&key)
+                .await
+                .or_code(ErrorCode::DaemonError, "Error validating key")?;
+
+            let is_valid: bool = key_info
+                .get("isvalid")
+                .and_then(|valid| valid.as_bool())
+                .unwrap_or(false);
+
+            if !is_valid {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Invalid address or ext pub key!",
+                ));
+            }
+
+            let is_ext_key: bool = key_info
+                .get("isextkey")
+                .and_then(|ext_key| ext_key.as_bool())
+                .unwrap_or(false);
+
+            // Stakes are matched on the spend address, so an ext pub key stands in for the
+            // addresses derived from it.
+            let addresses: Vec<String> = if is_ext_key {
+                self.daemon
+                    .derive_range_keys(&key, 0, TENANT_EXT_KEY_LOOKAHEAD - 1)
+                    .await
+                    .or_code(ErrorCode::DaemonError, "Error deriving addresses")?
+            } else {
+                vec![key.clone()]
+            };
+
+            if let Some(other) = self.db.get_tenants().into_iter().find(|tenant| {
+                tenant
+                    .addresses
+                    .iter()
+                    .any(|addr| addresses.contains(addr))
+            }) {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!("{} overlaps with tenant {}.", key, other.name),
+                ));
+            }
+
+            let payout_info: Value = self
+                .daemon
+                .get_address_info(&payout_address)
+                .await
+                .or_code(ErrorCode::InvalidArgument, "Invalid payout address!")?;
+
+            let is_mine: bool = payout_info
+                .get("ismine")
+                .and_then(|mine| mine.as_bool())
+                .unwrap_or(false);
+            let is_256bit: bool = payout_info
+                .get("is256bit")
+                .and_then(|is_256bit| is_256bit.as_bool())
+                .unwrap_or(false);
+
+            if is_mine || is_256bit {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Payout address must be a public or stealth address not owned by GhostVault!",
+                ));
+            }
+
+            let registered: u64 = chrono::Utc::now().timestamp() as u64;
+
+            // Stakes from before the first tenant never get split
+            if self.db.get_shared_vault().is_none() {
+                self.db
+                    .set_shared_vault(&SharedVaultDB {
+                        credited_until: registered,
+                    })
+                    .await
+                    .or_code(ErrorCode::Internal, "Database error")?;
+            }
+
+            let tenant: TenantDB = TenantDB {
+                name: name.clone(),
+                key,
+                addresses,
+                payout_address,
+                registered,
+                owed: 0,
+                earned: 0,
+                paid: 0,
+            };
+
+            self.db
+                .set_tenant(&tenant)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            Ok(response::message(format!(
+                "Tenant {} registered with {} address(es)!",
+                name,
+                tenant.addresses.len()
+            )))
+        })
+        .await
+    }
+
+    async fn remove_tenant(self, _: context::Context, name: String) -> GvReply<String> {
+        let audit: Audit = self.audit("remove_tenant", serde_json::json!({ "name": &name }));
+        audited(audit, async move {
+            let tenant: TenantDB = match self.db.get_tenant(&name) {
+                Some(tenant) => tenant,
+                None => {
+                    return Err(GvError::new(
+                        ErrorCode::NotFound,
+                        format!("No tenant named {}.", name),
+                    ))
+                }
+            };
+
+            self.db
+                .remove_tenant(&name)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            if tenant.owed > 0 {
+                return Ok(response::message(format!(
+                    "Tenant {} removed, the {} GHOST still owed to them stays with the vault.",
+                    name,
+                    Amount::from_sat(tenant.owed)
+                )));
+            }
+
+            Ok(response::message(format!("Tenant {} removed!", name)))
+        })
+        .await
+    }
+
+    async fn get_tenants(self, _: context::Context) -> GvReply<Vec<Tenant>> {
+        guarded("get_tenants", async move {
+            let mut tenants: Vec<Tenant> = Vec::new();
+
+            for tenant in self.db.get_tenants() {
+                tenants.push(Tenant {
+                    name: tenant.name,
+                    key: tenant.key,
+                    addresses: tenant.addresses.len(),
+                    payout_address: tenant.payout_address,
+                    registered: self.get_date_str(tenant.registered).await,
+                    owed: Amount::from_sat(tenant.owed),
+                    earned: Amount::from_sat(tenant.earned),
+                    paid: Amount::from_sat(tenant.paid),
+                });
+            }
+
+            Ok(response::ok(tenants))
+        })
+        .await
+    }
+
+    async fn get_tenant_statement(
+        self,
+        _: context::Context,
+        name: String,
+        start: u64,
+        end: u64,
+    ) -> GvReply<TenantStatement> {
+        guarded("get_tenant_statement", async move {
+            let tenant: TenantDB = match self.db.get_tenant(&name) {
+                Some(tenant) => tenant,
+                None => {
+                    return Err(GvError::new(
+                        ErrorCode::NotFound,
+                        format!("No tenant named {}.", name),
+                    ))
+                }
+            };
+
+            let statement: TenantStatement =
+                self.get_tenant_statement_vec(&tenant, start, end).await;
+
+            Ok(response::ok(statement))
+        })
+        .await
+    }
+
+    async fn process_balance_sample(self, _: context::Context) -> GvReply<()> {
+        guarded("process_balance_sample", async move {
+            if !self.daemon_ready().await {
+                return Ok(response::ok(()));
+            }
+
+            let cs_info: Value = self
+                .daemon
+                .getcoldstakinginfo()
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting cold staking info")?;
+            let balances: Value = self
+                .daemon
+                .get_balances()
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting balances")?;
+
+            let cs_amount = |key: &str| -> Amount {
+                Amount::from_ghost(
+                    cs_info
+                        .get(key)
+                        .and_then(|amount| amount.as_f64())
+                        .unwrap_or(0.0),
+                )
+            };
+            let mine_amount = |key: &str| -> Amount {
+                Amount::from_ghost(
+                    balances
+                        .get("mine")
+                        .and_then(|mine| mine.get(key))
+                        .and_then(|amount| amount.as_f64())
+                        .unwrap_or(0.0),
+                )
+            };
+
+            let sample: BalanceSampleDB = BalanceSampleDB {
+                timestamp: Utc::now().timestamp() as u64,
+                currently_staking: cs_amount("currently_staking").to_sat(),
+                total_coldstaking: cs_amount("coin_in_coldstakeable_script").to_sat(),
+                anon_balance: (mine_amount("anon_trusted")
+                    + mine_amount("anon_immature")
+                    + mine_amount("anon_untrusted_pending"))
+                .to_sat(),
+            };
+
+            self.db
+                .set_balance_sample(&sample)
+                .await
+                .or_code(ErrorCode::Internal, "Error saving balance sample")?;
+
+            Ok(response::ok(()))
+        })
+        .await
+    }
+
+    async fn get_balance_history(
+        self,
+        _: context::Context,
+        start: u64,
+        end: u64,
+    ) -> GvReply<BalanceHistory> {
+        guarded("get_balance_history", async move {
+            let data: Vec<Vec<f64>> = self
+                .db
+                .balance_history(start, end)
+                .into_iter()
+                .map(|sample| {
+                    vec![
+                        sample.timestamp as f64,
+                        Amount::from_sat(sample.currently_staking).to_ghost(),
+                        Amount::from_sat(sample.total_coldstaking).to_ghost(),
+                        Amount::from_sat(sample.anon_balance).to_ghost(),
+                    ]
+                })
+                .collect();
+
+            let range_start: u64 = data.first().map_or(start, |sample| sample[0] as u64);
+            let range_end: u64 = data.last().map_or(end, |sample| sample[0] as u64);
+
+            let history: BalanceHistory = BalanceHistory {
+                data,
+                start: self.get_date_str(range_start).await,
+                end: self.get_date_str(range_end).await,
+            };
+
+            Ok(response::ok(history))
+        })
+        .await
+    }
+
+    async fn get_network_stats(
+        self,
+        _: context::Context,
+        start: u64,
+        end: u64,
+    ) -> GvReply<NetworkStats> {
+        guarded("get_network_stats", async move {
+            let samples: Vec<NetworkSampleDB> = self.db.network_history(start, end);
+
+            let share = |sample: &NetworkSampleDB| -> f64 {
+                if sample.network_weight > 0 {
+                    (sample.our_weight as f64 / sample.network_weight as f64).min(1.0)
+                } else {
+                    0.0
+                }
+            };
+            // Percent change from the first sample to the last
+            let change = |first: f64, last: f64| -> Option<f64> {
+                (samples.len() > 1 && first > 0.0).then(|| (last - first) / first * 100.0)
+            };
+
+            let first: Option<&NetworkSampleDB> = samples.first();
+            let last: Option<&NetworkSampleDB> = samples.last();
+
+            let average_share: f64 = if samples.is_empty() {
+                0.0
+            } else {
+                samples.iter().map(share).sum::<f64>() / samples.len() as f64
+            };
+
+            let stride: usize = samples.len().div_ceil(NETWORK_STATS_MAX_POINTS).max(1);
+            let mut data: Vec<Vec<f64>> = samples
+                .iter()
+                .step_by(stride)
+                .map(|sample| {
+                    vec![
+                        sample.timestamp as f64,
+                        Amount::from_sat(sample.network_weight).to_ghost(),
+                        Amount::from_sat(sample.our_weight).to_ghost(),
+                        sample.difficulty,
+                    ]
+                })
+                .collect();
+
+            // Thinning out can skip the newest sample, the chart should end on it
+            if let Some(last) = last {
+                if data.last().map(|row| row[0] as u64) != Some(last.timestamp) {
+                    data.push(vec![
+                        last.timestamp as f64,
+                        Amount::from_sat(last.network_weight).to_ghost(),
+                        Amount::from_sat(last.our_weight).to_ghost(),
+                        last.difficulty,
+                    ]);
+                }
+            }
+
+            let range_start: u64 = first.map_or(start, |sample| sample.timestamp);
+            let range_end: u64 = last.map_or(end, |sample| sample.timestamp);
+
+            let stats: NetworkStats = NetworkStats {
+                data,
+                start: self.get_date_str(range_start).await,
+                end: self.get_date_str(range_end).await,
+                samples: samples.len() as u64,
+                network_weight: last.map_or(0.0, |sample| {
+                    Amount::from_sat(sample.network_weight).to_ghost()
+                }),
+                our_weight: last
+                    .map_or(0.0, |sample| Amount::from_sat(sample.our_weight).to_ghost()),
+                difficulty: last.map_or(0.0, |sample| sample.difficulty),
+                stake_share: last.map_or(0.0, share),
+                average_share,
+                network_weight_change: first.zip(last).and_then(|(first, last)| {
+                    change(first.network_weight as f64, last.network_weight as f64)
+                }),
+                difficulty_change: first
+                    .zip(last)
+                    .and_then(|(first, last)| change(first.difficulty, last.difficulty)),
+            };
+
+            Ok(response::ok(stats))
+        })
+        .await
+    }
+
+    async fn list_cold_wallets(self, _: context::Context) -> GvReply<ColdWallets> {
+        guarded("list_cold_wallets", async move {
+            let conf = self.gv_config.read().await;
+            let primary: String = conf.rpc_wallet.clone();
+            drop(conf);
+
+            let loaded: Vec<Value> = self
+                .daemon
+                .list_wallets()
+                .await
+                .ok()
+                .and_then(|wallets| wallets.as_array().cloned())
+                .unwrap_or_default();
+
+            let registered: Vec<ColdWalletDB> = self.db.get_cold_wallets();
+            let names: Vec<String> = std::iter::once(primary.clone())
+                .chain(
+                    registered
+                        .iter()
+                        .map(|wallet| wallet.name.clone())
+                        .filter(|name| name != &primary),
+                )
+                .collect();
+
+            let mut wallets: Vec<ColdWalletInfo> = names
+                .into_iter()
+                .map(|name| ColdWalletInfo {
+                    primary: name == primary,
+                    loaded: loaded.contains(&Value::String(name.clone())),
+                    added: registered
+                        .iter()
+                        .find(|wallet| wallet.name == name)
+                        .map(|wallet| wallet.added),
+                    stakes: 0,
+                    rewards: Amount::ZERO,
+                    last_stake: None,
+                    payouts: 0,
+                    paid: Amount::ZERO,
+                    name,
+                })
+                .collect();
+
+            for (_, value) in self.db.rewards_ts_index.iter().flatten() {
+                let stake: RewardsDB = match serde_json::from_slice(&value) {
+                    Ok(stake) => stake,
+                    Err(_) => continue,
+                };
+
+                let wallet_name: &str = stake.wallet_or(&primary);
+                if let Some(wallet) = wallets.iter_mut().find(|wallet| wallet.name == wallet_name) {
+                    wallet.stakes += 1;
+                    wallet.rewards += Amount::from_sat(stake.reward + stake.agvr_reward);
+                    wallet.last_stake = Some(stake.timestamp);
+                }
+            }
+
+            for (_, value) in self.db.payout_history_db.iter().flatten() {
+                let payout: PayoutDB = match serde_json::from_slice(&value) {
+                    Ok(payout) => payout,
+                    Err(_) => continue,
+                };
+
+                let wallet_name: &str = if payout.wallet.is_empty() {
+                    &primary
+                } else {
+                    &payout.wallet
+                };
+                if let Some(wallet) = wallets.iter_mut().find(|wallet| wallet.name == wallet_name) {
+                    wallet.payouts += 1;
+                    wallet.paid += Amount::from_sat(payout.amount);
+                }
+            }
+
+            Ok(response::ok(ColdWallets { primary, wallets }))
+        })
+        .await
+    }
+
+    async fn create_cold_wallet(
+        self,
+        _: context::Context,
+        name: String,
+    ) -> GvReply<ColdWalletCreated> {
+        let audit: Audit = self.audit("create_cold_wallet", serde_json::json!({ "name": &name }));
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+            self.check_new_cold_wallet(&name).await?;
+
+            let daemon: DaemonHelper = DaemonHelper::new(&self.gv_config, "no-wallet").await;
+
+            let seed_value: Value = daemon.create_cold_wallet(&name).await.map_err(|err| {
+                GvError::new(
+                    ErrorCode::DaemonError,
+                    format!("Error creating cold wallet: {}", err),
+                )
+            })?;
+
+            let cold_wallet: ColdWalletDB = ColdWalletDB {
+                name: name.clone(),
+                added: chrono::Utc::now().timestamp() as u64,
+                imported: false,
+                ext_pub_key: None,
+                internal_anon: None,
+                holds_mnemonic: false,
+            };
+            self.db
+                .set_cold_wallet(&cold_wallet)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            info!("Created cold wallet {}", name);
+
+            let created: ColdWalletCreated = ColdWalletCreated {
+                wallet: name,
+                mnemonic: seed_value["mnemonic"]
+                    .as_str()
+                    .or_code(ErrorCode::DaemonError, "Malformed mnemonic response")?
+                    .to_string(),
+            };
+            Ok(response::ok(created))
+        })
+        .await
+    }
+
+    async fn import_cold_wallet(
+        self,
+        _: context::Context,
+        name: String,
+        mnemonic: String,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit("import_cold_wallet", serde_json::json!({ "name": &name }));
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+            self.check_new_cold_wallet(&name).await?;
+
+            let mnemonic: &str = mnemonic.trim();
+
+            let mnemonic_valid: bool = self
+                .daemon
+                .validate_mnemonic(mnemonic)
+                .await
+                .or_code(ErrorCode::DaemonError, "Error validating mnemonic")?;
+
+            if !mnemonic_valid {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Invalid mnemonic!",
+                ));
+            }
+
+            let daemon: DaemonHelper = DaemonHelper::new(&self.gv_config, "no-wallet").await;
+
+            daemon
+                .import_cold_wallet(&name, mnemonic, true)
+                .await
+                .map_err(|err| {
+                    GvError::new(
+                        ErrorCode::DaemonError,
+                        format!("Error importing cold wallet: {}", err),
+                    )
+                })?;
+
+            let cold_wallet: ColdWalletDB = ColdWalletDB {
+                name: name.clone(),
+                added: chrono::Utc::now().timestamp() as u64,
+                imported: true,
+                ext_pub_key: None,
+                internal_anon: None,
+                holds_mnemonic: false,
+            };
+            self.db
+                .set_cold_wallet(&cold_wallet)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            info!("Imported cold wallet {}", name);
+
+            Ok(response::message(format!(
+                "Cold wallet {} imported, it stakes next to the primary wallet.",
+                name
+            )))
+        })
+        .await
+    }
+
+    // The primary wallet is the one that pays out and takes zaps, the old primary keeps staking.
+    async fn switch_cold_wallet(self, _: context::Context, name: String) -> GvReply<String> {
+        let audit: Audit = self.audit("switch_cold_wallet", serde_json::json!({ "name": &name }));
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+
+            let conf = self.gv_config.read().await;
+            let primary: String = conf.rpc_wallet.clone();
+            let ext_pub_key: Option<String> = conf.ext_pub_key.clone();
+            let internal_anon: Option<String> = conf.internal_anon.clone();
+            let has_mnemonic: bool = conf.mnemonic.is_some() || conf.sealed_mnemonic.is_some();
+            drop(conf);
+
+            if name == primary {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!("{} is already the primary wallet.", name),
+                ));
+            }
+
+            let mut target: ColdWalletDB = self.db.get_cold_wallet(&name).or_code(
+                ErrorCode::NotFound,
+                &format!("No cold wallet named {}", name),
+            )?;
+
+            // Keep the old primary's addresses so switching back gives the same ones
+            let mut outgoing: ColdWalletDB =
+                self.db
+                    .get_cold_wallet(&primary)
+                    .unwrap_or_else(|| ColdWalletDB {
+                        name: primary.clone(),
+                        added: chrono::Utc::now().timestamp() as u64,
+                        imported: false,
+                        ext_pub_key: None,
+                        internal_anon: None,
+                        holds_mnemonic: false,
+                    });
+            outgoing.ext_pub_key = ext_pub_key;
+            outgoing.internal_anon = internal_anon;
+            if has_mnemonic && self.db.mnemonic_wallet().is_none() {
+                outgoing.holds_mnemonic = true;
+            }
+            self.db
+                .set_cold_wallet(&outgoing)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            let mut conf = self.gv_config.write().await;
+            conf.update_gv_config("RPC_WALLET", &name)
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+            conf.update_gv_config("EXT_PUB_KEY", target.ext_pub_key.as_deref().unwrap_or(""))
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+            conf.update_gv_config(
+                "INTERNAL_ANON",
+                target.internal_anon.as_deref().unwrap_or(""),
+            )
+            .or_code(ErrorCode::Internal, "Error updating config")?;
+            drop(conf);
+
+            // Loads the wallet and makes any address it doesn't have yet
+            self.daemon
+                .check_wallets(&self.db)
+                .await
+                .or_code(ErrorCode::DaemonError, "Error setting up the wallet")?;
+
+            let mut conf = self.gv_config.write().await;
+            target.ext_pub_key = conf.ext_pub_key.clone();
+            target.internal_anon = conf.internal_anon.clone();
+
+            // Anon mode pays rewards to the primary wallet's own stealth address
+            let anon_address: Option<String> = if conf.anon_mode {
+                conf.internal_anon.clone()
+            } else {
+                None
+            };
+            if let Some(internal_anon) = &anon_address {
+                conf.update_gv_config("REWARD_ADDRESS", internal_anon)
+                    .or_code(ErrorCode::Internal, "Error updating config")?;
+            }
+            drop(conf);
+
+            if let Some(internal_anon) = &anon_address {
+                self.daemon
+                    .set_reward_addr_in_wallet(Some(internal_anon))
+                    .await
+                    .or_code(ErrorCode::DaemonError, "Error setting the reward address")?;
+            }
+
+            self.db
+                .set_cold_wallet(&target)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            info!(
+                "Cold wallet {} is now primary, {} keeps staking",
+                name, primary
+            );
+
+            Ok(response::message(format!(
+                "{} is now the primary wallet, {} keeps staking next to it.",
+                name, primary
+            )))
+        })
+        .await
+    }
+
+    async fn get_cold_wallet_stakes(
+        self,
+        _: context::Context,
+        name: String,
+        start: u64,
+        end: u64,
+        division: String,
+    ) -> GvReply<BarChart> {
+        guarded("get_cold_wallet_stakes", async move {
+            let period: Division = Division::from_name(&division).or_code(
+                ErrorCode::InvalidArgument,
+                &format!("Invalid division: {}", division),
+            )?;
+            let tz: Tz = self.vault_tz().await;
+
+            let conf = self.gv_config.read().await;
+            let primary: String = conf.rpc_wallet.clone();
+            drop(conf);
+
+            if name != primary && self.db.get_cold_wallet(&name).is_none() {
+                return Err(GvError::new(
+                    ErrorCode::NotFound,
+                    format!("No cold wallet named {}", name),
+                ));
+            }
+
+            // Rollups are for the whole vault, so a single wallet's stakes are counted here
+            let mut stakes: BTreeMap<u64, u64> = BTreeMap::new();
+            let mut first_stake: Option<u64> = None;
+            for (_, value) in self
+                .db
+                .rewards_ts_index
+                .range(start.to_be_bytes()..end.saturating_add(1).to_be_bytes())
+                .flatten()
+            {
+                let stake: RewardsDB = match serde_json::from_slice(&value) {
+                    Ok(stake) => stake,
+                    Err(_) => continue,
+                };
+
+                if stake.wallet_or(&primary) != name {
+                    continue;
+                }
+
+                first_stake.get_or_insert(stake.timestamp);
+                *stakes
+                    .entry(period.period_start(&tz, stake.timestamp))
+                    .or_insert(0) += 1;
+            }
+
+            let data: Vec<Vec<u64>> = stakes
+                .into_iter()
+                .map(|(period_start, stakes)| vec![period_start, stakes])
+                .collect();
+
+            let range_start: u64 = if start == 0 {
+                first_stake.unwrap_or(0)
+            } else {
+                start
+            };
+
+            let stake_data: BarChart = BarChart {
+                total_points: data.len() as u64,
+                data,
+                division: period.as_str().to_string(),
+                start: self.get_date_str(range_start).await,
+                end: self.get_date_str(end).await,
+                offset: 0,
+                next_offset: None,
+            };
+
+            Ok(response::ok(stake_data))
+        })
+        .await
+    }
+
+    async fn get_roi(self, _: context::Context) -> GvReply<StakingRoi> {
+        guarded("get_roi", async move {
+            let roi: StakingRoi = self.staking_roi().await;
+            Ok(response::ok(roi))
+        })
+        .await
+    }
+
+    async fn get_stake_barchart_page(
+        self,
+        _: context::Context,
+        start: u64,
+        end: u64,
+        division: String,
+        page: ChartPage,
+    ) -> GvReply<BarChart> {
+        guarded("get_stake_barchart_page", async move {
+            let division: Division = Division::from_name(&division).or_code(
+                ErrorCode::InvalidArgument,
+                &format!("Invalid division: {}", division),
+            )?;
+
+            let mut stake_data: BarChart = self
+                .get_stake_barchart_vec(start, end, division, &self.vault_tz().await)
+                .await;
+
+            // A merged bar keeps the timestamp of its first bar and the stakes of all of them
+            let data: Vec<Vec<u64>> = downsample(stake_data.data, page.max_points, |run| {
+                vec![run[0][0], run.iter().map(|bar| bar[1]).sum()]
+            });
+
+            stake_data.total_points = data.len() as u64;
+            (stake_data.data, stake_data.next_offset) = chart_page(data, &page);
+            stake_data.offset = page.offset;
+
+            Ok(response::ok(stake_data))
+        })
+        .await
+    }
+
+    async fn get_earnings_chart_page(
+        self,
+        _: context::Context,
+        start: u64,
+        end: u64,
+        page: ChartPage,
+    ) -> GvReply<AllTimeEarnigns> {
+        guarded("get_earnings_chart_page", async move {
+            let mut earnings_data: AllTimeEarnigns = self
+                .get_earnings_chart_vec(start, end, &self.vault_tz().await)
+                .await;
+
+            // Earnings are a running total, so the last point of a run stands in for all of it
+            let data: Vec<Vec<f64>> = downsample(earnings_data.data, page.max_points, |run| {
+                run[run.len() - 1].clone()
+            });
+
+            earnings_data.total_points = data.len() as u64;
+            (earnings_data.data, earnings_data.next_offset) = chart_page(data, &page);
+            earnings_data.offset = page.offset;
+
+            Ok(response::ok(earnings_data))
+        })
+        .await
+    }
+
+    async fn process_payouts(self, _: context::Context) {
+        isolated("process_payouts", async move {
+            tokio::spawn(async move {
+                self.payout_round().await;
+            });
+        })
+        .await
+    }
+
+    async fn rescan_wallet(self, _: context::Context, start_height: u32) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "rescan_wallet",
+            serde_json::json!({ "start_height": start_height }),
+        );
+        audited(audit, async move {
+            if let Some(rescan) = self.db.get_wallet_rescan() {
+                if rescan.status == "running" {
+                    return Err(GvError::new(
+                        ErrorCode::AlreadyRunning,
+                        "A wallet rescan is already running!",
+                    ));
+                }
+            }
+
+            let best_block: u32 = self.best_block().await;
+
+            if start_height > best_block {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!(
+                        "Start height {} is above the current best block {}!",
+                        start_height, best_block
+                    ),
+                ));
+            }
+
+            if let Some(prune_height) = self.daemon.prune_height().await {
+                if start_height < prune_height {
+                    return Err(GvError::new(
+                        ErrorCode::InvalidArgument,
+                        format!(
+                            "ghostd is pruned, the rescan can't start below block {}!",
+                            prune_height
+                        ),
+                    ));
+                }
+            }
+
+            let current_time: u64 = chrono::Utc::now().timestamp() as u64;
+
+            let rescan: WalletRescanDB = WalletRescanDB {
+                start_height,
+                status: "scheduled".to_string(),
+                requested: current_time,
+                started: None,
+                finished: None,
+                progress: 0.0,
+                error: None,
+                repair: false,
+            };
+
+            self.db.set_wallet_rescan(&rescan).await.or_code(ErrorCode::Internal, "Database error")?;
+
+            let conf = self.gv_config.read().await;
+            let msg: String = format!(
+                "Wallet rescan from block {} scheduled for the maintenance window ({:02}:00-{:02}:00 {}).",
+                start_height, conf.maint_window_start, conf.maint_window_end, conf.timezone
+            );
+            drop(conf);
+
+            info!("{}", msg);
+            Ok(response::message(msg))
+        })
+        .await
+    }
+
+    // For a wallet that is missing transactions or shows stale ones, like after a restore from
+    // the mnemonic. Runs in the maintenance window like a rescan, the status is shared with it.
+    async fn repair_wallet(self, _: context::Context) -> GvReply<String> {
+        let audit: Audit = self.audit("repair_wallet", serde_json::json!({}));
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+
+            if let Some(rescan) = self.db.get_wallet_rescan() {
+                if rescan.status == "running" {
+                    return Err(GvError::new(
+                        ErrorCode::AlreadyRunning,
+                        "A wallet rescan is already running!",
+                    ));
+                }
+            }
+
+            // Cleared transactions below the prune height could never be found again
+            if self.daemon.prune_height().await.is_some() {
+                return Err(GvError::new(
+                    ErrorCode::InvalidState,
+                    "ghostd is pruned, the wallet can't be rescanned from genesis. Use rescanwallet from the prune height instead.",
+                ));
+            }
+
+            let rescan: WalletRescanDB = WalletRescanDB {
+                start_height: 0,
+                status: "scheduled".to_string(),
+                requested: chrono::Utc::now().timestamp() as u64,
+                started: None,
+                finished: None,
+                progress: 0.0,
+                error: None,
+                repair: true,
+            };
+
+            self.db
+                .set_wallet_rescan(&rescan)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            let conf = self.gv_config.read().await;
+            let msg: String = format!(
+                "Wallet repair scheduled for the maintenance window ({:02}:00-{:02}:00 {}), follow it with gv-cli rescanstatus.",
+                conf.maint_window_start, conf.maint_window_end, conf.timezone
+            );
+            drop(conf);
+
+            info!("{}", msg);
+            Ok(response::message(msg))
+        })
+        .await
+    }
+
+    // Everyone cold staking to one of the vault's wallets, for operators hosting staking for
+    // others. Stakes are matched on the spend address, as that is what a stake is recorded under.
+    async fn get_delegations(self, _: context::Context) -> GvReply<Delegations> {
+        guarded("get_delegations", async move {
+            let conf = self.gv_config.read().await;
+            let primary: String = conf.rpc_wallet.clone();
+            drop(conf);
+
+            let wallets: Vec<String> = std::iter::once(primary)
+                .chain(
+                    self.db
+                        .get_cold_wallets()
+                        .into_iter()
+                        .map(|wallet| wallet.name),
+                )
+                .collect();
+
+            let mut by_address: BTreeMap<String, Delegation> = BTreeMap::new();
+
+            for wallet in wallets {
+                let daemon: DaemonHelper = match self.cold_wallet_daemon(&wallet).await {
+                    Some(daemon) => daemon,
+                    None => continue,
+                };
+
+                let unspent: Vec<Value> = match daemon.list_coldstake_unspent().await {
+                    Ok(unspent) => unspent,
+                    Err(err) => {
+                        warn!("Error listing delegations for {}: {}", wallet, err);
+                        continue;
+                    }
+                };
+
+                for item in unspent {
+                    let spend_address: String = item
+                        .get("address")
+                        .and_then(|address| address.as_str())
+                        .unwrap_or_default()
+                        .to_string();
+                    let amount: f64 = item
+                        .get("amount")
+                        .and_then(|amount| amount.as_f64())
+                        .unwrap_or_default();
+
+                    let entry: &mut Delegation = by_address
+                        .entry(spend_address.clone())
+                        .or_insert_with(|| Delegation {
+                            spend_address,
+                            wallet: wallet.clone(),
+                            utxos: 0,
+                            amount: Amount::ZERO,
+                            share: 0.0,
+                            stakes: 0,
+                            last_stake: None,
+                        });
+
+                    entry.utxos += 1;
+                    entry.amount += Amount::from_ghost(amount);
+                }
+            }
+
+            let now: u64 = chrono::Utc::now().timestamp() as u64;
+            let stakes: StakesByAddress = self.get_stakes_by_address_vec(0, now).await;
+
+            for address in stakes.addresses {
+                if let Some(entry) = by_address.get_mut(&address.address) {
+                    entry.stakes = address.stakes;
+                    entry.last_stake = Some(address.last_stake);
+                }
+            }
+
+            let total: Amount = by_address.values().map(|entry| entry.amount).sum();
+            let mut delegations: Vec<Delegation> = by_address.into_values().collect();
+
+            for entry in delegations.iter_mut() {
+                if total > Amount::ZERO {
+                    entry.share = entry.amount.to_sat() as f64 / total.to_sat() as f64 * 100.0;
+                }
+            }
+            delegations.sort_by_key(|entry| std::cmp::Reverse(entry.amount));
+
+            Ok(response::ok(Delegations {
+                total,
+                utxos: delegations.iter().map(|entry| entry.utxos).sum(),
+                delegations,
+            }))
+        })
+        .await
+    }
+
+    // The zap stakes with a key of the vault and is spent by the owner's reward address, each
+    // open request gets its own stake key so the deposit can be told apart from other zaps.
+    async fn build_zap_request(self, _: context::Context, amount: f64) -> GvReply<ZapRequest> {
+        let audit: Audit = self.audit("build_zap_request", serde_json::json!({ "amount": amount }));
+        audited(audit, async move {
+            if amount.is_nan() || amount <= 0.0 {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "The zap amount must be more than 0.",
+                ));
+            }
+
+            let conf = self.gv_config.read().await;
+            let spend_addr: Option<String> = if conf.anon_mode {
+                conf.anon_reward_address.clone()
+            } else {
+                conf.reward_address.clone()
+            };
+            drop(conf);
+
+            let spend_addr: String = spend_addr.filter(|addr| !addr.is_empty()).ok_or_else(|| {
+                GvError::new(
+                    ErrorCode::NotConfigured,
+                    "Zap requests are spent by your reward address, set one with setrewardmode first.",
+                )
+            })?;
+
+            let addr_info: Value = self
+                .daemon
+                .get_address_info(&spend_addr)
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting address info")?;
+            if addr_info
+                .get("isstealthaddress")
+                .and_then(|stealth| stealth.as_bool())
+                .unwrap_or(false)
+            {
+                return Err(GvError::new(
+                    ErrorCode::InvalidState,
+                    "Zaps can't be spent by a stealth address, use a public reward address.",
+                ));
+            }
+
+            let now: u64 = Utc::now().timestamp() as u64;
+            let mut used: Vec<u32> = Vec::new();
+
+            for request in self.db.get_zap_requests() {
+                if request.txid.is_some() || now - request.requested > ZAP_REQUEST_TTL {
+                    let _ = self.db.remove_zap_request(&request.stake_addr).await;
+                } else {
+                    used.push(request.key_index);
+                }
+            }
+
+            let key_index: u32 = (0..ZAP_REQUEST_KEYS)
+                .find(|index| !used.contains(index))
+                .ok_or_else(|| {
+                    GvError::new(
+                        ErrorCode::InvalidState,
+                        format!(
+                            "There are already {} open zap requests, wait for one to arrive or expire.",
+                            ZAP_REQUEST_KEYS
+                        ),
+                    )
+                })?;
+
+            let stake_addr: String = self
+                .daemon
+                .derive_stake_addr(key_index)
+                .await
+                .or_code(ErrorCode::DaemonError, "Error deriving stake address")?;
+            let script: Value = self
+                .daemon
+                .build_script(&stake_addr, &spend_addr)
+                .await
+                .or_code(ErrorCode::DaemonError, "Error building script")?;
+
+            let amount: Amount = Amount::from_ghost(amount);
+            let script_hex: String = script["hex"].as_str().unwrap_or_default().to_string();
+
+            self.db
+                .set_zap_request(&ZapRequestDB {
+                    key_index,
+                    stake_addr: stake_addr.clone(),
+                    spend_addr: spend_addr.clone(),
+                    amount: amount.to_sat(),
+                    script_hex: script_hex.clone(),
+                    requested: now,
+                    txid: None,
+                })
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            info!("Zap request for {} GHOST to {}", amount, stake_addr);
+
+            Ok(response::ok(ZapRequest {
+                amount,
+                uri: format!(
+                    "ghost:{}?amount={}&stakeaddress={}",
+                    spend_addr, amount, stake_addr
+                ),
+                stake_addr,
+                spend_addr,
+                script_hex,
+                script_asm: script["asm"].as_str().unwrap_or_default().to_string(),
+                expires: now + ZAP_REQUEST_TTL,
+            }))
+        })
+        .await
+    }
+
+    async fn add_expected_deposit(
+        self,
+        _: context::Context,
+        amount: f64,
+        memo: String,
+        hours: Option<u64>,
+    ) -> GvReply<ExpectedDeposit> {
+        let audit: Audit = self.audit(
+            "add_expected_deposit",
+            serde_json::json!({ "amount": amount, "memo": &memo, "hours": hours }),
+        );
+        audited(audit, async move {
+            if amount.is_nan() || amount <= 0.0 {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "The deposit amount must be more than 0.",
+                ));
+            }
+
+            let hours: u64 = hours.unwrap_or(DEFAULT_DEPOSIT_WINDOW_HOURS);
+            if hours == 0 {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "The deposit needs at least an hour to arrive.",
+                ));
+            }
+
+            let now: u64 = Utc::now().timestamp() as u64;
+            let deposit: ExpectedDepositDB = ExpectedDepositDB {
+                id: self.db.next_expected_deposit_id(),
+                amount: Amount::from_ghost(amount).to_sat(),
+                memo: memo.trim().to_string(),
+                created: now,
+                due: now + hours * 60 * 60,
+                txid: None,
+                missed: false,
+            };
+
+            self.db
+                .set_expected_deposit(&deposit)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            info!(
+                "Expecting a deposit of {} GHOST within {} hours",
+                Amount::from_sat(deposit.amount),
+                hours
+            );
+            Ok(response::ok(expected_deposit(&deposit, now)))
+        })
+        .await
+    }
+
+    async fn get_expected_deposits(self, _: context::Context) -> GvReply<Vec<ExpectedDeposit>> {
+        guarded("get_expected_deposits", async move {
+            let now: u64 = Utc::now().timestamp() as u64;
+            let deposits: Vec<ExpectedDeposit> = self
+                .db
+                .get_expected_deposits()
+                .iter()
+                .map(|deposit| expected_deposit(deposit, now))
+                .collect();
+
+            Ok(response::ok(deposits))
+        })
+        .await
+    }
+
+    async fn remove_expected_deposit(self, _: context::Context, id: u64) -> GvReply<String> {
+        let audit: Audit = self.audit("remove_expected_deposit", serde_json::json!({ "id": id }));
+        audited(audit, async move {
+            let removed: bool = self
+                .db
+                .remove_expected_deposit(id)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            if !removed {
+                return Err(GvError::new(
+                    ErrorCode::NotFound,
+                    format!("No expected deposit with id {}!", id),
+                ));
+            }
+
+            Ok(response::message(format!(
+                "Expected deposit {} removed.",
+                id
+            )))
+        })
+        .await
+    }
+
+    async fn set_tx_label(
+        self,
+        _: context::Context,
+        txid: String,
+        label: String,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_tx_label",
+            serde_json::json!({ "txid": &txid, "label": &label }),
+        );
+        audited(audit, async move {
+            let label: String = label.trim().to_string();
+
+            // An empty label removes the one the transaction has
+            if label.is_empty() {
+                let removed: bool = self
+                    .db
+                    .remove_tx_label(&txid)
+                    .await
+                    .or_code(ErrorCode::Internal, "Database error")?;
+
+                if !removed {
+                    return Err(GvError::new(
+                        ErrorCode::NotFound,
+                        format!("Transaction {} has no label!", txid),
+                    ));
+                }
+
+                return Ok(response::message(format!("Label removed from {}.", txid)));
+            }
+
+            if label.chars().count() > TX_LABEL_MAX_LEN {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Labels can be at most {} characters.", TX_LABEL_MAX_LEN),
+                ));
+            }
+
+            if self.daemon.get_transaction(&txid).await.is_err() {
+                return Err(GvError::new(
+                    ErrorCode::NotFound,
+                    format!("Transaction {} isn't in the wallet!", txid),
+                ));
+            }
+
+            let tx_label: TxLabelDB = TxLabelDB {
+                txid: txid.clone(),
+                label: label.clone(),
+                updated: Utc::now().timestamp() as u64,
+            };
+            self.db
+                .set_tx_label(&tx_label)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            Ok(response::message(format!(
+                "Transaction {} labeled \"{}\".",
+                txid, label
+            )))
+        })
+        .await
+    }
+
+    async fn get_tx_labels(self, _: context::Context) -> GvReply<Vec<TxLabel>> {
+        guarded("get_tx_labels", async move {
+            let mut labels: Vec<TxLabel> = self
+                .db
+                .get_tx_labels()
+                .into_iter()
+                .map(|label| TxLabel {
+                    txid: label.txid,
+                    label: label.label,
+                    updated: label.updated,
+                })
+                .collect();
+
+            labels.sort_by_key(|label| std::cmp::Reverse(label.updated));
+
+            Ok(response::ok(labels))
+        })
+        .await
+    }
+
+    async fn set_address_label(
+        self,
+        _: context::Context,
+        address: String,
+        label: String,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_address_label",
+            serde_json::json!({ "address": &address, "label": &label }),
+        );
+        audited(audit, async move {
+            let address: String = address.trim().to_string();
+            let label: String = label.trim().to_string();
+
+            // An empty label takes the address out of the book
+            if label.is_empty() {
+                let removed: bool = self
+                    .db
+                    .remove_address_label(&address)
+                    .await
+                    .or_code(ErrorCode::Internal, "Database error")?;
+
+                if !removed {
+                    return Err(GvError::new(
+                        ErrorCode::NotFound,
+                        format!("{} isn't in the address book!", address),
+                    ));
+                }
+
+                return Ok(response::message(format!(
+                    "{} removed from the address book.",
+                    address
+                )));
+            }
+
+            if label.chars().count() > ADDRESS_LABEL_MAX_LEN {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!(
+                        "Labels can be at most {} characters.",
+                        ADDRESS_LABEL_MAX_LEN
+                    ),
+                ));
+            }
+
+            if self.daemon.get_address_info(&address).await.is_err() {
+                return Err(GvError::new(ErrorCode::InvalidArgument, "Invalid address!"));
+            }
+
+            // A label can stand in for its address, so it has to pick out one
+            if let Some(entry) = self.db.find_labeled_address(&label) {
+                if entry.address != address {
+                    return Err(GvError::new(
+                        ErrorCode::InvalidArgument,
+                        format!("\"{}\" already names {}.", entry.label, entry.address),
+                    ));
+                }
+            }
+
+            let entry: AddressBookDB = AddressBookDB {
+                address: address.clone(),
+                label: label.clone(),
+                updated: Utc::now().timestamp() as u64,
+            };
+            self.db
+                .set_address_label(&entry)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            Ok(response::message(format!(
+                "{} saved as \"{}\".",
+                address, label
+            )))
+        })
+        .await
+    }
+
+    async fn get_address_book(self, _: context::Context) -> GvReply<Vec<AddressBookEntry>> {
+        guarded("get_address_book", async move {
+            let whitelist: Vec<String> =
+                self.gv_config.read().await.reward_address_whitelist.clone();
+
+            let mut entries: Vec<AddressBookEntry> = self
+                .db
+                .get_address_book()
+                .into_iter()
+                .map(|entry| AddressBookEntry {
+                    whitelisted: whitelist.contains(&entry.address),
+                    address: entry.address,
+                    label: entry.label,
+                    updated: entry.updated,
+                })
+                .collect();
+
+            entries.sort_by_cached_key(|entry| entry.label.to_lowercase());
+
+            Ok(response::ok(entries))
+        })
+        .await
+    }
+
+    async fn get_rescan_status(self, _: context::Context) -> GvReply<WalletRescanDB> {
+        guarded("get_rescan_status", async move {
+            match self.db.get_wallet_rescan() {
+                Some(rescan) => Ok(response::ok(rescan)),
+                None => Err(GvError::new(
+                    ErrorCode::NotFound,
+                    "No wallet rescan has been scheduled.",
+                )),
+            }
+        })
+        .await
+    }
+
+    async fn process_wallet_rescan(self, _: context::Context) {
+        isolated("process_wallet_rescan", async move {
+            let mut rescan: WalletRescanDB = match self.db.get_wallet_rescan() {
+                Some(rescan) if rescan.status == "scheduled" => rescan,
+                _ => return,
+            };
+
+            rescan.status = "running".to_string();
+            rescan.started = Some(chrono::Utc::now().timestamp() as u64);
+            rescan.finished = None;
+            self.db.set_wallet_rescan(&rescan).await.unwrap();
+
+            tokio::spawn(async move {
+                self.do_wallet_rescan(rescan).await;
+            });
+        })
+        .await
+    }
+
+    async fn create_hot_wallet(
+        self,
+        _: context::Context,
+        name: String,
+    ) -> GvReply<HotWalletCreated> {
+        let audit: Audit = self.audit("create_hot_wallet", serde_json::json!({ "name": &name }));
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+
+            let name: String = if name.is_empty() {
+                DEFAULT_HOT_WALLET.to_string()
+            } else {
+                name
+            };
+
+            let conf = self.gv_config.read().await;
+            let cold_wallet: String = conf.rpc_wallet.clone();
+            drop(conf);
+
+            if name == cold_wallet {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "The hot wallet can not be the cold staking wallet!",
+                ));
+            }
+
+            let hot_daemon: DaemonHelper = DaemonHelper::new(&self.gv_config, "no-wallet").await;
+
+            match hot_daemon.create_hot_wallet(&name).await {
+                Ok(seed_value) => {
+                    info!("Created hot wallet {}", name);
+                    let created: HotWalletCreated = HotWalletCreated {
+                        wallet: name,
+                        mnemonic: seed_value["mnemonic"]
+                            .as_str()
+                            .or_code(ErrorCode::DaemonError, "Malformed mnemonic response")?
+                            .to_string(),
+                    };
+                    Ok(response::ok(created))
+                }
+                Err(err) => Err(GvError::new(
+                    ErrorCode::DaemonError,
+                    format!("Error creating hot wallet: {}", err),
+                )),
+            }
+        })
+        .await
+    }
+
+    async fn link_hot_wallet(self, _: context::Context, name: String) -> GvReply<String> {
+        let audit: Audit = self.audit("link_hot_wallet", serde_json::json!({ "name": &name }));
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+
+            let conf = self.gv_config.read().await;
+            let cold_wallet: String = conf.rpc_wallet.clone();
+            drop(conf);
+
+            if name.is_empty() {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "No wallet name given!",
+                ));
+            } else if name == cold_wallet {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "The hot wallet can not be the cold staking wallet!",
+                ));
+            }
+
+            if let Err(err) = self.daemon.load_wallet(&name).await {
+                return Err(GvError::new(
+                    ErrorCode::DaemonError,
+                    format!("Error loading wallet {}: {}", name, err),
+                ));
+            }
+
+            let mut conf = self.gv_config.write().await;
+            let update_res = conf.update_gv_config("RPC_WALLET_HOT", &name);
+            drop(conf);
+
+            match update_res {
+                Ok(_) => {
+                    info!("Linked hot wallet {}", name);
+                    Ok(response::message(format!("Hot wallet {} linked!", name)))
+                }
+                Err(err) => Err(GvError::new(
+                    ErrorCode::DaemonError,
+                    format!("Error linking hot wallet: {}", err),
+                )),
+            }
+        })
+        .await
+    }
+
+    async fn get_hot_wallet_info(self, _: context::Context) -> GvReply<HotWalletInfo> {
+        guarded("get_hot_wallet_info", async move {
+            let hot_wallet_info: HotWalletInfo = self.get_hot_wallet_data().await;
+            Ok(response::ok(hot_wallet_info))
+        })
+        .await
+    }
+
+    async fn get_hot_wallet_script(self, _: context::Context) -> GvReply<HotWalletScript> {
+        let audit: Audit = self.audit("get_hot_wallet_script", serde_json::json!({}));
+        audited(audit, async move {
+            let conf = self.gv_config.read().await;
+            let wallet: String = conf.rpc_wallet_hot.clone();
+            drop(conf);
+
+            if !self.hot_wallet_loaded(&wallet).await {
+                return Err(GvError::new(
+                    ErrorCode::NotFound,
+                    format!("Hot wallet {} is not loaded!", wallet),
+                ));
+            }
+
+            let hot_daemon: DaemonHelper = DaemonHelper::new(&self.gv_config, "hot").await;
+
+            // Stake with the cold wallet, spend with the hot wallet.
+            let stake_addr: String = match self.daemon.get_stake_addr().await {
+                Ok(addr) => addr,
+                Err(err) => {
+                    return Err(GvError::new(
+                        ErrorCode::DaemonError,
+                        format!("Error getting stake address: {}", err),
+                    ))
+                }
+            };
+            let spend_addr: String = match hot_daemon.getnewaddress().await {
+                Ok(addr) => addr
+                    .as_str()
+                    .or_code(ErrorCode::DaemonError, "Malformed getnewaddress response")?
+                    .to_string(),
+                Err(err) => {
+                    return Err(GvError::new(
+                        ErrorCode::DaemonError,
+                        format!("Error getting spend address: {}", err),
+                    ))
+                }
+            };
+
+            match self.daemon.build_script(&stake_addr, &spend_addr).await {
+                Ok(script) => {
+                    let hot_wallet_script: HotWalletScript = HotWalletScript {
+                        stake_addr,
+                        spend_addr,
+                        script_hex: script["hex"].as_str().unwrap_or_default().to_string(),
+                        script_asm: script["asm"].as_str().unwrap_or_default().to_string(),
+                    };
+                    Ok(response::ok(hot_wallet_script))
+                }
+                Err(err) => Err(GvError::new(
+                    ErrorCode::DaemonError,
+                    format!("Error building script: {}", err),
+                )),
+            }
+        })
+        .await
+    }
+
+    async fn describe_api(self, _: context::Context) -> GvReply<Vec<ApiMethod>> {
+        guarded("describe_api", async move {
+            Ok(response::ok(api_schema::describe_api()))
+        })
+        .await
+    }
+
+    async fn get_api_capabilities(
+        self,
+        _: context::Context,
+        client_api_version: u32,
+    ) -> GvReply<ApiCapabilities> {
+        guarded("get_api_capabilities", async move {
+            if client_api_version != API_VERSION {
+                warn!(
+                    "Client speaks API version {}, this server version {}",
+                    client_api_version, API_VERSION
+                );
+            }
+
+            let capabilities: ApiCapabilities = ApiCapabilities {
+                api_version: API_VERSION,
+                gv_version: VERSION.to_string(),
+                features: ApiCapabilities::feature_list(CARGO_FEATURES),
+                methods: Some(api_schema::method_names()),
+            };
+
+            Ok(response::ok(capabilities))
+        })
+        .await
+    }
+
+    async fn confirm_update(self, _: context::Context, token: String) -> GvReply<String> {
+        let audit: Audit = self.audit("confirm_update", serde_json::json!({ "token": &token }));
+        audited(audit, async move {
+            let pending: PendingUpdateDB = match self.db.get_pending_update() {
+                Some(pending) => pending,
+                None => return Err(GvError::new(ErrorCode::NotFound, "No update is pending!")),
+            };
+
+            let now: u64 = Utc::now().timestamp() as u64;
+
+            if pending.created + PENDING_UPDATE_TTL < now {
+                self.db
+                    .remove_pending_update()
+                    .await
+                    .or_code(ErrorCode::Internal, "Database error")?;
+                return Err(GvError::new(
+                    ErrorCode::Expired,
+                    "Update token expired, check for updates again!",
+                ));
+            }
+
+            if pending.token != token {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Invalid update token!",
+                ));
+            }
+
+            let operation: OperationGuard = self.operations.start("daemon_update").await?;
+
+            self.db
+                .remove_pending_update()
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            info!("Daemon update to v{} confirmed.", pending.version);
+
+            let version: String = pending.version.clone();
+            tokio::spawn(async move {
+                let res = self.do_update(&version).await;
+                operation.finish(res).await;
+            });
+
+            Ok(response::ok(pending.version))
+        })
+        .await
+    }
+
+    // Offline mode, installs a release archive that was copied to the vault by hand.
+    async fn install_daemon_archive(self, _: context::Context, path: String) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "install_daemon_archive",
+            serde_json::json!({ "path": &path }),
+        );
+        audited(audit, async move {
+            let conf = self.gv_config.read().await;
+            let system_daemon: bool = conf.system_daemon;
+            let source: DaemonSource = conf.daemon_source();
+            drop(conf);
+
+            if system_daemon {
+                return Err(GvError::new(
+                    ErrorCode::Disabled,
+                    "System ghostd in use, update it outside of GhostVault!",
+                ));
+            }
+
+            let archive_path: PathBuf = PathBuf::from(path.trim());
+
+            let version: String =
+                gv_methods::archive_version(&archive_path).ok_or(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Not a ghostd release archive, expected ghost-<version>-<triple>.tar.gz",
+                ))?;
+
+            gv_methods::verify_daemon_archive(&archive_path, &source)
+                .or_code(ErrorCode::InvalidArgument, "Archive failed verification")?;
+
+            let mut daemon_ready: ServerReadyDB = self.db.get_server_ready().unwrap();
+
+            if !daemon_ready.daemon_ready {
+                return Err(GvError::new(
+                    ErrorCode::Unavailable,
+                    "Ghost daemon is busy, try again later!",
+                ));
+            }
+
+            let operation: OperationGuard = self.operations.start("daemon_update").await?;
+
+            info!(
+                "Installing ghostd v{} from {}",
+                version,
+                archive_path.display()
+            );
+
+            daemon_ready.daemon_ready = false;
+            daemon_ready.reason = Some("Daemon update in progress".to_string());
+            self.db
+                .set_server_ready(&daemon_ready)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            let release: String = version.clone();
+            tokio::spawn(async move {
+                let res = self
+                    .install_daemon(&archive_path, &release, daemon_ready)
+                    .await;
+                operation.finish(res).await;
+            });
+
+            Ok(response::ok(version))
+        })
+        .await
+    }
+
+    // Holds managed updates at one ghostd release, an empty version lifts the pin and goes back
+    // to the stable channel.
+    async fn pin_daemon_version(self, _: context::Context, version: String) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "pin_daemon_version",
+            serde_json::json!({ "version": &version }),
+        );
+        audited(audit, async move {
+            let channel: UpdateChannel = if version.trim().is_empty() {
+                UpdateChannel::Stable
+            } else {
+                match UpdateChannel::from_name(&version) {
+                    Some(UpdateChannel::Pinned(version)) => UpdateChannel::Pinned(version),
+                    _ => {
+                        return Err(GvError::new(
+                            ErrorCode::InvalidArgument,
+                            "Invalid ghostd version, expected a version like 0.21.1.9",
+                        ));
+                    }
+                }
+            };
+
+            let mut conf = self.gv_config.write().await;
+            conf.update_gv_config("update_channel", channel.as_str())
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+            drop(conf);
+
+            // Anything staged or deferred was for the old channel and could undo the pin
+            let mut maintenance: MaintenanceDB = self.db.get_maintenance();
+            if maintenance.update.take().is_some() {
+                self.db
+                    .set_maintenance(&maintenance)
+                    .await
+                    .or_code(ErrorCode::Internal, "Database error")?;
+            }
+
+            self.db
+                .remove_pending_update()
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            let msg: String = match &channel {
+                UpdateChannel::Pinned(version) => {
+                    info!("ghostd pinned to v{}", version);
+                    format!(
+                        "ghostd pinned to v{}, updatedaemon installs it if it isn't running already.",
+                        version
+                    )
+                }
+                _ => {
+                    info!("ghostd pin lifted");
+                    "ghostd pin lifted, updates follow the stable channel again.".to_string()
+                }
+            };
+
+            Ok(response::message(msg))
+        })
+        .await
+    }
+
+    async fn get_maintenance_window(self, _: context::Context) -> GvReply<MaintenanceWindow> {
+        guarded("get_maintenance_window", async move {
+            Ok(response::ok(self.maintenance_window().await))
+        })
+        .await
+    }
+
+    async fn get_operation_status(
+        self,
+        _: context::Context,
+        op_id: String,
+    ) -> GvReply<OperationStatus> {
+        guarded("get_operation_status", async move {
+            if !OPERATIONS.contains(&op_id.as_str()) {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!(
+                        "Unknown operation {}, use one of: {}",
+                        op_id,
+                        OPERATIONS.join(", ")
+                    ),
+                ));
+            }
+
+            let mut status: OperationStatus = self.operations.status(&op_id);
+
+            if status.state == OperationState::Running {
+                status.progress = self
+                    .db
+                    .get_server_ready()
+                    .and_then(|server_ready| server_ready.reason);
+            }
+
+            Ok(response::ok(status))
+        })
+        .await
+    }
+
+    async fn get_jobs(self, _: context::Context, limit: u64) -> GvReply<Vec<JobDB>> {
+        guarded("get_jobs", async move {
+            let limit: usize = if limit == 0 {
+                JOBS_DEFAULT
+            } else {
+                limit as usize
+            };
+
+            let mut jobs: Vec<JobDB> = self.db.get_jobs(limit);
+
+            // The jobs that take ghostd away report how far along they are in the unready reason
+            let reason: Option<String> = self
+                .db
+                .get_server_ready()
+                .and_then(|server_ready| server_ready.reason);
+
+            for job in jobs.iter_mut() {
+                if job.state == "running"
+                    && job.progress.is_none()
+                    && job.kind != "payout"
+                    && job.kind != "backup"
+                {
+                    job.progress = reason.clone();
+                }
+            }
+
+            Ok(response::ok(jobs))
+        })
+        .await
+    }
+
+    async fn get_scheduled_tasks(self, _: context::Context) -> GvReply<Vec<ScheduledTask>> {
+        guarded("get_scheduled_tasks", async move {
+            let tasks: Vec<ScheduledTask> = task_runner::get_scheduled_tasks(&self.db)
+                .into_iter()
+                .map(|task| ScheduledTask {
+                    name: task.name,
+                    interval: task.run_interval as u64,
+                    last_run: task.last_run.map(|last_run| last_run as u64),
+                    last_result: task.last_result,
+                    next_run: task.next_run as u64,
+                    running: task.task_running,
+                    paused: task.paused,
+                })
+                .collect();
+
+            Ok(response::ok(tasks))
+        })
+        .await
+    }
+
+    async fn run_task_now(self, _: context::Context, name: String) -> GvReply<String> {
+        let audit: Audit = self.audit("run_task_now", serde_json::json!({ "name": &name }));
+        audited(audit, async move {
+            let task: Task = self.scheduled_task(&name)?;
+
+            if task.task_running {
+                return Err(GvError::new(
+                    ErrorCode::AlreadyRunning,
+                    format!("{} is already running.", name),
+                ));
+            }
+
+            if task.paused {
+                return Err(GvError::new(
+                    ErrorCode::InvalidState,
+                    format!("{} is paused, resume it first.", name),
+                ));
+            }
+
+            task_runner::run_task_now(&self.db, &name)
+                .await
+                .or_code(ErrorCode::Internal, "Error updating task")?;
+
+            Ok(response::message(format!(
+                "{} will run within a few seconds.",
+                name
+            )))
+        })
+        .await
+    }
+
+    async fn pause_task(self, _: context::Context, name: String, paused: bool) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "pause_task",
+            serde_json::json!({ "name": &name, "paused": paused }),
+        );
+        audited(audit, async move {
+            self.scheduled_task(&name)?;
+
+            task_runner::pause_task(&self.db, &name, paused)
+                .await
+                .or_code(ErrorCode::Internal, "Error updating task")?;
+
+            if paused {
+                Ok(response::message(format!(
+                    "{} paused, it won't run until it's resumed.",
+                    name
+                )))
+            } else {
+                Ok(response::message(format!("{} resumed.", name)))
+            }
+        })
+        .await
+    }
+
+    async fn set_maintenance_window(
+        self,
+        _: context::Context,
+        start: u32,
+        end: u32,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_maintenance_window",
+            serde_json::json!({ "start": start, "end": end }),
+        );
+        audited(audit, async move {
+            let mut conf = self.gv_config.write().await;
+            conf.update_gv_config("maint_window_start", &start.to_string())
+                .or_code(
+                    ErrorCode::InvalidArgument,
+                    "Invalid start hour, use 0 to 23.",
+                )?;
+            conf.update_gv_config("maint_window_end", &end.to_string())
+                .or_code(ErrorCode::InvalidArgument, "Invalid end hour, use 0 to 23.")?;
+            let timezone: String = conf.timezone.clone();
+            drop(conf);
+
+            if start == end {
+                return Ok(response::message(
+                    "Maintenance window removed, updates and resyncs run right away.",
+                ));
+            }
+
+            Ok(response::message(format!(
+                "Maintenance window set to {:02}:00 to {:02}:00 {}.",
+                start, end, timezone
+            )))
+        })
+        .await
+    }
+
+    // Runs whatever was deferred, called by the task runner once the window opens.
+    async fn process_maintenance(self, _: context::Context) -> GvReply<bool> {
+        guarded("process_maintenance", async move {
+            if !task_runner::in_maint_window(&self.gv_config).await {
+                return Ok(response::ok(false));
+            }
+
+            let maintenance: MaintenanceDB = self.db.get_maintenance();
+
+            if maintenance.update.is_none() && !maintenance.resync {
+                return Ok(response::ok(false));
+            }
+
+            self.db
+                .set_maintenance(&MaintenanceDB::default())
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            // One after the other, a resync on top of an update would fight over ghostd
+            tokio::spawn(async move {
+                if let Some(version) = maintenance.update {
+                    match self.operations.start("daemon_update").await {
+                        Ok(operation) => {
+                            info!("Maintenance window open, updating ghostd to v{}", version);
+                            let res = self.do_update(&version).await;
+                            operation.finish(res).await;
+                        }
+                        Err(_) => {
+                            warn!("A ghostd update is already running, skipping v{}", version)
+                        }
+                    }
+                }
+
+                if maintenance.resync {
+                    match self.operations.start("force_resync").await {
+                        Ok(operation) => {
+                            info!("Maintenance window open, starting the scheduled resync");
+                            self.do_force_resync().await;
+                            operation.finish(Ok(())).await;
                         }
+                        Err(_) => warn!("A resync is already running, skipping the scheduled one"),
                     }
                 }
+            });
+
+            Ok(response::ok(true))
+        })
+        .await
+    }
+
+    async fn get_system_stats(self, _: context::Context) -> GvReply<SystemStats> {
+        guarded("get_system_stats", async move {
+            let sample: SystemSampleDB = self.system_sample().await;
+            let day_start: u64 = sample.timestamp.saturating_sub(60 * 60 * 24);
+            let day_ago: Option<SystemSampleDB> = self
+                .db
+                .system_sample_before(day_start)
+                .filter(|then| then.timestamp + 2 * SYSTEM_SAMPLE_INTERVAL >= day_start);
+
+            // None without a sample from a day ago, or when a counter went back after a restart
+            let growth =
+                |now: Option<u64>, then: Option<u64>| -> Option<u64> { now?.checked_sub(then?) };
+
+            let sys: System = System::new();
+            let load_avg: CpuLoad = self.load(&sys);
+            let uptime: String = format_duration(sys.uptime().unwrap_or_default()).to_string();
+
+            let conf = self.gv_config.read().await;
+            let disk_alert_gb: u64 = conf.disk_alert_gb;
+            let daemon_data_dir: String = conf.daemon_data_dir.display().to_string();
+            let gv_home: String = conf.gv_home.display().to_string();
+            drop(conf);
+
+            let stats: SystemStats = SystemStats {
+                uptime,
+                load_one: load_avg.one,
+                load_five: load_avg.five,
+                load_fifteen: load_avg.fifteen,
+                daemon_data_dir,
+                daemon_disk_free: sample.daemon_disk_free,
+                daemon_disk_total: sample.daemon_disk_total,
+                gv_home,
+                gv_disk_free: sample.gv_disk_free,
+                gv_disk_total: sample.gv_disk_total,
+                chain_size: sample.chain_size,
+                chain_growth_24h: growth(
+                    sample.chain_size,
+                    day_ago.as_ref().and_then(|then| then.chain_size),
+                ),
+                net_recv_24h: growth(
+                    sample.net_bytes_recv,
+                    day_ago.as_ref().and_then(|then| then.net_bytes_recv),
+                ),
+                net_sent_24h: growth(
+                    sample.net_bytes_sent,
+                    day_ago.as_ref().and_then(|then| then.net_bytes_sent),
+                ),
+                disk_alert_gb,
+                low_disk: low_disk(
+                    sample.daemon_disk_free,
+                    sample.daemon_disk_total,
+                    disk_alert_gb,
+                ) || low_disk(sample.gv_disk_free, sample.gv_disk_total, disk_alert_gb),
+                db_size: self.db.gvdb.size_on_disk().ok(),
+                db_maintained: self.db.get_db_maintenance().map(|report| report.timestamp),
+            };
+
+            Ok(response::ok(stats))
+        })
+        .await
+    }
+
+    // Checks and repairs the database, the task runner calls this weekly in the maintenance window.
+    async fn db_maintenance(self, _: context::Context) -> GvReply<DbMaintenanceDB> {
+        let audit: Audit = self.audit("db_maintenance", serde_json::json!({}));
+        audited(audit, async move {
+            info!("Running database maintenance...");
+
+            let report: DbMaintenanceDB = self
+                .db
+                .run_maintenance()
+                .await
+                .or_code(ErrorCode::Internal, "Database maintenance failed")?;
+
+            self.db
+                .set_db_maintenance(&report)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            info!(
+                "Database maintenance done in {} ms, {} stakes checked, {} entries repaired, {} bytes on disk",
+                report.duration_ms,
+                report.rewards,
+                report.repaired(),
+                report.size_after
+            );
+
+            for read_error in report.read_errors.iter() {
+                error!("Database read error in {}", read_error);
+            }
+
+            if report.unreadable_rewards > 0 {
+                warn!(
+                    "{} stakes in the rewards index can't be read, a wallet rescan records them again",
+                    report.unreadable_rewards
+                );
+            }
+
+            Ok(response::ok(report))
+        })
+        .await
+    }
+
+    async fn set_daemon_prune(self, _: context::Context, size_mb: u64) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_daemon_prune",
+            serde_json::json!({ "size_mb": size_mb }),
+        );
+        audited(audit, async move {
+            let mut conf = self.gv_config.write().await;
+            conf.update_gv_config("daemon_prune", &size_mb.to_string())
+                .or_code(
+                    ErrorCode::InvalidArgument,
+                    &format!("Use 0 for a full node or at least {} MiB", MIN_DAEMON_PRUNE),
+                )?;
+            let prune_change: PruneChange = conf
+                .prune_change()
+                .or_code(ErrorCode::Internal, "Error reading ghost.conf")?;
+            drop(conf);
+
+            let msg: String = match prune_change {
+                PruneChange::None => {
+                    return Ok(response::message("ghostd prune setting unchanged."))
+                }
+                PruneChange::Prune => format!(
+                    "Restarting ghostd to keep {} MiB of blocks, the address index is turned off.",
+                    size_mb
+                ),
+                PruneChange::Unprune => {
+                    "Pruning turned off, ghostd will download the whole chain again.".to_string()
+                }
+            };
+
+            tokio::spawn(async move {
+                self.do_daemon_prune(prune_change).await;
+            });
+
+            Ok(response::message(msg))
+        })
+        .await
+    }
+
+    async fn bootstrap_sync(self, _: context::Context) -> GvReply<String> {
+        let audit: Audit = self.audit("bootstrap_sync", serde_json::json!({}));
+        audited(audit, async move {
+            let conf = self.gv_config.read().await;
+            let source: BootstrapSource = conf.bootstrap_source().or_code(
+                ErrorCode::Disabled,
+                "No bootstrap set, add BOOTSTRAP_URL to the config first",
+            )?;
+            drop(conf);
+
+            if let Some(bootstrap) = self.db.get_bootstrap() {
+                if bootstrap.status == "downloading" || bootstrap.status == "installing" {
+                    return Err(GvError::new(
+                        ErrorCode::AlreadyRunning,
+                        "A blockchain bootstrap is already running!",
+                    ));
+                }
             }
+
+            tokio::spawn(async move {
+                self.do_bootstrap_sync().await;
+            });
+
+            Ok(response::message(format!(
+                "Downloading the blockchain bootstrap from {}, ghostd keeps running until it's ready to install.",
+                source.url
+            )))
+        })
+        .await
+    }
+
+    async fn set_log_level(
+        self,
+        _: context::Context,
+        level: String,
+        module: String,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_log_level",
+            serde_json::json!({ "level": &level, "module": &module }),
+        );
+        audited(audit, async move {
+            let module: Option<&str> = Some(module.as_str()).filter(|module| !module.is_empty());
+
+            logging::set_level(&level, module)
+                .or_code(ErrorCode::InvalidArgument, "Unable to set the log level")?;
+
+            let msg: String = match module {
+                Some(module) => format!(
+                    "Log level for {} set to {} until GhostVault restarts.",
+                    module,
+                    level.to_lowercase()
+                ),
+                None => format!(
+                    "Log level set to {} until GhostVault restarts.",
+                    level.to_lowercase()
+                ),
+            };
+
+            info!("{}", msg);
+            Ok(response::message(msg))
+        })
+        .await
+    }
+
+    async fn get_logs(
+        self,
+        _: context::Context,
+        lines: u64,
+        level_filter: String,
+        daemon: bool,
+    ) -> GvReply<RecentLogs> {
+        let audit: Audit = self.audit(
+            "get_logs",
+            serde_json::json!({ "lines": lines, "level_filter": &level_filter, "daemon": daemon }),
+        );
+        audited(audit, async move {
+            let lines: u64 = match lines {
+                0 => DEFAULT_LOG_TAIL_LINES,
+                lines => lines.min(LOG_TAIL_MAX_LINES),
+            };
+            let lines: usize = lines as usize;
+
+            let level: Option<log::Level> = if level_filter.is_empty() {
+                None
+            } else {
+                Some(log::Level::from_str(&level_filter).map_err(|_| {
+                    GvError::new(
+                        ErrorCode::InvalidArgument,
+                        format!(
+                            "Invalid log level '{}', use error, warn, info, debug or trace.",
+                            level_filter
+                        ),
+                    )
+                })?)
+            };
+
+            let conf = self.gv_config.read().await;
+            let gv_log: PathBuf = conf.gv_home.join(GV_LOG_FILE);
+            let daemon_log: PathBuf = conf.daemon_data_dir.join("debug.log");
             drop(conf);
-        }
+
+            let gv_lines: Vec<String> = file_ops::tail_file(&gv_log, LOG_TAIL_MAX_BYTES)
+                .or_code(ErrorCode::Internal, "Unable to read ghostvault.log")?;
+
+            let ghostvault: Vec<String> = match level {
+                Some(level) => filter_log_lines(gv_lines, level),
+                None => gv_lines,
+            };
+
+            // ghostd's log has no levels to filter on.
+            let ghostd: Option<Vec<String>> = if daemon {
+                let daemon_lines: Vec<String> =
+                    file_ops::tail_file(&daemon_log, LOG_TAIL_MAX_BYTES)
+                        .or_code(ErrorCode::Internal, "Unable to read ghostd's debug.log")?;
+
+                Some(last_lines(daemon_lines, lines))
+            } else {
+                None
+            };
+
+            Ok(response::ok(RecentLogs {
+                ghostvault: last_lines(ghostvault, lines),
+                ghostd,
+            }))
+        })
+        .await
     }
 
-    async fn process_rewards_status(&self) {
-        for result in self.db.new_stake_status.iter() {
-            match result {
-                Ok((key, value)) => {
-                    let mut stake_status: NewStakeStatusDB =
-                        serde_json::from_slice(&value).unwrap();
-                    let txid: String = stake_status.txid.clone();
-                    let tx_details_res = self.daemon.get_transaction(&txid).await;
+    async fn set_origin(self, _: context::Context, origin: String) -> GvReply<String> {
+        guarded("set_origin", async move {
+            let origin: String = origin.to_lowercase();
+
+            if !AUDIT_ORIGINS.contains(&origin.as_str()) {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!(
+                        "Unknown origin '{}', use {}.",
+                        origin,
+                        AUDIT_ORIGINS.join(", ")
+                    ),
+                ));
+            }
 
-                    let tx_details = if tx_details_res.is_err() {
-                        self.db.remove_new_stake_status(&key).await.unwrap();
-                        self.db
-                            .remove_reward(stake_status.timestamp.to_be_bytes())
-                            .await
-                            .unwrap();
-                        continue;
-                    } else {
-                        tx_details_res.unwrap()
-                    };
+            *self.connection.origin.lock().unwrap() = origin.clone();
 
-                    let tx_output = tx_details.get("details").unwrap().as_array().unwrap();
+            Ok(response::ok(origin))
+        })
+        .await
+    }
 
-                    if tx_output.is_empty()
-                        || tx_output[0].get("category").unwrap().as_str().unwrap() != "stake"
-                    {
-                        self.db.remove_new_stake_status(&key).await.unwrap();
-                        self.db
-                            .remove_reward(stake_status.timestamp.to_be_bytes())
-                            .await
-                            .unwrap();
+    async fn get_audit_log(self, _: context::Context, limit: u64) -> GvReply<Vec<AuditDB>> {
+        guarded("get_audit_log", async move {
+            let limit: usize = if limit == 0 {
+                AUDIT_LOG_DEFAULT
+            } else {
+                limit as usize
+            };
 
-                        if self.tg_bot_active {
-                            let current_time = chrono::Utc::now();
-                            let timestamp: u64 = current_time.timestamp() as u64;
+            Ok(response::ok(self.db.get_audit_log(limit)))
+        })
+        .await
+    }
 
-                            let header = format!("👻 Stake removed! 👻");
-                            let msg = None;
-                            let url = None;
-                            let msg_type = "stake_removal".to_string();
+    async fn get_pending_reward_change(
+        self,
+        _: context::Context,
+    ) -> GvReply<PendingRewardChangeDB> {
+        guarded("get_pending_reward_change", async move {
+            match self.db.get_pending_reward_change() {
+                Some(pending) => Ok(response::ok(pending)),
+                None => Err(GvError::new(
+                    ErrorCode::NotFound,
+                    "No reward change is pending.",
+                )),
+            }
+        })
+        .await
+    }
 
-                            let tg_queue: TgBotQueueDB = TgBotQueueDB {
-                                timestamp,
-                                header,
-                                msg,
-                                code_block: None,
-                                url,
-                                msg_type,
-                                reward_txid: None,
-                                msg_to_delete: stake_status.tg_msg_id.clone(),
-                            };
+    async fn cancel_reward_change(self, _: context::Context) -> GvReply<String> {
+        let audit: Audit = self.audit("cancel_reward_change", serde_json::json!({}));
+        audited(audit, async move {
+            let pending: PendingRewardChangeDB = self
+                .db
+                .get_pending_reward_change()
+                .or_code(ErrorCode::NotFound, "No reward change is pending.")?;
 
-                            self.db
-                                .set_tg_bot_queue(timestamp.to_string().as_bytes(), &tg_queue)
-                                .await
-                                .unwrap();
-                        }
+            self.db
+                .remove_pending_reward_change()
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            let msg: String = format!(
+                "The reward change to {} was cancelled, rewards still go where they did.",
+                self.address_name(&pending.address)
+            );
+            info!("{}", msg);
+
+            self.queue_bot_message(
+                "reward_changed",
+                "👻 Reward Address Change Cancelled 👻",
+                msg.clone(),
+            )
+            .await;
+
+            Ok(response::message(msg))
+        })
+        .await
+    }
+
+    async fn get_restart_history(self, _: context::Context, limit: u64) -> GvReply<Vec<RestartDB>> {
+        guarded("get_restart_history", async move {
+            let limit: usize = if limit == 0 {
+                RESTART_HISTORY_MAX
+            } else {
+                limit as usize
+            };
+
+            Ok(response::ok(self.db.get_restart_history(limit)))
+        })
+        .await
+    }
+
+    async fn get_bootstrap_status(self, _: context::Context) -> GvReply<BootstrapDB> {
+        guarded("get_bootstrap_status", async move {
+            match self.db.get_bootstrap() {
+                Some(bootstrap) => Ok(response::ok(bootstrap)),
+                None => Err(GvError::new(
+                    ErrorCode::NotFound,
+                    "No blockchain bootstrap has been run.",
+                )),
+            }
+        })
+        .await
+    }
+
+    async fn take_bot_messages(self, _: context::Context) -> GvReply<Vec<TgBotQueueDB>> {
+        guarded("take_bot_messages", async move {
+            let conf = self.gv_config.read().await;
+            let bot_relay: bool = conf.bot_relay;
+            drop(conf);
 
+            if !bot_relay {
+                return Err(GvError::new(
+                    ErrorCode::Disabled,
+                    "Bot relay is not enabled on this vault.",
+                ));
+            }
+
+            let timestamp: u64 = chrono::Utc::now().timestamp() as u64;
+            let mut messages: Vec<TgBotQueueDB> = Vec::new();
+
+            for result in self.db.tg_bot_queue.iter() {
+                let (key, value) = match result {
+                    Ok(entry) => entry,
+                    Err(err) => {
+                        error!("Error reading bot queue: {}", err);
                         continue;
                     }
+                };
 
-                    let confirms: u64 = tx_details
-                        .get("confirmations")
-                        .map_or(0, |val| val.as_u64().unwrap());
+                self.db
+                    .remove_tg_bot_queue(&key)
+                    .await
+                    .or_code(ErrorCode::Internal, "Database error")?;
 
-                    stake_status.confirmations = confirms as u32;
+                let msg_details: TgBotQueueDB = match serde_json::from_slice(&value) {
+                    Ok(msg_details) => msg_details,
+                    Err(_) => continue,
+                };
 
-                    if confirms > 100 {
-                        self.do_flush_rewards_to_anon().await;
-                        self.db.remove_new_stake_status(&key).await.unwrap();
-                    } else {
-                        self.db
-                            .set_new_stake_status(&key, &stake_status)
-                            .await
-                            .unwrap();
-                    }
-                }
-                Err(err) => {
-                    eprintln!("Error during iteration: {:?}", err);
+                // Same five minute cutoff the bot uses, and message ids only mean something to
+                // the bot that sent them so edits in place can't be relayed.
+                if timestamp.saturating_sub(msg_details.timestamp) > 300
+                    || msg_details.msg_type == "stake_confirmations"
+                {
+                    continue;
                 }
+
+                messages.push(msg_details);
             }
-        }
+
+            Ok(response::ok(messages))
+        })
+        .await
     }
 
-    async fn process_zap_status(&self) {
-        for result in self.db.zap_status_db.iter() {
-            match result {
-                Ok((key, value)) => {
-                    let mut zap_status: ZapStatusDB = serde_json::from_slice(&value).unwrap();
-                    let txid: String = zap_status.txid.clone();
-                    let tx_details: Value = self.daemon.get_transaction(&txid).await.unwrap();
-                    let confirms: i64 = tx_details
-                        .get("confirmations")
-                        .map_or(0, |val| val.as_i64().unwrap());
+    async fn get_announce_routes(self, _: context::Context) -> GvReply<Vec<AnnounceRoute>> {
+        guarded("get_announce_routes", async move {
+            let routes: Vec<AnnounceRoute> = announce::ANNOUNCE_EVENTS
+                .iter()
+                .map(|event| AnnounceRoute {
+                    event: event.to_string(),
+                    destinations: self
+                        .db
+                        .get_announce_route(event)
+                        .unwrap_or(vec![AnnounceDestination::TelegramAdmin]),
+                })
+                .collect();
+
+            Ok(response::ok(routes))
+        })
+        .await
+    }
 
-                    if confirms < 0 {
-                        self.db.remove_zap_status(&key).await.unwrap();
-                        continue;
+    async fn set_announce_route(
+        self,
+        _: context::Context,
+        event: String,
+        destinations: String,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_announce_route",
+            serde_json::json!({ "event": &event, "destinations": &destinations }),
+        );
+        audited(audit, async move {
+            let event: String = event.to_lowercase();
+
+            if !announce::ANNOUNCE_EVENTS.contains(&event.as_str()) {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!(
+                        "Invalid event: {}, use one of {}.",
+                        event,
+                        announce::ANNOUNCE_EVENTS.join(", ")
+                    ),
+                ));
+            }
+
+            let destinations: Vec<AnnounceDestination> =
+                match announce::parse_destinations(&destinations) {
+                    Ok(destinations) => destinations,
+                    Err(err) => {
+                        return Err(GvError::new(ErrorCode::InvalidArgument, err.to_string()))
                     }
+                };
 
-                    let current_time = chrono::Utc::now();
-                    let timestamp: u64 = current_time.timestamp() as u64;
+            let conf = self.gv_config.read().await;
+            let missing: Option<&str> =
+                destinations
+                    .iter()
+                    .find_map(|destination| match destination {
+                        AnnounceDestination::TelegramAdmin
+                            if conf.tg_user.is_none() && !conf.bot_relay =>
+                        {
+                            Some("TELEGRAM_USER")
+                        }
+                        AnnounceDestination::TelegramChannel if conf.tg_channel.is_none() => {
+                            Some("TG_CHANNEL")
+                        }
+                        AnnounceDestination::Webhook if conf.announce_webhook_url.is_none() => {
+                            Some("ANNOUNCE_WEBHOOK_URL")
+                        }
+                        AnnounceDestination::Email if conf.announce_email.is_none() => {
+                            Some("ANNOUNCE_EMAIL")
+                        }
+                        _ => None,
+                    });
+            drop(conf);
 
-                    if confirms >= 225 {
-                        if self.tg_bot_active {
-                            let header = format!("👻 Zap Now Staking! 👻");
-                            let msg = Some(format!(
-                                "The deposit of {} GHOST in your GhostVault is now staking!",
-                                self.daemon.convert_from_sat(zap_status.amount)
-                            ));
+            if let Some(missing) = missing {
+                return Err(GvError::new(
+                    ErrorCode::NotConfigured,
+                    format!("Set {} before routing announcements to it.", missing),
+                ));
+            }
 
-                            let url = None;
-                            let msg_type = "zap".to_string();
+            self.db
+                .set_announce_route(&event, &destinations)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            Ok(response::message(format!(
+                "Announcement route for {} updated!",
+                event
+            )))
+        })
+        .await
+    }
 
-                            let tg_queue: TgBotQueueDB = TgBotQueueDB {
-                                timestamp,
-                                header,
-                                msg,
-                                code_block: None,
-                                url,
-                                msg_type,
-                                reward_txid: None,
-                                msg_to_delete: None,
-                            };
+    async fn get_quiet_hours(self, _: context::Context) -> GvReply<Vec<QuietHours>> {
+        guarded("get_quiet_hours", async move {
+            let conf: GVConfig = self.gv_config.read().await.clone();
+
+            let quiet_hours: Vec<QuietHours> = AnnounceDestination::ALL
+                .into_iter()
+                .map(|destination| {
+                    let hours: Option<QuietHoursDB> = self.db.get_quiet_hours(destination);
+
+                    QuietHours {
+                        destination,
+                        start: hours.map(|hours| hours.start),
+                        end: hours.map(|hours| hours.end),
+                        quiet_now: announce::in_quiet_hours(&self.db, &conf, destination),
+                        held: self.db.count_held_notices(destination),
+                    }
+                })
+                .collect();
 
-                            let in_tg_queue: Option<TgBotQueueDB> = self.db.get_tg_bot_queue(&key);
-                            if in_tg_queue.is_none() {
-                                self.db.set_tg_bot_queue(&key, &tg_queue).await.unwrap();
-                            }
-                        }
-                        self.db.remove_zap_status(&key).await.unwrap();
-                    } else {
-                        zap_status.confirmations = confirms as u32;
-                        if self.tg_bot_active {
-                            let amount = self.daemon.convert_from_sat(zap_status.amount);
+            Ok(response::ok(quiet_hours))
+        })
+        .await
+    }
 
-                            let in_msg_que = self.db.get_tg_bot_queue(&key).is_some();
+    // Turning quiet hours off leaves the held notices to go out on the next check.
+    async fn set_quiet_hours(
+        self,
+        _: context::Context,
+        destination: String,
+        hours: String,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_quiet_hours",
+            serde_json::json!({ "destination": &destination, "hours": &hours }),
+        );
+        audited(audit, async move {
+            let destination: AnnounceDestination = AnnounceDestination::from_name(&destination)
+                .ok_or_else(|| {
+                    GvError::new(
+                        ErrorCode::InvalidArgument,
+                        format!(
+                            "Invalid destination: {}, use one of {}.",
+                            destination,
+                            AnnounceDestination::ALL
+                                .iter()
+                                .map(|destination| destination.as_str())
+                                .collect::<Vec<&str>>()
+                                .join(", ")
+                        ),
+                    )
+                })?;
+
+            let hours: Option<QuietHoursDB> = announce::parse_quiet_hours(&hours)
+                .map_err(|err| GvError::new(ErrorCode::InvalidArgument, err.to_string()))?;
 
-                            let header = format!("👻 New Zap Detected! 👻");
+            self.db
+                .set_quiet_hours(destination, hours)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            let timezone: String = self.gv_config.read().await.timezone.clone();
+
+            Ok(response::message(match hours {
+                Some(hours) => format!(
+                    "Quiet hours for {} set to {:02}:00-{:02}:00 {}!",
+                    destination.as_str(),
+                    hours.start,
+                    hours.end,
+                    timezone
+                ),
+                None => format!("Quiet hours for {} turned off!", destination.as_str()),
+            }))
+        })
+        .await
+    }
 
-                            let msg = Some(format!(
-                                "New deposit of {} GHOST is in your GhostVault!",
-                                amount
-                            ));
+    async fn get_announce_templates(self, _: context::Context) -> GvReply<Vec<AnnounceTemplate>> {
+        guarded("get_announce_templates", async move {
+            let templates: Vec<AnnounceTemplate> = announce::TEMPLATE_TYPES
+                .iter()
+                .map(|msg_type| {
+                    let template: Option<AnnounceTemplateDB> =
+                        self.db.get_announce_template(msg_type);
+
+                    AnnounceTemplate {
+                        msg_type: msg_type.to_string(),
+                        header: template
+                            .as_ref()
+                            .and_then(|template| template.header.clone()),
+                        body: template.map(|template| template.body),
+                    }
+                })
+                .collect();
 
-                            let url = Some(vec![format!("https://ghostscan.io/tx/{}/", txid)]);
+            Ok(response::ok(templates))
+        })
+        .await
+    }
 
-                            let msg_type = "zap".to_string();
+    // A body of "default" goes back to the built in text, an empty header keeps the built in one.
+    async fn set_announce_template(
+        self,
+        _: context::Context,
+        msg_type: String,
+        body: String,
+        header: String,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "set_announce_template",
+            serde_json::json!({ "msg_type": &msg_type, "body": &body, "header": &header }),
+        );
+        audited(audit, async move {
+            let msg_type: String = msg_type.to_lowercase();
+
+            if !announce::TEMPLATE_TYPES.contains(&msg_type.as_str()) {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!(
+                        "Invalid message type: {}, use one of {}.",
+                        msg_type,
+                        announce::TEMPLATE_TYPES.join(", ")
+                    ),
+                ));
+            }
 
-                            let tg_queue: TgBotQueueDB = TgBotQueueDB {
-                                timestamp,
-                                header,
-                                msg,
-                                code_block: None,
-                                url,
-                                msg_type,
-                                reward_txid: None,
-                                msg_to_delete: None,
-                            };
+            if body.trim().eq_ignore_ascii_case("default") {
+                self.db
+                    .set_announce_template(&msg_type, None)
+                    .await
+                    .or_code(ErrorCode::Internal, "Database error")?;
 
-                            if !zap_status.first_notice && !in_msg_que {
-                                self.db
-                                    .set_tg_bot_queue(txid.as_bytes(), &tg_queue)
-                                    .await
-                                    .unwrap();
-                                zap_status.first_notice = true;
-                                self.db
-                                    .set_zap_status(txid.as_bytes(), &zap_status)
-                                    .await
-                                    .unwrap();
-                            }
-                        }
+                return Ok(response::message(format!(
+                    "The {} template is back to the default!",
+                    msg_type
+                )));
+            }
 
-                        self.db.set_zap_status(&key, &zap_status).await.unwrap();
-                    }
-                }
-                Err(err) => {
-                    eprintln!("Error during iteration: {:?}", err);
-                }
+            if body.trim().is_empty() {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "A template can't be empty, use default to go back to the built in text.",
+                ));
             }
-        }
+
+            for text in [&body, &header] {
+                announce::check_template(text)
+                    .map_err(|err| GvError::new(ErrorCode::InvalidArgument, err.to_string()))?;
+            }
+
+            let template: AnnounceTemplateDB = AnnounceTemplateDB {
+                header: Some(header.trim().to_string()).filter(|header| !header.is_empty()),
+                body: body.trim().to_string(),
+            };
+
+            self.db
+                .set_announce_template(&msg_type, Some(&template))
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+
+            Ok(response::message(format!(
+                "The {} template is set!",
+                msg_type
+            )))
+        })
+        .await
     }
-}
 
-impl GvCLI for GvCLIServer {
-    async fn getblockcount(self, _: context::Context) -> Value {
-        let blocks = self.daemon.getblockcount().await.unwrap();
-        blocks
+    async fn get_explorer_health(self, _: context::Context) -> GvReply<Vec<ExplorerHealth>> {
+        guarded("get_explorer_health", async move {
+            let health: Vec<ExplorerHealth> = self
+                .explorer()
+                .await
+                .check_health()
+                .await
+                .or_code(ErrorCode::Unavailable, "Error checking the explorer APIs")?;
+
+            Ok(response::ok(health))
+        })
+        .await
+    }
+
+    async fn get_chain_tip(self, _: context::Context) -> GvReply<ChainTip> {
+        guarded("get_chain_tip", async move {
+            let state: DaemonState = self.current_daemon_state().await;
+
+            if !state.online {
+                return Err(GvError::new(ErrorCode::Unavailable, "ghostd is offline"));
+            }
+
+            Ok(response::ok(ChainTip {
+                best_block: state.best_block,
+                best_block_hash: state.best_block_hash,
+                synced: state.synced,
+            }))
+        })
+        .await
+    }
+
+    async fn get_block_hash_at(self, _: context::Context, height: u32) -> GvReply<String> {
+        guarded("get_block_hash_at", async move {
+            let block_hash: String = self
+                .daemon
+                .getblockhash(height)
+                .await
+                .or_code(ErrorCode::NotFound, "Error getting block hash")?;
+
+            Ok(response::ok(block_hash))
+        })
+        .await
+    }
+
+    async fn get_stake_forecast(self, _: context::Context) -> GvReply<StakeForecast> {
+        guarded("get_stake_forecast", async move {
+            let forecast: StakeForecast = self
+                .stake_forecast()
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting staking info")?;
+
+            Ok(response::ok(forecast))
+        })
+        .await
+    }
+
+    async fn simulate_rewards(
+        self,
+        _: context::Context,
+        mode: Option<String>,
+        days: u64,
+    ) -> GvReply<RewardSimulation> {
+        guarded("simulate_rewards", async move {
+            let days: u64 = if days == 0 {
+                DEFAULT_SIMULATION_DAYS
+            } else {
+                days
+            };
+
+            if days > SIMULATION_MAX_DAYS {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Days must be {} or less", SIMULATION_MAX_DAYS),
+                ));
+            }
+
+            let mode: Option<String> = mode.map(|mode| mode.to_uppercase());
+
+            if let Some(mode) = &mode {
+                if !matches!(mode.as_str(), "DEFAULT" | "STANDARD" | "ANON") {
+                    return Err(GvError::new(ErrorCode::InvalidArgument, "Invalid mode!"));
+                }
+            }
+
+            let mut simulation: RewardSimulation = self.simulate_rewards_for(days).await;
+
+            if let Some(mode) = mode {
+                simulation.modes.retain(|simulated| simulated.mode == mode);
+            }
+
+            Ok(response::ok(simulation))
+        })
+        .await
     }
 
-    async fn shutdown(self, _: context::Context) -> Value {
-        let conf = self.gv_config.read().await;
-        let gv_data_dir = conf.gv_home.clone();
-        drop(conf);
-        let pid_file: PathBuf = gv_data_dir.join(GV_PID_FILE);
-        file_ops::rm_file(&pid_file).unwrap();
+    async fn get_chain_emergency(self, _: context::Context) -> GvReply<ChainEmergency> {
+        guarded("get_chain_emergency", async move {
+            let conf = self.gv_config.read().await;
+            let chain_split_timeout: u64 = conf.chain_split_timeout;
+            drop(conf);
 
-        let is_docker: bool = env::vars().any(|(key, _)| key == "DOCKER_RUNNING");
+            let emergency: Option<ChainEmergencyDB> = self.db.get_chain_emergency();
 
-        if is_docker {
-            let _ = self.daemon.stop_daemon().await;
-        }
+            let status: ChainEmergency = ChainEmergency {
+                active: emergency.as_ref().is_some_and(|emergency| emergency.active),
+                good_chain: self.good_chain().await,
+                since: emergency.as_ref().map(|emergency| emergency.since),
+                snoozed_until: emergency.and_then(|emergency| emergency.snoozed_until),
+                chain_split_timeout,
+            };
 
-        tokio::spawn(async move {
-            do_shutdown().await;
-        });
-        Value::String("GhostVault going down for shutdown...".to_string())
+            Ok(response::ok(status))
+        })
+        .await
     }
 
-    async fn get_daemon_state(self, _: context::Context) -> Value {
-        serde_json::to_value(self.get_gv_status().await.unwrap()).unwrap()
-    }
+    async fn chain_emergency_action(self, _: context::Context, action: String) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "chain_emergency_action",
+            serde_json::json!({ "action": &action }),
+        );
+        audited(audit, async move {
+            let mut emergency: ChainEmergencyDB = match self.db.get_chain_emergency() {
+                Some(emergency) if emergency.active => emergency,
+                _ => {
+                    return Err(GvError::new(
+                        ErrorCode::InvalidState,
+                        "No chain split emergency is active.",
+                    ))
+                }
+            };
 
-    async fn get_ext_pub_key(self, _: context::Context) -> Value {
-        let mut conf = self.gv_config.write().await;
-        let ext_pub_key = conf.ext_pub_key.clone();
+            match action.to_lowercase().as_str() {
+                "resync" => {
+                    let operation: OperationGuard = self.operations.start("force_resync").await?;
 
-        if ext_pub_key.is_none() {
-            let ext_pub_key = self
-                .daemon
-                .getnewextaddress()
-                .await
-                .unwrap()
-                .as_str()
-                .unwrap()
-                .to_string();
-            conf.update_gv_config("ext_pub_key", &ext_pub_key).unwrap();
-            return Value::String(ext_pub_key);
-        }
+                    tokio::spawn(async move {
+                        self.do_force_resync().await;
+                        operation.finish(Ok(())).await;
+                    });
 
-        Value::String(ext_pub_key.unwrap())
-    }
+                    Ok(response::message("Forcing a resync of the daemon..."))
+                }
+                "ban_peers" | "banpeers" => {
+                    let peers: Vec<Value> = match self.daemon.getpeerinfo().await {
+                        Ok(Value::Array(peers)) => peers,
+                        Ok(_) => Vec::new(),
+                        Err(err) => {
+                            return Err(GvError::new(
+                                ErrorCode::DaemonError,
+                                format!("Error getting peers: {}", err),
+                            ))
+                        }
+                    };
 
-    async fn enable_telegram_bot(self, _: context::Context, token: String, user: String) -> Value {
-        let mut conf = self.gv_config.write().await;
+                    let mut banned: usize = 0;
 
-        let plausible_userid = user.parse::<u64>();
+                    for peer in peers {
+                        let addr: &str = peer
+                            .get("addr")
+                            .and_then(|addr| addr.as_str())
+                            .unwrap_or("");
 
-        if plausible_userid.is_err() {
-            return Value::String("Invalid user ID!".to_string());
-        }
+                        // Drop the port, setban wants a bare address.
+                        let ip: &str = match addr.rsplit_once(':') {
+                            Some((ip, _)) => ip.trim_start_matches('[').trim_end_matches(']'),
+                            None => addr,
+                        };
 
-        let token_is_valid: bool = gv_methods::validate_bot_token(&token).await.unwrap();
+                        if ip.is_empty() {
+                            continue;
+                        }
 
-        if !token_is_valid {
-            return Value::String("Invalid bot token!".to_string());
-        }
+                        match self.daemon.setban(ip, CHAIN_EMERGENCY_BAN_TIME).await {
+                            Ok(_) => banned += 1,
+                            Err(err) => warn!("Error banning peer {}: {}", ip, err),
+                        }
+                    }
 
-        conf.update_gv_config("TELOXIDE_TOKEN", &token).unwrap();
-        conf.update_gv_config("TELEGRAM_USER", &user).unwrap();
-        Value::String("Telegram bot enabled!".to_string())
-    }
+                    info!("Banned {} peers for the chain split emergency.", banned);
 
-    async fn disable_telegram_bot(self, _: context::Context) -> Value {
-        let mut conf = self.gv_config.write().await;
-        conf.update_gv_config("TELOXIDE_TOKEN", "").unwrap();
-        conf.update_gv_config("TELEGRAM_USER", "").unwrap();
-        Value::String("Telegram bot disabled!".to_string())
+                    Ok(response::message(format!(
+                        "Banned {} peers for 24 hours, ghostd will look for new ones.",
+                        banned
+                    )))
+                }
+                "wait" => {
+                    let snoozed_until: u64 =
+                        chrono::Utc::now().timestamp() as u64 + CHAIN_EMERGENCY_SNOOZE;
+                    emergency.snoozed_until = Some(snoozed_until);
+                    self.db
+                        .set_chain_emergency(&emergency)
+                        .await
+                        .or_code(ErrorCode::Internal, "Database error")?;
+
+                    Ok(response::message(format!(
+                        "Alerts snoozed for {}, payouts stay suspended until the chain recovers.",
+                        format_duration(Duration::from_secs(CHAIN_EMERGENCY_SNOOZE))
+                    )))
+                }
+                _ => Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Invalid action: {}, use resync, ban_peers or wait.", action),
+                )),
+            }
+        })
+        .await
     }
 
-    async fn set_reward_interval(self, _: context::Context, interval: String) -> Value {
-        let second: i64 = 1;
-        let minute: i64 = 60 * second;
-        let hour: i64 = 60 * minute;
-        let day: i64 = 24 * hour;
-        let week: i64 = 7 * day;
-        let month: i64 = 30 * day;
-        let year: i64 = 365 * day;
+    async fn create_backup(self, _: context::Context, path: String) -> GvReply<BackupInfo> {
+        let audit: Audit = self.audit("create_backup", serde_json::json!({ "path": &path }));
+        audited(audit, async move {
+            let path: PathBuf = if path.is_empty() {
+                let conf = self.gv_config.read().await;
+                conf.backup_dir.clone()
+            } else {
+                file_ops::expand_user(&path)
+            };
 
-        let multiplier: char = interval.chars().last().unwrap();
+            let job: JobGuard = self.jobs.start("backup").await;
 
-        let striped_str: String = interval.replace(multiplier, "");
+            let mut info: BackupInfo = match self.do_create_backup(path).await {
+                Ok(info) => info,
+                Err(err) => {
+                    let msg: String = format!("Error creating backup: {}", err);
+                    job.finish(Err(msg.clone())).await;
+                    return Err(GvError::new(ErrorCode::Internal, msg));
+                }
+            };
 
-        let timeframe: i64 = if striped_str.is_empty() {
-            return Value::String("Invalid interval!".to_string());
-        } else {
-            let parsed_str = striped_str.parse::<i64>();
-            if parsed_str.is_err() {
-                return Value::String("Invalid interval!".to_string());
-            } else {
-                parsed_str.unwrap()
+            match self.do_remote_backup(&info).await {
+                Ok(remote) => info.remote = remote,
+                Err(err) => {
+                    let msg: String = format!(
+                        "Backup written to {}, but the remote upload failed: {}",
+                        info.path, err
+                    );
+                    job.finish(Err(msg.clone())).await;
+                    return Err(GvError::new(ErrorCode::Unavailable, msg));
+                }
             }
-        };
-
-        let interval: i64 = match multiplier {
-            's' => timeframe * second,
-            'm' => timeframe * minute,
-            'h' => timeframe * hour,
-            'd' => timeframe * day,
-            'w' => timeframe * week,
-            'M' => timeframe * month,
-            'y' => timeframe * year,
-            _ => return Value::String("Invalid interval!".to_string()),
-        };
-        let mut conf = self.gv_config.write().await;
-        conf.update_gv_config("reward_interval", &interval.to_string())
-            .unwrap();
 
-        task_runner::update_payout_interval(&self.db, interval)
-            .await
-            .unwrap();
+            let location: String = info.remote.clone().unwrap_or(info.path.clone());
+            job.finish(Ok(Some(format!("Backup saved to {}", location))))
+                .await;
 
-        Value::String("Reward interval updated!".to_string())
+            Ok(response::ok(info))
+        })
+        .await
     }
 
-    async fn set_payout_min(self, _: context::Context, min: f64) -> Value {
-        let mut conf = self.gv_config.write().await;
-        let min_int: u64 = self.daemon.convert_to_sat(min);
+    async fn restore_backup(
+        self,
+        _: context::Context,
+        path: String,
+        passphrase: String,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit("restore_backup", serde_json::json!({ "path": &path }));
+        audited(audit, async move {
+            let conf = self.gv_config.read().await;
+            let gv_home: PathBuf = conf.gv_home.clone();
+            drop(conf);
 
-        if min_int < MIN_TX_VALUE {
-            return Value::String("Minimum payout too low!".to_string());
-        }
+            let path: PathBuf = file_ops::expand_user(&path);
 
-        conf.update_gv_config("min_reward_payout", &min_int.to_string())
-            .unwrap();
+            let stage_res = tokio::task::spawn_blocking(move || {
+                backup::stage_restore(&gv_home, &path, &passphrase)
+            })
+            .await;
 
-        task_runner::update_payout_min(&self.db, min_int)
-            .await
-            .unwrap();
+            match stage_res {
+                Ok(Ok(info)) => Ok(response::message(format!(
+                    "Backup {} staged! Restart GhostVault to restore it, the config passphrase is now the one used for the backup.",
+                    info.path
+                ))),
+                Ok(Err(err)) => Err(GvError::new(ErrorCode::Internal, format!("Error restoring backup: {}", err))),
+                Err(err) => Err(GvError::new(ErrorCode::Internal, format!("Error restoring backup: {}", err))),
+            }
+        })
+        .await
+    }
 
-        Value::String("Minimum payout updated!".to_string())
+    async fn migrate_export(self, _: context::Context, path: String) -> GvReply<MigrateInfo> {
+        let audit: Audit = self.audit("migrate_export", serde_json::json!({ "path": &path }));
+        audited(audit, async move {
+            let conf = self.gv_config.read().await;
+            let gv_home: PathBuf = conf.gv_home.clone();
+            let vault_name: String = conf.vault_name.clone();
+            let path: PathBuf = if path.is_empty() {
+                conf.backup_dir.clone()
+            } else {
+                file_ops::expand_user(&path)
+            };
+            drop(conf);
+
+            let wallet_meta: WalletMeta = self.wallet_meta().await;
+            let db: Arc<GVDB> = Arc::clone(&self.db);
+
+            let export_res = tokio::task::spawn_blocking(move || {
+                backup::create_migration(&gv_home, &db, &wallet_meta, &vault_name, &path)
+            })
+            .await;
+
+            match export_res {
+                Ok(Ok(info)) => Ok(response::ok(info)),
+                Ok(Err(err)) => Err(GvError::new(
+                    ErrorCode::Internal,
+                    format!("Error exporting vault: {}", err),
+                )),
+                Err(err) => Err(GvError::new(
+                    ErrorCode::Internal,
+                    format!("Error exporting vault: {}", err),
+                )),
+            }
+        })
+        .await
     }
 
-    async fn set_reward_mode(
+    async fn migrate_import(
         self,
         _: context::Context,
-        mode: String,
-        addr: Option<String>,
-    ) -> Value {
-        let mut conf = self.gv_config.write().await;
+        path: String,
+        passphrase: String,
+        force: bool,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "migrate_import",
+            serde_json::json!({ "path": &path, "force": force }),
+        );
+        audited(audit, async move {
+            let gv_home: PathBuf = self.gv_config.read().await.gv_home.clone();
+            let path: PathBuf = file_ops::expand_user(&path);
+
+            let stage_gv_home: PathBuf = gv_home.clone();
+            let stage_res = tokio::task::spawn_blocking(move || {
+                backup::stage_migration(&stage_gv_home, &path, &passphrase)
+            })
+            .await;
+
+            let (info, bundle_meta) = match stage_res {
+                Ok(Ok(staged)) => staged,
+                Ok(Err(err)) => return Err(GvError::new(ErrorCode::Internal, format!("Error importing vault: {}", err))),
+                Err(err) => return Err(GvError::new(ErrorCode::Internal, format!("Error importing vault: {}", err))),
+            };
 
-        match mode.to_uppercase().as_str() {
-            "ANON" => {
-                if addr.is_none() {
-                    return Value::String("An address is required for anon mode!".to_string());
-                }
+            let local_meta: WalletMeta = self.wallet_meta().await;
+            let conflicts: Vec<String> = backup::migration_conflicts(&local_meta, &bundle_meta);
 
-                let addr: &String = addr.as_ref().unwrap();
+            if !conflicts.is_empty() {
+                if !force {
+                    if let Err(err) = backup::discard_staged(&gv_home) {
+                        error!("Error discarding staged migration: {}", err);
+                    }
 
-                let addr_info = self.daemon.get_address_info(addr).await;
+                    return Err(GvError::new(
+                        ErrorCode::InvalidState,
+                        format!(
+                            "{}. Restore the wallet from the vault's mnemonic first, or force the import to keep this wallet.",
+                            conflicts.join(". ")
+                        ),
+                    ));
+                }
 
-                if addr_info.is_err() {
-                    return Value::String("Invalid address!".to_string());
+                for conflict in &conflicts {
+                    warn!("Importing vault despite conflict: {}", conflict);
                 }
+            }
 
-                let addr_info: Value = addr_info.unwrap();
+            Ok(response::message(format!(
+                "Vault {} staged! Restart GhostVault to finish the migration, the config passphrase is now the one used for the export.",
+                info.vault_name
+            )))
+        })
+        .await
+    }
 
-                let is_mine: bool = addr_info
-                    .get("ismine")
-                    .unwrap_or(&Value::Bool(false))
-                    .as_bool()
-                    .unwrap();
+    async fn process_auto_backup(self, _: context::Context) -> GvReply<()> {
+        guarded("process_auto_backup", async move {
+            let conf = self.gv_config.read().await;
+            let backup_interval: u64 = conf.backup_interval;
+            let backup_dir: PathBuf = conf.backup_dir.clone();
+            let backup_retention: u64 = conf.backup_retention;
+            let gv_home: PathBuf = conf.gv_home.clone();
+            drop(conf);
 
-                if is_mine {
-                    return Value::String("Cannot use a address owned by GhostVault!".to_string());
-                }
+            if backup_interval == 0 {
+                return Ok(response::ok(()));
+            }
 
-                let mut internal_anon: String =
-                    conf.internal_anon.clone().unwrap_or("".to_string());
+            if !config::config_encrypted(&gv_home) {
+                warn!("Skipping automatic backup, set a config passphrase with 'ghostvaultd --set-passphrase' to enable them.");
+                return Ok(response::ok(()));
+            }
 
-                if internal_anon.is_empty() {
-                    let anon_addr = self
-                        .daemon
-                        .getnewstealthaddress()
-                        .await
-                        .unwrap()
-                        .as_str()
-                        .unwrap()
-                        .to_string();
-                    conf.update_gv_config("internal_anon", &anon_addr).unwrap();
-                } else {
-                    let addr_info = self.daemon.get_address_info(&internal_anon).await;
+            let job: JobGuard = self.jobs.start("backup").await;
 
-                    let addr_err: bool = addr_info.is_err();
+            let info: BackupInfo = match self.do_create_backup(backup_dir.clone()).await {
+                Ok(info) => info,
+                Err(err) => {
+                    error!("Automatic backup failed: {}", err);
+                    job.finish(Err(err.to_string())).await;
+
+                    self.queue_bot_message(
+                        "incident",
+                        "👻 Backup Failed! 👻",
+                        format!("The automatic backup failed.\n{}", err),
+                    )
+                    .await;
 
-                    let addr_is_valid: bool = if addr_err {
-                        false
-                    } else {
-                        let addr_info: Value = addr_info.unwrap();
+                    return Ok(response::ok(()));
+                }
+            };
 
-                        let is_stealth = addr_info
-                            .get("isstealthaddress")
-                            .unwrap_or(&Value::Bool(false))
-                            .as_bool()
-                            .unwrap();
+            if let Err(err) = backup::prune_backups(&backup_dir, backup_retention) {
+                error!("Error pruning old backups: {}", err);
+            }
 
-                        let is_mine = addr_info
-                            .get("ismine")
-                            .unwrap_or(&Value::Bool(false))
-                            .as_bool()
-                            .unwrap();
+            match self.do_remote_backup(&info).await {
+                Ok(remote) => {
+                    let location: String = remote.unwrap_or(info.path.clone());
+                    job.finish(Ok(Some(format!("Backup saved to {}", location))))
+                        .await;
+
+                    self.queue_bot_message(
+                        "backup",
+                        "👻 Backup Complete 👻",
+                        format!("Backup saved to {}", location),
+                    )
+                    .await;
+                }
+                Err(err) => {
+                    error!("Remote backup upload failed: {}", err);
+                    job.finish(Err(format!(
+                        "Backup saved locally to {}, but the upload failed: {}",
+                        info.path, err
+                    )))
+                    .await;
 
-                        is_stealth && is_mine
-                    };
+                    self.queue_bot_message(
+                        "incident",
+                        "👻 Remote Backup Failed! 👻",
+                        format!(
+                            "Backup saved locally to {}, but the upload failed.\n{}",
+                            info.path, err
+                        ),
+                    )
+                    .await;
+                }
+            }
 
-                    if !addr_is_valid {
-                        let anon_addr = self
-                            .daemon
-                            .getnewstealthaddress()
-                            .await
-                            .unwrap()
-                            .as_str()
-                            .unwrap()
-                            .to_string();
-                        conf.update_gv_config("internal_anon", &anon_addr).unwrap();
-                        internal_anon = anon_addr;
+            Ok(response::ok(()))
+        })
+        .await
+    }
+
+    async fn export_parquet(
+        self,
+        _: context::Context,
+        out_dir: String,
+    ) -> GvReply<ParquetExportSummary> {
+        guarded("export_parquet", async move {
+            #[cfg(feature = "parquet")]
+            {
+                let out_dir: PathBuf = if out_dir.is_empty() {
+                    let conf = self.gv_config.read().await;
+                    conf.gv_home.join("exports/parquet")
+                } else {
+                    file_ops::expand_user(&out_dir)
+                };
+
+                let db: Arc<GVDB> = Arc::clone(&self.db);
+                let export_res = tokio::task::spawn_blocking(move || {
+                    service::parquet_export::export_parquet(&db, &out_dir)
+                })
+                .await;
+
+                match export_res {
+                    Ok(Ok(summary)) => {
+                        info!(
+                            "Exported {} rewards and {} payouts to {}",
+                            summary.rewards, summary.payouts, summary.out_dir
+                        );
+                        Ok(response::ok(summary))
                     }
+                    Ok(Err(err)) => Err(GvError::new(
+                        ErrorCode::Internal,
+                        format!("Error exporting to parquet: {}", err),
+                    )),
+                    Err(err) => Err(GvError::new(
+                        ErrorCode::Internal,
+                        format!("Error exporting to parquet: {}", err),
+                    )),
                 }
+            }
 
-                conf.update_gv_config("reward_address", &internal_anon)
-                    .unwrap();
-                conf.update_gv_config("anon_mode", "true").unwrap();
-                conf.update_gv_config("anon_reward_address", addr).unwrap();
-                self.daemon
-                    .set_reward_addr_in_wallet(Some(&internal_anon))
-                    .await
-                    .unwrap();
-                return Value::String("Reward mode updated!".to_string());
+            #[cfg(not(feature = "parquet"))]
+            {
+                let _ = out_dir;
+                Err(GvError::new(
+                    ErrorCode::Disabled,
+                    "GhostVault was built without the 'parquet' feature.",
+                ))
             }
-            "STANDARD" => {
-                if addr.is_none() {
-                    return Value::String("An address is required for standard mode!".to_string());
+        })
+        .await
+    }
+
+    async fn set_wallet_passphrase(
+        self,
+        _: context::Context,
+        passphrase: String,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit("set_wallet_passphrase", serde_json::json!({}));
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+
+            if passphrase.is_empty() || passphrase.contains('"') {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Passphrase can not be empty or contain quotes!",
+                ));
+            }
+
+            let status: String = match self.daemon.get_encryption_status().await {
+                Ok(status) => status,
+                Err(err) => {
+                    return Err(GvError::new(
+                        ErrorCode::DaemonError,
+                        format!("Error getting wallet status: {}", err),
+                    ))
                 }
+            };
 
-                let addr: &String = addr.as_ref().unwrap();
+            if status != "Unencrypted" {
+                return Err(GvError::new(ErrorCode::InvalidState, "Wallet is already encrypted!"));
+            }
 
-                let addr_info = self.daemon.get_address_info(addr).await;
+            if let Err(err) = self.daemon.encrypt_wallet(&passphrase).await {
+                return Err(GvError::new(
+                    ErrorCode::DaemonError,
+                    format!("Error encrypting wallet: {}", err),
+                ));
+            }
 
-                if addr_info.is_err() {
-                    return Value::String("Invalid address!".to_string());
-                }
+            info!("Wallet encrypted.");
 
-                let addr_info: Value = addr_info.unwrap();
+            // The mnemonic can't sit in plaintext next to an encrypted wallet.
+            let mut conf = self.gv_config.write().await;
+            conf.update_gv_config("MNEMONIC", "").or_code(ErrorCode::Internal, "Error updating config")?;
+            drop(conf);
 
-                let is_mine: bool = addr_info
-                    .get("ismine")
-                    .unwrap_or(&Value::Bool(false))
-                    .as_bool()
-                    .unwrap();
+            match self.daemon.unlock_wallet_staking(&passphrase).await {
+                Ok(_) => Ok(response::message("Wallet encrypted and unlocked for staking! The mnemonic has been removed from the config."
+                        )),
+                Err(err) => Err(GvError::new(ErrorCode::DaemonError, format!(
+                    "Wallet encrypted, but unlocking for staking failed: {}",
+                    err
+                ))),
+            }
+        })
+        .await
+    }
 
-                if is_mine {
-                    return Value::String("Cannot use a address owned by GhostVault!".to_string());
-                }
+    async fn unlock_wallet(self, _: context::Context, passphrase: String) -> GvReply<String> {
+        let audit: Audit = self.audit("unlock_wallet", serde_json::json!({}));
+        audited(audit, async move {
+            self.require_wallet_control().await?;
 
-                conf.update_gv_config("reward_address", addr).unwrap();
-                conf.update_gv_config("anon_mode", "false").unwrap();
-                self.daemon
-                    .set_reward_addr_in_wallet(Some(addr))
-                    .await
-                    .unwrap();
-                return Value::String("Reward mode updated!".to_string());
+            if passphrase.is_empty() || passphrase.contains('"') {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Passphrase can not be empty or contain quotes!",
+                ));
             }
-            "DEFAULT" => {
-                conf.update_gv_config("anon_mode", "false").unwrap();
-                conf.update_gv_config("reward_address", "").unwrap();
-                self.daemon.set_reward_addr_in_wallet(None).await.unwrap();
-                return Value::String("Reward mode updated!".to_string());
+
+            match self.daemon.get_encryption_status().await {
+                Ok(status) if status == "Unencrypted" => {
+                    return Err(GvError::new(
+                        ErrorCode::InvalidState,
+                        "Wallet is not encrypted!",
+                    ))
+                }
+                Ok(_) => (),
+                Err(err) => {
+                    return Err(GvError::new(
+                        ErrorCode::DaemonError,
+                        format!("Error getting wallet status: {}", err),
+                    ))
+                }
             }
-            _ => {
-                return Value::String("Invalid mode!".to_string());
+
+            match self.daemon.unlock_wallet_staking(&passphrase).await {
+                Ok(_) => {
+                    info!("Wallet unlocked for staking.");
+                    Ok(response::message(format!(
+                        "Wallet unlocked for staking!\n{}",
+                        STAKING_ONLY_UNLOCK
+                    )))
+                }
+                Err(_) => Err(GvError::new(
+                    ErrorCode::Unauthorized,
+                    "Failed to unlock wallet, check the passphrase!",
+                )),
             }
-        }
+        })
+        .await
     }
 
-    async fn new_block(self, _: context::Context, new_block: String) {
-        if new_block != self.best_block_hash().await {
-            info!("New block from daemon: {new_block}");
-            let block_value: Value = self.daemon.getblock(&new_block, 1).await.unwrap();
-            let block_height: u32 = block_value.get("height").unwrap().as_u64().unwrap() as u32;
-            let cycle: u32 = self.cycle().await + 1;
+    // Payouts are held back while the wallet is only unlocked for staking. This fully unlocks it
+    // for one round, then locks it again and puts the staking unlock back.
+    async fn unlock_for_payout(self, _: context::Context, passphrase: String) -> GvReply<String> {
+        let audit: Audit = self.audit("unlock_for_payout", serde_json::json!({}));
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+
+            if passphrase.is_empty() || passphrase.contains('"') {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Passphrase can not be empty or contain quotes!",
+                ));
+            }
+
+            let status: String = self
+                .daemon
+                .get_encryption_status()
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting wallet status")?;
 
-            let block_hash: String = new_block.clone();
+            if status == "Unencrypted" {
+                return Err(GvError::new(
+                    ErrorCode::InvalidState,
+                    "Wallet is not encrypted!",
+                ));
+            }
 
-            let new_status: DaemonStatusDB = DaemonStatusDB {
-                height: block_height,
-                block_hash,
-            };
+            // Already fully unlocked, it's left the way it was found.
+            if status == "Unlocked" {
+                return Ok(response::message(self.payout_round().await));
+            }
 
-            let synced: bool = !self.daemon.is_syncing().await.unwrap();
+            if self
+                .daemon
+                .unlock_wallet_spending(&passphrase, PAYOUT_UNLOCK_TIMEOUT)
+                .await
+                .is_err()
+            {
+                return Err(GvError::new(
+                    ErrorCode::Unauthorized,
+                    "Failed to unlock wallet, check the passphrase!",
+                ));
+            }
 
-            self.db.set_daemon_status(&new_status).await.unwrap();
+            info!("Wallet unlocked for a payout round.");
+            let outcome: String = self.payout_round().await;
 
-            let is_ready = self.daemon_ready().await;
+            self.daemon.lock_wallet().await.or_code(
+                ErrorCode::DaemonError,
+                "Payout round done, but relocking the wallet failed",
+            )?;
 
-            if is_ready {
-                let _ = self.process_zap_status().await;
-                let _ = self.process_rewards_status().await;
+            if status != "Locked" {
+                self.daemon
+                    .unlock_wallet_staking(&passphrase)
+                    .await
+                    .or_code(
+                        ErrorCode::DaemonError,
+                        "Payout round done, but unlocking the wallet for staking again failed",
+                    )?;
             }
 
-            self.set_best_block(block_height).await;
-            self.set_best_block_hash(&new_block).await;
-            self.set_daemon_synced(synced).await;
-            self.set_cycle(cycle).await;
-        }
+            info!("Wallet relocked after the payout round.");
+            Ok(response::message(outcome))
+        })
+        .await
     }
 
-    async fn new_remote_block(self, _: context::Context, block_hash: String, height: u32) {
-        if block_hash != self.remote_best_block_hash().await {
-            info!("New block from remote: {block_hash}");
-
-            self.set_remote_best_block(height).await;
-            self.set_remote_best_block_hash(&block_hash).await;
-        }
+    async fn get_wallet_lock_status(self, _: context::Context) -> GvReply<String> {
+        guarded("get_wallet_lock_status", async move {
+            match self.daemon.get_encryption_status().await {
+                Ok(status) => Ok(response::ok(status)),
+                Err(err) => Err(GvError::new(
+                    ErrorCode::DaemonError,
+                    format!("Error getting wallet status: {}", err),
+                )),
+            }
+        })
+        .await
     }
 
-    async fn new_wallet_tx(self, _: context::Context, txid_and_wal: TxidAndWallet) {
-        let txid: String = txid_and_wal.txid;
-        let wallet: String = txid_and_wal.wallet;
-
-        let conf = self.gv_config.read().await;
+    async fn force_resync(self, _: context::Context, force: bool) -> GvReply<String> {
+        let audit: Audit = self.audit("force_resync", serde_json::json!({ "force": force }));
+        audited(audit, async move {
+            if !force && !task_runner::in_maint_window(&self.gv_config).await {
+                let mut maintenance: MaintenanceDB = self.db.get_maintenance();
+                maintenance.resync = true;
+                self.db
+                    .set_maintenance(&maintenance)
+                    .await
+                    .or_code(ErrorCode::Internal, "Database error")?;
 
-        if wallet == conf.rpc_wallet {
-            let tx_details: Value = self.daemon.get_transaction(&txid).await.unwrap();
-            let tx_io: &Vec<Value> = tx_details.get("details").unwrap().as_array().unwrap();
+                let window: MaintenanceWindow = self.maintenance_window().await;
 
-            if tx_io.is_empty() {
-                return;
+                return Ok(response::message(format!(
+                    "Resync scheduled for the maintenance window, {:02}:00 to {:02}:00 {}.",
+                    window.start, window.end, window.timezone
+                )));
             }
 
-            let tx_category: &str = tx_io[0].get("category").unwrap().as_str().unwrap();
+            let operation: OperationGuard = self.operations.start("force_resync").await?;
+
+            tokio::spawn(async move {
+                self.do_force_resync().await;
+                operation.finish(Ok(())).await;
+            });
+
+            Ok(response::message("Forcing a resync of the daemon..."))
+        })
+        .await
+    }
+
+    async fn get_overview(self, _: context::Context) -> GvReply<StakingDataOverview> {
+        guarded("get_overview", async move {
+            let cs_info = self
+                .daemon
+                .getcoldstakinginfo()
+                .await
+                .or_code(ErrorCode::DaemonError, "Error getting cold staking info")?;
 
             let current_time = chrono::Utc::now();
-            let timestamp: u64 = current_time.timestamp() as u64;
+            let conf = self.gv_config.read().await;
+
+            let total_staking = cs_info
+                .get("currently_staking")
+                .and_then(|staking| staking.as_f64())
+                .or_code(
+                    ErrorCode::DaemonError,
+                    "Malformed getcoldstakinginfo response",
+                )?;
+            let total_coldstaking = cs_info
+                .get("coin_in_coldstakeable_script")
+                .and_then(|coldstaking| coldstaking.as_f64())
+                .or_code(
+                    ErrorCode::DaemonError,
+                    "Malformed getcoldstakinginfo response",
+                )?;
+
+            let stakes_all: StakeTotals = self.get_stakes_days(0).await;
+            let stakes_24h: StakeTotals = self.get_stakes_days(1).await;
+            let stakes_7d: StakeTotals = self.get_stakes_days(7).await;
+            let stakes_14d: StakeTotals = self.get_stakes_days(14).await;
+            let stakes_30d: StakeTotals = self.get_stakes_days(30).await;
+            let stakes_90d: StakeTotals = self.get_stakes_days(90).await;
+            let stakes_180d: StakeTotals = self.get_stakes_days(180).await;
+            let stakes_1y: StakeTotals = self.get_stakes_days(365).await;
+
+            let time_zone: String = conf.timezone.clone();
+            let tz: Tz = parse_timezone(&time_zone)?;
+            drop(conf);
 
-            let is_stake: bool = match tx_category {
-                "stake" => true,
-                _ => false,
+            let start_year: u64 = time_ranges::year_start(&tz, current_time.timestamp() as u64);
+            let stakes_ytd: StakeTotals = self.get_stakes_days(start_year).await;
+
+            let stake_streak: StakeStreak = self.get_stake_streak().await;
+            let stake_forecast: Option<StakeForecast> = self.stake_forecast().await.ok();
+            let roi: StakingRoi = self.staking_roi().await;
+            let hot_wallet_info: HotWalletInfo = self.get_hot_wallet_data().await;
+            let hot_wallet: Option<HotWalletInfo> = if hot_wallet_info.loaded {
+                Some(hot_wallet_info)
+            } else {
+                None
             };
 
-            if is_stake {
-                let reward: RewardsDB = self
-                    .daemon
-                    .process_stake_transaction(&tx_details, &self.db)
-                    .await;
-                info!("New stake reward: {:?}", reward);
+            let staking_data = StakingDataOverview {
+                total_staking,
+                total_coldstaking,
+                stakes_24h,
+                stakes_7d,
+                stakes_14d,
+                stakes_30d,
+                stakes_90d,
+                stakes_180d,
+                stakes_1y,
+                stakes_ytd,
+                stakes_all,
+                hot_wallet,
+                stake_streak,
+                stake_forecast,
+                roi: Some(roi),
+            };
 
-                let stake_new_status = NewStakeStatusDB {
-                    txid: txid.clone(),
-                    confirmations: 1,
-                    timestamp: reward.timestamp,
-                    tg_msg_id: None,
-                };
+            Ok(response::ok(staking_data))
+        })
+        .await
+    }
 
-                let _ = self
-                    .db
-                    .set_new_stake_status(txid.as_bytes(), &stake_new_status)
-                    .await;
+    async fn get_mnemonic(
+        self,
+        _: context::Context,
+        passphrase: String,
+    ) -> GvReply<Option<String>> {
+        let audit: Audit = self.audit("get_mnemonic", serde_json::json!({}));
+        audited(audit, async move {
+            let conf = self.gv_config.read().await;
+            let mnemonic = conf.mnemonic.clone();
+            let sealed_mnemonic = conf.sealed_mnemonic.clone();
+            let primary: String = conf.rpc_wallet.clone();
+            drop(conf);
 
-                if self.tg_bot_active {
-                    let cs_info = self.daemon.getcoldstakinginfo().await.unwrap();
-
-                    let total_staking = cs_info.get("currently_staking").unwrap().as_f64().unwrap();
-                    let total_coldstaking = cs_info
-                        .get("coin_in_coldstakeable_script")
-                        .unwrap()
-                        .as_f64()
-                        .unwrap();
-
-                    let stakes_24h: StakeTotals = self.get_stakes_days(1).await;
-
-                    let january_first: chrono::prelude::NaiveDateTime =
-                        NaiveDate::from_ymd_opt(current_time.year(), 1, 1)
-                            .unwrap()
-                            .and_hms_opt(0, 0, 0)
-                            .unwrap();
-                    let time_zone: String = conf.timezone.clone();
-                    let tz: Tz = Tz::from_str_insensitive(&time_zone).unwrap();
-
-                    let start_year: u64 =
-                        january_first.and_local_timezone(tz).unwrap().timestamp() as u64;
-                    let stakes_ytd: StakeTotals = self.get_stakes_days(start_year).await;
-
-                    let staking_data: StakingData = StakingData {
-                        total_staking,
-                        total_coldstaking,
-                        stakes_24h,
-                        stakes_ytd,
-                    };
+            if let Some(owner) = self.db.mnemonic_wallet() {
+                if owner != primary {
+                    return Err(GvError::new(
+                        ErrorCode::InvalidState,
+                        format!(
+                            "The mnemonic on file is for cold wallet {}, {} has its own.",
+                            owner, primary
+                        ),
+                    ));
+                }
+            }
 
-                    let new_stake: NewStake = NewStake {
-                        height: reward.height,
-                        block_hash: reward.block_hash.clone(),
-                        txid: reward.txid.clone(),
-                        reward: self.daemon.convert_from_sat(reward.reward),
-                        agvr_reward: self.daemon.convert_from_sat(reward.agvr_reward),
-                        total_reward: self
-                            .daemon
-                            .convert_from_sat(reward.reward + reward.agvr_reward),
-                        staking_data,
-                    };
+            if let Some(mnemonic) = mnemonic {
+                return Ok(response::ok(Some(mnemonic)));
+            }
 
-                    let msg: Option<String> = None;
+            let sealed_mnemonic: String = match sealed_mnemonic {
+                Some(sealed_mnemonic) => sealed_mnemonic,
+                None => return Ok(response::ok(None)),
+            };
 
-                    let code_block: Option<String> =
-                        Some(serde_json::to_string_pretty(&new_stake).unwrap());
+            if passphrase.is_empty() {
+                self.notify_mnemonic_access("was refused, no passphrase was given")
+                    .await;
+                return Err(GvError::new(
+                    ErrorCode::Unauthorized,
+                    "The mnemonic is sealed, get it with gv-cli getmnemonic PASSPHRASE",
+                ));
+            }
 
-                    let header: String = format!("👻 New Block Found! 👻");
-                    let url = Some(vec![format!("https://ghostscan.io/tx/{}/", txid)]);
+            match config::open_mnemonic(&sealed_mnemonic, &passphrase) {
+                Ok(mnemonic) => {
+                    self.notify_mnemonic_access("was read").await;
+                    Ok(response::ok(Some(mnemonic)))
+                }
+                Err(_) => {
+                    self.notify_mnemonic_access("was refused, the passphrase was wrong")
+                        .await;
+                    Err(GvError::new(
+                        ErrorCode::Unauthorized,
+                        "Wrong passphrase for the sealed mnemonic",
+                    ))
+                }
+            }
+        })
+        .await
+    }
 
-                    let msg_type = "stake".to_string();
+    async fn seal_mnemonic(self, _: context::Context, passphrase: String) -> GvReply<String> {
+        let audit: Audit = self.audit("seal_mnemonic", serde_json::json!({}));
+        audited(audit, async move {
+            if passphrase.is_empty() {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "A passphrase is needed to seal the mnemonic.",
+                ));
+            }
 
-                    let tg_queue: TgBotQueueDB = TgBotQueueDB {
-                        timestamp,
-                        header,
-                        msg,
-                        code_block,
-                        url,
-                        msg_type,
-                        reward_txid: Some(reward.txid.clone()),
-                        msg_to_delete: None,
-                    };
+            let mut conf = self.gv_config.write().await;
 
-                    let in_tg_queue = self.db.get_tg_bot_queue(txid.as_bytes());
-                    if in_tg_queue.is_none() {
-                        self.db
-                            .set_tg_bot_queue(txid.as_bytes(), &tg_queue)
-                            .await
-                            .unwrap();
-                    }
+            let mnemonic: String = match conf.mnemonic.clone() {
+                Some(mnemonic) => mnemonic,
+                None if conf.sealed_mnemonic.is_some() => {
+                    return Err(GvError::new(
+                        ErrorCode::InvalidArgument,
+                        "The mnemonic is already sealed.",
+                    ))
                 }
-            } else {
-                info!("wallet tx!");
+                None => {
+                    return Err(GvError::new(
+                        ErrorCode::NotFound,
+                        "There is no mnemonic to seal.",
+                    ))
+                }
+            };
 
-                let mut is_incoming_zap = false;
-                let mut amount_int = 0;
-                let mut amount: f64 = 0.0;
+            let sealed_mnemonic: String = config::seal_mnemonic(&mnemonic, &passphrase)
+                .or_code(ErrorCode::Internal, "Unable to seal the mnemonic")?;
 
-                for tx in tx_io {
-                    let is_watchonly = tx
-                        .get("involvesWatchonly")
-                        .map_or(false, |val| val.as_bool().unwrap());
+            conf.update_gv_config("SEALED_MNEMONIC", &sealed_mnemonic)
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+            conf.update_gv_config("MNEMONIC", "")
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+            drop(conf);
 
-                    let is_receive = match tx.get("category").unwrap().as_str().unwrap() {
-                        "receive" => true,
-                        _ => false,
-                    };
+            let msg: &str = "The mnemonic is sealed, gv-cli getmnemonic now needs the passphrase. It can't be recovered without it.";
+            info!("{}", msg);
 
-                    if is_watchonly && is_receive {
-                        is_incoming_zap = true;
-                        amount += tx.get("amount").unwrap().as_f64().unwrap();
-                        amount_int += self.daemon.convert_to_sat(amount);
-                    }
-                }
+            self.queue_bot_message(
+                "mnemonic_access",
+                "👻 Mnemonic Sealed 👻",
+                "The mnemonic was sealed with a passphrase.".to_string(),
+            )
+            .await;
 
-                if is_incoming_zap {
-                    let confirms = tx_details
-                        .get("confirmations")
-                        .map_or(0, |val| val.as_i64().unwrap());
+            Ok(response::message(msg))
+        })
+        .await
+    }
 
-                    if confirms < 0 {
-                        return;
-                    }
+    async fn reload_config(self, _: context::Context) -> GvReply<ConfigReload> {
+        let audit: Audit = self.audit("reload_config", serde_json::json!({}));
+        audited(audit, async move {
+            let (gv_home, daemon_data_dir) = {
+                let conf = self.gv_config.read().await;
+                (conf.gv_home.clone(), conf.daemon_data_dir.clone())
+            };
+
+            // Encrypted values are read with the key unlock_config loaded at startup.
+            let new_conf: GVConfig = GVConfig::new(&gv_home, &daemon_data_dir).map_err(|err| {
+                GvError::new(
+                    ErrorCode::InvalidArgument,
+                    format!("Unable to read the config: {}", err),
+                )
+            })?;
+
+            let mut conf = self.gv_config.write().await;
+            let reload: ConfigReload = conf.apply_reload(new_conf);
+            let reloaded: GVConfig = conf.clone();
+            drop(conf);
 
-                    if confirms < 225 {
-                        let in_queue = self.db.get_zap_status(txid.as_bytes());
-                        let first_notice = false;
-                        let confirmations = confirms as u32;
-
-                        if in_queue.is_none() {
-                            let zap_status = ZapStatusDB {
-                                txid: txid.clone(),
-                                amount: amount_int,
-                                confirmations,
-                                first_notice,
-                            };
-                            self.db
-                                .set_zap_status(txid.as_bytes(), &zap_status)
-                                .await
-                                .unwrap();
-
-                            if self.tg_bot_active {
-                                let header = format!("👻 New Zap Detected! 👻");
-
-                                let msg = Some(format!(
-                                    "New deposit of {} GHOST is in your GhostVault!",
-                                    amount
-                                ));
-
-                                let url = Some(vec![format!("https://ghostscan.io/tx/{}/", txid)]);
-
-                                let msg_type = "zap".to_string();
-
-                                let tg_queue: TgBotQueueDB = TgBotQueueDB {
-                                    timestamp,
-                                    header,
-                                    msg,
-                                    code_block: None,
-                                    url,
-                                    msg_type,
-                                    reward_txid: None,
-                                    msg_to_delete: None,
-                                };
+            let applied = |key: &str| reload.applied.iter().any(|applied| applied == key);
 
-                                let mut zap_status =
-                                    self.db.get_zap_status(txid.as_bytes()).unwrap();
+            if applied("REWARD_INTERVAL") {
+                task_runner::update_payout_interval(&self.db, reloaded.reward_interval as i64)
+                    .await
+                    .or_code(ErrorCode::Internal, "Error updating payout task")?;
+            }
 
-                                let in_tg_queue = self.db.get_tg_bot_queue(txid.as_bytes());
-                                if in_tg_queue.is_none() && !zap_status.first_notice {
-                                    self.db
-                                        .set_tg_bot_queue(txid.as_bytes(), &tg_queue)
-                                        .await
-                                        .unwrap();
-                                    zap_status.first_notice = true;
-                                    self.db
-                                        .set_zap_status(txid.as_bytes(), &zap_status)
-                                        .await
-                                        .unwrap();
-                                }
-                            }
-                        }
-                    }
-                }
+            if [
+                "BACKUP_INTERVAL",
+                "BALANCE_SAMPLE_INTERVAL",
+                "SPLIT_INTERVAL",
+            ]
+            .iter()
+            .any(|key| applied(key))
+            {
+                task_runner::sync_task_intervals(&self.db, &reloaded).await;
             }
-        }
 
-        drop(conf);
+            if reload.applied.is_empty() && reload.restart_required.is_empty() {
+                info!("Config reloaded, nothing changed.");
+            } else {
+                info!(
+                    "Config reloaded, applied: [{}], needs a restart: [{}]",
+                    reload.applied.join(", "),
+                    reload.restart_required.join(", ")
+                );
+            }
+
+            Ok(response::ok(reload))
+        })
+        .await
     }
 
-    async fn set_bot_announce(
+    // The import runs in the background, its progress goes into the ready reason and the bot.
+    async fn import_wallet(
         self,
         _: context::Context,
-        msg_type: String,
-        new_value: bool,
-    ) -> Value {
-        let mut conf = self.gv_config.write().await;
+        mnemonic: String,
+        name: String,
+        birthday: Option<String>,
+    ) -> GvReply<String> {
+        let audit: Audit = self.audit(
+            "import_wallet",
+            serde_json::json!({ "name": &name, "birthday": &birthday }),
+        );
+        audited(audit, async move {
+            self.require_wallet_control().await?;
+
+            let mnemonic = mnemonic.trim();
+
+            let scan_from: Option<u64> = match birthday
+                .as_deref()
+                .map(str::trim)
+                .filter(|birthday| !birthday.is_empty())
+            {
+                Some(birthday) => Some(
+                    self.birthday_timestamp(birthday)
+                        .await?
+                        .saturating_sub(IMPORT_BIRTHDAY_MARGIN),
+                ),
+                None => None,
+            };
 
-        match msg_type.to_uppercase().as_str() {
-            "STAKE" => {
-                conf.update_gv_config("ANNOUNCE_STAKES", &new_value.to_string())
-                    .unwrap();
-            }
-            "ZAP" => {
-                conf.update_gv_config("ANNOUNCE_ZAPS", &new_value.to_string())
-                    .unwrap();
-            }
-            "REWARD" => {
-                conf.update_gv_config("ANNOUNCE_REWARDS", &new_value.to_string())
-                    .unwrap();
-            }
-            "ALL" => {
-                conf.update_gv_config("ANNOUNCE_STAKES", &new_value.to_string())
-                    .unwrap();
-                conf.update_gv_config("ANNOUNCE_ZAPS", &new_value.to_string())
-                    .unwrap();
-                conf.update_gv_config("ANNOUNCE_REWARDS", &new_value.to_string())
-                    .unwrap();
-            }
-            _ => {
-                return Value::String("Invalid message type!".to_string());
-            }
-        }
-        drop(conf);
+            let mnemonic_valid = self
+                .daemon
+                .validate_mnemonic(mnemonic)
+                .await
+                .or_code(ErrorCode::DaemonError, "Error validating mnemonic")?;
 
-        Value::String("Bot announcement updated!".to_string())
-    }
+            if !mnemonic_valid {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Invalid mnemonic!",
+                ));
+            }
 
-    async fn get_version_info(self, _: context::Context) -> Value {
-        let gv_version: String = VERSION.to_string();
-        let daemon_state: DaemonState = self.current_daemon_state().await;
+            let operation: OperationGuard = self.operations.start("import_wallet").await?;
 
-        let ghostd_version: String = daemon_state.version.clone();
-        let latest_release: String = daemon_state.latest_release.clone();
+            let mut server_ready: ServerReadyDB = self
+                .db
+                .get_server_ready()
+                .or_code(ErrorCode::Internal, "Database error")?;
 
-        let version_info: VersionInfo = VersionInfo {
-            gv_version,
-            ghostd_version,
-            latest_release,
-        };
-        serde_json::to_value(version_info).unwrap()
-    }
+            server_ready.daemon_ready = false;
+            server_ready.reason = Some("Importing wallet".to_string());
 
-    async fn get_reward_options(self, _: context::Context) -> Value {
-        let conf = self.gv_config.read().await;
-        let anon_mode = conf.anon_mode;
-        let reward_address = if anon_mode {
-            conf.anon_reward_address.clone().unwrap()
-        } else {
-            if conf.reward_address.is_none() {
-                "".to_string()
-            } else {
-                conf.reward_address.clone().unwrap()
-            }
-        };
+            self.db
+                .set_server_ready(&server_ready)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
+            self.set_daemon_available(false).await;
 
-        let reward_mode = if anon_mode {
-            "ANON".to_string()
-        } else if !anon_mode && !reward_address.is_empty() {
-            "STANDARD".to_string()
-        } else {
-            "DEFAULT".to_string()
-        };
+            let mnemonic: String = mnemonic.to_string();
+            tokio::spawn(async move {
+                let res = self
+                    .do_import_wallet(name, mnemonic, scan_from, server_ready)
+                    .await;
+                operation.finish(res).await;
+            });
 
-        let reward_interval_secs: Duration = Duration::from_secs(conf.reward_interval);
-        let reward_interval: String = format_duration(reward_interval_secs).to_string();
-        let reward_min: f64 = self.daemon.convert_from_sat(conf.min_reward_payout);
+            Ok(response::message(
+                "Importing the wallet, follow it with gv-cli status or in the Telegram bot.",
+            ))
+        })
+        .await
+    }
 
-        let rewards: RewardOptions = RewardOptions {
-            reward_mode,
-            reward_address,
-            reward_interval,
-            reward_min,
-        };
+    // Rebuilds the stake history from the wallets, for after a stake parsing fix or restoring an
+    // old database onto a newer wallet. Config and wallet state are left alone.
+    async fn reindex_history(self, _: context::Context) -> GvReply<String> {
+        let audit: Audit = self.audit("reindex_history", serde_json::json!({}));
+        audited(audit, async move {
+            // Stakes below the prune height couldn't be read back
+            if self.daemon.prune_height().await.is_some() {
+                return Err(GvError::new(
+                    ErrorCode::InvalidState,
+                    "ghostd is pruned, stakes from pruned blocks can't be read again.",
+                ));
+            }
 
-        serde_json::to_value(rewards).unwrap()
-    }
+            if let Some(rescan) = self.db.get_wallet_rescan() {
+                if rescan.status == "running" {
+                    return Err(GvError::new(
+                        ErrorCode::AlreadyRunning,
+                        "A wallet rescan is running, reindex once it's done!",
+                    ));
+                }
+            }
 
-    async fn check_chain(self, _: context::Context) -> Value {
-        let daemon_info = self.current_daemon_state().await;
-        Value::Bool(daemon_info.good_chain)
-    }
+            let mut server_ready: ServerReadyDB = self
+                .db
+                .get_server_ready()
+                .or_code(ErrorCode::Internal, "Database error")?;
 
-    async fn validate_address(self, _: context::Context, address: String) -> Value {
-        let addr_info = self.daemon.get_address_info(&address).await;
+            if !server_ready.daemon_ready {
+                return Err(GvError::new(
+                    ErrorCode::Unavailable,
+                    "Ghost daemon is busy, try again later!",
+                ));
+            }
 
-        let is_valid = !addr_info.is_err();
+            server_ready.daemon_ready = false;
+            server_ready.reason = Some("Reindexing stake history".to_string());
 
-        let is_mine: bool = if is_valid {
-            addr_info
-                .as_ref()
-                .unwrap()
-                .get("ismine")
-                .unwrap_or(&Value::Bool(false))
-                .as_bool()
-                .unwrap()
-        } else {
-            false
-        };
+            self.db
+                .set_server_ready(&server_ready)
+                .await
+                .or_code(ErrorCode::Internal, "Database error")?;
 
-        let is_256bit: bool = if is_valid {
-            addr_info
-                .as_ref()
-                .unwrap()
-                .get("is256bit")
-                .unwrap_or(&Value::Bool(false))
-                .as_bool()
-                .unwrap()
-        } else {
-            false
-        };
+            info!("Reindexing stake history...");
 
-        let addr_info: AddressInfo = AddressInfo {
-            is_mine,
-            is_valid,
-            is_256bit,
-        };
+            tokio::spawn(async move {
+                self.do_reindex_history(server_ready).await;
+            });
 
-        serde_json::to_value(addr_info).unwrap()
+            Ok(response::message(
+                "Reindexing the stake history, follow it with gv-cli status or in the Telegram bot.",
+            ))
+        })
+        .await
     }
 
-    async fn get_pending_rewards(self, _: context::Context) -> Value {
-        let balances = self.daemon.get_balances().await.unwrap();
-        let my_balances = balances.get("mine").unwrap().as_object().unwrap();
+    async fn start_server_tasks(self, _: context::Context) {
+        isolated("start_server_tasks", async move {
+            self.check_wallet_locked().await;
 
-        let trusted: f64 = my_balances.get("trusted").unwrap().as_f64().unwrap();
-        let untrusted_pending: f64 = my_balances
-            .get("untrusted_pending")
-            .unwrap()
-            .as_f64()
-            .unwrap();
-        let immature: f64 = my_balances.get("immature").unwrap().as_f64().unwrap();
-        let staked: f64 = my_balances.get("staked").unwrap().as_f64().unwrap();
-        let anon_trusted: f64 = my_balances.get("anon_trusted").unwrap().as_f64().unwrap();
-        let anon_immature: f64 = my_balances.get("anon_immature").unwrap().as_f64().unwrap();
-        let anon_pending: f64 = my_balances
-            .get("anon_untrusted_pending")
-            .unwrap()
-            .as_f64()
-            .unwrap();
+            let self_ref = Arc::new(async_RwLock::new(self));
 
-        let total_pending: f64 = trusted
-            + untrusted_pending
-            + immature
-            + staked
-            + anon_trusted
-            + anon_pending
-            + anon_immature;
+            let self_clone = Arc::clone(&self_ref);
+            let self_clone2 = Arc::clone(&self_ref);
+            let self_clone3 = Arc::clone(&self_ref);
+            let self_clone4 = Arc::clone(&self_ref);
+            let self_clone5 = Arc::clone(&self_ref);
+            let self_clone6 = Arc::clone(&self_ref);
+            let self_clone7 = Arc::clone(&self_ref);
 
-        let conf = self.gv_config.read().await;
+            tokio::spawn(async move {
+                let self_lock = self_clone.read().await;
+                self_lock.monitor_daemon_sync().await;
+            });
 
-        let next_payout_time: i64 = task_runner::get_next_payout_time(&self.db).await.unwrap();
-
-        let next_payout_time: DateTime<Utc> =
-            DateTime::from_timestamp(next_payout_time, 0).unwrap();
-        let time_zone: String = conf.timezone.clone();
-        let tz: Tz = Tz::from_str_insensitive(&time_zone).unwrap();
-        let next_payout_run: String = next_payout_time.with_timezone(&tz).to_string();
-
-        let pending_rewards: PendingRewards = PendingRewards {
-            total_pending,
-            staked,
-            pending_anonymization: trusted + untrusted_pending + immature,
-            pending_anon_confs: anon_immature + anon_pending,
-            pending_payout: anon_trusted,
-            payout_run_interval: format_duration(Duration::from_secs(conf.reward_interval))
-                .to_string(),
-            next_payout_run,
-            min_payout: self.daemon.convert_from_sat(conf.min_reward_payout),
-        };
+            tokio::spawn(async move {
+                let self_lock = self_clone2.read().await;
+                self_lock.check_chain_task().await;
+            });
 
-        serde_json::to_value(&pending_rewards).unwrap()
-    }
+            tokio::spawn(async move {
+                let self_lock = self_clone3.read().await;
+                self_lock.monitor_daemon_online().await;
+            });
 
-    async fn process_daemon_update(self, _: context::Context) -> Value {
-        info!("Checking for new update");
-        let version_str: String = self.daemon.get_daemon_version().await.unwrap();
-        let latest_release_res: Result<String, Box<dyn std::error::Error + Send + Sync>> =
-            gv_methods::get_latest_release().await;
+            tokio::spawn(async move {
+                let self_lock = self_clone4.read().await;
+                self_lock.monitor_stake_streak().await;
+            });
 
-        let latest_release_str: String = if latest_release_res.is_err() {
-            return Value::String("Failed to check for updates!".to_string());
-        } else {
-            latest_release_res.unwrap()
-        };
+            tokio::spawn(async move {
+                let self_lock = self_clone5.read().await;
+                self_lock.monitor_missed_stakes().await;
+            });
 
-        let version: u64 = version_str.replace(".", "").parse::<u64>().unwrap();
-        let latest_release: u64 = latest_release_str.replace(".", "").parse::<u64>().unwrap();
+            tokio::spawn(async move {
+                let self_lock = self_clone6.read().await;
+                self_lock.monitor_system_stats().await;
+            });
 
-        if latest_release > version {
-            let release_clone = latest_release_str.clone();
             tokio::spawn(async move {
-                let _ = self.do_update(&release_clone).await;
+                let self_lock = self_clone7.read().await;
+                self_lock.monitor_network_stats().await;
             });
-            return Value::String(latest_release_str);
-        } else {
-            info!("Daemon is up to date!");
-            return Value::Bool(false);
-        }
+        })
+        .await
     }
 
-    async fn get_daemon_online(self, _: context::Context) -> Value {
-        let daemon_online: bool = self.daemon_online().await;
+    async fn set_timezone(self, _: context::Context, timezone: String) -> GvReply<String> {
+        let audit: Audit = self.audit("set_timezone", serde_json::json!({ "timezone": &timezone }));
+        audited(audit, async move {
+            let valid_timezone = Tz::from_str_insensitive(&timezone);
 
-        if !daemon_online {
-            self.db.gvdb.flush_async().await.unwrap();
-            let server_ready = self.db.get_server_ready().unwrap();
-            serde_json::to_value(server_ready).unwrap()
-        } else {
-            Value::Bool(daemon_online)
-        }
+            if valid_timezone.is_err() {
+                return Err(GvError::new(
+                    ErrorCode::InvalidArgument,
+                    "Invalid timezone!",
+                ));
+            }
+
+            let mut conf = self.gv_config.write().await;
+            conf.update_gv_config("TIMEZONE", &timezone)
+                .or_code(ErrorCode::Internal, "Error updating config")?;
+            Ok(response::message("Timezone updated!"))
+        })
+        .await
     }
 
-    async fn get_stake_barchart_data(
+    async fn get_payout_history(
         self,
         _: context::Context,
         start: u64,
         end: u64,
-        division: String,
-    ) -> Value {
-        let stake_data: BarChart = self.get_stake_barchart_vec(start, end, &division).await;
-        serde_json::to_value(stake_data).unwrap()
+    ) -> GvReply<PayoutHistory> {
+        guarded("get_payout_history", async move {
+            let payout_history: PayoutHistory = self.get_payout_history_vec(start, end).await;
+            Ok(response::ok(payout_history))
+        })
+        .await
     }
+}
+
+fn bool_to_yn(bool_val: bool) -> String {
+    let new_val: &str = if bool_val { "YES" } else { "NO" };
+    new_val.to_string()
+}
 
-    async fn get_earnings_chart_data(self, _: context::Context, start: u64, end: u64) -> Value {
-        let earnings_data: AllTimeEarnigns = self.get_earnings_chart_vec(start, end).await;
-        serde_json::to_value(earnings_data).unwrap()
+// Runs a GvCLI handler. A handler that panics answers with an internal error instead of
+// leaving the caller waiting on a request that will never finish.
+async fn guarded<T>(method: &str, handler: impl Future<Output = GvResult<T>>) -> GvReply<T> {
+    match AssertUnwindSafe(handler).catch_unwind().await {
+        Ok(result) => response::from_result(result),
+        Err(panic) => {
+            error!("Handler {} panicked: {}", method, panic_reason(&panic));
+            response::error(
+                ErrorCode::Internal,
+                format!("Internal error while handling {}", method),
+            )
+        }
     }
+}
 
-    async fn process_payouts(self, _: context::Context) {
-        tokio::spawn(async move {
-            self.do_reward_payout().await;
-        });
+// Same as guarded, with the outcome added to the audit log.
+async fn audited<T>(audit: Audit, handler: impl Future<Output = GvResult<T>>) -> GvReply<T> {
+    let reply: GvReply<T> = guarded(audit.method, handler).await;
+
+    let entry: AuditDB = AuditDB {
+        id: audit.db.next_audit_id(),
+        timestamp: chrono::Utc::now().timestamp() as u64,
+        method: audit.method.to_string(),
+        origin: audit.origin,
+        peer: audit.peer,
+        params: audit.params,
+        ok: reply.ok,
+        error: if reply.ok {
+            None
+        } else {
+            reply.message.clone()
+        },
+    };
+
+    if let Err(err) = audit.db.set_audit(&entry).await {
+        error!("Unable to add {} to the audit log: {}", entry.method, err);
     }
 
-    async fn force_resync(self, _: context::Context) -> Value {
-        tokio::spawn(async move {
-            self.do_force_resync().await;
-        });
+    reply
+}
 
-        Value::String("Forcing a resync of the daemon...".to_string())
+// Same as guarded, for the notification handlers that have nothing to answer with.
+async fn isolated(method: &str, handler: impl Future<Output = ()>) {
+    if let Err(panic) = AssertUnwindSafe(handler).catch_unwind().await {
+        error!("Handler {} panicked: {}", method, panic_reason(&panic));
     }
+}
 
-    async fn get_overview(self, _: context::Context) -> Value {
-        let cs_info = self.daemon.getcoldstakinginfo().await.unwrap();
+// What the network says about the chain at our best block. The explorers are asked unless
+// CHAIN_PEERS lists other vaults to ask instead, or LOCAL_CHAIN_CHECK is on, then ghostd's
+// peers and chain tips are all there is to go on.
+struct RemoteChain {
+    best_block: u32,
+    best_block_hash: String,
+    remote_hash: String,
+    good_chain: bool,
+}
 
-        let current_time = chrono::Utc::now();
-        let conf = self.gv_config.read().await;
+async fn check_remote_chain(
+    gv_config: &Arc<async_RwLock<GVConfig>>,
+    daemon: &DaemonHelper,
+    best_block: u32,
+    best_block_hash: &str,
+) -> Result<RemoteChain, Box<dyn std::error::Error + Send + Sync>> {
+    let conf = gv_config.read().await;
+    let local_chain_check: bool = conf.local_chain_check;
+    let chain_peers: Vec<FleetVault> = conf.chain_peers.clone();
+    let explorer: ExplorerProvider = ExplorerProvider::new(&conf);
+    drop(conf);
 
-        let total_staking = cs_info.get("currently_staking").unwrap().as_f64().unwrap();
-        let total_coldstaking = cs_info
-            .get("coin_in_coldstakeable_script")
-            .unwrap()
-            .as_f64()
-            .unwrap();
+    if !chain_peers.is_empty() {
+        return check_chain_peers(&chain_peers, daemon, best_block, best_block_hash).await;
+    }
 
-        let stakes_all: StakeTotals = self.get_stakes_days(0).await;
-        let stakes_24h: StakeTotals = self.get_stakes_days(1).await;
-        let stakes_7d: StakeTotals = self.get_stakes_days(7).await;
-        let stakes_14d: StakeTotals = self.get_stakes_days(14).await;
-        let stakes_30d: StakeTotals = self.get_stakes_days(30).await;
-        let stakes_90d: StakeTotals = self.get_stakes_days(90).await;
-        let stakes_180d: StakeTotals = self.get_stakes_days(180).await;
-        let stakes_1y: StakeTotals = self.get_stakes_days(365).await;
-
-        let january_first: chrono::prelude::NaiveDateTime =
-            NaiveDate::from_ymd_opt(current_time.year(), 1, 1)
-                .unwrap()
-                .and_hms_opt(0, 0, 0)
-                .unwrap();
-        let time_zone: String = conf.timezone.clone();
-        let tz: Tz = Tz::from_str_insensitive(&time_zone).unwrap();
-
-        let start_year: u64 = january_first.and_local_timezone(tz).unwrap().timestamp() as u64;
-        let stakes_ytd: StakeTotals = self.get_stakes_days(start_year).await;
-
-        let staking_data = StakingDataOverview {
-            total_staking,
-            total_coldstaking,
-            stakes_24h,
-            stakes_7d,
-            stakes_14d,
-            stakes_30d,
-            stakes_90d,
-            stakes_180d,
-            stakes_1y,
-            stakes_ytd,
-            stakes_all,
-        };
+    if local_chain_check {
+        let check: LocalChainCheck = daemon.local_chain_check().await?;
 
-        serde_json::to_value(staking_data).unwrap()
+        return Ok(RemoteChain {
+            best_block: check.peer_height,
+            best_block_hash: check.best_header_hash,
+            remote_hash: check.fork_hash.unwrap_or(best_block_hash.to_string()),
+            good_chain: check.good_chain,
+        });
     }
 
-    async fn get_mnemonic(self, _: context::Context) -> Value {
-        let conf = self.gv_config.read().await;
-        let mnemonic = conf.mnemonic.clone();
+    let (bc_info, remote_hash) = tokio::try_join!(
+        explorer.get_blockchain_info(),
+        explorer.get_block_hash(best_block)
+    )?;
+
+    Ok(RemoteChain {
+        best_block: bc_info
+            .get("blocks")
+            .and_then(|blocks| blocks.as_u64())
+            .ok_or("Malformed getblockchaininfo response")? as u32,
+        best_block_hash: bc_info
+            .get("bestblockhash")
+            .and_then(|hash| hash.as_str())
+            .ok_or("Malformed getblockchaininfo response")?
+            .to_string(),
+        good_chain: remote_hash == best_block_hash,
+        remote_hash,
+    })
+}
 
-        if mnemonic.is_none() {
-            Value::Null
-        } else {
-            Value::String(mnemonic.unwrap())
+struct PeerChain {
+    tip: ChainTip,
+    hash: String,
+    agrees: bool,
+}
+
+// Each peer is asked for its tip and its hash at the lower of the two best blocks. The chain
+// is bad once more peers disagree with ours than agree, peers that can't be reached or
+// aren't synced don't count either way.
+async fn check_chain_peers(
+    peers: &[FleetVault],
+    daemon: &DaemonHelper,
+    best_block: u32,
+    best_block_hash: &str,
+) -> Result<RemoteChain, Box<dyn std::error::Error + Send + Sync>> {
+    let checks = peers.iter().map(|peer| async move {
+        let check = tokio::time::timeout(
+            Duration::from_secs(CHAIN_PEER_TIMEOUT),
+            peer_chain(peer, daemon, best_block, best_block_hash),
+        )
+        .await;
+
+        match check {
+            Ok(Ok(peer_chain)) => Some(peer_chain),
+            Ok(Err(err)) => {
+                warn!("Chain peer {} not used: {}", peer.name, err);
+                None
+            }
+            Err(_) => {
+                warn!("Chain peer {} timed out", peer.name);
+                None
+            }
         }
+    });
+
+    let results: Vec<PeerChain> = future::join_all(checks)
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let best: &PeerChain = results
+        .iter()
+        .max_by_key(|peer_chain| peer_chain.tip.best_block)
+        .ok_or("No chain peers could be used")?;
+
+    let disagree: Option<&PeerChain> = results.iter().find(|peer_chain| !peer_chain.agrees);
+    let disagreeing: usize = results
+        .iter()
+        .filter(|peer_chain| !peer_chain.agrees)
+        .count();
+
+    Ok(RemoteChain {
+        best_block: best.tip.best_block,
+        best_block_hash: best.tip.best_block_hash.clone(),
+        remote_hash: disagree.map_or(best_block_hash.to_string(), |peer_chain| {
+            peer_chain.hash.clone()
+        }),
+        good_chain: disagreeing <= results.len() - disagreeing,
+    })
+}
+
+async fn peer_chain(
+    peer: &FleetVault,
+    daemon: &DaemonHelper,
+    best_block: u32,
+    best_block_hash: &str,
+) -> Result<PeerChain, Box<dyn std::error::Error + Send + Sync>> {
+    let caller: CLICaller = CLICaller::new(&peer.cli_address, true).await?;
+    let tip: ChainTip = caller.call_get_chain_tip().await?;
+
+    if !tip.synced {
+        return Err("ghostd is not synced".into());
     }
 
-    async fn import_wallet(self, _: context::Context, mnemonic: String, name: String) -> Value {
-        let mnemonic = mnemonic.trim();
+    let height: u32 = best_block.min(tip.best_block);
 
-        let mnemonic_valid = self.daemon.validate_mnemonic(mnemonic).await.unwrap();
+    let hash: String = if height == tip.best_block {
+        tip.best_block_hash.clone()
+    } else {
+        caller.call_get_block_hash_at(height).await?
+    };
 
-        if !mnemonic_valid {
-            return Value::String("Invalid mnemonic!".to_string());
-        } else {
-            let mut server_ready: ServerReadyDB = self.db.get_server_ready().unwrap();
+    let local_hash: String = if height == best_block {
+        best_block_hash.to_string()
+    } else {
+        daemon.getblockhash(height).await?
+    };
+
+    Ok(PeerChain {
+        agrees: hash == local_hash,
+        tip,
+        hash,
+    })
+}
 
-            server_ready.daemon_ready = false;
-            server_ready.reason = Some("Importing Wallet".to_string());
+fn expected_deposit(deposit: &ExpectedDepositDB, now: u64) -> ExpectedDeposit {
+    let status: &str = if deposit.txid.is_some() {
+        "arrived"
+    } else if now > deposit.due {
+        "missed"
+    } else {
+        "waiting"
+    };
+
+    ExpectedDeposit {
+        id: deposit.id,
+        amount: Amount::from_sat(deposit.amount),
+        memo: deposit.memo.clone(),
+        created: deposit.created,
+        due: deposit.due,
+        status: status.to_string(),
+        txid: deposit.txid.clone(),
+    }
+}
 
-            self.db.set_server_ready(&server_ready).await.unwrap();
-            self.set_daemon_available(false).await;
-            let res = self.daemon.import_wallet(&name, mnemonic, &self.db).await;
-            match res {
-                Ok(_) => {
-                    let _ = tokio::spawn(async move {
-                        self.db.clear_db().await.unwrap();
-                        self.daemon.cleanup_missing_tx(&self.db).await;
-                        self.set_daemon_available(true).await;
-                        server_ready.daemon_ready = true;
-                        server_ready.reason = None;
-                        self.db.set_server_ready(&server_ready).await.unwrap();
-                    });
+// A txid or block hash, 64 hex characters.
+// Dates in chart and history replies, in the vault's TIMEZONE or one the caller asked for.
+fn date_str_in(tz: &Tz, timestamp: u64) -> String {
+    DateTime::from_timestamp(timestamp as i64, 0)
+        .unwrap_or_default()
+        .with_timezone(tz)
+        .format("%d/%m/%y")
+        .to_string()
+}
 
-                    Value::String("Wallet imported!".to_string())
-                }
-                Err(err) => Value::String(format!("Error importing wallet: {:?}", err)),
-            }
-        }
-    }
+// A timezone given with a GvCLI call, rather than the vault's TIMEZONE.
+fn parse_timezone(timezone: &str) -> Result<Tz, GvError> {
+    Tz::from_str_insensitive(timezone)
+        .map_err(|_| GvError::new(ErrorCode::InvalidArgument, "Invalid timezone!"))
+}
 
-    async fn start_server_tasks(self, _: context::Context) {
-        let self_ref = Arc::new(async_RwLock::new(self));
+// Whether ghostd is synced, its tip height and hash, and its version.
+async fn startup_chain_state(
+    daemon: &DaemonHelper,
+) -> Result<(bool, u32, String, String), GvError> {
+    let blockchain_info: Value = daemon
+        .call_status(true)
+        .await
+        .or_code(ErrorCode::DaemonError, "Error getting blockchain info")?;
+    let synced: bool = !daemon
+        .is_syncing()
+        .await
+        .or_code(ErrorCode::DaemonError, "Error checking the sync state")?;
+    let best_block: u32 = reply_field(&blockchain_info, "blocks", Value::as_u64)? as u32;
+    let best_block_hash: String = reply_str(&blockchain_info, "bestblockhash")?;
+    let version: String = daemon
+        .get_daemon_version()
+        .await
+        .or_code(ErrorCode::DaemonError, "Error getting daemon version")?;
+
+    Ok((synced, best_block, best_block_hash, version))
+}
 
-        let self_clone = Arc::clone(&self_ref);
-        let self_clone2 = Arc::clone(&self_ref);
-        let self_clone3 = Arc::clone(&self_ref);
+// A field of a ghostd reply. One that's missing or of another type is a DaemonError naming it,
+// so a changed RPC fails the call instead of panicking the handler or task it runs in.
+fn reply_field<T>(reply: &Value, key: &str, read: fn(&Value) -> Option<T>) -> Result<T, GvError> {
+    reply.get(key).and_then(read).or_code(
+        ErrorCode::DaemonError,
+        &format!("ghostd reply has no {}", key),
+    )
+}
 
-        tokio::spawn(async move {
-            let self_lock = self_clone.read().await;
-            self_lock.monitor_daemon_sync().await;
-        });
+fn reply_str(reply: &Value, key: &str) -> Result<String, GvError> {
+    reply_field(reply, key, |value| value.as_str().map(str::to_string))
+}
 
-        tokio::spawn(async move {
-            let self_lock = self_clone2.read().await;
-            self_lock.check_chain_task().await;
-        });
+fn is_hash(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
 
-        tokio::spawn(async move {
-            let self_lock = self_clone3.read().await;
-            self_lock.monitor_daemon_online().await;
-        });
-    }
+// Free and total bytes of the filesystem path is on, the deepest mount point above it.
+// Zeros when it can't be found.
+fn disk_space(sys: &System, path: &Path) -> (u64, u64) {
+    let path: PathBuf = path.canonicalize().unwrap_or(path.to_path_buf());
+
+    sys.mounts()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|mount| path.starts_with(&mount.fs_mounted_on))
+        .max_by_key(|mount| mount.fs_mounted_on.len())
+        .map_or((0, 0), |mount| (mount.avail.as_u64(), mount.total.as_u64()))
+}
+
+// A disk that couldn't be read has no total and never counts as low.
+fn stake_progress(confirms: u64, stake_confirmations: u64) -> String {
+    format!(
+        "Confirmations: {}/{}, the reward is spendable once it matures.",
+        confirms.min(stake_confirmations),
+        stake_confirmations
+    )
+}
 
-    async fn set_timezone(self, _: context::Context, timezone: String) -> Value {
-        let valid_timezone = Tz::from_str_insensitive(&timezone);
+fn low_disk(free: u64, total: u64, disk_alert_gb: u64) -> bool {
+    disk_alert_gb > 0 && total > 0 && free < disk_alert_gb.saturating_mul(1_000_000_000)
+}
 
-        if valid_timezone.is_err() {
-            return Value::String("Invalid timezone!".to_string());
+// Merges runs of neighbouring points so a chart has at most max_points, `merge` folds one run.
+fn downsample<T>(
+    data: Vec<Vec<T>>,
+    max_points: Option<u64>,
+    merge: impl Fn(&[Vec<T>]) -> Vec<T>,
+) -> Vec<Vec<T>> {
+    match max_points {
+        Some(max_points) if max_points > 0 && data.len() as u64 > max_points => {
+            let run: usize = data.len().div_ceil(max_points as usize);
+            data.chunks(run).map(merge).collect()
         }
+        _ => data,
+    }
+}
 
-        let mut conf = self.gv_config.write().await;
-        conf.update_gv_config("TIMEZONE", &timezone).unwrap();
-        Value::String("Timezone updated!".to_string())
+// The requested page of chart points, along with the offset of the next page if there is one.
+fn chart_page<T>(data: Vec<T>, page: &ChartPage) -> (Vec<T>, Option<u64>) {
+    let limit: u64 = if page.limit == 0 {
+        CHART_PAGE_DEFAULT
+    } else {
+        page.limit.min(CHART_PAGE_MAX)
+    };
+    let total: u64 = data.len() as u64;
+    let next: u64 = page.offset.saturating_add(limit);
+
+    let page: Vec<T> = data
+        .into_iter()
+        .skip(page.offset as usize)
+        .take(limit as usize)
+        .collect();
+
+    (page, (next < total).then_some(next))
+}
+
+fn last_lines(mut lines: Vec<String>, count: usize) -> Vec<String> {
+    lines.split_off(lines.len().saturating_sub(count))
+}
+
+// Keeps lines at the given level or worse. Plain lines carry the level as a word, JSON lines
+// in their level field. Lines without one, like the rest of a multi line message, go with the
+// line before them.
+fn filter_log_lines(lines: Vec<String>, max_level: log::Level) -> Vec<String> {
+    let mut keep: bool = false;
+
+    lines
+        .into_iter()
+        .filter(|line| {
+            if let Some(level) = log_line_level(line) {
+                keep = level <= max_level;
+            }
+            keep
+        })
+        .collect()
+}
+
+fn log_line_level(line: &str) -> Option<log::Level> {
+    if line.starts_with('{') {
+        if let Ok(entry) = serde_json::from_str::<Value>(line) {
+            return entry["level"]
+                .as_str()
+                .and_then(|level| log::Level::from_str(level).ok());
+        }
     }
+
+    line.split_whitespace().take(3).find_map(|word| match word {
+        "ERROR" | "WARN" | "INFO" | "DEBUG" | "TRACE" => log::Level::from_str(word).ok(),
+        _ => None,
+    })
 }
 
-fn bool_to_yn(bool_val: bool) -> String {
-    let new_val: &str = if bool_val { "YES" } else { "NO" };
-    new_val.to_string()
+fn panic_reason(panic: &Box<dyn Any + Send>) -> &str {
+    panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(|reason| reason.as_str()))
+        .unwrap_or("unknown panic")
 }
 
 async fn spawn(fut: impl Future<Output = ()> + Send + 'static) {
@@ -2498,24 +11785,6 @@ async fn do_shutdown() {
     std::process::exit(0);
 }
 
-async fn get_zmq_listen_addr(gv_config: Arc<async_RwLock<GVConfig>>) -> Vec<String> {
-    let conf = gv_config.read().await;
-    let zmq_block_host = conf.zmq_block_host.clone();
-    let zmq_tx_host = conf.zmq_tx_host.clone();
-    drop(conf);
-
-    let mut host_vec: Vec<String> = Vec::new();
-
-    if zmq_block_host == zmq_tx_host {
-        host_vec.push(zmq_block_host);
-    } else {
-        host_vec.push(zmq_block_host);
-        host_vec.push(zmq_tx_host);
-    }
-
-    host_vec
-}
-
 pub async fn run_server(
     gv_config: &Arc<async_RwLock<GVConfig>>,
     db: &Arc<GVDB>,
@@ -2530,6 +11799,40 @@ pub async fn run_server(
         split_ip[1].parse::<u16>().unwrap(),
     );
     let server = GvCLIServer::new(gv_config, db).await;
+
+    if let Some(grpc_address) = conf_clone.grpc_address.clone() {
+        #[cfg(feature = "grpc")]
+        {
+            let grpc_server: GvCLIServer = server.clone();
+            let tls = conf_clone
+                .cli_tls_cert
+                .clone()
+                .zip(conf_clone.cli_tls_key.clone());
+
+            tokio::spawn(async move {
+                if let Err(err) = crate::grpc_server::serve(grpc_server, grpc_address, tls).await {
+                    error!("gRPC server stopped: {}", err);
+                }
+            });
+        }
+
+        #[cfg(not(feature = "grpc"))]
+        warn!(
+            "GRPC_ADDRESS is set to {}, but GhostVault was built without the 'grpc' feature.",
+            grpc_address
+        );
+    }
+
+    if let (Some(cert), Some(key)) = (&conf_clone.cli_tls_cert, &conf_clone.cli_tls_key) {
+        let acceptor: TlsAcceptor = cli_tls::acceptor(cert, key)
+            .map_err(|err| anyhow::anyhow!("Unable to set up TLS: {}", err))?;
+        return run_tls_server(server, server_addr, acceptor).await;
+    }
+
+    if !server_addr.0.is_loopback() {
+        warn!("The RPC server is reachable from other hosts without TLS, set CLI_TLS_CERT and CLI_TLS_KEY to turn it on");
+    }
+
     let mut listener = tarpc::serde_transport::tcp::listen(&server_addr, Json::default).await?;
     tracing::info!("Listening on port {}", listener.local_addr().port());
     listener.config_mut().max_frame_length(usize::MAX);
@@ -2537,7 +11840,90 @@ pub async fn run_server(
         .filter_map(|r| future::ready(r.ok()))
         .map(BaseChannel::with_defaults)
         .max_channels_per_key(10, |t| t.transport().peer_addr().unwrap().ip())
-        .map(|channel| channel.execute(server.clone().serve()).for_each(spawn))
+        .map(|channel| {
+            let peer: Option<String> = channel
+                .transport()
+                .peer_addr()
+                .ok()
+                .map(|addr| addr.to_string());
+            channel
+                .execute(server.for_connection(peer).serve())
+                .for_each(spawn)
+        })
+        .buffer_unordered(10)
+        .for_each(|_| async {})
+        .await;
+
+    Ok(())
+}
+
+// Handshakes run in their own tasks so a slow client can't hold up the others, finished
+// connections are fed into the same channel pipeline as the plain listener.
+async fn run_tls_server(
+    server: GvCLIServer,
+    server_addr: (IpAddr, u16),
+    acceptor: TlsAcceptor,
+) -> anyhow::Result<()> {
+    let listener: TcpListener = TcpListener::bind(server_addr).await?;
+    tracing::info!(
+        "Listening with TLS on port {}",
+        listener.local_addr()?.port()
+    );
+
+    let (sender, receiver) = futures::channel::mpsc::unbounded();
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(err) => {
+                    warn!("Unable to accept RPC connection: {}", err);
+                    continue;
+                }
+            };
+
+            let acceptor: TlsAcceptor = acceptor.clone();
+            let sender = sender.clone();
+
+            tokio::spawn(async move {
+                match acceptor.accept(stream).await {
+                    Ok(stream) => {
+                        let transport = tarpc::serde_transport::new(
+                            cli_tls::server_framed(stream),
+                            Json::default(),
+                        );
+                        let _ = sender.unbounded_send(transport);
+                    }
+                    Err(err) => warn!("TLS handshake with {} failed: {}", peer, err),
+                }
+            });
+        }
+    });
+
+    receiver
+        .map(BaseChannel::with_defaults)
+        .max_channels_per_key(10, |t| {
+            t.transport()
+                .get_ref()
+                .get_ref()
+                .0
+                .peer_addr()
+                .unwrap()
+                .ip()
+        })
+        .map(|channel| {
+            let peer: Option<String> = channel
+                .transport()
+                .get_ref()
+                .get_ref()
+                .0
+                .peer_addr()
+                .ok()
+                .map(|addr| addr.to_string());
+            channel
+                .execute(server.for_connection(peer).serve())
+                .for_each(spawn)
+        })
         .buffer_unordered(10)
         .for_each(|_| async {})
         .await;
@@ -0,0 +1,199 @@
+// gRPC service from proto/ghostvault.proto, for frontends that want a schema to generate clients
+// from. Each call is answered by the matching GvCLI handler, its data is read straight into the
+// reply message through JSON, so both interfaces always agree.
+use crate::cli_server::GvCLIServer;
+use pb::{
+    ghost_vault_server::{GhostVault, GhostVaultServer},
+    ActionReply, CsvExport, Empty, Overview, PayoutHistory, RewardOptions, SetPayoutMinRequest,
+    SetRewardIntervalRequest, SetRewardModeRequest, TimeRange, VaultStatus, VersionInfo,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use service::{
+    response::{ErrorCode, GvError, GvReply},
+    GvCLI,
+};
+use std::{error::Error, net::SocketAddr, path::PathBuf};
+use tarpc::context;
+use tonic::{
+    transport::{Identity, Server, ServerTlsConfig},
+    Request, Response, Status,
+};
+
+pub mod pb {
+    tonic::include_proto!("ghostvault.v1");
+}
+
+struct GrpcService {
+    server: GvCLIServer,
+}
+
+impl GrpcService {
+    // Each request gets its own copy of the server, so the audit log has its peer and origin.
+    fn server<T>(&self, request: &Request<T>) -> GvCLIServer {
+        let peer: Option<String> = request.remote_addr().map(|addr| addr.to_string());
+        self.server.for_origin(peer, "grpc")
+    }
+}
+
+fn to_status(err: GvError) -> Status {
+    let message: String = err.message;
+
+    match err.code {
+        ErrorCode::InvalidArgument => Status::invalid_argument(message),
+        ErrorCode::NotFound => Status::not_found(message),
+        ErrorCode::AlreadyRunning => Status::already_exists(message),
+        ErrorCode::Unauthorized => Status::permission_denied(message),
+        ErrorCode::InvalidState
+        | ErrorCode::Expired
+        | ErrorCode::Disabled
+        | ErrorCode::NotConfigured => Status::failed_precondition(message),
+        ErrorCode::DaemonError | ErrorCode::Unavailable => Status::unavailable(message),
+        ErrorCode::Unsupported => Status::unimplemented(message),
+        ErrorCode::Internal => Status::internal(message),
+    }
+}
+
+fn reply<T: DeserializeOwned, D: Serialize + DeserializeOwned>(
+    reply: GvReply<D>,
+) -> Result<Response<T>, Status> {
+    let data: D = reply.into_result().map_err(to_status)?;
+
+    serde_json::to_value(data)
+        .and_then(serde_json::from_value)
+        .map(Response::new)
+        .map_err(|err| Status::internal(format!("Malformed response: {}", err)))
+}
+
+fn action_reply(reply: GvReply<String>) -> Result<Response<ActionReply>, Status> {
+    let message: String = reply.into_result().map_err(to_status)?;
+    Ok(Response::new(ActionReply { message }))
+}
+
+fn time_range(range: TimeRange) -> (u64, u64) {
+    let end: u64 = if range.end == 0 {
+        chrono::Utc::now().timestamp() as u64
+    } else {
+        range.end
+    };
+
+    (range.start, end)
+}
+
+#[tonic::async_trait]
+impl GhostVault for GrpcService {
+    async fn get_status(&self, request: Request<Empty>) -> Result<Response<VaultStatus>, Status> {
+        let server: GvCLIServer = self.server(&request);
+        reply(server.get_daemon_state(context::current()).await)
+    }
+
+    async fn get_version(&self, request: Request<Empty>) -> Result<Response<VersionInfo>, Status> {
+        let server: GvCLIServer = self.server(&request);
+        reply(server.get_version_info(context::current()).await)
+    }
+
+    async fn get_overview(&self, request: Request<Empty>) -> Result<Response<Overview>, Status> {
+        let server: GvCLIServer = self.server(&request);
+        reply(server.get_overview(context::current()).await)
+    }
+
+    async fn get_reward_options(
+        &self,
+        request: Request<Empty>,
+    ) -> Result<Response<RewardOptions>, Status> {
+        let server: GvCLIServer = self.server(&request);
+        reply(server.get_reward_options(context::current()).await)
+    }
+
+    async fn set_reward_mode(
+        &self,
+        request: Request<SetRewardModeRequest>,
+    ) -> Result<Response<ActionReply>, Status> {
+        let server: GvCLIServer = self.server(&request);
+        let request: SetRewardModeRequest = request.into_inner();
+
+        action_reply(
+            server
+                .set_reward_mode(context::current(), request.mode, request.address)
+                .await,
+        )
+    }
+
+    async fn set_payout_min(
+        &self,
+        request: Request<SetPayoutMinRequest>,
+    ) -> Result<Response<ActionReply>, Status> {
+        let server: GvCLIServer = self.server(&request);
+        let min: f64 = request.into_inner().min;
+
+        action_reply(server.set_payout_min(context::current(), min).await)
+    }
+
+    async fn set_reward_interval(
+        &self,
+        request: Request<SetRewardIntervalRequest>,
+    ) -> Result<Response<ActionReply>, Status> {
+        let server: GvCLIServer = self.server(&request);
+        let interval: String = request.into_inner().interval;
+
+        action_reply(
+            server
+                .set_reward_interval(context::current(), interval)
+                .await,
+        )
+    }
+
+    async fn get_payout_history(
+        &self,
+        request: Request<TimeRange>,
+    ) -> Result<Response<PayoutHistory>, Status> {
+        let server: GvCLIServer = self.server(&request);
+        let (start, end) = time_range(request.into_inner());
+
+        reply(
+            server
+                .get_payout_history(context::current(), start, end)
+                .await,
+        )
+    }
+
+    async fn export_stakes_csv(
+        &self,
+        request: Request<TimeRange>,
+    ) -> Result<Response<CsvExport>, Status> {
+        let server: GvCLIServer = self.server(&request);
+        let (start, end) = time_range(request.into_inner());
+
+        let csv: String = server
+            .get_stakes_by_address_csv(context::current(), start, end)
+            .await
+            .into_result()
+            .map_err(to_status)?;
+
+        Ok(Response::new(CsvExport { csv }))
+    }
+}
+
+// With CLI_TLS_CERT and CLI_TLS_KEY set the service uses the same certificate as the RPC server.
+pub async fn serve(
+    server: GvCLIServer,
+    address: String,
+    tls: Option<(PathBuf, PathBuf)>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let address: SocketAddr = address.parse()?;
+    let mut builder: Server = Server::builder();
+
+    if let Some((cert, key)) = tls {
+        let identity: Identity = Identity::from_pem(std::fs::read(cert)?, std::fs::read(key)?);
+        builder = builder.tls_config(ServerTlsConfig::new().identity(identity))?;
+        info!("gRPC listening with TLS on {}", address);
+    } else {
+        info!("gRPC listening on {}", address);
+    }
+
+    builder
+        .add_service(GhostVaultServer::new(GrpcService { server }))
+        .serve(address)
+        .await?;
+
+    Ok(())
+}
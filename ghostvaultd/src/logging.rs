@@ -0,0 +1,123 @@
+// log4rs setup. The handle is kept so set_log_level can swap in a new config while running,
+// levels changed that way last until GhostVault restarts.
+use log::LevelFilter;
+use log4rs::{
+    append::console::ConsoleAppender,
+    append::rolling_file::policy::compound::{
+        roll::fixed_window::FixedWindowRoller, trigger::size::SizeTrigger, CompoundPolicy,
+    },
+    append::rolling_file::RollingFileAppender,
+    config::{Appender, Config, Logger, Root},
+    encode::{json::JsonEncoder, pattern::PatternEncoder, Encode},
+    Handle,
+};
+use service::config::LogSettings;
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Mutex, OnceLock},
+};
+
+struct LogState {
+    handle: Handle,
+    log_file_path: PathBuf,
+    settings: LogSettings,
+}
+
+static LOG_STATE: OnceLock<Mutex<LogState>> = OnceLock::new();
+
+pub fn init(log_file_path: PathBuf, settings: LogSettings) {
+    let log_config: Config =
+        build_config(&log_file_path, &settings).expect("Failed to create log4rs configuration");
+    let handle: Handle = log4rs::init_config(log_config).expect("Failed to initialize log4rs");
+
+    let _ = LOG_STATE.set(Mutex::new(LogState {
+        handle,
+        log_file_path,
+        settings,
+    }));
+}
+
+// Without a module the default level changes. "default" as a module's level drops its
+// override so it follows the default again.
+pub fn set_level(level: &str, module: Option<&str>) -> Result<LogSettings, Box<dyn Error>> {
+    let level: String = level.to_lowercase();
+
+    if !(service::config::valid_log_level(&level) || (module.is_some() && level == "default")) {
+        return Err(format!("Invalid log level '{}'", level).into());
+    }
+
+    let mut state = LOG_STATE
+        .get()
+        .ok_or("Logging isn't set up")?
+        .lock()
+        .unwrap();
+    let mut settings: LogSettings = state.settings.clone();
+
+    match module {
+        Some(module) if level == "default" => {
+            settings.modules.remove(module);
+        }
+        Some(module) => {
+            settings.modules.insert(module.to_string(), level);
+        }
+        None => settings.level = level,
+    }
+
+    let log_config: Config = build_config(&state.log_file_path, &settings)?;
+    state.handle.set_config(log_config);
+    state.settings = settings.clone();
+
+    Ok(settings)
+}
+
+fn build_config(log_file_path: &Path, settings: &LogSettings) -> Result<Config, Box<dyn Error>> {
+    let roller: FixedWindowRoller = FixedWindowRoller::builder().build(
+        log_file_path.with_extension("{}.gz").to_str().unwrap(),
+        settings.file_count,
+    )?;
+
+    let policy: CompoundPolicy = CompoundPolicy::new(
+        Box::new(SizeTrigger::new(settings.file_size_mb * 1024 * 1024)),
+        Box::new(roller),
+    );
+
+    let file_appender: RollingFileAppender = RollingFileAppender::builder()
+        .encoder(encoder(settings.json))
+        .build(log_file_path, Box::new(policy))?;
+
+    let console_appender: ConsoleAppender = ConsoleAppender::builder()
+        .encoder(encoder(settings.json))
+        .build();
+
+    let loggers = settings
+        .modules
+        .iter()
+        .map(|(module, level)| Logger::builder().build(module, parse_level(level)));
+
+    let log_config: Config = Config::builder()
+        .appender(Appender::builder().build("file", Box::new(file_appender)))
+        .appender(Appender::builder().build("console", Box::new(console_appender)))
+        .loggers(loggers)
+        .build(
+            Root::builder()
+                .appender("file")
+                .appender("console")
+                .build(parse_level(&settings.level)),
+        )?;
+
+    Ok(log_config)
+}
+
+fn encoder(json: bool) -> Box<dyn Encode> {
+    if json {
+        Box::new(JsonEncoder::new())
+    } else {
+        Box::new(PatternEncoder::default())
+    }
+}
+
+fn parse_level(level: &str) -> LevelFilter {
+    LevelFilter::from_str(level).unwrap_or(LevelFilter::Info)
+}
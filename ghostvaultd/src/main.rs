@@ -3,34 +3,41 @@
 extern crate log;
 use clap::Parser;
 use daemonize::Daemonize;
-use log::LevelFilter;
-use log4rs::{
-    append::console::ConsoleAppender,
-    append::rolling_file::policy::compound::{
-        roll::fixed_window::FixedWindowRoller, trigger::size::SizeTrigger, CompoundPolicy,
-    },
-    append::rolling_file::RollingFileAppender,
-    config::{Appender, Config, Root},
-    encode::pattern::PatternEncoder,
-};
 use serde_json::Value;
 use service::{
-    config,
-    config::GVConfig,
-    constants::{DEFAULT_DAEMON_DIR, DEFAULT_GV_DIR, GV_PID_FILE},
+    backup, cli_tls, config,
+    config::{BootstrapSource, GVConfig, LogSettings},
+    config_check::{self, CheckStatus, ConfigReport},
+    constants::{
+        BUILD_DATE, CARGO_FEATURES, CONFIG_PASSPHRASE_ENV, DEFAULT_DAEMON_DIR, DEFAULT_DB_DIR,
+        DEFAULT_GV_DIR, DOWNLOAD_CACHE_MAX_AGE, GIT_COMMIT, GV_LOG_FILE, GV_PID_FILE,
+        GV_PROFILE_ENV, GV_SETTINGS_FILE, VERSION,
+    },
     daemon_helper::DaemonHelper,
     file_ops, gv_home_init, gv_methods,
     gv_methods::PathAndDigest,
-    gvdb::{ServerReadyDB, GVDB},
+    gvdb::{self, ServerReadyDB, GVDB},
     term_link::Link,
     tg_bot::tg_bot,
 };
-use std::{env, path::PathBuf, process::exit, sync::Arc};
+use std::{
+    env,
+    io::{IsTerminal, Write},
+    path::{Path, PathBuf},
+    process::{exit, Command},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 use systemstat::Duration;
 use tokio::runtime::Runtime;
 use tokio::sync::RwLock as async_RwLock;
 
 mod cli_server;
+#[cfg(feature = "grpc")]
+mod grpc_server;
+mod logging;
 
 #[derive(Parser, Debug)]
 struct Flags {
@@ -43,6 +50,18 @@ struct Flags {
     /// Run GhostVault in the console without daemonizing.
     #[clap(short, long)]
     console: bool,
+    /// Set, change or remove the passphrase that encrypts secrets in the config, then exit.
+    #[clap(long)]
+    set_passphrase: bool,
+    /// Log level for this run, overrides LOG_LEVEL in the config.
+    #[clap(long)]
+    log_level: Option<String>,
+    /// Use gv_settings.<PROFILE>.toml instead of gv_settings.toml.
+    #[clap(long)]
+    profile: Option<String>,
+    /// Validate the config and ghost.conf, print a report and exit without starting anything.
+    #[clap(long)]
+    check_config: bool,
 }
 
 fn main() {
@@ -61,6 +80,22 @@ fn main() {
         .map(|dir| file_ops::expand_user(&dir))
         .unwrap_or_else(|| file_ops::expand_user(DEFAULT_GV_DIR));
 
+    if let Some(profile) = &flags.profile {
+        if !config::valid_profile_name(profile) {
+            eprintln!(
+                "Invalid --profile '{}', use letters, numbers, '-' and '_'.",
+                profile
+            );
+            exit(1);
+        }
+        env::set_var(GV_PROFILE_ENV, profile);
+    }
+
+    if flags.check_config && !gv_data_dir.exists() {
+        eprintln!("GV data dir {} not found.", gv_data_dir.display());
+        exit(1);
+    }
+
     let first_run: bool = if !gv_data_dir.exists() {
         info!("GV Data dir not found, creating...");
         gv_home_init::init_data_dir(&gv_data_dir).unwrap();
@@ -69,38 +104,43 @@ fn main() {
         false
     };
 
-    let log_file_path: PathBuf = gv_data_dir.join("logs/ghostvault.log");
-
-    let roller: FixedWindowRoller = FixedWindowRoller::builder()
-        .build(&log_file_path.with_extension("{}.gz").to_str().unwrap(), 3)
-        .expect("Failed to build roller");
+    // A profile is never created from defaults, it's meant to be a copy of a working config.
+    let settings_path: PathBuf = config::settings_path(&gv_data_dir);
+    if flags.profile.is_some() && !settings_path.exists() {
+        eprintln!(
+            "{} not found, copy {} to create the profile.",
+            settings_path.display(),
+            GV_SETTINGS_FILE
+        );
+        exit(1);
+    }
 
-    let policy: CompoundPolicy = CompoundPolicy::new(
-        Box::new(SizeTrigger::new(1024 * 1024 * 10)), // 10 MB
-        Box::new(roller),
-    );
+    let log_file_path: PathBuf = gv_data_dir.join(GV_LOG_FILE);
+    let mut log_settings: LogSettings = config::read_log_settings(&gv_data_dir);
 
-    let file_appender: RollingFileAppender = RollingFileAppender::builder()
-        .encoder(Box::new(PatternEncoder::default()))
-        .build(log_file_path, Box::new(policy))
-        .expect("Failed to create file appender");
+    if let Some(log_level) = flags.log_level {
+        if !config::valid_log_level(&log_level) {
+            eprintln!(
+                "Invalid --log-level '{}', use off, error, warn, info, debug or trace.",
+                log_level
+            );
+            exit(1);
+        }
+        log_settings.level = log_level.to_lowercase();
+    }
 
-    let console_appender: ConsoleAppender = ConsoleAppender::builder()
-        .encoder(Box::new(PatternEncoder::default()))
-        .build();
+    logging::init(log_file_path, log_settings);
 
-    let log_config: Config = Config::builder()
-        .appender(Appender::builder().build("file", Box::new(file_appender)))
-        .appender(Appender::builder().build("console", Box::new(console_appender)))
-        .build(
-            Root::builder()
-                .appender("file")
-                .appender("console")
-                .build(LevelFilter::Info),
-        )
-        .expect("Failed to create log4rs configuration");
+    let cargo_features: &str = if CARGO_FEATURES.is_empty() {
+        "none"
+    } else {
+        CARGO_FEATURES
+    };
 
-    log4rs::init_config(log_config).expect("Failed to initialize log4rs");
+    info!(
+        "GhostVault v{} ({}) built {} [features: {}]",
+        VERSION, GIT_COMMIT, BUILD_DATE, cargo_features
+    );
 
     let do_daemon: bool = flags.console.clone() == false;
     let is_windows: bool = cfg!(target_os = "windows");
@@ -111,7 +151,7 @@ fn main() {
     let pid_file: PathBuf = gv_data_dir.join(GV_PID_FILE);
 
     let pid_from_file: u32 = file_ops::get_pid(&gv_data_dir, GV_PID_FILE);
-    if file_ops::pid_exists(pid_from_file) && !is_docker {
+    if file_ops::pid_exists(pid_from_file) && !is_docker && !flags.check_config {
         let running_msg: String = format!(
             "Detected running GhostVault instance at PID: {}",
             pid_from_file
@@ -122,6 +162,60 @@ fn main() {
 
         exit(0);
     }
+
+    if flags.set_passphrase {
+        set_config_passphrase(&gv_data_dir);
+        exit(0);
+    }
+
+    // --check-config looks at the config as it is, a staged restore waits for a real start.
+    if !flags.check_config {
+        match backup::apply_pending_restore(&gv_data_dir) {
+            Ok(true) => info!("GhostVault restored from backup."),
+            Ok(false) => (),
+            Err(err) => {
+                error!("Failed to restore from backup: {}", err);
+                exit(1);
+            }
+        }
+    }
+
+    // Must happen before daemonizing, the passphrase may need to be typed in.
+    if config::config_encrypted(&gv_data_dir) {
+        let passphrase: String = match config::stored_config_passphrase()
+            .or_else(|| prompt_passphrase("Config passphrase: "))
+        {
+            Some(passphrase) => passphrase,
+            None => {
+                error!(
+                    "Config is encrypted, set {} or run in a terminal to enter the passphrase.",
+                    CONFIG_PASSPHRASE_ENV
+                );
+                exit(1);
+            }
+        };
+
+        if let Err(err) = config::unlock_config(&gv_data_dir, &daemon_data_dir, &passphrase) {
+            error!("Unable to unlock config: {}", err);
+            exit(1);
+        }
+
+        info!("Config unlocked.");
+    }
+
+    if flags.check_config {
+        let rt: Runtime = Runtime::new().unwrap();
+        let report: ConfigReport =
+            rt.block_on(config_check::check_config(&gv_data_dir, &daemon_data_dir));
+
+        print_config_report(&report);
+        exit(if report.count(CheckStatus::Error) > 0 {
+            1
+        } else {
+            0
+        });
+    }
+
     file_ops::make_pid_file(&gv_data_dir, GV_PID_FILE).unwrap();
 
     env::set_var("GV_HOME", gv_data_dir.to_str().unwrap());
@@ -155,6 +249,109 @@ fn main() {
     }
 }
 
+fn print_config_report(report: &ConfigReport) {
+    println!("Settings: {}", report.settings_file.display());
+    println!("ghost.conf: {}\n", report.daemon_config.display());
+
+    let key_width: usize = report
+        .checks
+        .iter()
+        .map(|check| check.key.len())
+        .max()
+        .unwrap_or(0);
+
+    for check in &report.checks {
+        let status: &str = match check.status {
+            CheckStatus::Ok => "OK",
+            CheckStatus::Warning => "WARN",
+            CheckStatus::Error => "ERROR",
+        };
+
+        println!(
+            "{:<5}  {:<width$}  {}",
+            status,
+            check.key,
+            check.message,
+            width = key_width
+        );
+    }
+
+    println!(
+        "\n{} errors, {} warnings",
+        report.count(CheckStatus::Error),
+        report.count(CheckStatus::Warning)
+    );
+}
+
+fn prompt_passphrase(prompt: &str) -> Option<String> {
+    if !std::io::stdin().is_terminal() {
+        return None;
+    }
+
+    print!("{}", prompt);
+    std::io::stdout().flush().ok()?;
+
+    let _ = Command::new("stty").arg("-echo").status();
+    let mut passphrase: String = String::new();
+    let read_res = std::io::stdin().read_line(&mut passphrase);
+    let _ = Command::new("stty").arg("echo").status();
+    println!();
+
+    read_res.ok()?;
+
+    Some(passphrase.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn set_config_passphrase(gv_home: &Path) {
+    let old_passphrase: Option<String> = if config::config_encrypted(gv_home) {
+        match prompt_passphrase("Current config passphrase: ") {
+            Some(passphrase) => Some(passphrase),
+            None => {
+                error!("Unable to read the current passphrase.");
+                exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    let new_passphrase: String =
+        match prompt_passphrase("New config passphrase (leave empty to remove): ") {
+            Some(passphrase) => passphrase,
+            None => {
+                error!("Unable to read the new passphrase.");
+                exit(1);
+            }
+        };
+
+    if prompt_passphrase("Confirm new config passphrase: ").as_deref() != Some(&new_passphrase) {
+        error!("Passphrases do not match.");
+        exit(1);
+    }
+
+    if let Err(err) =
+        config::set_config_passphrase(gv_home, old_passphrase.as_deref(), &new_passphrase)
+    {
+        error!("Unable to set config passphrase: {}", err);
+        exit(1);
+    }
+
+    #[cfg(feature = "keyring")]
+    if let Err(err) = config::store_keyring_passphrase(&new_passphrase) {
+        warn!("Unable to store the passphrase in the OS keyring: {}", err);
+    }
+
+    if new_passphrase.is_empty() {
+        info!("Config passphrase removed, secrets are stored in plaintext.");
+    } else {
+        info!("Config passphrase set, secrets are encrypted at rest.");
+        info!(
+            "Set {} or enter the passphrase when starting GhostVault.",
+            CONFIG_PASSPHRASE_ENV
+        );
+    }
+}
+
 async fn run_init(gv_home: &PathBuf, daemon_data_dir: &PathBuf, first_run: bool) {
     let config: Arc<async_RwLock<GVConfig>> = startup(&gv_home, &daemon_data_dir, first_run)
         .await
@@ -185,9 +382,17 @@ async fn run_init(gv_home: &PathBuf, daemon_data_dir: &PathBuf, first_run: bool)
 
     drop(conf);
 
-    let db: Arc<GVDB> = Arc::new(GVDB::new(&gv_home).await);
+    let conf = config.read().await;
+    let db_path: PathBuf = conf.db_path.clone();
+    drop(conf);
+
+    let db: Arc<GVDB> = Arc::new(GVDB::new(&db_path).await);
     let bot_db = Arc::clone(&db);
 
+    if let Err(err) = db.backfill_stake_rollups().await {
+        error!("Failed to build stake rollups: {}", err);
+    }
+
     let ready: ServerReadyDB = ServerReadyDB {
         ready: false,
         daemon_ready: false,
@@ -238,6 +443,7 @@ async fn startup(
     first_run: bool,
 ) -> std::io::Result<Arc<async_RwLock<GVConfig>>> {
     let config_data: config::GVConfig = GVConfig::new(&gv_home, &daemon_data_dir).unwrap();
+    cli_tls::trust_vaults(&config_data);
 
     let config: Arc<async_RwLock<GVConfig>> = Arc::new(async_RwLock::new(config_data));
 
@@ -252,13 +458,60 @@ async fn startup(
     })
     .expect("Error setting Ctrl-C handler");
 
+    // Partial downloads are kept for a while so they can be resumed, anything older is stale.
+    match gv_methods::clean_download_cache(Duration::from_secs(DOWNLOAD_CACHE_MAX_AGE)) {
+        Ok(removed) if removed > 0 => info!("Removed {} stale download artifacts", removed),
+        Ok(_) => (),
+        Err(err) => warn!("Unable to clean download cache: {}", err),
+    }
+
     let mut conf_lock = config.write().await;
 
+    if conf_lock.system_daemon && conf_lock.daemon_path.exists() {
+        match gv_methods::check_system_daemon(&conf_lock.daemon_path, &conf_lock.update_channel)
+            .await
+        {
+            Ok(version) => info!(
+                "Using system ghostd v{} at {}",
+                version,
+                conf_lock.daemon_path.display()
+            ),
+            Err(err) => error!("Unable to verify system ghostd: {}", err),
+        }
+    }
+
+    if !conf_lock.daemon_path.exists() {
+        if let Some(system_path) = gv_methods::find_system_daemon() {
+            match gv_methods::check_system_daemon(&system_path, &conf_lock.update_channel).await {
+                Ok(version) => {
+                    info!(
+                        "Found system ghostd v{} at {}",
+                        version,
+                        system_path.display()
+                    );
+
+                    conf_lock
+                        .update_gv_config("daemon_path", system_path.to_str().unwrap())
+                        .unwrap();
+                    conf_lock.update_gv_config("daemon_hash", "").unwrap();
+                    conf_lock.update_gv_config("system_daemon", "true").unwrap();
+                }
+                Err(err) => {
+                    warn!(
+                        "Ignoring system ghostd at {}: {}",
+                        system_path.display(),
+                        err
+                    );
+                }
+            }
+        }
+    }
+
     if !conf_lock.daemon_path.exists() {
         info!("Ghost daemon not found, fetching...");
 
         let dl_path: PathBuf = loop {
-            let dl_path_res = gv_methods::download_daemon().await;
+            let dl_path_res = gv_methods::download_daemon(&conf_lock.daemon_source()).await;
             let dl_path: PathBuf = if let Err(ref err) = dl_path_res {
                 error!("Error downloading daemon: {}", err);
                 error!("Retrying in 30 seconds...");
@@ -273,6 +526,10 @@ async fn startup(
 
         let path_and_hash: PathAndDigest = gv_methods::extract_archive(&dl_path, &gv_home).unwrap();
 
+        if let Err(err) = gv_methods::clean_download_cache(Duration::ZERO) {
+            warn!("Unable to clean download cache: {}", err);
+        }
+
         conf_lock
             .update_gv_config("daemon_path", path_and_hash.daemon_path.to_str().unwrap())
             .unwrap();
@@ -280,6 +537,10 @@ async fn startup(
         conf_lock
             .update_gv_config("daemon_hash", path_and_hash.daemon_hash.as_str())
             .unwrap();
+
+        conf_lock
+            .update_gv_config("system_daemon", "false")
+            .unwrap();
     }
     drop(conf_lock);
 
@@ -299,9 +560,34 @@ async fn startup(
         tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
     }
 
+    // A node without a chain yet starts from the bootstrap instead of syncing from genesis
+    let conf_lock = config.read().await;
+    let bootstrap_source: Option<BootstrapSource> = conf_lock.bootstrap_source();
+    let daemon_data_dir: PathBuf = conf_lock.daemon_data_dir.clone();
+    drop(conf_lock);
+
+    if let Some(source) = bootstrap_source {
+        if first_run || !daemon_data_dir.join("chainstate").exists() {
+            bootstrap_first_start(&daemon, &source, &daemon_data_dir).await;
+        }
+    }
+
     daemon.wait_for_daemon_startup().await;
 
-    let db: GVDB = GVDB::new(&gv_home).await;
+    let conf_lock = config.read().await;
+    let db_path: PathBuf = conf_lock.db_path.clone();
+    drop(conf_lock);
+
+    // Move the database out of gv_home if DB_PATH points somewhere else
+    let default_db_path: PathBuf = gv_home.join(DEFAULT_DB_DIR);
+    if db_path != default_db_path && default_db_path.exists() {
+        if let Err(err) = gvdb::migrate_db(&default_db_path, &db_path).await {
+            error!("Failed to migrate database: {}", err);
+            exit(1);
+        }
+    }
+
+    let db: GVDB = GVDB::new(&db_path).await;
     let check_wallets: Result<Value, Box<dyn std::error::Error + Send + Sync>> =
         daemon.check_wallets(&db).await;
     tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
@@ -313,6 +599,63 @@ async fn startup(
     Ok(config)
 }
 
+// Nothing is lost if this fails, ghostd just syncs from the network like it would without one.
+async fn bootstrap_first_start(
+    daemon: &DaemonHelper,
+    source: &BootstrapSource,
+    daemon_data_dir: &PathBuf,
+) {
+    info!(
+        "Downloading the blockchain bootstrap from {}...",
+        source.url
+    );
+
+    let last_logged: AtomicU64 = AtomicU64::new(0);
+    let download_res = gv_methods::download_bootstrap(source, |done, size| {
+        if size == 0 {
+            return;
+        }
+
+        let percent: u64 = done * 100 / size / 10 * 10;
+
+        if percent > last_logged.load(Ordering::Relaxed) {
+            last_logged.store(percent, Ordering::Relaxed);
+            info!("Blockchain bootstrap download at {}%", percent);
+        }
+    })
+    .await;
+
+    let archive_path: PathBuf = match download_res {
+        Ok(archive_path) => archive_path,
+        Err(err) => {
+            error!("Blockchain bootstrap download failed: {}", err);
+            return;
+        }
+    };
+
+    let _ = daemon.stop_daemon().await;
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    let install_archive: PathBuf = archive_path.clone();
+    let install_dir: PathBuf = daemon_data_dir.clone();
+    let install_res = tokio::task::spawn_blocking(move || {
+        gv_methods::install_bootstrap(&install_archive, &install_dir)
+    })
+    .await;
+
+    match install_res {
+        Ok(Ok(_)) => info!("Blockchain bootstrap installed!"),
+        Ok(Err(err)) => {
+            error!("Blockchain bootstrap install failed: {}", err);
+            let _ = file_ops::rm_dir(&daemon_data_dir.join("blocks"));
+            let _ = file_ops::rm_dir(&daemon_data_dir.join("chainstate"));
+        }
+        Err(err) => error!("Blockchain bootstrap install failed: {}", err),
+    }
+
+    let _ = file_ops::rm_file(&archive_path);
+}
+
 async fn start_rpc_server(gv_config: &Arc<async_RwLock<GVConfig>>, db: &Arc<GVDB>) {
     info!("Starting CLI server...");
 
@@ -4,18 +4,29 @@
 // license that can be found in the LICENSE file or at
 // https://opensource.org/licenses/MIT.
 
+mod completions;
+mod dashboard;
+
 use serde::ser::StdError;
+use serde_json::Value;
 use service::{
-    config,
+    api_schema, cli_tls, config,
     config::GVConfig,
-    constants::{DEFAULT_DAEMON_DIR, DEFAULT_GV_DIR, GV_PID_FILE, VERSION},
+    constants::{
+        BUILD_DATE, DASHBOARD_REFRESH, DEFAULT_DAEMON_DIR, DEFAULT_GV_DIR, FLEET_FILE, GIT_COMMIT,
+        GV_LOG_FILE, GV_PID_FILE, GV_PROFILE_ENV, VERSION,
+    },
     file_ops,
-    gv_client_methods::{CLICaller, GVStatus, StakingDataOverview},
+    gv_client_methods::{CLICaller, FleetConfig},
+    response::{self, ErrorCode, GvReply, GvResponse},
 };
 use std::{
     env::{self},
+    io::Write,
     path::PathBuf,
+    process::Command,
     sync::Arc,
+    time::Duration,
 };
 
 use std::process::exit;
@@ -24,6 +35,8 @@ use std::process::exit;
 struct Flags {
     gv_data_dir: Option<String>,
     daemon_data_dir: Option<String>,
+    fleet_file: Option<String>,
+    profile: Option<String>,
 }
 
 #[tokio::main]
@@ -32,11 +45,14 @@ async fn main() {
     let mut flags: Flags = Flags {
         gv_data_dir: None,
         daemon_data_dir: None,
+        fleet_file: None,
+        profile: None,
     };
 
     let mut rpc_method: &str = "";
     let mut rpc_method_args: Vec<String> = Vec::new();
     let mut is_json: bool = false;
+    let mut is_quiet: bool = false;
     let mut multi_param: Vec<String> = Vec::new();
 
     for arg in &args[1..] {
@@ -50,9 +66,18 @@ async fn main() {
                 Some(&"daemon-data-dir") => {
                     flags.daemon_data_dir = Some(split.get(1).unwrap_or(&"").to_string());
                 }
+                Some(&"fleet") => {
+                    flags.fleet_file = Some(split.get(1).unwrap_or(&"").to_string());
+                }
+                Some(&"profile") => {
+                    flags.profile = Some(split.get(1).unwrap_or(&"").to_string());
+                }
                 Some(&"json") => {
                     is_json = true;
                 }
+                Some(&"quiet") => {
+                    is_quiet = true;
+                }
                 Some(&"help") => {
                     display_help();
                     return;
@@ -97,9 +122,21 @@ async fn main() {
             display_version();
             return;
         }
+        "completions" => {
+            let shell: &str = rpc_method_args.first().map_or("", |shell| shell.as_str());
+
+            if let Err(err) = completions::print_completions(shell) {
+                usage_error(&err, is_json);
+            }
+            return;
+        }
         _ => {}
     }
 
+    // --json prints each response envelope, --quiet prints nothing and scripts go by the exit
+    // status, everything else is for people.
+    let human_out: bool = !is_json && !is_quiet;
+
     let gv_data_dir: PathBuf = flags
         .gv_data_dir
         .map(|dir| file_ops::expand_user(&dir))
@@ -110,6 +147,31 @@ async fn main() {
         .map(|dir| file_ops::expand_user(&dir))
         .unwrap_or_else(|| file_ops::expand_user(DEFAULT_DAEMON_DIR));
 
+    // Fleet commands talk to remote vaults, so they don't need a local GhostVault.
+    if let Some(fleet_method) = rpc_method.strip_prefix("fleet") {
+        let fleet_path: PathBuf = flags
+            .fleet_file
+            .map(|file| file_ops::expand_user(&file))
+            .unwrap_or_else(|| gv_data_dir.join(FLEET_FILE));
+
+        let fleet_res = match FleetConfig::load(&fleet_path) {
+            Ok(fleet) => CLICaller::call_fleet(&fleet, fleet_method, !human_out).await,
+            Err(err) => Err(err),
+        };
+
+        match fleet_res {
+            Ok(fleet) if is_json => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&response::ok(fleet)).unwrap()
+                );
+            }
+            Ok(_) => {}
+            Err(err) => handle_command_error(err, is_json),
+        }
+        return;
+    }
+
     if !gv_data_dir.exists() {
         let msg = "GV Data dir not found, exiting";
         println!("{}", msg);
@@ -122,10 +184,43 @@ async fn main() {
         exit(1);
     }
 
+    // The profile's CLI_ADDRESS is the one to connect to.
+    if let Some(profile) = &flags.profile {
+        if !config::valid_profile_name(profile) {
+            usage_error(
+                &format!(
+                    "Invalid --profile '{}', use letters, numbers, '-' and '_'.",
+                    profile
+                ),
+                is_json,
+            );
+        }
+        env::set_var(GV_PROFILE_ENV, profile);
+
+        if !config::settings_path(&gv_data_dir).exists() {
+            println!("Profile '{}' not found, exiting", profile);
+            exit(1);
+        }
+    }
+
     let config: Arc<config::GVConfig> =
         Arc::new(GVConfig::new(&gv_data_dir, &daemon_data_dir).unwrap());
 
-    let gv_client_res = CLICaller::new(&config.cli_address, is_json).await;
+    // Writes files and settings only, GhostVault doesn't have to be running.
+    if rpc_method == "gentlscert" {
+        generate_tls_cert(&config, &rpc_method_args, human_out, is_json);
+        return;
+    }
+
+    cli_tls::trust_vaults(&config);
+
+    let gv_client_res = CLICaller::new(&config.cli_address, !human_out)
+        .await
+        .map(|gv_client| gv_client.with_envelope_out(is_json));
+
+    if gv_client_res.is_err() && !human_out {
+        handle_command_error(gv_client_res.err().unwrap(), is_json);
+    }
 
     if gv_client_res.is_err() {
         let pid_from_file: u32 = file_ops::get_pid(&gv_data_dir, GV_PID_FILE);
@@ -142,35 +237,53 @@ async fn main() {
         exit(1);
     }
 
-    let gv_client = gv_client_res.unwrap();
+    let gv_client = gv_client_res.unwrap().negotiate().await;
+
+    // On stderr so --json output stays parseable.
+    if let Some(mismatch) = gv_client.api_mismatch() {
+        if !is_quiet {
+            eprintln!("Warning: {}", mismatch);
+        }
+    }
+
+    if let Some(method) = api_schema::method_for_command(rpc_method) {
+        if let Err(err) = gv_client.require(method) {
+            if is_json {
+                let envelope: GvReply<()> = response::error(err.code, &err.message);
+                println!("{}", serde_json::to_string_pretty(&envelope).unwrap());
+            }
+            handle_command_error(Box::new(err), is_json);
+        }
+    }
 
     match rpc_method {
         "getdaemonstate" | "status" => {
-            let daemon_state = gv_client.call_get_daemon_state().await;
-
-            if let Ok(daemon_state) = daemon_state {
-                if is_json {
-                    let status: GVStatus = serde_json::from_value(daemon_state.clone()).unwrap();
-                    println!("{}", serde_json::to_string_pretty(&status).unwrap());
+            // A timezone shows the times in it rather than the vault's TIMEZONE
+            let daemon_state = match rpc_method_args.first() {
+                Some(timezone) => {
+                    gv_client
+                        .call_get_daemon_state_tz(timezone.to_string())
+                        .await
                 }
-            } else if let Err(err) = daemon_state {
-                handle_command_error(err);
+                None => gv_client.call_get_daemon_state().await,
+            };
+
+            if let Err(err) = daemon_state {
+                handle_command_error(err, is_json);
             }
         }
         "setrewardmode" => {
             let len_args = rpc_method_args.len();
 
             if len_args < 1 {
-                println!("Method 'setrewardmode' missing required mode.");
-                return;
+                usage_error("Method 'setrewardmode' missing required mode.", is_json);
             }
 
             let mode = rpc_method_args[0].to_uppercase();
 
             let addr = if mode != "DEFAULT" {
                 if len_args < 2 {
-                    println!("Method 'setrewardmode' missing required address.");
-                    return;
+                    usage_error("Method 'setrewardmode' missing required address.", is_json);
                 }
                 Some(rpc_method_args[1].to_string())
             } else {
@@ -179,57 +292,44 @@ async fn main() {
 
             let reward_mode_res = gv_client.call_set_reward_mode(mode, addr).await;
 
-            if let Ok(reward_mode) = reward_mode_res {
-                if is_json {
-                    println!("{}", reward_mode.as_str().unwrap());
-                }
-            } else if let Err(err) = reward_mode_res {
-                handle_command_error(err);
+            if let Err(err) = reward_mode_res {
+                handle_command_error(err, is_json);
             }
         }
         "setminpayout" => {
             if rpc_method_args.len() < 1 {
-                println!("Method 'setminpayout' missing required amount.");
-                return;
+                usage_error("Method 'setminpayout' missing required amount.", is_json);
             }
 
             let min_payout: f64 = rpc_method_args[0].parse::<f64>().unwrap();
 
             let min_payout_res = gv_client.call_set_payout_min(min_payout).await;
 
-            if let Ok(min_payout) = min_payout_res {
-                if is_json {
-                    println!("{}", min_payout.as_str().unwrap());
-                }
-            } else if let Err(err) = min_payout_res {
-                handle_command_error(err);
+            if let Err(err) = min_payout_res {
+                handle_command_error(err, is_json);
             }
         }
         "setrewardtime" => {
             if rpc_method_args.len() < 1 {
-                println!("Method 'setpayoutime' missing required interval.");
-                return;
+                usage_error("Method 'setpayoutime' missing required interval.", is_json);
             }
 
             let interval = rpc_method_args[0].to_string();
 
             let interval_res = gv_client.call_set_reward_interval(interval).await;
 
-            if let Ok(interval) = interval_res {
-                if is_json {
-                    println!("{}", interval.as_str().unwrap());
-                }
-            } else if let Err(err) = interval_res {
-                handle_command_error(err);
+            if let Err(err) = interval_res {
+                handle_command_error(err, is_json);
             }
         }
         "enablebot" => {
             if rpc_method_args.len() < 1 {
-                println!("Method 'enabletelegrambot' missing required token.");
-                return;
+                usage_error(
+                    "Method 'enabletelegrambot' missing required token.",
+                    is_json,
+                );
             } else if rpc_method_args.len() < 2 {
-                println!("Method 'enabletelegrambot' missing required user.");
-                return;
+                usage_error("Method 'enabletelegrambot' missing required user.", is_json);
             }
 
             let token: String = rpc_method_args[0].to_string();
@@ -237,40 +337,29 @@ async fn main() {
 
             let enable_bot_res = gv_client.call_enable_telegram_bot(token, user).await;
 
-            if let Ok(enable_bot) = enable_bot_res {
-                if is_json {
-                    println!("{}", enable_bot.as_str().unwrap());
-                } else {
-                    println!(
-                        "Telegram bot enabled. Restart GhostVault for changes to take effect."
-                    );
-                }
-            } else if let Err(err) = enable_bot_res {
-                handle_command_error(err);
+            if let Err(err) = enable_bot_res {
+                handle_command_error(err, is_json);
+            } else if human_out {
+                println!("Telegram bot enabled. Restart GhostVault for changes to take effect.");
             }
         }
         "disablebot" => {
             let disable_bot_res = gv_client.call_disable_telegram_bot().await;
 
-            if let Ok(disable_bot) = disable_bot_res {
-                if is_json {
-                    println!("{}", disable_bot.as_str().unwrap());
-                } else {
-                    println!(
-                        "Telegram bot disabled. Restart GhostVault for changes to take effect."
-                    );
-                }
-            } else if let Err(err) = disable_bot_res {
-                handle_command_error(err);
+            if let Err(err) = disable_bot_res {
+                handle_command_error(err, is_json);
+            } else if human_out {
+                println!("Telegram bot disabled. Restart GhostVault for changes to take effect.");
             }
         }
         "setbotannounce" => {
             if rpc_method_args.len() < 1 {
-                println!("Method 'setbotannounce' missing required message type.");
-                return;
+                usage_error(
+                    "Method 'setbotannounce' missing required message type.",
+                    is_json,
+                );
             } else if rpc_method_args.len() < 2 {
-                println!("Method 'setbotannounce' missing required value.");
-                return;
+                usage_error("Method 'setbotannounce' missing required value.", is_json);
             }
 
             let msg_type: String = rpc_method_args[0].to_string();
@@ -278,180 +367,1821 @@ async fn main() {
             let new_val = match new_val_opt {
                 Ok(val) => val,
                 Err(_) => {
-                    println!("Method 'setbotannounce' value must be a boolean.");
-                    return;
+                    usage_error("Method 'setbotannounce' value must be a boolean.", is_json);
                 }
             };
 
             let set_bot_announce_res = gv_client.call_set_bot_announce(msg_type, new_val).await;
 
-            if let Ok(set_bot_announce) = set_bot_announce_res {
-                if is_json {
-                    println!("{}", set_bot_announce.as_str().unwrap());
+            if let Err(err) = set_bot_announce_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "digest" => {
+            let digest_res = gv_client.call_get_digest_settings().await;
+
+            if let Err(err) = digest_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "setdigest" => {
+            if rpc_method_args.len() < 2 {
+                usage_error(
+                    "Method 'setdigest' requires daily or weekly and a value.",
+                    is_json,
+                );
+            }
+
+            let period: String = rpc_method_args[0].to_string();
+            let enabled: bool = match rpc_method_args[1].parse::<bool>() {
+                Ok(val) => val,
+                Err(_) => {
+                    usage_error("Method 'setdigest' value must be a boolean.", is_json);
+                }
+            };
+
+            let digest_res = gv_client.call_set_digest(period, enabled).await;
+
+            if let Err(err) = digest_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "setdigesttime" => {
+            let hour: u32 = match rpc_method_args.first().map(|arg| arg.parse::<u32>()) {
+                Some(Ok(hour)) if hour < 24 => hour,
+                _ => {
+                    usage_error(
+                        "Method 'setdigesttime' requires an hour from 0 to 23.",
+                        is_json,
+                    );
                 }
-            } else if let Err(err) = set_bot_announce_res {
-                handle_command_error(err);
+            };
+
+            let digest_res = gv_client.call_set_digest_time(hour).await;
+
+            if let Err(err) = digest_res {
+                handle_command_error(err, is_json);
             }
         }
         "extpubkey" => {
             let ext_pub_key_res = gv_client.call_get_ext_pub_key().await;
 
-            if let Ok(ext_pub_key) = ext_pub_key_res {
-                if is_json {
-                    println!("{}", ext_pub_key.as_str().unwrap());
-                }
-            } else if let Err(err) = ext_pub_key_res {
-                handle_command_error(err);
+            if let Err(err) = ext_pub_key_res {
+                handle_command_error(err, is_json);
             }
         }
         "shutdown" => {
             let shutdown_res = gv_client.call_shutdown().await;
 
-            if let Ok(shutdown) = shutdown_res {
-                if is_json {
-                    println!("{}", shutdown.as_str().unwrap());
-                }
-            } else if let Err(err) = shutdown_res {
-                handle_command_error(err);
+            if let Err(err) = shutdown_res {
+                handle_command_error(err, is_json);
             }
         }
         "forceresync" => {
-            let force_resync_res = gv_client.call_force_resync().await;
+            let force: bool = rpc_method_args
+                .first()
+                .is_some_and(|arg| arg.to_lowercase() == "now");
 
-            if let Ok(force_resync) = force_resync_res {
-                if is_json {
-                    println!("{}", force_resync);
-                }
-            } else if let Err(err) = force_resync_res {
-                handle_command_error(err);
+            let force_resync_res = gv_client.call_force_resync(force).await;
+
+            if let Err(err) = force_resync_res {
+                handle_command_error(err, is_json);
             }
         }
         "getoverview" | "stats" => {
             let overview_res = gv_client.call_get_overview().await;
 
-            if let Ok(overview) = overview_res {
-                if is_json {
-                    let staking_data: StakingDataOverview =
-                        serde_json::from_value(overview.clone()).unwrap();
-                    println!("{}", serde_json::to_string_pretty(&staking_data).unwrap());
-                }
-            } else if let Err(err) = overview_res {
-                handle_command_error(err);
+            if let Err(err) = overview_res {
+                handle_command_error(err, is_json);
             }
         }
         "getmnemonic" => {
-            let mnemonic_res = gv_client.call_get_mnemonic().await;
-
-            if let Ok(mnemonic) = mnemonic_res {
-                if is_json {
-                    if mnemonic.is_string() {
-                        println!("{}", mnemonic.as_str().unwrap());
-                    } else {
-                        println!("{}", mnemonic);
-                    }
-                }
-            } else if let Err(err) = mnemonic_res {
-                handle_command_error(err);
+            let passphrase: String = rpc_method_args
+                .first()
+                .map(|passphrase| passphrase.to_string())
+                .unwrap_or_default();
+
+            let mnemonic_res = gv_client.call_get_mnemonic(passphrase).await;
+
+            if let Err(err) = mnemonic_res {
+                handle_command_error(err, is_json);
             }
         }
         "settimezone" => {
             if rpc_method_args.len() < 1 {
-                println!("Method 'settimezone' missing required timezone.");
-                return;
+                usage_error("Method 'settimezone' missing required timezone.", is_json);
             }
 
             let timezone: String = rpc_method_args[0].to_string();
 
             let set_timezone_res = gv_client.call_set_timezone(timezone).await;
 
-            if let Ok(set_timezone) = set_timezone_res {
-                if is_json {
-                    println!("{}", set_timezone.as_str().unwrap());
-                }
-            } else if let Err(err) = set_timezone_res {
-                handle_command_error(err);
+            if let Err(err) = set_timezone_res {
+                handle_command_error(err, is_json);
             }
         }
         "importwallet" => {
             if rpc_method_args.len() < 1 {
-                println!("Method 'importwallet' missing required mnemonic.");
-                return;
+                usage_error("Method 'importwallet' missing required mnemonic.", is_json);
             }
             if rpc_method_args.len() < 2 {
-                println!("Method 'importwallet' missing required wallet name.");
-                return;
+                usage_error(
+                    "Method 'importwallet' missing required wallet name.",
+                    is_json,
+                );
             }
 
             let mnemonic: String = rpc_method_args[0].to_string();
             let wallet_name: String = rpc_method_args[1].to_string();
+            let birthday: Option<String> = rpc_method_args.get(2).map(|arg| arg.to_string());
+
+            let import_wallet_res = gv_client
+                .call_import_wallet(mnemonic, wallet_name, birthday)
+                .await;
+
+            if let Err(err) = import_wallet_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "payouthistory" => {
+            let (start, end) = match parse_time_range(rpc_method, &rpc_method_args, is_json) {
+                Some(range) => range,
+                None => return,
+            };
+
+            let payout_history_res = gv_client.call_get_payout_history(start, end).await;
+
+            if let Err(err) = payout_history_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "rescanwallet" => {
+            let start_height: u32 = match rpc_method_args.first() {
+                Some(start_height) => match start_height.parse::<u32>() {
+                    Ok(start_height) => start_height,
+                    Err(_) => {
+                        usage_error(
+                            "Method 'rescanwallet' start height must be a block number.",
+                            is_json,
+                        );
+                    }
+                },
+                None => 0,
+            };
+
+            let rescan_res = gv_client.call_rescan_wallet(start_height).await;
+
+            if let Err(err) = rescan_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "repairwallet" => {
+            let repair_res = gv_client.call_repair_wallet().await;
+
+            if let Err(err) = repair_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "reindexhistory" => {
+            let reindex_res = gv_client.call_reindex_history().await;
+
+            if let Err(err) = reindex_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "rescanstatus" => {
+            let rescan_status_res = gv_client.call_get_rescan_status().await;
 
-            if !is_json {
-                println!("Importing wallet\nThis may take a long time, please be patient.");
+            if let Err(err) = rescan_status_res {
+                handle_command_error(err, is_json);
             }
+        }
+        "createhotwallet" => {
+            let name: String = rpc_method_args.first().cloned().unwrap_or_default();
 
-            let import_wallet_res = gv_client.call_import_wallet(mnemonic, wallet_name).await;
+            let create_res = gv_client.call_create_hot_wallet(name).await;
 
-            if let Ok(import_wallet) = import_wallet_res {
-                if is_json {
-                    println!("{}", import_wallet.as_str().unwrap());
+            if let Err(err) = create_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "linkhotwallet" => {
+            let name: String = match rpc_method_args.first() {
+                Some(name) => name.to_string(),
+                None => {
+                    usage_error("Method 'linkhotwallet' requires a wallet name.", is_json);
                 }
-            } else if let Err(err) = import_wallet_res {
-                handle_command_error(err);
+            };
+
+            let link_res = gv_client.call_link_hot_wallet(name).await;
+
+            if let Err(err) = link_res {
+                handle_command_error(err, is_json);
             }
         }
-        "version" => display_version(),
-        "" | "help" => display_help(),
-        _ => println!("Method '{}' not found.", rpc_method),
-    }
-}
+        "hotwalletinfo" => {
+            let info_res = gv_client.call_get_hot_wallet_info().await;
 
-fn handle_command_error(err: Box<dyn StdError>) {
-    println!("Error: {}", err.to_string());
-    if err.to_string().contains("Connection refused") {
-        println!("Ensure that the GhostVault server is runing and try again.")
-    }
-}
+            if let Err(err) = info_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "hotwalletscript" => {
+            let script_res = gv_client.call_get_hot_wallet_script().await;
 
-fn display_version() {
-    println!("GhostVault CLI v{}", VERSION);
-}
+            if let Err(err) = script_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "describeapi" => {
+            let describe_res = gv_client.call_describe_api().await;
 
-fn display_help() {
-    println!("Usage: gv-cli [OPTIONS] [METHOD] [ARGS]");
-    println!("\nOptions:");
-    println!("  --gv-data-dir=GV_DATA_DIR    Set the GhostVault data directory");
-    println!("  --daemon-data-dir=DAEMON_DATA_DIR    Set the Ghost daemon data directory");
-    println!("  --json    Output in JSON format");
-    println!("\nMethods:");
-    println!("  status    Get the current state of GhostVault");
-    println!("  setrewardmode MODE [ADDRESS]    Set the reward mode");
-    println!("  setminpayout AMOUNT    Set the minimum payout amount");
-    println!("  setrewardtime INTERVAL    Set how often payouts are processed, in seconds");
-    println!("  enablebot TOKEN USER    Enable the Telegram bot (Restart required)");
-    println!("  disablebot    Disable the Telegram bot (Restart required)");
-    println!("  setbotannounce TYPE VALUE    Set the bot announcement value");
-    println!("  extpubkey    Get the extended public key for zapping");
-    println!("  shutdown    Shutdown the GhostVault server");
-    println!("  forceresync    Force a resync of ghostd");
-    println!("  stats    Get the staking overview");
-    println!("  getmnemonic    Get the wallet mnemonic");
-    println!("  settimezone TIMEZONE    Set the timezone");
-    println!("  importwallet MNEMONIC WALLET_NAME    Import a wallet");
-    println!("  version    Display the GhostVault CLI version");
-    println!("\nExamples:");
-    println!("  gv-cli setrewardmode DEFAULT");
-    println!("  gv-cli setrewardmode ANON \"ANON_REWARD_ADDRESS\"");
-    println!("  gv-cli setminpayout 25.5");
-    println!("  gv-cli setrewardtime 900");
-    println!("  gv-cli enablebot \"TELOXIDE_TOKEN\" \"TELEGRAM_USER\"");
-    println!("  gv-cli disablebot");
-    println!("  gv-cli setbotannounce rewards true");
-    println!("  gv-cli extpubkey");
-    println!("  gv-cli shutdown");
-    println!("  gv-cli forceresync");
-    println!("  gv-cli stats");
-    println!("  gv-cli status");
-    println!("  gv-cli getmnemonic");
-    println!("  gv-cli importwallet \"words between quotes\" WALLET_NAME");
-    println!("  gv-cli settimezone \"America/New_York\"");
+            if let Err(err) = describe_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "setwalletpassphrase" => {
+            let passphrase: String = match prompt_passphrase("Enter new wallet passphrase: ") {
+                Some(passphrase) => passphrase,
+                None => return,
+            };
+
+            match prompt_passphrase("Confirm wallet passphrase: ") {
+                Some(confirm) if confirm == passphrase => (),
+                _ => {
+                    println!("Passphrases do not match.");
+                    return;
+                }
+            }
+
+            let set_res = gv_client.call_set_wallet_passphrase(passphrase).await;
+
+            if let Err(err) = set_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "unlockwallet" => {
+            let passphrase: String = match prompt_passphrase("Enter wallet passphrase: ") {
+                Some(passphrase) => passphrase,
+                None => return,
+            };
+
+            let unlock_res = gv_client.call_unlock_wallet(passphrase).await;
+
+            if let Err(err) = unlock_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "unlockforpayout" => {
+            let passphrase: String = match prompt_passphrase("Enter wallet passphrase: ") {
+                Some(passphrase) => passphrase,
+                None => return,
+            };
+
+            let payout_res = gv_client.call_unlock_for_payout(passphrase).await;
+
+            if let Err(err) = payout_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "walletlockstatus" => {
+            let status_res = gv_client.call_get_wallet_lock_status().await;
+
+            if let Err(err) = status_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "exportparquet" => {
+            let out_dir: String = rpc_method_args.first().cloned().unwrap_or_default();
+
+            let export_res = gv_client.call_export_parquet(out_dir).await;
+
+            if let Err(err) = export_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "createbackup" => {
+            let path: String = rpc_method_args.first().cloned().unwrap_or_default();
+
+            let backup_res = gv_client.call_create_backup(path).await;
+
+            if let Err(err) = backup_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "restorebackup" => {
+            let path: String = match rpc_method_args.first() {
+                Some(path) => path.to_string(),
+                None => {
+                    usage_error(
+                        "Method 'restorebackup' requires the path to a backup file.",
+                        is_json,
+                    );
+                }
+            };
+
+            let passphrase: String = match prompt_passphrase("Backup passphrase: ") {
+                Some(passphrase) => passphrase,
+                None => return,
+            };
+
+            let restore_res = gv_client.call_restore_backup(path, passphrase).await;
+
+            if let Err(err) = restore_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "migrateexport" => {
+            let path: String = rpc_method_args.first().cloned().unwrap_or_default();
+
+            let export_res = gv_client.call_migrate_export(path).await;
+
+            if let Err(err) = export_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "migrateimport" => {
+            let path: String = match rpc_method_args.first() {
+                Some(path) => path.to_string(),
+                None => {
+                    usage_error(
+                        "Method 'migrateimport' requires the path to a migration bundle.",
+                        is_json,
+                    );
+                }
+            };
+
+            let force: bool = rpc_method_args
+                .get(1)
+                .is_some_and(|arg| arg.to_lowercase() == "force");
+
+            let passphrase: String = match prompt_passphrase("Export passphrase: ") {
+                Some(passphrase) => passphrase,
+                None => return,
+            };
+
+            let import_res = gv_client.call_migrate_import(path, passphrase, force).await;
+
+            if let Err(err) = import_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "updatedaemon" => {
+            let force: bool = rpc_method_args
+                .first()
+                .is_some_and(|arg| arg.to_lowercase() == "now");
+
+            let update_res = gv_client.call_process_daemon_update(force).await;
+
+            if let Err(err) = update_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "confirmupdate" => {
+            let token: String = match rpc_method_args.first() {
+                Some(token) => token.to_string(),
+                None => {
+                    usage_error("Method 'confirmupdate' requires the update token.", is_json);
+                }
+            };
+
+            let confirm_res = gv_client.call_confirm_update(token).await;
+
+            if let Err(err) = confirm_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "installdaemon" => {
+            let path: String = match rpc_method_args.first() {
+                Some(path) => path.to_string(),
+                None => {
+                    usage_error(
+                        "Method 'installdaemon' requires the path to a ghostd release archive.",
+                        is_json,
+                    );
+                }
+            };
+
+            let install_res = gv_client.call_install_daemon_archive(path).await;
+
+            if let Err(err) = install_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "pindaemon" => {
+            // No version lifts the pin
+            let version: String = rpc_method_args.first().cloned().unwrap_or_default();
+
+            let pin_res = gv_client.call_pin_daemon_version(version).await;
+
+            if let Err(err) = pin_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "systemstats" => {
+            let stats_res = gv_client.call_get_system_stats().await;
+
+            if let Err(err) = stats_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "dbmaintenance" => {
+            let maintenance_res = gv_client.call_db_maintenance().await;
+
+            if let Err(err) = maintenance_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "setprune" => {
+            if rpc_method_args.is_empty() {
+                usage_error(
+                    "Method 'setprune' requires a size in MiB, 0 turns pruning off.",
+                    is_json,
+                );
+            }
+
+            let size_mb: u64 = match rpc_method_args[0].parse::<u64>() {
+                Ok(size_mb) => size_mb,
+                Err(_) => usage_error(
+                    "Method 'setprune' SIZE must be a whole number of MiB.",
+                    is_json,
+                ),
+            };
+
+            let prune_res = gv_client.call_set_daemon_prune(size_mb).await;
+
+            if let Err(err) = prune_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "bootstrapsync" => {
+            let bootstrap_res = gv_client.call_bootstrap_sync().await;
+
+            if let Err(err) = bootstrap_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "bootstrapstatus" => {
+            let bootstrap_status_res = gv_client.call_get_bootstrap_status().await;
+
+            if let Err(err) = bootstrap_status_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "restarthistory" => {
+            let limit: u64 = match rpc_method_args.first() {
+                Some(limit) => match limit.parse::<u64>() {
+                    Ok(limit) => limit,
+                    Err(_) => usage_error(
+                        "Method 'restarthistory' LIMIT must be a whole number.",
+                        is_json,
+                    ),
+                },
+                None => 0,
+            };
+
+            let history_res = gv_client.call_get_restart_history(limit).await;
+
+            if let Err(err) = history_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "setloglevel" => {
+            if rpc_method_args.is_empty() {
+                usage_error(
+                    "Method 'setloglevel' requires a level: off, error, warn, info, debug or trace.",
+                    is_json,
+                );
+            }
+
+            let level: String = rpc_method_args[0].clone();
+            let module: String = rpc_method_args.get(1).cloned().unwrap_or_default();
+
+            let log_res = gv_client.call_set_log_level(level, module).await;
+
+            if let Err(err) = log_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "getlogs" => {
+            let mut lines: u64 = 0;
+            let mut level_filter: String = String::new();
+            let mut daemon: bool = false;
+
+            for arg in rpc_method_args.iter() {
+                if arg == "ghostd" {
+                    daemon = true;
+                } else if let Ok(count) = arg.parse::<u64>() {
+                    lines = count;
+                } else {
+                    level_filter = arg.clone();
+                }
+            }
+
+            let logs_res = gv_client.call_get_logs(lines, level_filter, daemon).await;
+
+            if let Err(err) = logs_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "auditlog" => {
+            let limit: u64 = match rpc_method_args.first() {
+                Some(limit) => match limit.parse::<u64>() {
+                    Ok(limit) => limit,
+                    Err(_) => {
+                        usage_error("Method 'auditlog' LIMIT must be a whole number.", is_json)
+                    }
+                },
+                None => 0,
+            };
+
+            let audit_res = gv_client.call_get_audit_log(limit).await;
+
+            if let Err(err) = audit_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "pendingrewardchange" => {
+            let pending_res = gv_client.call_get_pending_reward_change().await;
+
+            if let Err(err) = pending_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "cancelrewardchange" => {
+            let cancel_res = gv_client.call_cancel_reward_change().await;
+
+            if let Err(err) = cancel_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "sealmnemonic" => {
+            if rpc_method_args.len() < 1 {
+                usage_error(
+                    "Method 'sealmnemonic' missing required passphrase.",
+                    is_json,
+                );
+            }
+
+            let passphrase: String = rpc_method_args[0].to_string();
+
+            let seal_res = gv_client.call_seal_mnemonic(passphrase).await;
+
+            if let Err(err) = seal_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "reloadconfig" => {
+            let reload_res = gv_client.call_reload_config().await;
+
+            if let Err(err) = reload_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "simulaterewards" => {
+            let days: u64 = match rpc_method_args.first() {
+                Some(days) => match days.parse::<u64>() {
+                    Ok(days) => days,
+                    Err(_) => usage_error(
+                        "Method 'simulaterewards' DAYS must be a number of days.",
+                        is_json,
+                    ),
+                },
+                None => 0,
+            };
+            let mode: Option<String> = rpc_method_args.get(1).map(|mode| mode.to_uppercase());
+
+            let simulation_res = gv_client.call_simulate_rewards(mode, days).await;
+
+            if let Err(err) = simulation_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "networkstats" => {
+            let (start, end) = match parse_time_range(rpc_method, &rpc_method_args, is_json) {
+                Some(range) => range,
+                None => return,
+            };
+
+            let stats_res = gv_client.call_get_network_stats(start, end).await;
+
+            if let Err(err) = stats_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "coldwallets" => {
+            let wallets_res = gv_client.call_list_cold_wallets().await;
+
+            if let Err(err) = wallets_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "createcoldwallet" => {
+            let name: String = match rpc_method_args.first() {
+                Some(name) => name.to_string(),
+                None => {
+                    usage_error("Method 'createcoldwallet' requires a wallet name.", is_json);
+                }
+            };
+
+            let create_res = gv_client.call_create_cold_wallet(name).await;
+
+            if let Err(err) = create_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "importcoldwallet" => {
+            if rpc_method_args.len() < 2 {
+                usage_error(
+                    "Method 'importcoldwallet' requires a wallet name and a mnemonic.",
+                    is_json,
+                );
+            }
+
+            let name: String = rpc_method_args[0].to_string();
+            let mnemonic: String = rpc_method_args[1].to_string();
+
+            if human_out {
+                println!("Importing cold wallet\nThis may take a long time, please be patient.");
+            }
+
+            let import_res = gv_client.call_import_cold_wallet(name, mnemonic).await;
+
+            if let Err(err) = import_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "switchcoldwallet" => {
+            let name: String = match rpc_method_args.first() {
+                Some(name) => name.to_string(),
+                None => {
+                    usage_error("Method 'switchcoldwallet' requires a wallet name.", is_json);
+                }
+            };
+
+            let switch_res = gv_client.call_switch_cold_wallet(name).await;
+
+            if let Err(err) = switch_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "coldwalletstakes" => {
+            let name: String = match rpc_method_args.first() {
+                Some(name) => name.to_string(),
+                None => {
+                    usage_error("Method 'coldwalletstakes' requires a wallet name.", is_json);
+                }
+            };
+            let division: String = rpc_method_args
+                .get(1)
+                .cloned()
+                .unwrap_or_else(|| "day".to_string());
+
+            let (start, end) = match parse_time_range(
+                rpc_method,
+                rpc_method_args.get(2..).unwrap_or_default(),
+                is_json,
+            ) {
+                Some(range) => range,
+                None => return,
+            };
+
+            let stakes_res = gv_client
+                .call_get_cold_wallet_stakes(name, start, end, division)
+                .await;
+
+            if let Err(err) = stakes_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "delegations" => {
+            let delegations_res = gv_client.call_get_delegations().await;
+
+            if let Err(err) = delegations_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "zaprequest" => {
+            if rpc_method_args.len() < 1 {
+                usage_error("Method 'zaprequest' missing required amount.", is_json);
+            }
+
+            let amount: f64 = match rpc_method_args[0].parse::<f64>() {
+                Ok(amount) => amount,
+                Err(_) => usage_error("Method 'zaprequest' amount must be a number.", is_json),
+            };
+
+            let zap_request_res = gv_client.call_build_zap_request(amount).await;
+
+            if let Err(err) = zap_request_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "expectdeposit" => {
+            if rpc_method_args.len() < 1 {
+                usage_error("Method 'expectdeposit' missing required amount.", is_json);
+            }
+
+            let amount: f64 = match rpc_method_args[0].parse::<f64>() {
+                Ok(amount) => amount,
+                Err(_) => usage_error("Method 'expectdeposit' amount must be a number.", is_json),
+            };
+            let memo: String = rpc_method_args
+                .get(1)
+                .map(|memo| memo.to_string())
+                .unwrap_or_default();
+            let hours: Option<u64> = match rpc_method_args.get(2).map(|arg| arg.parse::<u64>()) {
+                Some(Ok(hours)) => Some(hours),
+                Some(Err(_)) => {
+                    usage_error("Method 'expectdeposit' hours must be a number.", is_json)
+                }
+                None => None,
+            };
+
+            let expect_res = gv_client
+                .call_add_expected_deposit(amount, memo, hours)
+                .await;
+
+            if let Err(err) = expect_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "expecteddeposits" => {
+            let deposits_res = gv_client.call_get_expected_deposits().await;
+
+            if let Err(err) = deposits_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "removeexpecteddeposit" => {
+            if rpc_method_args.len() < 1 {
+                usage_error(
+                    "Method 'removeexpecteddeposit' missing required id.",
+                    is_json,
+                );
+            }
+
+            let id: u64 = match rpc_method_args[0].parse::<u64>() {
+                Ok(id) => id,
+                Err(_) => usage_error(
+                    "Method 'removeexpecteddeposit' id must be a number.",
+                    is_json,
+                ),
+            };
+
+            let remove_res = gv_client.call_remove_expected_deposit(id).await;
+
+            if let Err(err) = remove_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "anonstatus" => {
+            let anon_status_res = gv_client.call_get_anonymization_status().await;
+
+            if let Err(err) = anon_status_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "setanonpolicy" => {
+            let enabled: bool = match rpc_method_args.first().map(|arg| arg.parse::<bool>()) {
+                Some(Ok(enabled)) => enabled,
+                _ => {
+                    usage_error(
+                        "Method 'setanonpolicy' requires true or false to enable or disable the conversion.",
+                        is_json,
+                    );
+                }
+            };
+
+            // Skipped settings are given as -, like setanonpolicy true - 3600
+            let arg = |index: usize| -> Option<&String> {
+                rpc_method_args.get(index).filter(|arg| arg.as_str() != "-")
+            };
+
+            let threshold: Option<f64> = match arg(1).map(|arg| arg.parse::<f64>()) {
+                None => None,
+                Some(Ok(threshold)) => Some(threshold),
+                Some(Err(_)) => {
+                    usage_error(
+                        "Method 'setanonpolicy' threshold must be a number.",
+                        is_json,
+                    );
+                }
+            };
+            let interval: Option<u64> = match arg(2).map(|arg| arg.parse::<u64>()) {
+                None => None,
+                Some(Ok(interval)) => Some(interval),
+                Some(Err(_)) => {
+                    usage_error(
+                        "Method 'setanonpolicy' interval must be a number of seconds.",
+                        is_json,
+                    );
+                }
+            };
+            let ring_size: Option<u32> = match arg(3).map(|arg| arg.parse::<u32>()) {
+                None => None,
+                Some(Ok(ring_size)) => Some(ring_size),
+                Some(Err(_)) => {
+                    usage_error(
+                        "Method 'setanonpolicy' ring size must be a number.",
+                        is_json,
+                    );
+                }
+            };
+            let batch_size: Option<u64> = match arg(4).map(|arg| arg.parse::<u64>()) {
+                None => None,
+                Some(Ok(batch_size)) => Some(batch_size),
+                Some(Err(_)) => {
+                    usage_error(
+                        "Method 'setanonpolicy' batch size must be a number.",
+                        is_json,
+                    );
+                }
+            };
+
+            let policy_res = gv_client
+                .call_set_anon_policy(enabled, threshold, interval, ring_size, batch_size)
+                .await;
+
+            if let Err(err) = policy_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "settxlabel" => {
+            if rpc_method_args.is_empty() {
+                usage_error("Method 'settxlabel' missing required txid.", is_json);
+            }
+
+            let txid: String = rpc_method_args[0].to_string();
+            // Everything after the txid is the label, none removes it
+            let label: String = rpc_method_args[1..].join(" ");
+
+            let label_res = gv_client.call_set_tx_label(txid, label).await;
+
+            if let Err(err) = label_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "txlabels" => {
+            let labels_res = gv_client.call_get_tx_labels().await;
+
+            if let Err(err) = labels_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "setaddresslabel" => {
+            if rpc_method_args.is_empty() {
+                usage_error(
+                    "Method 'setaddresslabel' missing required address.",
+                    is_json,
+                );
+            }
+
+            let address: String = rpc_method_args[0].to_string();
+            // Everything after the address is the label, none removes it from the book
+            let label: String = rpc_method_args[1..].join(" ");
+
+            let label_res = gv_client.call_set_address_label(address, label).await;
+
+            if let Err(err) = label_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "addressbook" => {
+            let book_res = gv_client.call_get_address_book().await;
+
+            if let Err(err) = book_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "capabilities" => {
+            let capabilities_res = gv_client.call_get_api_capabilities().await;
+
+            if let Err(err) = capabilities_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "maintenancewindow" => {
+            let window_res = gv_client.call_get_maintenance_window().await;
+
+            if let Err(err) = window_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "operationstatus" => {
+            let op_id: String = match rpc_method_args.first() {
+                Some(op_id) => op_id.to_string(),
+                None => {
+                    usage_error(
+                        "Method 'operationstatus' requires an operation: force_resync, daemon_update or import_wallet.",
+                        is_json,
+                    );
+                }
+            };
+
+            let status_res = gv_client.call_get_operation_status(op_id).await;
+
+            if let Err(err) = status_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "jobs" => {
+            let limit: u64 = match rpc_method_args.first() {
+                Some(limit) => match limit.parse::<u64>() {
+                    Ok(limit) => limit,
+                    Err(_) => usage_error("Method 'jobs' LIMIT must be a whole number.", is_json),
+                },
+                None => 0,
+            };
+
+            let jobs_res = gv_client.call_get_jobs(limit).await;
+
+            if let Err(err) = jobs_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "tasks" => {
+            let tasks_res = gv_client.call_get_scheduled_tasks().await;
+
+            if let Err(err) = tasks_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "runtask" => {
+            let name: String = match rpc_method_args.first() {
+                Some(name) => name.to_string(),
+                None => usage_error("Method 'runtask' requires a task name, see tasks.", is_json),
+            };
+
+            let run_res = gv_client.call_run_task_now(name).await;
+
+            if let Err(err) = run_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "pausetask" | "resumetask" => {
+            let name: String = match rpc_method_args.first() {
+                Some(name) => name.to_string(),
+                None => usage_error(
+                    &format!("Method '{}' requires a task name, see tasks.", rpc_method),
+                    is_json,
+                ),
+            };
+
+            let pause_res = gv_client
+                .call_pause_task(name, rpc_method == "pausetask")
+                .await;
+
+            if let Err(err) = pause_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "setmaintenancewindow" => {
+            if rpc_method_args.len() < 2 {
+                usage_error(
+                    "Method 'setmaintenancewindow' requires a start and end hour from 0 to 23.",
+                    is_json,
+                );
+            }
+
+            let (start, end) = match (
+                rpc_method_args[0].parse::<u32>(),
+                rpc_method_args[1].parse::<u32>(),
+            ) {
+                (Ok(start), Ok(end)) => (start, end),
+                _ => usage_error(
+                    "Method 'setmaintenancewindow' START and END must be hours from 0 to 23.",
+                    is_json,
+                ),
+            };
+
+            let window_res = gv_client.call_set_maintenance_window(start, end).await;
+
+            if let Err(err) = window_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "chainemergency" => {
+            let emergency_res = gv_client.call_get_chain_emergency().await;
+
+            if let Err(err) = emergency_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "chainaction" => {
+            let action: String = match rpc_method_args.first() {
+                Some(action) => action.to_string(),
+                None => {
+                    usage_error(
+                        "Method 'chainaction' requires an action: resync, ban_peers or wait.",
+                        is_json,
+                    );
+                }
+            };
+
+            let action_res = gv_client.call_chain_emergency_action(action).await;
+
+            if let Err(err) = action_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "announceroutes" => {
+            let routes_res = gv_client.call_get_announce_routes().await;
+
+            if let Err(err) = routes_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "setannounceroute" => {
+            if rpc_method_args.len() < 2 {
+                usage_error("Method 'setannounceroute' requires an event and a comma separated list of destinations.", is_json);
+            }
+
+            let event: String = rpc_method_args[0].to_string();
+            let destinations: String = rpc_method_args[1].to_string();
+
+            let route_res = gv_client.call_set_announce_route(event, destinations).await;
+
+            if let Err(err) = route_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "templates" => {
+            let templates_res = gv_client.call_get_announce_templates().await;
+
+            if let Err(err) = templates_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "settemplate" => {
+            if rpc_method_args.len() < 2 {
+                usage_error(
+                    "Method 'settemplate' requires a message type and a template, or default.",
+                    is_json,
+                );
+            }
+
+            let msg_type: String = rpc_method_args[0].to_string();
+            let body: String = rpc_method_args[1].to_string();
+            let header: String = rpc_method_args
+                .get(2)
+                .map(|header| header.to_string())
+                .unwrap_or_default();
+
+            let template_res = gv_client
+                .call_set_announce_template(msg_type, body, header)
+                .await;
+
+            if let Err(err) = template_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "quiethours" => {
+            let quiet_res = gv_client.call_get_quiet_hours().await;
+
+            if let Err(err) = quiet_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "setquiethours" => {
+            if rpc_method_args.len() < 2 {
+                usage_error(
+                    "Method 'setquiethours' requires a destination and hours such as 22-7, or off.",
+                    is_json,
+                );
+            }
+
+            let destination: String = rpc_method_args[0].to_string();
+            let hours: String = rpc_method_args[1].to_string();
+
+            let quiet_res = gv_client.call_set_quiet_hours(destination, hours).await;
+
+            if let Err(err) = quiet_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "explorerhealth" => {
+            let health_res = gv_client.call_get_explorer_health().await;
+
+            if let Err(err) = health_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "chaintip" => {
+            let tip_res = gv_client.call_get_chain_tip().await;
+
+            if let Err(err) = tip_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "lookuptx" => {
+            if rpc_method_args.is_empty() {
+                usage_error("Method 'lookuptx' requires a txid.", is_json);
+            }
+
+            let txid: String = rpc_method_args[0].to_string();
+
+            let tx_res = gv_client.call_lookup_tx(txid).await;
+
+            if let Err(err) = tx_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "lookupblock" => {
+            if rpc_method_args.is_empty() {
+                usage_error(
+                    "Method 'lookupblock' requires a block height or hash.",
+                    is_json,
+                );
+            }
+
+            let block: String = rpc_method_args[0].to_string();
+
+            let block_res = gv_client.call_lookup_block(block).await;
+
+            if let Err(err) = block_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "stakesbyaddress" => {
+            let (start, end) = match parse_time_range(rpc_method, &rpc_method_args, is_json) {
+                Some(range) => range,
+                None => return,
+            };
+
+            let stakes_res = gv_client.call_get_stakes_by_address(start, end).await;
+
+            if let Err(err) = stakes_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "stakesbyaddresscsv" => {
+            let (start, end) = match parse_time_range(rpc_method, &rpc_method_args, is_json) {
+                Some(range) => range,
+                None => return,
+            };
+
+            let csv_res = gv_client.call_get_stakes_by_address_csv(start, end).await;
+
+            if let Err(err) = csv_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "registertenant" => {
+            if rpc_method_args.len() < 3 {
+                usage_error("Method 'registertenant' requires a name, an address or ext pub key, and a payout address.", is_json);
+            }
+
+            let name: String = rpc_method_args[0].to_string();
+            let key: String = rpc_method_args[1].to_string();
+            let payout_address: String = rpc_method_args[2].to_string();
+
+            let tenant_res = gv_client
+                .call_register_tenant(name, key, payout_address)
+                .await;
+
+            if let Err(err) = tenant_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "removetenant" => {
+            if rpc_method_args.is_empty() {
+                usage_error("Method 'removetenant' missing required name.", is_json);
+            }
+
+            let name: String = rpc_method_args[0].to_string();
+
+            let tenant_res = gv_client.call_remove_tenant(name).await;
+
+            if let Err(err) = tenant_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "tenants" => {
+            let tenants_res = gv_client.call_get_tenants().await;
+
+            if let Err(err) = tenants_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "tenantstatement" => {
+            if rpc_method_args.is_empty() {
+                usage_error("Method 'tenantstatement' missing required name.", is_json);
+            }
+
+            let name: String = rpc_method_args[0].to_string();
+
+            let (start, end) = match parse_time_range(rpc_method, &rpc_method_args[1..], is_json) {
+                Some(range) => range,
+                None => return,
+            };
+
+            let statement_res = gv_client.call_get_tenant_statement(name, start, end).await;
+
+            if let Err(err) = statement_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "setfeepolicy" => {
+            if rpc_method_args.is_empty() {
+                usage_error("Method 'setfeepolicy' missing required policy.", is_json);
+            }
+
+            let policy: String = rpc_method_args[0].to_uppercase();
+
+            let mut amounts: Vec<Option<f64>> = Vec::new();
+            for arg in rpc_method_args.iter().skip(1).take(2) {
+                match arg.parse::<f64>() {
+                    Ok(amount) => amounts.push(Some(amount)),
+                    Err(_) => {
+                        usage_error("Method 'setfeepolicy' values must be numbers.", is_json);
+                    }
+                }
+            }
+
+            let value: Option<f64> = amounts.first().copied().flatten();
+            let max_fee: Option<f64> = amounts.get(1).copied().flatten();
+
+            let fee_res = gv_client.call_set_fee_policy(policy, value, max_fee).await;
+
+            if let Err(err) = fee_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "listutxos" => {
+            let wallet: String = rpc_method_args
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "cold".to_string());
+            let utxo_type: String = rpc_method_args
+                .get(1)
+                .cloned()
+                .unwrap_or_else(|| "all".to_string());
+
+            let mut amounts: Vec<f64> = Vec::new();
+            for arg in rpc_method_args.iter().skip(2).take(2) {
+                match arg.parse::<f64>() {
+                    Ok(amount) => amounts.push(amount),
+                    Err(_) => {
+                        usage_error("Method 'listutxos' amounts must be numbers.", is_json);
+                    }
+                }
+            }
+
+            let min_confirmations: u64 = match rpc_method_args.get(4) {
+                Some(arg) => match arg.parse::<u64>() {
+                    Ok(confirmations) => confirmations,
+                    Err(_) => {
+                        usage_error(
+                            "Method 'listutxos' confirmations must be a whole number.",
+                            is_json,
+                        );
+                    }
+                },
+                None => 0,
+            };
+
+            let utxos_res = gv_client
+                .call_list_utxos(
+                    wallet,
+                    utxo_type,
+                    amounts.first().copied().unwrap_or(0.0),
+                    amounts.get(1).copied().unwrap_or(0.0),
+                    min_confirmations,
+                )
+                .await;
+
+            if let Err(err) = utxos_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "freezeutxo" | "unfreezeutxo" => {
+            if rpc_method_args.len() < 3 {
+                usage_error(
+                    &format!("Method '{}' requires WALLET, TXID and VOUT.", rpc_method),
+                    is_json,
+                );
+            }
+
+            let vout: u32 = match rpc_method_args[2].parse::<u32>() {
+                Ok(vout) => vout,
+                Err(_) => {
+                    usage_error(
+                        &format!("Method '{}' VOUT must be a whole number.", rpc_method),
+                        is_json,
+                    );
+                }
+            };
+
+            let wallet: String = rpc_method_args[0].clone();
+            let txid: String = rpc_method_args[1].clone();
+
+            let freeze_res = if rpc_method == "freezeutxo" {
+                gv_client.call_freeze_utxo(wallet, txid, vout).await
+            } else {
+                gv_client.call_unfreeze_utxo(wallet, txid, vout).await
+            };
+
+            if let Err(err) = freeze_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "consolidate" => {
+            if rpc_method_args.len() < 2 {
+                usage_error(
+                    "Method 'consolidate' requires WALLET and MAX_AMOUNT.",
+                    is_json,
+                );
+            }
+
+            let mut amounts: Vec<f64> = Vec::new();
+            for arg in rpc_method_args.iter().skip(1).take(2) {
+                match arg.parse::<f64>() {
+                    Ok(amount) => amounts.push(amount),
+                    Err(_) => {
+                        usage_error("Method 'consolidate' amounts must be numbers.", is_json);
+                    }
+                }
+            }
+
+            let consolidate_res = gv_client
+                .call_consolidate_utxos(
+                    rpc_method_args[0].clone(),
+                    amounts[0],
+                    amounts.get(1).copied().unwrap_or(0.0),
+                )
+                .await;
+
+            if let Err(err) = consolidate_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "splitutxos" => {
+            let split_res = gv_client.call_split_utxos().await;
+
+            if let Err(err) = split_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "confirmsplit" => {
+            let token: String = match rpc_method_args.first() {
+                Some(token) => token.to_string(),
+                None => {
+                    usage_error("Method 'confirmsplit' requires the split token.", is_json);
+                }
+            };
+
+            let confirm_res = gv_client.call_confirm_split(token).await;
+
+            if let Err(err) = confirm_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "events" => {
+            let mut numbers: Vec<u64> = Vec::new();
+
+            for arg in rpc_method_args.iter().take(2) {
+                match arg.parse::<u64>() {
+                    Ok(number) => numbers.push(number),
+                    Err(_) => {
+                        usage_error(
+                            "Method 'events' CURSOR and WAIT must be whole numbers.",
+                            is_json,
+                        );
+                    }
+                }
+            }
+
+            let cursor: u64 = numbers.first().copied().unwrap_or(0);
+            let wait: u64 = numbers.get(1).copied().unwrap_or(0);
+
+            let events_res = gv_client.call_get_events_since(cursor, wait).await;
+
+            if let Err(err) = events_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "dashboard" => {
+            let refresh: u64 = match rpc_method_args.first() {
+                Some(arg) => match arg.parse::<u64>() {
+                    Ok(secs) if secs > 0 => secs,
+                    _ => {
+                        usage_error(
+                            "Method 'dashboard' REFRESH must be a whole number of seconds.",
+                            is_json,
+                        );
+                    }
+                },
+                None => DASHBOARD_REFRESH,
+            };
+
+            // The dashboard draws everything itself, so the client must not print results
+            let quiet_client: CLICaller = match CLICaller::new(&config.cli_address, true).await {
+                Ok(client) => client,
+                Err(err) => handle_command_error(err, is_json),
+            };
+
+            let dashboard_res = dashboard::run_dashboard(
+                &quiet_client,
+                gv_data_dir.join(GV_LOG_FILE),
+                Duration::from_secs(refresh),
+            )
+            .await;
+
+            if let Err(err) = dashboard_res {
+                println!("Dashboard error: {}", err);
+            }
+        }
+        "roi" => {
+            let roi_res = gv_client.call_get_roi().await;
+
+            if let Err(err) = roi_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "stakeforecast" => {
+            let forecast_res = gv_client.call_get_stake_forecast().await;
+
+            if let Err(err) = forecast_res {
+                handle_command_error(err, is_json);
+            }
+        }
+        "version" => display_version(),
+        "" | "help" => display_help(),
+        _ => usage_error(&format!("Method '{}' not found.", rpc_method), is_json),
+    }
+}
+
+// In JSON mode a failed call's envelope was already printed by the client, anything that
+// failed before a response came back still gets one. Either way the exit status is 1.
+// A self-signed certificate for CLI_ADDRESS, localhost and any extra hosts, which is then set
+// as CLI_TLS_CERT and CLI_TLS_KEY.
+fn generate_tls_cert(config: &GVConfig, extra_hosts: &[String], human_out: bool, is_json: bool) {
+    let mut hosts: Vec<String> = vec!["localhost".to_string(), "127.0.0.1".to_string()];
+    let cli_host: &str = config
+        .cli_address
+        .rsplit_once(':')
+        .map_or(config.cli_address.as_str(), |(host, _)| host);
+    hosts.push(cli_host.to_string());
+    hosts.extend(extra_hosts.iter().cloned());
+    hosts.sort();
+    hosts.dedup();
+
+    let (cert_path, key_path) = match cli_tls::generate_cert(&config.gv_home, hosts.clone()) {
+        Ok(paths) => paths,
+        Err(err) => handle_command_error(err, is_json),
+    };
+
+    let mut conf: GVConfig = config.clone();
+    for (key, path) in [("CLI_TLS_CERT", &cert_path), ("CLI_TLS_KEY", &key_path)] {
+        if let Err(err) = conf.update_gv_config(key, &path.to_string_lossy()) {
+            handle_command_error(err, is_json);
+        }
+    }
+
+    if is_json {
+        let result: Value = serde_json::json!({
+            "cert": cert_path,
+            "key": key_path,
+            "hosts": hosts,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response::ok(result)).unwrap()
+        );
+    } else if human_out {
+        println!("Certificate: {}", cert_path.display());
+        println!("Private key: {}", key_path.display());
+        println!("Valid for: {}", hosts.join(", "));
+        println!();
+        println!("Restart GhostVault to serve TLS. Remote clients need a copy of the certificate,");
+        println!("set as tls_cert for the vault in fleet.toml, TG_VAULTS or CHAIN_PEERS.");
+    }
+}
+
+fn handle_command_error(err: Box<dyn StdError>, is_json: bool) -> ! {
+    if is_json {
+        if response::error_code(err.as_ref()).is_none() {
+            let envelope: GvResponse = GvResponse::from_error(err.as_ref());
+            println!("{}", serde_json::to_string_pretty(&envelope).unwrap());
+        }
+        exit(1);
+    }
+
+    eprintln!("Error: {}", err);
+    if err.to_string().contains("Connection refused") {
+        eprintln!("Ensure that the GhostVault server is runing and try again.")
+    }
+    exit(1);
+}
+
+// Bad or missing arguments, nothing was sent to GhostVault. Exits with status 2.
+fn usage_error(msg: &str, is_json: bool) -> ! {
+    if is_json {
+        let envelope: GvReply<()> = response::error(ErrorCode::InvalidArgument, msg);
+        println!("{}", serde_json::to_string_pretty(&envelope).unwrap());
+    } else {
+        eprintln!("{}", msg);
+    }
+    exit(2);
+}
+
+// Optional [START] [END] unix timestamps, defaulting to all time up to now.
+fn parse_time_range(rpc_method: &str, args: &[String], is_json: bool) -> Option<(u64, u64)> {
+    let start: u64 = match args.first() {
+        Some(start) => match start.parse::<u64>() {
+            Ok(start) => start,
+            Err(_) => usage_error(
+                &format!("Method '{}' start must be a unix timestamp.", rpc_method),
+                is_json,
+            ),
+        },
+        None => 0,
+    };
+
+    let end: u64 = match args.get(1) {
+        Some(end) => match end.parse::<u64>() {
+            Ok(end) => end,
+            Err(_) => usage_error(
+                &format!("Method '{}' end must be a unix timestamp.", rpc_method),
+                is_json,
+            ),
+        },
+        None => chrono::Utc::now().timestamp() as u64,
+    };
+
+    Some((start, end))
+}
+
+// Read a passphrase from stdin with echo turned off, so it never ends up in the shell history.
+fn prompt_passphrase(prompt: &str) -> Option<String> {
+    print!("{}", prompt);
+    std::io::stdout().flush().ok()?;
+
+    let _ = Command::new("stty").arg("-echo").status();
+    let mut passphrase: String = String::new();
+    let read_res = std::io::stdin().read_line(&mut passphrase);
+    let _ = Command::new("stty").arg("echo").status();
+    println!();
+
+    if read_res.is_err() {
+        println!("Failed to read passphrase.");
+        return None;
+    }
+
+    let passphrase: String = passphrase.trim_end_matches(['\r', '\n']).to_string();
+
+    if passphrase.is_empty() {
+        println!("Passphrase can not be empty.");
+        return None;
+    }
+
+    Some(passphrase)
+}
+
+fn display_version() {
+    println!(
+        "GhostVault CLI v{} ({}) built {}",
+        VERSION, GIT_COMMIT, BUILD_DATE
+    );
+}
+
+fn display_help() {
+    println!("Usage: gv-cli [OPTIONS] [METHOD] [ARGS]");
+    println!("\nOptions:");
+    println!("  --gv-data-dir=GV_DATA_DIR    Set the GhostVault data directory");
+    println!("  --daemon-data-dir=DAEMON_DATA_DIR    Set the Ghost daemon data directory");
+    println!("  --fleet=FLEET_FILE    Set the fleet file used by the fleet commands");
+    println!("  --profile=PROFILE    Use the vault configured in gv_settings.PROFILE.toml");
+    println!("  --json    Output each response as a JSON {{ok, code, message, data}} envelope");
+    println!("  --quiet    Print nothing, check the exit status: 0 ok, 1 failed, 2 bad arguments");
+    println!("\nMethods:");
+    println!(
+        "  status [TIMEZONE]    Get the current state of GhostVault, times in TIMEZONE if given"
+    );
+    println!("  setrewardmode MODE [ADDRESS]    Set the reward mode");
+    println!("  setminpayout AMOUNT    Set the minimum payout amount");
+    println!("  setfeepolicy FIXED [RATE] [MAX_FEE]    Pay a fixed fee rate in GHOST per kB, capped at MAX_FEE GHOST per transaction");
+    println!("  setfeepolicy ESTIMATE [BLOCKS] [MAX_FEE]    Estimate a fee rate that confirms within BLOCKS");
+    println!("  listutxos [WALLET] [TYPE] [MIN] [MAX] [MINCONF]    List unspent outputs of a wallet, TYPE is ghost, anon or all");
+    println!(
+        "  freezeutxo WALLET TXID VOUT    Keep an output from being spent by sweeps and payouts"
+    );
+    println!("  unfreezeutxo WALLET TXID VOUT    Release a frozen output");
+    println!("  consolidate WALLET MAX_AMOUNT [MAX_FEE_RATE]    Merge outputs at or below MAX_AMOUNT into the coldstake script, only while the fee rate is at or below MAX_FEE_RATE");
+    println!("  splitutxos    Plan splitting staking outputs above SPLIT_THRESHOLD into SPLIT_COUNT pieces");
+    println!("  confirmsplit TOKEN    Carry out a planned UTXO split");
+    println!("  events [CURSOR] [WAIT]    Logged events after CURSOR, waiting up to WAIT seconds for new ones");
+    println!("  setrewardtime INTERVAL    Set how often payouts are processed, in seconds");
+    println!("  enablebot TOKEN USER    Enable the Telegram bot (Restart required)");
+    println!("  disablebot    Disable the Telegram bot (Restart required)");
+    println!("  setbotannounce TYPE VALUE    Set the bot announcement value");
+    println!("  digest    Get the daily and weekly digest settings");
+    println!("  setdigest daily|weekly VALUE    Turn the daily or weekly summary digest on or off");
+    println!(
+        "  setdigesttime HOUR    Set the hour digests are sent at, in the configured timezone"
+    );
+    println!("  extpubkey    Get the extended public key for zapping");
+    println!("  shutdown    Shutdown the GhostVault server");
+    println!(
+        "  forceresync [now]    Resync ghostd in the maintenance window, or right away with now"
+    );
+    println!("  stats    Get the staking overview");
+    println!("  completions SHELL    Print the completion script for bash, zsh, fish, elvish or powershell");
+    println!("  dashboard [REFRESH]    Live dashboard of status, staking, rewards and the log, refreshed every REFRESH seconds");
+    println!("  getmnemonic [PASSPHRASE]    Get the wallet mnemonic, the passphrase is needed once it's sealed");
+    println!("  settimezone TIMEZONE    Set the timezone");
+    println!("  importwallet MNEMONIC WALLET_NAME [BIRTHDAY]    Import a wallet, scanning from a block height or YYYY-MM-DD date");
+    println!("  payouthistory [START] [END]    Get reward payouts between unix timestamps");
+    println!(
+        "  rescanwallet [START_HEIGHT]    Schedule a wallet rescan for the maintenance window"
+    );
+    println!("  repairwallet    Schedule clearing the wallet's transactions and rescanning them from genesis");
+    println!("  reindexhistory    Rebuild the stake history from the wallets, config and wallets are left alone");
+    println!("  rescanstatus    Get the status of the scheduled wallet rescan or repair");
+    println!("  createhotwallet [NAME]    Create a hot wallet for spending");
+    println!("  linkhotwallet NAME    Use an existing wallet as the hot wallet");
+    println!("  hotwalletinfo    Get the hot wallet balances and delegation");
+    println!("  hotwalletscript    Build a cold staking script spendable by the hot wallet");
+    println!("  describeapi    List the RPC methods and the scope each one requires");
+    println!("  capabilities    Get the server's API version, build features and RPC methods");
+    println!("  setwalletpassphrase    Encrypt the wallet and unlock it for staking");
+    println!("  unlockwallet    Unlock the encrypted wallet for staking only, payouts need a full unlock");
+    println!("  unlockforpayout    Fully unlock the wallet for one payout round, then relock it for staking");
+    println!("  walletlockstatus    Get the wallet encryption status");
+    println!("  exportparquet [DIR]    Export rewards, payouts and balance history to Parquet");
+    println!("  createbackup [PATH]    Create an encrypted backup of the config and database");
+    println!("  restorebackup PATH    Restore a backup on the next GhostVault restart");
+    println!("  migrateexport [PATH]    Export the config, database and reward history to move the vault to another machine");
+    println!("  migrateimport PATH [force]    Import a migration bundle on the next restart, force keeps going if the wallet differs");
+    println!("  updatedaemon [now]    Check for a ghostd update and stage it or apply it in the maintenance window, or right away with now");
+    println!("  systemstats    Get the load, disk space, blockchain growth and ghostd traffic");
+    println!("  dbmaintenance    Check the database, repair stake totals and rollups, and report its size");
+    println!("  setprune SIZE    Run ghostd pruned to SIZE MiB of blocks, at least 550, 0 turns it off and resyncs");
+    println!("  bootstrapsync    Download the blockchain bootstrap from BOOTSTRAP_URL and install it into ghostd");
+    println!("  bootstrapstatus    Get the progress of the blockchain bootstrap");
+    println!("  restarthistory [LIMIT]    Get the ghostd restarts done by the Docker supervisor, newest first");
+    println!("  setloglevel LEVEL [MODULE]    Change the log level until GhostVault restarts, for one MODULE if given, default drops a module's level");
+    println!("  getlogs [LINES] [LEVEL] [ghostd]    Show the end of ghostvault.log, only LEVEL and worse if given, ghostd adds ghostd's debug.log");
+    println!("  auditlog [LIMIT]    Show the latest administrative actions, who made them and from where");
+    println!(
+        "  pendingrewardchange    Show a reward address change waiting out REWARD_CHANGE_DELAY"
+    );
+    println!("  cancelrewardchange    Cancel a reward address change before it takes effect");
+    println!("  sealmnemonic    Encrypt the mnemonic with a passphrase, getmnemonic then needs it");
+    println!("  reloadconfig    Re-read gv_settings.toml, lists the changes that need a restart");
+    println!("  simulaterewards [DAYS] [MODE]    Compare what each reward mode would have paid over the last DAYS (default 30)");
+    println!("  networkstats [START] [END]    Get the network stake weight and difficulty history and the vault's share of the weight");
+    println!("  coldwallets    List the cold wallets with their stakes and payouts");
+    println!(
+        "  createcoldwallet NAME    Create another cold wallet that stakes next to the primary one"
+    );
+    println!("  importcoldwallet NAME MNEMONIC    Import another cold wallet that stakes next to the primary one");
+    println!("  switchcoldwallet NAME    Make a cold wallet the primary wallet that pays out and takes zaps");
+    println!("  coldwalletstakes NAME [DIVISION] [START] [END]    Get a cold wallet's stakes by day, week or month");
+    println!("  delegations    List the addresses cold staking to the vault with their amounts and stakes");
+    println!("  zaprequest AMOUNT    Get a cold staking script to zap AMOUNT GHOST into the vault");
+    println!("  expectdeposit AMOUNT [MEMO] [HOURS]    Expect a deposit, you're told if it doesn't arrive in time");
+    println!("  expecteddeposits    List the expected deposits and whether they arrived");
+    println!("  removeexpecteddeposit ID    Stop expecting a deposit");
+    println!("  anonstatus    Get where rewards are on their way from public to anon");
+    println!("  setanonpolicy ENABLED [THRESHOLD] [INTERVAL] [RING_SIZE] [BATCH_SIZE]    Set when rewards are converted to anon, - skips a setting, false pays out from the public balance");
+    println!("  settxlabel TXID [LABEL]    Label a payout, zap or other transaction for bookkeeping, no label removes it");
+    println!("  txlabels    List the labeled transactions");
+    println!("  setaddresslabel ADDRESS [LABEL]    Name an address in the address book, the name can then stand in for it in setrewardmode, no label removes it");
+    println!("  addressbook    List the named addresses");
+    println!("  maintenancewindow    Get the maintenance window and what's waiting for it");
+    println!(
+        "  operationstatus OP    Get the progress of force_resync, daemon_update or import_wallet"
+    );
+    println!("  jobs [LIMIT]    List the latest payouts, resyncs, updates, backups and rescans and how they ended");
+    println!("  tasks    List the scheduled tasks with their interval, last run, last result and next run");
+    println!("  runtask NAME    Run a scheduled task now instead of waiting for its next run");
+    println!("  pausetask NAME    Stop a scheduled task from running until it's resumed");
+    println!("  resumetask NAME    Let a paused task run on its schedule again");
+    println!("  setmaintenancewindow START END    Set the hours updates and resyncs wait for, in the configured timezone, equal hours means any time");
+    println!("  confirmupdate TOKEN    Apply a staged ghostd update");
+    println!("  installdaemon PATH    Install ghostd from a downloaded release archive, hashes.txt must be in the same directory");
+    println!("  pindaemon [VERSION]    Hold ghostd at VERSION instead of following the update channel, no version lifts the pin");
+    println!("  chainemergency    Get the chain split emergency status");
+    println!(
+        "  chainaction ACTION    Respond to a chain split emergency with resync, ban_peers or wait"
+    );
+    println!("  announceroutes    Get where each announcement event is sent");
+    println!("  setannounceroute EVENT DESTINATIONS    Route an event to telegram_admin, telegram_channel, webhook and/or email, or none");
+    println!("  templates    Get the message templates for stake, zap and rewards announcements");
+    println!("  settemplate TYPE TEMPLATE [HEADER]    Set the text of an announcement using {{amount}}, {{txid}}, {{height}} and {{total_24h}}, or default");
+    println!("  quiethours    Get the quiet hours of each announcement destination");
+    println!("  setquiethours DESTINATION HOURS    Hold non-critical notices for a destination between local hours such as 22-7, or off");
+    println!("  explorerhealth    Check each block explorer API used to verify the chain");
+    println!("  chaintip    Get the best block other vaults check their chain against");
+    println!(
+        "  lookuptx TXID    Get a transaction from the vault's wallets with its explorer link"
+    );
+    println!("  lookupblock HEIGHT|HASH    Get a block by height or hash with its explorer link");
+    println!("  stakeforecast    Get the expected time to stake and how recent stakes compare");
+    println!(
+        "  roi    Get the return on the average staked balance over 30 days, 90 days and a year"
+    );
+    println!("  stakesbyaddress [START] [END]    Get stakes and rewards per staking address between unix timestamps");
+    println!("  stakesbyaddresscsv [START] [END]    Same as stakesbyaddress, as CSV");
+    println!("  registertenant <NAME> <ADDRESS|EXTPUBKEY> <PAYOUT_ADDRESS>    Share the vault with a delegator, splitting anon mode rewards by stake");
+    println!("  removetenant <NAME>    Stop sharing rewards with a tenant");
+    println!("  tenants    List the vault's tenants and what they're owed");
+    println!("  tenantstatement <NAME> [START] [END]    Get a tenant's stakes and payouts between unix timestamps");
+    println!("  fleetstatus    Get the status of every vault in the fleet");
+    println!("  fleetoverview    Get the staking overview of every vault in the fleet");
+    println!("  fleetversion    Get the versions running on every vault in the fleet");
+    println!("  gentlscert [HOST ...]    Make a self-signed certificate for the RPC server and turn on TLS");
+    println!("  version    Display the GhostVault CLI version");
+    println!("\nExamples:");
+    println!("  gv-cli setrewardmode DEFAULT");
+    println!("  gv-cli setrewardmode ANON \"ANON_REWARD_ADDRESS\"");
+    println!("  gv-cli setaddresslabel \"ANON_REWARD_ADDRESS\" Cold storage");
+    println!("  gv-cli setrewardmode ANON \"Cold storage\"");
+    println!("  gv-cli setminpayout 25.5");
+    println!("  gv-cli setfeepolicy FIXED 0.000075");
+    println!("  gv-cli setfeepolicy ESTIMATE 6 0.5");
+    println!("  gv-cli listutxos cold ghost 0 1 10");
+    println!("  gv-cli freezeutxo cold <txid> 0");
+    println!("  gv-cli consolidate cold 1 0.0001");
+    println!("  gv-cli splitutxos");
+    println!("  gv-cli confirmsplit \"SPLIT_TOKEN\"");
+    println!("  gv-cli events 120 30");
+    println!("  gv-cli lookupblock 591621");
+    println!("  gv-cli installdaemon /root/ghost-0.21.1-x86_64-pc-linux-gnu.tar.gz");
+    println!("  gv-cli setrewardtime 900");
+    println!("  gv-cli enablebot \"TELOXIDE_TOKEN\" \"TELEGRAM_USER\"");
+    println!("  gv-cli disablebot");
+    println!("  gv-cli setbotannounce rewards true");
+    println!("  gv-cli setdigest weekly true");
+    println!("  gv-cli setdigesttime 8");
+    println!("  gv-cli extpubkey");
+    println!("  gv-cli shutdown");
+    println!("  gv-cli forceresync");
+    println!("  gv-cli setmaintenancewindow 3 5");
+    println!("  gv-cli operationstatus daemon_update");
+    println!("  gv-cli jobs 10");
+    println!("  gv-cli runtask auto_backup");
+    println!("  gv-cli stats");
+    println!("  gv-cli status");
+    println!("  gv-cli status America/New_York");
+    println!("  gv-cli dashboard 10");
+    println!("  gv-cli completions bash > /etc/bash_completion.d/gv-cli");
+    println!("  gv-cli getmnemonic");
+    println!("  gv-cli importwallet \"words between quotes\" WALLET_NAME");
+    println!("  gv-cli importwallet \"words between quotes\" WALLET_NAME 2023-06-01");
+    println!("  gv-cli settimezone \"America/New_York\"");
+    println!("  gv-cli payouthistory");
+    println!("  gv-cli payouthistory 1704067200 1706745599");
+    println!("  gv-cli rescanwallet 1500000");
+    println!("  gv-cli rescanstatus");
+    println!("  gv-cli createhotwallet GV_HOT");
+    println!("  gv-cli linkhotwallet MY_WALLET");
+    println!("  gv-cli hotwalletinfo");
+    println!("  gv-cli hotwalletscript");
+    println!("  gv-cli describeapi");
+    println!("  gv-cli setwalletpassphrase");
+    println!("  gv-cli unlockwallet");
+    println!("  gv-cli unlockforpayout");
+    println!("  gv-cli walletlockstatus");
+    println!("  gv-cli exportparquet ~/gv_parquet");
+    println!("  gv-cli createbackup");
+    println!("  gv-cli restorebackup ~/.ghostvault/backups/ghostvault-20240101-000000.gvbak");
+    println!("  gv-cli migrateexport ~/migrate");
+    println!("  gv-cli migrateimport ~/ghostvault-migrate-20240101-000000.gvmig");
+    println!("  gv-cli updatedaemon");
+    println!("  gv-cli confirmupdate \"UPDATE_TOKEN\"");
+    println!("  gv-cli chainemergency");
+    println!("  gv-cli chainaction ban_peers");
+    println!("  gv-cli announceroutes");
+    println!("  gv-cli setannounceroute incident telegram_admin,email");
+    println!("  gv-cli setquiethours telegram_admin 22-7");
+    println!("  gv-cli settemplate stake \"Block {{height}} staked for {{amount}} GHOST\"");
+    println!("  gv-cli explorerhealth");
+    println!("  gv-cli chaintip");
+    println!("  gv-cli stakeforecast");
+    println!("  gv-cli roi");
+    println!("  gv-cli stakesbyaddress 1704067200");
+    println!("  gv-cli stakesbyaddresscsv > stakes.csv");
+    println!("  gv-cli registertenant alice <ADDRESS> <PAYOUT_ADDRESS>");
+    println!("  gv-cli tenantstatement alice 1704067200");
+    println!("  gv-cli fleetstatus");
+    println!("  gv-cli --fleet=~/vaults.toml fleetoverview");
+    println!("  gv-cli fleetversion");
+    println!("\nFleet:");
+    println!(
+        "  The fleet commands read ~/.ghostvault/{} unless --fleet is set:",
+        FLEET_FILE
+    );
+    println!("    [[vault]]");
+    println!("    name = \"vault-1\"");
+    println!("    cli_address = \"10.0.0.2:50051\"");
+    println!("    tls_cert = \"~/vault-1_cert.pem\"    (only for vaults that serve TLS)");
+    println!("\nScopes:");
+    for method in api_schema::describe_api() {
+        if let Some(cli_command) = method.cli_command {
+            println!("  {}    {}", cli_command, method.scope);
+        }
+    }
 }
@@ -0,0 +1,106 @@
+// Shell completions for gv-cli. The argument parsing itself stays hand rolled, this clap
+// command only describes it so clap_complete can generate the scripts.
+use clap::{Arg, ArgAction, Command, ValueHint};
+use clap_complete::{generate, Shell};
+use service::api_schema;
+use std::io;
+
+// Commands handled by gv-cli itself rather than a GvCLI method.
+const CLIENT_COMMANDS: &[&str] = &[
+    "completions",
+    "dashboard",
+    "fleetstatus",
+    "fleetoverview",
+    "fleetversion",
+    "gentlscert",
+    "help",
+    "version",
+];
+
+fn gv_cli_command() -> Command {
+    let mut commands: Vec<&'static str> = api_schema::cli_commands();
+    commands.extend_from_slice(CLIENT_COMMANDS);
+    commands.sort_unstable();
+    commands.dedup();
+
+    Command::new("gv-cli")
+        .disable_help_flag(true)
+        .disable_version_flag(true)
+        .arg(
+            Arg::new("gv-data-dir")
+                .long("gv-data-dir")
+                .value_name("GV_DATA_DIR")
+                .value_hint(ValueHint::DirPath)
+                .global(true),
+        )
+        .arg(
+            Arg::new("daemon-data-dir")
+                .long("daemon-data-dir")
+                .value_name("DAEMON_DATA_DIR")
+                .value_hint(ValueHint::DirPath)
+                .global(true),
+        )
+        .arg(
+            Arg::new("fleet")
+                .long("fleet")
+                .value_name("FLEET_FILE")
+                .value_hint(ValueHint::FilePath)
+                .global(true),
+        )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .value_name("PROFILE")
+                .global(true),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            Arg::new("quiet")
+                .long("quiet")
+                .action(ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(Arg::new("help").long("help").action(ArgAction::SetTrue))
+        .arg(
+            Arg::new("version")
+                .long("version")
+                .action(ArgAction::SetTrue),
+        )
+        .subcommands(commands.into_iter().map(|name| {
+            let command: Command = Command::new(name);
+
+            if name == "completions" {
+                command.arg(
+                    Arg::new("shell")
+                        .value_parser(clap::value_parser!(Shell))
+                        .required(true),
+                )
+            } else {
+                command.arg(
+                    Arg::new("args")
+                        .num_args(0..)
+                        .trailing_var_arg(true)
+                        .value_hint(ValueHint::Other),
+                )
+            }
+        }))
+}
+
+pub fn print_completions(shell: &str) -> Result<(), String> {
+    let shell: Shell = shell.parse::<Shell>().map_err(|_| {
+        format!(
+            "Unknown shell '{}', use bash, zsh, fish, elvish or powershell.",
+            shell
+        )
+    })?;
+
+    let mut command: Command = gv_cli_command();
+    generate(shell, &mut command, "gv-cli", &mut io::stdout());
+
+    Ok(())
+}
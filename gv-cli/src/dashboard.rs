@@ -0,0 +1,364 @@
+// Live terminal dashboard for a local GhostVault. Everything comes from the same RPCs as the
+// status, rewards and stats commands, the log pane tails GhostVault's own log file.
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame, Terminal,
+};
+use service::{
+    constants::DASHBOARD_LOG_TAIL,
+    file_ops,
+    gv_client_methods::{CLICaller, GVStatus, PendingRewards, StakingDataOverview},
+};
+use std::{
+    io::{self, Stdout},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+#[derive(Default)]
+struct DashboardState {
+    status: Option<GVStatus>,
+    pending: Option<PendingRewards>,
+    overview: Option<StakingDataOverview>,
+    errors: Vec<String>,
+    log_lines: Vec<String>,
+    updated: String,
+}
+
+pub async fn run_dashboard(
+    gv_client: &CLICaller,
+    log_path: PathBuf,
+    refresh: Duration,
+) -> io::Result<()> {
+    enable_raw_mode()?;
+    let mut stdout: Stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal: Terminal<CrosstermBackend<Stdout>> =
+        Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let res: io::Result<()> = dashboard_loop(&mut terminal, gv_client, &log_path, refresh).await;
+
+    // Always hand the terminal back, even when drawing failed
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    res
+}
+
+async fn dashboard_loop(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    gv_client: &CLICaller,
+    log_path: &Path,
+    refresh: Duration,
+) -> io::Result<()> {
+    let mut state: DashboardState = fetch_state(gv_client, log_path).await;
+    let mut last_refresh: Instant = Instant::now();
+
+    loop {
+        terminal.draw(|frame| draw(frame, &state, refresh))?;
+
+        if event::poll(Duration::from_millis(250))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(())
+                    }
+                    KeyCode::Char('r') => {
+                        state = fetch_state(gv_client, log_path).await;
+                        last_refresh = Instant::now();
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if last_refresh.elapsed() >= refresh {
+            state = fetch_state(gv_client, log_path).await;
+            last_refresh = Instant::now();
+        }
+    }
+}
+
+async fn fetch_state(gv_client: &CLICaller, log_path: &Path) -> DashboardState {
+    let mut state: DashboardState = DashboardState::default();
+
+    // A vault that isn't ready answers with its ready state instead, which won't parse
+    match gv_client.call_get_daemon_state().await {
+        Ok(status) => state.status = Some(status),
+        Err(err) => state.errors.push(format!("Status: {}", err)),
+    }
+
+    if state.status.is_some() {
+        match gv_client.call_get_pending_rewards().await {
+            Ok(pending) => state.pending = Some(pending),
+            Err(err) => state.errors.push(format!("Pending rewards: {}", err)),
+        }
+
+        match gv_client.call_get_overview().await {
+            Ok(overview) => state.overview = Some(overview),
+            Err(err) => state.errors.push(format!("Overview: {}", err)),
+        }
+    }
+
+    state.log_lines = match file_ops::tail_file(log_path, DASHBOARD_LOG_TAIL) {
+        Ok(lines) => lines,
+        Err(err) => vec![format!("Unable to read {}: {}", log_path.display(), err)],
+    };
+    state.updated = chrono::Local::now().format("%H:%M:%S").to_string();
+
+    state
+}
+
+fn draw(frame: &mut Frame, state: &DashboardState, refresh: Duration) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(12),
+            Constraint::Length(8),
+            Constraint::Min(5),
+            Constraint::Length(1),
+        ])
+        .split(frame.size());
+
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[0]);
+
+    let middle = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[1]);
+
+    draw_panel(frame, top[0], "Daemon", daemon_lines(state));
+    draw_panel(frame, top[1], "Staking", staking_lines(state));
+    draw_panel(frame, middle[0], "Pending Rewards", pending_lines(state));
+    draw_panel(frame, middle[1], "Earnings", earnings_lines(state));
+    draw_log(frame, rows[2], state);
+
+    let footer: Line = Line::from(vec![
+        Span::styled(" q ", Style::default().add_modifier(Modifier::REVERSED)),
+        Span::raw(" quit  "),
+        Span::styled(" r ", Style::default().add_modifier(Modifier::REVERSED)),
+        Span::raw(format!(
+            " refresh  Updated {} every {}s",
+            state.updated,
+            refresh.as_secs()
+        )),
+    ]);
+    frame.render_widget(Paragraph::new(footer), rows[3]);
+}
+
+fn draw_panel(frame: &mut Frame, area: Rect, title: &str, lines: Vec<Line<'static>>) {
+    let panel: Paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title.to_string()),
+        )
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(panel, area);
+}
+
+fn draw_log(frame: &mut Frame, area: Rect, state: &DashboardState) {
+    // Errors go on top of the log so they can't scroll out of view
+    let visible: usize = area.height.saturating_sub(2) as usize;
+    let mut lines: Vec<Line> = state
+        .errors
+        .iter()
+        .map(|err| Line::styled(err.clone(), Style::default().fg(Color::Red)))
+        .collect();
+
+    let log_rows: usize = visible.saturating_sub(lines.len());
+    let skip: usize = state.log_lines.len().saturating_sub(log_rows);
+    lines.extend(
+        state
+            .log_lines
+            .iter()
+            .skip(skip)
+            .map(|line| Line::styled(line.clone(), log_style(line))),
+    );
+
+    let log: Paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("GhostVault Log"),
+    );
+
+    frame.render_widget(log, area);
+}
+
+fn log_style(line: &str) -> Style {
+    if line.contains("ERROR") {
+        Style::default().fg(Color::Red)
+    } else if line.contains("WARN") {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    }
+}
+
+fn row(label: &str, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            format!("{:<18}", label),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(value),
+    ])
+}
+
+fn yn_row(label: &str, value: &str) -> Line<'static> {
+    let color: Color = if value == "YES" {
+        Color::Green
+    } else {
+        Color::Red
+    };
+
+    Line::from(vec![
+        Span::styled(
+            format!("{:<18}", label),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(value.to_string(), Style::default().fg(color)),
+    ])
+}
+
+fn not_available() -> Vec<Line<'static>> {
+    vec![Line::styled(
+        "Not available",
+        Style::default().fg(Color::DarkGray),
+    )]
+}
+
+fn daemon_lines(state: &DashboardState) -> Vec<Line<'static>> {
+    let status: &GVStatus = match &state.status {
+        Some(status) => status,
+        None => return not_available(),
+    };
+
+    let mut lines: Vec<Line<'static>> = vec![
+        row("GhostVault up", status.uptime.clone()),
+        row("Reward mode", status.privacy_mode.clone()),
+        row(
+            "Ghostd",
+            format!(
+                "{} (latest {})",
+                status.daemon_version, status.latest_release
+            ),
+        ),
+        row("Ghostd up", status.daemon_uptime.clone()),
+        row("Peers", status.daemon_peers.to_string()),
+        yn_row("Synced", &status.daemon_synced),
+        row(
+            "Best block",
+            format!("{} / {}", status.best_block, status.best_block_extern),
+        ),
+        yn_row("Good chain", &status.good_chain),
+        row("Chain check", status.chain_check.clone()),
+    ];
+
+    if let Some(progress) = &status.sync_progress {
+        lines.insert(
+            6,
+            row(
+                "Sync ETA",
+                format!(
+                    "{} ({:.0} blocks/min)",
+                    progress.eta, progress.blocks_per_minute
+                ),
+            ),
+        );
+    }
+
+    lines
+}
+
+fn staking_lines(state: &DashboardState) -> Vec<Line<'static>> {
+    let status: &GVStatus = match &state.status {
+        Some(status) => status,
+        None => return not_available(),
+    };
+
+    vec![
+        yn_row("Staking enabled", &status.staking_enabled),
+        yn_row("Staking", &status.active_staking),
+        row(
+            "Staking weight",
+            format!("{:.8}", status.currently_staking_amount.to_ghost()),
+        ),
+        row(
+            "Coldstaking",
+            format!("{:.8}", status.total_coldstaking_amount.to_ghost()),
+        ),
+        row(
+            "Network weight",
+            format!("{:.8}", status.network_stake_weight_amount.to_ghost()),
+        ),
+        row("Expected stake", status.expected_stake_time.clone()),
+        row("Last stake", status.last_stake.clone()),
+        row("Stakes 24h", status.stakes_24.to_string()),
+        row(
+            "Earned 24h",
+            format!("{:.8}", status.total_24_amount.to_ghost()),
+        ),
+    ]
+}
+
+fn pending_lines(state: &DashboardState) -> Vec<Line<'static>> {
+    let pending: &PendingRewards = match &state.pending {
+        Some(pending) => pending,
+        None => return not_available(),
+    };
+
+    vec![
+        row("Total pending", format!("{:.8}", pending.total_pending)),
+        row("Staked", format!("{:.8}", pending.staked)),
+        row(
+            "Anonymizing",
+            format!("{:.8}", pending.pending_anonymization),
+        ),
+        row("Ready to pay", format!("{:.8}", pending.pending_payout)),
+        row("Min payout", format!("{:.8}", pending.min_payout)),
+        row("Next payout", pending.next_payout_run.clone()),
+    ]
+}
+
+fn earnings_lines(state: &DashboardState) -> Vec<Line<'static>> {
+    let overview: &StakingDataOverview = match &state.overview {
+        Some(overview) => overview,
+        None => return not_available(),
+    };
+
+    [
+        ("24 hours", &overview.stakes_24h),
+        ("7 days", &overview.stakes_7d),
+        ("30 days", &overview.stakes_30d),
+        ("Year to date", &overview.stakes_ytd),
+        ("All time", &overview.stakes_all),
+    ]
+    .into_iter()
+    .map(|(label, totals)| {
+        row(
+            label,
+            format!("{:.8} ({} stakes)", totals.total, totals.stakes),
+        )
+    })
+    .collect()
+}
@@ -0,0 +1,102 @@
+// Integer amount of GHOST in sats. Floats are only ever produced at the edges for display,
+// on the wire an Amount is {"sat": 150000000, "ghost": 1.5} so clients can pick either one
+// and the integer is the one to trust.
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Sub},
+};
+
+pub const SATS_PER_GHOST: u64 = 100_000_000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(u64);
+
+#[derive(Serialize, Deserialize)]
+struct AmountRepr {
+    sat: u64,
+    #[serde(default)]
+    ghost: f64,
+}
+
+impl Amount {
+    pub const ZERO: Amount = Amount(0);
+
+    pub fn from_sat(sat: u64) -> Amount {
+        Amount(sat)
+    }
+
+    // Rounds to the nearest sat, negative values clamp to zero.
+    pub fn from_ghost(ghost: f64) -> Amount {
+        Amount((ghost * SATS_PER_GHOST as f64).round().max(0.0) as u64)
+    }
+
+    pub fn to_sat(self) -> u64 {
+        self.0
+    }
+
+    pub fn to_ghost(self) -> f64 {
+        self.0 as f64 / SATS_PER_GHOST as f64
+    }
+
+    pub fn saturating_sub(self, other: Amount) -> Amount {
+        Amount(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Add for Amount {
+    type Output = Amount;
+
+    fn add(self, other: Amount) -> Amount {
+        Amount(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Amount {
+    fn add_assign(&mut self, other: Amount) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for Amount {
+    type Output = Amount;
+
+    fn sub(self, other: Amount) -> Amount {
+        Amount(self.0 - other.0)
+    }
+}
+
+impl Sum for Amount {
+    fn sum<I: Iterator<Item = Amount>>(iter: I) -> Amount {
+        iter.fold(Amount::ZERO, Add::add)
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{:08}",
+            self.0 / SATS_PER_GHOST,
+            self.0 % SATS_PER_GHOST
+        )
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        AmountRepr {
+            sat: self.0,
+            ghost: self.to_ghost(),
+        }
+        .serialize(serializer)
+    }
+}
+
+// Only the sat field is read back, the float is informational.
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Amount, D::Error> {
+        AmountRepr::deserialize(deserializer).map(|repr| Amount(repr.sat))
+    }
+}
@@ -0,0 +1,361 @@
+// Routing of announcements to their destinations. Every queued bot message has a msg_type,
+// the routable ones map onto an event and each event has its own list of destinations.
+// Events without a stored route go to the Telegram admin, the same as before routing existed.
+use crate::{
+    config::GVConfig,
+    constants::{QUIET_SUMMARY_MAX_NOTICES, SENDMAIL_PATH},
+    gvdb::{HeldNoticeDB, QuietHoursDB, TgBotQueueDB, GVDB},
+};
+use chrono::Timelike;
+use chrono_tz::Tz;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    error::Error,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+pub const ANNOUNCE_EVENTS: &[&str] = &[
+    "offline",
+    "online",
+    "update",
+    "bad_chain",
+    "payout",
+    "deposit",
+    "goal",
+    "incident",
+    "digest",
+    "security",
+];
+
+// Message types that can be given a template, and what their templates can use.
+pub const TEMPLATE_TYPES: &[&str] = &["stake", "zap", "rewards"];
+pub const TEMPLATE_PLACEHOLDERS: &[&str] = &["amount", "txid", "height", "total_24h"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnounceDestination {
+    TelegramAdmin,
+    TelegramChannel,
+    Webhook,
+    Email,
+}
+
+impl AnnounceDestination {
+    pub const ALL: [AnnounceDestination; 4] = [
+        AnnounceDestination::TelegramAdmin,
+        AnnounceDestination::TelegramChannel,
+        AnnounceDestination::Webhook,
+        AnnounceDestination::Email,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnnounceDestination::TelegramAdmin => "telegram_admin",
+            AnnounceDestination::TelegramChannel => "telegram_channel",
+            AnnounceDestination::Webhook => "webhook",
+            AnnounceDestination::Email => "email",
+        }
+    }
+
+    pub fn from_name(dest: &str) -> Option<AnnounceDestination> {
+        AnnounceDestination::ALL
+            .into_iter()
+            .find(|destination| destination.as_str() == dest.trim().to_lowercase())
+    }
+
+    pub fn is_telegram(&self) -> bool {
+        matches!(
+            self,
+            AnnounceDestination::TelegramAdmin | AnnounceDestination::TelegramChannel
+        )
+    }
+}
+
+// Whether a queued message of this type should be sent with the current announce settings.
+pub fn announce_enabled(conf: &GVConfig, msg_type: &str) -> bool {
+    match msg_type {
+        "rewards" => conf.announce_rewards,
+        "stake" | "stake_confirmations" | "stake_matured" | "streak" => conf.announce_stakes,
+        "zap" | "deposit_missed" => conf.announce_zaps,
+        "offline" | "online" | "update" | "bad_chain" | "emergency" | "drought" | "incident"
+        | "disk" | "backup" | "split" | "stake_removal" | "digest" | "sync" | "synced"
+        | "import" | "imported" | "reward_change" | "reward_changed" | "mnemonic_access"
+        | "payout_deferred" | "reindex" => true,
+        _ => false,
+    }
+}
+
+// The event a queued message belongs to, stakes, backup notices and sync progress aren't
+// routable and only ever go to the Telegram admin.
+pub fn event_for_msg_type(msg_type: &str) -> Option<&'static str> {
+    match msg_type {
+        "offline" => Some("offline"),
+        "online" => Some("online"),
+        "update" => Some("update"),
+        "bad_chain" | "emergency" => Some("bad_chain"),
+        "rewards" | "payout_deferred" => Some("payout"),
+        "zap" | "deposit_missed" => Some("deposit"),
+        "streak" => Some("goal"),
+        "drought" | "incident" | "disk" | "reindex" => Some("incident"),
+        "digest" => Some("digest"),
+        "reward_change" | "reward_changed" | "mnemonic_access" => Some("security"),
+        _ => None,
+    }
+}
+
+// Critical alerts go out during quiet hours, so do messages waiting on a decision since their
+// buttons expire. Edits of an existing message don't notify and aren't held either.
+pub fn bypasses_quiet_hours(msg_type: &str) -> bool {
+    matches!(
+        msg_type,
+        "offline"
+            | "bad_chain"
+            | "emergency"
+            | "mnemonic_access"
+            | "reward_change"
+            | "split"
+            | "stake_confirmations"
+            | "stake_removal"
+            | "sync"
+            | "synced"
+            | "import"
+            | "imported"
+    )
+}
+
+// Whether the destination is inside its quiet hours right now, in the vault's TIMEZONE.
+pub fn in_quiet_hours(db: &GVDB, conf: &GVConfig, destination: AnnounceDestination) -> bool {
+    let hours: QuietHoursDB = match db.get_quiet_hours(destination) {
+        Some(hours) => hours,
+        None => return false,
+    };
+
+    let tz: Tz = Tz::from_str_insensitive(&conf.timezone).unwrap_or(Tz::UTC);
+    let hour: u32 = chrono::Utc::now().with_timezone(&tz).hour();
+
+    if hours.start < hours.end {
+        (hours.start..hours.end).contains(&hour)
+    } else {
+        hour >= hours.start || hour < hours.end
+    }
+}
+
+// A window like 22-7 in local hours, "off" turns quiet hours off.
+pub fn parse_quiet_hours(
+    hours: &str,
+) -> Result<Option<QuietHoursDB>, Box<dyn Error + Send + Sync>> {
+    let hours: &str = hours.trim();
+
+    if hours.eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+
+    let (start, end) = hours
+        .split_once('-')
+        .ok_or("Quiet hours look like 22-7, or off")?;
+
+    let parse_hour = |hour: &str| -> Result<u32, Box<dyn Error + Send + Sync>> {
+        match hour.trim().parse::<u32>() {
+            Ok(hour) if hour < 24 => Ok(hour),
+            _ => Err(format!("Invalid hour: {}, use 0 to 23", hour.trim()).into()),
+        }
+    };
+
+    let (start, end) = (parse_hour(start)?, parse_hour(end)?);
+
+    if start == end {
+        return Err("Quiet hours need to start and end at different hours".into());
+    }
+
+    Ok(Some(QuietHoursDB { start, end }))
+}
+
+pub fn held_notice(msg_details: &TgBotQueueDB, prefix: &str) -> HeldNoticeDB {
+    HeldNoticeDB {
+        timestamp: msg_details.timestamp,
+        prefix: prefix.to_string(),
+        header: msg_details.header.trim().to_string(),
+        msg: msg_details.msg.clone(),
+    }
+}
+
+// The notices held during quiet hours as one message, oldest first.
+pub fn quiet_hours_summary(notices: &[HeldNoticeDB]) -> TgBotQueueDB {
+    let mut lines: Vec<String> = notices
+        .iter()
+        .take(QUIET_SUMMARY_MAX_NOTICES)
+        .map(|notice| match &notice.msg {
+            Some(msg) => format!("• {}{}\n{}", notice.prefix, notice.header, msg),
+            None => format!("• {}{}", notice.prefix, notice.header),
+        })
+        .collect();
+
+    if notices.len() > QUIET_SUMMARY_MAX_NOTICES {
+        lines.push(format!(
+            "…and {} more.",
+            notices.len() - QUIET_SUMMARY_MAX_NOTICES
+        ));
+    }
+
+    TgBotQueueDB {
+        timestamp: chrono::Utc::now().timestamp() as u64,
+        header: format!("🌙 While You Were Away: {} Notices 🌙", notices.len()),
+        msg: Some(lines.join("\n\n")),
+        code_block: None,
+        url: None,
+        msg_type: "quiet_summary".to_string(),
+        reward_txid: None,
+        msg_to_delete: None,
+        action_token: None,
+        msg_to_edit: None,
+    }
+}
+
+// What a template's placeholders are filled with, anything a message doesn't have stays empty.
+#[derive(Clone, Debug, Default)]
+pub struct TemplateValues {
+    pub amount: String,
+    pub txid: String,
+    pub height: Option<u32>,
+    pub total_24h: String,
+}
+
+pub fn render_template(template: &str, values: &TemplateValues) -> String {
+    let height: String = values
+        .height
+        .map(|height| height.to_string())
+        .unwrap_or_default();
+
+    template
+        .replace("{amount}", &values.amount)
+        .replace("{txid}", &values.txid)
+        .replace("{height}", &height)
+        .replace("{total_24h}", &values.total_24h)
+}
+
+// Placeholders are checked when a template is set, a typo would otherwise go out as is.
+pub fn check_template(template: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+    for part in template.split('{').skip(1) {
+        let placeholder: &str = part.split('}').next().unwrap_or_default();
+
+        if !part.contains('}') || !TEMPLATE_PLACEHOLDERS.contains(&placeholder) {
+            return Err(format!(
+                "Unknown placeholder {{{}}}, use {}.",
+                placeholder,
+                TEMPLATE_PLACEHOLDERS
+                    .iter()
+                    .map(|placeholder| format!("{{{}}}", placeholder))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+// Comma separated list of destinations, "none" mutes the event.
+pub fn parse_destinations(
+    destinations: &str,
+) -> Result<Vec<AnnounceDestination>, Box<dyn Error + Send + Sync>> {
+    if destinations.trim().eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+
+    let mut parsed: Vec<AnnounceDestination> = Vec::new();
+
+    for dest in destinations
+        .split(',')
+        .filter(|dest| !dest.trim().is_empty())
+    {
+        let destination: AnnounceDestination = AnnounceDestination::from_name(dest)
+            .ok_or(format!("Invalid destination: {}", dest.trim()))?;
+
+        if !parsed.contains(&destination) {
+            parsed.push(destination);
+        }
+    }
+
+    Ok(parsed)
+}
+
+pub async fn send_webhook(
+    conf: &GVConfig,
+    event: &str,
+    msg_details: &TgBotQueueDB,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let webhook_url: &str = conf
+        .announce_webhook_url
+        .as_deref()
+        .ok_or("No webhook URL set")?;
+
+    let payload = json!({
+        "vault": conf.vault_name,
+        "event": event,
+        "timestamp": msg_details.timestamp,
+        "header": msg_details.header,
+        "msg": msg_details.msg,
+        "code_block": msg_details.code_block,
+        "url": msg_details.url,
+    });
+
+    Client::new()
+        .post(webhook_url)
+        .json(&payload)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
+
+// Mail goes out through the local sendmail, relaying is left to the system MTA.
+pub fn send_email(
+    conf: &GVConfig,
+    msg_details: &TgBotQueueDB,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let recipient: &str = conf
+        .announce_email
+        .as_deref()
+        .ok_or("No announcement email set")?;
+
+    let subject: String = format!("[{}] {}", conf.vault_name, msg_details.header.trim());
+    let mut body: String = String::new();
+
+    if let Some(code_block) = &msg_details.code_block {
+        body.push_str(&format!("{}\n\n", code_block));
+    }
+    if let Some(msg) = &msg_details.msg {
+        body.push_str(&format!("{}\n", msg));
+    }
+    for link in msg_details.url.iter().flatten() {
+        body.push_str(&format!("{}\n", link));
+    }
+
+    let mut sendmail = Command::new(SENDMAIL_PATH)
+        .arg("-t")
+        .stdin(Stdio::piped())
+        .spawn()?;
+
+    let mail: String = format!(
+        "To: {}\nSubject: {}\nContent-Type: text/plain; charset=utf-8\n\n{}",
+        recipient, subject, body
+    );
+    sendmail
+        .stdin
+        .take()
+        .ok_or("Unable to write to sendmail")?
+        .write_all(mail.as_bytes())?;
+
+    let status = sendmail.wait()?;
+
+    if !status.success() {
+        return Err(format!("sendmail exited with {}", status).into());
+    }
+
+    Ok(())
+}
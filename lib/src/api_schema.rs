@@ -0,0 +1,745 @@
+// Describes the GvCLI methods along with the permission scope each one requires.
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ApiScope {
+    // Read only queries of state and stats.
+    Read,
+    // Changes to payout and notification settings.
+    Write,
+    // Wallet secrets, wallet management and daemon control.
+    Admin,
+    // Called by GhostVault itself, not meant for integrators.
+    Internal,
+}
+
+impl ApiScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiScope::Read => "read",
+            ApiScope::Write => "write",
+            ApiScope::Admin => "admin",
+            ApiScope::Internal => "internal",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiMethod {
+    pub name: String,
+    pub params: Vec<String>,
+    pub scope: String,
+    pub cli_command: Option<String>,
+}
+
+// (method, params, scope, gv-cli command)
+// Keep this in step with the GvCLI trait when adding methods.
+const API_METHODS: &[(&str, &[&str], ApiScope, Option<&str>)] = &[
+    ("getblockcount", &[], ApiScope::Read, None),
+    ("shutdown", &[], ApiScope::Admin, Some("shutdown")),
+    (
+        "force_resync",
+        &["force: bool"],
+        ApiScope::Admin,
+        Some("forceresync"),
+    ),
+    (
+        "set_reward_mode",
+        &["mode: String", "addr: Option<String>"],
+        ApiScope::Write,
+        Some("setrewardmode"),
+    ),
+    (
+        "set_payout_min",
+        &["min: f64"],
+        ApiScope::Write,
+        Some("setminpayout"),
+    ),
+    ("get_ext_pub_key", &[], ApiScope::Read, Some("extpubkey")),
+    (
+        "set_reward_interval",
+        &["interval: String"],
+        ApiScope::Write,
+        Some("setrewardtime"),
+    ),
+    (
+        "enable_telegram_bot",
+        &["token: String", "user: String"],
+        ApiScope::Admin,
+        Some("enablebot"),
+    ),
+    (
+        "disable_telegram_bot",
+        &[],
+        ApiScope::Admin,
+        Some("disablebot"),
+    ),
+    (
+        "new_block",
+        &["block_hash: String"],
+        ApiScope::Internal,
+        None,
+    ),
+    ("get_daemon_state", &[], ApiScope::Read, Some("status")),
+    (
+        "get_daemon_state_tz",
+        &["timezone: String"],
+        ApiScope::Read,
+        None,
+    ),
+    (
+        "new_wallet_tx",
+        &["txid_and_wal: TxidAndWallet"],
+        ApiScope::Internal,
+        None,
+    ),
+    (
+        "process_daemon_update",
+        &["force: bool"],
+        ApiScope::Admin,
+        Some("updatedaemon"),
+    ),
+    ("process_payouts", &[], ApiScope::Internal, None),
+    ("start_server_tasks", &[], ApiScope::Internal, None),
+    (
+        "set_bot_announce",
+        &["msg_type: String", "new_val: bool"],
+        ApiScope::Write,
+        Some("setbotannounce"),
+    ),
+    ("get_version_info", &[], ApiScope::Read, None),
+    ("check_chain", &[], ApiScope::Read, None),
+    ("get_reward_options", &[], ApiScope::Read, None),
+    ("validate_address", &["addr: String"], ApiScope::Read, None),
+    ("get_daemon_online", &[], ApiScope::Read, None),
+    (
+        "get_stake_barchart_data",
+        &["start: u64", "end: u64", "division: String"],
+        ApiScope::Read,
+        None,
+    ),
+    (
+        "get_earnings_chart_data",
+        &["start: u64", "end: u64"],
+        ApiScope::Read,
+        None,
+    ),
+    (
+        "get_stake_barchart_data_tz",
+        &[
+            "start: u64",
+            "end: u64",
+            "division: String",
+            "timezone: String",
+        ],
+        ApiScope::Read,
+        None,
+    ),
+    (
+        "get_earnings_chart_data_tz",
+        &["start: u64", "end: u64", "timezone: String"],
+        ApiScope::Read,
+        None,
+    ),
+    (
+        "get_stake_barchart_page",
+        &[
+            "start: u64",
+            "end: u64",
+            "division: String",
+            "page: ChartPage",
+        ],
+        ApiScope::Read,
+        None,
+    ),
+    (
+        "get_earnings_chart_page",
+        &["start: u64", "end: u64", "page: ChartPage"],
+        ApiScope::Read,
+        None,
+    ),
+    (
+        "set_timezone",
+        &["timezone: String"],
+        ApiScope::Write,
+        Some("settimezone"),
+    ),
+    ("get_pending_rewards", &[], ApiScope::Read, None),
+    ("get_overview", &[], ApiScope::Read, Some("stats")),
+    (
+        "get_mnemonic",
+        &["passphrase: String"],
+        ApiScope::Admin,
+        Some("getmnemonic"),
+    ),
+    (
+        "import_wallet",
+        &[
+            "mnemonic: String",
+            "name: String",
+            "birthday: Option<String>",
+        ],
+        ApiScope::Admin,
+        Some("importwallet"),
+    ),
+    (
+        "new_remote_block",
+        &["block_hash: String", "height: u32"],
+        ApiScope::Internal,
+        None,
+    ),
+    (
+        "get_payout_history",
+        &["start: u64", "end: u64"],
+        ApiScope::Read,
+        Some("payouthistory"),
+    ),
+    (
+        "rescan_wallet",
+        &["start_height: u32"],
+        ApiScope::Admin,
+        Some("rescanwallet"),
+    ),
+    (
+        "get_rescan_status",
+        &[],
+        ApiScope::Read,
+        Some("rescanstatus"),
+    ),
+    ("process_wallet_rescan", &[], ApiScope::Internal, None),
+    (
+        "create_hot_wallet",
+        &["name: String"],
+        ApiScope::Admin,
+        Some("createhotwallet"),
+    ),
+    (
+        "link_hot_wallet",
+        &["name: String"],
+        ApiScope::Admin,
+        Some("linkhotwallet"),
+    ),
+    (
+        "get_hot_wallet_info",
+        &[],
+        ApiScope::Read,
+        Some("hotwalletinfo"),
+    ),
+    (
+        "get_hot_wallet_script",
+        &[],
+        ApiScope::Write,
+        Some("hotwalletscript"),
+    ),
+    ("describe_api", &[], ApiScope::Read, Some("describeapi")),
+    (
+        "set_wallet_passphrase",
+        &["passphrase: String"],
+        ApiScope::Admin,
+        Some("setwalletpassphrase"),
+    ),
+    (
+        "unlock_wallet",
+        &["passphrase: String"],
+        ApiScope::Admin,
+        Some("unlockwallet"),
+    ),
+    (
+        "unlock_for_payout",
+        &["passphrase: String"],
+        ApiScope::Admin,
+        Some("unlockforpayout"),
+    ),
+    (
+        "get_wallet_lock_status",
+        &[],
+        ApiScope::Read,
+        Some("walletlockstatus"),
+    ),
+    (
+        "export_parquet",
+        &["out_dir: String"],
+        ApiScope::Read,
+        Some("exportparquet"),
+    ),
+    (
+        "create_backup",
+        &["path: String"],
+        ApiScope::Admin,
+        Some("createbackup"),
+    ),
+    (
+        "restore_backup",
+        &["path: String", "passphrase: String"],
+        ApiScope::Admin,
+        Some("restorebackup"),
+    ),
+    ("process_auto_backup", &[], ApiScope::Internal, None),
+    (
+        "migrate_export",
+        &["path: String"],
+        ApiScope::Admin,
+        Some("migrateexport"),
+    ),
+    (
+        "migrate_import",
+        &["path: String", "passphrase: String", "force: bool"],
+        ApiScope::Admin,
+        Some("migrateimport"),
+    ),
+    (
+        "confirm_update",
+        &["token: String"],
+        ApiScope::Admin,
+        Some("confirmupdate"),
+    ),
+    ("take_bot_messages", &[], ApiScope::Internal, None),
+    (
+        "get_chain_emergency",
+        &[],
+        ApiScope::Read,
+        Some("chainemergency"),
+    ),
+    (
+        "chain_emergency_action",
+        &["action: String"],
+        ApiScope::Admin,
+        Some("chainaction"),
+    ),
+    (
+        "get_announce_routes",
+        &[],
+        ApiScope::Read,
+        Some("announceroutes"),
+    ),
+    (
+        "set_announce_route",
+        &["event: String", "destinations: String"],
+        ApiScope::Write,
+        Some("setannounceroute"),
+    ),
+    ("get_quiet_hours", &[], ApiScope::Read, Some("quiethours")),
+    (
+        "set_quiet_hours",
+        &["destination: String", "hours: String"],
+        ApiScope::Write,
+        Some("setquiethours"),
+    ),
+    (
+        "get_announce_templates",
+        &[],
+        ApiScope::Read,
+        Some("templates"),
+    ),
+    (
+        "set_announce_template",
+        &["msg_type: String", "body: String", "header: String"],
+        ApiScope::Write,
+        Some("settemplate"),
+    ),
+    (
+        "get_explorer_health",
+        &[],
+        ApiScope::Read,
+        Some("explorerhealth"),
+    ),
+    ("get_chain_tip", &[], ApiScope::Read, Some("chaintip")),
+    ("get_block_hash_at", &["height: u32"], ApiScope::Read, None),
+    (
+        "get_stake_forecast",
+        &[],
+        ApiScope::Read,
+        Some("stakeforecast"),
+    ),
+    (
+        "get_stake_heatmap_data",
+        &["start: u64", "end: u64"],
+        ApiScope::Read,
+        None,
+    ),
+    (
+        "get_stake_heatmap_data_tz",
+        &["start: u64", "end: u64", "timezone: String"],
+        ApiScope::Read,
+        None,
+    ),
+    ("get_roi", &[], ApiScope::Read, Some("roi")),
+    ("process_balance_sample", &[], ApiScope::Internal, None),
+    (
+        "get_balance_history",
+        &["start: u64", "end: u64"],
+        ApiScope::Read,
+        None,
+    ),
+    (
+        "get_stakes_by_address",
+        &["start: u64", "end: u64"],
+        ApiScope::Read,
+        Some("stakesbyaddress"),
+    ),
+    (
+        "get_stakes_by_address_csv",
+        &["start: u64", "end: u64"],
+        ApiScope::Read,
+        Some("stakesbyaddresscsv"),
+    ),
+    (
+        "register_tenant",
+        &["name: String", "key: String", "payout_address: String"],
+        ApiScope::Write,
+        Some("registertenant"),
+    ),
+    (
+        "remove_tenant",
+        &["name: String"],
+        ApiScope::Write,
+        Some("removetenant"),
+    ),
+    ("get_tenants", &[], ApiScope::Read, Some("tenants")),
+    (
+        "get_tenant_statement",
+        &["name: String", "start: u64", "end: u64"],
+        ApiScope::Read,
+        Some("tenantstatement"),
+    ),
+    (
+        "set_fee_policy",
+        &[
+            "policy: String",
+            "value: Option<f64>",
+            "max_fee: Option<f64>",
+        ],
+        ApiScope::Write,
+        Some("setfeepolicy"),
+    ),
+    (
+        "list_utxos",
+        &[
+            "wallet: String",
+            "utxo_type: String",
+            "min_amount: f64",
+            "max_amount: f64",
+            "min_confirmations: u64",
+        ],
+        ApiScope::Read,
+        Some("listutxos"),
+    ),
+    (
+        "freeze_utxo",
+        &["wallet: String", "txid: String", "vout: u32"],
+        ApiScope::Write,
+        Some("freezeutxo"),
+    ),
+    (
+        "unfreeze_utxo",
+        &["wallet: String", "txid: String", "vout: u32"],
+        ApiScope::Write,
+        Some("unfreezeutxo"),
+    ),
+    (
+        "consolidate_utxos",
+        &["wallet: String", "max_amount: f64", "max_fee_rate: f64"],
+        ApiScope::Write,
+        Some("consolidate"),
+    ),
+    ("split_utxos", &[], ApiScope::Write, Some("splitutxos")),
+    (
+        "confirm_split",
+        &["token: String"],
+        ApiScope::Write,
+        Some("confirmsplit"),
+    ),
+    (
+        "get_events_since",
+        &["cursor: u64", "wait: u64"],
+        ApiScope::Read,
+        Some("events"),
+    ),
+    ("get_digest_settings", &[], ApiScope::Read, Some("digest")),
+    (
+        "set_digest",
+        &["period: String", "enabled: bool"],
+        ApiScope::Write,
+        Some("setdigest"),
+    ),
+    (
+        "set_digest_time",
+        &["hour: u32"],
+        ApiScope::Write,
+        Some("setdigesttime"),
+    ),
+    ("process_digest", &[], ApiScope::Internal, None),
+    (
+        "lookup_tx",
+        &["txid: String"],
+        ApiScope::Read,
+        Some("lookuptx"),
+    ),
+    (
+        "lookup_block",
+        &["block: String"],
+        ApiScope::Read,
+        Some("lookupblock"),
+    ),
+    (
+        "install_daemon_archive",
+        &["path: String"],
+        ApiScope::Admin,
+        Some("installdaemon"),
+    ),
+    (
+        "pin_daemon_version",
+        &["version: String"],
+        ApiScope::Admin,
+        Some("pindaemon"),
+    ),
+    (
+        "get_maintenance_window",
+        &[],
+        ApiScope::Read,
+        Some("maintenancewindow"),
+    ),
+    (
+        "get_operation_status",
+        &["op_id: String"],
+        ApiScope::Read,
+        Some("operationstatus"),
+    ),
+    ("get_jobs", &["limit: u64"], ApiScope::Read, Some("jobs")),
+    ("get_scheduled_tasks", &[], ApiScope::Read, Some("tasks")),
+    (
+        "run_task_now",
+        &["name: String"],
+        ApiScope::Write,
+        Some("runtask"),
+    ),
+    (
+        "pause_task",
+        &["name: String", "paused: bool"],
+        ApiScope::Write,
+        Some("pausetask"),
+    ),
+    (
+        "set_maintenance_window",
+        &["start: u32", "end: u32"],
+        ApiScope::Write,
+        Some("setmaintenancewindow"),
+    ),
+    ("process_maintenance", &[], ApiScope::Internal, None),
+    ("get_system_stats", &[], ApiScope::Read, Some("systemstats")),
+    (
+        "db_maintenance",
+        &[],
+        ApiScope::Admin,
+        Some("dbmaintenance"),
+    ),
+    (
+        "set_daemon_prune",
+        &["size_mb: u64"],
+        ApiScope::Admin,
+        Some("setprune"),
+    ),
+    (
+        "bootstrap_sync",
+        &[],
+        ApiScope::Admin,
+        Some("bootstrapsync"),
+    ),
+    (
+        "get_bootstrap_status",
+        &[],
+        ApiScope::Read,
+        Some("bootstrapstatus"),
+    ),
+    (
+        "get_restart_history",
+        &["limit: u64"],
+        ApiScope::Read,
+        Some("restarthistory"),
+    ),
+    (
+        "set_log_level",
+        &["level: String", "module: String"],
+        ApiScope::Admin,
+        Some("setloglevel"),
+    ),
+    (
+        "get_logs",
+        &["lines: u64", "level_filter: String", "daemon: bool"],
+        ApiScope::Admin,
+        Some("getlogs"),
+    ),
+    ("set_origin", &["origin: String"], ApiScope::Internal, None),
+    (
+        "get_audit_log",
+        &["limit: u64"],
+        ApiScope::Admin,
+        Some("auditlog"),
+    ),
+    (
+        "get_pending_reward_change",
+        &[],
+        ApiScope::Read,
+        Some("pendingrewardchange"),
+    ),
+    (
+        "cancel_reward_change",
+        &[],
+        ApiScope::Write,
+        Some("cancelrewardchange"),
+    ),
+    (
+        "seal_mnemonic",
+        &["passphrase: String"],
+        ApiScope::Admin,
+        Some("sealmnemonic"),
+    ),
+    ("reload_config", &[], ApiScope::Admin, Some("reloadconfig")),
+    (
+        "simulate_rewards",
+        &["mode: Option<String>", "days: u64"],
+        ApiScope::Read,
+        Some("simulaterewards"),
+    ),
+    (
+        "get_network_stats",
+        &["start: u64", "end: u64"],
+        ApiScope::Read,
+        Some("networkstats"),
+    ),
+    (
+        "list_cold_wallets",
+        &[],
+        ApiScope::Read,
+        Some("coldwallets"),
+    ),
+    (
+        "create_cold_wallet",
+        &["name: String"],
+        ApiScope::Admin,
+        Some("createcoldwallet"),
+    ),
+    (
+        "import_cold_wallet",
+        &["name: String", "mnemonic: String"],
+        ApiScope::Admin,
+        Some("importcoldwallet"),
+    ),
+    (
+        "switch_cold_wallet",
+        &["name: String"],
+        ApiScope::Admin,
+        Some("switchcoldwallet"),
+    ),
+    (
+        "get_cold_wallet_stakes",
+        &["name: String", "start: u64", "end: u64", "division: String"],
+        ApiScope::Read,
+        Some("coldwalletstakes"),
+    ),
+    ("repair_wallet", &[], ApiScope::Admin, Some("repairwallet")),
+    (
+        "reindex_history",
+        &[],
+        ApiScope::Admin,
+        Some("reindexhistory"),
+    ),
+    ("get_delegations", &[], ApiScope::Read, Some("delegations")),
+    (
+        "build_zap_request",
+        &["amount: f64"],
+        ApiScope::Admin,
+        Some("zaprequest"),
+    ),
+    (
+        "add_expected_deposit",
+        &["amount: f64", "memo: String", "hours: Option<u64>"],
+        ApiScope::Admin,
+        Some("expectdeposit"),
+    ),
+    (
+        "get_expected_deposits",
+        &[],
+        ApiScope::Read,
+        Some("expecteddeposits"),
+    ),
+    (
+        "remove_expected_deposit",
+        &["id: u64"],
+        ApiScope::Admin,
+        Some("removeexpecteddeposit"),
+    ),
+    (
+        "get_anonymization_status",
+        &[],
+        ApiScope::Read,
+        Some("anonstatus"),
+    ),
+    (
+        "set_anon_policy",
+        &[
+            "enabled: bool",
+            "threshold: Option<f64>",
+            "interval: Option<u64>",
+            "ring_size: Option<u32>",
+            "batch_size: Option<u64>",
+        ],
+        ApiScope::Write,
+        Some("setanonpolicy"),
+    ),
+    (
+        "set_tx_label",
+        &["txid: String", "label: String"],
+        ApiScope::Write,
+        Some("settxlabel"),
+    ),
+    ("get_tx_labels", &[], ApiScope::Read, Some("txlabels")),
+    (
+        "set_address_label",
+        &["address: String", "label: String"],
+        ApiScope::Write,
+        Some("setaddresslabel"),
+    ),
+    ("get_address_book", &[], ApiScope::Read, Some("addressbook")),
+    (
+        "get_api_capabilities",
+        &["client_api_version: u32"],
+        ApiScope::Read,
+        Some("capabilities"),
+    ),
+];
+
+// gv-cli commands that call a GvCLI method, for generating shell completions.
+pub fn cli_commands() -> Vec<&'static str> {
+    API_METHODS
+        .iter()
+        .filter_map(|(_, _, _, cli_command)| *cli_command)
+        .collect()
+}
+
+// The GvCLI method behind a gv-cli command.
+pub fn method_for_command(command: &str) -> Option<&'static str> {
+    API_METHODS
+        .iter()
+        .find(|(_, _, _, cli_command)| *cli_command == Some(command))
+        .map(|(name, _, _, _)| *name)
+}
+
+pub fn method_names() -> Vec<String> {
+    API_METHODS
+        .iter()
+        .map(|(name, _, _, _)| name.to_string())
+        .collect()
+}
+
+pub fn describe_api() -> Vec<ApiMethod> {
+    API_METHODS
+        .iter()
+        .map(|(name, params, scope, cli_command)| ApiMethod {
+            name: name.to_string(),
+            params: params.iter().map(|param| param.to_string()).collect(),
+            scope: scope.as_str().to_string(),
+            cli_command: cli_command.map(|cmd| cmd.to_string()),
+        })
+        .collect()
+}
@@ -0,0 +1,522 @@
+// Encrypted backups of the GhostVault config, database and wallet metadata.
+// An archive is BACKUP_MAGIC, the 16 byte config salt, then the sealed tar.gz, so it can be
+// restored on a fresh machine with nothing more than the config passphrase. Migration bundles
+// use the same layout under MIGRATE_MAGIC, minus the config keys that belong to the machine.
+use crate::{
+    config,
+    constants::{DEFAULT_DB_DIR, GV_SETTINGS_FILE},
+    file_ops,
+    gvdb::GVDB,
+};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sled::Db;
+use std::{
+    error::Error,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
+use tar::{Archive, Builder, Header};
+
+const BACKUP_MAGIC: &[u8] = b"GVBACKUP1";
+const BACKUP_EXTENSION: &str = "gvbak";
+const RESTORE_STAGING_DIR: &str = "restore_pending/";
+const DB_EXPORT_FILE: &str = "database.json";
+const WALLET_META_FILE: &str = "wallet_meta.json";
+const BACKUP_INFO_FILE: &str = "backup_info.json";
+const MIGRATE_MAGIC: &[u8] = b"GVMIGRATE1";
+const MIGRATE_EXTENSION: &str = "gvmig";
+const MIGRATE_INFO_FILE: &str = "migrate_info.json";
+
+// Paths, binds and the ghostd install describe the machine rather than the vault, they are left
+// out of a migration bundle and kept from the local config on import.
+const MACHINE_CONFIG_KEYS: [&str; 12] = [
+    "DAEMON_PATH",
+    "DAEMON_HASH",
+    "SYSTEM_DAEMON",
+    "DAEMON_PRUNE",
+    "DB_PATH",
+    "BACKUP_DIR",
+    "BACKUP_SFTP_KEY",
+    "CLI_ADDRESS",
+    "CLI_TLS_CERT",
+    "CLI_TLS_KEY",
+    "GRPC_ADDRESS",
+    "EVENTS_ADDRESS",
+];
+
+type DbExport = Vec<(Vec<u8>, Vec<u8>, Vec<Vec<Vec<u8>>>)>;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WalletMeta {
+    pub cold_wallet: String,
+    pub hot_wallet: String,
+    pub ext_pub_key: Option<String>,
+    pub ext_pub_key_label: Option<String>,
+    pub reward_address: Option<String>,
+    pub anon_mode: bool,
+    pub anon_reward_address: Option<String>,
+    pub wallet_info: Option<Value>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub path: String,
+    pub created: u64,
+    pub version: String,
+    pub size: u64,
+    pub remote: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MigrateInfo {
+    pub path: String,
+    pub created: u64,
+    pub version: String,
+    pub vault_name: String,
+    pub size: u64,
+    pub stripped_keys: Vec<String>,
+}
+
+fn append_file(
+    builder: &mut Builder<GzEncoder<Vec<u8>>>,
+    name: &str,
+    data: &[u8],
+    created: u64,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut header: Header = Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o600);
+    header.set_mtime(created);
+    header.set_cksum();
+
+    builder.append_data(&mut header, name, data)?;
+
+    Ok(())
+}
+
+fn archive_file_path(path: &Path, created: u64, prefix: &str, extension: &str) -> PathBuf {
+    if path.extension().is_some_and(|ext| ext == extension) {
+        return path.to_path_buf();
+    }
+
+    let date: String = chrono::DateTime::from_timestamp(created as i64, 0)
+        .unwrap_or_default()
+        .format("%Y%m%d-%H%M%S")
+        .to_string();
+
+    path.join(format!("{}-{}.{}", prefix, date, extension))
+}
+
+fn export_db(db: &GVDB) -> DbExport {
+    db.gvdb
+        .export()
+        .into_iter()
+        .map(|(kind, name, items)| (kind, name, items.collect()))
+        .collect()
+}
+
+// Seal the archive and write it out as magic, salt, sealed data. Returns the size written.
+fn write_archive(
+    magic: &[u8],
+    salt: &[u8],
+    archive: &[u8],
+    path: &Path,
+) -> Result<u64, Box<dyn Error + Send + Sync>> {
+    let sealed: Vec<u8> = config::seal_bytes(archive)?;
+
+    let mut contents: Vec<u8> = magic.to_vec();
+    contents.extend_from_slice(salt);
+    contents.extend_from_slice(&sealed);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Write then rename so a crash never leaves a truncated archive behind.
+    let tmp_path: PathBuf = path.with_extension("tmp");
+    fs::write(&tmp_path, &contents)?;
+    fs::rename(&tmp_path, path)?;
+
+    Ok(contents.len() as u64)
+}
+
+pub fn create_backup(
+    gv_home: &Path,
+    db: &GVDB,
+    wallet_meta: &WalletMeta,
+    path: &Path,
+) -> Result<BackupInfo, Box<dyn Error + Send + Sync>> {
+    let salt: Vec<u8> = config::config_salt(gv_home).ok_or(
+        "Set a config passphrase with 'ghostvaultd --set-passphrase' to enable encrypted backups",
+    )?;
+
+    let created: u64 = chrono::Utc::now().timestamp() as u64;
+    let backup_path: PathBuf = archive_file_path(path, created, "ghostvault", BACKUP_EXTENSION);
+
+    let db_export: DbExport = export_db(db);
+
+    let mut info: BackupInfo = BackupInfo {
+        path: backup_path.display().to_string(),
+        created,
+        version: crate::constants::VERSION.to_string(),
+        size: 0,
+        remote: None,
+    };
+
+    let mut builder: Builder<GzEncoder<Vec<u8>>> =
+        Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+
+    let settings: Vec<u8> = fs::read(config::settings_path(gv_home))?;
+    append_file(&mut builder, GV_SETTINGS_FILE, &settings, created)?;
+    append_file(
+        &mut builder,
+        DB_EXPORT_FILE,
+        &serde_json::to_vec(&db_export)?,
+        created,
+    )?;
+    append_file(
+        &mut builder,
+        WALLET_META_FILE,
+        &serde_json::to_vec_pretty(wallet_meta)?,
+        created,
+    )?;
+    append_file(
+        &mut builder,
+        BACKUP_INFO_FILE,
+        &serde_json::to_vec_pretty(&info)?,
+        created,
+    )?;
+
+    let archive: Vec<u8> = builder.into_inner()?.finish()?;
+
+    info.size = write_archive(BACKUP_MAGIC, &salt, &archive, &backup_path)?;
+    info!("Backup written to {}", info.path);
+
+    Ok(info)
+}
+
+// Keep the newest `keep` backups in `dir`, returns how many were removed.
+pub fn prune_backups(dir: &Path, keep: u64) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let mut backups: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == BACKUP_EXTENSION))
+        .collect();
+
+    // The timestamp in the name sorts oldest first.
+    backups.sort();
+
+    let remove_count: usize = backups.len().saturating_sub(keep as usize);
+
+    for backup in backups.iter().take(remove_count) {
+        fs::remove_file(backup)?;
+        info!("Removed old backup {}", backup.display());
+    }
+
+    Ok(remove_count)
+}
+
+fn open_archive(
+    magic: &[u8],
+    path: &Path,
+    passphrase: &str,
+) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    let contents: Vec<u8> = fs::read(path)?;
+
+    let body: &[u8] = match contents.strip_prefix(magic) {
+        Some(body) => body,
+        None if magic == MIGRATE_MAGIC => return Err("Not a GhostVault migration bundle".into()),
+        None => return Err("Not a GhostVault backup file".into()),
+    };
+
+    if body.len() < 16 {
+        return Err("Archive is truncated".into());
+    }
+
+    let (salt, sealed) = body.split_at(16);
+
+    config::open_bytes(passphrase, salt, sealed)
+}
+
+// Decrypt a backup into the staging dir, it is applied on the next start before the
+// database is opened.
+pub fn stage_restore(
+    gv_home: &Path,
+    path: &Path,
+    passphrase: &str,
+) -> Result<BackupInfo, Box<dyn Error + Send + Sync>> {
+    let staging_dir: PathBuf = unpack_staged(
+        gv_home,
+        BACKUP_MAGIC,
+        path,
+        passphrase,
+        &[GV_SETTINGS_FILE, DB_EXPORT_FILE, BACKUP_INFO_FILE],
+    )?;
+
+    let info: BackupInfo = serde_json::from_slice(&fs::read(staging_dir.join(BACKUP_INFO_FILE))?)?;
+    info!("Backup from {} staged for restore", info.path);
+
+    Ok(info)
+}
+
+// Decrypt an archive into the staging dir, checking the files a restore needs are there.
+fn unpack_staged(
+    gv_home: &Path,
+    magic: &[u8],
+    path: &Path,
+    passphrase: &str,
+    required: &[&str],
+) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+    let archive: Vec<u8> = open_archive(magic, path, passphrase)?;
+
+    let staging_dir: PathBuf = gv_home.join(RESTORE_STAGING_DIR);
+    if staging_dir.exists() {
+        file_ops::rm_dir(&staging_dir)?;
+    }
+    fs::create_dir_all(&staging_dir)?;
+
+    Archive::new(GzDecoder::new(archive.as_slice())).unpack(&staging_dir)?;
+
+    for name in required {
+        if !staging_dir.join(name).exists() {
+            file_ops::rm_dir(&staging_dir)?;
+            return Err(format!("Archive is missing {}", name).into());
+        }
+    }
+
+    Ok(staging_dir)
+}
+
+// Drop a staged restore or migration that shouldn't be applied.
+pub fn discard_staged(gv_home: &Path) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let staging_dir: PathBuf = gv_home.join(RESTORE_STAGING_DIR);
+
+    if staging_dir.exists() {
+        file_ops::rm_dir(&staging_dir)?;
+    }
+
+    Ok(())
+}
+
+// Write a bundle to move this vault to another machine: the config without MACHINE_CONFIG_KEYS,
+// the whole database including the reward history, and the wallet metadata used to spot a
+// different wallet on the other side.
+pub fn create_migration(
+    gv_home: &Path,
+    db: &GVDB,
+    wallet_meta: &WalletMeta,
+    vault_name: &str,
+    path: &Path,
+) -> Result<MigrateInfo, Box<dyn Error + Send + Sync>> {
+    let salt: Vec<u8> = config::config_salt(gv_home).ok_or(
+        "Set a config passphrase with 'ghostvaultd --set-passphrase' to export a migration bundle",
+    )?;
+
+    let created: u64 = chrono::Utc::now().timestamp() as u64;
+    let bundle_path: PathBuf =
+        archive_file_path(path, created, "ghostvault-migrate", MIGRATE_EXTENSION);
+
+    let mut settings: toml::Value =
+        toml::from_str(&fs::read_to_string(config::settings_path(gv_home))?)?;
+    let settings_table = settings
+        .as_table_mut()
+        .ok_or("GhostVault settings are malformed")?;
+
+    let stripped_keys: Vec<String> = MACHINE_CONFIG_KEYS
+        .iter()
+        .filter(|key| settings_table.remove(**key).is_some())
+        .map(|key| key.to_string())
+        .collect();
+
+    let mut info: MigrateInfo = MigrateInfo {
+        path: bundle_path.display().to_string(),
+        created,
+        version: crate::constants::VERSION.to_string(),
+        vault_name: vault_name.to_string(),
+        size: 0,
+        stripped_keys,
+    };
+
+    let mut builder: Builder<GzEncoder<Vec<u8>>> =
+        Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+
+    append_file(
+        &mut builder,
+        GV_SETTINGS_FILE,
+        toml::to_string_pretty(&settings)?.as_bytes(),
+        created,
+    )?;
+    append_file(
+        &mut builder,
+        DB_EXPORT_FILE,
+        &serde_json::to_vec(&export_db(db))?,
+        created,
+    )?;
+    append_file(
+        &mut builder,
+        WALLET_META_FILE,
+        &serde_json::to_vec_pretty(wallet_meta)?,
+        created,
+    )?;
+    append_file(
+        &mut builder,
+        MIGRATE_INFO_FILE,
+        &serde_json::to_vec_pretty(&info)?,
+        created,
+    )?;
+
+    let archive: Vec<u8> = builder.into_inner()?.finish()?;
+
+    info.size = write_archive(MIGRATE_MAGIC, &salt, &archive, &bundle_path)?;
+    info!("Migration bundle written to {}", info.path);
+
+    Ok(info)
+}
+
+// Decrypt a migration bundle into the staging dir with this machine's MACHINE_CONFIG_KEYS put
+// back into its config, so it applies on the next start like a restore. The caller checks the
+// returned wallet metadata against the local wallet and discards the stage on a conflict.
+pub fn stage_migration(
+    gv_home: &Path,
+    path: &Path,
+    passphrase: &str,
+) -> Result<(MigrateInfo, WalletMeta), Box<dyn Error + Send + Sync>> {
+    let staging_dir: PathBuf = unpack_staged(
+        gv_home,
+        MIGRATE_MAGIC,
+        path,
+        passphrase,
+        &[
+            GV_SETTINGS_FILE,
+            DB_EXPORT_FILE,
+            WALLET_META_FILE,
+            MIGRATE_INFO_FILE,
+        ],
+    )?;
+
+    let merge_res = merge_machine_keys(gv_home, &staging_dir.join(GV_SETTINGS_FILE));
+    if let Err(err) = merge_res {
+        file_ops::rm_dir(&staging_dir)?;
+        return Err(err);
+    }
+
+    let info: MigrateInfo =
+        serde_json::from_slice(&fs::read(staging_dir.join(MIGRATE_INFO_FILE))?)?;
+    let wallet_meta: WalletMeta =
+        serde_json::from_slice(&fs::read(staging_dir.join(WALLET_META_FILE))?)?;
+    info!("Migration bundle from {} staged", info.vault_name);
+
+    Ok((info, wallet_meta))
+}
+
+fn merge_machine_keys(gv_home: &Path, staged: &Path) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let local: toml::Value = toml::from_str(&fs::read_to_string(config::settings_path(gv_home))?)?;
+    let mut settings: toml::Value = toml::from_str(&fs::read_to_string(staged)?)?;
+    let settings_table = settings
+        .as_table_mut()
+        .ok_or("Migrated settings are malformed")?;
+
+    for key in MACHINE_CONFIG_KEYS {
+        match local.get(key) {
+            Some(value) => settings_table.insert(key.to_string(), value.clone()),
+            None => settings_table.remove(key),
+        };
+    }
+
+    fs::write(staged, toml::to_string_pretty(&settings)?)?;
+
+    Ok(())
+}
+
+// Why a bundle's wallet can't be the local one, empty when they match or can't be told apart.
+// The HD seed id changes with the mnemonic, the ext pub key with the cold staking setup.
+pub fn migration_conflicts(local: &WalletMeta, bundle: &WalletMeta) -> Vec<String> {
+    let mut conflicts: Vec<String> = Vec::new();
+
+    let seed_id = |meta: &WalletMeta| -> Option<String> {
+        meta.wallet_info
+            .as_ref()
+            .and_then(|info| info.get("hdseedid"))
+            .and_then(|seed_id| seed_id.as_str())
+            .map(|seed_id| seed_id.to_string())
+    };
+
+    if let (Some(local_seed), Some(bundle_seed)) = (seed_id(local), seed_id(bundle)) {
+        if local_seed != bundle_seed {
+            conflicts.push(format!(
+                "Wallet {} was created from a different mnemonic than the exported wallet {}",
+                local.cold_wallet, bundle.cold_wallet
+            ));
+        }
+    }
+
+    if let (Some(local_key), Some(bundle_key)) = (&local.ext_pub_key, &bundle.ext_pub_key) {
+        if !local_key.is_empty() && !bundle_key.is_empty() && local_key != bundle_key {
+            conflicts
+                .push("The cold staking ext pub key differs from the exported one".to_string());
+        }
+    }
+
+    conflicts
+}
+
+// Swap in a staged restore, the current config and database are kept with a .pre_restore
+// suffix in case the backup turns out to be the wrong one.
+pub fn apply_pending_restore(gv_home: &Path) -> Result<bool, Box<dyn Error + Send + Sync>> {
+    let staging_dir: PathBuf = gv_home.join(RESTORE_STAGING_DIR);
+
+    if !staging_dir.exists() {
+        return Ok(false);
+    }
+
+    info!("Restoring GhostVault from backup...");
+
+    let settings_path: PathBuf = config::settings_path(gv_home);
+    if settings_path.exists() {
+        fs::rename(
+            &settings_path,
+            settings_path.with_extension("toml.pre_restore"),
+        )?;
+    }
+    fs::copy(staging_dir.join(GV_SETTINGS_FILE), &settings_path)?;
+
+    let settings: toml::Value = toml::from_str(&fs::read_to_string(&settings_path)?)?;
+    let db_dir: PathBuf = match settings.get("DB_PATH").and_then(|path| path.as_str()) {
+        Some(path) if !path.is_empty() => file_ops::expand_user(path),
+        _ => gv_home.join(DEFAULT_DB_DIR),
+    };
+
+    if db_dir.exists() {
+        let db_name: String = db_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let old_db_dir: PathBuf = db_dir.with_file_name(format!("{}.pre_restore", db_name));
+        if old_db_dir.exists() {
+            file_ops::rm_dir(&old_db_dir)?;
+        }
+        fs::rename(&db_dir, &old_db_dir)?;
+    }
+
+    let mut db_json: Vec<u8> = Vec::new();
+    fs::File::open(staging_dir.join(DB_EXPORT_FILE))?.read_to_end(&mut db_json)?;
+    let db_export: DbExport = serde_json::from_slice(&db_json)?;
+
+    let db: Db = sled::Config::new().path(&db_dir).open()?;
+    db.import(
+        db_export
+            .into_iter()
+            .map(|(kind, name, items)| (kind, name, items.into_iter()))
+            .collect(),
+    );
+    db.flush()?;
+    drop(db);
+
+    file_ops::rm_dir(&staging_dir)?;
+    info!("Restore complete!");
+
+    Ok(true)
+}
@@ -0,0 +1,169 @@
+// TLS for the tarpc transport. A vault serves TLS once CLI_TLS_CERT and CLI_TLS_KEY are set,
+// clients only use it for addresses whose certificate they were given, so vaults without TLS
+// keep working. The certificates are self-signed and pinned, there is no CA to check against.
+use crate::{
+    config::GVConfig,
+    constants::{CLI_TLS_CERT_FILE, CLI_TLS_DIR, CLI_TLS_KEY_FILE},
+    file_ops,
+};
+use log::warn;
+use rustls::{
+    pki_types::{CertificateDer, PrivateKeyDer, ServerName},
+    ClientConfig, RootCertStore, ServerConfig,
+};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::{self, File},
+    io::{BufReader, Write},
+    os::unix::fs::OpenOptionsExt,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+};
+use tokio::net::TcpStream;
+use tokio_rustls::{client, server, TlsAcceptor, TlsConnector};
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+// cli_address -> connector trusting that vault's certificate.
+fn trusted_vaults() -> &'static Mutex<HashMap<String, TlsConnector>> {
+    static TRUSTED: OnceLock<Mutex<HashMap<String, TlsConnector>>> = OnceLock::new();
+    TRUSTED.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn read_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, Box<dyn Error + Send + Sync>> {
+    let file: File = File::open(path)
+        .map_err(|err| format!("Unable to read certificate {}: {}", path.display(), err))?;
+    let certs: Vec<CertificateDer<'static>> =
+        rustls_pemfile::certs(&mut BufReader::new(file)).collect::<Result<_, _>>()?;
+
+    if certs.is_empty() {
+        return Err(format!("No certificate found in {}", path.display()).into());
+    }
+
+    Ok(certs)
+}
+
+fn read_key(path: &Path) -> Result<PrivateKeyDer<'static>, Box<dyn Error + Send + Sync>> {
+    let file: File = File::open(path)
+        .map_err(|err| format!("Unable to read private key {}: {}", path.display(), err))?;
+
+    rustls_pemfile::private_key(&mut BufReader::new(file))?
+        .ok_or_else(|| format!("No private key found in {}", path.display()).into())
+}
+
+// Connections to cli_address will use TLS and only accept the certificate at cert_path.
+pub fn trust(cli_address: &str, cert_path: &Path) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut roots: RootCertStore = RootCertStore::empty();
+    for cert in read_certs(cert_path)? {
+        roots.add(cert)?;
+    }
+
+    let client_config: ClientConfig = ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    trusted_vaults().lock().unwrap().insert(
+        cli_address.to_string(),
+        TlsConnector::from(Arc::new(client_config)),
+    );
+
+    Ok(())
+}
+
+// Trust the local vault and every configured remote vault that has a certificate.
+pub fn trust_vaults(conf: &GVConfig) {
+    let local = conf
+        .cli_tls_cert
+        .as_ref()
+        .map(|cert| (conf.cli_address.as_str(), cert.clone()));
+    let remotes = conf
+        .tg_vaults
+        .iter()
+        .chain(conf.chain_peers.iter())
+        .filter_map(|vault| {
+            vault
+                .tls_cert
+                .as_ref()
+                .map(|cert| (vault.cli_address.as_str(), file_ops::expand_user(cert)))
+        });
+
+    for (cli_address, cert) in local.into_iter().chain(remotes) {
+        if let Err(err) = trust(cli_address, &cert) {
+            warn!("Unable to use TLS for {}: {}", cli_address, err);
+        }
+    }
+}
+
+pub fn is_trusted(cli_address: &str) -> bool {
+    trusted_vaults().lock().unwrap().contains_key(cli_address)
+}
+
+// Framed the way tarpc's own TCP transport is, ready for serde_transport::new.
+pub async fn connect(
+    cli_address: &str,
+) -> Result<Framed<client::TlsStream<TcpStream>, LengthDelimitedCodec>, Box<dyn Error + Send + Sync>>
+{
+    let connector: TlsConnector = trusted_vaults()
+        .lock()
+        .unwrap()
+        .get(cli_address)
+        .cloned()
+        .ok_or_else(|| format!("No TLS certificate configured for {}", cli_address))?;
+
+    let host: &str = cli_address
+        .rsplit_once(':')
+        .map_or(cli_address, |(host, _)| host);
+    let server_name: ServerName<'static> = ServerName::try_from(host)?.to_owned();
+
+    let stream: TcpStream = TcpStream::connect(cli_address).await?;
+    let stream: client::TlsStream<TcpStream> = connector.connect(server_name, stream).await?;
+
+    Ok(Framed::new(stream, LengthDelimitedCodec::new()))
+}
+
+pub fn acceptor(
+    cert_path: &Path,
+    key_path: &Path,
+) -> Result<TlsAcceptor, Box<dyn Error + Send + Sync>> {
+    let server_config: ServerConfig = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(read_certs(cert_path)?, read_key(key_path)?)?;
+
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
+// No frame limit, as the plain listener is set up.
+pub fn server_framed(
+    stream: server::TlsStream<TcpStream>,
+) -> Framed<server::TlsStream<TcpStream>, LengthDelimitedCodec> {
+    let mut codec: LengthDelimitedCodec = LengthDelimitedCodec::new();
+    codec.set_max_frame_length(usize::MAX);
+
+    Framed::new(stream, codec)
+}
+
+// Write a self-signed certificate for hosts into gv_home, returns the cert and key paths.
+pub fn generate_cert(
+    gv_home: &Path,
+    hosts: Vec<String>,
+) -> Result<(PathBuf, PathBuf), Box<dyn Error + Send + Sync>> {
+    let cert = rcgen::generate_simple_self_signed(hosts)?;
+
+    let tls_dir: PathBuf = gv_home.join(CLI_TLS_DIR);
+    fs::create_dir_all(&tls_dir)?;
+
+    let cert_path: PathBuf = tls_dir.join(CLI_TLS_CERT_FILE);
+    let key_path: PathBuf = tls_dir.join(CLI_TLS_KEY_FILE);
+
+    fs::write(&cert_path, cert.serialize_pem()?)?;
+
+    let mut key_file: File = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(&key_path)?;
+    key_file.write_all(cert.serialize_private_key_pem().as_bytes())?;
+
+    Ok((cert_path, key_path))
+}
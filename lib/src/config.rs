@@ -1,15 +1,338 @@
 use crate::{
     constants::{
-        DAEMON_SETTINGS_FILE, DEFAULT_HOT_WALLET, DEFAULT_PROCESS_REWARDS, GV_SETTINGS_FILE,
+        CONFIG_PASSPHRASE_ENV, DAEMON_BASE_URL, DAEMON_SETTINGS_FILE, DEFAULT_BACKUP_DIR,
+        DEFAULT_BACKUP_INTERVAL, DEFAULT_BACKUP_RETENTION, DEFAULT_BALANCE_SAMPLE_INTERVAL,
+        DEFAULT_CACHE_TTL_BLOCKCHAININFO, DEFAULT_CACHE_TTL_COLDSTAKINGINFO,
+        DEFAULT_CACHE_TTL_NETWORKINFO, DEFAULT_CACHE_TTL_STAKINGINFO, DEFAULT_CHAIN_SPLIT_TIMEOUT,
+        DEFAULT_DB_DIR, DEFAULT_DIGEST_HOUR, DEFAULT_DISK_ALERT_GB, DEFAULT_DOCKER_MAX_RESTARTS,
+        DEFAULT_EXPLORER_APIS, DEFAULT_EXPLORER_BLOCK_URL, DEFAULT_EXPLORER_TX_URL,
+        DEFAULT_FEE_CONF_TARGET, DEFAULT_FEE_RATE, DEFAULT_HOOK_TIMEOUT, DEFAULT_HOT_WALLET,
+        DEFAULT_LOG_FILE_COUNT, DEFAULT_LOG_FILE_SIZE_MB, DEFAULT_LOG_LEVEL, DEFAULT_MAX_TX_FEE,
+        DEFAULT_MISSED_STAKE_FACTOR, DEFAULT_PAYOUT_SAFE_DEPTH, DEFAULT_PROCESS_REWARDS,
+        DEFAULT_REMOTE_BACKUP_RETENTION, DEFAULT_RING_SIZE, DEFAULT_S3_ENDPOINT, DEFAULT_S3_REGION,
+        DEFAULT_SPLIT_COUNT, DEFAULT_SPLIT_INTERVAL, DEFAULT_SPLIT_THRESHOLD,
+        DEFAULT_STAKE_CONFIRMATIONS, DEFAULT_ZAP_CONFIRMATIONS, ENCRYPTED_VALUE_PREFIX,
+        GV_PROFILE_ENV, GV_SETTINGS_FILE, MAX_FEE_CONF_TARGET, MAX_RING_SIZE, MAX_SPLIT_COUNT,
+        MIN_DAEMON_PRUNE, MIN_RING_SIZE, MIN_TX_VALUE, SEALED_VALUE_PREFIX, SENSITIVE_CONFIG_KEYS,
     },
     daemon_helper::DaemonHelper,
     file_ops,
+    gv_client_methods::FleetVault,
+    hooks::HOOK_EVENTS,
 };
-use log::info;
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use argon2::Argon2;
+use data_encoding::{BASE64, HEXLOWER};
+use hmac::{Hmac, Mac};
+use log::{info, warn};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
 use serde_json::Value as json_Value;
-use std::{error::Error, path::PathBuf};
+use sha2::Sha256;
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::RwLock,
+};
 use toml::Value as toml_Value;
 
+// Key derived from the config passphrase, set once by unlock_config at startup.
+static CONFIG_KEY: RwLock<Option<[u8; 32]>> = RwLock::new(None);
+
+// Encrypted with the config key so a wrong passphrase is caught before anything is decrypted.
+const CONFIG_CHECK_PLAINTEXT: &str = "ghostvault";
+
+// How sends pick a fee rate, FIXED always uses FEE_RATE while ESTIMATE asks ghostd for a rate
+// that confirms within FEE_CONF_TARGET blocks and falls back to FEE_RATE when it can't say.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeePolicy {
+    Fixed,
+    Estimate,
+}
+
+impl FeePolicy {
+    pub fn from_name(name: &str) -> Option<FeePolicy> {
+        match name.to_uppercase().as_str() {
+            "FIXED" => Some(FeePolicy::Fixed),
+            "ESTIMATE" => Some(FeePolicy::Estimate),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeePolicy::Fixed => "FIXED",
+            FeePolicy::Estimate => "ESTIMATE",
+        }
+    }
+}
+
+// How the release checksums are checked before a ghostd archive is trusted. NONE only checks the
+// archive against hashes.txt, MINISIGN and GPG also need hashes.txt signed by DAEMON_SIGNING_KEY.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DaemonVerify {
+    None,
+    Minisign,
+    Gpg,
+}
+
+impl DaemonVerify {
+    pub fn from_name(name: &str) -> Option<DaemonVerify> {
+        match name.to_uppercase().as_str() {
+            "NONE" | "" => Some(DaemonVerify::None),
+            "MINISIGN" => Some(DaemonVerify::Minisign),
+            "GPG" => Some(DaemonVerify::Gpg),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DaemonVerify::None => "NONE",
+            DaemonVerify::Minisign => "MINISIGN",
+            DaemonVerify::Gpg => "GPG",
+        }
+    }
+}
+
+// Which ghostd release updates go to. STABLE follows the latest release, TESTING also takes
+// release candidates, and a version number holds ghostd at that release until the pin is lifted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UpdateChannel {
+    Stable,
+    Testing,
+    Pinned(String),
+}
+
+impl UpdateChannel {
+    pub fn from_name(name: &str) -> Option<UpdateChannel> {
+        match name.trim().to_uppercase().as_str() {
+            "STABLE" | "" => Some(UpdateChannel::Stable),
+            "TESTING" => Some(UpdateChannel::Testing),
+            _ => {
+                let version: &str = name.trim().trim_start_matches('v');
+                let valid: bool = version.starts_with(|c: char| c.is_ascii_digit())
+                    && version
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '.');
+
+                valid.then(|| UpdateChannel::Pinned(version.to_string()))
+            }
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            UpdateChannel::Stable => "STABLE",
+            UpdateChannel::Testing => "TESTING",
+            UpdateChannel::Pinned(version) => version,
+        }
+    }
+}
+
+// Where ghostd releases come from and how they're checked, copied out so downloads don't hold
+// the config lock.
+#[derive(Clone, Debug)]
+pub struct DaemonSource {
+    pub mirrors: Vec<String>,
+    pub channel: UpdateChannel,
+    pub verify: DaemonVerify,
+    // A minisign public key, or the path of a binary GPG public key for gpgv.
+    pub signing_key: String,
+}
+
+// How GhostVault logs. level is the default, modules overrides it for single modules, set in
+// the config as LOG_MODULES = { "service::rpc" = "debug" }.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogSettings {
+    pub level: String,
+    pub modules: BTreeMap<String, String>,
+    pub file_size_mb: u64,
+    pub file_count: u32,
+    pub json: bool,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        LogSettings {
+            level: DEFAULT_LOG_LEVEL.to_string(),
+            modules: BTreeMap::new(),
+            file_size_mb: DEFAULT_LOG_FILE_SIZE_MB,
+            file_count: DEFAULT_LOG_FILE_COUNT,
+            json: false,
+        }
+    }
+}
+
+impl LogSettings {
+    pub fn from_toml(gv_conf: &toml_Value) -> LogSettings {
+        let level: String = gv_conf
+            .get("LOG_LEVEL")
+            .and_then(|level| level.as_str())
+            .filter(|level| valid_log_level(level))
+            .unwrap_or(DEFAULT_LOG_LEVEL)
+            .to_lowercase();
+        let modules: BTreeMap<String, String> = gv_conf
+            .get("LOG_MODULES")
+            .and_then(|modules| modules.as_table())
+            .map(|modules| {
+                modules
+                    .iter()
+                    .filter_map(|(module, level)| {
+                        let level: &str = level.as_str().filter(|level| valid_log_level(level))?;
+                        Some((module.to_string(), level.to_lowercase()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let file_size_mb: u64 = gv_conf
+            .get("LOG_FILE_SIZE_MB")
+            .and_then(|size| size.as_integer())
+            .filter(|size| *size > 0)
+            .map_or(DEFAULT_LOG_FILE_SIZE_MB, |size| size as u64);
+        let file_count: u32 = gv_conf
+            .get("LOG_FILE_COUNT")
+            .and_then(|count| count.as_integer())
+            .filter(|count| *count > 0)
+            .map_or(DEFAULT_LOG_FILE_COUNT, |count| count as u32);
+        let json: bool = gv_conf
+            .get("LOG_JSON")
+            .and_then(|json| json.as_bool())
+            .unwrap_or(false);
+
+        LogSettings {
+            level,
+            modules,
+            file_size_mb,
+            file_count,
+            json,
+        }
+    }
+}
+
+// Scripts run on events, set in the config as HOOKS = { new_stake = "~/hooks/stake.sh" } with a
+// path or a list of paths per event. Each gets HOOK_TIMEOUT seconds before it's killed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HookSettings {
+    pub scripts: BTreeMap<String, Vec<PathBuf>>,
+    pub timeout: u64,
+}
+
+impl HookSettings {
+    pub fn from_toml(gv_conf: &toml_Value) -> HookSettings {
+        let scripts: BTreeMap<String, Vec<PathBuf>> = gv_conf
+            .get("HOOKS")
+            .and_then(|hooks| hooks.as_table())
+            .map(|hooks| {
+                hooks
+                    .iter()
+                    .filter_map(|(event, scripts)| {
+                        if !HOOK_EVENTS.contains(&event.as_str()) {
+                            warn!("Ignoring hook for unknown event '{}'", event);
+                            return None;
+                        }
+
+                        let scripts: Vec<PathBuf> = match scripts {
+                            toml_Value::String(script) => vec![file_ops::expand_user(script)],
+                            toml_Value::Array(scripts) => scripts
+                                .iter()
+                                .filter_map(|script| script.as_str())
+                                .map(file_ops::expand_user)
+                                .collect(),
+                            _ => Vec::new(),
+                        };
+
+                        Some((event.to_string(), scripts))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let timeout: u64 = gv_conf
+            .get("HOOK_TIMEOUT")
+            .and_then(|timeout| timeout.as_integer())
+            .filter(|timeout| *timeout > 0)
+            .map_or(DEFAULT_HOOK_TIMEOUT, |timeout| timeout as u64);
+
+        HookSettings { scripts, timeout }
+    }
+}
+
+// gv_settings.toml, or gv_settings.<profile>.toml once a profile is picked with --profile.
+pub fn settings_path(gv_home: &Path) -> PathBuf {
+    match std::env::var(GV_PROFILE_ENV) {
+        Ok(profile) if !profile.is_empty() => gv_home.join(format!("gv_settings.{}.toml", profile)),
+        _ => gv_home.join(GV_SETTINGS_FILE),
+    }
+}
+
+// Profile names end up in a file name.
+pub fn valid_profile_name(profile: &str) -> bool {
+    !profile.is_empty()
+        && profile
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+pub fn valid_log_level(level: &str) -> bool {
+    log::LevelFilter::from_str(level).is_ok()
+}
+
+// Logging is set up before the rest of the config is read, none of the log keys are encrypted.
+pub fn read_log_settings(gv_home: &Path) -> LogSettings {
+    match read_settings(gv_home) {
+        Ok(gv_conf) => LogSettings::from_toml(&gv_conf),
+        Err(_) => LogSettings::default(),
+    }
+}
+
+// A blockchain bootstrap archive, blocks/ and chainstate/ in a .tar.gz. sha256 is the archive's
+// hex digest, when empty it's read from <url>.sha256 instead.
+#[derive(Clone, Debug)]
+pub struct BootstrapSource {
+    pub url: String,
+    pub sha256: String,
+}
+
+// What ghost.conf needs to match DAEMON_PRUNE. Pruning drops the address index, going back to
+// a full node means downloading the whole chain again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PruneChange {
+    None,
+    Prune,
+    Unprune,
+}
+
+// What reload_config made of the keys that changed on disk.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ConfigReload {
+    pub applied: Vec<String>,
+    pub restart_required: Vec<String>,
+}
+
+// The fee part of the config, copied out so sends don't hold the config lock. Sats per kB.
+#[derive(Clone, Copy, Debug)]
+pub struct FeeSettings {
+    pub policy: FeePolicy,
+    pub rate: u64,
+    pub conf_target: u64,
+    pub max_fee: u64,
+}
+
+// How public rewards in anon mode are moved to anon before being paid out.
+#[derive(Debug, Clone, Copy)]
+pub struct AnonPolicy {
+    pub enabled: bool,
+    pub threshold: u64,
+    pub interval: u64,
+    pub ring_size: u32,
+    pub batch_size: u64,
+}
+
 #[derive(Debug, Clone)]
 pub struct GVConfig {
     pub bot_token: Option<String>,
@@ -27,13 +350,26 @@ pub struct GVConfig {
     pub rpc_user: String,
     pub rpc_pass: String,
     pub cli_address: String,
+    pub cli_tls_cert: Option<PathBuf>,
+    pub cli_tls_key: Option<PathBuf>,
     pub gv_home: PathBuf,
+    pub db_path: PathBuf,
     pub config_file: PathBuf,
     pub daemon_data_dir: PathBuf,
     pub daemon_path: PathBuf,
     pub daemon_hash: Option<String>,
+    pub system_daemon: bool,
+    pub confirm_daemon_update: bool,
+    pub update_channel: UpdateChannel,
+    pub vault_name: String,
+    pub tg_vaults: Vec<FleetVault>,
+    pub bot_relay: bool,
+    pub tg_channel: Option<String>,
+    pub announce_webhook_url: Option<String>,
+    pub announce_email: Option<String>,
     pub min_reward_payout: u64,
     pub mnemonic: Option<String>,
+    pub sealed_mnemonic: Option<String>,
     pub reward_interval: u64,
     pub zmq_block_host: String,
     pub zmq_tx_host: String,
@@ -41,6 +377,66 @@ pub struct GVConfig {
     pub announce_zaps: bool,
     pub announce_rewards: bool,
     pub timezone: String,
+    pub maint_window_start: u32,
+    pub maint_window_end: u32,
+    pub backup_dir: PathBuf,
+    pub backup_interval: u64,
+    pub backup_retention: u64,
+    pub backup_remote_url: Option<String>,
+    pub backup_s3_endpoint: String,
+    pub backup_s3_region: String,
+    pub backup_remote_access_key: Option<String>,
+    pub backup_remote_secret_key: Option<String>,
+    pub backup_sftp_key: Option<String>,
+    pub backup_remote_retention: u64,
+    pub chain_split_timeout: u64,
+    pub cache_ttl_blockchaininfo: u64,
+    pub cache_ttl_stakinginfo: u64,
+    pub cache_ttl_coldstakinginfo: u64,
+    pub cache_ttl_networkinfo: u64,
+    pub explorer_apis: Vec<String>,
+    pub explorer_tx_url: String,
+    pub explorer_block_url: String,
+    pub daemon_mirrors: Vec<String>,
+    pub daemon_verify: DaemonVerify,
+    pub daemon_signing_key: String,
+    pub daemon_prune: u64,
+    pub bootstrap_url: String,
+    pub bootstrap_sha256: String,
+    pub bootstrap_on_resync: bool,
+    pub local_chain_check: bool,
+    pub chain_peers: Vec<FleetVault>,
+    pub missed_stake_factor: u64,
+    pub stake_confirmations: u64,
+    pub zap_confirmations: u64,
+    pub payout_safe_depth: u64,
+    pub balance_sample_interval: u64,
+    pub disk_alert_gb: u64,
+    pub docker_max_restarts: u64,
+    pub log: LogSettings,
+    pub hooks: HookSettings,
+    pub fee_policy: FeePolicy,
+    pub fee_rate: u64,
+    pub fee_conf_target: u64,
+    pub max_tx_fee: u64,
+    pub split_utxos: bool,
+    pub split_threshold: u64,
+    pub split_count: u64,
+    pub split_interval: u64,
+    pub anon_flush: bool,
+    pub anon_flush_threshold: u64,
+    pub anon_flush_interval: u64,
+    pub anon_ring_size: u32,
+    pub anon_batch_size: u64,
+    pub watch_only: bool,
+    pub events_address: Option<String>,
+    pub payout_label: Option<String>,
+    pub grpc_address: Option<String>,
+    pub digest_daily: bool,
+    pub digest_weekly: bool,
+    pub digest_hour: u32,
+    pub reward_address_whitelist: Vec<String>,
+    pub reward_change_delay: u64,
 }
 
 trait EmptyAsNone {
@@ -96,7 +492,7 @@ impl IntIntoBool for i64 {
 impl GVConfig {
     pub fn new(gv_home: &PathBuf, daemon_data_dir: &PathBuf) -> Result<Self, Box<dyn Error>> {
         log::info!("Reading Configuration...");
-        let toml_file_path = gv_home.join(PathBuf::from(GV_SETTINGS_FILE));
+        let toml_file_path = settings_path(gv_home);
         let toml_content = std::fs::read_to_string(&toml_file_path)?;
 
         let gv_conf: toml_Value = toml::from_str(&toml_content)?;
@@ -104,11 +500,7 @@ impl GVConfig {
         let daemon_conf: json_Value =
             file_ops::ghost_config_to_value(&daemon_data_dir.join(DAEMON_SETTINGS_FILE))?;
 
-        let bot_token: Option<String> = gv_conf
-            .get("TELOXIDE_TOKEN")
-            .unwrap_or(&toml_Value::String(String::new()))
-            .clone()
-            .empty_as_none();
+        let bot_token: Option<String> = read_secret(&gv_conf, "TELOXIDE_TOKEN");
         let tg_user: Option<String> = gv_conf
             .get("TELEGRAM_USER")
             .unwrap_or(&toml_Value::String(String::new()))
@@ -134,24 +526,40 @@ impl GVConfig {
             .unwrap_or("")
             .to_string();
 
-        let rpc_user: String = daemon_conf
-            .get("rpcuser")
-            .unwrap_or(&serde_json::Value::String("user".to_string()))
-            .as_str()
-            .unwrap_or("user")
-            .to_string();
-        let rpc_pass: String = daemon_conf
-            .get("rpcpassword")
-            .unwrap_or(&serde_json::Value::String("password".to_string()))
-            .as_str()
-            .unwrap_or("password")
-            .to_string();
+        // With RPC_AUTH set the config keeps the credentials and ghost.conf only their rpcauth hash
+        let rpc_user: String = read_secret(&gv_conf, "RPC_USER").unwrap_or_else(|| {
+            daemon_conf
+                .get("rpcuser")
+                .unwrap_or(&serde_json::Value::String("user".to_string()))
+                .as_str()
+                .unwrap_or("user")
+                .to_string()
+        });
+        let rpc_pass: String = read_secret(&gv_conf, "RPC_PASSWORD").unwrap_or_else(|| {
+            daemon_conf
+                .get("rpcpassword")
+                .unwrap_or(&serde_json::Value::String("password".to_string()))
+                .as_str()
+                .unwrap_or("password")
+                .to_string()
+        });
         let cli_address: String = gv_conf
             .get("CLI_ADDRESS")
             .unwrap_or(&toml_Value::String("127.0.0.1:50051".to_string()))
             .as_str()
             .unwrap_or("127.0.0.1:50051")
             .to_string();
+        // The RPC server uses TLS when both are set, gv-cli gentlscert makes a self-signed pair
+        let cli_tls_cert: Option<PathBuf> = gv_conf
+            .get("CLI_TLS_CERT")
+            .and_then(|path| path.as_str())
+            .filter(|path| !path.is_empty())
+            .map(file_ops::expand_user);
+        let cli_tls_key: Option<PathBuf> = gv_conf
+            .get("CLI_TLS_KEY")
+            .and_then(|path| path.as_str())
+            .filter(|path| !path.is_empty())
+            .map(file_ops::expand_user);
 
         let config_file: PathBuf = toml_file_path;
 
@@ -193,11 +601,143 @@ impl GVConfig {
             .unwrap_or(&toml_Value::String(String::new()))
             .clone()
             .empty_as_none();
+        let system_daemon: bool = gv_conf
+            .get("SYSTEM_DAEMON")
+            .unwrap_or(&toml_Value::Boolean(false))
+            .as_bool()
+            .unwrap_or(false);
+
+        let confirm_daemon_update: bool = gv_conf
+            .get("CONFIRM_DAEMON_UPDATE")
+            .unwrap_or(&toml_Value::Boolean(false))
+            .as_bool()
+            .unwrap_or(false);
+        let update_channel: UpdateChannel = gv_conf
+            .get("UPDATE_CHANNEL")
+            .and_then(|channel| channel.as_str())
+            .and_then(UpdateChannel::from_name)
+            .unwrap_or(UpdateChannel::Stable);
+
+        let vault_name: String = match gv_conf.get("VAULT_NAME").and_then(|name| name.as_str()) {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => "local".to_string(),
+        };
+
+        // Other vaults the Telegram bot manages, [{ name = "...", cli_address = "host:port" }],
+        // tls_cert = "path" for vaults that serve TLS
+        let tg_vaults: Vec<FleetVault> = gv_conf
+            .get("TG_VAULTS")
+            .and_then(|vaults| vaults.clone().try_into().ok())
+            .unwrap_or_default();
+
+        let bot_relay: bool = gv_conf
+            .get("BOT_RELAY")
+            .unwrap_or(&toml_Value::Boolean(false))
+            .as_bool()
+            .unwrap_or(false);
+
+        let db_path: PathBuf = match gv_conf.get("DB_PATH").and_then(|path| path.as_str()) {
+            Some(path) if !path.is_empty() => file_ops::expand_user(path),
+            _ => gv_home.join(DEFAULT_DB_DIR),
+        };
+
+        let backup_dir: PathBuf = match gv_conf.get("BACKUP_DIR").and_then(|path| path.as_str()) {
+            Some(path) if !path.is_empty() => file_ops::expand_user(path),
+            _ => gv_home.join(DEFAULT_BACKUP_DIR),
+        };
+        let backup_interval: u64 = gv_conf
+            .get("BACKUP_INTERVAL")
+            .unwrap_or(&toml_Value::Integer(DEFAULT_BACKUP_INTERVAL))
+            .as_integer()
+            .unwrap_or(DEFAULT_BACKUP_INTERVAL) as u64;
+        let backup_retention: u64 = gv_conf
+            .get("BACKUP_RETENTION")
+            .unwrap_or(&toml_Value::Integer(DEFAULT_BACKUP_RETENTION))
+            .as_integer()
+            .unwrap_or(DEFAULT_BACKUP_RETENTION) as u64;
+        let tg_channel: Option<String> = gv_conf
+            .get("TG_CHANNEL")
+            .unwrap_or(&toml_Value::String(String::new()))
+            .clone()
+            .empty_as_none();
+        let announce_webhook_url: Option<String> = read_secret(&gv_conf, "ANNOUNCE_WEBHOOK_URL");
+        let announce_email: Option<String> = gv_conf
+            .get("ANNOUNCE_EMAIL")
+            .unwrap_or(&toml_Value::String(String::new()))
+            .clone()
+            .empty_as_none();
+        let backup_remote_url: Option<String> = gv_conf
+            .get("BACKUP_REMOTE_URL")
+            .unwrap_or(&toml_Value::String(String::new()))
+            .clone()
+            .empty_as_none();
+        let backup_s3_endpoint: String = gv_conf
+            .get("BACKUP_S3_ENDPOINT")
+            .and_then(|endpoint| endpoint.as_str())
+            .filter(|endpoint| !endpoint.is_empty())
+            .unwrap_or(DEFAULT_S3_ENDPOINT)
+            .to_string();
+        let backup_s3_region: String = gv_conf
+            .get("BACKUP_S3_REGION")
+            .and_then(|region| region.as_str())
+            .filter(|region| !region.is_empty())
+            .unwrap_or(DEFAULT_S3_REGION)
+            .to_string();
+        let backup_remote_access_key: Option<String> = gv_conf
+            .get("BACKUP_REMOTE_ACCESS_KEY")
+            .unwrap_or(&toml_Value::String(String::new()))
+            .clone()
+            .empty_as_none();
+        let backup_remote_secret_key: Option<String> =
+            read_secret(&gv_conf, "BACKUP_REMOTE_SECRET_KEY");
+        let backup_sftp_key: Option<String> = gv_conf
+            .get("BACKUP_SFTP_KEY")
+            .and_then(|key| key.as_str())
+            .filter(|key| !key.is_empty())
+            .map(|key| file_ops::expand_user(key).display().to_string());
+        let backup_remote_retention: u64 = gv_conf
+            .get("BACKUP_REMOTE_RETENTION")
+            .unwrap_or(&toml_Value::Integer(DEFAULT_REMOTE_BACKUP_RETENTION))
+            .as_integer()
+            .unwrap_or(DEFAULT_REMOTE_BACKUP_RETENTION)
+            as u64;
+        let chain_split_timeout: u64 = gv_conf
+            .get("CHAIN_SPLIT_TIMEOUT")
+            .unwrap_or(&toml_Value::Integer(DEFAULT_CHAIN_SPLIT_TIMEOUT))
+            .as_integer()
+            .unwrap_or(DEFAULT_CHAIN_SPLIT_TIMEOUT) as u64;
+        let cache_ttl_blockchaininfo: u64 = gv_conf
+            .get("CACHE_TTL_BLOCKCHAININFO")
+            .unwrap_or(&toml_Value::Integer(DEFAULT_CACHE_TTL_BLOCKCHAININFO))
+            .as_integer()
+            .unwrap_or(DEFAULT_CACHE_TTL_BLOCKCHAININFO)
+            as u64;
+        let cache_ttl_stakinginfo: u64 = gv_conf
+            .get("CACHE_TTL_STAKINGINFO")
+            .unwrap_or(&toml_Value::Integer(DEFAULT_CACHE_TTL_STAKINGINFO))
+            .as_integer()
+            .unwrap_or(DEFAULT_CACHE_TTL_STAKINGINFO)
+            as u64;
+        let cache_ttl_coldstakinginfo: u64 = gv_conf
+            .get("CACHE_TTL_COLDSTAKINGINFO")
+            .unwrap_or(&toml_Value::Integer(DEFAULT_CACHE_TTL_COLDSTAKINGINFO))
+            .as_integer()
+            .unwrap_or(DEFAULT_CACHE_TTL_COLDSTAKINGINFO)
+            as u64;
+        let cache_ttl_networkinfo: u64 = gv_conf
+            .get("CACHE_TTL_NETWORKINFO")
+            .unwrap_or(&toml_Value::Integer(DEFAULT_CACHE_TTL_NETWORKINFO))
+            .as_integer()
+            .unwrap_or(DEFAULT_CACHE_TTL_NETWORKINFO)
+            as u64;
 
         let gv_home: PathBuf = gv_home.to_owned();
         let daemon_data_dir: PathBuf = daemon_data_dir.to_owned();
 
-        let rpc_wallet_hot: String = DEFAULT_HOT_WALLET.to_string();
+        let rpc_wallet_hot: String = match gv_conf.get("RPC_WALLET_HOT").and_then(|w| w.as_str()) {
+            Some(wallet) if !wallet.is_empty() => wallet.to_string(),
+            _ => DEFAULT_HOT_WALLET.to_string(),
+        };
 
         let min_reward_payout: u64 = gv_conf
             .get("MIN_REWARD_PAYOUT")
@@ -250,11 +790,272 @@ impl GVConfig {
             .as_str()
             .unwrap_or("UTC")
             .to_string();
-        let mnemonic: Option<String> = gv_conf
-            .get("MNEMONIC")
+        let maint_window_start: u32 = gv_conf
+            .get("MAINT_WINDOW_START")
+            .unwrap_or(&toml_Value::Integer(2))
+            .as_integer()
+            .unwrap_or(2) as u32;
+        let maint_window_end: u32 = gv_conf
+            .get("MAINT_WINDOW_END")
+            .unwrap_or(&toml_Value::Integer(5))
+            .as_integer()
+            .unwrap_or(5) as u32;
+        let mnemonic: Option<String> = read_secret(&gv_conf, "MNEMONIC");
+        // Encrypted with the user's own passphrase, which GhostVault never keeps
+        let sealed_mnemonic: Option<String> = gv_conf
+            .get("SEALED_MNEMONIC")
+            .unwrap_or(&toml_Value::String(String::new()))
+            .clone()
+            .empty_as_none();
+        // Summaries of the previous day or week, sent at DIGEST_HOUR in TIMEZONE
+        let digest_daily: bool = gv_conf
+            .get("DIGEST_DAILY")
+            .unwrap_or(&toml_Value::Boolean(false))
+            .as_bool()
+            .unwrap_or(false);
+        let digest_weekly: bool = gv_conf
+            .get("DIGEST_WEEKLY")
+            .unwrap_or(&toml_Value::Boolean(false))
+            .as_bool()
+            .unwrap_or(false);
+        let digest_hour: u32 = gv_conf
+            .get("DIGEST_HOUR")
+            .and_then(|hour| hour.as_integer())
+            .filter(|hour| (0..24).contains(hour))
+            .map_or(DEFAULT_DIGEST_HOUR, |hour| hour as u32);
+        // Reward addresses set_reward_mode accepts, an empty list allows any address. Neither
+        // this nor the delay can be changed over RPC, only by editing the settings file.
+        let reward_address_whitelist: Vec<String> = gv_conf
+            .get("REWARD_ADDRESS_WHITELIST")
+            .and_then(|addresses| addresses.clone().try_into::<Vec<String>>().ok())
+            .unwrap_or_default();
+        // Seconds a new reward address waits before it's used, 0 applies it straight away
+        let reward_change_delay: u64 = gv_conf
+            .get("REWARD_CHANGE_DELAY")
+            .unwrap_or(&toml_Value::Integer(0))
+            .as_integer()
+            .filter(|delay| *delay > 0)
+            .unwrap_or(0) as u64;
+
+        // Tried in order when checking the chain, an empty list means the defaults
+        let explorer_apis: Vec<String> = gv_conf
+            .get("EXPLORER_APIS")
+            .and_then(|apis| apis.clone().try_into::<Vec<String>>().ok())
+            .filter(|apis| !apis.is_empty())
+            .unwrap_or(
+                DEFAULT_EXPLORER_APIS
+                    .iter()
+                    .map(|api| api.to_string())
+                    .collect(),
+            );
+        // Verify the chain from ghostd's peers only, no explorer or socket.io calls
+        let local_chain_check: bool = gv_conf
+            .get("LOCAL_CHAIN_CHECK")
+            .unwrap_or(&toml_Value::Boolean(false))
+            .as_bool()
+            .unwrap_or(false);
+        // Other vaults to check the chain against instead of the explorers,
+        // [{ name = "...", cli_address = "host:port" }]
+        let chain_peers: Vec<FleetVault> = gv_conf
+            .get("CHAIN_PEERS")
+            .and_then(|peers| peers.clone().try_into().ok())
+            .unwrap_or_default();
+        // Alert when no stake was found for this many times the expected interval, 0 disables
+        let missed_stake_factor: u64 = gv_conf
+            .get("MISSED_STAKE_FACTOR")
+            .unwrap_or(&toml_Value::Integer(DEFAULT_MISSED_STAKE_FACTOR as i64))
+            .as_integer()
+            .unwrap_or(DEFAULT_MISSED_STAKE_FACTOR as i64)
+            as u64;
+        // Confirmations before a stake's reward counts as matured and a zap as staking
+        let stake_confirmations: u64 = gv_conf
+            .get("STAKE_CONFIRMATIONS")
+            .and_then(|confirms| confirms.as_integer())
+            .filter(|confirms| *confirms > 0)
+            .unwrap_or(DEFAULT_STAKE_CONFIRMATIONS as i64)
+            as u64;
+        let zap_confirmations: u64 = gv_conf
+            .get("ZAP_CONFIRMATIONS")
+            .and_then(|confirms| confirms.as_integer())
+            .filter(|confirms| *confirms > 0)
+            .unwrap_or(DEFAULT_ZAP_CONFIRMATIONS as i64)
+            as u64;
+        // Payouts wait until every input they'd spend is at least this deep
+        let payout_safe_depth: u64 = gv_conf
+            .get("PAYOUT_SAFE_DEPTH")
+            .and_then(|depth| depth.as_integer())
+            .filter(|depth| *depth > 0)
+            .unwrap_or(DEFAULT_PAYOUT_SAFE_DEPTH as i64)
+            as u64;
+        let balance_sample_interval: u64 = gv_conf
+            .get("BALANCE_SAMPLE_INTERVAL")
+            .unwrap_or(&toml_Value::Integer(DEFAULT_BALANCE_SAMPLE_INTERVAL))
+            .as_integer()
+            .unwrap_or(DEFAULT_BALANCE_SAMPLE_INTERVAL)
+            as u64;
+        // Alert when the ghostd data dir or GV home has less than this many GB free, 0 disables
+        let disk_alert_gb: u64 = gv_conf
+            .get("DISK_ALERT_GB")
+            .unwrap_or(&toml_Value::Integer(DEFAULT_DISK_ALERT_GB as i64))
+            .as_integer()
+            .unwrap_or(DEFAULT_DISK_ALERT_GB as i64) as u64;
+        // Only used in Docker, where GhostVault looks after ghostd on its own
+        let docker_max_restarts: u64 = gv_conf
+            .get("DOCKER_MAX_RESTARTS")
+            .unwrap_or(&toml_Value::Integer(DEFAULT_DOCKER_MAX_RESTARTS as i64))
+            .as_integer()
+            .filter(|max_restarts| *max_restarts > 0)
+            .unwrap_or(DEFAULT_DOCKER_MAX_RESTARTS as i64)
+            as u64;
+        let fee_policy: FeePolicy = gv_conf
+            .get("FEE_POLICY")
+            .and_then(|policy| policy.as_str())
+            .and_then(FeePolicy::from_name)
+            .unwrap_or(FeePolicy::Fixed);
+        // Sats per kB
+        let fee_rate: u64 = gv_conf
+            .get("FEE_RATE")
+            .and_then(|rate| rate.as_integer())
+            .filter(|rate| *rate > 0)
+            .map_or(DEFAULT_FEE_RATE, |rate| rate as u64);
+        let fee_conf_target: u64 = gv_conf
+            .get("FEE_CONF_TARGET")
+            .and_then(|target| target.as_integer())
+            .filter(|target| *target > 0 && *target as u64 <= MAX_FEE_CONF_TARGET)
+            .map_or(DEFAULT_FEE_CONF_TARGET, |target| target as u64);
+        let max_tx_fee: u64 = gv_conf
+            .get("MAX_TX_FEE")
+            .and_then(|fee| fee.as_integer())
+            .filter(|fee| *fee > 0)
+            .map_or(DEFAULT_MAX_TX_FEE, |fee| fee as u64);
+        // Staking outputs above SPLIT_THRESHOLD sats are split into SPLIT_COUNT pieces, once the
+        // split is confirmed from Telegram or gv-cli
+        let split_utxos: bool = gv_conf
+            .get("SPLIT_UTXOS")
+            .unwrap_or(&toml_Value::Boolean(false))
+            .as_bool()
+            .unwrap_or(false);
+        let split_threshold: u64 = gv_conf
+            .get("SPLIT_THRESHOLD")
+            .and_then(|threshold| threshold.as_integer())
+            .filter(|threshold| *threshold > 0)
+            .map_or(DEFAULT_SPLIT_THRESHOLD, |threshold| threshold as u64);
+        let split_count: u64 = gv_conf
+            .get("SPLIT_COUNT")
+            .and_then(|count| count.as_integer())
+            .filter(|count| *count >= 2 && *count as u64 <= MAX_SPLIT_COUNT)
+            .map_or(DEFAULT_SPLIT_COUNT, |count| count as u64);
+        let split_interval: u64 = gv_conf
+            .get("SPLIT_INTERVAL")
+            .and_then(|interval| interval.as_integer())
+            .filter(|interval| *interval > 0)
+            .unwrap_or(DEFAULT_SPLIT_INTERVAL) as u64;
+        // In anon mode public rewards of at least ANON_FLUSH_THRESHOLD sats are converted to anon
+        // once a stake matures, at most every ANON_FLUSH_INTERVAL seconds. With ANON_FLUSH off
+        // payouts are sent straight from the public balance.
+        let anon_flush: bool = gv_conf
+            .get("ANON_FLUSH")
+            .unwrap_or(&toml_Value::Boolean(true))
+            .as_bool()
+            .unwrap_or(true);
+        let anon_flush_threshold: u64 = gv_conf
+            .get("ANON_FLUSH_THRESHOLD")
+            .and_then(|threshold| threshold.as_integer())
+            .filter(|threshold| *threshold >= MIN_TX_VALUE as i64)
+            .map_or(MIN_TX_VALUE, |threshold| threshold as u64);
+        let anon_flush_interval: u64 = gv_conf
+            .get("ANON_FLUSH_INTERVAL")
+            .and_then(|interval| interval.as_integer())
+            .filter(|interval| *interval >= 0)
+            .map_or(0, |interval| interval as u64);
+        // Ring members for each anon input, and how many inputs go into one conversion
+        // transaction, 0 leaves it to MAX_TX_FEE
+        let anon_ring_size: u32 = gv_conf
+            .get("ANON_RING_SIZE")
+            .and_then(|size| size.as_integer())
+            .filter(|size| (MIN_RING_SIZE as i64..=MAX_RING_SIZE as i64).contains(size))
+            .map_or(DEFAULT_RING_SIZE, |size| size as u32);
+        let anon_batch_size: u64 = gv_conf
+            .get("ANON_BATCH_SIZE")
+            .and_then(|size| size.as_integer())
+            .filter(|size| *size >= 0)
+            .map_or(0, |size| size as u64);
+        // Monitor the vault behind EXT_PUB_KEY from a wallet without private keys, nothing is
+        // ever spent so payouts, zaps and UTXO management are turned off
+        let watch_only: bool = gv_conf
+            .get("WATCH_ONLY")
+            .unwrap_or(&toml_Value::Boolean(false))
+            .as_bool()
+            .unwrap_or(false);
+        // Where the WebSocket event stream listens, left empty it isn't started
+        let events_address: Option<String> = gv_conf
+            .get("EVENTS_ADDRESS")
             .unwrap_or(&toml_Value::String(String::new()))
             .clone()
             .empty_as_none();
+        // Label put on every reward payout and sent along as the transaction comment
+        let payout_label: Option<String> = gv_conf
+            .get("PAYOUT_LABEL")
+            .unwrap_or(&toml_Value::String(String::new()))
+            .clone()
+            .empty_as_none();
+        // Where the gRPC service listens in builds with the grpc feature, left empty it isn't started
+        let grpc_address: Option<String> = gv_conf
+            .get("GRPC_ADDRESS")
+            .unwrap_or(&toml_Value::String(String::new()))
+            .clone()
+            .empty_as_none();
+        // Link templates for notifications, one that won't make a valid link falls back to ghostscan
+        let explorer_tx_url: String = explorer_url_template(
+            gv_conf.get("EXPLORER_TX_URL").and_then(|url| url.as_str()),
+            "{txid}",
+            DEFAULT_EXPLORER_TX_URL,
+        );
+        let explorer_block_url: String = explorer_url_template(
+            gv_conf
+                .get("EXPLORER_BLOCK_URL")
+                .and_then(|url| url.as_str()),
+            "{hash}",
+            DEFAULT_EXPLORER_BLOCK_URL,
+        );
+
+        // Release download URLs tried in order, each holding v<version>/ like the GitHub releases
+        let daemon_mirrors: Vec<String> = gv_conf
+            .get("DAEMON_MIRRORS")
+            .and_then(|mirrors| mirrors.clone().try_into::<Vec<String>>().ok())
+            .filter(|mirrors| !mirrors.is_empty())
+            .unwrap_or(vec![DAEMON_BASE_URL.to_string()]);
+        let daemon_verify: DaemonVerify = gv_conf
+            .get("DAEMON_VERIFY")
+            .and_then(|verify| verify.as_str())
+            .and_then(DaemonVerify::from_name)
+            .unwrap_or(DaemonVerify::None);
+        let daemon_signing_key: String = gv_conf
+            .get("DAEMON_SIGNING_KEY")
+            .and_then(|key| key.as_str())
+            .unwrap_or_default()
+            .to_string();
+        // MiB of blocks ghostd keeps, 0 runs a full node
+        let daemon_prune: u64 = gv_conf
+            .get("DAEMON_PRUNE")
+            .and_then(|prune| prune.as_integer())
+            .filter(|prune| *prune > 0)
+            .map_or(0, |prune| (prune as u64).max(MIN_DAEMON_PRUNE));
+        let bootstrap_url: String = gv_conf
+            .get("BOOTSTRAP_URL")
+            .and_then(|url| url.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let bootstrap_sha256: String = gv_conf
+            .get("BOOTSTRAP_SHA256")
+            .and_then(|sha256| sha256.as_str())
+            .unwrap_or_default()
+            .to_lowercase();
+        // Resyncs start from the bootstrap too, instead of syncing from genesis
+        let bootstrap_on_resync: bool = gv_conf
+            .get("BOOTSTRAP_ON_RESYNC")
+            .and_then(|on_resync| on_resync.as_bool())
+            .unwrap_or(false);
 
         let config = GVConfig {
             bot_token,
@@ -272,13 +1073,26 @@ impl GVConfig {
             rpc_user,
             rpc_pass,
             cli_address,
+            cli_tls_cert,
+            cli_tls_key,
             gv_home,
+            db_path,
             config_file,
             daemon_data_dir,
             daemon_path,
             daemon_hash,
+            system_daemon,
+            confirm_daemon_update,
+            update_channel,
+            vault_name,
+            tg_vaults,
+            bot_relay,
+            tg_channel,
+            announce_webhook_url,
+            announce_email,
             min_reward_payout,
             mnemonic,
+            sealed_mnemonic,
             reward_interval,
             zmq_block_host,
             zmq_tx_host,
@@ -286,6 +1100,66 @@ impl GVConfig {
             announce_zaps,
             announce_rewards,
             timezone,
+            maint_window_start,
+            maint_window_end,
+            backup_dir,
+            backup_interval,
+            backup_retention,
+            backup_remote_url,
+            backup_s3_endpoint,
+            backup_s3_region,
+            backup_remote_access_key,
+            backup_remote_secret_key,
+            backup_sftp_key,
+            backup_remote_retention,
+            chain_split_timeout,
+            cache_ttl_blockchaininfo,
+            cache_ttl_stakinginfo,
+            cache_ttl_coldstakinginfo,
+            cache_ttl_networkinfo,
+            explorer_apis,
+            explorer_tx_url,
+            explorer_block_url,
+            daemon_mirrors,
+            daemon_verify,
+            daemon_signing_key,
+            daemon_prune,
+            bootstrap_url,
+            bootstrap_sha256,
+            bootstrap_on_resync,
+            local_chain_check,
+            missed_stake_factor,
+            stake_confirmations,
+            zap_confirmations,
+            payout_safe_depth,
+            balance_sample_interval,
+            disk_alert_gb,
+            docker_max_restarts,
+            log: LogSettings::from_toml(&gv_conf),
+            hooks: HookSettings::from_toml(&gv_conf),
+            chain_peers,
+            fee_policy,
+            fee_rate,
+            fee_conf_target,
+            max_tx_fee,
+            split_utxos,
+            split_threshold,
+            split_count,
+            split_interval,
+            anon_flush,
+            anon_flush_threshold,
+            anon_flush_interval,
+            anon_ring_size,
+            anon_batch_size,
+            watch_only,
+            events_address,
+            payout_label,
+            grpc_address,
+            digest_daily,
+            digest_weekly,
+            digest_hour,
+            reward_address_whitelist,
+            reward_change_delay,
         };
 
         Ok(config)
@@ -307,8 +1181,13 @@ impl GVConfig {
 
         let mut missing_keys: Vec<(&str, &str)> = Vec::new();
 
+        // rpcauth stands in for the credentials once they've moved into the encrypted config
+        let has_rpc_auth: bool = daemon_conf.get("rpcauth").is_some();
+
         for (index, &(key, _)) in required_keys.iter().enumerate() {
-            if daemon_conf.get(key).is_none() {
+            let credential: bool = key == "rpcuser" || key == "rpcpassword";
+
+            if daemon_conf.get(key).is_none() && !(credential && has_rpc_auth) {
                 missing_keys.push(required_keys[index])
             }
         }
@@ -327,9 +1206,235 @@ impl GVConfig {
             }
         }
 
+        let prune_change: PruneChange = self.prune_change().map_err(|err| err.to_string())?;
+
+        if prune_change != PruneChange::None {
+            info!("DAEMON_PRUNE changed, updating ghost.conf...");
+            daemon.stop_daemon().await.map_err(|err| err.to_string())?;
+            self.write_daemon_prune().map_err(|err| err.to_string())?;
+
+            // ghostd won't start unpruned on top of pruned blocks
+            if prune_change == PruneChange::Unprune {
+                info!("Removing the pruned blocks, ghostd will download the whole chain again");
+                file_ops::rm_dir(&self.daemon_data_dir.join("blocks/"))?;
+                file_ops::rm_dir(&self.daemon_data_dir.join("chainstate/"))?;
+            }
+        }
+
         Ok(())
     }
 
+    pub fn prune_change(&self) -> Result<PruneChange, Box<dyn Error + Send + Sync>> {
+        let daemon_conf_file: PathBuf = self.daemon_data_dir.join(DAEMON_SETTINGS_FILE);
+        let daemon_conf: json_Value = file_ops::ghost_config_to_value(&daemon_conf_file)?;
+        let current_prune: u64 = daemon_conf
+            .get("prune")
+            .and_then(|prune| prune.as_u64())
+            .unwrap_or(0);
+
+        let change: PruneChange = if self.daemon_prune > 0 {
+            if current_prune != self.daemon_prune
+                || daemon_conf.get("addressindex").is_some()
+                || daemon_conf.get("txindex").is_some()
+            {
+                PruneChange::Prune
+            } else {
+                PruneChange::None
+            }
+        } else if current_prune > 0 {
+            PruneChange::Unprune
+        } else {
+            PruneChange::None
+        };
+
+        Ok(change)
+    }
+
+    // ghostd only reads this on start, it has to be restarted to pick it up.
+    pub fn write_daemon_prune(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let daemon_conf_file: PathBuf = self.daemon_data_dir.join(DAEMON_SETTINGS_FILE);
+
+        if self.daemon_prune > 0 {
+            let prune: String = self.daemon_prune.to_string();
+            file_ops::update_ghost_config(&daemon_conf_file, "prune", Some(&prune))?;
+            file_ops::update_ghost_config(&daemon_conf_file, "addressindex", None)?;
+            file_ops::update_ghost_config(&daemon_conf_file, "txindex", None)?;
+        } else {
+            file_ops::update_ghost_config(&daemon_conf_file, "prune", None)?;
+            file_ops::update_ghost_config(&daemon_conf_file, "addressindex", Some("1"))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn fee_settings(&self) -> FeeSettings {
+        FeeSettings {
+            policy: self.fee_policy,
+            rate: self.fee_rate,
+            conf_target: self.fee_conf_target,
+            max_fee: self.max_tx_fee,
+        }
+    }
+
+    pub fn anon_policy(&self) -> AnonPolicy {
+        AnonPolicy {
+            enabled: self.anon_flush,
+            threshold: self.anon_flush_threshold,
+            interval: self.anon_flush_interval,
+            ring_size: self.anon_ring_size,
+            batch_size: self.anon_batch_size,
+        }
+    }
+
+    pub fn bootstrap_source(&self) -> Option<BootstrapSource> {
+        if self.bootstrap_url.is_empty() {
+            return None;
+        }
+
+        Some(BootstrapSource {
+            url: self.bootstrap_url.clone(),
+            sha256: self.bootstrap_sha256.clone(),
+        })
+    }
+
+    pub fn daemon_source(&self) -> DaemonSource {
+        DaemonSource {
+            mirrors: self.daemon_mirrors.clone(),
+            channel: self.update_channel.clone(),
+            verify: self.daemon_verify,
+            signing_key: self.daemon_signing_key.clone(),
+        }
+    }
+
+    // Take on the settings from a freshly read config that are looked up each time they're used.
+    // The rest were handed to listeners, clients and tasks at startup, those keep their running
+    // value and are only reported. Wallet state GhostVault writes itself is left alone.
+    pub fn apply_reload(&mut self, new: GVConfig) -> ConfigReload {
+        let mut reload: ConfigReload = ConfigReload::default();
+
+        macro_rules! live {
+            ($($field:ident => $key:literal),* $(,)?) => {
+                $(
+                    if self.$field != new.$field {
+                        self.$field = new.$field;
+                        reload.applied.push($key.to_string());
+                    }
+                )*
+            };
+        }
+
+        macro_rules! on_restart {
+            ($($field:ident => $key:literal),* $(,)?) => {
+                $(
+                    if self.$field != new.$field {
+                        reload.restart_required.push($key.to_string());
+                    }
+                )*
+            };
+        }
+
+        live!(
+            vault_name => "VAULT_NAME",
+            tg_vaults => "TG_VAULTS",
+            bot_relay => "BOT_RELAY",
+            tg_channel => "TG_CHANNEL",
+            announce_webhook_url => "ANNOUNCE_WEBHOOK_URL",
+            announce_email => "ANNOUNCE_EMAIL",
+            announce_stakes => "ANNOUNCE_STAKES",
+            announce_zaps => "ANNOUNCE_ZAPS",
+            announce_rewards => "ANNOUNCE_REWARDS",
+            min_reward_payout => "MIN_REWARD_PAYOUT",
+            reward_interval => "REWARD_INTERVAL",
+            reward_address_whitelist => "REWARD_ADDRESS_WHITELIST",
+            reward_change_delay => "REWARD_CHANGE_DELAY",
+            payout_label => "PAYOUT_LABEL",
+            timezone => "TIMEZONE",
+            maint_window_start => "MAINT_WINDOW_START",
+            maint_window_end => "MAINT_WINDOW_END",
+            digest_daily => "DIGEST_DAILY",
+            digest_weekly => "DIGEST_WEEKLY",
+            digest_hour => "DIGEST_HOUR",
+            backup_dir => "BACKUP_DIR",
+            backup_interval => "BACKUP_INTERVAL",
+            backup_retention => "BACKUP_RETENTION",
+            backup_remote_url => "BACKUP_REMOTE_URL",
+            backup_s3_endpoint => "BACKUP_S3_ENDPOINT",
+            backup_s3_region => "BACKUP_S3_REGION",
+            backup_remote_access_key => "BACKUP_REMOTE_ACCESS_KEY",
+            backup_remote_secret_key => "BACKUP_REMOTE_SECRET_KEY",
+            backup_sftp_key => "BACKUP_SFTP_KEY",
+            backup_remote_retention => "BACKUP_REMOTE_RETENTION",
+            balance_sample_interval => "BALANCE_SAMPLE_INTERVAL",
+            split_utxos => "SPLIT_UTXOS",
+            split_threshold => "SPLIT_THRESHOLD",
+            split_count => "SPLIT_COUNT",
+            split_interval => "SPLIT_INTERVAL",
+            anon_flush => "ANON_FLUSH",
+            anon_flush_threshold => "ANON_FLUSH_THRESHOLD",
+            anon_flush_interval => "ANON_FLUSH_INTERVAL",
+            anon_ring_size => "ANON_RING_SIZE",
+            anon_batch_size => "ANON_BATCH_SIZE",
+            fee_policy => "FEE_POLICY",
+            fee_rate => "FEE_RATE",
+            fee_conf_target => "FEE_CONF_TARGET",
+            max_tx_fee => "MAX_TX_FEE",
+            chain_split_timeout => "CHAIN_SPLIT_TIMEOUT",
+            cache_ttl_blockchaininfo => "CACHE_TTL_BLOCKCHAININFO",
+            cache_ttl_stakinginfo => "CACHE_TTL_STAKINGINFO",
+            cache_ttl_coldstakinginfo => "CACHE_TTL_COLDSTAKINGINFO",
+            cache_ttl_networkinfo => "CACHE_TTL_NETWORKINFO",
+            explorer_apis => "EXPLORER_APIS",
+            explorer_tx_url => "EXPLORER_TX_URL",
+            explorer_block_url => "EXPLORER_BLOCK_URL",
+            daemon_mirrors => "DAEMON_MIRRORS",
+            daemon_verify => "DAEMON_VERIFY",
+            daemon_signing_key => "DAEMON_SIGNING_KEY",
+            confirm_daemon_update => "CONFIRM_DAEMON_UPDATE",
+            update_channel => "UPDATE_CHANNEL",
+            bootstrap_url => "BOOTSTRAP_URL",
+            bootstrap_sha256 => "BOOTSTRAP_SHA256",
+            bootstrap_on_resync => "BOOTSTRAP_ON_RESYNC",
+            missed_stake_factor => "MISSED_STAKE_FACTOR",
+            stake_confirmations => "STAKE_CONFIRMATIONS",
+            zap_confirmations => "ZAP_CONFIRMATIONS",
+            payout_safe_depth => "PAYOUT_SAFE_DEPTH",
+            disk_alert_gb => "DISK_ALERT_GB",
+            docker_max_restarts => "DOCKER_MAX_RESTARTS",
+            zmq_block_host => "ghost.conf zmqpubhashblock",
+            zmq_tx_host => "ghost.conf zmqpubhashwtx",
+        );
+
+        on_restart!(
+            bot_token => "TELOXIDE_TOKEN",
+            tg_user => "TELEGRAM_USER",
+            cli_address => "CLI_ADDRESS",
+            cli_tls_cert => "CLI_TLS_CERT",
+            cli_tls_key => "CLI_TLS_KEY",
+            events_address => "EVENTS_ADDRESS",
+            grpc_address => "GRPC_ADDRESS",
+            rpc_wallet => "RPC_WALLET",
+            rpc_wallet_hot => "RPC_WALLET_HOT",
+            db_path => "DB_PATH",
+            daemon_path => "DAEMON_PATH",
+            system_daemon => "SYSTEM_DAEMON",
+            daemon_prune => "DAEMON_PRUNE",
+            local_chain_check => "LOCAL_CHAIN_CHECK",
+            chain_peers => "CHAIN_PEERS",
+            watch_only => "WATCH_ONLY",
+            reward_address => "REWARD_ADDRESS",
+            anon_mode => "ANON_MODE",
+            anon_reward_address => "ANON_REWARD_ADDRESS",
+            log => "LOG_*",
+            hooks => "HOOKS",
+            rpc_host => "ghost.conf rpcbind",
+            rpc_port => "ghost.conf rpcport",
+            rpc_user => "RPC_USER",
+            rpc_pass => "RPC_PASSWORD",
+        );
+
+        reload
+    }
+
     pub fn update_gv_config(
         &mut self,
         field_name: &str,
@@ -340,7 +1445,24 @@ impl GVConfig {
             "teloxide_token" => self.bot_token = new_value.empty_as_none(),
             "telegram_user" => self.tg_user = new_value.empty_as_none(),
             "rpc_wallet" => self.rpc_wallet = new_value.to_string(),
+            "rpc_wallet_hot" => {
+                self.rpc_wallet_hot = if new_value.is_empty() {
+                    DEFAULT_HOT_WALLET.to_string()
+                } else {
+                    new_value.to_string()
+                }
+            }
             "cli_address" => self.cli_address = new_value.to_string(),
+            "cli_tls_cert" => {
+                self.cli_tls_cert = new_value
+                    .empty_as_none()
+                    .map(|path| file_ops::expand_user(&path))
+            }
+            "cli_tls_key" => {
+                self.cli_tls_key = new_value
+                    .empty_as_none()
+                    .map(|path| file_ops::expand_user(&path))
+            }
             "ext_pub_key" => self.ext_pub_key = new_value.empty_as_none(),
             "ext_pub_key_label" => self.ext_pub_key_label = new_value.empty_as_none(),
             "reward_address" => self.reward_address = new_value.empty_as_none(),
@@ -356,6 +1478,7 @@ impl GVConfig {
                     .map_err(|_| "Invalid value for min_payout")?
             }
             "mnemonic" => self.mnemonic = new_value.empty_as_none(),
+            "sealed_mnemonic" => self.sealed_mnemonic = new_value.empty_as_none(),
             "anon_mode" => {
                 self.anon_mode = if new_value.to_lowercase().contains("true") {
                     true
@@ -366,6 +1489,234 @@ impl GVConfig {
             "anon_reward_address" => self.anon_reward_address = new_value.empty_as_none(),
             "daemon_path" => self.daemon_path = PathBuf::from(new_value),
             "daemon_hash" => self.daemon_hash = new_value.empty_as_none(),
+            "db_path" => {
+                self.db_path = if new_value.is_empty() {
+                    self.gv_home.join(DEFAULT_DB_DIR)
+                } else {
+                    file_ops::expand_user(new_value)
+                }
+            }
+            "system_daemon" => self.system_daemon = new_value.to_lowercase().contains("true"),
+            "confirm_daemon_update" => {
+                self.confirm_daemon_update = new_value.to_lowercase().contains("true")
+            }
+            "vault_name" => {
+                self.vault_name = if new_value.is_empty() {
+                    "local".to_string()
+                } else {
+                    new_value.to_string()
+                }
+            }
+            "bot_relay" => self.bot_relay = new_value.to_lowercase().contains("true"),
+            "local_chain_check" => {
+                self.local_chain_check = new_value.to_lowercase().contains("true")
+            }
+            "backup_dir" => {
+                self.backup_dir = if new_value.is_empty() {
+                    self.gv_home.join(DEFAULT_BACKUP_DIR)
+                } else {
+                    file_ops::expand_user(new_value)
+                }
+            }
+            "backup_interval" => {
+                self.backup_interval = new_value
+                    .parse::<u64>()
+                    .map_err(|_| "Invalid value for backup_interval")?
+            }
+            "balance_sample_interval" => {
+                self.balance_sample_interval = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|interval| *interval > 0)
+                    .ok_or("Invalid value for balance_sample_interval")?
+            }
+            "fee_policy" => {
+                self.fee_policy =
+                    FeePolicy::from_name(new_value).ok_or("Invalid value for fee_policy")?
+            }
+            "fee_rate" => {
+                self.fee_rate = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|rate| *rate > 0)
+                    .ok_or("Invalid value for fee_rate")?
+            }
+            "fee_conf_target" => {
+                self.fee_conf_target = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|target| *target > 0 && *target <= MAX_FEE_CONF_TARGET)
+                    .ok_or("Invalid value for fee_conf_target")?
+            }
+            "max_tx_fee" => {
+                self.max_tx_fee = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|fee| *fee > 0)
+                    .ok_or("Invalid value for max_tx_fee")?
+            }
+            "split_utxos" => self.split_utxos = new_value.to_lowercase().contains("true"),
+            "anon_flush" => self.anon_flush = new_value.to_lowercase().contains("true"),
+            "anon_flush_threshold" => {
+                self.anon_flush_threshold = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|threshold| *threshold >= MIN_TX_VALUE)
+                    .ok_or("Invalid value for anon_flush_threshold")?
+            }
+            "anon_flush_interval" => {
+                self.anon_flush_interval = new_value
+                    .parse::<u64>()
+                    .map_err(|_| "Invalid value for anon_flush_interval")?
+            }
+            "anon_ring_size" => {
+                self.anon_ring_size = new_value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|size| (MIN_RING_SIZE..=MAX_RING_SIZE).contains(size))
+                    .ok_or("Invalid value for anon_ring_size")?
+            }
+            "anon_batch_size" => {
+                self.anon_batch_size = new_value
+                    .parse::<u64>()
+                    .map_err(|_| "Invalid value for anon_batch_size")?
+            }
+            "watch_only" => self.watch_only = new_value.to_lowercase().contains("true"),
+            "digest_daily" => self.digest_daily = new_value.to_lowercase().contains("true"),
+            "digest_weekly" => self.digest_weekly = new_value.to_lowercase().contains("true"),
+            "digest_hour" => {
+                self.digest_hour = new_value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|hour| *hour < 24)
+                    .ok_or("Invalid value for digest_hour, must be an hour from 0 to 23")?
+            }
+            "split_threshold" => {
+                self.split_threshold = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|threshold| *threshold > 0)
+                    .ok_or("Invalid value for split_threshold")?
+            }
+            "split_count" => {
+                self.split_count = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|count| *count >= 2 && *count <= MAX_SPLIT_COUNT)
+                    .ok_or("Invalid value for split_count")?
+            }
+            "split_interval" => {
+                self.split_interval = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|interval| *interval > 0)
+                    .ok_or("Invalid value for split_interval")?
+            }
+            "missed_stake_factor" => {
+                self.missed_stake_factor = new_value
+                    .parse::<u64>()
+                    .map_err(|_| "Invalid value for missed_stake_factor")?
+            }
+            "stake_confirmations" => {
+                self.stake_confirmations = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|confirms| *confirms > 0)
+                    .ok_or("Invalid value for stake_confirmations")?
+            }
+            "zap_confirmations" => {
+                self.zap_confirmations = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|confirms| *confirms > 0)
+                    .ok_or("Invalid value for zap_confirmations")?
+            }
+            "payout_safe_depth" => {
+                self.payout_safe_depth = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|depth| *depth > 0)
+                    .ok_or("Invalid value for payout_safe_depth")?
+            }
+            "disk_alert_gb" => {
+                self.disk_alert_gb = new_value
+                    .parse::<u64>()
+                    .map_err(|_| "Invalid value for disk_alert_gb")?
+            }
+            "docker_max_restarts" => {
+                self.docker_max_restarts = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|max_restarts| *max_restarts > 0)
+                    .ok_or("Invalid value for docker_max_restarts")?
+            }
+            "chain_split_timeout" => {
+                self.chain_split_timeout = new_value
+                    .parse::<u64>()
+                    .map_err(|_| "Invalid value for chain_split_timeout")?
+            }
+            "cache_ttl_blockchaininfo" => {
+                self.cache_ttl_blockchaininfo = new_value
+                    .parse::<u64>()
+                    .map_err(|_| "Invalid value for cache_ttl_blockchaininfo")?
+            }
+            "cache_ttl_stakinginfo" => {
+                self.cache_ttl_stakinginfo = new_value
+                    .parse::<u64>()
+                    .map_err(|_| "Invalid value for cache_ttl_stakinginfo")?
+            }
+            "cache_ttl_coldstakinginfo" => {
+                self.cache_ttl_coldstakinginfo = new_value
+                    .parse::<u64>()
+                    .map_err(|_| "Invalid value for cache_ttl_coldstakinginfo")?
+            }
+            "cache_ttl_networkinfo" => {
+                self.cache_ttl_networkinfo = new_value
+                    .parse::<u64>()
+                    .map_err(|_| "Invalid value for cache_ttl_networkinfo")?
+            }
+            "tg_channel" => self.tg_channel = new_value.empty_as_none(),
+            "announce_webhook_url" => self.announce_webhook_url = new_value.empty_as_none(),
+            "announce_email" => self.announce_email = new_value.empty_as_none(),
+            "events_address" => self.events_address = new_value.empty_as_none(),
+            "payout_label" => self.payout_label = new_value.empty_as_none(),
+            "grpc_address" => self.grpc_address = new_value.empty_as_none(),
+            "backup_remote_url" => self.backup_remote_url = new_value.empty_as_none(),
+            "backup_s3_endpoint" => {
+                self.backup_s3_endpoint = if new_value.is_empty() {
+                    DEFAULT_S3_ENDPOINT.to_string()
+                } else {
+                    new_value.to_string()
+                }
+            }
+            "backup_s3_region" => {
+                self.backup_s3_region = if new_value.is_empty() {
+                    DEFAULT_S3_REGION.to_string()
+                } else {
+                    new_value.to_string()
+                }
+            }
+            "backup_remote_access_key" => self.backup_remote_access_key = new_value.empty_as_none(),
+            "backup_remote_secret_key" => self.backup_remote_secret_key = new_value.empty_as_none(),
+            "backup_sftp_key" => {
+                self.backup_sftp_key = new_value
+                    .empty_as_none()
+                    .map(|key| file_ops::expand_user(&key).display().to_string())
+            }
+            "backup_remote_retention" => {
+                self.backup_remote_retention = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|keep| *keep > 0)
+                    .ok_or("Invalid value for backup_remote_retention, must keep at least 1")?
+            }
+            "backup_retention" => {
+                self.backup_retention = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|keep| *keep > 0)
+                    .ok_or("Invalid value for backup_retention, must keep at least 1")?
+            }
             "announce_stakes" => {
                 self.announce_stakes = if new_value.to_lowercase().contains("true") {
                     true
@@ -388,6 +1739,115 @@ impl GVConfig {
                 }
             }
             "timezone" => self.timezone = new_value.to_string(),
+            "explorer_apis" => {
+                let apis: Vec<String> = explorer_api_list(new_value);
+
+                self.explorer_apis = if apis.is_empty() {
+                    DEFAULT_EXPLORER_APIS
+                        .iter()
+                        .map(|api| api.to_string())
+                        .collect()
+                } else {
+                    apis
+                }
+            }
+            "daemon_mirrors" => {
+                let mirrors: Vec<String> = explorer_api_list(new_value);
+
+                self.daemon_mirrors = if mirrors.is_empty() {
+                    vec![DAEMON_BASE_URL.to_string()]
+                } else {
+                    mirrors
+                }
+            }
+            "daemon_verify" => {
+                self.daemon_verify =
+                    DaemonVerify::from_name(new_value).ok_or("Invalid value for daemon_verify")?
+            }
+            "daemon_signing_key" => self.daemon_signing_key = new_value.to_string(),
+            "update_channel" => {
+                self.update_channel = UpdateChannel::from_name(new_value).ok_or(
+                    "Invalid value for update_channel, use STABLE, TESTING or a ghostd version",
+                )?
+            }
+            "bootstrap_url" => {
+                if !new_value.is_empty() && url::Url::parse(new_value).is_err() {
+                    return Err("Invalid bootstrap URL".into());
+                }
+                self.bootstrap_url = new_value.to_string()
+            }
+            "bootstrap_sha256" => {
+                if !new_value.is_empty()
+                    && (new_value.len() != 64 || !new_value.chars().all(|c| c.is_ascii_hexdigit()))
+                {
+                    return Err("Invalid bootstrap SHA256, it must be 64 hex characters".into());
+                }
+                self.bootstrap_sha256 = new_value.to_lowercase()
+            }
+            "bootstrap_on_resync" => self.bootstrap_on_resync = new_value.to_lowercase() == "true",
+            "log_level" => {
+                if !valid_log_level(new_value) {
+                    return Err(
+                        "Invalid log level, use off, error, warn, info, debug or trace".into(),
+                    );
+                }
+                self.log.level = new_value.to_lowercase()
+            }
+            "log_file_size_mb" => {
+                self.log.file_size_mb = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|size| *size > 0)
+                    .ok_or("Invalid value for log_file_size_mb")?
+            }
+            "log_file_count" => {
+                self.log.file_count = new_value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|count| *count > 0)
+                    .ok_or("Invalid value for log_file_count")?
+            }
+            "log_json" => self.log.json = new_value.to_lowercase() == "true",
+            "daemon_prune" => {
+                self.daemon_prune = new_value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|prune| *prune == 0 || *prune >= MIN_DAEMON_PRUNE)
+                    .ok_or("Invalid value for daemon_prune")?
+            }
+            "explorer_tx_url" => {
+                if !new_value.is_empty() && !valid_url_template(new_value, "{txid}") {
+                    return Err(
+                        "Invalid explorer tx URL, it must be a URL containing {txid}".into(),
+                    );
+                }
+
+                self.explorer_tx_url =
+                    explorer_url_template(Some(new_value), "{txid}", DEFAULT_EXPLORER_TX_URL)
+            }
+            "explorer_block_url" => {
+                if !new_value.is_empty() && !valid_url_template(new_value, "{hash}") {
+                    return Err(
+                        "Invalid explorer block URL, it must be a URL containing {hash}".into(),
+                    );
+                }
+
+                self.explorer_block_url =
+                    explorer_url_template(Some(new_value), "{hash}", DEFAULT_EXPLORER_BLOCK_URL)
+            }
+            "maint_window_start" | "maint_window_end" => {
+                let hour: u32 = new_value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|hour| *hour < 24)
+                    .ok_or("Invalid value for maintenance window, must be an hour from 0 to 23")?;
+
+                if field_name.to_lowercase() == "maint_window_start" {
+                    self.maint_window_start = hour
+                } else {
+                    self.maint_window_end = hour
+                }
+            }
             _ => {
                 return Err(format!("Invalid field name: {}", field_name).into());
             }
@@ -398,12 +1858,68 @@ impl GVConfig {
         let mut toml_value: toml_Value = toml::from_str(&toml_content)?;
 
         let field_value = match field_name.to_lowercase().as_str() {
-            "anon_mode" | "announce_stakes" | "announce_zaps" | "announce_rewards" => {
-                toml::Value::Boolean(new_value.to_lowercase() == "true")
-            }
-            "min_reward_payout" | "reward_interval" => {
-                toml::Value::Integer(new_value.parse::<i64>()?)
-            }
+            "anon_mode"
+            | "announce_stakes"
+            | "announce_zaps"
+            | "announce_rewards"
+            | "system_daemon"
+            | "confirm_daemon_update"
+            | "bot_relay"
+            | "local_chain_check"
+            | "split_utxos"
+            | "anon_flush"
+            | "watch_only"
+            | "digest_daily"
+            | "digest_weekly"
+            | "bootstrap_on_resync"
+            | "log_json" => toml::Value::Boolean(new_value.to_lowercase() == "true"),
+            "min_reward_payout"
+            | "reward_interval"
+            | "maint_window_start"
+            | "maint_window_end"
+            | "backup_interval"
+            | "backup_retention"
+            | "backup_remote_retention"
+            | "chain_split_timeout"
+            | "cache_ttl_blockchaininfo"
+            | "cache_ttl_stakinginfo"
+            | "cache_ttl_coldstakinginfo"
+            | "cache_ttl_networkinfo"
+            | "missed_stake_factor"
+            | "stake_confirmations"
+            | "zap_confirmations"
+            | "payout_safe_depth"
+            | "balance_sample_interval"
+            | "disk_alert_gb"
+            | "docker_max_restarts"
+            | "log_file_size_mb"
+            | "log_file_count"
+            | "fee_rate"
+            | "fee_conf_target"
+            | "max_tx_fee"
+            | "split_threshold"
+            | "split_count"
+            | "split_interval"
+            | "anon_flush_threshold"
+            | "anon_flush_interval"
+            | "anon_ring_size"
+            | "anon_batch_size"
+            | "daemon_prune"
+            | "digest_hour" => toml::Value::Integer(new_value.parse::<i64>()?),
+            "fee_policy" => toml::Value::String(new_value.to_uppercase()),
+            "log_level" => toml::Value::String(new_value.to_lowercase()),
+            "daemon_verify" => toml::Value::String(new_value.to_uppercase()),
+            "update_channel" => toml::Value::String(self.update_channel.as_str().to_string()),
+            "explorer_apis" | "daemon_mirrors" => toml::Value::Array(
+                explorer_api_list(new_value)
+                    .into_iter()
+                    .map(toml::Value::String)
+                    .collect(),
+            ),
+            field if is_sensitive_key(field) && !new_value.is_empty() => match config_key() {
+                Some(key) => toml::Value::String(encrypt_value(&key, new_value)?),
+                None => toml::Value::String(new_value.to_string()),
+            },
             _ => toml::Value::String(new_value.to_string()),
         };
 
@@ -423,3 +1939,474 @@ impl GVConfig {
         Ok(self.clone())
     }
 }
+
+// An explorer link template, or the default when it's unset or wouldn't make a valid link.
+fn explorer_url_template(template: Option<&str>, placeholder: &str, default: &str) -> String {
+    match template {
+        Some(template) if valid_url_template(template, placeholder) => template.to_string(),
+        Some(template) if !template.is_empty() => {
+            warn!(
+                "Explorer URL {} is missing {} or invalid, using {}",
+                template, placeholder, default
+            );
+            default.to_string()
+        }
+        _ => default.to_string(),
+    }
+}
+
+fn valid_url_template(template: &str, placeholder: &str) -> bool {
+    template.contains(placeholder) && url::Url::parse(&template.replace(placeholder, "0")).is_ok()
+}
+
+// Comma separated URLs, in the order they should be tried.
+fn explorer_api_list(apis: &str) -> Vec<String> {
+    apis.split(',')
+        .map(|api| api.trim().to_string())
+        .filter(|api| !api.is_empty())
+        .collect()
+}
+
+fn is_sensitive_key(field_name: &str) -> bool {
+    SENSITIVE_CONFIG_KEYS.contains(&field_name.to_uppercase().as_str())
+}
+
+fn config_key() -> Option<[u8; 32]> {
+    *CONFIG_KEY.read().unwrap()
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], Box<dyn Error + Send + Sync>> {
+    let mut key: [u8; 32] = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| format!("Failed to derive config key: {}", err))?;
+
+    Ok(key)
+}
+
+// Output is the 12 byte nonce followed by the AES-256-GCM ciphertext.
+fn seal_with_key(key: &[u8; 32], data: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    let cipher = Aes256Gcm::new(key.into());
+
+    let mut nonce_bytes: [u8; 12] = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+
+    let ciphertext: Vec<u8> = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), data)
+        .map_err(|_| "Failed to encrypt data")?;
+
+    let mut sealed: Vec<u8> = nonce_bytes.to_vec();
+    sealed.extend_from_slice(&ciphertext);
+
+    Ok(sealed)
+}
+
+fn open_with_key(key: &[u8; 32], sealed: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    if sealed.len() < 12 {
+        return Err("Encrypted data is too short".into());
+    }
+
+    let (nonce_bytes, ciphertext) = sealed.split_at(12);
+    let cipher = Aes256Gcm::new(key.into());
+
+    let data: Vec<u8> = cipher
+        .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| "Failed to decrypt data, wrong passphrase?")?;
+
+    Ok(data)
+}
+
+fn encrypt_value(key: &[u8; 32], plaintext: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let sealed: Vec<u8> = seal_with_key(key, plaintext.as_bytes())?;
+
+    Ok(format!(
+        "{}{}",
+        ENCRYPTED_VALUE_PREFIX,
+        BASE64.encode(&sealed)
+    ))
+}
+
+fn decrypt_value(key: &[u8; 32], value: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let encoded: &str = value
+        .strip_prefix(ENCRYPTED_VALUE_PREFIX)
+        .ok_or("Config value is not encrypted")?;
+    let sealed: Vec<u8> = BASE64.decode(encoded.as_bytes())?;
+
+    Ok(String::from_utf8(open_with_key(key, &sealed)?)?)
+}
+
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_VALUE_PREFIX)
+}
+
+// Sensitive values are decrypted when the config key is loaded, gv-cli never loads it and
+// sees them as unset.
+fn read_secret(gv_conf: &toml_Value, field_name: &str) -> Option<String> {
+    let value: String = gv_conf
+        .get(field_name)
+        .unwrap_or(&toml_Value::String(String::new()))
+        .clone()
+        .empty_as_none()?;
+
+    if !is_encrypted(&value) {
+        return Some(value);
+    }
+
+    match config_key().map(|key| decrypt_value(&key, &value)) {
+        Some(Ok(plaintext)) => plaintext.empty_as_none(),
+        Some(Err(err)) => {
+            warn!("Unable to decrypt {}: {}", field_name, err);
+            None
+        }
+        None => None,
+    }
+}
+
+fn read_settings(gv_home: &Path) -> Result<toml_Value, Box<dyn Error + Send + Sync>> {
+    let toml_content = std::fs::read_to_string(settings_path(gv_home))?;
+    Ok(toml::from_str(&toml_content)?)
+}
+
+fn write_settings(
+    gv_home: &Path,
+    toml_value: &toml_Value,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    std::fs::write(settings_path(gv_home), toml::to_string_pretty(toml_value)?)?;
+    Ok(())
+}
+
+fn settings_key(
+    toml_value: &toml_Value,
+    passphrase: &str,
+) -> Result<[u8; 32], Box<dyn Error + Send + Sync>> {
+    let salt: Vec<u8> = HEXLOWER.decode(
+        toml_value
+            .get("CONFIG_SALT")
+            .and_then(|salt| salt.as_str())
+            .unwrap_or("")
+            .as_bytes(),
+    )?;
+    let key: [u8; 32] = derive_key(passphrase, &salt)?;
+
+    let check: &str = toml_value
+        .get("CONFIG_CHECK")
+        .and_then(|check| check.as_str())
+        .unwrap_or("");
+
+    match decrypt_value(&key, check) {
+        Ok(plaintext) if plaintext == CONFIG_CHECK_PLAINTEXT => Ok(key),
+        _ => Err("Incorrect config passphrase".into()),
+    }
+}
+
+pub fn config_encrypted(gv_home: &Path) -> bool {
+    read_settings(gv_home)
+        .ok()
+        .and_then(|toml_value| {
+            toml_value
+                .get("CONFIG_SALT")
+                .and_then(|salt| salt.as_str())
+                .map(|salt| !salt.is_empty())
+        })
+        .unwrap_or(false)
+}
+
+// Load the config key for this process and encrypt any sensitive values still in plaintext,
+// such as a token set by hand or from the environment while GhostVault was stopped. With
+// RPC_AUTH set the RPC credentials in ghost.conf are moved in as well, leaving ghostd their
+// rpcauth hash.
+pub fn unlock_config(
+    gv_home: &Path,
+    daemon_data_dir: &Path,
+    passphrase: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut toml_value: toml_Value = read_settings(gv_home)?;
+    let key: [u8; 32] = settings_key(&toml_value, passphrase)?;
+
+    let daemon_conf_file: PathBuf = daemon_data_dir.join(DAEMON_SETTINGS_FILE);
+    let rpc_auth: Option<String> = if rpc_auth_enabled(&toml_value) {
+        take_rpc_credentials(&mut toml_value, &daemon_conf_file)?
+    } else {
+        warn_plaintext_rpc_password(&daemon_conf_file);
+        None
+    };
+    let mut migrated: bool = rpc_auth.is_some();
+
+    for field_name in SENSITIVE_CONFIG_KEYS {
+        let value: String = toml_value
+            .get(field_name)
+            .and_then(|value| value.as_str())
+            .unwrap_or("")
+            .to_string();
+
+        if !value.is_empty() && !is_encrypted(&value) {
+            toml_value.as_table_mut().unwrap().insert(
+                field_name.to_string(),
+                toml_Value::String(encrypt_value(&key, &value)?),
+            );
+            migrated = true;
+        }
+    }
+
+    if migrated {
+        info!("Encrypting plaintext secrets in the config...");
+        write_settings(gv_home, &toml_value)?;
+    }
+
+    // Only once the credentials are safely in the settings file
+    if let Some(rpc_auth) = rpc_auth {
+        let backup_file: PathBuf = daemon_conf_file.with_extension("conf.bak");
+        std::fs::copy(&daemon_conf_file, &backup_file)?;
+        warn!(
+            "Rewriting ghost.conf, rpcuser and rpcpassword are replaced by rpcauth. The original is at {}",
+            backup_file.display()
+        );
+
+        file_ops::update_ghost_config(&daemon_conf_file, "rpcauth", Some(&rpc_auth))?;
+        file_ops::update_ghost_config(&daemon_conf_file, "rpcuser", None)?;
+        file_ops::update_ghost_config(&daemon_conf_file, "rpcpassword", None)?;
+        info!("RPC credentials moved from ghost.conf into the encrypted config.");
+    }
+
+    *CONFIG_KEY.write().unwrap() = Some(key);
+
+    Ok(())
+}
+
+// Whether ghost.conf may be rewritten to hold only an rpcauth hash, tools reading rpcuser and
+// rpcpassword from it stop working after that.
+fn rpc_auth_enabled(toml_value: &toml_Value) -> bool {
+    toml_value
+        .get("RPC_AUTH")
+        .and_then(|enabled| enabled.as_bool())
+        .unwrap_or(false)
+}
+
+fn warn_plaintext_rpc_password(daemon_conf_file: &PathBuf) {
+    let has_password: bool = daemon_conf_file.exists()
+        && file_ops::ghost_config_to_value(daemon_conf_file)
+            .map(|daemon_conf| daemon_conf.get("rpcpassword").is_some())
+            .unwrap_or(false);
+
+    if has_password {
+        warn!("ghost.conf keeps the RPC password in plaintext, set RPC_AUTH = true to move it into the encrypted config.");
+    }
+}
+
+// Copies plaintext rpcuser and rpcpassword from ghost.conf into RPC_USER and RPC_PASSWORD, for
+// unlock_config to encrypt. Returns the rpcauth line that replaces them in ghost.conf.
+fn take_rpc_credentials(
+    toml_value: &mut toml_Value,
+    daemon_conf_file: &PathBuf,
+) -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+    if !daemon_conf_file.exists() {
+        return Ok(None);
+    }
+
+    let daemon_conf: json_Value = file_ops::ghost_config_to_value(daemon_conf_file)?;
+
+    // Numeric values come back from ghost.conf as numbers
+    let conf_str = |value: &json_Value| -> String {
+        value
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| value.to_string())
+    };
+
+    let (user, password) = match (daemon_conf.get("rpcuser"), daemon_conf.get("rpcpassword")) {
+        (Some(user), Some(password)) => (conf_str(user), conf_str(password)),
+        _ => return Ok(None),
+    };
+
+    let table = toml_value.as_table_mut().unwrap();
+    table.insert("RPC_USER".to_string(), toml_Value::String(user.clone()));
+    table.insert(
+        "RPC_PASSWORD".to_string(),
+        toml_Value::String(password.clone()),
+    );
+
+    Ok(Some(rpc_auth(&user, &password)))
+}
+
+// The same user:salt$hmac line share/rpcauth/rpcauth.py makes, ghostd checks the password against
+// it without keeping the password itself.
+fn rpc_auth(user: &str, password: &str) -> String {
+    let mut salt: [u8; 16] = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let salt: String = HEXLOWER.encode(&salt);
+
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(salt.as_bytes())
+        .expect("HMAC accepts any key length");
+    mac.update(password.as_bytes());
+
+    format!(
+        "{}:{}${}",
+        user,
+        salt,
+        HEXLOWER.encode(&mac.finalize().into_bytes())
+    )
+}
+
+// Re-encrypt the sensitive values under a new passphrase. An empty new passphrase turns
+// encryption off and writes the values back in plaintext.
+pub fn set_config_passphrase(
+    gv_home: &Path,
+    old_passphrase: Option<&str>,
+    new_passphrase: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut toml_value: toml_Value = read_settings(gv_home)?;
+
+    let old_key: Option<[u8; 32]> = match old_passphrase {
+        Some(passphrase) => Some(settings_key(&toml_value, passphrase)?),
+        None => None,
+    };
+
+    let new_key: Option<[u8; 32]> = if new_passphrase.is_empty() {
+        None
+    } else {
+        let mut salt: [u8; 16] = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let key: [u8; 32] = derive_key(new_passphrase, &salt)?;
+
+        let table = toml_value.as_table_mut().unwrap();
+        table.insert(
+            "CONFIG_SALT".to_string(),
+            toml_Value::String(HEXLOWER.encode(&salt)),
+        );
+        table.insert(
+            "CONFIG_CHECK".to_string(),
+            toml_Value::String(encrypt_value(&key, CONFIG_CHECK_PLAINTEXT)?),
+        );
+
+        Some(key)
+    };
+
+    for field_name in SENSITIVE_CONFIG_KEYS {
+        let mut value: String = toml_value
+            .get(field_name)
+            .and_then(|value| value.as_str())
+            .unwrap_or("")
+            .to_string();
+
+        if is_encrypted(&value) {
+            let key: [u8; 32] =
+                old_key.ok_or("Config is encrypted, current passphrase required")?;
+            value = decrypt_value(&key, &value)?;
+        }
+
+        if let (Some(key), false) = (new_key, value.is_empty()) {
+            value = encrypt_value(&key, &value)?;
+        }
+
+        toml_value
+            .as_table_mut()
+            .unwrap()
+            .insert(field_name.to_string(), toml_Value::String(value));
+    }
+
+    if new_key.is_none() {
+        let table = toml_value.as_table_mut().unwrap();
+        table.insert("CONFIG_SALT".to_string(), toml_Value::String(String::new()));
+        table.insert(
+            "CONFIG_CHECK".to_string(),
+            toml_Value::String(String::new()),
+        );
+    }
+
+    write_settings(gv_home, &toml_value)?;
+
+    *CONFIG_KEY.write().unwrap() = new_key;
+
+    Ok(())
+}
+
+pub fn config_salt(gv_home: &Path) -> Option<Vec<u8>> {
+    let toml_value: toml_Value = read_settings(gv_home).ok()?;
+    let salt: &str = toml_value.get("CONFIG_SALT")?.as_str()?;
+
+    HEXLOWER
+        .decode(salt.as_bytes())
+        .ok()
+        .filter(|salt| !salt.is_empty())
+}
+
+// Seal the mnemonic with a passphrase of its own, the salt is stored with it.
+pub fn seal_mnemonic(
+    mnemonic: &str,
+    passphrase: &str,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let mut salt: [u8; 16] = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let key: [u8; 32] = derive_key(passphrase, &salt)?;
+    let sealed: Vec<u8> = seal_with_key(&key, mnemonic.as_bytes())?;
+
+    Ok(format!(
+        "{}{}:{}",
+        SEALED_VALUE_PREFIX,
+        HEXLOWER.encode(&salt),
+        BASE64.encode(&sealed)
+    ))
+}
+
+pub fn open_mnemonic(
+    sealed: &str,
+    passphrase: &str,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let (salt, data) = sealed
+        .strip_prefix(SEALED_VALUE_PREFIX)
+        .and_then(|sealed| sealed.split_once(':'))
+        .ok_or("Sealed mnemonic is malformed")?;
+
+    let salt: Vec<u8> = HEXLOWER.decode(salt.as_bytes())?;
+    let data: Vec<u8> = BASE64.decode(data.as_bytes())?;
+    let key: [u8; 32] = derive_key(passphrase, &salt)?;
+
+    Ok(String::from_utf8(open_with_key(&key, &data)?)?)
+}
+
+// Encrypt arbitrary data, such as a backup archive, with the loaded config key.
+pub fn seal_bytes(data: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    let key: [u8; 32] = config_key().ok_or("Config passphrase is not set")?;
+    seal_with_key(&key, data)
+}
+
+// Decrypt data sealed on possibly another machine, the salt travels with the data.
+pub fn open_bytes(
+    passphrase: &str,
+    salt: &[u8],
+    sealed: &[u8],
+) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    let key: [u8; 32] = derive_key(passphrase, salt)?;
+    open_with_key(&key, sealed)
+}
+
+// Passphrase for a non-interactive start, from the environment or the OS keyring.
+pub fn stored_config_passphrase() -> Option<String> {
+    if let Ok(passphrase) = std::env::var(CONFIG_PASSPHRASE_ENV) {
+        if !passphrase.is_empty() {
+            return Some(passphrase);
+        }
+    }
+
+    #[cfg(feature = "keyring")]
+    {
+        let entry = keyring::Entry::new("ghostvault", &whoami::username()).ok()?;
+        entry.get_password().ok()
+    }
+
+    #[cfg(not(feature = "keyring"))]
+    None
+}
+
+#[cfg(feature = "keyring")]
+pub fn store_keyring_passphrase(passphrase: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let entry = keyring::Entry::new("ghostvault", &whoami::username())?;
+
+    if passphrase.is_empty() {
+        let _ = entry.delete_credential();
+    } else {
+        entry.set_password(passphrase)?;
+    }
+
+    Ok(())
+}
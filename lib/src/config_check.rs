@@ -0,0 +1,480 @@
+// Validation behind ghostvaultd --check-config. Everything startup would otherwise trip over part
+// way in, or panic on, is checked up front and reported in one go. Nothing is started or written,
+// ghostd is only asked about addresses when it's already running.
+use crate::{
+    cli_tls,
+    config::{self, GVConfig},
+    constants::{DAEMON_SETTINGS_FILE, MIN_TX_VALUE},
+    file_ops,
+    gv_client_methods::FleetVault,
+    remote_backup,
+    rpc::{self, RPCURL},
+};
+use chrono_tz::Tz;
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    net::{SocketAddr, SocketAddrV4},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::net::TcpStream;
+use url::Url;
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConfigCheck {
+    pub key: String,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConfigReport {
+    pub settings_file: PathBuf,
+    pub daemon_config: PathBuf,
+    pub checks: Vec<ConfigCheck>,
+}
+
+impl ConfigReport {
+    fn push(&mut self, key: &str, status: CheckStatus, message: impl Into<String>) {
+        self.checks.push(ConfigCheck {
+            key: key.to_string(),
+            status,
+            message: message.into(),
+        });
+    }
+
+    fn ok(&mut self, key: &str, message: impl Into<String>) {
+        self.push(key, CheckStatus::Ok, message);
+    }
+
+    fn warn(&mut self, key: &str, message: impl Into<String>) {
+        self.push(key, CheckStatus::Warning, message);
+    }
+
+    fn error(&mut self, key: &str, message: impl Into<String>) {
+        self.push(key, CheckStatus::Error, message);
+    }
+
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| check.status == status)
+            .count()
+    }
+}
+
+pub async fn check_config(gv_home: &Path, daemon_data_dir: &Path) -> ConfigReport {
+    let mut report: ConfigReport = ConfigReport {
+        settings_file: config::settings_path(gv_home),
+        daemon_config: daemon_data_dir.join(DAEMON_SETTINGS_FILE),
+        checks: Vec::new(),
+    };
+
+    let conf: GVConfig = match load_config(gv_home, daemon_data_dir, &mut report) {
+        Some(conf) => conf,
+        None => return report,
+    };
+
+    check_addresses(&conf, &mut report);
+    check_schedule(&conf, &mut report);
+    check_daemon(&conf, &mut report);
+    check_notifications(&conf, &mut report);
+    check_files(&conf, &mut report);
+
+    let ghostd_running: bool = check_ghostd(&conf, &mut report).await;
+    check_zmq(&conf, ghostd_running, &mut report).await;
+    check_reward_addresses(&conf, ghostd_running, &mut report).await;
+
+    report
+}
+
+fn load_config(
+    gv_home: &Path,
+    daemon_data_dir: &Path,
+    report: &mut ConfigReport,
+) -> Option<GVConfig> {
+    let settings_file: PathBuf = report.settings_file.clone();
+    let settings_name: String = settings_file.display().to_string();
+
+    let content: String = match std::fs::read_to_string(&settings_file) {
+        Ok(content) => content,
+        Err(err) => {
+            report.error(&settings_name, format!("Unable to read: {}", err));
+            return None;
+        }
+    };
+
+    if let Err(err) = toml::from_str::<toml::Value>(&content) {
+        report.error(&settings_name, format!("Invalid TOML: {}", err));
+        return None;
+    }
+
+    report.ok(&settings_name, "Parsed");
+
+    let daemon_config: PathBuf = report.daemon_config.clone();
+    if daemon_config.exists() {
+        report.ok(&daemon_config.display().to_string(), "Found");
+    } else {
+        report.warn(
+            &daemon_config.display().to_string(),
+            "Missing, a default one is written on startup",
+        );
+    }
+
+    // GVConfig::new indexes into the settings in places, a panic there is a config error too.
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let loaded = panic::catch_unwind(AssertUnwindSafe(|| {
+        GVConfig::new(&gv_home.to_path_buf(), &daemon_data_dir.to_path_buf())
+            .map_err(|err| err.to_string())
+    }));
+    panic::set_hook(default_hook);
+
+    match loaded {
+        Ok(Ok(conf)) => Some(conf),
+        Ok(Err(err)) => {
+            report.error(&settings_name, format!("Unable to load: {}", err));
+            None
+        }
+        Err(panic) => {
+            let reason: String = panic
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| panic.downcast_ref::<&str>().map(|msg| msg.to_string()))
+                .unwrap_or_else(|| "unknown error".to_string());
+            report.error(&settings_name, format!("Unable to load: {}", reason));
+            None
+        }
+    }
+}
+
+fn check_addresses(conf: &GVConfig, report: &mut ConfigReport) {
+    // run_server only binds IPv4.
+    match conf.cli_address.parse::<SocketAddrV4>() {
+        Ok(addr) if !addr.ip().is_loopback() && conf.cli_tls_cert.is_none() => report.warn(
+            "CLI_ADDRESS",
+            format!("{} is reachable from the network without TLS", addr),
+        ),
+        Ok(addr) => report.ok("CLI_ADDRESS", addr.to_string()),
+        Err(_) => report.error(
+            "CLI_ADDRESS",
+            format!("'{}' is not an IPv4 address and port", conf.cli_address),
+        ),
+    }
+
+    for (key, address) in [
+        ("EVENTS_ADDRESS", &conf.events_address),
+        ("GRPC_ADDRESS", &conf.grpc_address),
+    ] {
+        match address.as_deref().map(|addr| addr.parse::<SocketAddr>()) {
+            None => (),
+            Some(Err(_)) => report.error(
+                key,
+                format!(
+                    "'{}' is not an address and port",
+                    address.as_deref().unwrap()
+                ),
+            ),
+            Some(Ok(_)) if key == "GRPC_ADDRESS" && !cfg!(feature = "grpc") => {
+                report.warn(key, "Ignored, this build has no grpc feature")
+            }
+            Some(Ok(addr)) => report.ok(key, addr.to_string()),
+        }
+    }
+
+    match (&conf.cli_tls_cert, &conf.cli_tls_key) {
+        (None, None) => (),
+        (Some(cert), Some(key)) => match cli_tls::acceptor(cert, key) {
+            Ok(_) => report.ok("CLI_TLS_CERT", "Certificate and key load"),
+            Err(err) => report.error("CLI_TLS_CERT", err.to_string()),
+        },
+        _ => report.error(
+            "CLI_TLS_CERT",
+            "CLI_TLS_CERT and CLI_TLS_KEY must be set together",
+        ),
+    }
+
+    let vaults = conf
+        .tg_vaults
+        .iter()
+        .map(|vault| ("TG_VAULTS", vault))
+        .chain(conf.chain_peers.iter().map(|vault| ("CHAIN_PEERS", vault)));
+    for (key, vault) in vaults {
+        check_vault(key, vault, report);
+    }
+
+    for api in &conf.explorer_apis {
+        if let Err(err) = Url::parse(api) {
+            report.error("EXPLORER_APIS", format!("'{}': {}", api, err));
+        }
+    }
+
+    for mirror in &conf.daemon_mirrors {
+        if let Err(err) = Url::parse(mirror) {
+            report.error("DAEMON_MIRRORS", format!("'{}': {}", mirror, err));
+        }
+    }
+
+    if conf.backup_remote_url.is_some() {
+        match remote_backup::check_target(conf) {
+            Ok(()) => report.ok("BACKUP_REMOTE_URL", "Valid"),
+            Err(err) => report.error("BACKUP_REMOTE_URL", err.to_string()),
+        }
+    }
+}
+
+fn check_vault(key: &str, vault: &FleetVault, report: &mut ConfigReport) {
+    let port = vault
+        .cli_address
+        .rsplit_once(':')
+        .and_then(|(host, port)| (!host.is_empty()).then(|| port.parse::<u16>()));
+
+    if !matches!(port, Some(Ok(_))) {
+        report.error(
+            key,
+            format!(
+                "{}: '{}' is not a host and port",
+                vault.name, vault.cli_address
+            ),
+        );
+    }
+
+    if let Some(cert) = &vault.tls_cert {
+        if !file_ops::expand_user(cert).exists() {
+            report.error(
+                key,
+                format!("{}: certificate {} not found", vault.name, cert),
+            );
+        }
+    }
+}
+
+fn check_schedule(conf: &GVConfig, report: &mut ConfigReport) {
+    match Tz::from_str_insensitive(&conf.timezone) {
+        Ok(tz) => report.ok("TIMEZONE", tz.name()),
+        Err(_) => report.error(
+            "TIMEZONE",
+            format!("'{}' is not a known timezone", conf.timezone),
+        ),
+    }
+
+    for (key, interval) in [
+        ("REWARD_INTERVAL", conf.reward_interval),
+        ("BACKUP_INTERVAL", conf.backup_interval),
+        ("BALANCE_SAMPLE_INTERVAL", conf.balance_sample_interval),
+        ("SPLIT_INTERVAL", conf.split_interval),
+    ] {
+        if interval == 0 {
+            report.error(key, "Must be more than 0 seconds");
+        }
+    }
+
+    for (key, hour) in [
+        ("MAINT_WINDOW_START", conf.maint_window_start),
+        ("MAINT_WINDOW_END", conf.maint_window_end),
+        ("DIGEST_HOUR", conf.digest_hour),
+    ] {
+        if hour > 23 {
+            report.error(key, format!("{} is not an hour of the day", hour));
+        }
+    }
+
+    if conf.min_reward_payout < MIN_TX_VALUE {
+        report.error(
+            "MIN_REWARD_PAYOUT",
+            format!("Must be at least {} sats", MIN_TX_VALUE),
+        );
+    }
+}
+
+fn check_daemon(conf: &GVConfig, report: &mut ConfigReport) {
+    if conf.system_daemon && !conf.daemon_path.exists() {
+        report.error(
+            "DAEMON_PATH",
+            format!(
+                "{} not found and SYSTEM_DAEMON is set",
+                conf.daemon_path.display()
+            ),
+        );
+    } else if !conf.daemon_path.exists() {
+        report.warn(
+            "DAEMON_PATH",
+            "ghostd not found, it will be downloaded on startup",
+        );
+    } else {
+        report.ok("DAEMON_PATH", conf.daemon_path.display().to_string());
+    }
+
+    if conf.rpc_user.is_empty() || conf.rpc_pass.is_empty() {
+        report.error(
+            DAEMON_SETTINGS_FILE,
+            "rpcuser and rpcpassword, or RPC_USER and RPC_PASSWORD, must be set",
+        );
+    }
+}
+
+fn check_notifications(conf: &GVConfig, report: &mut ConfigReport) {
+    // Bot tokens are <bot id>:<35 character secret>.
+    if let Some(token) = &conf.bot_token {
+        let valid: bool = token.split_once(':').is_some_and(|(id, secret)| {
+            !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) && secret.len() == 35
+        });
+
+        if !valid {
+            report.error("TELOXIDE_TOKEN", "Not a Telegram bot token");
+        } else if conf.tg_user.is_none() {
+            report.warn("TELEGRAM_USER", "Not set, the bot will answer nobody");
+        } else {
+            report.ok("TELOXIDE_TOKEN", "Looks valid");
+        }
+    }
+
+    if let Some(webhook) = &conf.announce_webhook_url {
+        if let Err(err) = Url::parse(webhook) {
+            report.error("ANNOUNCE_WEBHOOK_URL", err.to_string());
+        }
+    }
+}
+
+fn check_files(conf: &GVConfig, report: &mut ConfigReport) {
+    for (hook, scripts) in &conf.hooks.scripts {
+        for script in scripts {
+            if !script.is_file() {
+                report.error("HOOKS", format!("{}: {} not found", hook, script.display()));
+            }
+        }
+    }
+
+    if let Some(parent) = conf.backup_dir.parent() {
+        if !parent.exists() {
+            report.warn("BACKUP_DIR", format!("{} does not exist", parent.display()));
+        }
+    }
+}
+
+// Asks ghostd directly rather than through DaemonHelper, which would start it.
+async fn ghostd_call(conf: &GVConfig, args: &str) -> Option<Value> {
+    let rpcurl: RPCURL = RPCURL::default().target(
+        &conf.rpc_host,
+        &conf.rpc_port,
+        "",
+        &conf.rpc_user,
+        &conf.rpc_pass,
+    );
+
+    match tokio::time::timeout(
+        CHECK_TIMEOUT,
+        rpc::call(args, &rpcurl, &rpc::pooled_client()),
+    )
+    .await
+    {
+        Ok(Ok(value)) => Some(value),
+        _ => None,
+    }
+}
+
+async fn check_ghostd(conf: &GVConfig, report: &mut ConfigReport) -> bool {
+    let rpc_address: String = format!("{}:{}", conf.rpc_host, conf.rpc_port);
+
+    match ghostd_call(conf, "getblockcount").await {
+        Some(blocks) => {
+            report.ok(
+                "ghostd",
+                format!("Answering on {} at block {}", rpc_address, blocks),
+            );
+            true
+        }
+        None => {
+            report.warn(
+                "ghostd",
+                format!(
+                    "Not answering on {}, checks that need it are skipped",
+                    rpc_address
+                ),
+            );
+            false
+        }
+    }
+}
+
+// The ZMQ publishers belong to ghostd, so they're only expected to be up while it is. Without
+// them GhostVault falls back to polling ghostd, so it's a warning rather than an error.
+async fn check_zmq(conf: &GVConfig, ghostd_running: bool, report: &mut ConfigReport) {
+    for (key, endpoint) in [
+        ("zmqpubhashblock", &conf.zmq_block_host),
+        ("zmqpubhashwtx", &conf.zmq_tx_host),
+    ] {
+        let address: &str = match endpoint.strip_prefix("tcp://") {
+            Some(address) => address,
+            None => {
+                report.error(key, format!("'{}' is not a tcp:// endpoint", endpoint));
+                continue;
+            }
+        };
+
+        let reachable: bool = matches!(
+            tokio::time::timeout(CHECK_TIMEOUT, TcpStream::connect(address)).await,
+            Ok(Ok(_))
+        );
+
+        if reachable {
+            report.ok(key, format!("{} reachable", endpoint));
+        } else if ghostd_running {
+            report.warn(
+                key,
+                format!(
+                    "{} not reachable while ghostd is running, polling ghostd instead",
+                    endpoint
+                ),
+            );
+        } else {
+            report.warn(key, format!("{} not reachable", endpoint));
+        }
+    }
+}
+
+async fn check_reward_addresses(conf: &GVConfig, ghostd_running: bool, report: &mut ConfigReport) {
+    let addresses = conf
+        .reward_address
+        .iter()
+        .map(|addr| ("REWARD_ADDRESS", addr))
+        .chain(
+            conf.anon_reward_address
+                .iter()
+                .map(|addr| ("ANON_REWARD_ADDRESS", addr)),
+        )
+        .chain(
+            conf.reward_address_whitelist
+                .iter()
+                .map(|addr| ("REWARD_ADDRESS_WHITELIST", addr)),
+        );
+
+    for (key, address) in addresses {
+        if !ghostd_running {
+            report.warn(key, format!("{} not validated", address));
+            continue;
+        }
+
+        let valid: Option<bool> = ghostd_call(conf, &format!("validateaddress {}", address))
+            .await
+            .and_then(|info| info.get("isvalid").and_then(|valid| valid.as_bool()));
+
+        match valid {
+            Some(true) => report.ok(key, address.to_string()),
+            Some(false) => report.error(key, format!("{} is not a valid address", address)),
+            None => report.warn(key, format!("{} not validated", address)),
+        }
+    }
+}
@@ -1,22 +1,178 @@
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const GIT_COMMIT: &str = env!("VERGEN_GIT_SHA");
+pub const BUILD_DATE: &str = env!("VERGEN_BUILD_TIMESTAMP");
+pub const CARGO_FEATURES: &str = env!("VERGEN_CARGO_FEATURES");
+pub const API_VERSION: u32 = 14; // bumped when a GvCLI reply changes shape or a method is added
+pub const CAPABILITIES_API_VERSION: u32 = 3; // first API version with get_api_capabilities
 pub const DAEMON_BASE_URL: &str = "https://github.com/ghost-coin/ghost-core/releases/download/";
 pub const LATEST_RELEASE_URL: &str = "https://github.com/ghost-coin/ghost-core/releases/latest";
+pub const RELEASES_API_URL: &str = "https://api.github.com/repos/ghost-coin/ghost-core/releases";
 pub const TMP_PATH: &str = "/tmp/GhostVault";
+pub const SENDMAIL_PATH: &str = "/usr/sbin/sendmail";
+pub const GPGV_PATH: &str = "/usr/bin/gpgv";
+pub const DOWNLOAD_CACHE_MAX_AGE: u64 = 60 * 60 * 24; // 24 hours
 pub const DEFAULT_GV_DIR: &str = "~/.ghostvault/";
 pub const DEFAULT_DAEMON_DIR: &str = "~/.ghost/";
 pub const DAEMON_PID_FILE: &str = "ghost.pid";
 pub const GV_PID_FILE: &str = "ghostvault.pid";
+pub const GV_LOG_FILE: &str = "logs/ghostvault.log";
 pub const GV_SETTINGS_FILE: &str = "gv_settings.toml";
+pub const GV_PROFILE_ENV: &str = "GV_PROFILE"; // picks gv_settings.<profile>.toml instead
+pub const FLEET_FILE: &str = "fleet.toml";
+pub const DEFAULT_LOG_LEVEL: &str = "info";
+pub const DEFAULT_LOG_FILE_SIZE_MB: u64 = 10;
+pub const DEFAULT_LOG_FILE_COUNT: u32 = 3; // rolled over logs kept
+pub const DASHBOARD_REFRESH: u64 = 5; // seconds
+pub const DASHBOARD_LOG_TAIL: u64 = 64 * 1024; // bytes read from the end of the log
+pub const LOG_TAIL_MAX_BYTES: u64 = 1024 * 1024; // bytes get_logs reads from the end of a log
+pub const DEFAULT_LOG_TAIL_LINES: u64 = 100; // lines get_logs returns when none are asked for
+pub const LOG_TAIL_MAX_LINES: u64 = 1000; // lines per get_logs call
+pub const TG_LOG_LINES: u64 = 30; // lines sent by the Recent Logs button
+pub const DEFAULT_DB_DIR: &str = "gv_database/";
+pub const DEFAULT_BACKUP_DIR: &str = "backups/";
+pub const CONFIG_PASSPHRASE_ENV: &str = "GV_CONFIG_PASSPHRASE";
+pub const ENCRYPTED_VALUE_PREFIX: &str = "enc:v1:";
+pub const CLI_TLS_DIR: &str = "tls/";
+pub const CLI_TLS_CERT_FILE: &str = "cli_cert.pem";
+pub const CLI_TLS_KEY_FILE: &str = "cli_key.pem";
+pub const SEALED_VALUE_PREFIX: &str = "sealed:v1:"; // followed by the hex salt, a colon and the data
+pub const SENSITIVE_CONFIG_KEYS: [&str; 6] = [
+    "MNEMONIC",
+    "TELOXIDE_TOKEN",
+    "BACKUP_REMOTE_SECRET_KEY",
+    "ANNOUNCE_WEBHOOK_URL",
+    "RPC_USER",
+    "RPC_PASSWORD",
+];
 pub const DAEMON_SETTINGS_FILE: &str = "ghost.conf";
 pub const DEFAULT_COLD_WALLET: &str = "GV_COLD";
+pub const DEFAULT_WATCH_WALLET: &str = "GV_WATCH";
 pub const DEFAULT_HOT_WALLET: &str = "GV_HOT";
 pub const DEFAULT_DEAMON_UPDATE: u64 = 60 * 60 * 2; // 2 hours
 pub const DEFAULT_SELF_UPDATE: u64 = 60 * 60 * 2; // 2 hours
+pub const PENDING_UPDATE_TTL: u64 = 60 * 60; // 1 hour
+pub const MIN_DAEMON_PRUNE: u64 = 550; // MiB, the smallest prune target ghostd accepts
+pub const DOCKER_RESTART_BACKOFF: u64 = 5; // seconds before the first ghostd restart, doubled after each failure
+pub const DOCKER_RESTART_BACKOFF_MAX: u64 = 60 * 5; // 5 minutes
+pub const DOCKER_RESTART_TIMEOUT: u64 = 60 * 5; // 5 minutes for ghostd to load its block index and answer
+pub const DEFAULT_DOCKER_MAX_RESTARTS: u64 = 5; // failed restarts in a row before the container exits
+pub const RESTART_HISTORY_MAX: usize = 100; // ghostd restarts kept
+pub const DEFAULT_HOOK_TIMEOUT: u64 = 30; // seconds a hook script gets before it's killed
+pub const AUDIT_ORIGINS: [&str; 5] = ["cli", "telegram", "rest", "grpc", "internal"];
+pub const AUDIT_LOG_DEFAULT: usize = 100; // entries get_audit_log returns when no limit is given
+pub const JOB_HISTORY_MAX: usize = 500; // finished and running jobs kept
+pub const JOBS_DEFAULT: usize = 20; // jobs get_jobs returns when no limit is given
+pub const SCHEDULED_TASKS: [&str; 8] = [
+    "daemon_update",
+    "self_update",
+    "process_rewards",
+    "auto_backup",
+    "balance_sample",
+    "utxo_split",
+    "digest",
+    "db_maintenance",
+];
+pub const DAEMON_UPGRADE_TIMEOUT: u64 = 60 * 30; // 30 minutes for a new ghostd to start and sync
 pub const DEFAULT_PROCESS_REWARDS: i64 = 60 * 15; // 15 minutes
+pub const DEFAULT_BACKUP_INTERVAL: i64 = 60 * 60 * 24; // 24 hours
+pub const DEFAULT_BALANCE_SAMPLE_INTERVAL: i64 = 60 * 60; // 1 hour
+pub const DEFAULT_BACKUP_RETENTION: i64 = 7;
+pub const DEFAULT_REMOTE_BACKUP_RETENTION: i64 = 30;
+pub const DEFAULT_S3_ENDPOINT: &str = "https://s3.amazonaws.com";
+pub const DEFAULT_S3_REGION: &str = "us-east-1";
+pub const DEFAULT_EXPLORER_APIS: [&str; 4] = [
+    "https://api.tuxprint.com",
+    "https://api2.tuxprint.com",
+    "https://socket.tuxprint.com",
+    "https://socket2.tuxprint.com",
+];
+pub const DEFAULT_EXPLORER_TX_URL: &str = "https://ghostscan.io/tx/{txid}/";
+pub const DEFAULT_EXPLORER_BLOCK_URL: &str = "https://ghostscan.io/block/{hash}/";
+pub const EXPLORER_REQUEST_TIMEOUT: u64 = 15; // seconds
+pub const EXPLORER_RETRY_AFTER: u64 = 60 * 5; // 5 minutes
+pub const LOCAL_CHAIN_MIN_PEERS: usize = 2; // peers needed to judge the chain without explorers
+pub const LOCAL_CHAIN_FORK_DEPTH: u32 = 6; // blocks a fork can lead ours by before the chain is bad
+pub const CHAIN_PEER_TIMEOUT: u64 = 10; // seconds
+pub const DEFAULT_CHAIN_SPLIT_TIMEOUT: i64 = 60 * 30; // 30 minutes
+pub const CHAIN_EMERGENCY_ALERT_INTERVAL: u64 = 60 * 15; // 15 minutes
+pub const CHAIN_EMERGENCY_SNOOZE: u64 = 60 * 60 * 2; // 2 hours
+pub const CHAIN_EMERGENCY_BAN_TIME: u64 = 60 * 60 * 24; // 24 hours
+pub const RPC_POOL_MAX_IDLE: usize = 8; // idle connections kept open to ghostd
+pub const RPC_POOL_IDLE_TIMEOUT: u64 = 90; // seconds
+pub const OPERATIONS: [&str; 3] = ["force_resync", "daemon_update", "import_wallet"]; // ops with get_operation_status
+pub const BLOCK_REPLAY_LIMIT: u32 = 720; // blocks replayed after downtime, about a day
+pub const ZMQ_WATCHDOG_INTERVAL: u64 = 60; // seconds between checks on a quiet ZMQ socket
+pub const ZMQ_SILENCE_TIMEOUT: u64 = 60 * 10; // 10 minutes without a notification before ghostd is polled
+pub const ZMQ_RESUBSCRIBE_DELAY: u64 = 5; // seconds
+pub const ZMQ_POLL_INTERVAL: u64 = 15; // seconds between RPC polls when ghostd has no ZMQ notifiers
+pub const DEFAULT_CACHE_TTL_BLOCKCHAININFO: i64 = 5; // seconds
+pub const DEFAULT_CACHE_TTL_STAKINGINFO: i64 = 10; // seconds
+pub const DEFAULT_CACHE_TTL_COLDSTAKINGINFO: i64 = 10; // seconds
+pub const DEFAULT_CACHE_TTL_NETWORKINFO: i64 = 30; // seconds
+pub const RPC_BATCH_MAX: usize = 100; // calls per JSON-RPC batch request
+pub const CHART_PAGE_DEFAULT: u64 = 1000; // points per chart page when no limit is given
+pub const CHART_PAGE_MAX: u64 = 10000; // points per chart page
 pub const DEFAULT_MIN_PAYOUT: u64 = 10000000; // 0.10000000 Ghost
 pub const MIN_TX_VALUE: u64 = 10000000; // 0.10000000 Ghost
-pub const MAX_TX_FEES: u64 = 25000000; // 0.25000000 Ghost
+pub const DEFAULT_MAX_TX_FEE: u64 = 25000000; // 0.25000000 Ghost
+pub const DEFAULT_FEE_RATE: u64 = 7500; // sats per kB, 0.00007500 Ghost
+pub const DEFAULT_FEE_CONF_TARGET: u64 = 6; // blocks
+pub const MAX_FEE_CONF_TARGET: u64 = 1008; // blocks, the most estimatesmartfee looks ahead
+pub const DEFAULT_RING_SIZE: u32 = 12; // anon inputs per ring
+pub const MIN_RING_SIZE: u32 = 3;
+pub const MAX_RING_SIZE: u32 = 32;
+pub const DEFAULT_SPLIT_THRESHOLD: u64 = 200000000000; // 2000.00000000 Ghost
+pub const DEFAULT_SPLIT_COUNT: u64 = 4;
+pub const MAX_SPLIT_COUNT: u64 = 50; // outputs per split transaction
+pub const DEFAULT_SPLIT_INTERVAL: i64 = 60 * 60 * 24; // 24 hours
+pub const PENDING_SPLIT_TTL: u64 = 60 * 60; // 1 hour
+pub const DEFAULT_DIGEST_HOUR: u32 = 8; // local time
+pub const DIGEST_CHECK_INTERVAL: i64 = 60 * 15; // 15 minutes
+pub const QUIET_HOURS_CHECK_INTERVAL: u64 = 60; // seconds between checks for held notices to send
+pub const QUIET_SUMMARY_MAX_NOTICES: usize = 30; // the rest of a long night is only counted
+pub const QUIET_HOURS_PRESETS: [&str; 4] = ["22-7", "23-7", "0-8", "21-6"]; // offered by the bot
+pub const DB_MAINTENANCE_INTERVAL: i64 = 60 * 60 * 24 * 7; // 7 days
+pub const DB_MAINTENANCE_RETRY: i64 = 60 * 60; // 1 hour, while waiting for the maintenance window
+pub const EVENT_LOG_RETENTION: u64 = 60 * 60 * 24 * 7; // 7 days
+pub const EVENT_PAGE_MAX: u64 = 500; // events per get_events_since call
+pub const EVENT_LONG_POLL_MAX: u64 = 30; // seconds, inside the client's 45 second deadline
+pub const SYSTEM_SAMPLE_INTERVAL: u64 = 60 * 10; // 10 minutes
+pub const SYSTEM_STATS_RETENTION: u64 = 60 * 60 * 24 * 30; // 30 days
+pub const NETWORK_SAMPLE_INTERVAL: u64 = 60 * 30; // 30 minutes
+pub const NETWORK_STATS_RETENTION: u64 = 60 * 60 * 24 * 365 * 2; // 2 years
+pub const NETWORK_STATS_MAX_POINTS: usize = 1000; // samples get_network_stats returns per range
+pub const DEFAULT_DISK_ALERT_GB: u64 = 5;
+pub const SYNC_RATE_WINDOW: u64 = 60 * 5; // seconds of block counts the sync rate is taken over
+pub const SYNC_MESSAGE_INTERVAL: u64 = 60 * 5; // seconds between edits of the Telegram sync message
+pub const SYNC_MESSAGE_MIN_BLOCKS: u64 = 720; // ~1 day behind before a sync gets a Telegram message
+pub const IMPORT_MESSAGE_INTERVAL: u64 = 60; // seconds between edits of the Telegram import message
+pub const IMPORT_BIRTHDAY_MARGIN: u64 = 60 * 60 * 24 * 2; // scan from 2 days before the birthday
 pub const AGVR_ACTIVATION_HEIGHT: u32 = 591621;
+pub const STREAK_MILESTONES: [u32; 7] = [7, 14, 30, 60, 90, 180, 365]; // days
+pub const DEFAULT_MISSED_STAKE_FACTOR: u64 = 3; // ~5% chance of going this long by luck alone
+pub const DEFAULT_STAKE_CONFIRMATIONS: u64 = 100; // a stake's reward is spendable past this many
+pub const DEFAULT_ZAP_CONFIRMATIONS: u64 = 225; // a zapped deposit starts staking at this many
+pub const DEFAULT_PAYOUT_SAFE_DEPTH: u64 = 20; // confirmations a payout's inputs need, a reorg won't reach that far
+pub const STAKING_ONLY_UNLOCK: &str = "Payouts and anon conversion need the wallet fully unlocked, gv-cli unlockforpayout sends a payout round and relocks it for staking.";
+pub const PAYOUT_UNLOCK_TIMEOUT: u64 = 300; // seconds, ghostd relocks by itself if the payout round never finishes
+pub const STAKE_MESSAGE_STEP: u64 = 10; // confirmations between edits of a stake's message
+pub const MISSED_STAKE_CHECK_INTERVAL: u64 = 600; // seconds
+pub const BLOCK_TARGET_SPACING: u64 = 120; // seconds
+pub const STAKE_TIMESTAMP_GRANULARITY: u64 = 16; // seconds, one kernel check per slot
+pub const STAKE_FORECAST_WINDOW_DAYS: u64 = 7;
+pub const STAKE_UNDERPERFORM_PROBABILITY: f64 = 0.05; // flag fewer stakes than this by luck
+pub const DEFAULT_SIMULATION_DAYS: u64 = 30;
+pub const SIMULATION_MAX_DAYS: u64 = 365 * 5;
+pub const COLD_WALLET_CHART_DAYS: u64 = 30;
+pub const ZAP_REQUEST_TTL: u64 = 60 * 60 * 24 * 7; // open zap requests are dropped after a week
+pub const ZAP_REQUEST_KEYS: u32 = 64; // stake keys the wallet looks ahead for, one per open request
+pub const DEFAULT_DEPOSIT_WINDOW_HOURS: u64 = 24; // how long an expected deposit has to arrive
+pub const EXPECTED_DEPOSIT_KEEP: u64 = 60 * 60 * 24 * 30; // closed expected deposits are kept a month
+pub const ANON_PAYOUT_TX_SIZE: u64 = 5000; // bytes, a typical anon payout with its ring signatures
+pub const ANON_MATURITY_CONFIRMATIONS: u64 = 12; // before anon outputs can be spent
+pub const TX_LABEL_MAX_LEN: usize = 100; // characters
+pub const ADDRESS_LABEL_MAX_LEN: usize = 32; // characters, short enough for a button
+pub const TENANT_EXT_KEY_LOOKAHEAD: u32 = 200; // addresses derived from a tenant's ext pub key
 pub const DEV_FUND_ADDRESS: [&str; 5] = [
     "GgtiuDqVxAzg47yW7oSMmophe3tU8qoE1f",
     "GQJ4unJi6hAzd881YM17rEzPNWaWZ4AR3f",
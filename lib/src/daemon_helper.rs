@@ -1,13 +1,15 @@
 #![allow(dead_code)]
 use crate::{
-    config::GVConfig,
+    config::{AnonPolicy, FeePolicy, FeeSettings, GVConfig},
     constants::{
         AGVR_ACTIVATION_HEIGHT, DAEMON_PID_FILE, DAEMON_SETTINGS_FILE, DEFAULT_COLD_WALLET,
-        DEV_FUND_ADDRESS, MAX_TX_FEES, TMP_PATH,
+        DEFAULT_WATCH_WALLET, DEV_FUND_ADDRESS, LOCAL_CHAIN_FORK_DEPTH, LOCAL_CHAIN_MIN_PEERS,
+        ZMQ_POLL_INTERVAL, ZMQ_RESUBSCRIBE_DELAY, ZMQ_SILENCE_TIMEOUT, ZMQ_WATCHDOG_INTERVAL,
     },
+    explorer::ExplorerProvider,
     file_ops,
     gv_client_methods::CLICaller,
-    gv_methods::{self, get_remote_block_chain_info, sha256_digest, PathAndDigest},
+    gv_methods::{self, sha256_digest, PathAndDigest},
     gvdb::{DaemonStatusDB, NewStakeStatusDB, RewardsDB, ZapStatusDB, GVDB},
     rpc::{self, RPCURL},
 };
@@ -17,8 +19,8 @@ use ghostcore_zmq::{
     subscribe_async, Message,
     Message::{HashBlock, HashWTx},
 };
+use humantime::format_duration;
 use log::{error, info, trace, warn};
-use rand::prelude::SliceRandom;
 use rand::Rng;
 use reqwest::Client;
 use rust_socketio::{
@@ -29,33 +31,22 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use serde_json::Value;
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, HashSet, VecDeque},
     error::Error,
     path::PathBuf,
     process::{Command, Stdio},
-    sync::Arc,
-    time::Duration,
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
 };
 use tokio::sync::{Mutex as async_Mutex, RwLock as async_RwLock};
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
 struct GVDaemonError {
     message: String,
 }
 
-impl std::fmt::Display for GVDaemonError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.message)
-    }
-}
-
-impl std::error::Error for GVDaemonError {}
-
-unsafe impl Send for GVDaemonError {}
-
-unsafe impl Sync for GVDaemonError {}
-
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DaemonState {
     pub online: bool,
@@ -69,6 +60,82 @@ pub struct DaemonState {
     pub remote_best_block: u32,
     pub remote_best_block_hash: String,
     pub cycle: u32,
+    pub sync_progress: Option<SyncProgress>,
+    // Why the last payout was held back, cleared once one goes through.
+    #[serde(default)]
+    pub payout_deferred: Option<String>,
+}
+
+// A sync that's under way. The rate is taken over the last SYNC_RATE_WINDOW and the ETA assumes
+// it holds, while headers are still coming in early on it runs short.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SyncProgress {
+    pub blocks: u64,
+    pub headers: u64,
+    pub verification_progress: f64,
+    pub blocks_per_minute: f64,
+    pub eta_seconds: Option<u64>,
+    pub eta: String,
+}
+
+impl SyncProgress {
+    // samples are (timestamp, blocks) pairs, oldest first.
+    pub fn from_info(
+        blockchaininfo: &Value,
+        samples: &VecDeque<(u64, u64)>,
+    ) -> Result<SyncProgress, Box<dyn std::error::Error + Send + Sync>> {
+        let blocks: u64 = blockchaininfo
+            .get("blocks")
+            .and_then(|blocks| blocks.as_u64())
+            .ok_or("Malformed getblockchaininfo response")?;
+        let headers: u64 = blockchaininfo
+            .get("headers")
+            .and_then(|headers| headers.as_u64())
+            .ok_or("Malformed getblockchaininfo response")?;
+        let verification_progress: f64 = blockchaininfo
+            .get("verificationprogress")
+            .and_then(|progress| progress.as_f64())
+            .unwrap_or(0.0);
+
+        let blocks_per_minute: f64 = match (samples.front(), samples.back()) {
+            (Some((first_time, first_blocks)), Some((last_time, last_blocks)))
+                if last_time > first_time =>
+            {
+                last_blocks.saturating_sub(*first_blocks) as f64 * 60.0
+                    / (last_time - first_time) as f64
+            }
+            _ => 0.0,
+        };
+
+        let eta_seconds: Option<u64> = (blocks_per_minute > 0.0)
+            .then(|| (headers.saturating_sub(blocks) as f64 / blocks_per_minute * 60.0) as u64);
+
+        // To the minute, the rate moves around too much for seconds to mean anything
+        let eta: String = match eta_seconds {
+            Some(secs) => format_duration(Duration::from_secs(secs / 60 * 60)).to_string(),
+            None => "Unknown".to_string(),
+        };
+
+        Ok(SyncProgress {
+            blocks,
+            headers,
+            verification_progress,
+            blocks_per_minute,
+            eta_seconds,
+            eta,
+        })
+    }
+}
+
+// The chain as ghostd's own peers see it, used instead of the explorers with LOCAL_CHAIN_CHECK.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LocalChainCheck {
+    pub good_chain: bool,
+    pub peers: usize,
+    pub peer_height: u32,
+    pub best_header: u32,
+    pub best_header_hash: String,
+    pub fork_hash: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -80,6 +147,15 @@ pub struct DaemonHelper {
     config: Arc<async_RwLock<GVConfig>>,
 }
 
+// Recent results of the read-only status calls, shared by every DaemonHelper so the bot,
+// the tasks and gv-cli asking for the same thing in quick succession cost ghostd one call.
+// Keyed by RPC URL and method, the whole cache is dropped on every new block.
+static RPC_CACHE: OnceLock<Mutex<HashMap<String, (Instant, Value)>>> = OnceLock::new();
+
+fn rpc_cache() -> &'static Mutex<HashMap<String, (Instant, Value)>> {
+    RPC_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TxidAndWallet {
     pub txid: String,
@@ -104,16 +180,24 @@ impl DaemonHelper {
             "no-wallet" => "".to_string(),
             _ => "".to_string(),
         };
+        drop(conf);
+
+        Self::for_wallet(config, &wallet).await
+    }
+
+    // A helper for a wallet by its name in ghostd, such as one of the extra cold wallets.
+    pub async fn for_wallet(config: &Arc<async_RwLock<GVConfig>>, wallet: &str) -> Self {
+        let conf = config.read().await;
 
         let rpcurl: RPCURL = RPCURL::default().target(
             &conf.rpc_host.as_str(),
             &conf.rpc_port,
-            wallet.as_str(),
+            wallet,
             &conf.rpc_user.as_str(),
             &conf.rpc_pass.as_str(),
         );
 
-        let rpc_client: Client = Client::new();
+        let rpc_client: Client = rpc::pooled_client();
         let daemon_path: PathBuf = conf.daemon_path.to_owned();
         let daemon_data_path: PathBuf = conf.daemon_data_dir.to_owned();
         drop(conf);
@@ -135,6 +219,10 @@ impl DaemonHelper {
         rpcurl.clone()
     }
 
+    pub async fn wallet_name(&self) -> String {
+        self.rpcurl.lock().await.wallet().to_string()
+    }
+
     async fn set_rpcurl(&self, wallet_name: &str) {
         let conf = self.config.read().await;
         let rpcurl_template: RPCURL = RPCURL::default().target(
@@ -148,6 +236,71 @@ impl DaemonHelper {
         *rpcurl = rpcurl_template;
     }
 
+    // TTL for a cacheable method, zero for everything else and for caching turned off.
+    async fn cache_ttl(&self, method: &str) -> Duration {
+        let conf = self.config.read().await;
+
+        let ttl: u64 = match method {
+            "getblockchaininfo" => conf.cache_ttl_blockchaininfo,
+            "getstakinginfo" => conf.cache_ttl_stakinginfo,
+            "getcoldstakinginfo" => conf.cache_ttl_coldstakinginfo,
+            "getnetworkinfo" => conf.cache_ttl_networkinfo,
+            _ => 0,
+        };
+
+        Duration::from_secs(ttl)
+    }
+
+    fn cache_key(rpcurl: &RPCURL, method: &str) -> String {
+        format!("{} {}", rpcurl.as_str(), method)
+    }
+
+    fn cache_get(key: &str, ttl: Duration) -> Option<Value> {
+        if ttl.is_zero() {
+            return None;
+        }
+
+        let cache = rpc_cache().lock().ok()?;
+
+        cache
+            .get(key)
+            .filter(|(fetched, _)| fetched.elapsed() < ttl)
+            .map(|(_, value)| value.clone())
+    }
+
+    fn cache_put(key: String, ttl: Duration, value: &Value) {
+        if ttl.is_zero() {
+            return;
+        }
+
+        if let Ok(mut cache) = rpc_cache().lock() {
+            cache.insert(key, (Instant::now(), value.clone()));
+        }
+    }
+
+    // Forgets every cached result, called when a new block arrives.
+    pub fn invalidate_cache() {
+        if let Ok(mut cache) = rpc_cache().lock() {
+            cache.clear();
+        }
+    }
+
+    // rpc::call for a method without arguments, served from the cache while it is fresh.
+    async fn cached_call(&self, method: &str) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let rpcurl: RPCURL = self.get_rpcurl().await;
+        let ttl: Duration = self.cache_ttl(method).await;
+        let key: String = DaemonHelper::cache_key(&rpcurl, method);
+
+        if let Some(value) = DaemonHelper::cache_get(&key, ttl) {
+            return Ok(value);
+        }
+
+        let value: Value = rpc::call(method, &rpcurl, &self.rpc_client).await?;
+        DaemonHelper::cache_put(key, ttl, &value);
+
+        Ok(value)
+    }
+
     pub async fn getblockcount(&self) -> Result<Value, Box<dyn Error>> {
         let res: Result<Value, Box<dyn Error + Send + Sync>> =
             rpc::call("getblockcount", &self.get_rpcurl().await, &self.rpc_client).await;
@@ -167,12 +320,8 @@ impl DaemonHelper {
     pub async fn getcoldstakinginfo(
         &self,
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
-        let res: Result<Value, Box<dyn Error + Send + Sync>> = rpc::call(
-            "getcoldstakinginfo",
-            &self.get_rpcurl().await,
-            &self.rpc_client,
-        )
-        .await;
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            self.cached_call("getcoldstakinginfo").await;
 
         let cold_info = match res {
             Ok(value) => value,
@@ -202,6 +351,148 @@ impl DaemonHelper {
         Ok(balances)
     }
 
+    pub async fn get_wallet_info(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call("getwalletinfo", &self.get_rpcurl().await, &self.rpc_client).await;
+
+        let wallet_info = match res {
+            Ok(value) => value,
+            Err(err) => {
+                self.parse_error_msg(err.to_string()).await;
+                error!("{}", err.to_string());
+                return Err(err);
+            }
+        };
+
+        Ok(wallet_info)
+    }
+
+    pub async fn get_encryption_status(
+        &self,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let wallet_info: Value = self.get_wallet_info().await?;
+
+        let status: String = wallet_info
+            .get("encryptionstatus")
+            .and_then(|status| status.as_str())
+            .unwrap_or("Unencrypted")
+            .to_string();
+
+        Ok(status)
+    }
+
+    pub async fn encrypt_wallet(
+        &self,
+        passphrase: &str,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let args: String = format!("encryptwallet {}", comment_arg(passphrase));
+
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        let encrypted = match res {
+            Ok(value) => value,
+            Err(err) => {
+                error!("Failed to encrypt wallet: {}", err);
+                return Err(err);
+            }
+        };
+
+        Ok(encrypted)
+    }
+
+    pub async fn unlock_wallet_staking(
+        &self,
+        passphrase: &str,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        // A timeout of 0 keeps the wallet unlocked until ghostd restarts.
+        let args: String = format!("walletpassphrase {} 0 true", comment_arg(passphrase));
+
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        let unlocked = match res {
+            Ok(value) => value,
+            Err(err) => {
+                error!("Failed to unlock wallet: {}", err);
+                return Err(err);
+            }
+        };
+
+        Ok(unlocked)
+    }
+
+    // A full unlock that lapses after timeout seconds, for sending a payout round.
+    pub async fn unlock_wallet_spending(
+        &self,
+        passphrase: &str,
+        timeout: u64,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let args: String = format!(
+            "walletpassphrase {} {} false",
+            comment_arg(passphrase),
+            timeout
+        );
+
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        let unlocked = match res {
+            Ok(value) => value,
+            Err(err) => {
+                error!("Failed to unlock wallet: {}", err);
+                return Err(err);
+            }
+        };
+
+        Ok(unlocked)
+    }
+
+    pub async fn lock_wallet(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        rpc::call("walletlock", &self.get_rpcurl().await, &self.rpc_client).await
+    }
+
+    pub async fn rescan_blockchain(
+        &self,
+        start_height: u32,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let command: String = format!("rescanblockchain {}", start_height);
+
+        // This call blocks until ghostd has finished the rescan.
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&command, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        let rescan_info = match res {
+            Ok(value) => value,
+            Err(err) => {
+                error!("{}", err.to_string());
+                return Err(err);
+            }
+        };
+
+        Ok(rescan_info)
+    }
+
+    // Only the transactions go, keys and addresses stay so a rescan can find them all again.
+    pub async fn clear_wallet_transactions(
+        &self,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let res: Result<Value, Box<dyn Error + Send + Sync>> = rpc::call(
+            "clearwallettransactions true",
+            &self.get_rpcurl().await,
+            &self.rpc_client,
+        )
+        .await;
+
+        match res {
+            Ok(value) => Ok(value),
+            Err(err) => {
+                error!("{}", err.to_string());
+                Err(err)
+            }
+        }
+    }
+
     pub async fn validate_address(
         &self,
         address: &str,
@@ -245,6 +536,44 @@ impl DaemonHelper {
         Ok(best_block_hash)
     }
 
+    // The notifiers ghostd is publishing, as [{"type", "address", "hwm"}].
+    pub async fn get_zmq_notifications(&self) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let res: Result<Value, Box<dyn Error + Send + Sync>> = rpc::call(
+            "getzmqnotifications",
+            &self.get_rpcurl().await,
+            &self.rpc_client,
+        )
+        .await;
+
+        match res {
+            Ok(value) => Ok(value),
+            Err(err) => {
+                self.parse_error_msg(err.to_string()).await;
+                error!("{}", err.to_string());
+                Err(err)
+            }
+        }
+    }
+
+    pub async fn list_since_block(
+        &self,
+        block_hash: &str,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let args: String = format!("listsinceblock {} 1 true", block_hash);
+
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        match res {
+            Ok(value) => Ok(value),
+            Err(err) => {
+                self.parse_error_msg(err.to_string()).await;
+                error!("{}", err.to_string());
+                Err(err)
+            }
+        }
+    }
+
     pub async fn get_address_info(
         &self,
         address: &str,
@@ -273,13 +602,44 @@ impl DaemonHelper {
         let res = self.validate_address(address).await?;
 
         if res.is_object() {
-            let is_valid: bool = res.get("isvalid").unwrap().as_bool().unwrap();
+            let is_valid: bool = res
+                .get("isvalid")
+                .and_then(|valid| valid.as_bool())
+                .ok_or("Malformed validateaddress response")?;
             Ok(is_valid)
         } else {
             Ok(false)
         }
     }
 
+    pub async fn derive_range_keys(
+        &self,
+        ext_key: &str,
+        start: u32,
+        end: u32,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let args: String = format!("deriverangekeys {} {} {}", start, end, ext_key);
+
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        let addresses: Value = match res {
+            Ok(value) => value,
+            Err(err) => {
+                self.parse_error_msg(err.to_string()).await;
+                error!("{}", err);
+                return Err(err);
+            }
+        };
+
+        Ok(addresses
+            .as_array()
+            .ok_or("Malformed deriverangekeys response")?
+            .iter()
+            .filter_map(|addr| addr.as_str().map(|addr| addr.to_string()))
+            .collect())
+    }
+
     pub async fn getnewaddress(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         let res: Result<Value, Box<dyn Error + Send + Sync>> =
             rpc::call("getnewaddress", &self.get_rpcurl().await, &self.rpc_client).await;
@@ -342,7 +702,7 @@ impl DaemonHelper {
 
     pub async fn getstakinginfo(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         let res: Result<Value, Box<dyn Error + Send + Sync>> =
-            rpc::call("getstakinginfo", &self.get_rpcurl().await, &self.rpc_client).await;
+            self.cached_call("getstakinginfo").await;
 
         let staking_info = match res {
             Ok(value) => value,
@@ -394,7 +754,7 @@ impl DaemonHelper {
 
     pub async fn getnetworkinfo(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         let res: Result<Value, Box<dyn Error + Send + Sync>> =
-            rpc::call("getnetworkinfo", &self.get_rpcurl().await, &self.rpc_client).await;
+            self.cached_call("getnetworkinfo").await;
 
         let networkinfo = match res {
             Ok(ref value) => value,
@@ -407,88 +767,274 @@ impl DaemonHelper {
         Ok(networkinfo.to_owned())
     }
 
-    pub async fn getblockchaininfo(
-        &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
-        let res: Result<Value, Box<dyn Error + Send + Sync>> = rpc::call(
-            "getblockchaininfo",
-            &self.get_rpcurl().await,
-            &self.rpc_client,
-        )
-        .await;
+    pub async fn getpeerinfo(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call("getpeerinfo", &self.get_rpcurl().await, &self.rpc_client).await;
 
-        let blockchaininfo = match res {
+        let peerinfo = match res {
             Ok(ref value) => value,
             Err(err) => {
-                error!("{}", err.to_string());
+                error!("{}", err);
                 return Err(err);
             }
         };
 
-        Ok(blockchaininfo.to_owned())
+        Ok(peerinfo.to_owned())
     }
 
-    pub async fn list_wallets(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+    pub async fn getnettotals(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         let res: Result<Value, Box<dyn Error + Send + Sync>> =
-            rpc::call("listwallets", &self.get_rpcurl().await, &self.rpc_client).await;
+            rpc::call("getnettotals", &self.get_rpcurl().await, &self.rpc_client).await;
 
-        let loaded_wallets = match res {
-            Ok(ref value) => value.to_owned(),
+        let nettotals = match res {
+            Ok(ref value) => value,
             Err(err) => {
-                error!("{}", err.to_string());
+                error!("{}", err);
                 return Err(err);
             }
         };
 
-        Ok(loaded_wallets)
+        Ok(nettotals.to_owned())
     }
 
-    pub async fn get_new_mnemonic(
+    pub async fn getblockhash(
         &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        height: u32,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let command: String = format!("getblockhash {}", height);
+
         let res: Result<Value, Box<dyn Error + Send + Sync>> =
-            rpc::call("mnemonic new", &self.get_rpcurl().await, &self.rpc_client).await;
+            rpc::call(&command, &self.get_rpcurl().await, &self.rpc_client).await;
 
-        let mnemonic = match res {
-            Ok(ref value) => value.to_owned(),
+        match res {
+            Ok(value) => Ok(value
+                .as_str()
+                .ok_or("Malformed getblockhash response")?
+                .to_string()),
             Err(err) => {
-                error!("{}", err.to_string());
-                return Err(err);
+                error!("{}", err);
+                Err(err)
             }
-        };
-
-        Ok(mnemonic)
+        }
     }
 
-    pub async fn is_syncing(&self) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
-        let res: Result<Value, Box<dyn Error + Send + Sync>> = rpc::call(
-            "getblockchaininfo",
-            &self.get_rpcurl().await,
-            &self.rpc_client,
-        )
-        .await;
+    pub async fn getchaintips(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call("getchaintips", &self.get_rpcurl().await, &self.rpc_client).await;
 
-        let blockchaininfo = match res {
+        let chaintips = match res {
             Ok(ref value) => value,
             Err(err) => {
-                error!("{}", err.to_string());
+                error!("{}", err);
                 return Err(err);
             }
         };
 
-        let blocks: u64 = blockchaininfo.get("blocks").unwrap().as_u64().unwrap();
-        let headers: u64 = blockchaininfo.get("headers").unwrap().as_u64().unwrap();
-        let ibdl: bool = blockchaininfo
-            .get("initialblockdownload")
-            .unwrap()
-            .as_bool()
-            .unwrap();
+        Ok(chaintips.to_owned())
+    }
 
-        let is_syncing = if ibdl || blocks != headers {
-            true
-        } else {
-            false
-        };
+    // Checks the chain without asking anyone but ghostd. The peers' median synced header
+    // stands in for the remote best block, and the chain is bad when ghostd knows of a fork
+    // that split off below our tip and has got more than LOCAL_CHAIN_FORK_DEPTH blocks ahead.
+    pub async fn local_chain_check(
+        &self,
+    ) -> Result<LocalChainCheck, Box<dyn std::error::Error + Send + Sync>> {
+        let (bc_info, peer_info, chain_tips) = tokio::try_join!(
+            self.getblockchaininfo(),
+            self.getpeerinfo(),
+            self.getchaintips()
+        )?;
+
+        let best_block: u32 = bc_info
+            .get("blocks")
+            .and_then(|blocks| blocks.as_u64())
+            .ok_or("Malformed getblockchaininfo response")? as u32;
+
+        let mut peer_heights: Vec<u32> = peer_info
+            .as_array()
+            .ok_or("Malformed getpeerinfo response")?
+            .iter()
+            .filter_map(|peer| peer.get("synced_headers")?.as_i64())
+            .filter(|height| *height > 0)
+            .map(|height| height as u32)
+            .collect();
+
+        if peer_heights.len() < LOCAL_CHAIN_MIN_PEERS {
+            return Err(format!(
+                "Only {} synced peers, need {} to verify the chain",
+                peer_heights.len(),
+                LOCAL_CHAIN_MIN_PEERS
+            )
+            .into());
+        }
+
+        peer_heights.sort_unstable();
+        let peer_height: u32 = peer_heights[peer_heights.len() / 2];
+
+        let tips: &Vec<Value> = chain_tips
+            .as_array()
+            .ok_or("Malformed getchaintips response")?;
+
+        let mut best_header: u32 = best_block;
+        let mut best_header_hash: String = bc_info
+            .get("bestblockhash")
+            .and_then(|hash| hash.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let mut fork_hash: Option<String> = None;
+
+        for tip in tips {
+            let status: &str = tip.get("status").and_then(|s| s.as_str()).unwrap_or("");
+            let height: u32 = tip.get("height").and_then(|h| h.as_u64()).unwrap_or(0) as u32;
+            let branch_len: u32 = tip.get("branchlen").and_then(|b| b.as_u64()).unwrap_or(0) as u32;
+            let hash: &str = tip.get("hash").and_then(|h| h.as_str()).unwrap_or("");
+
+            if status == "invalid" {
+                continue;
+            }
+
+            if height > best_header {
+                best_header = height;
+                best_header_hash = hash.to_string();
+            }
+
+            // A branch starting at our tip is just headers we haven't got the blocks for yet
+            let forks_below_tip: bool = height.saturating_sub(branch_len) < best_block;
+
+            if status != "active" && forks_below_tip && height > best_block + LOCAL_CHAIN_FORK_DEPTH
+            {
+                fork_hash = Some(hash.to_string());
+            }
+        }
+
+        Ok(LocalChainCheck {
+            good_chain: fork_hash.is_none(),
+            peers: peer_heights.len(),
+            peer_height,
+            best_header,
+            best_header_hash,
+            fork_hash,
+        })
+    }
+
+    pub async fn setban(
+        &self,
+        subnet: &str,
+        ban_seconds: u64,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let command: String = format!("setban {} add {}", subnet, ban_seconds);
+
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&command, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        let ban = match res {
+            Ok(ref value) => value,
+            Err(err) => {
+                error!("{}", err);
+                return Err(err);
+            }
+        };
+
+        Ok(ban.to_owned())
+    }
+
+    pub async fn getblockchaininfo(
+        &self,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            self.cached_call("getblockchaininfo").await;
+
+        let blockchaininfo = match res {
+            Ok(ref value) => value,
+            Err(err) => {
+                error!("{}", err.to_string());
+                return Err(err);
+            }
+        };
+
+        Ok(blockchaininfo.to_owned())
+    }
+
+    // The lowest block a pruned ghostd still has, None on a full node.
+    pub async fn prune_height(&self) -> Option<u32> {
+        let info: Value = self.getblockchaininfo().await.ok()?;
+
+        if !info.get("pruned")?.as_bool()? {
+            return None;
+        }
+
+        Some(info.get("pruneheight")?.as_u64()? as u32)
+    }
+
+    pub async fn list_wallets(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call("listwallets", &self.get_rpcurl().await, &self.rpc_client).await;
+
+        let loaded_wallets = match res {
+            Ok(ref value) => value.to_owned(),
+            Err(err) => {
+                error!("{}", err.to_string());
+                return Err(err);
+            }
+        };
+
+        Ok(loaded_wallets)
+    }
+
+    pub async fn get_new_mnemonic(
+        &self,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call("mnemonic new", &self.get_rpcurl().await, &self.rpc_client).await;
+
+        let mnemonic = match res {
+            Ok(ref value) => value.to_owned(),
+            Err(err) => {
+                error!("{}", err.to_string());
+                return Err(err);
+            }
+        };
+
+        Ok(mnemonic)
+    }
+
+    pub async fn is_syncing(&self) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            self.cached_call("getblockchaininfo").await;
+
+        let blockchaininfo = match res {
+            Ok(ref value) => value,
+            Err(err) => {
+                error!("{}", err.to_string());
+                return Err(err);
+            }
+        };
+
+        DaemonHelper::syncing_from_info(blockchaininfo)
+    }
+
+    // Whether a getblockchaininfo result shows the daemon still catching up.
+    pub fn syncing_from_info(
+        blockchaininfo: &Value,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let blocks: u64 = blockchaininfo
+            .get("blocks")
+            .and_then(|blocks| blocks.as_u64())
+            .ok_or("Malformed getblockchaininfo response")?;
+        let headers: u64 = blockchaininfo
+            .get("headers")
+            .and_then(|headers| headers.as_u64())
+            .ok_or("Malformed getblockchaininfo response")?;
+        let ibdl: bool = blockchaininfo
+            .get("initialblockdownload")
+            .and_then(|ibdl| ibdl.as_bool())
+            .ok_or("Malformed getblockchaininfo response")?;
+
+        let is_syncing = if ibdl || blocks != headers {
+            true
+        } else {
+            false
+        };
 
         Ok(is_syncing)
     }
@@ -499,7 +1045,10 @@ impl DaemonHelper {
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         let loaded_wallets: Value = self.list_wallets().await?;
 
-        for wall in loaded_wallets.as_array().unwrap() {
+        for wall in loaded_wallets
+            .as_array()
+            .ok_or("Malformed listwallets response")?
+        {
             if wallet == wall {
                 return Ok(Value::String("Wallet Alrady loaded, ok".to_string()));
             }
@@ -553,13 +1102,12 @@ impl DaemonHelper {
         &self,
         reward_addr: Option<&str>,
     ) -> Result<Value, Box<dyn Error + Send + Sync>> {
-        let setting_req: String = if reward_addr.is_none() {
-            r#"{}"#.to_string()
-        } else {
-            format!(r#"{{"rewardaddress":"{}"}}"#, reward_addr.unwrap())
+        let setting_req: String = match reward_addr {
+            Some(reward_addr) => format!(r#"{{"rewardaddress":"{}"}}"#, reward_addr),
+            None => r#"{}"#.to_string(),
         };
 
-        let json_data: Value = serde_json::from_str(&setting_req).unwrap();
+        let json_data: Value = serde_json::from_str(&setting_req)?;
 
         let args: String = format!("walletsettings stakingoptions {}", json_data);
 
@@ -582,8 +1130,10 @@ impl DaemonHelper {
         wallet_name: &str,
         _db: &GVDB,
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
-        let seed_value: Value = self.get_new_mnemonic().await.unwrap();
-        let mnemonic: &str = seed_value["mnemonic"].as_str().unwrap();
+        let seed_value: Value = self.get_new_mnemonic().await?;
+        let mnemonic: &str = seed_value["mnemonic"]
+            .as_str()
+            .ok_or("Malformed mnemonic response")?;
 
         let args: String = format!("createwallet {wallet_name} false false \"\" false false true");
         let res: Result<Value, Box<dyn Error + Send + Sync>> =
@@ -613,24 +1163,122 @@ impl DaemonHelper {
         };
 
         let ext_pub_key_value: Value = self.getnewextaddress().await?;
-        let ext_pub_key: &str = ext_pub_key_value.as_str().unwrap();
+        let ext_pub_key: &str = ext_pub_key_value
+            .as_str()
+            .ok_or("Malformed getnewextaddress response")?;
         let internal_anon = self
             .getnewstealthaddress()
             .await?
             .as_str()
-            .unwrap()
+            .ok_or("Malformed getnewstealthaddress response")?
             .to_string();
         let mut conf = self.config.write().await;
 
         conf.update_gv_config("EXT_PUB_KEY", ext_pub_key)?;
         conf.update_gv_config("INTERNAL_ANON", &internal_anon)?;
         conf.update_gv_config("MNEMONIC", mnemonic)?;
+        clear_sealed_mnemonic(&mut conf)?;
+
+        drop(conf);
+
+        Ok(seed_value)
+    }
+
+    pub async fn create_hot_wallet(
+        &self,
+        wallet_name: &str,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let seed_value: Value = self.get_new_mnemonic().await?;
+        let mnemonic: &str = seed_value["mnemonic"]
+            .as_str()
+            .ok_or("Malformed mnemonic response")?;
 
+        let args: String = format!("createwallet {wallet_name} false false \"\" false false true");
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        if let Err(err) = res {
+            error!("{}", err.to_string());
+            return Err(err);
+        }
+
+        let args: String = format!(
+            "extkeyimportmaster \"{mnemonic}\" \"\" false \"GV_HOT_WALLET\" \"GV_HOT_WALLET\" -1"
+        );
+
+        self.set_rpcurl(wallet_name).await;
+
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        if let Err(err) = res {
+            error!("{}", err.to_string());
+            return Err(err);
+        }
+
+        // Only the hot wallet name is stored, the cold wallet settings are left alone.
+        let mut conf = self.config.write().await;
+        conf.update_gv_config("RPC_WALLET_HOT", wallet_name)?;
         drop(conf);
 
         Ok(seed_value)
     }
 
+    // An extra cold wallet staking next to the primary one, the config is left alone.
+    pub async fn create_cold_wallet(
+        &self,
+        wallet_name: &str,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let seed_value: Value = self.get_new_mnemonic().await?;
+        let mnemonic: &str = seed_value["mnemonic"]
+            .as_str()
+            .ok_or("Malformed mnemonic response")?;
+
+        self.import_cold_wallet(wallet_name, mnemonic, false)
+            .await?;
+
+        Ok(seed_value)
+    }
+
+    // A new wallet has no history, an imported one is scanned from genesis for its stakes.
+    pub async fn import_cold_wallet(
+        &self,
+        wallet_name: &str,
+        mnemonic: &str,
+        rescan: bool,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let args: String = format!(
+            r#"createwallet "{}" false false "" false false true"#,
+            wallet_name
+        );
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        if let Err(err) = res {
+            error!("{}", err.to_string());
+            return Err(err);
+        }
+
+        let scan_from: i64 = if rescan { 0 } else { -1 };
+        let args: String = format!(
+            r#"extkeyimportmaster "{}" "" false "{}" "{}" {}"#,
+            mnemonic, wallet_name, wallet_name, scan_from
+        );
+
+        self.set_rpcurl(wallet_name).await;
+
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        match res {
+            Ok(value) => Ok(value),
+            Err(err) => {
+                error!("{}", err.to_string());
+                Err(err)
+            }
+        }
+    }
+
     pub async fn validate_mnemonic(
         &self,
         mnemonic: &str,
@@ -648,10 +1296,12 @@ impl DaemonHelper {
         Ok(mnemonic_valid)
     }
 
+    // With scan_from only blocks after that timestamp are scanned for the wallet's transactions.
     pub async fn import_wallet(
         &self,
         wallet_name: &str,
         mnemonic: &str,
+        scan_from: Option<u64>,
         db: &GVDB,
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         let mnemonic_valid: bool = self.validate_mnemonic(mnemonic).await?;
@@ -688,10 +1338,13 @@ impl DaemonHelper {
         self.set_rpcurl(wallet_name).await;
 
         // Import master key
-        let args: String = format!(
+        let mut args: String = format!(
             r#"extkeyimportmaster "{}" "" false "{}" "{}""#,
             mnemonic, wallet_name, wallet_name
         );
+        if let Some(scan_from) = scan_from {
+            args.push_str(&format!(" {}", scan_from));
+        }
         let res: Result<Value, Box<dyn Error + Send + Sync>> =
             rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
 
@@ -723,12 +1376,14 @@ impl DaemonHelper {
         self.set_rpcurl(wallet_name).await;
 
         let ext_pub_key_value: Value = self.getnewextaddress().await?;
-        let ext_pub_key: &str = ext_pub_key_value.as_str().unwrap();
+        let ext_pub_key: &str = ext_pub_key_value
+            .as_str()
+            .ok_or("Malformed getnewextaddress response")?;
         let internal_anon = self
             .getnewstealthaddress()
             .await?
             .as_str()
-            .unwrap()
+            .ok_or("Malformed getnewstealthaddress response")?
             .to_string();
 
         let mut conf = self.config.write().await;
@@ -741,6 +1396,7 @@ impl DaemonHelper {
         }
 
         conf.update_gv_config("MNEMONIC", mnemonic)?;
+        clear_sealed_mnemonic(&mut conf)?;
         conf.update_gv_config("RPC_WALLET", wallet_name)?;
 
         drop(conf);
@@ -756,6 +1412,12 @@ impl DaemonHelper {
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         info!("Checking wallets...");
         let conf = self.config.read().await;
+
+        if conf.watch_only {
+            drop(conf);
+            return self.check_watch_wallet().await;
+        }
+
         let create_on_fail: bool = if conf.rpc_wallet.is_empty() {
             true
         } else {
@@ -773,14 +1435,14 @@ impl DaemonHelper {
             &rpc_wallet
         };
 
-        let loaded_wallets: Value = self.list_wallets().await.unwrap();
+        let loaded_wallets: Value = self.list_wallets().await?;
 
         let is_loaded: bool = if rpc_wallet.is_empty() {
             false
         } else {
             loaded_wallets
                 .as_array()
-                .unwrap()
+                .ok_or("Malformed listwallets response")?
                 .contains(&Value::String(cold_wallet.to_string()))
         };
 
@@ -797,7 +1459,7 @@ impl DaemonHelper {
                     conf.update_gv_config("RPC_WALLET", cold_wallet)?;
                     drop(conf);
                 } else {
-                    panic!("Failed to load wallet");
+                    return Err(format!("Failed to load wallet {}", cold_wallet).into());
                 }
             } else {
                 if rpc_wallet != cold_wallet {
@@ -812,25 +1474,17 @@ impl DaemonHelper {
 
         self.set_rpcurl(conf.rpc_wallet.as_str()).await;
 
-        let wallet_reward_addr: Option<String> = self.get_reward_addr_from_wallet().await.unwrap();
+        let wallet_reward_addr: Option<String> = self.get_reward_addr_from_wallet().await?;
 
-        if conf.reward_address.is_some() {
-            let reward_addr: String = conf.clone().reward_address.unwrap();
-            if wallet_reward_addr.is_none() {
-                info!("Setting reward address in wallet...");
-                self.set_reward_addr_in_wallet(Some(&reward_addr))
-                    .await
-                    .unwrap();
-            } else if wallet_reward_addr.unwrap() != reward_addr {
+        if let Some(reward_addr) = &conf.reward_address {
+            if wallet_reward_addr.as_ref() != Some(reward_addr) {
                 info!("Setting reward address in wallet...");
-                self.set_reward_addr_in_wallet(Some(&reward_addr))
-                    .await
-                    .unwrap();
+                self.set_reward_addr_in_wallet(Some(reward_addr)).await?;
             }
         } else {
             if wallet_reward_addr.is_some() {
                 info!("Clearing reward address in wallet...");
-                self.set_reward_addr_in_wallet(None).await.unwrap();
+                self.set_reward_addr_in_wallet(None).await?;
             }
         }
 
@@ -839,10 +1493,9 @@ impl DaemonHelper {
         if internal_anon.is_none() {
             let anon_addr: String = self
                 .getnewstealthaddress()
-                .await
-                .unwrap()
+                .await?
                 .as_str()
-                .unwrap()
+                .ok_or("Malformed getnewstealthaddress response")?
                 .to_string();
             let mut conf = self.config.write().await;
             conf.update_gv_config("INTERNAL_ANON", &anon_addr)?;
@@ -853,10 +1506,9 @@ impl DaemonHelper {
         if ext_pub_key.is_none() {
             let ext_pub_key: String = self
                 .getnewextaddress()
-                .await
-                .unwrap()
+                .await?
                 .as_str()
-                .unwrap()
+                .ok_or("Malformed getnewextaddress response")?
                 .to_string();
             let mut conf = self.config.write().await;
             conf.update_gv_config("EXT_PUB_KEY", &ext_pub_key)?;
@@ -864,6 +1516,65 @@ impl DaemonHelper {
             drop(conf);
         }
 
+        // The extra cold wallets stake too, one that fails to load doesn't stop the vault.
+        for wallet in db.get_cold_wallets() {
+            if wallet.name == cold_wallet
+                || loaded_wallets
+                    .as_array()
+                    .is_some_and(|loaded| loaded.contains(&Value::String(wallet.name.clone())))
+            {
+                continue;
+            }
+
+            if let Err(err) = self.load_wallet(&wallet.name).await {
+                warn!("Failed to load cold wallet {}: {}", wallet.name, err);
+            }
+        }
+
+        Ok(Value::String("ok".to_string()))
+    }
+
+    // A watch-only vault only ever has the ext pub key of the vault it monitors, so there is no
+    // mnemonic, reward address or internal anon address to set up.
+    async fn check_watch_wallet(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let conf = self.config.read().await;
+        let rpc_wallet: String = conf.rpc_wallet.clone();
+        let ext_pub_key: String = conf
+            .ext_pub_key
+            .clone()
+            .ok_or("WATCH_ONLY needs EXT_PUB_KEY set to the ext pub key of the vault to watch")?;
+        drop(conf);
+
+        let watch_wallet: &str = if rpc_wallet.is_empty() {
+            DEFAULT_WATCH_WALLET
+        } else {
+            &rpc_wallet
+        };
+
+        if self.load_wallet(watch_wallet).await.is_err() {
+            info!("Creating watch-only wallet {}...", watch_wallet);
+
+            // No private keys and blank, the ext pub key is the only thing in it
+            let args: String = format!(r#"createwallet "{}" true true"#, watch_wallet);
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await?;
+
+            self.set_rpcurl(watch_wallet).await;
+
+            let args: String = format!(
+                r#"extkey importAccount "{}" 0 "GV_WATCH_ONLY""#,
+                ext_pub_key
+            );
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await?;
+        }
+
+        if rpc_wallet != watch_wallet {
+            let mut conf = self.config.write().await;
+            conf.update_gv_config("RPC_WALLET", watch_wallet)?;
+            drop(conf);
+        }
+
+        self.set_rpcurl(watch_wallet).await;
+
         Ok(Value::String("ok".to_string()))
     }
 
@@ -922,8 +1633,8 @@ impl DaemonHelper {
 
         if cli_path.exists() {
             let command = Command::new(&cli_path)
-                .arg(format!("-datadir={}", daemon_data_dir.to_str().unwrap()))
-                .arg(format!("-conf={}", daemon_conf_path.to_str().unwrap()))
+                .arg(format!("-datadir={}", daemon_data_dir.display()))
+                .arg(format!("-conf={}", daemon_conf_path.display()))
                 .arg("stop")
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
@@ -949,11 +1660,18 @@ impl DaemonHelper {
         info!("Ghost daemon is fully shut down...");
     }
 
+    // An error from call_status counts as down, it keeps retrying the same as a null reply.
+    async fn daemon_down(&self) -> bool {
+        self.call_status(true)
+            .await
+            .map_or(true, |status| status.is_null())
+    }
+
     pub async fn wait_for_daemon_startup(&self) {
-        if self.call_status(true).await.unwrap().is_null() {
+        if self.daemon_down().await {
             info!("Waiting for Ghost daemon to startup...");
 
-            while self.call_status(true).await.unwrap().is_null() {
+            while self.daemon_down().await {
                 tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
             }
 
@@ -963,10 +1681,39 @@ impl DaemonHelper {
         info!("Ghost daemon is ready...");
     }
 
+    // Waits for ghostd to answer and catch up to its peers, restarting it when it's down.
+    // Used to prove a freshly installed ghostd works before the old one is let go.
+    pub async fn wait_for_daemon_health(
+        &self,
+        timeout: Duration,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let deadline: tokio::time::Instant = tokio::time::Instant::now() + timeout;
+
+        info!("Waiting for Ghost daemon to start and sync...");
+
+        while tokio::time::Instant::now() < deadline {
+            let status: Value = self.call_status(true).await?;
+
+            if !status.is_null() && !DaemonHelper::syncing_from_info(&status).unwrap_or(true) {
+                info!("Ghost daemon is healthy...");
+                return Ok(());
+            }
+
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+
+        Err(format!(
+            "ghostd did not start and sync within {}",
+            humantime::format_duration(timeout)
+        )
+        .into())
+    }
+
     pub async fn call_status(
         &self,
         restart_on_error: bool,
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        // A liveness check, so always asks the daemon rather than the cache.
         let res: Result<Value, Box<dyn Error + Send + Sync>> = rpc::call(
             "getblockchaininfo",
             &self.get_rpcurl().await,
@@ -1008,50 +1755,105 @@ impl DaemonHelper {
         Ok(tx_details.to_owned())
     }
 
-    pub async fn cleanup_missing_tx(&self, db: &Arc<GVDB>) {
-        info!("Checking missed stakes...");
-        let last_status: Option<DaemonStatusDB> = db.get_daemon_status();
-
-        if last_status.is_none() {
-            self.import_legacy_history(db).await;
-        } else {
-            let last_status: DaemonStatusDB = last_status.unwrap();
-            let args: String = format!("listsinceblock {} 1 true", last_status.block_hash);
+    // Several calls in one round trip, results are in the order of `calls`.
+    pub async fn batch(
+        &self,
+        calls: &[String],
+    ) -> Result<Vec<Result<Value, Box<dyn Error + Send + Sync>>>, Box<dyn Error + Send + Sync>>
+    {
+        let rpcurl: RPCURL = self.get_rpcurl().await;
 
-            let res = rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+        let mut results: Vec<Option<Result<Value, Box<dyn Error + Send + Sync>>>> =
+            Vec::with_capacity(calls.len());
+        let mut ttls: Vec<Duration> = Vec::with_capacity(calls.len());
+        let mut to_send: Vec<String> = Vec::new();
 
-            let res_opt = match res {
-                Ok(value) => Some(value),
-                Err(_) => None,
-            };
+        // Fresh cached results are used as is, only the rest goes to the daemon.
+        for call in calls {
+            let ttl: Duration = self.cache_ttl(call).await;
+            let cached: Option<Value> =
+                DaemonHelper::cache_get(&DaemonHelper::cache_key(&rpcurl, call), ttl);
 
-            let tx_array_opt = if res_opt.is_some() {
-                let value = res_opt.as_ref().unwrap();
-                let tx_array = value.get("transactions").unwrap().as_array();
-                tx_array
-            } else {
-                None
-            };
+            if cached.is_none() {
+                to_send.push(call.to_string());
+            }
 
-            if tx_array_opt.is_some() {
-                let mut tx_array = tx_array_opt.unwrap().clone();
-                tx_array.sort_by_key(|tx| {
-                    tx.get("confirmations")
-                        .unwrap_or(&Value::Number(0.into()))
-                        .as_i64()
-                        .unwrap()
-                });
-                let mut count = 0;
-                for tx in tx_array.iter().rev() {
-                    let trusted: Option<&Value> = tx.get("trusted");
+            results.push(cached.map(Ok));
+            ttls.push(ttl);
+        }
 
-                    if trusted.is_some() {
-                        if !trusted.unwrap().as_bool().unwrap() {
-                            continue;
-                        }
+        let mut sent = match rpc::call_batch(&to_send, &rpcurl, &self.rpc_client).await {
+            Ok(sent) => sent.into_iter(),
+            Err(err) => {
+                error!("{}", err);
+                return Err(err);
+            }
+        };
+
+        let results = results
+            .into_iter()
+            .zip(calls.iter().zip(ttls))
+            .map(|(result, (call, ttl))| {
+                result.unwrap_or_else(|| {
+                    let result = sent
+                        .next()
+                        .unwrap_or_else(|| Err("No reply in batch response".into()));
+
+                    if let Ok(value) = &result {
+                        DaemonHelper::cache_put(DaemonHelper::cache_key(&rpcurl, call), ttl, value);
                     }
 
-                    let category: &str = tx.get("category").unwrap().as_str().unwrap();
+                    result
+                })
+            })
+            .collect();
+
+        Ok(results)
+    }
+
+    pub async fn cleanup_missing_tx(&self, db: &Arc<GVDB>) {
+        info!("Checking missed stakes...");
+        let last_status: Option<DaemonStatusDB> = db.get_daemon_status();
+
+        if let Some(last_status) = last_status {
+            // Stakes from blocks a pruned ghostd dropped can't be looked into any more
+            let since_hash: String = match self.prune_height().await {
+                Some(prune_height) if last_status.height < prune_height => {
+                    info!(
+                        "Last seen block {} is pruned, checking from block {}",
+                        last_status.height, prune_height
+                    );
+                    self.getblockhash(prune_height)
+                        .await
+                        .unwrap_or(last_status.block_hash)
+                }
+                _ => last_status.block_hash,
+            };
+            let args: String = format!("listsinceblock {} 1 true", since_hash);
+
+            let res = rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+            let tx_array_opt: Option<Vec<Value>> = match res {
+                Ok(value) => value.get("transactions").and_then(Value::as_array).cloned(),
+                Err(err) => {
+                    error!("Error listing transactions since the last block: {}", err);
+                    None
+                }
+            };
+
+            if let Some(mut tx_array) = tx_array_opt {
+                tx_array
+                    .sort_by_key(|tx| tx.get("confirmations").and_then(Value::as_i64).unwrap_or(0));
+                let mut count = 0;
+                for tx in tx_array.iter().rev() {
+                    if tx.get("trusted").and_then(Value::as_bool) == Some(false) {
+                        continue;
+                    }
+
+                    let category: &str = tx
+                        .get("category")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default();
                     match category {
                         "stake" => {
                             self.process_stake_transaction(tx, &db).await;
@@ -1060,7 +1862,8 @@ impl DaemonHelper {
                         "receive" => {
                             let is_watchonly = tx
                                 .get("involvesWatchonly")
-                                .map_or(false, |value| value.as_bool().unwrap());
+                                .and_then(Value::as_bool)
+                                .unwrap_or(false);
 
                             if !is_watchonly {
                                 continue;
@@ -1075,29 +1878,43 @@ impl DaemonHelper {
                     info!("Successfully imported {count} stakes...");
                 }
             }
+        } else {
+            self.import_legacy_history(db).await;
         }
 
+        let zap_confirmations: u32 = self.config.read().await.zap_confirmations as u32;
+
         for result in db.zap_status_db.iter() {
             match result {
                 Ok((key, value)) => {
-                    let mut zap_item: ZapStatusDB = serde_json::from_slice(&value).unwrap();
+                    let mut zap_item: ZapStatusDB = match serde_json::from_slice(&value) {
+                        Ok(zap_item) => zap_item,
+                        Err(err) => {
+                            error!("Unreadable zap status: {}", err);
+                            continue;
+                        }
+                    };
                     let txid: &str = zap_item.txid.as_str();
-                    let tx = self.get_transaction(txid).await;
 
-                    let tx = if tx.is_err() {
-                        db.remove_zap_status(key).await.unwrap();
-                        continue;
-                    } else {
-                        tx.unwrap()
+                    // A zap ghostd no longer knows, or that's in conflict, is dropped
+                    let confirms: Option<u32> = match self.get_transaction(txid).await {
+                        Ok(tx) => tx
+                            .get("confirmations")
+                            .and_then(Value::as_u64)
+                            .map(|confirms| confirms as u32),
+                        Err(_) => None,
                     };
 
-                    let confirms: u32 = tx.get("confirmations").unwrap().as_u64().unwrap() as u32;
+                    let res = match confirms {
+                        Some(confirms) if confirms <= zap_confirmations => {
+                            zap_item.confirmations = confirms;
+                            db.set_zap_status(key, &zap_item).await
+                        }
+                        _ => db.remove_zap_status(key).await,
+                    };
 
-                    if confirms > 225 {
-                        db.remove_zap_status(key).await.unwrap();
-                    } else {
-                        zap_item.confirmations = confirms;
-                        db.set_zap_status(key, &zap_item).await.unwrap();
+                    if let Err(err) = res {
+                        error!("Error updating the zap status: {}", err);
                     }
                 }
                 Err(err) => {
@@ -1106,43 +1923,54 @@ impl DaemonHelper {
             }
         }
 
-        let bc_info: Value = self.getblockchaininfo().await.unwrap();
-        let height: u32 = bc_info.get("blocks").unwrap().as_u64().unwrap() as u32;
-        let block_hash: String = bc_info
-            .get("bestblockhash")
-            .unwrap()
-            .as_str()
-            .unwrap()
-            .to_string();
+        let bc_info: Value = match self.getblockchaininfo().await {
+            Ok(bc_info) => bc_info,
+            Err(err) => {
+                error!("Error getting blockchain info: {}", err);
+                return;
+            }
+        };
+
+        let tip = (
+            bc_info.get("blocks").and_then(Value::as_u64),
+            bc_info.get("bestblockhash").and_then(Value::as_str),
+        );
 
-        let daemon_status: DaemonStatusDB = DaemonStatusDB { height, block_hash };
-        db.set_daemon_status(&daemon_status).await.unwrap();
+        if let (Some(height), Some(block_hash)) = tip {
+            let daemon_status: DaemonStatusDB = DaemonStatusDB {
+                height: height as u32,
+                block_hash: block_hash.to_string(),
+            };
+
+            if let Err(err) = db.set_daemon_status(&daemon_status).await {
+                error!("Error saving the daemon status: {}", err);
+            }
+        } else {
+            error!("Malformed getblockchaininfo response");
+        }
     }
 
     async fn process_received_tx(&self, tx: &Value, db: &Arc<GVDB>) -> Option<ZapStatusDB> {
-        let tx_category: &str = tx.get("category").unwrap().as_str().unwrap();
+        let tx_category: &str = tx.get("category")?.as_str()?;
 
         if tx_category != "receive" {
             return None;
         }
 
-        let confirms = tx.get("confirmations");
-        let confirms = if confirms.is_none() {
-            return None;
-        } else {
-            confirms.unwrap().as_i64().unwrap() as i32
-        };
+        let confirms: i32 = tx.get("confirmations")?.as_i64()? as i32;
+
+        let zap_confirmations: i32 = self.config.read().await.zap_confirmations as i32;
 
-        if confirms > 225 || confirms < 0 {
+        if confirms > zap_confirmations || confirms < 0 {
             return None;
         }
 
-        let txid: &str = tx.get("txid").unwrap().as_str().unwrap();
+        let txid: &str = tx.get("txid")?.as_str()?;
 
         let zap_item: Option<ZapStatusDB> = db.get_zap_status(txid);
 
         if zap_item.is_none() {
-            let amount: f64 = tx.get("amount").unwrap().as_f64().unwrap();
+            let amount: f64 = tx.get("amount")?.as_f64()?;
             let amount_int: u64 = self.convert_to_sat(amount);
             let first_notice: bool = false;
 
@@ -1153,21 +1981,26 @@ impl DaemonHelper {
                 first_notice,
             };
 
-            db.set_zap_status(txid.as_bytes(), &zap_item).await.unwrap();
+            if let Err(err) = db.set_zap_status(txid.as_bytes(), &zap_item).await {
+                error!("Error saving the zap status of {}: {}", txid, err);
+            }
             Some(zap_item)
         } else {
-            Some(zap_item.unwrap())
+            zap_item
         }
     }
 
     pub async fn clear_wallet_tx(&self) {
-        let _: Value = rpc::call(
+        let res: Result<Value, Box<dyn Error + Send + Sync>> = rpc::call(
             "clearwallettransactions",
             &self.get_rpcurl().await,
             &self.rpc_client,
         )
-        .await
-        .unwrap();
+        .await;
+
+        if let Err(err) = res {
+            error!("Error clearing the wallet transactions: {}", err);
+        }
     }
 
     pub async fn get_last_stake(
@@ -1179,21 +2012,18 @@ impl DaemonHelper {
             "include_watchonly": true
         }"#;
 
-        let json_data: Value = serde_json::from_str(req).unwrap();
+        let json_data: Value = serde_json::from_str(req)?;
         let args: String = format!("filtertransactions {}", json_data);
 
         let res: Result<Value, Box<dyn Error + Send + Sync>> =
             rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
 
         let last_stake = match res {
-            Ok(value) => {
-                let value_array = value.as_array().unwrap();
-                if value_array.is_empty() {
-                    None
-                } else {
-                    Some(value_array[0].to_owned())
-                }
-            }
+            Ok(value) => value
+                .as_array()
+                .ok_or("Malformed filtertransactions response")?
+                .first()
+                .cloned(),
             Err(err) => {
                 self.parse_error_msg(err.to_string()).await;
                 error!("{}", err.to_string());
@@ -1205,24 +2035,45 @@ impl DaemonHelper {
     }
 
     pub async fn import_legacy_history(&self, db: &Arc<GVDB>) {
+        if let Err(err) = self.import_history(db, |_, _| ()).await {
+            error!("Error importing the wallet history: {}", err);
+        }
+    }
+
+    // Reads every stake and watch only receive in the wallet into the database, progress is
+    // called with the transactions done and the total. Returns the stakes recorded.
+    pub async fn import_history(
+        &self,
+        db: &Arc<GVDB>,
+        progress: impl Fn(usize, usize) + Send + Sync,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
         let req = r#"{
             "count": 0,
             "include_watchonly": true,
             "sort": "confirmations"
         }"#;
 
-        let json_data: Value = serde_json::from_str(req).unwrap();
+        let json_data: Value = serde_json::from_str(req)?;
         let args: String = format!("filtertransactions {}", json_data);
 
-        let res: Value = rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client)
-            .await
-            .unwrap();
+        let res: Value = rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await?;
+
+        let tx_array: &Vec<Value> = res
+            .as_array()
+            .ok_or("Malformed filtertransactions response")?;
+        let mut stakes: usize = 0;
 
-        let tx_array: &Vec<Value> = res.as_array().unwrap();
+        for (index, tx) in tx_array.iter().enumerate() {
+            progress(index, tx_array.len());
 
-        for tx in tx_array.iter() {
-            let category: &str = tx.get("category").unwrap().as_str().unwrap();
-            let confirms: i64 = tx.get("confirmations").unwrap().as_i64().unwrap();
+            let category: &str = tx
+                .get("category")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let confirms: i64 = tx
+                .get("confirmations")
+                .and_then(Value::as_i64)
+                .unwrap_or(-1);
 
             if confirms < 0 {
                 continue;
@@ -1230,20 +2081,18 @@ impl DaemonHelper {
 
             match category {
                 "stake" => {
-                    self.process_stake_transaction(tx, &db).await;
+                    if self.process_stake_transaction(tx, db).await.is_some() {
+                        stakes += 1;
+                    }
                 }
                 "receive" => {
-                    let tx_outputs = tx.get("outputs").unwrap().as_array().unwrap();
-
-                    if tx_outputs.is_empty() {
-                        continue;
-                    }
-
-                    let is_watchonly = tx_outputs[0]
-                        .get("involvesWatchonly")
-                        .unwrap_or(&Value::Bool(false))
-                        .as_bool()
-                        .unwrap();
+                    let is_watchonly = tx
+                        .get("outputs")
+                        .and_then(Value::as_array)
+                        .and_then(|tx_outputs| tx_outputs.first())
+                        .and_then(|output| output.get("involvesWatchonly"))
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false);
 
                     if !is_watchonly {
                         continue;
@@ -1256,32 +2105,55 @@ impl DaemonHelper {
                 }
             }
         }
+
+        progress(tx_array.len(), tx_array.len());
+
+        Ok(stakes)
     }
 
-    pub async fn process_stake_transaction(&self, tx: &Value, db: &Arc<GVDB>) -> RewardsDB {
-        let timestamp: u64 = tx.get("blocktime").unwrap().as_u64().unwrap();
-        let height: u32 = tx.get("blockheight").unwrap().as_u64().unwrap() as u32;
+    pub async fn process_stake_transaction(&self, tx: &Value, db: &Arc<GVDB>) -> Option<RewardsDB> {
+        let timestamp: u64 = tx.get("blocktime")?.as_u64()?;
+        let height: u32 = tx.get("blockheight")?.as_u64()? as u32;
 
-        let block_hash: String = tx.get("blockhash").unwrap().as_str().unwrap().to_string();
-        let txid: String = tx.get("txid").unwrap().as_str().unwrap().to_string();
+        let block_hash: String = tx.get("blockhash")?.as_str()?.to_string();
+        let txid: String = tx.get("txid")?.as_str()?.to_string();
 
-        let block_reward_details: BlockReward = self.get_block_reward(&txid, height).await.unwrap();
+        let block_reward_details: BlockReward = match self.get_block_reward(&txid, height).await {
+            Ok(block_reward_details) => block_reward_details,
+            Err(err) => {
+                warn!("Skipping stake {}: {}", txid, err);
+                return None;
+            }
+        };
 
         let reward: u64 = block_reward_details.stake_reward;
         let agvr_reward: u64 = block_reward_details.agvr_reward;
         let address: String = block_reward_details.stake_kernel;
         let is_coldstake: bool = block_reward_details.is_coldstake;
 
-        let last_stake_opt = db.rewards_ts_index.last().unwrap();
+        let last_stake_opt: Option<RewardsDB> = match db.rewards_ts_index.last() {
+            Ok(Some((_, value))) => match serde_json::from_slice(&value) {
+                Ok(stake_info) => Some(stake_info),
+                Err(err) => {
+                    error!("Unreadable last stake, skipping stake {}: {}", txid, err);
+                    return None;
+                }
+            },
+            Ok(None) => None,
+            Err(err) => {
+                error!(
+                    "Error reading the last stake, skipping stake {}: {}",
+                    txid, err
+                );
+                return None;
+            }
+        };
 
         let (all_time_reward, all_time_agvr_reward) = match last_stake_opt {
-            Some((_, value)) => {
-                let stake_info: RewardsDB = serde_json::from_slice(&value).unwrap();
-                (
-                    stake_info.all_time_reward + reward,
-                    stake_info.all_time_agvr_reward + agvr_reward,
-                )
-            }
+            Some(stake_info) => (
+                stake_info.all_time_reward + reward,
+                stake_info.all_time_agvr_reward + agvr_reward,
+            ),
             None => (reward, agvr_reward),
         };
 
@@ -1296,31 +2168,37 @@ impl DaemonHelper {
             all_time_agvr_reward,
             address,
             is_coldstake,
+            wallet: self.wallet_name().await,
         };
 
-        let confirms: u64 = tx
-            .get("confirmations")
-            .map_or(0, |val| val.as_u64().unwrap());
-
-        if confirms <= 100 {
-            let txid: &str = tx.get("txid").unwrap().as_str().unwrap();
-            let timestamp: u64 = tx.get("blocktime").unwrap().as_u64().unwrap();
+        let confirms: u64 = tx.get("confirmations").and_then(Value::as_u64).unwrap_or(0);
 
+        if confirms <= self.config.read().await.stake_confirmations {
             let stake_item: NewStakeStatusDB = NewStakeStatusDB {
-                txid: txid.to_string(),
+                txid: final_reward.txid.clone(),
                 timestamp,
                 confirmations: confirms as u32,
                 tg_msg_id: None,
+                summary: None,
             };
 
-            db.set_new_stake_status(txid.as_bytes(), &stake_item)
+            if let Err(err) = db
+                .set_new_stake_status(final_reward.txid.as_bytes(), &stake_item)
                 .await
-                .unwrap();
+            {
+                error!(
+                    "Error saving the stake status of {}: {}",
+                    final_reward.txid, err
+                );
+            }
         }
 
-        db.set_reward(&final_reward).await.unwrap();
+        if let Err(err) = db.set_reward(&final_reward).await {
+            error!("Error saving stake {}: {}", final_reward.txid, err);
+            return None;
+        }
 
-        final_reward
+        Some(final_reward)
     }
 
     pub async fn get_block_reward(
@@ -1328,7 +2206,7 @@ impl DaemonHelper {
         txid: &str,
         height: u32,
     ) -> Result<BlockReward, Box<dyn std::error::Error + Send + Sync>> {
-        let tx_details: Value = self.get_transaction(txid).await.unwrap();
+        let tx_details: Value = self.get_transaction(txid).await?;
 
         let tx_vin = &tx_details
             .get("decoded")
@@ -1349,15 +2227,39 @@ impl DaemonHelper {
             .as_array()
             .ok_or("Vout not an array")?;
 
+        let mut prev_vouts: Vec<u64> = Vec::with_capacity(tx_vin.len());
+        let mut prev_tx_calls: Vec<String> = Vec::with_capacity(tx_vin.len());
+
         for vin in tx_vin.iter() {
-            let prev_txid: &str = vin.get("txid").unwrap().as_str().unwrap();
-            let prev_vout: u64 = vin.get("vout").unwrap().as_u64().unwrap();
+            let prev_txid: &str = vin
+                .get("txid")
+                .and_then(|txid| txid.as_str())
+                .ok_or("Vin txid not found")?;
+            let prev_vout: u64 = vin
+                .get("vout")
+                .and_then(|vout| vout.as_u64())
+                .ok_or("Vin vout not found")?;
+
+            prev_vouts.push(prev_vout);
+            prev_tx_calls.push(format!("gettransaction {} true true", prev_txid));
+        }
 
-            let prev_tx: Result<Value, Box<dyn Error + Send + Sync>> =
-                self.get_transaction(prev_txid).await;
+        // All the inputs are looked up in one batch rather than a call per input.
+        let prev_txs: Vec<Result<Value, Box<dyn Error + Send + Sync>>> =
+            self.batch(&prev_tx_calls).await?;
+
+        // Without an input's value the whole stake would be counted as the reward. A full node
+        // gets by, a pruned one may have dropped the block that funded it.
+        if prev_txs.iter().any(|prev_tx| prev_tx.is_err()) && self.prune_height().await.is_some() {
+            return Err(format!(
+                "The inputs of {} are not available, their blocks may be pruned",
+                txid
+            )
+            .into());
+        }
 
-            if prev_tx.is_ok() {
-                let prev_tx = prev_tx.unwrap();
+        for (prev_vout, prev_tx) in prev_vouts.into_iter().zip(prev_txs) {
+            if let Ok(prev_tx) = prev_tx {
                 let prev_vout_array = prev_tx
                     .get("decoded")
                     .ok_or("No decoded value")?
@@ -1368,9 +2270,8 @@ impl DaemonHelper {
 
                 in_amount += prev_vout_array[prev_vout as usize]
                     .get("valueSat")
-                    .unwrap()
-                    .as_u64()
-                    .unwrap();
+                    .and_then(|value| value.as_u64())
+                    .ok_or("Vout valueSat not found")?;
 
                 if stake_kernel.is_empty() {
                     stake_kernel = self.get_addr_from_vout(&prev_vout_array[prev_vout as usize]);
@@ -1395,9 +2296,8 @@ impl DaemonHelper {
 
             vout_array[agvr_vout]
                 .get("valueSat")
-                .unwrap()
-                .as_u64()
-                .unwrap()
+                .and_then(|value| value.as_u64())
+                .ok_or("Vout valueSat not found")?
         };
 
         let mut vout_total: u64 = 0;
@@ -1406,13 +2306,7 @@ impl DaemonHelper {
         for vout in vout_array {
             let blacklist_type: Vec<&str> = vec!["data", "anon", "blind"];
 
-            let default_vout_type = Value::String(String::new());
-
-            let vout_type: &str = vout
-                .get("type")
-                .unwrap_or(&default_vout_type)
-                .as_str()
-                .unwrap();
+            let vout_type: &str = vout.get("type").and_then(Value::as_str).unwrap_or_default();
             if blacklist_type.contains(&vout_type) {
                 continue;
             }
@@ -1431,7 +2325,10 @@ impl DaemonHelper {
                 is_coldstake = true;
             }
 
-            vout_total += vout.get("valueSat").unwrap().as_u64().unwrap();
+            vout_total += vout
+                .get("valueSat")
+                .and_then(|value| value.as_u64())
+                .ok_or("Vout valueSat not found")?;
         }
 
         let stake_reward: u64 = vout_total - in_amount - agvr_reward;
@@ -1466,41 +2363,7 @@ impl DaemonHelper {
             daemon_path
         };
 
-        let output = Command::new(&daemon_path)
-            .arg("--version")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .expect("Command failed to start")
-            .wait_with_output()
-            .expect("Failed to wait for child process");
-
-        if output.status.success() {
-            let result_string = String::from_utf8_lossy(&output.stdout);
-            let version = result_string
-                .split("\n")
-                .collect::<Vec<&str>>()
-                .first()
-                .unwrap()
-                .split(" ")
-                .collect::<Vec<&str>>()
-                .last()
-                .unwrap()
-                .strip_prefix("v")
-                .unwrap()
-                .split("-")
-                .collect::<Vec<&str>>()
-                .first()
-                .unwrap()
-                .strip_suffix(".0")
-                .unwrap()
-                .to_string();
-            Ok(version)
-        } else {
-            let error_string = String::from_utf8_lossy(&output.stderr);
-            let err: String = format!("Command failed with error:\n{}", error_string);
-            Err(err.into())
-        }
+        gv_methods::get_binary_version(&daemon_path)
     }
 
     pub async fn build_script(
@@ -1534,36 +2397,145 @@ impl DaemonHelper {
         Ok(script)
     }
 
+    pub async fn estimate_smart_fee(
+        &self,
+        conf_target: u64,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let args: String = format!("estimatesmartfee {}", conf_target);
+
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        let estimate = match res {
+            Ok(value) => value,
+            Err(err) => {
+                self.parse_error_msg(err.to_string()).await;
+                error!("{}", err);
+                return Err(err);
+            }
+        };
+
+        Ok(estimate)
+    }
+
+    // The fee rate in sats per kB a send should use under the configured policy.
+    pub async fn fee_rate(&self, fees: &FeeSettings) -> u64 {
+        if fees.policy == FeePolicy::Fixed {
+            return fees.rate;
+        }
+
+        let estimate: Option<f64> = match self.estimate_smart_fee(fees.conf_target).await {
+            Ok(estimate) => estimate.get("feerate").and_then(|rate| rate.as_f64()),
+            Err(_) => None,
+        };
+
+        match estimate {
+            Some(rate) if rate > 0.0 => self.convert_to_sat(rate),
+            _ => {
+                warn!(
+                    "No fee estimate for {} blocks, using the fixed rate.",
+                    fees.conf_target
+                );
+                fees.rate
+            }
+        }
+    }
+
+    // Sends a set amount rather than every unspent output, the fee comes out of the amount.
+    pub async fn send_ghost_amount(
+        &self,
+        addr: &str,
+        in_type: &str,
+        out_type: &str,
+        amount: f64,
+        fee_rate: u64,
+        comment: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let precise_amount = self.precise(amount);
+        let fee_rate: f64 = self.convert_from_sat(fee_rate);
+        let ring_size: u32 = self.config.read().await.anon_ring_size;
+        let comment: String = comment_arg(comment);
+
+        let outputs: Value = json!([{
+            "address": addr,
+            "amount": precise_amount,
+            "subfee": true
+        }]);
+
+        let args: String = format!(
+            r#"sendtypeto {} {} {} {} "" {} 1 false {{"feeRate":{:.8}}}"#,
+            in_type, out_type, outputs, comment, ring_size, fee_rate
+        );
+
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        let txid: Value = match res {
+            Ok(value) => value,
+            Err(err) => {
+                error!("{}", err);
+                return Err(err);
+            }
+        };
+
+        Ok(txid
+            .as_str()
+            .ok_or("Malformed sendtypeto response")?
+            .to_string())
+    }
+
     pub async fn send_ghost(
         &self,
         addr: &str,
         in_type: &str,
         out_type: &str,
+        fee_rate: u64,
+        max_fee: u64,
+        comment: &str,
+        min_conf: u64,
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         let mut txids: Vec<Value> = Vec::new();
-        let max_fee: f64 = self.convert_from_sat(MAX_TX_FEES);
+        let fee_rate: f64 = self.convert_from_sat(fee_rate);
+        let max_fee: f64 = self.convert_from_sat(max_fee);
         let mut output_amt: f64 = 0.0;
+        let policy: AnonPolicy = self.config.read().await.anon_policy();
+        let ring_size: u32 = policy.ring_size;
+        let comment: String = comment_arg(comment);
+        // ANON_BATCH_SIZE only caps the inputs of public to anon conversions
+        let batch_size: usize = if in_type == "ghost" && out_type == "anon" {
+            policy.batch_size as usize
+        } else {
+            0
+        };
         let mut inputs: Vec<String> = Vec::new();
 
-        let unspent: Value = self.list_unspent(in_type).await?;
-        let unspent_array: &Vec<Value> = unspent.as_array().unwrap();
+        let unspent: Value = self.list_unspent_depth(in_type, min_conf).await?;
+        let unspent_array: &Vec<Value> =
+            unspent.as_array().ok_or("Malformed listunspent response")?;
         let unspent_len: usize = unspent_array.len();
 
         for (index, unspent_item) in unspent_array.iter().enumerate() {
-            let amount: f64 = unspent_item.get("amount").unwrap().as_f64().unwrap();
-            let txid: &str = unspent_item.get("txid").unwrap().as_str().unwrap();
-            let vout: u32 = unspent_item.get("vout").unwrap().as_u64().unwrap() as u32;
+            let amount: f64 = unspent_item
+                .get("amount")
+                .and_then(|amount| amount.as_f64())
+                .ok_or("Unspent amount not found")?;
+            let txid: &str = unspent_item
+                .get("txid")
+                .and_then(|txid| txid.as_str())
+                .ok_or("Unspent txid not found")?;
+            let vout: u32 = unspent_item
+                .get("vout")
+                .and_then(|vout| vout.as_u64())
+                .ok_or("Unspent vout not found")? as u32;
             let spendable: bool = {
                 let safe: bool = unspent_item
                     .get("safe")
-                    .unwrap_or(&Value::Bool(false))
-                    .as_bool()
-                    .unwrap();
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
                 let inner_spendable: bool = unspent_item
                     .get("spendable")
-                    .unwrap_or(&Value::Bool(false))
-                    .as_bool()
-                    .unwrap();
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
 
                 if in_type == "ghost" {
                     safe && inner_spendable
@@ -1593,8 +2565,10 @@ impl DaemonHelper {
                 continue;
             }
 
+            let batch_full: bool = batch_size > 0 && inputs.len() >= batch_size;
+
             // every 100 inputs we check the fee and send the tx, or if we are at the last unspent item
-            if inputs.len() % 100 == 0 || is_last {
+            if inputs.len() % 100 == 0 || is_last || batch_full {
                 let precise_amount = self.precise(output_amt);
 
                 let outputs: String = format!(
@@ -1617,27 +2591,33 @@ impl DaemonHelper {
                 ))?;
 
                 let args: String = format!(
-                    r#"sendtypeto {} {} {} "" "" 12 1 true {{"feeRate":0.00007500,"inputs":{}}}"#,
-                    in_type, out_type, json_data_out, json_data_in
+                    r#"sendtypeto {} {} {} {} "" {} 1 true {{"feeRate":{:.8},"inputs":{}}}"#,
+                    in_type, out_type, json_data_out, comment, ring_size, fee_rate, json_data_in
                 );
 
                 let fee_res = rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
 
                 let fee: Value = match fee_res {
-                    Ok(value) => value.get("fee").unwrap().to_owned(),
+                    Ok(value) => value.get("fee").ok_or("Fee not found")?.to_owned(),
                     Err(err) => {
                         error!("{}", err.to_string());
                         return Err(err);
                     }
                 };
 
-                let fee_amt: f64 = fee.as_f64().unwrap();
+                let fee_amt: f64 = fee.as_f64().ok_or("Fee not a number")?;
 
-                // If the fee is greater than the max fee or we are at the last unspent item
-                if fee_amt >= max_fee || is_last {
+                // Send once the fee reaches the max fee, the batch is full or at the last unspent item
+                if fee_amt >= max_fee || is_last || batch_full {
                     let args: String = format!(
-                        r#"sendtypeto {} {} {} "" "" 12 1 false {{"feeRate":0.00007500,"inputs":{}}}"#,
-                        in_type, out_type, json_data_out, json_data_in
+                        r#"sendtypeto {} {} {} {} "" {} 1 false {{"feeRate":{:.8},"inputs":{}}}"#,
+                        in_type,
+                        out_type,
+                        json_data_out,
+                        comment,
+                        ring_size,
+                        fee_rate,
+                        json_data_in
                     );
 
                     let res: Result<Value, Box<dyn Error + Send + Sync>> =
@@ -1666,43 +2646,57 @@ impl DaemonHelper {
         &self,
         spend_addr: &str,
         in_type: &str,
+        fee_rate: u64,
+        max_fee: u64,
+        comment: &str,
+        min_conf: u64,
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         let stake_addr: String = self.get_stake_addr().await?;
-        let max_fee: f64 = self.convert_from_sat(MAX_TX_FEES);
+        let fee_rate: f64 = self.convert_from_sat(fee_rate);
+        let max_fee: f64 = self.convert_from_sat(max_fee);
+        let ring_size: u32 = self.config.read().await.anon_ring_size;
+        let comment: String = comment_arg(comment);
 
         let mut txids: Vec<Value> = Vec::new();
 
         let cs_script_value: Value = self.build_script(&stake_addr, &spend_addr).await?;
         let cs_script: String = cs_script_value
             .get("hex")
-            .unwrap()
-            .as_str()
-            .unwrap()
+            .and_then(Value::as_str)
+            .ok_or("Malformed buildscript response")?
             .to_string();
 
         let mut output_amt: f64 = 0.0;
 
         let mut inputs: Vec<String> = Vec::new();
 
-        let unspent: Value = self.list_unspent(in_type).await?;
-        let unspent_array: &Vec<Value> = unspent.as_array().unwrap();
+        let unspent: Value = self.list_unspent_depth(in_type, min_conf).await?;
+        let unspent_array: &Vec<Value> =
+            unspent.as_array().ok_or("Malformed listunspent response")?;
         let unspent_len: usize = unspent_array.len();
 
         for (index, unspent_item) in unspent_array.iter().enumerate() {
-            let amount: f64 = unspent_item.get("amount").unwrap().as_f64().unwrap();
-            let txid: &str = unspent_item.get("txid").unwrap().as_str().unwrap();
-            let vout: u32 = unspent_item.get("vout").unwrap().as_u64().unwrap() as u32;
+            let amount: f64 = unspent_item
+                .get("amount")
+                .and_then(|amount| amount.as_f64())
+                .ok_or("Unspent amount not found")?;
+            let txid: &str = unspent_item
+                .get("txid")
+                .and_then(|txid| txid.as_str())
+                .ok_or("Unspent txid not found")?;
+            let vout: u32 = unspent_item
+                .get("vout")
+                .and_then(|vout| vout.as_u64())
+                .ok_or("Unspent vout not found")? as u32;
             let spendable: bool = {
                 let safe: bool = unspent_item
                     .get("safe")
-                    .unwrap_or(&Value::Bool(false))
-                    .as_bool()
-                    .unwrap();
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
                 let inner_spendable: bool = unspent_item
                     .get("spendable")
-                    .unwrap_or(&Value::Bool(false))
-                    .as_bool()
-                    .unwrap();
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
 
                 if in_type == "ghost" {
                     safe && inner_spendable
@@ -1757,27 +2751,27 @@ impl DaemonHelper {
                 ))?;
 
                 let args: String = format!(
-                    r#"sendtypeto {} ghost {} "" "" 12 1 true {{"feeRate":0.00007500,"inputs":{}}}"#,
-                    in_type, json_data_out, json_data_in
+                    r#"sendtypeto {} ghost {} {} "" {} 1 true {{"feeRate":{:.8},"inputs":{}}}"#,
+                    in_type, json_data_out, comment, ring_size, fee_rate, json_data_in
                 );
 
                 let fee_res = rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
 
                 let fee: Value = match fee_res {
-                    Ok(value) => value.get("fee").unwrap().to_owned(),
+                    Ok(value) => value.get("fee").ok_or("Fee not found")?.to_owned(),
                     Err(err) => {
                         error!("{}", err.to_string());
                         return Err(err);
                     }
                 };
 
-                let fee_amt: f64 = fee.as_f64().unwrap();
+                let fee_amt: f64 = fee.as_f64().ok_or("Fee not a number")?;
 
                 // If the fee is greater than the max fee or we are at the last unspent item
                 if fee_amt >= max_fee || is_last {
                     let args: String = format!(
-                        r#"sendtypeto {} ghost {} "" "" 12 1 false {{"feeRate":0.00007500,"inputs":{}}}"#,
-                        in_type, json_data_out, json_data_in
+                        r#"sendtypeto {} ghost {} {} "" {} 1 false {{"feeRate":{:.8},"inputs":{}}}"#,
+                        in_type, json_data_out, comment, ring_size, fee_rate, json_data_in
                     );
 
                     let res: Result<Value, Box<dyn Error + Send + Sync>> =
@@ -1805,11 +2799,20 @@ impl DaemonHelper {
     pub async fn list_unspent(
         &self,
         uns_type: &str,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.list_unspent_depth(uns_type, 0).await
+    }
+
+    // Like list_unspent but only outputs with at least min_conf confirmations, never fewer than
+    // the 1 public and 12 anon outputs need anyway.
+    pub async fn list_unspent_depth(
+        &self,
+        uns_type: &str,
+        min_conf: u64,
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         let args: String = match uns_type {
-            "ghost" => format!("listunspent 1 9999999 [] false"),
-            "anon" => format!("listunspentanon 12 9999999 [] false"),
-            _ => format!("listunspent 1 9999999 [] false"),
+            "anon" => format!("listunspentanon {} 9999999 [] false", min_conf.max(12)),
+            _ => format!("listunspent {} 9999999 [] false", min_conf.max(1)),
         };
 
         let res: Result<Value, Box<dyn Error + Send + Sync>> =
@@ -1823,13 +2826,254 @@ impl DaemonHelper {
             }
         };
 
-        Ok(unspent)
+        // Frozen outputs are locked in the wallet, keep them out of anything that sweeps
+        let locked: Vec<(String, u32)> = self.list_lock_unspent().await?;
+        if locked.is_empty() {
+            return Ok(unspent);
+        }
+
+        let unspent: Vec<Value> = unspent
+            .as_array()
+            .ok_or("Malformed listunspent response")?
+            .iter()
+            .filter(|item| {
+                let txid: &str = item
+                    .get("txid")
+                    .and_then(|txid| txid.as_str())
+                    .unwrap_or("");
+                let vout: u64 = item.get("vout").and_then(|vout| vout.as_u64()).unwrap_or(0);
+                !locked.iter().any(|(locked_txid, locked_vout)| {
+                    locked_txid == txid && *locked_vout as u64 == vout
+                })
+            })
+            .cloned()
+            .collect();
+
+        Ok(Value::Array(unspent))
+    }
+
+    // What send_ghost and zap_ghost would spend from in_type outputs with at least min_conf
+    // confirmations.
+    pub async fn unspent_balance(
+        &self,
+        in_type: &str,
+        min_conf: u64,
+    ) -> Result<f64, Box<dyn std::error::Error + Send + Sync>> {
+        let unspent: Value = self.list_unspent_depth(in_type, min_conf).await?;
+        let unspent_array: &Vec<Value> =
+            unspent.as_array().ok_or("Malformed listunspent response")?;
+
+        let mut balance: f64 = 0.0;
+
+        for unspent_item in unspent_array {
+            let safe: bool = unspent_item
+                .get("safe")
+                .and_then(|safe| safe.as_bool())
+                .unwrap_or(false);
+            let spendable: bool = unspent_item
+                .get("spendable")
+                .and_then(|spendable| spendable.as_bool())
+                .unwrap_or(false);
+
+            if safe && (spendable || in_type != "ghost") {
+                balance += unspent_item
+                    .get("amount")
+                    .and_then(|amount| amount.as_f64())
+                    .ok_or("Unspent amount not found")?;
+            }
+        }
+
+        Ok(self.precise(balance))
+    }
+
+    // Outputs other wallets delegated to this one's staking key, the wallet can stake but not
+    // spend them.
+    pub async fn list_coldstake_unspent(
+        &self,
+    ) -> Result<Vec<Value>, Box<dyn std::error::Error + Send + Sync>> {
+        let unspent: Value = self.list_unspent("ghost").await?;
+
+        Ok(unspent
+            .as_array()
+            .ok_or("Malformed listunspent response")?
+            .iter()
+            .filter(|item| item.get("coldstaking_address").is_some())
+            .cloned()
+            .collect())
+    }
+
+    pub async fn list_lock_unspent(
+        &self,
+    ) -> Result<Vec<(String, u32)>, Box<dyn std::error::Error + Send + Sync>> {
+        let res: Result<Value, Box<dyn Error + Send + Sync>> = rpc::call(
+            "listlockunspent",
+            &self.get_rpcurl().await,
+            &self.rpc_client,
+        )
+        .await;
+
+        let locked: Value = match res {
+            Ok(value) => value,
+            Err(err) => {
+                error!("{}", err);
+                return Err(err);
+            }
+        };
+
+        Ok(locked
+            .as_array()
+            .ok_or("Malformed listlockunspent response")?
+            .iter()
+            .filter_map(|output| {
+                let txid: String = output.get("txid")?.as_str()?.to_string();
+                let vout: u32 = output.get("vout")?.as_u64()? as u32;
+                Some((txid, vout))
+            })
+            .collect())
+    }
+
+    pub async fn lock_unspent(
+        &self,
+        unlock: bool,
+        outputs: &[(String, u32)],
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let outputs: Value = Value::Array(
+            outputs
+                .iter()
+                .map(|(txid, vout)| json!({"txid": txid, "vout": vout}))
+                .collect(),
+        );
+
+        let args: String = format!("lockunspent {} {}", unlock, outputs);
+
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        let locked = match res {
+            Ok(value) => value,
+            Err(err) => {
+                self.parse_error_msg(err.to_string()).await;
+                error!("{}", err);
+                return Err(err);
+            }
+        };
+
+        Ok(locked)
+    }
+
+    // Spends the given public outputs into a coldstake script, 100 inputs per transaction.
+    pub async fn consolidate_to_script(
+        &self,
+        utxos: &[(String, u32, f64)],
+        script_hex: &str,
+        fee_rate: u64,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let fee_rate: f64 = self.convert_from_sat(fee_rate);
+        let mut txids: Vec<String> = Vec::new();
+
+        for chunk in utxos.chunks(100) {
+            let amount: f64 = self.precise(chunk.iter().map(|(_, _, amount)| amount).sum());
+
+            let outputs: Value = json!([{
+                "address": "script",
+                "amount": amount,
+                "script": script_hex,
+                "subfee": true
+            }]);
+            let inputs: Value = Value::Array(
+                chunk
+                    .iter()
+                    .map(|(txid, vout, _)| json!({"tx": txid, "n": vout}))
+                    .collect(),
+            );
+
+            let args: String = format!(
+                r#"sendtypeto ghost ghost {} "" "" 12 1 false {{"feeRate":{:.8},"inputs":{}}}"#,
+                outputs, fee_rate, inputs
+            );
+
+            let res: Result<Value, Box<dyn Error + Send + Sync>> =
+                rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+            let txid: Value = match res {
+                Ok(value) => value,
+                Err(err) => {
+                    error!("{}", err);
+                    return Err(err);
+                }
+            };
+
+            txids.push(
+                txid.as_str()
+                    .ok_or("Malformed sendtypeto response")?
+                    .to_string(),
+            );
+        }
+
+        Ok(txids)
+    }
+
+    // Splits one output into equal pieces at the script, the last piece pays the fee.
+    pub async fn split_to_script(
+        &self,
+        txid: &str,
+        vout: u32,
+        amount: u64,
+        pieces: u64,
+        script_hex: &str,
+        fee_rate: u64,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let fee_rate: f64 = self.convert_from_sat(fee_rate);
+        let piece: u64 = amount / pieces;
+
+        let outputs: Value = Value::Array(
+            (0..pieces)
+                .map(|index| {
+                    let is_last: bool = index + 1 == pieces;
+                    // The last piece also takes what's left over from the division
+                    let piece_amount: u64 = if is_last {
+                        amount - piece * (pieces - 1)
+                    } else {
+                        piece
+                    };
+
+                    json!({
+                        "address": "script",
+                        "amount": self.convert_from_sat(piece_amount),
+                        "script": script_hex,
+                        "subfee": is_last
+                    })
+                })
+                .collect(),
+        );
+        let inputs: Value = json!([{"tx": txid, "n": vout}]);
+
+        let args: String = format!(
+            r#"sendtypeto ghost ghost {} "" "" 12 1 false {{"feeRate":{:.8},"inputs":{}}}"#,
+            outputs, fee_rate, inputs
+        );
+
+        let res: Result<Value, Box<dyn Error + Send + Sync>> =
+            rpc::call(&args, &self.get_rpcurl().await, &self.rpc_client).await;
+
+        let txid: Value = match res {
+            Ok(value) => value,
+            Err(err) => {
+                error!("{}", err);
+                return Err(err);
+            }
+        };
+
+        Ok(txid
+            .as_str()
+            .ok_or("Malformed sendtypeto response")?
+            .to_string())
     }
 
     pub async fn get_stake_addr(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let conf = self.config.read().await;
 
-        let ext_pub_key: String = conf.ext_pub_key.clone().unwrap();
+        let ext_pub_key: String = conf.ext_pub_key.clone().ok_or("No ext pub key set")?;
         drop(conf);
         let addr_index: i32 = rand::thread_rng().gen_range(0..64);
         let args: String = format!(
@@ -1848,18 +3092,37 @@ impl DaemonHelper {
             }
         };
 
-        let addr: String = stake_addr.as_array().unwrap()[0]
-            .as_str()
-            .unwrap()
+        let addr: String = stake_addr
+            .as_array()
+            .and_then(|addrs| addrs.first())
+            .and_then(Value::as_str)
+            .ok_or("Malformed deriverangekeys response")?
             .to_string();
 
         Ok(addr)
     }
 
+    // The stake address at index of the ext pub key, for requests that need a known key.
+    pub async fn derive_stake_addr(
+        &self,
+        index: u32,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let conf = self.config.read().await;
+        let ext_pub_key: String = conf.ext_pub_key.clone().ok_or("No ext pub key is set")?;
+        drop(conf);
+
+        self.derive_range_keys(&ext_pub_key, index, index)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| "Malformed deriverangekeys response".into())
+    }
+
     pub async fn start_daemon(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let conf = self.config.read().await;
         let daemon_path = conf.daemon_path.clone();
         let daemon_hash_opt = conf.daemon_hash.clone();
+        let system_daemon = conf.system_daemon;
 
         let daemon_data_dir = conf.daemon_data_dir.clone();
         let daemon_conf_path = daemon_data_dir.join(DAEMON_SETTINGS_FILE);
@@ -1871,12 +3134,14 @@ impl DaemonHelper {
             self.download_daemon().await?;
         }
 
-        let valid_hash = if daemon_hash_opt.is_none() {
-            false
-        } else {
-            let expected_daemon_hash = daemon_hash_opt.unwrap();
+        // System installs are owned by the package manager, so there is no hash to verify
+        let valid_hash = if system_daemon {
+            true
+        } else if let Some(expected_daemon_hash) = daemon_hash_opt {
             let actual_daemon_hash = sha256_digest(&daemon_path)?;
             expected_daemon_hash == actual_daemon_hash
+        } else {
+            false
         };
 
         if !valid_hash {
@@ -1885,13 +3150,12 @@ impl DaemonHelper {
         }
 
         let _command: std::process::Child = Command::new(&daemon_path)
-            .arg(format!("-datadir={}", daemon_data_dir.to_str().unwrap()))
-            .arg(format!("-conf={}", daemon_conf_path.to_str().unwrap()))
+            .arg(format!("-datadir={}", daemon_data_dir.display()))
+            .arg(format!("-conf={}", daemon_conf_path.display()))
             .arg("-daemon")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
-            .spawn()
-            .expect("Ghost daemon failed to start");
+            .spawn()?;
         tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
         Ok(())
     }
@@ -1900,28 +3164,29 @@ impl DaemonHelper {
         let mut conf = self.config.write().await;
         let gv_home = conf.gv_home.clone();
 
-        file_ops::rm_dir(&gv_home.join("daemon/")).unwrap();
-        file_ops::rm_dir(&PathBuf::from(TMP_PATH)).unwrap();
         let dl_path: PathBuf = loop {
-            let dl_path_res = gv_methods::download_daemon().await;
-            let dl_path: PathBuf = if let Err(ref err) = dl_path_res {
-                error!("Error downloading daemon: {}", err);
-                error!("Retrying in 30 seconds...");
-                tokio::time::sleep(Duration::from_secs(30)).await;
-                continue;
-            } else {
-                dl_path_res.unwrap()
-            };
-
-            break dl_path;
+            match gv_methods::download_daemon(&conf.daemon_source()).await {
+                Ok(dl_path) => break dl_path,
+                Err(err) => {
+                    error!("Error downloading daemon: {}", err);
+                    error!("Retrying in 30 seconds...");
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                }
+            }
         };
 
         let path_and_hash: PathAndDigest = gv_methods::extract_archive(&dl_path, &gv_home)?;
 
-        conf.update_gv_config("daemon_path", path_and_hash.daemon_path.to_str().unwrap())?;
+        if let Err(err) = gv_methods::clean_download_cache(Duration::ZERO) {
+            warn!("Unable to clean download cache: {}", err);
+        }
+
+        conf.update_gv_config("daemon_path", &path_and_hash.daemon_path.to_string_lossy())?;
 
         conf.update_gv_config("daemon_hash", path_and_hash.daemon_hash.as_str())?;
 
+        conf.update_gv_config("system_daemon", "false")?;
+
         drop(conf);
 
         Ok(())
@@ -1985,50 +3250,349 @@ impl DaemonHelper {
     }
 }
 
+// A transaction comment or wallet passphrase as a single argument. rpc::call splits its arguments
+// on spaces so they're escaped inside the JSON string, which also keeps words like true or 5 from
+// being read as JSON values.
+fn comment_arg(comment: &str) -> String {
+    serde_json::to_string(comment)
+        .unwrap_or_else(|_| r#""""#.to_string())
+        .replace(' ', "\\u0020")
+}
+
+// A sealed mnemonic belongs to the old wallet, the new one is stored in the clear until it's sealed again.
+fn clear_sealed_mnemonic(conf: &mut GVConfig) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if conf.sealed_mnemonic.is_some() {
+        conf.update_gv_config("SEALED_MNEMONIC", "")?;
+        warn!("The sealed mnemonic was replaced by the new wallet's, seal it again with gv-cli sealmnemonic");
+    }
+
+    Ok(())
+}
+
+// The ghostd endpoints from the config, read on every subscribe so a reload with new ZMQ
+// settings is picked up.
+async fn zmq_listen_addr(gv_config: &Arc<async_RwLock<GVConfig>>) -> Vec<String> {
+    let conf = gv_config.read().await;
+    let mut host_vec: Vec<String> = vec![conf.zmq_block_host.clone()];
+
+    if conf.zmq_tx_host != conf.zmq_block_host {
+        host_vec.push(conf.zmq_tx_host.clone());
+    }
+
+    host_vec
+}
+
+// The ZMQ topics GhostVault needs that ghostd isn't publishing, None if ghostd couldn't be asked.
+async fn missing_zmq_topics(daemon: &DaemonHelper) -> Option<Vec<&'static str>> {
+    let notifications: Value = daemon.get_zmq_notifications().await.ok()?;
+    let active: Vec<&str> = notifications
+        .as_array()?
+        .iter()
+        .filter_map(|notifier| notifier.get("type").and_then(|kind| kind.as_str()))
+        .collect();
+
+    Some(
+        ["pubhashblock", "pubhashwtx"]
+            .into_iter()
+            .filter(|topic| !active.contains(topic))
+            .collect(),
+    )
+}
+
+// Stands in for ZMQ while ghostd runs without it: the best block goes to new_block and each
+// wallet's listsinceblock to new_wallet_tx, the same way the notifications would. Returns once
+// ghostd publishes both topics again.
+async fn poll_daemon(
+    gv_config: &Arc<async_RwLock<GVConfig>>,
+    cli_client: &CLICaller,
+    db: &Arc<GVDB>,
+    last_block: &mut Option<String>,
+) {
+    // Per wallet, the block listsinceblock last reached.
+    let mut since: HashMap<String, String> = HashMap::new();
+    // Unconfirmed txids already sent, they're sent once more when they confirm like with ZMQ.
+    let mut pending: HashSet<String> = HashSet::new();
+
+    loop {
+        let daemon: DaemonHelper = DaemonHelper::new(gv_config, "cold").await;
+
+        if missing_zmq_topics(&daemon)
+            .await
+            .is_some_and(|missing| missing.is_empty())
+        {
+            info!("ghostd is publishing ZMQ notifications again, stopped polling.");
+            return;
+        }
+
+        let best_block: String = match daemon.get_best_block_hash().await {
+            Ok(hash) => hash.as_str().unwrap_or_default().to_string(),
+            Err(_) => {
+                tokio::time::sleep(Duration::from_secs(ZMQ_POLL_INTERVAL)).await;
+                continue;
+            }
+        };
+
+        if last_block.as_ref() != Some(&best_block) {
+            if let Err(err) = cli_client.call_new_block(best_block.clone()).await {
+                error!("Error sending polled block {}: {}", best_block, err);
+            }
+            *last_block = Some(best_block.clone());
+        }
+
+        let primary_wallet: String = gv_config.read().await.rpc_wallet.clone();
+        let wallets: Vec<String> = std::iter::once(primary_wallet)
+            .chain(db.get_cold_wallets().into_iter().map(|wallet| wallet.name))
+            .collect();
+
+        for wallet in wallets {
+            let wallet_since: String = since
+                .entry(wallet.clone())
+                .or_insert_with(|| best_block.clone())
+                .clone();
+
+            let listed: Value = match DaemonHelper::for_wallet(gv_config, &wallet)
+                .await
+                .list_since_block(&wallet_since)
+                .await
+            {
+                Ok(listed) => listed,
+                Err(_) => continue,
+            };
+
+            let mut sent: HashSet<String> = HashSet::new();
+
+            for tx in listed
+                .get("transactions")
+                .and_then(|txs| txs.as_array())
+                .into_iter()
+                .flatten()
+            {
+                let txid: String = match tx.get("txid").and_then(|txid| txid.as_str()) {
+                    Some(txid) => txid.to_string(),
+                    None => continue,
+                };
+                let confirmed: bool = tx
+                    .get("confirmations")
+                    .and_then(|confirmations| confirmations.as_i64())
+                    .is_some_and(|confirmations| confirmations > 0);
+
+                if !sent.insert(txid.clone()) {
+                    continue;
+                }
+
+                if confirmed {
+                    pending.remove(&txid);
+                } else if !pending.insert(txid.clone()) {
+                    continue;
+                }
+
+                let txid_and_wal: TxidAndWallet = TxidAndWallet {
+                    txid,
+                    wallet: wallet.clone(),
+                };
+
+                if let Err(err) = cli_client.call_new_wallet_tx(txid_and_wal).await {
+                    error!("Error sending polled wallet tx: {}", err);
+                }
+            }
+
+            if let Some(last) = listed.get("lastblock").and_then(|last| last.as_str()) {
+                since.insert(wallet, last.to_string());
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(ZMQ_POLL_INTERVAL)).await;
+    }
+}
+
+// Check a notification's sequence number against the last one on its topic. Each ghostd
+// notifier counts up from 0, so a skip means notifications were dropped and going backwards
+// means ghostd restarted, either way the wallet needs a catch up.
+fn zmq_sequence_gap(last: &mut Option<u32>, seq: u32, topic: &str) -> bool {
+    let gap: bool = match *last {
+        Some(last_seq) if seq == last_seq.wrapping_add(1) => false,
+        Some(last_seq) if seq > last_seq => {
+            warn!(
+                "Missed {} ZMQ {} notifications, catching up...",
+                seq - last_seq - 1,
+                topic
+            );
+            true
+        }
+        Some(_) => {
+            warn!("ZMQ {} sequence restarted, ghostd was restarted.", topic);
+            true
+        }
+        None => false,
+    };
+
+    *last = Some(seq);
+
+    gap
+}
+
+// Follows ghostd's block and wallet notifications. The socket is resubscribed if it closes or
+// the ZMQ settings change, and when it goes quiet for ZMQ_SILENCE_TIMEOUT while ghostd has moved
+// on, new blocks are polled over RPC until notifications come back. A ghostd started without
+// zmqpubhashblock or zmqpubhashwtx is polled by poll_daemon instead.
 pub async fn listen_zmq(
-    listen_addr: &[String],
+    gv_config: Arc<async_RwLock<GVConfig>>,
     cli_address: &str,
     db: Arc<GVDB>,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
     info!("Starting ZMQ listener...");
-    let listen_addr_str: Vec<&str> = listen_addr.iter().map(|s| s.as_str()).collect();
-    let mut stream: ghostcore_zmq::MessageStream = subscribe_async(&listen_addr_str).unwrap();
 
-    while !db.get_server_ready().unwrap().ready {
+    while !db.get_server_ready().is_some_and(|ready| ready.ready) {
         tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
     }
 
     let cli_client: CLICaller = CLICaller::new(cli_address, true).await?;
 
-    while let Some(msg) = stream.next().await {
-        match &msg {
-            Ok(Message::HashWTx(_, _, _)) => {
-                let txid_and_wal: TxidAndWallet = match get_tx_hash_and_wallet(&msg) {
-                    Ok(txid_wallet) => txid_wallet,
-                    Err(_) => {
-                        continue;
-                    }
-                };
-                cli_client.call_new_wallet_tx(txid_and_wal).await.unwrap();
+    let mut last_block: Option<String> = None;
+    let mut polling: bool = false;
+    let mut resubscribed: bool = false;
+
+    loop {
+        let daemon: DaemonHelper = DaemonHelper::new(&gv_config, "cold").await;
+
+        if let Some(missing) = missing_zmq_topics(&daemon).await {
+            if !missing.is_empty() {
+                warn!(
+                    "ghostd isn't publishing {} over ZMQ, polling it over RPC instead. Stakes and zaps will be seen up to {} seconds late, add zmqpubhashblock and zmqpubhashwtx to ghost.conf to fix this.",
+                    missing.join(", "),
+                    ZMQ_POLL_INTERVAL
+                );
+                poll_daemon(&gv_config, &cli_client, &db, &mut last_block).await;
+                resubscribed = true;
+                continue;
             }
-            Ok(Message::HashBlock(_, _)) => {
-                let block_hash: String = match gethash(&msg) {
-                    Ok(hash) => hash,
-                    Err(_) => continue,
-                };
+        }
 
-                cli_client.call_new_block(block_hash).await.unwrap();
+        let listen_addr: Vec<String> = zmq_listen_addr(&gv_config).await;
+        let listen_addr_str: Vec<&str> = listen_addr.iter().map(|s| s.as_str()).collect();
+
+        let mut stream: ghostcore_zmq::MessageStream = match subscribe_async(&listen_addr_str) {
+            Ok(stream) => stream,
+            Err(err) => {
+                error!(
+                    "Unable to subscribe to ZMQ on {}: {}",
+                    listen_addr.join(", "),
+                    err
+                );
+                tokio::time::sleep(Duration::from_secs(ZMQ_RESUBSCRIBE_DELAY)).await;
+                continue;
             }
-            Ok(_) => {
-                error!("Got unexpected value from ZMQ.");
+        };
+        info!("Subscribed to ZMQ on {}", listen_addr.join(", "));
+
+        // A new socket starts counting again, and whatever happened while the old one was
+        // down is picked up from the wallet.
+        let mut block_seq: Option<u32> = None;
+        let mut wtx_seq: Option<u32> = None;
+        let mut catch_up: bool = resubscribed;
+        let mut last_message: Instant = Instant::now();
+
+        loop {
+            if catch_up {
+                DaemonHelper::new(&gv_config, "cold")
+                    .await
+                    .cleanup_missing_tx(&db)
+                    .await;
+                catch_up = false;
+            }
+
+            let next =
+                tokio::time::timeout(Duration::from_secs(ZMQ_WATCHDOG_INTERVAL), stream.next())
+                    .await;
+
+            let msg = match next {
+                Ok(Some(msg)) => msg,
+                Ok(None) => {
+                    warn!("ZMQ stream closed, resubscribing...");
+                    break;
+                }
+                Err(_) => {
+                    if zmq_listen_addr(&gv_config).await != listen_addr {
+                        info!("ZMQ settings changed, resubscribing...");
+                        break;
+                    }
+
+                    if !polling && last_message.elapsed().as_secs() < ZMQ_SILENCE_TIMEOUT {
+                        continue;
+                    }
+
+                    let daemon: DaemonHelper = DaemonHelper::new(&gv_config, "cold").await;
+                    let best_block: String = match daemon.get_best_block_hash().await {
+                        Ok(hash) => hash.as_str().unwrap_or_default().to_string(),
+                        Err(_) => continue,
+                    };
+
+                    if last_block.as_ref() == Some(&best_block) {
+                        continue;
+                    }
+
+                    if !polling {
+                        warn!("No ZMQ notifications while ghostd has new blocks, polling RPC and resubscribing...");
+                        polling = true;
+                    }
+
+                    if let Err(err) = cli_client.call_new_block(best_block.clone()).await {
+                        error!("Error sending polled block {}: {}", best_block, err);
+                    }
+                    last_block = Some(best_block);
+
+                    break;
+                }
+            };
+
+            last_message = Instant::now();
+
+            if polling {
+                info!("ZMQ notifications are back, stopped polling ghostd.");
+                polling = false;
             }
-            Err(e) => {
-                error!("zmq error: {}", e);
+
+            match &msg {
+                Ok(Message::HashWTx(_, _, seq)) => {
+                    catch_up |= zmq_sequence_gap(&mut wtx_seq, *seq, "hashwtx");
+
+                    let txid_and_wal: TxidAndWallet = match get_tx_hash_and_wallet(&msg) {
+                        Ok(txid_wallet) => txid_wallet,
+                        Err(_) => {
+                            continue;
+                        }
+                    };
+
+                    if let Err(err) = cli_client.call_new_wallet_tx(txid_and_wal).await {
+                        error!("Error sending wallet tx: {}", err);
+                    }
+                }
+                Ok(Message::HashBlock(_, seq)) => {
+                    catch_up |= zmq_sequence_gap(&mut block_seq, *seq, "hashblock");
+
+                    let block_hash: String = match gethash(&msg) {
+                        Ok(hash) => hash,
+                        Err(_) => continue,
+                    };
+
+                    last_block = Some(block_hash.clone());
+
+                    if let Err(err) = cli_client.call_new_block(block_hash).await {
+                        error!("Error sending new block: {}", err);
+                    }
+                }
+                Ok(_) => {
+                    error!("Got unexpected value from ZMQ.");
+                }
+                Err(e) => {
+                    error!("zmq error: {}", e);
+                }
             }
         }
-    }
 
-    Ok(())
+        resubscribed = true;
+        tokio::time::sleep(Duration::from_secs(ZMQ_RESUBSCRIBE_DELAY)).await;
+    }
 }
 
 fn get_tx_hash_and_wallet<E: Error + Sized>(
@@ -2066,7 +3630,13 @@ async fn handle_event(payload: Payload, _: sio_Client, gv_config: Arc<async_RwLo
     let cli_addr = conf.cli_address.clone();
     drop(conf);
 
-    let cli_caller = CLICaller::new(&cli_addr, true).await.unwrap();
+    let cli_caller: CLICaller = match CLICaller::new(&cli_addr, true).await {
+        Ok(cli_caller) => cli_caller,
+        Err(err) => {
+            error!("Unable to reach the RPC server for a remote block: {}", err);
+            return;
+        }
+    };
 
     match payload {
         Payload::Text(text) => {
@@ -2076,20 +3646,15 @@ async fn handle_event(payload: Payload, _: sio_Client, gv_config: Arc<async_RwLo
             };
 
             if data.is_object() {
-                let block_hash_opt = data.get("bestblockhash");
-
-                if block_hash_opt.is_none() {
-                    return;
-                }
-
-                let block_hash: String = block_hash_opt.unwrap().as_str().unwrap().to_string();
-                let block_height_opt: Option<&Value> = data.get("blocks");
-
-                if block_height_opt.is_none() {
-                    return;
-                }
+                let block_hash: String = match data.get("bestblockhash").and_then(Value::as_str) {
+                    Some(block_hash) => block_hash.to_string(),
+                    None => return,
+                };
 
-                let block_height = block_height_opt.unwrap().as_u64().unwrap() as u32;
+                let block_height: u32 = match data.get("blocks").and_then(Value::as_u64) {
+                    Some(block_height) => block_height as u32,
+                    None => return,
+                };
 
                 let _ = cli_caller
                     .call_new_remote_block(block_hash, block_height)
@@ -2101,14 +3666,14 @@ async fn handle_event(payload: Payload, _: sio_Client, gv_config: Arc<async_RwLo
 }
 
 async fn connect_to_servers(
-    urls: &VecDeque<&str>,
+    urls: &[String],
     gv_config: Arc<async_RwLock<GVConfig>>,
     is_error: Arc<async_Mutex<bool>>,
 ) -> Option<sio_Client> {
     for url in urls {
         let is_error_clone = Arc::clone(&is_error);
         let gv_config_clone = Arc::clone(&gv_config);
-        match ClientBuilder::new(url.to_string())
+        match ClientBuilder::new(url.as_str())
             .on("room_message", move |payload, socket| {
                 let gv_config_clone = Arc::clone(&gv_config_clone);
                 async move {
@@ -2145,27 +3710,18 @@ pub async fn listen_for_events(
     gv_config: Arc<async_RwLock<GVConfig>>,
     db: Arc<GVDB>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let mut urls: Vec<&str> = vec![
-        "https://api.tuxprint.com",
-        "https://api2.tuxprint.com",
-        "https://socket.tuxprint.com",
-        "https://socket2.tuxprint.com",
-    ];
-    urls.shuffle(&mut rand::thread_rng());
-    let mut url_vec = VecDeque::from_iter(urls);
-
     let is_error: Arc<async_Mutex<bool>> = Arc::new(async_Mutex::new(false));
     loop {
-        // Move the first element to the back
-        let first_el = url_vec.pop_front().unwrap();
-        url_vec.push_back(first_el);
-
         let conf_clone: Arc<async_RwLock<GVConfig>> = Arc::clone(&gv_config);
         let conf = conf_clone.read().await;
         let cli_addr: String = conf.cli_address.clone();
+        // Built on every reconnect so changes to the explorer list are picked up
+        let explorer: ExplorerProvider = ExplorerProvider::new(&conf);
         drop(conf);
 
-        while !db.get_server_ready().unwrap().ready {
+        let urls: Vec<String> = explorer.apis();
+
+        while !db.get_server_ready().is_some_and(|ready| ready.ready) {
             info!("SIO Waiting for RPC server to be ready...");
             tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
         }
@@ -2176,7 +3732,7 @@ pub async fn listen_for_events(
         let is_error_clone: Arc<async_Mutex<bool>> = Arc::clone(&is_error);
         let gv_config_clone: Arc<async_RwLock<GVConfig>> = Arc::clone(&gv_config);
 
-        let socket = match connect_to_servers(&url_vec, gv_config_clone, is_error_clone).await {
+        let socket = match connect_to_servers(&urls, gv_config_clone, is_error_clone).await {
             Some(client) => client,
             None => {
                 warn!("All servers are unreachable. Retrying...");
@@ -2192,23 +3748,25 @@ pub async fn listen_for_events(
             .emit("join", json!({"room": "block", "username": id.to_string()}))
             .await?;
 
-        let remote_bc_info_res = get_remote_block_chain_info().await;
-
-        if remote_bc_info_res.is_err() {
-            warn!("Failed to get remote blockchain info. Retrying...");
-            tokio::time::sleep(Duration::from_secs(5)).await;
-            continue;
-        }
-
-        let remote_bc_info = remote_bc_info_res.unwrap();
+        let remote_tip: Option<(String, u32)> =
+            explorer
+                .get_blockchain_info()
+                .await
+                .ok()
+                .and_then(|remote_bc_info| {
+                    let block_hash = remote_bc_info.get("bestblockhash")?.as_str()?.to_string();
+                    let block_height = remote_bc_info.get("blocks")?.as_u64()? as u32;
+                    Some((block_hash, block_height))
+                });
 
-        let block_hash = remote_bc_info
-            .get("bestblockhash")
-            .unwrap()
-            .as_str()
-            .unwrap()
-            .to_string();
-        let block_height = remote_bc_info.get("blocks").unwrap().as_u64().unwrap() as u32;
+        let (block_hash, block_height) = match remote_tip {
+            Some(remote_tip) => remote_tip,
+            None => {
+                warn!("Failed to get remote blockchain info. Retrying...");
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
 
         let _ = cli_caller
             .call_new_remote_block(block_hash, block_height)
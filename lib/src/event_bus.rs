@@ -0,0 +1,182 @@
+// Pushes GhostVault's internal events to external UIs over a WebSocket, so they don't have to
+// poll the RPCs. Every message is {"id": ..., "event": "...", "timestamp": ..., "data": {...}}.
+// Clients pick what they get with ?events=stake,zap on connect or by sending
+// {"subscribe": ["stake", "zap"]} later on, an empty list means every event.
+// Events are also kept in GVDB's event log, a client that was away catches up with
+// get_events_since using the last id it saw. Events with a hook also run the user's scripts.
+use crate::{config::HookSettings, gvdb::GVDB, hooks};
+use futures_util::{SinkExt, StreamExt};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{error::Error, sync::Arc};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::broadcast::{self, error::RecvError},
+};
+use tokio_tungstenite::tungstenite::{
+    handshake::server::{Callback, ErrorResponse, Request, Response},
+    Message,
+};
+
+pub const EVENT_TYPES: &[&str] = &[
+    "stake", "zap", "payout", "offline", "online", "sync", "update",
+];
+
+// Events held for a slow client before it starts missing them.
+const EVENT_BUFFER: usize = 256;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GvEvent {
+    pub id: u64,
+    pub event: String,
+    pub timestamp: u64,
+    pub data: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct Subscribe {
+    subscribe: Vec<String>,
+}
+
+// Reads ?events= off the handshake request. tungstenite's rejection type is a whole HTTP
+// response, as a Callback impl it stays tungstenite's signature rather than one of ours.
+struct QueryFilter<'a>(&'a mut Vec<String>);
+
+impl Callback for QueryFilter<'_> {
+    fn on_request(self, request: &Request, response: Response) -> Result<Response, ErrorResponse> {
+        *self.0 = parse_query_filter(request.uri().query().unwrap_or(""));
+        Ok(response)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EventBus {
+    sender: broadcast::Sender<GvEvent>,
+    db: Arc<GVDB>,
+    hooks: Arc<HookSettings>,
+}
+
+impl EventBus {
+    pub fn new(db: &Arc<GVDB>, hooks: HookSettings) -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUFFER);
+        EventBus {
+            sender,
+            db: Arc::clone(db),
+            hooks: Arc::new(hooks),
+        }
+    }
+
+    // The event is logged before it goes out, so anything a client is told about can be
+    // fetched again. Nobody listening is the normal case, so a failed send is ignored.
+    pub async fn publish(&self, event: &str, data: impl Serialize) {
+        let gv_event: GvEvent = GvEvent {
+            id: self.db.next_event_id(),
+            event: event.to_string(),
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            data: serde_json::to_value(data).unwrap_or(Value::Null),
+        };
+
+        if let Err(err) = self.db.set_event(&gv_event).await {
+            warn!("Unable to log {} event: {}", event, err);
+        }
+
+        if let Some(hook) = hooks::hook_for_event(event) {
+            hooks::run_hooks(&self.hooks, hook, gv_event.timestamp, &gv_event.data);
+        }
+
+        let _ = self.sender.send(gv_event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<GvEvent> {
+        self.sender.subscribe()
+    }
+}
+
+pub async fn serve_events(
+    bus: EventBus,
+    address: String,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let listener: TcpListener = TcpListener::bind(&address).await?;
+    info!("Event stream listening on ws://{}", address);
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let receiver: broadcast::Receiver<GvEvent> = bus.subscribe();
+
+        tokio::spawn(async move {
+            if let Err(err) = handle_client(stream, receiver).await {
+                warn!("Event stream client {} dropped: {}", peer, err);
+            }
+        });
+    }
+}
+
+async fn handle_client(
+    stream: TcpStream,
+    mut receiver: broadcast::Receiver<GvEvent>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut filter: Vec<String> = Vec::new();
+
+    let ws_stream = tokio_tungstenite::accept_hdr_async(stream, QueryFilter(&mut filter)).await?;
+
+    let (mut ws_sender, mut ws_receiver) = ws_stream.split();
+
+    loop {
+        tokio::select! {
+            event = receiver.recv() => {
+                let event: GvEvent = match event {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!("Event stream client fell behind, {} events skipped", missed);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return Ok(()),
+                };
+
+                if !filter.is_empty() && !filter.contains(&event.event) {
+                    continue;
+                }
+
+                ws_sender
+                    .send(Message::Text(serde_json::to_string(&event)?))
+                    .await?;
+            }
+            message = ws_receiver.next() => {
+                match message {
+                    Some(Ok(Message::Text(text))) => {
+                        match serde_json::from_str::<Subscribe>(&text) {
+                            Ok(subscribe) => filter = valid_events(subscribe.subscribe),
+                            Err(_) => warn!("Ignoring event stream message: {}", text),
+                        }
+                    }
+                    Some(Ok(Message::Ping(payload))) => {
+                        ws_sender.send(Message::Pong(payload)).await?;
+                    }
+                    Some(Ok(Message::Close(_))) | None => return Ok(()),
+                    Some(Ok(_)) => {}
+                    Some(Err(err)) => return Err(err.into()),
+                }
+            }
+        }
+    }
+}
+
+fn parse_query_filter(query: &str) -> Vec<String> {
+    let events: Vec<String> = query
+        .split('&')
+        .filter_map(|pair| pair.strip_prefix("events="))
+        .flat_map(|events| events.split(','))
+        .map(|event| event.to_string())
+        .collect();
+
+    valid_events(events)
+}
+
+fn valid_events(events: Vec<String>) -> Vec<String> {
+    events
+        .into_iter()
+        .map(|event| event.trim().to_lowercase())
+        .filter(|event| EVENT_TYPES.contains(&event.as_str()))
+        .collect()
+}
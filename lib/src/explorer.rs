@@ -0,0 +1,175 @@
+// Remote block explorer APIs, used to check the local chain against the rest of the network.
+// The configured APIs are tried in order. One that fails is passed over until
+// EXPLORER_RETRY_AFTER has gone by, unless every API is failing, then they are all tried again.
+use crate::{
+    config::GVConfig,
+    constants::{EXPLORER_REQUEST_TIMEOUT, EXPLORER_RETRY_AFTER},
+};
+use log::warn;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    error::Error,
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExplorerHealth {
+    pub url: String,
+    pub healthy: bool,
+    pub last_checked: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+// Health of every API this process has talked to, shared by all providers.
+static EXPLORER_HEALTH: OnceLock<Mutex<HashMap<String, ExplorerHealth>>> = OnceLock::new();
+
+fn explorer_health() -> &'static Mutex<HashMap<String, ExplorerHealth>> {
+    EXPLORER_HEALTH.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+#[derive(Clone, Debug)]
+pub struct ExplorerProvider {
+    apis: Vec<String>,
+    tx_url: String,
+    block_url: String,
+}
+
+impl ExplorerProvider {
+    pub fn new(conf: &GVConfig) -> ExplorerProvider {
+        ExplorerProvider {
+            apis: conf
+                .explorer_apis
+                .iter()
+                .map(|api| api.trim_end_matches('/').to_string())
+                .collect(),
+            tx_url: conf.explorer_tx_url.clone(),
+            block_url: conf.explorer_block_url.clone(),
+        }
+    }
+
+    // The APIs in the order they should be tried, ones that failed recently go last.
+    pub fn apis(&self) -> Vec<String> {
+        let now: u64 = chrono::Utc::now().timestamp() as u64;
+        let health = explorer_health().lock().unwrap();
+
+        let (mut healthy, failing): (Vec<String>, Vec<String>) = self
+            .apis
+            .iter()
+            .cloned()
+            .partition(|api| match health.get(api) {
+                Some(status) if !status.healthy => status
+                    .last_checked
+                    .is_none_or(|checked| now >= checked + EXPLORER_RETRY_AFTER),
+                _ => true,
+            });
+
+        healthy.extend(failing);
+        healthy
+    }
+
+    pub fn tx_url(&self, txid: &str) -> String {
+        self.tx_url.replace("{txid}", txid)
+    }
+
+    pub fn block_url(&self, block_hash: &str) -> String {
+        self.block_url.replace("{hash}", block_hash)
+    }
+
+    pub async fn get_blockchain_info(&self) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        self.get("getblockchaininfo/").await
+    }
+
+    pub async fn get_block_count(&self) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        self.get("getblockcount/").await
+    }
+
+    pub async fn get_block_hash(
+        &self,
+        height: u32,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let block: Value = self.get(&format!("api/block-index/{}/", height)).await?;
+
+        let block_hash: String = block
+            .get("blockHash")
+            .and_then(|hash| hash.as_str())
+            .ok_or("Malformed block-index response")?
+            .to_string();
+
+        Ok(block_hash)
+    }
+
+    // Asks every API for the block count and records which ones answered.
+    pub async fn check_health(&self) -> Result<Vec<ExplorerHealth>, Box<dyn Error + Send + Sync>> {
+        let client: Client = explorer_client()?;
+        let mut statuses: Vec<ExplorerHealth> = Vec::new();
+
+        for api in &self.apis {
+            let res = request(&client, &format!("{}/getblockcount/", api)).await;
+            statuses.push(record_health(
+                api,
+                res.as_ref().err().map(|err| err.as_ref()),
+            ));
+        }
+
+        Ok(statuses)
+    }
+
+    async fn get(&self, path: &str) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let client: Client = explorer_client()?;
+        let mut last_err: Box<dyn Error + Send + Sync> = "No explorer APIs configured".into();
+
+        for api in self.apis() {
+            match request(&client, &format!("{}/{}", api, path)).await {
+                Ok(value) => {
+                    record_health(&api, None);
+                    return Ok(value);
+                }
+                Err(err) => {
+                    warn!("Explorer API {} failed: {}", api, err);
+                    record_health(&api, Some(err.as_ref()));
+                    last_err = err;
+                }
+            }
+        }
+
+        Err(last_err)
+    }
+}
+
+fn explorer_client() -> Result<Client, reqwest::Error> {
+    Client::builder()
+        .timeout(Duration::from_secs(EXPLORER_REQUEST_TIMEOUT))
+        .build()
+}
+
+async fn request(client: &Client, url: &str) -> Result<Value, Box<dyn Error + Send + Sync>> {
+    let value: Value = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(value)
+}
+
+fn record_health(api: &str, err: Option<&(dyn Error + Send + Sync)>) -> ExplorerHealth {
+    let status: ExplorerHealth = ExplorerHealth {
+        url: api.to_string(),
+        healthy: err.is_none(),
+        last_checked: Some(chrono::Utc::now().timestamp() as u64),
+        last_error: err.map(|err| err.to_string()),
+    };
+
+    explorer_health()
+        .lock()
+        .unwrap()
+        .insert(api.to_string(), status.clone());
+
+    status
+}
@@ -5,8 +5,8 @@ use std::fs;
 use std::{
     env::temp_dir,
     fs::File,
-    io::{prelude::*, BufReader, Write},
-    path::PathBuf,
+    io::{prelude::*, BufReader, SeekFrom, Write},
+    path::{Path, PathBuf},
     process::{Command, Stdio},
 };
 
@@ -298,3 +298,24 @@ pub fn pid_exists(pid: u32) -> bool {
     }
     PathBuf::from(&format!("/proc/{pid}")).exists()
 }
+
+// Only the last max_bytes of the file are read, logs can grow to several MB before they roll over.
+pub fn tail_file(path: &Path, max_bytes: u64) -> std::io::Result<Vec<String>> {
+    let mut file: File = File::open(path)?;
+    let len: u64 = file.metadata()?.len();
+    let start: u64 = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start))?;
+
+    let mut buf: Vec<u8> = Vec::new();
+    file.read_to_end(&mut buf)?;
+
+    let text: String = String::from_utf8_lossy(&buf).to_string();
+    let mut lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+
+    // The first line is most likely cut in half by the seek
+    if start > 0 && !lines.is_empty() {
+        lines.remove(0);
+    }
+
+    Ok(lines)
+}
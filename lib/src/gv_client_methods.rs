@@ -1,14 +1,33 @@
 use context::Context;
 use core::time;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 use tarpc::{client, context, tokio_serde::formats::Json};
 use tracing::Instrument;
 extern crate colored;
-use crate::{constants::VERSION, daemon_helper::TxidAndWallet, GvCLIClient};
+use crate::{
+    amount::Amount,
+    announce::AnnounceDestination,
+    api_schema::ApiMethod,
+    backup::{BackupInfo, MigrateInfo},
+    cli_tls,
+    config::ConfigReload,
+    constants::{API_VERSION, CAPABILITIES_API_VERSION, VERSION},
+    daemon_helper::{SyncProgress, TxidAndWallet},
+    event_bus::GvEvent,
+    explorer::ExplorerHealth,
+    file_ops, gv_methods,
+    gvdb::{
+        AddressInfo, AuditDB, BootstrapDB, DbMaintenanceDB, JobDB, PendingRewardChangeDB,
+        RestartDB, ServerReadyDB, TgBotQueueDB, WalletRescanDB,
+    },
+    response::{ErrorCode, GvError, GvReply},
+    GvCLIClient,
+};
 use colored::*;
-use log::error;
-use std::{process::Command as Cmd, time::SystemTime};
+use futures::future::join_all;
+use log::{error, warn};
+use std::{path::Path, process::Command as Cmd, time::SystemTime};
 
 fn clear_screen() {
     let _command: Result<std::process::ExitStatus, std::io::Error> =
@@ -24,10 +43,16 @@ pub struct GVStatus {
     pub daemon_uptime: String,
     pub daemon_peers: u16,
     pub daemon_synced: String,
+    // Only while ghostd is catching up.
+    #[serde(default)]
+    pub sync_progress: Option<SyncProgress>,
     pub best_block: u32,
     pub best_block_hash: String,
     pub best_block_extern: u32,
     pub good_chain: String,
+    // How good_chain was decided, EXPLORER or LOCAL.
+    #[serde(default)]
+    pub chain_check: String,
     pub staking_enabled: String,
     pub active_staking: String,
     pub staking_difficulty: f64,
@@ -35,10 +60,25 @@ pub struct GVStatus {
     pub currently_staking: f64,
     pub total_coldstaking: f64,
     pub last_stake: String,
+    #[serde(default)]
+    pub expected_stake_time: String,
     pub stakes_24: u32,
     pub rewards_24: f64,
     pub agvr_24: f64,
     pub total_24: f64,
+    // Exact amounts, the float fields above are kept for older clients.
+    #[serde(default)]
+    pub network_stake_weight_amount: Amount,
+    #[serde(default)]
+    pub currently_staking_amount: Amount,
+    #[serde(default)]
+    pub total_coldstaking_amount: Amount,
+    #[serde(default)]
+    pub rewards_24_amount: Amount,
+    #[serde(default)]
+    pub agvr_24_amount: Amount,
+    #[serde(default)]
+    pub total_24_amount: Amount,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -51,6 +91,41 @@ pub struct PendingRewards {
     pub payout_run_interval: String,
     pub next_payout_run: String,
     pub min_payout: f64,
+    // Exact amounts, the float fields above are kept for older clients.
+    #[serde(default)]
+    pub total_pending_amount: Amount,
+    #[serde(default)]
+    pub staked_amount: Amount,
+    #[serde(default)]
+    pub pending_anonymization_amount: Amount,
+    #[serde(default)]
+    pub pending_anon_confs_amount: Amount,
+    #[serde(default)]
+    pub pending_payout_amount: Amount,
+    #[serde(default)]
+    pub min_payout_amount: Amount,
+}
+
+// Where rewards are on their way from public to spendable anon. Converting is anon that hasn't
+// confirmed yet, in_flight the conversions that haven't matured, oldest first.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnonymizationStatus {
+    pub public_trusted: Amount,
+    pub public_pending: Amount,
+    pub converting: Amount,
+    pub anon_immature: Amount,
+    pub anon_trusted: Amount,
+    pub in_flight: Vec<AnonConversion>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnonConversion {
+    pub txids: Vec<String>,
+    pub amount: Amount,
+    pub started: u64,
+    pub confirmations: u64,
+    // Estimated from the block target spacing.
+    pub matures: u64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -59,6 +134,216 @@ pub struct BarChart {
     pub division: String,
     pub start: String,
     pub end: String,
+    // Points in the whole series, data holds the page starting at offset.
+    #[serde(default)]
+    pub total_points: u64,
+    #[serde(default)]
+    pub offset: u64,
+    #[serde(default)]
+    pub next_offset: Option<u64>,
+}
+
+// Stakes grouped by the address whose coins found them, largest total first.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StakesByAddress {
+    pub start: String,
+    pub end: String,
+    pub total_stakes: u32,
+    pub total: Amount,
+    pub addresses: Vec<AddressStakes>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AddressStakes {
+    pub address: String,
+    pub stakes: u32,
+    pub rewards: Amount,
+    pub agvr: Amount,
+    pub total: Amount,
+    // Percent of the total earned over the range.
+    pub share: f64,
+    pub first_stake: u64,
+    pub last_stake: u64,
+}
+
+impl StakesByAddress {
+    pub fn to_csv(&self) -> String {
+        let mut csv: String =
+            "address,stakes,rewards,agvr,total,share,first_stake,last_stake\n".to_string();
+
+        for entry in &self.addresses {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{:.4},{},{}\n",
+                entry.address,
+                entry.stakes,
+                entry.rewards,
+                entry.agvr,
+                entry.total,
+                entry.share,
+                entry.first_stake,
+                entry.last_stake
+            ));
+        }
+
+        csv
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Utxo {
+    pub txid: String,
+    pub vout: u32,
+    pub utxo_type: String,
+    pub address: String,
+    pub amount: Amount,
+    pub confirmations: u64,
+    pub spendable: bool,
+    pub frozen: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Consolidation {
+    pub wallet: String,
+    pub inputs: u32,
+    pub amount: Amount,
+    // GHOST per kB
+    pub fee_rate: f64,
+    pub txids: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Tenant {
+    pub name: String,
+    pub key: String,
+    pub addresses: usize,
+    pub payout_address: String,
+    pub registered: String,
+    pub owed: Amount,
+    pub earned: Amount,
+    pub paid: Amount,
+}
+
+// What a tenant earned and was paid over a range, owed is as of now.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TenantStatement {
+    pub name: String,
+    pub payout_address: String,
+    pub start: String,
+    pub end: String,
+    pub stakes: u32,
+    pub earned: Amount,
+    pub paid: Amount,
+    pub owed: Amount,
+    pub entries: Vec<TenantStatementEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TenantStatementEntry {
+    pub date: String,
+    pub timestamp: u64,
+    pub kind: String,
+    pub txid: String,
+    pub amount: Amount,
+}
+
+// Each point is [timestamp, currently_staking, total_coldstaking, anon_balance] in GHOST.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BalanceHistory {
+    pub data: Vec<Vec<f64>>,
+    pub start: String,
+    pub end: String,
+}
+
+// Weights in GHOST, each data row is [timestamp, network weight, our weight, difficulty]. Long
+// ranges are thinned out to NETWORK_STATS_MAX_POINTS rows.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NetworkStats {
+    pub data: Vec<Vec<f64>>,
+    pub start: String,
+    pub end: String,
+    pub samples: u64,
+    pub network_weight: f64,
+    pub our_weight: f64,
+    pub difficulty: f64,
+    pub stake_share: f64,
+    pub average_share: f64,
+    // Percent change from the first sample in the range to the last.
+    pub network_weight_change: Option<f64>,
+    pub difficulty_change: Option<f64>,
+}
+
+// The primary cold wallet pays out, the others only stake. Stakes and payouts recorded before
+// wallets were tracked count for the primary wallet.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ColdWallets {
+    pub primary: String,
+    pub wallets: Vec<ColdWalletInfo>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ColdWalletInfo {
+    pub name: String,
+    pub primary: bool,
+    pub loaded: bool,
+    pub added: Option<u64>,
+    pub stakes: u64,
+    pub rewards: Amount,
+    pub last_stake: Option<u64>,
+    pub payouts: u64,
+    pub paid: Amount,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ColdWalletCreated {
+    pub wallet: String,
+    pub mnemonic: String,
+}
+
+// Coins cold staked to the vault, grouped by the spend address that owns them, largest first.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Delegations {
+    pub total: Amount,
+    pub utxos: u64,
+    pub delegations: Vec<Delegation>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Delegation {
+    pub spend_address: String,
+    pub wallet: String,
+    pub utxos: u64,
+    pub amount: Amount,
+    // Percent of everything delegated to the vault.
+    pub share: f64,
+    pub stakes: u32,
+    pub last_stake: Option<u64>,
+}
+
+// One entry per calendar day in the vault's timezone, days without stakes included.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StakeHeatmap {
+    pub data: Vec<HeatmapDay>,
+    pub max_stakes: u64,
+    pub total_stakes: u64,
+    pub timezone: String,
+    pub start: String,
+    pub end: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HeatmapDay {
+    pub date: String,
+    pub timestamp: u64,
+    pub stakes: u64,
+}
+
+// Which part of a chart to return, a limit of 0 gets the default page size and max_points
+// merges neighbouring points before the series is paged.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ChartPage {
+    pub offset: u64,
+    pub limit: u64,
+    pub max_points: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -66,6 +351,13 @@ pub struct AllTimeEarnigns {
     pub data: Vec<Vec<f64>>,
     pub start: String,
     pub end: String,
+    // Points in the whole series, data holds the page starting at offset.
+    #[serde(default)]
+    pub total_points: u64,
+    #[serde(default)]
+    pub offset: u64,
+    #[serde(default)]
+    pub next_offset: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -74,6 +366,13 @@ pub struct StakeTotals {
     pub rewards: f64,
     pub agvr: f64,
     pub total: f64,
+    // Exact amounts, the float fields above are kept for older clients.
+    #[serde(default)]
+    pub rewards_amount: Amount,
+    #[serde(default)]
+    pub agvr_amount: Amount,
+    #[serde(default)]
+    pub total_amount: Amount,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -97,416 +396,3813 @@ pub struct StakingDataOverview {
     pub stakes_ytd: StakeTotals,
     pub stakes_1y: StakeTotals,
     pub stakes_all: StakeTotals,
+    pub hot_wallet: Option<HotWalletInfo>,
+    pub stake_streak: StakeStreak,
+    #[serde(default)]
+    pub stake_forecast: Option<StakeForecast>,
+    #[serde(default)]
+    pub roi: Option<StakingRoi>,
+}
+
+// Windows are None until the vault has a staked balance sample from before their end.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StakingRoi {
+    pub roi_30d: Option<RoiWindow>,
+    pub roi_90d: Option<RoiWindow>,
+    pub roi_1y: Option<RoiWindow>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RoiWindow {
+    // Days of balance history the figures are based on, less than the window for new vaults.
+    pub covered_days: f64,
+    pub average_staked: f64,
+    pub rewards: f64,
+    pub roi: f64,
+    pub apr: f64,
+    pub apy: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StakeStreak {
+    pub streak_days: u32,
+    pub drought: String,
+    pub drought_secs: u64,
+    pub expected_secs: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StakeForecast {
+    pub currently_staking: f64,
+    pub network_stake_weight: f64,
+    pub staking_difficulty: f64,
+    pub stake_share: f64,
+    pub expected_secs: Option<u64>,
+    pub expected_time: String,
+    pub stakes_per_day: f64,
+    // Actual against expected stakes over the last window_days.
+    pub window_days: f64,
+    pub expected_stakes: f64,
+    pub actual_stakes: u32,
+    pub performance: f64,
+    // Chance of finding actual_stakes or fewer by luck alone.
+    pub probability: f64,
+    pub underperforming: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RewardSimulation {
+    pub days: u64,
+    pub stakes: u32,
+    pub rewards: Amount,
+    pub current_mode: String,
+    // Fee charged per ANON payout, the average of past payouts or an estimate when there are none.
+    pub payout_fee: Amount,
+    pub payout_fee_estimated: bool,
+    pub modes: Vec<ModeSimulation>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModeSimulation {
+    pub mode: String,
+    // Rewards that reached the reward address, in DEFAULT mode the ones that stayed staking.
+    pub received: Amount,
+    pub fees: Amount,
+    pub payouts: u32,
+    // ANON rewards still short of the payout minimum when the window ends.
+    pub unpaid: Amount,
+    pub compounding: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HotWalletInfo {
+    pub wallet: String,
+    pub loaded: bool,
+    pub spendable: f64,
+    pub delegated: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HotWalletCreated {
+    pub wallet: String,
+    pub mnemonic: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HotWalletScript {
+    pub stake_addr: String,
+    pub spend_addr: String,
+    pub script_hex: String,
+    pub script_asm: String,
+}
+
+// Where the owner sends a zap. The uri is what goes into the QR code, wallets that can't send
+// to a cold staking script from it can use sendtypeto with script_hex.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ZapRequest {
+    pub amount: Amount,
+    pub stake_addr: String,
+    pub spend_addr: String,
+    pub script_hex: String,
+    pub script_asm: String,
+    pub uri: String,
+    pub expires: u64,
+}
+
+// Status is waiting, arrived or missed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExpectedDeposit {
+    pub id: u64,
+    pub amount: Amount,
+    pub memo: String,
+    pub created: u64,
+    pub due: u64,
+    pub status: String,
+    pub txid: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TxLabel {
+    pub txid: String,
+    pub label: String,
+    pub updated: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AddressBookEntry {
+    pub address: String,
+    pub label: String,
+    pub updated: u64,
+    // In REWARD_ADDRESS_WHITELIST
+    pub whitelisted: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PayoutEntry {
+    pub txid: String,
+    pub date: String,
+    pub timestamp: u64,
+    pub amount: f64,
+    pub fee: f64,
+    pub destination: String,
+    pub mode: String,
+    // GHOST per kB
+    #[serde(default)]
+    pub fee_rate: f64,
+    #[serde(default)]
+    pub label: String,
+    // The destination's name in the address book
+    #[serde(default)]
+    pub destination_label: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PayoutHistory {
+    pub payouts: Vec<PayoutEntry>,
+    pub payout_count: u32,
+    pub total_paid: f64,
+    pub total_fees: f64,
+    pub start: String,
+    pub end: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PendingUpdate {
+    pub version: String,
+    pub token: String,
+    pub expires: u64,
+}
+
+// What get_daemon_online answers, the startup status while ghostd is down and true once it's up.
+// Untagged like DaemonUpdate below.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DaemonOnline {
+    Starting(Option<ServerReadyDB>),
+    Online(bool),
+}
+
+// What process_daemon_update did. Untagged so it goes on the wire as the bare value older
+// clients read: the pending update, the window it waits for, the version being installed or
+// false when ghostd is already up to date.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DaemonUpdate {
+    Pending(PendingUpdate),
+    Deferred(MaintenanceWindow),
+    Updating(String),
+    UpToDate(bool),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VersionInfo {
+    pub gv_version: String,
+    // Empty from servers that didn't report their build.
+    #[serde(default)]
+    pub git_commit: String,
+    #[serde(default)]
+    pub build_date: String,
+    #[serde(default)]
+    pub cargo_features: String,
+    pub ghostd_version: String,
+    pub latest_release: String,
+    // Servers from before it was reported answer with the first version.
+    #[serde(default = "first_api_version")]
+    pub api_version: u32,
+}
+
+fn first_api_version() -> u32 {
+    1
+}
+
+// What a server can do, checked by CLICaller before it sends a command. Servers from before
+// get_api_capabilities are described from their version info, without a list of methods.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiCapabilities {
+    pub api_version: u32,
+    pub gv_version: String,
+    pub features: Vec<String>,
+    pub methods: Option<Vec<String>>,
+}
+
+impl ApiCapabilities {
+    // CARGO_FEATURES as a list, it's "none" in the version info of a build without any.
+    pub fn feature_list(cargo_features: &str) -> Vec<String> {
+        cargo_features
+            .split(',')
+            .map(|feature| feature.trim())
+            .filter(|feature| !feature.is_empty() && *feature != "none")
+            .map(|feature| feature.to_string())
+            .collect()
+    }
+
+    pub fn supports(&self, method: &str) -> bool {
+        match &self.methods {
+            Some(methods) => methods.iter().any(|name| name == method),
+            None => true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RewardOptions {
+    pub reward_mode: String,
+    pub reward_interval: String,
+    pub reward_address: String,
+    #[serde(default)]
+    pub reward_address_label: Option<String>,
+    pub reward_min: f64,
+}
+
+// What a daily or weekly digest covers, from start up to but not including end.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Digest {
+    pub period: String,
+    pub start: u64,
+    pub end: u64,
+    pub stakes: u32,
+    pub rewards: Amount,
+    pub agvr: Amount,
+    pub uptime: f64,
+    pub payouts: u32,
+    pub paid: Amount,
+    pub fees: Amount,
+    // In sats, None without two balance samples in the period.
+    pub balance_change: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ParquetExportSummary {
+    pub out_dir: String,
+    pub rewards: usize,
+    pub payouts: usize,
+    pub balance_points: usize,
+    pub partitions: usize,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PendingSplit {
+    pub token: String,
+    pub expires: u64,
+    pub pieces: u64,
+    pub utxos: Vec<SplitUtxo>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SplitUtxo {
+    pub txid: String,
+    pub vout: u32,
+    pub amount: Amount,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DigestSettings {
+    pub daily: bool,
+    pub weekly: bool,
+    pub hour: u32,
+    pub timezone: String,
+}
+
+// Hours in TIMEZONE that updates and resyncs wait for, start == end means any time.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    pub start: u32,
+    pub end: u32,
+    pub timezone: String,
+    pub open: bool,
+    pub deferred_update: Option<String>,
+    pub deferred_resync: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationState {
+    Idle,
+    Running,
+    Failed,
+}
+
+// One of the long operations in OPERATIONS, only one run of each goes at a time. A run that
+// finished goes back to idle, a failed one keeps its error until the next. progress is what
+// status shows while it runs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OperationStatus {
+    pub op_id: String,
+    pub state: OperationState,
+    pub started: Option<u64>,
+    pub finished: Option<u64>,
+    pub error: Option<String>,
+    pub progress: Option<String>,
+}
+
+// A task of the task runner. last_result is "ok" or the error the last run ended with.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduledTask {
+    pub name: String,
+    pub interval: u64,
+    pub last_run: Option<u64>,
+    pub last_result: Option<String>,
+    pub next_run: u64,
+    pub running: bool,
+    pub paused: bool,
+}
+
+// The last lines of ghostvault.log, and of ghostd's debug.log when it was asked for.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecentLogs {
+    pub ghostvault: Vec<String>,
+    pub ghostd: Option<Vec<String>>,
+}
+
+// Load, disk and network use of the host, sizes in bytes. The 24h figures compare against the
+// sample taken a day ago and are None until there is one, or when ghostd restarted since.
+// db_maintained is when dbmaintenance last checked the database.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SystemStats {
+    pub uptime: String,
+    pub load_one: f32,
+    pub load_five: f32,
+    pub load_fifteen: f32,
+    pub daemon_data_dir: String,
+    pub daemon_disk_free: u64,
+    pub daemon_disk_total: u64,
+    pub gv_home: String,
+    pub gv_disk_free: u64,
+    pub gv_disk_total: u64,
+    pub chain_size: Option<u64>,
+    pub chain_growth_24h: Option<u64>,
+    pub net_recv_24h: Option<u64>,
+    pub net_sent_24h: Option<u64>,
+    pub disk_alert_gb: u64,
+    pub low_disk: bool,
+    #[serde(default)]
+    pub db_size: Option<u64>,
+    #[serde(default)]
+    pub db_maintained: Option<u64>,
+}
+
+// A transaction from one of the vault's wallets, wallet is cold or hot.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TxSummary {
+    pub txid: String,
+    pub wallet: String,
+    pub category: String,
+    pub amount: Amount,
+    pub fee: Amount,
+    pub confirmations: i64,
+    pub block_hash: Option<String>,
+    pub block_height: Option<u32>,
+    pub time: u64,
+    pub url: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlockSummary {
+    pub hash: String,
+    pub height: u32,
+    pub confirmations: i64,
+    pub time: u64,
+    pub tx_count: u32,
+    pub size: u64,
+    pub previous_hash: Option<String>,
+    pub url: String,
+}
+
+// cursor is the id of the last event returned, or the one asked for when there were none.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EventPage {
+    pub cursor: u64,
+    pub events: Vec<GvEvent>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnnounceRoute {
+    pub event: String,
+    pub destinations: Vec<AnnounceDestination>,
+}
+
+// body None is the built in text, header None keeps the built in header.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnnounceTemplate {
+    pub msg_type: String,
+    pub header: Option<String>,
+    pub body: Option<String>,
+}
+
+// start and end are local hours, None when the destination has no quiet hours. held is the
+// notices waiting for them to end.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub destination: AnnounceDestination,
+    pub start: Option<u32>,
+    pub end: Option<u32>,
+    pub quiet_now: bool,
+    pub held: usize,
+}
+
+impl QuietHours {
+    pub fn window(&self) -> String {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => format!("{:02}:00-{:02}:00", start, end),
+            _ => "off".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChainEmergency {
+    pub active: bool,
+    pub good_chain: bool,
+    pub since: Option<u64>,
+    pub snoozed_until: Option<u64>,
+    pub chain_split_timeout: u64,
+}
+
+// What a vault's ghostd has as its best block, other vaults check their chain against it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChainTip {
+    pub best_block: u32,
+    pub best_block_hash: String,
+    pub synced: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FleetVault {
+    pub name: String,
+    pub cli_address: String,
+    // The vault's TLS certificate, connections to it stay plaintext without one.
+    #[serde(default)]
+    pub tls_cert: Option<String>,
+}
+
+// Vaults for the fleet commands, read from fleet.toml as a list of [[vault]] tables.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FleetConfig {
+    #[serde(default, rename = "vault")]
+    pub vaults: Vec<FleetVault>,
+}
+
+impl FleetConfig {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let fleet_str: String = std::fs::read_to_string(path)
+            .map_err(|err| format!("Unable to read fleet file {}: {}", path.display(), err))?;
+        let fleet: FleetConfig = toml::from_str(&fleet_str)?;
+
+        if fleet.vaults.is_empty() {
+            return Err(format!("No vaults configured in {}", path.display()).into());
+        }
+
+        for vault in &fleet.vaults {
+            if let Some(cert) = &vault.tls_cert {
+                cli_tls::trust(&vault.cli_address, &file_ops::expand_user(cert))?;
+            }
+        }
+
+        Ok(fleet)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct CLICaller {
     client: GvCLIClient,
     json_out: bool,
+    envelope_out: bool,
     timeout: time::Duration,
+    capabilities: Option<ApiCapabilities>,
 }
 
-impl CLICaller {
-    pub async fn new(
-        cli_address: &str,
-        json_out: bool,
-    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
-        let transport =
-            match tarpc::serde_transport::tcp::connect(&cli_address, Json::default).await {
-                Ok(transport) => transport,
-                Err(err) => {
-                    error!("Failed to connect to GhostVault server at: {}", cli_address);
-                    return Err(Box::new(std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        err,
-                    )));
-                }
-            };
+impl CLICaller {
+    pub async fn new(
+        cli_address: &str,
+        json_out: bool,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let client: GvCLIClient = if cli_tls::is_trusted(cli_address) {
+            let framed = match cli_tls::connect(cli_address).await {
+                Ok(framed) => framed,
+                Err(err) => {
+                    error!(
+                        "Failed to connect to GhostVault server over TLS at: {}",
+                        cli_address
+                    );
+                    return Err(err);
+                }
+            };
+
+            let transport = tarpc::serde_transport::new(framed, Json::default());
+            GvCLIClient::new(client::Config::default(), transport).spawn()
+        } else {
+            let transport =
+                match tarpc::serde_transport::tcp::connect(&cli_address, Json::default).await {
+                    Ok(transport) => transport,
+                    Err(err) => {
+                        error!("Failed to connect to GhostVault server at: {}", cli_address);
+                        return Err(Box::new(std::io::Error::new(
+                            std::io::ErrorKind::Other,
+                            err,
+                        )));
+                    }
+                };
+
+            GvCLIClient::new(client::Config::default(), transport).spawn()
+        };
+
+        let timeout: time::Duration = std::time::Duration::from_secs(45);
+
+        Ok(CLICaller {
+            client,
+            json_out,
+            envelope_out: false,
+            timeout,
+            capabilities: None,
+        })
+    }
+
+    // Tells the server who is calling, for its audit log. Vaults from before the audit log
+    // don't know set_origin, so a failure is only logged.
+    pub async fn with_origin(self, origin: &str) -> Self {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = self
+            .client
+            .set_origin(ctx, origin.to_string())
+            .instrument(tracing::info_span!("call set_origin"))
+            .await;
+
+        match result {
+            Ok(result) => {
+                if let Err(err) = result.into_result() {
+                    warn!("Unable to set the caller origin: {}", err);
+                }
+            }
+            Err(err) => warn!("Unable to set the caller origin: {}", err),
+        }
+
+        self
+    }
+
+    // Learns what the server can do and warns when it speaks another API version. Servers from
+    // before get_api_capabilities drop the connection on methods they don't know, so the
+    // version info decides whether to ask. A server that answers neither is left unchecked.
+    pub async fn negotiate(mut self) -> Self {
+        match self.fetch_capabilities().await {
+            Ok(capabilities) => {
+                self.capabilities = Some(capabilities);
+
+                if let Some(mismatch) = self.api_mismatch() {
+                    warn!("{}", mismatch);
+                }
+            }
+            Err(err) => warn!("Unable to get the server API version: {}", err),
+        }
+
+        self
+    }
+
+    async fn fetch_capabilities(
+        &self,
+    ) -> Result<ApiCapabilities, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+
+        let version_info: VersionInfo = self
+            .client
+            .get_version_info(ctx)
+            .instrument(tracing::info_span!("call get_version_info"))
+            .await?
+            .into_result()?;
+
+        if version_info.api_version < CAPABILITIES_API_VERSION {
+            return Ok(ApiCapabilities {
+                api_version: version_info.api_version,
+                gv_version: version_info.gv_version,
+                features: ApiCapabilities::feature_list(&version_info.cargo_features),
+                methods: None,
+            });
+        }
+
+        Ok(self
+            .client
+            .get_api_capabilities(ctx, API_VERSION)
+            .instrument(tracing::info_span!("call get_api_capabilities"))
+            .await?
+            .into_result()?)
+    }
+
+    // Why client and server may not understand each other, None while they're on the same
+    // API version or the server wasn't asked.
+    pub fn api_mismatch(&self) -> Option<String> {
+        let capabilities: &ApiCapabilities = self.capabilities.as_ref()?;
+
+        let update: &str = match capabilities.api_version.cmp(&API_VERSION) {
+            std::cmp::Ordering::Equal => return None,
+            std::cmp::Ordering::Less => "Update GhostVault for the newer commands",
+            std::cmp::Ordering::Greater => "Update this client to match it",
+        };
+
+        Some(format!(
+            "GhostVault {} speaks API version {}, this client version {}. {}.",
+            capabilities.gv_version, capabilities.api_version, API_VERSION, update
+        ))
+    }
+
+    // Fails without sending anything when the server is known not to have the method.
+    pub fn require(&self, method: &str) -> Result<(), GvError> {
+        match &self.capabilities {
+            Some(capabilities) if !capabilities.supports(method) => Err(GvError::new(
+                ErrorCode::Unsupported,
+                format!(
+                    "GhostVault {} (API version {}) doesn't support {}",
+                    capabilities.gv_version, capabilities.api_version, method
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    // Prints every response envelope as it arrives, for gv-cli --json.
+    pub fn with_envelope_out(mut self, envelope_out: bool) -> Self {
+        self.envelope_out = envelope_out;
+        self
+    }
+
+    fn unwrap_response<T: Serialize + DeserializeOwned>(
+        &self,
+        reply: GvReply<T>,
+    ) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
+        if self.envelope_out {
+            println!("{}", serde_json::to_string_pretty(&reply).unwrap());
+        }
+
+        Ok(reply.into_result()?)
+    }
+
+    // Calls that need ghostd fail while the vault is still starting up, with the startup
+    // status in the error.
+    async fn ensure_ready(
+        &self,
+        ctx: Context,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let daemon_check = tokio::select! {
+            res1 = self.client.get_daemon_online(ctx) => { res1 }
+            res2 = self.client.get_daemon_online(ctx) => { res2 }
+        };
+
+        if let Ok(DaemonOnline::Starting(Some(server_ready))) = daemon_check?.into_result() {
+            let msg: String = format!(
+                "GhostVault Not Ready!\n{}",
+                serde_json::to_string_pretty(&server_ready).unwrap()
+            );
+            return Err(Box::new(GvError::new(ErrorCode::Unavailable, msg)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn call_getblockcount(
+        &self,
+    ) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+
+        self.ensure_ready(ctx).await?;
+
+        let result: Result<GvReply<u64>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.getblockcount(ctx) => { res1 }
+                res2 = self.client.getblockcount(ctx) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call getblockcount"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: u64 = self.unwrap_response(result)?;
+                self.display_result(&result.to_string());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_new_block(
+        &self,
+        new_block: String,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let _result: Result<(), client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.new_block(ctx, new_block.clone()) => { res1 }
+                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call new block"))
+        .await;
+
+        Ok(())
+    }
+
+    pub async fn call_new_remote_block(
+        &self,
+        block_hash: String,
+        height: u32,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let _result: Result<(), client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.new_remote_block(ctx, block_hash.clone(), height) => { res1 }
+                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call new remote block"))
+        .await;
+
+        Ok(())
+    }
+
+    pub async fn call_new_wallet_tx(
+        &self,
+        txid_and_wal: TxidAndWallet,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let _result: Result<(), client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.new_wallet_tx(ctx, txid_and_wal.clone()) => { res1 }
+                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call new wallet tx"))
+        .await;
+
+        Ok(())
+    }
+
+    pub async fn call_get_daemon_state(
+        &self,
+    ) -> Result<GVStatus, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        self.ensure_ready(ctx).await?;
+        let result: Result<GvReply<GVStatus>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_daemon_state(ctx) => { res1 }
+                //res2 = self.client.get_daemon_state(ctx) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call getblockcount"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: GVStatus = self.unwrap_response(result)?;
+                if !self.json_out {
+                    display_stats_page(&result);
+                }
+
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_daemon_state_tz(
+        &self,
+        timezone: String,
+    ) -> Result<GVStatus, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        self.ensure_ready(ctx).await?;
+        let result: Result<GvReply<GVStatus>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_daemon_state_tz(ctx, timezone) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_daemon_state_tz"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: GVStatus = self.unwrap_response(result)?;
+                if !self.json_out {
+                    display_stats_page(&result);
+                }
+
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_shutdown(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.shutdown(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call shutdown"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_enable_telegram_bot(
+        &self,
+        token: String,
+        user: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.enable_telegram_bot(ctx, token, user) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call enable_telegram_bot"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_disable_telegram_bot(
+        &self,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.disable_telegram_bot(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call disable_telegram_bot"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_reward_interval(
+        &self,
+        interval: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.set_reward_interval(ctx, interval) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_reward_interval"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_ext_pub_key(
+        &self,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_ext_pub_key(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_ext_pub_key"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_payout_min(
+        &self,
+        min_payout: f64,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.set_payout_min(ctx, min_payout) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_payout_min"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_reward_mode(
+        &self,
+        mode: String,
+        addr: Option<String>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+
+        self.ensure_ready(ctx).await?;
+
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.set_reward_mode(ctx, mode, addr) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_reward_mode"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_process_daemon_update(
+        &self,
+        force: bool,
+    ) -> Result<DaemonUpdate, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<DaemonUpdate>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.process_daemon_update(ctx, force) => { res1 }
+                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call process_daemon_update"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: DaemonUpdate = self.unwrap_response(result)?;
+                match &result {
+                    DaemonUpdate::UpToDate(up_to_date) => {
+                        self.display_result(&up_to_date.to_string())
+                    }
+                    DaemonUpdate::Updating(version) => self.display_result(version),
+                    _ => self.display_result(&serde_json::to_string_pretty(&result).unwrap()),
+                }
+
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_reward_options(
+        &self,
+    ) -> Result<RewardOptions, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<RewardOptions>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_reward_options(ctx) => { res1 }
+                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_reward_options"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: RewardOptions = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_validate_address(
+        &self,
+        addr: String,
+    ) -> Result<AddressInfo, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+
+        self.ensure_ready(ctx).await?;
+
+        let result: Result<GvReply<AddressInfo>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.validate_address(ctx, addr) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call validate_address"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: AddressInfo = self.unwrap_response(result)?;
+                let res = serde_json::to_string_pretty(&result).unwrap();
+                self.display_result(&res);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_pending_rewards(
+        &self,
+    ) -> Result<PendingRewards, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+
+        self.ensure_ready(ctx).await?;
+
+        let result: Result<GvReply<PendingRewards>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_pending_rewards(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_pending_rewards"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: PendingRewards = self.unwrap_response(result)?;
+                let res = serde_json::to_string_pretty(&result).unwrap();
+                self.display_result(&res);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_process_reward_payout(
+        &self,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let _result: Result<(), client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.process_payouts(ctx) => { res1 }
+                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call process_payouts"))
+        .await;
+
+        Ok(())
+    }
+
+    pub async fn call_start_server_tasks(
+        &self,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let _result: Result<(), client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.start_server_tasks(ctx) => { res1 }
+                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call start_server_tasks"))
+        .await;
+
+        Ok(())
+    }
+
+    pub async fn call_get_version_info(
+        &self,
+    ) -> Result<VersionInfo, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+
+        let result: Result<GvReply<VersionInfo>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_version_info(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_version_info"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: VersionInfo = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_check_chain(&self) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+
+        let result: Result<GvReply<bool>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.check_chain(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call check_chain"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: bool = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_bot_announce(
+        &self,
+        msg_type: String,
+        new_val: bool,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.set_bot_announce(ctx, msg_type, new_val) => { res1 }
+                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_bot_announce"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_overview(
+        &self,
+    ) -> Result<StakingDataOverview, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+
+        self.ensure_ready(ctx).await?;
+
+        let result: Result<GvReply<StakingDataOverview>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_overview(ctx) => { res1 }
+                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_overview"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: StakingDataOverview = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_mnemonic(
+        &self,
+        passphrase: String,
+    ) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+
+        let result: Result<GvReply<Option<String>>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_mnemonic(ctx, passphrase) => { res1 }
+                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_mnemonic"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Option<String> = self.unwrap_response(result)?;
+                match &result {
+                    Some(mnemonic) => self.display_result(mnemonic),
+                    None => self.display_result("Failed to retrieve mnemonic!"),
+                }
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_import_wallet(
+        &self,
+        mnemonic: String,
+        wallet_name: String,
+        birthday: Option<String>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.import_wallet(ctx, mnemonic, wallet_name, birthday) => { res1 }
+                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call import_wallet"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_earnings_chart_data(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<AllTimeEarnigns, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<AllTimeEarnigns>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_earnings_chart_data(ctx, start, end) => { res1 }
+                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_earnings_chart_data"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: AllTimeEarnigns = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_earnings_chart_data_tz(
+        &self,
+        start: u64,
+        end: u64,
+        timezone: String,
+    ) -> Result<AllTimeEarnigns, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<AllTimeEarnigns>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_earnings_chart_data_tz(ctx, start, end, timezone) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_earnings_chart_data_tz"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: AllTimeEarnigns = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_payout_history(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<PayoutHistory, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<PayoutHistory>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_payout_history(ctx, start, end) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_payout_history"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: PayoutHistory = self.unwrap_response(result)?;
+                let res = serde_json::to_string_pretty(&result).unwrap();
+                self.display_result(&res);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_rescan_wallet(
+        &self,
+        start_height: u32,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.rescan_wallet(ctx, start_height) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call rescan_wallet"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_rescan_status(
+        &self,
+    ) -> Result<WalletRescanDB, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<WalletRescanDB>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_rescan_status(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_rescan_status"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: WalletRescanDB = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_process_wallet_rescan(
+        &self,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let _result: Result<(), client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.process_wallet_rescan(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call process_wallet_rescan"))
+        .await;
+
+        Ok(())
+    }
+
+    pub async fn call_create_hot_wallet(
+        &self,
+        name: String,
+    ) -> Result<HotWalletCreated, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<HotWalletCreated>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.create_hot_wallet(ctx, name) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call create_hot_wallet"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: HotWalletCreated = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_link_hot_wallet(
+        &self,
+        name: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.link_hot_wallet(ctx, name) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call link_hot_wallet"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_hot_wallet_info(
+        &self,
+    ) -> Result<HotWalletInfo, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<HotWalletInfo>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_hot_wallet_info(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_hot_wallet_info"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: HotWalletInfo = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_hot_wallet_script(
+        &self,
+    ) -> Result<HotWalletScript, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<HotWalletScript>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_hot_wallet_script(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_hot_wallet_script"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: HotWalletScript = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_describe_api(
+        &self,
+    ) -> Result<Vec<ApiMethod>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<ApiMethod>>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.describe_api(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call describe_api"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Vec<ApiMethod> = self.unwrap_response(result)?;
+                let res = serde_json::to_string_pretty(&result).unwrap();
+                self.display_result(&res);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_export_parquet(
+        &self,
+        out_dir: String,
+    ) -> Result<ParquetExportSummary, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<ParquetExportSummary>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.export_parquet(ctx, out_dir) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call export_parquet"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: ParquetExportSummary = self.unwrap_response(result)?;
+                let res = serde_json::to_string_pretty(&result).unwrap();
+                self.display_result(&res);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_confirm_update(
+        &self,
+        token: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.confirm_update(ctx, token) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call confirm_update"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_install_daemon_archive(
+        &self,
+        path: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.install_daemon_archive(ctx, path) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call install_daemon_archive"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&format!("Installing ghostd version: {}", result));
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_pin_daemon_version(
+        &self,
+        version: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.pin_daemon_version(ctx, version) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call pin_daemon_version"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_operation_status(
+        &self,
+        op_id: String,
+    ) -> Result<OperationStatus, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<OperationStatus>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_operation_status(ctx, op_id) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_operation_status"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: OperationStatus = self.unwrap_response(result)?;
+                let res = serde_json::to_string_pretty(&result).unwrap();
+                self.display_result(&res);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_jobs(
+        &self,
+        limit: u64,
+    ) -> Result<Vec<JobDB>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<JobDB>>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_jobs(ctx, limit) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_jobs"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Vec<JobDB> = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_scheduled_tasks(
+        &self,
+    ) -> Result<Vec<ScheduledTask>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<ScheduledTask>>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_scheduled_tasks(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_scheduled_tasks"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Vec<ScheduledTask> = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_run_task_now(
+        &self,
+        name: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.run_task_now(ctx, name) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call run_task_now"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_pause_task(
+        &self,
+        name: String,
+        paused: bool,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.pause_task(ctx, name, paused) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call pause_task"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_maintenance_window(
+        &self,
+    ) -> Result<MaintenanceWindow, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<MaintenanceWindow>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_maintenance_window(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_maintenance_window"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: MaintenanceWindow = self.unwrap_response(result)?;
+                let window: &MaintenanceWindow = &result;
+
+                self.display_result(&format!(
+                    "Maintenance window: {:02}:00 to {:02}:00 {}\nOpen now: {}\nDeferred update: {}\nDeferred resync: {}",
+                    window.start,
+                    window.end,
+                    window.timezone,
+                    bool_to_yn(window.open),
+                    window.deferred_update.as_deref().unwrap_or("none"),
+                    bool_to_yn(window.deferred_resync)
+                ));
+
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_maintenance_window(
+        &self,
+        start: u32,
+        end: u32,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.set_maintenance_window(ctx, start, end) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_maintenance_window"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_process_maintenance(
+        &self,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<bool>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.process_maintenance(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call process_maintenance"))
+        .await;
+
+        match result {
+            Ok(result) => self.unwrap_response(result),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_system_stats(
+        &self,
+    ) -> Result<SystemStats, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<SystemStats>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_system_stats(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_system_stats"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: SystemStats = self.unwrap_response(result)?;
+                let stats: &SystemStats = &result;
+
+                let bytes = |bytes: Option<u64>| -> String {
+                    bytes.map_or("n/a".to_string(), format_bytes)
+                };
+                let disk_alert: String = if stats.disk_alert_gb == 0 {
+                    "off".to_string()
+                } else {
+                    format!("below {} GB", stats.disk_alert_gb)
+                };
+
+                let db_maintained: String = stats
+                    .db_maintained
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts as i64, 0))
+                    .map_or("never".to_string(), |checked| {
+                        checked.format("%Y-%m-%d %H:%M UTC").to_string()
+                    });
+
+                self.display_result(&format!(
+                    "Uptime: {}\nLoad average: {:.2} {:.2} {:.2}\nGhostd data dir: {} free of {} ({})\nGV home: {} free of {} ({})\nBlockchain size: {}\nBlockchain growth 24h: {}\nGhostd received 24h: {}\nGhostd sent 24h: {}\nDatabase size: {}\nDatabase checked: {}\nDisk alert: {}\nLow disk: {}",
+                    stats.uptime,
+                    stats.load_one,
+                    stats.load_five,
+                    stats.load_fifteen,
+                    format_bytes(stats.daemon_disk_free),
+                    format_bytes(stats.daemon_disk_total),
+                    stats.daemon_data_dir,
+                    format_bytes(stats.gv_disk_free),
+                    format_bytes(stats.gv_disk_total),
+                    stats.gv_home,
+                    bytes(stats.chain_size),
+                    bytes(stats.chain_growth_24h),
+                    bytes(stats.net_recv_24h),
+                    bytes(stats.net_sent_24h),
+                    bytes(stats.db_size),
+                    db_maintained,
+                    disk_alert,
+                    bool_to_yn(stats.low_disk)
+                ));
+
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_db_maintenance(
+        &self,
+    ) -> Result<DbMaintenanceDB, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<DbMaintenanceDB>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.db_maintenance(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call db_maintenance"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: DbMaintenanceDB = self.unwrap_response(result)?;
+                let trees: String = result
+                    .trees
+                    .iter()
+                    .map(|(name, entries)| format!("  {}: {}", name, entries))
+                    .collect::<Vec<String>>()
+                    .join("\n");
+                let read_errors: String = if result.read_errors.is_empty() {
+                    "none".to_string()
+                } else {
+                    result.read_errors.join(", ")
+                };
+
+                self.display_result(&format!(
+                    "Database size: {} before, {} after\nTook: {} ms\nRead errors: {}\nStakes checked: {}\nUnreadable stakes: {}\nStakes moved to their timestamp: {}\nAll time totals repaired: {}\nRollup periods repaired: {}\nEntries per tree:\n{}",
+                    format_bytes(result.size_before),
+                    format_bytes(result.size_after),
+                    result.duration_ms,
+                    read_errors,
+                    result.rewards,
+                    result.unreadable_rewards,
+                    result.rekeyed_rewards,
+                    result.totals_repaired,
+                    result.rollups_repaired,
+                    trees
+                ));
+
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_daemon_prune(
+        &self,
+        size_mb: u64,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.set_daemon_prune(ctx, size_mb) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_daemon_prune"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_bootstrap_sync(
+        &self,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.bootstrap_sync(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call bootstrap_sync"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_bootstrap_status(
+        &self,
+    ) -> Result<BootstrapDB, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<BootstrapDB>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_bootstrap_status(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_bootstrap_status"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: BootstrapDB = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_restart_history(
+        &self,
+        limit: u64,
+    ) -> Result<Vec<RestartDB>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<RestartDB>>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_restart_history(ctx, limit) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_restart_history"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Vec<RestartDB> = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_log_level(
+        &self,
+        level: String,
+        module: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.set_log_level(ctx, level, module) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_log_level"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_logs(
+        &self,
+        lines: u64,
+        level_filter: String,
+        daemon: bool,
+    ) -> Result<RecentLogs, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<RecentLogs>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_logs(ctx, lines, level_filter, daemon) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_logs"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: RecentLogs = self.unwrap_response(result)?;
+                let logs: &RecentLogs = &result;
+
+                let mut output: String = logs.ghostvault.join("\n");
+
+                if let Some(ghostd) = &logs.ghostd {
+                    output.push_str(&format!(
+                        "\n\n-- ghostd debug.log --\n{}",
+                        ghostd.join("\n")
+                    ));
+                }
+
+                self.display_result(&output);
+
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_audit_log(
+        &self,
+        limit: u64,
+    ) -> Result<Vec<AuditDB>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<AuditDB>>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_audit_log(ctx, limit) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_audit_log"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Vec<AuditDB> = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_pending_reward_change(
+        &self,
+    ) -> Result<PendingRewardChangeDB, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<PendingRewardChangeDB>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_pending_reward_change(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_pending_reward_change"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: PendingRewardChangeDB = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_cancel_reward_change(
+        &self,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.cancel_reward_change(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call cancel_reward_change"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_seal_mnemonic(
+        &self,
+        passphrase: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.seal_mnemonic(ctx, passphrase) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call seal_mnemonic"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_reload_config(
+        &self,
+    ) -> Result<ConfigReload, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<ConfigReload>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.reload_config(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call reload_config"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: ConfigReload = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_simulate_rewards(
+        &self,
+        mode: Option<String>,
+        days: u64,
+    ) -> Result<RewardSimulation, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<RewardSimulation>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.simulate_rewards(ctx, mode, days) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call simulate_rewards"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: RewardSimulation = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_network_stats(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<NetworkStats, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<NetworkStats>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_network_stats(ctx, start, end) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_network_stats"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: NetworkStats = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_list_cold_wallets(
+        &self,
+    ) -> Result<ColdWallets, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<ColdWallets>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.list_cold_wallets(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call list_cold_wallets"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: ColdWallets = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_create_cold_wallet(
+        &self,
+        name: String,
+    ) -> Result<ColdWalletCreated, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<ColdWalletCreated>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.create_cold_wallet(ctx, name) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call create_cold_wallet"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: ColdWalletCreated = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_import_cold_wallet(
+        &self,
+        name: String,
+        mnemonic: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.import_cold_wallet(ctx, name, mnemonic) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call import_cold_wallet"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_switch_cold_wallet(
+        &self,
+        name: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.switch_cold_wallet(ctx, name) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call switch_cold_wallet"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_cold_wallet_stakes(
+        &self,
+        name: String,
+        start: u64,
+        end: u64,
+        division: String,
+    ) -> Result<BarChart, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<BarChart>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_cold_wallet_stakes(ctx, name, start, end, division) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_cold_wallet_stakes"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: BarChart = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_repair_wallet(
+        &self,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.repair_wallet(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call repair_wallet"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_reindex_history(
+        &self,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.reindex_history(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call reindex_history"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_delegations(
+        &self,
+    ) -> Result<Delegations, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Delegations>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_delegations(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_delegations"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Delegations = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_build_zap_request(
+        &self,
+        amount: f64,
+    ) -> Result<ZapRequest, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<ZapRequest>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.build_zap_request(ctx, amount) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call build_zap_request"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: ZapRequest = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_add_expected_deposit(
+        &self,
+        amount: f64,
+        memo: String,
+        hours: Option<u64>,
+    ) -> Result<ExpectedDeposit, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<ExpectedDeposit>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.add_expected_deposit(ctx, amount, memo, hours) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call add_expected_deposit"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: ExpectedDeposit = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_expected_deposits(
+        &self,
+    ) -> Result<Vec<ExpectedDeposit>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<ExpectedDeposit>>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_expected_deposits(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_expected_deposits"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Vec<ExpectedDeposit> = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_remove_expected_deposit(
+        &self,
+        id: u64,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.remove_expected_deposit(ctx, id) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call remove_expected_deposit"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_anonymization_status(
+        &self,
+    ) -> Result<AnonymizationStatus, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<AnonymizationStatus>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_anonymization_status(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_anonymization_status"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: AnonymizationStatus = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_anon_policy(
+        &self,
+        enabled: bool,
+        threshold: Option<f64>,
+        interval: Option<u64>,
+        ring_size: Option<u32>,
+        batch_size: Option<u64>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.set_anon_policy(ctx, enabled, threshold, interval, ring_size, batch_size) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_anon_policy"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_tx_label(
+        &self,
+        txid: String,
+        label: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.set_tx_label(ctx, txid, label) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_tx_label"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_tx_labels(
+        &self,
+    ) -> Result<Vec<TxLabel>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<TxLabel>>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_tx_labels(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_tx_labels"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Vec<TxLabel> = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_address_label(
+        &self,
+        address: String,
+        label: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.set_address_label(ctx, address, label) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_address_label"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_address_book(
+        &self,
+    ) -> Result<Vec<AddressBookEntry>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<AddressBookEntry>>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_address_book(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_address_book"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Vec<AddressBookEntry> = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_api_capabilities(
+        &self,
+    ) -> Result<ApiCapabilities, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<ApiCapabilities>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_api_capabilities(ctx, API_VERSION) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_api_capabilities"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: ApiCapabilities = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_create_backup(
+        &self,
+        path: String,
+    ) -> Result<BackupInfo, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<BackupInfo>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.create_backup(ctx, path) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call create_backup"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: BackupInfo = self.unwrap_response(result)?;
+                let res = serde_json::to_string_pretty(&result).unwrap();
+                self.display_result(&res);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_restore_backup(
+        &self,
+        path: String,
+        passphrase: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.restore_backup(ctx, path, passphrase) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call restore_backup"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_migrate_export(
+        &self,
+        path: String,
+    ) -> Result<MigrateInfo, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<MigrateInfo>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.migrate_export(ctx, path) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call migrate_export"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: MigrateInfo = self.unwrap_response(result)?;
+                let res = serde_json::to_string_pretty(&result).unwrap();
+                self.display_result(&res);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_migrate_import(
+        &self,
+        path: String,
+        passphrase: String,
+        force: bool,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.migrate_import(ctx, path, passphrase, force) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call migrate_import"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_process_auto_backup(
+        &self,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<()>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.process_auto_backup(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call process_auto_backup"))
+        .await;
+
+        match result {
+            Ok(result) => self.unwrap_response(result),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_take_bot_messages(
+        &self,
+    ) -> Result<Vec<TgBotQueueDB>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<TgBotQueueDB>>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.take_bot_messages(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call take_bot_messages"))
+        .await;
+
+        match result {
+            Ok(result) => self.unwrap_response(result),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_chain_emergency(
+        &self,
+    ) -> Result<ChainEmergency, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<ChainEmergency>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_chain_emergency(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_chain_emergency"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: ChainEmergency = self.unwrap_response(result)?;
+                let res = serde_json::to_string_pretty(&result).unwrap();
+                self.display_result(&res);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_chain_emergency_action(
+        &self,
+        action: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.chain_emergency_action(ctx, action) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call chain_emergency_action"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_announce_routes(
+        &self,
+    ) -> Result<Vec<AnnounceRoute>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<AnnounceRoute>>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_announce_routes(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_announce_routes"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Vec<AnnounceRoute> = self.unwrap_response(result)?;
+                for route in &result {
+                    let destinations: Vec<&str> = route
+                        .destinations
+                        .iter()
+                        .map(|destination| destination.as_str())
+                        .collect();
+
+                    let destinations: String = if destinations.is_empty() {
+                        "none".to_string()
+                    } else {
+                        destinations.join(", ")
+                    };
+
+                    self.display_result(&format!("{}: {}", route.event, destinations));
+                }
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_announce_route(
+        &self,
+        event: String,
+        destinations: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.set_announce_route(ctx, event, destinations) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_announce_route"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_announce_templates(
+        &self,
+    ) -> Result<Vec<AnnounceTemplate>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<AnnounceTemplate>>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_announce_templates(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_announce_templates"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Vec<AnnounceTemplate> = self.unwrap_response(result)?;
+                for template in &result {
+                    let text: String = match (&template.header, &template.body) {
+                        (Some(header), Some(body)) => format!("{} | {}", header, body),
+                        (None, Some(body)) => body.clone(),
+                        _ => "default".to_string(),
+                    };
+
+                    self.display_result(&format!("{}: {}", template.msg_type, text));
+                }
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_announce_template(
+        &self,
+        msg_type: String,
+        body: String,
+        header: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.set_announce_template(ctx, msg_type, body, header) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_announce_template"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_quiet_hours(
+        &self,
+    ) -> Result<Vec<QuietHours>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<QuietHours>>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_quiet_hours(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_quiet_hours"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Vec<QuietHours> = self.unwrap_response(result)?;
+                for quiet in &result {
+                    let state: String = if quiet.quiet_now {
+                        format!(" (quiet now, {} held)", quiet.held)
+                    } else {
+                        String::new()
+                    };
+
+                    self.display_result(&format!(
+                        "{}: {}{}",
+                        quiet.destination.as_str(),
+                        quiet.window(),
+                        state
+                    ));
+                }
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_quiet_hours(
+        &self,
+        destination: String,
+        hours: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.set_quiet_hours(ctx, destination, hours) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_quiet_hours"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_explorer_health(
+        &self,
+    ) -> Result<Vec<ExplorerHealth>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<ExplorerHealth>>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_explorer_health(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_explorer_health"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Vec<ExplorerHealth> = self.unwrap_response(result)?;
+                for status in &result {
+                    let health: String = match &status.last_error {
+                        Some(err) => format!("failing ({})", err),
+                        None => "ok".to_string(),
+                    };
+
+                    self.display_result(&format!("{}: {}", status.url, health));
+                }
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_chain_tip(
+        &self,
+    ) -> Result<ChainTip, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<ChainTip>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_chain_tip(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_chain_tip"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: ChainTip = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_block_hash_at(
+        &self,
+        height: u32,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_block_hash_at(ctx, height) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_block_hash_at"))
+        .await;
 
-        let client: GvCLIClient = GvCLIClient::new(client::Config::default(), transport).spawn();
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
 
-        let timeout: time::Duration = std::time::Duration::from_secs(45);
+    pub async fn call_get_stake_forecast(
+        &self,
+    ) -> Result<StakeForecast, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<StakeForecast>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_stake_forecast(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_stake_forecast"))
+        .await;
 
-        Ok(CLICaller {
-            client,
-            json_out,
-            timeout,
-        })
+        match result {
+            Ok(result) => {
+                let result: StakeForecast = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
     }
 
-    pub async fn call_getblockcount(
+    // Readiness of the vault, the same thing the local bot reads straight from its own db.
+    pub async fn call_get_server_ready(
         &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ServerReadyDB, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<DaemonOnline>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.get_daemon_online(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_daemon_online"))
+        .await;
 
-        let daemon_check = tokio::select! {
-            res1 = self.client.get_daemon_online(ctx) => { res1 }
-            res2 = self.client.get_daemon_online(ctx) => { res2 }
-        };
+        match result {
+            Ok(result) => match result.into_result()? {
+                DaemonOnline::Starting(Some(server_ready)) => Ok(server_ready),
+                _ => Ok(ServerReadyDB {
+                    ready: true,
+                    daemon_ready: true,
+                    reason: None,
+                }),
+            },
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_wallet_passphrase(
+        &self,
+        passphrase: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            // Send the request twice, just to be safe! ;)
+            tokio::select! {
+                res1 = self.client.set_wallet_passphrase(ctx, passphrase) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call set_wallet_passphrase"))
+        .await;
 
-        match daemon_check {
+        match result {
             Ok(result) => {
-                if result.is_object() {
-                    let res_obj = serde_json::to_string_pretty(&result).unwrap();
-                    let msg = format!("GhostVault Not Ready!\n{}", res_obj);
-                    self.display_result(&msg);
-                    return Ok(result);
-                }
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
             }
-            Err(e) => return Err(e.into()),
+            Err(e) => Err(e.into()),
         }
+    }
 
-        let result: Result<Value, client::RpcError> = async move {
+    pub async fn call_unlock_wallet(
+        &self,
+        passphrase: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
             // Send the request twice, just to be safe! ;)
             tokio::select! {
-                res1 = self.client.getblockcount(ctx) => { res1 }
-                res2 = self.client.getblockcount(ctx) => { res2 }
+                res1 = self.client.unlock_wallet(ctx, passphrase) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call getblockcount"))
+        .instrument(tracing::info_span!("call unlock_wallet"))
         .await;
 
         match result {
             Ok(result) => {
-                self.display_result(&result.as_u64().unwrap().to_string());
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_new_block(
+    pub async fn call_unlock_for_payout(
         &self,
-        new_block: String,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        passphrase: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let _result: Result<(), client::RpcError> = async move {
+        let result: Result<GvReply<String>, client::RpcError> = async move {
             // Send the request twice, just to be safe! ;)
             tokio::select! {
-                res1 = self.client.new_block(ctx, new_block.clone()) => { res1 }
-                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+                res1 = self.client.unlock_for_payout(ctx, passphrase) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call new block"))
+        .instrument(tracing::info_span!("call unlock_for_payout"))
         .await;
 
-        Ok(())
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
     }
 
-    pub async fn call_new_remote_block(
+    pub async fn call_get_wallet_lock_status(
         &self,
-        block_hash: String,
-        height: u32,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let _result: Result<(), client::RpcError> = async move {
+        let result: Result<GvReply<String>, client::RpcError> = async move {
             // Send the request twice, just to be safe! ;)
             tokio::select! {
-                res1 = self.client.new_remote_block(ctx, block_hash.clone(), height) => { res1 }
-                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+                res1 = self.client.get_wallet_lock_status(ctx) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call new remote block"))
+        .instrument(tracing::info_span!("call get_wallet_lock_status"))
         .await;
 
-        Ok(())
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
     }
 
-    pub async fn call_new_wallet_tx(
+    pub async fn call_get_stake_barchart_data(
         &self,
-        txid_and_wal: TxidAndWallet,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        start: u64,
+        end: u64,
+        division: String,
+    ) -> Result<BarChart, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let _result: Result<(), client::RpcError> = async move {
+        let result: Result<GvReply<BarChart>, client::RpcError> = async move {
             // Send the request twice, just to be safe! ;)
             tokio::select! {
-                res1 = self.client.new_wallet_tx(ctx, txid_and_wal.clone()) => { res1 }
+                res1 = self.client.get_stake_barchart_data(ctx, start, end, division) => { res1 }
                 //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
             }
         }
-        .instrument(tracing::info_span!("call new wallet tx"))
+        .instrument(tracing::info_span!("call get_stake_heatmap_data"))
         .await;
 
-        Ok(())
+        match result {
+            Ok(result) => {
+                let result: BarChart = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
     }
 
-    pub async fn call_get_daemon_state(
+    pub async fn call_get_stake_barchart_data_tz(
         &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        start: u64,
+        end: u64,
+        division: String,
+        timezone: String,
+    ) -> Result<BarChart, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let daemon_check = tokio::select! {
-            res1 = self.client.get_daemon_online(ctx) => { res1 }
-            res2 = self.client.get_daemon_online(ctx) => { res2 }
-        };
+        let result: Result<GvReply<BarChart>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_stake_barchart_data_tz(ctx, start, end, division, timezone) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_stake_barchart_data_tz"))
+        .await;
 
-        match daemon_check {
+        match result {
             Ok(result) => {
-                if result.is_object() {
-                    let res_obj = serde_json::to_string_pretty(&result).unwrap();
-                    let msg = format!("GhostVault Not Ready!\n{}", res_obj);
-                    self.display_result(&msg);
-                    return Ok(result);
-                }
+                let result: BarChart = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string(&result).unwrap());
+                Ok(result)
             }
-            Err(e) => return Err(e.into()),
+            Err(e) => Err(e.into()),
         }
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+    }
+
+    pub async fn call_get_stakes_by_address(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<StakesByAddress, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<StakesByAddress>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.get_daemon_state(ctx) => { res1 }
-                //res2 = self.client.get_daemon_state(ctx) => { res2 }
+                res1 = self.client.get_stakes_by_address(ctx, start, end) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call getblockcount"))
+        .instrument(tracing::info_span!("call get_stakes_by_address"))
         .await;
 
         match result {
             Ok(result) => {
-                if !self.json_out {
-                    display_stats_page(&result);
-                }
+                let result: StakesByAddress = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_stakes_by_address_csv(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_stakes_by_address_csv(ctx, start, end) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_stakes_by_address_csv"))
+        .await;
 
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                // Only the CSV goes to stdout so it can be redirected straight to a file
+                self.display_result(result.trim_end());
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_shutdown(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+    pub async fn call_register_tenant(
+        &self,
+        name: String,
+        key: String,
+        payout_address: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<String>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.shutdown(ctx) => { res1 }
+                res1 = self.client.register_tenant(ctx, name, key, payout_address) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call shutdown"))
+        .instrument(tracing::info_span!("call register_tenant"))
         .await;
 
         match result {
             Ok(result) => {
-                self.display_result(result.as_str().unwrap());
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_enable_telegram_bot(
+    pub async fn call_remove_tenant(
         &self,
-        token: String,
-        user: String,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        name: String,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<String>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.enable_telegram_bot(ctx, token, user) => { res1 }
+                res1 = self.client.remove_tenant(ctx, name) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call enable_telegram_bot"))
+        .instrument(tracing::info_span!("call remove_tenant"))
         .await;
 
         match result {
             Ok(result) => {
-                self.display_result(result.as_str().unwrap());
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_disable_telegram_bot(
+    pub async fn call_get_tenants(
         &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<Vec<Tenant>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<Vec<Tenant>>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_tenants(ctx) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_tenants"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: Vec<Tenant> = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_get_tenant_statement(
+        &self,
+        name: String,
+        start: u64,
+        end: u64,
+    ) -> Result<TenantStatement, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<TenantStatement>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_tenant_statement(ctx, name, start, end) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_tenant_statement"))
+        .await;
 
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        match result {
+            Ok(result) => {
+                let result: TenantStatement = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_set_fee_policy(
+        &self,
+        policy: String,
+        value: Option<f64>,
+        max_fee: Option<f64>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.disable_telegram_bot(ctx) => { res1 }
+                res1 = self.client.set_fee_policy(ctx, policy, value, max_fee) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call disable_telegram_bot"))
+        .instrument(tracing::info_span!("call set_fee_policy"))
         .await;
 
         match result {
             Ok(result) => {
-                self.display_result(result.as_str().unwrap());
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_set_reward_interval(
+    pub async fn call_list_utxos(
         &self,
-        interval: String,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        wallet: String,
+        utxo_type: String,
+        min_amount: f64,
+        max_amount: f64,
+        min_confirmations: u64,
+    ) -> Result<Vec<Utxo>, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<Vec<Utxo>>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.set_reward_interval(ctx, interval) => { res1 }
+                res1 = self.client.list_utxos(ctx, wallet, utxo_type, min_amount, max_amount, min_confirmations) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call set_reward_interval"))
+        .instrument(tracing::info_span!("call list_utxos"))
         .await;
 
         match result {
             Ok(result) => {
-                self.display_result(result.as_str().unwrap());
+                let result: Vec<Utxo> = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_get_ext_pub_key(
+    pub async fn call_freeze_utxo(
         &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        wallet: String,
+        txid: String,
+        vout: u32,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<String>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.get_ext_pub_key(ctx) => { res1 }
+                res1 = self.client.freeze_utxo(ctx, wallet, txid, vout) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call get_ext_pub_key"))
+        .instrument(tracing::info_span!("call freeze_utxo"))
+        .await;
+
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn call_unfreeze_utxo(
+        &self,
+        wallet: String,
+        txid: String,
+        vout: u32,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<String>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.unfreeze_utxo(ctx, wallet, txid, vout) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call unfreeze_utxo"))
         .await;
 
         match result {
             Ok(result) => {
-                self.display_result(result.as_str().unwrap());
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_set_payout_min(
+    pub async fn call_consolidate_utxos(
         &self,
-        min_payout: f64,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        wallet: String,
+        max_amount: f64,
+        max_fee_rate: f64,
+    ) -> Result<Consolidation, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<Consolidation>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.set_payout_min(ctx, min_payout) => { res1 }
+                res1 = self.client.consolidate_utxos(ctx, wallet, max_amount, max_fee_rate) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call set_payout_min"))
+        .instrument(tracing::info_span!("call consolidate_utxos"))
         .await;
 
         match result {
             Ok(result) => {
-                self.display_result(result.as_str().unwrap());
+                let result: Consolidation = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_set_reward_mode(
+    pub async fn call_split_utxos(
         &self,
-        mode: String,
-        addr: Option<String>,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<Option<PendingSplit>, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-
-        let daemon_check = tokio::select! {
-            res1 = self.client.get_daemon_online(ctx) => { res1 }
-            res2 = self.client.get_daemon_online(ctx) => { res2 }
-        };
-
-        match daemon_check {
-            Ok(result) => {
-                if result.is_object() {
-                    let res_obj = serde_json::to_string_pretty(&result).unwrap();
-                    let msg = format!("GhostVault Not Ready!\n{}", res_obj);
-                    self.display_result(&msg);
-                    return Ok(result);
-                }
-            }
-            Err(e) => return Err(e.into()),
-        }
-
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<Option<PendingSplit>>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.set_reward_mode(ctx, mode, addr) => { res1 }
+                res1 = self.client.split_utxos(ctx) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call set_reward_mode"))
+        .instrument(tracing::info_span!("call split_utxos"))
         .await;
 
         match result {
             Ok(result) => {
-                self.display_result(result.as_str().unwrap());
+                // Without anything to split the reason is in the message.
+                let message: Option<String> = result.message.clone();
+                let result: Option<PendingSplit> = self.unwrap_response(result)?;
+                match &result {
+                    Some(pending) => {
+                        self.display_result(&serde_json::to_string_pretty(pending).unwrap())
+                    }
+                    None => self.display_result(&message.unwrap_or_default()),
+                }
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_process_daemon_update(
+    pub async fn call_confirm_split(
         &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        token: String,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<Vec<String>>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.process_daemon_update(ctx) => { res1 }
-                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+                res1 = self.client.confirm_split(ctx, token) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call process_daemon_update"))
+        .instrument(tracing::info_span!("call confirm_split"))
         .await;
 
         match result {
             Ok(result) => {
-                if result.is_boolean() {
-                    self.display_result(&result.as_bool().unwrap().to_string());
-                } else {
-                    self.display_result(result.as_str().unwrap());
-                }
-
+                let result: Vec<String> = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_get_reward_options(
+    pub async fn call_get_events_since(
         &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        cursor: u64,
+        wait: u64,
+    ) -> Result<EventPage, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<EventPage>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.get_reward_options(ctx) => { res1 }
-                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+                res1 = self.client.get_events_since(ctx, cursor, wait) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call get_reward_options"))
+        .instrument(tracing::info_span!("call get_events_since"))
         .await;
 
         match result {
             Ok(result) => {
+                let result: EventPage = self.unwrap_response(result)?;
                 self.display_result(&serde_json::to_string_pretty(&result).unwrap());
                 Ok(result)
             }
@@ -514,144 +4210,123 @@ impl CLICaller {
         }
     }
 
-    pub async fn call_validate_address(
+    pub async fn call_get_digest_settings(
         &self,
-        addr: String,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<DigestSettings, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-
-        let daemon_check = tokio::select! {
-            res1 = self.client.get_daemon_online(ctx) => { res1 }
-            res2 = self.client.get_daemon_online(ctx) => { res2 }
-        };
-
-        match daemon_check {
-            Ok(result) => {
-                if result.is_object() {
-                    let res_obj = serde_json::to_string_pretty(&result).unwrap();
-                    let msg = format!("GhostVault Not Ready!\n{}", res_obj);
-                    self.display_result(&msg);
-                    return Ok(result);
-                }
-            }
-            Err(e) => return Err(e.into()),
-        }
-
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<DigestSettings>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.validate_address(ctx, addr) => { res1 }
+                res1 = self.client.get_digest_settings(ctx) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call validate_address"))
+        .instrument(tracing::info_span!("call get_digest_settings"))
         .await;
 
         match result {
             Ok(result) => {
-                let res = serde_json::to_string_pretty(&result).unwrap();
-                self.display_result(&res);
+                let result: DigestSettings = self.unwrap_response(result)?;
+                let settings: &DigestSettings = &result;
+
+                self.display_result(&format!(
+                    "Daily digest: {}\nWeekly digest: {}\nSent at: {:02}:00 {}",
+                    bool_to_yn(settings.daily),
+                    bool_to_yn(settings.weekly),
+                    settings.hour,
+                    settings.timezone
+                ));
+
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_get_pending_rewards(
+    pub async fn call_set_digest(
         &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        period: String,
+        enabled: bool,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-
-        let daemon_check = tokio::select! {
-            res1 = self.client.get_daemon_online(ctx) => { res1 }
-            res2 = self.client.get_daemon_online(ctx) => { res2 }
-        };
-
-        match daemon_check {
-            Ok(result) => {
-                if result.is_object() {
-                    let res_obj = serde_json::to_string_pretty(&result).unwrap();
-                    let msg = format!("GhostVault Not Ready!\n{}", res_obj);
-                    self.display_result(&msg);
-                    return Ok(result);
-                }
-            }
-            Err(e) => return Err(e.into()),
-        }
-
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<String>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.get_pending_rewards(ctx) => { res1 }
+                res1 = self.client.set_digest(ctx, period, enabled) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call get_pending_rewards"))
+        .instrument(tracing::info_span!("call set_digest"))
         .await;
 
         match result {
             Ok(result) => {
-                let res = serde_json::to_string_pretty(&result).unwrap();
-                self.display_result(&res);
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_process_reward_payout(
+    pub async fn call_set_digest_time(
         &self,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        hour: u32,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let _result: Result<(), client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<String>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.process_payouts(ctx) => { res1 }
-                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+                res1 = self.client.set_digest_time(ctx, hour) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call process_payouts"))
+        .instrument(tracing::info_span!("call set_digest_time"))
         .await;
 
-        Ok(())
+        match result {
+            Ok(result) => {
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
+                Ok(result)
+            }
+            Err(e) => Err(e.into()),
+        }
     }
 
-    pub async fn call_start_server_tasks(
+    pub async fn call_process_digest(
         &self,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<Vec<Digest>, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let _result: Result<(), client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<Vec<Digest>>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.start_server_tasks(ctx) => { res1 }
-                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+                res1 = self.client.process_digest(ctx) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call start_server_tasks"))
+        .instrument(tracing::info_span!("call process_digest"))
         .await;
 
-        Ok(())
+        match result {
+            Ok(result) => self.unwrap_response(result),
+            Err(e) => Err(e.into()),
+        }
     }
 
-    pub async fn call_get_version_info(
+    pub async fn call_lookup_tx(
         &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        txid: String,
+    ) -> Result<TxSummary, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<TxSummary>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.get_version_info(ctx) => { res1 }
+                res1 = self.client.lookup_tx(ctx, txid) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call get_version_info"))
+        .instrument(tracing::info_span!("call lookup_tx"))
         .await;
 
         match result {
             Ok(result) => {
+                let result: TxSummary = self.unwrap_response(result)?;
                 self.display_result(&serde_json::to_string_pretty(&result).unwrap());
                 Ok(result)
             }
@@ -659,23 +4334,23 @@ impl CLICaller {
         }
     }
 
-    pub async fn call_check_chain(
+    pub async fn call_lookup_block(
         &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        block: String,
+    ) -> Result<BlockSummary, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<BlockSummary>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.check_chain(ctx) => { res1 }
+                res1 = self.client.lookup_block(ctx, block) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call check_chain"))
+        .instrument(tracing::info_span!("call lookup_block"))
         .await;
 
         match result {
             Ok(result) => {
+                let result: BlockSummary = self.unwrap_response(result)?;
                 self.display_result(&serde_json::to_string_pretty(&result).unwrap());
                 Ok(result)
             }
@@ -683,179 +4358,171 @@ impl CLICaller {
         }
     }
 
-    pub async fn call_set_bot_announce(
+    pub async fn call_process_balance_sample(
         &self,
-        msg_type: String,
-        new_val: bool,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<()>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.set_bot_announce(ctx, msg_type, new_val) => { res1 }
-                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+                res1 = self.client.process_balance_sample(ctx) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call set_bot_announce"))
+        .instrument(tracing::info_span!("call process_balance_sample"))
         .await;
 
         match result {
-            Ok(result) => {
-                self.display_result(result.as_str().unwrap());
-                Ok(result)
-            }
+            Ok(result) => self.unwrap_response(result),
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_get_overview(
+    pub async fn call_get_balance_history(
         &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        start: u64,
+        end: u64,
+    ) -> Result<BalanceHistory, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<BalanceHistory>, client::RpcError> = async move {
+            tokio::select! {
+                res1 = self.client.get_balance_history(ctx, start, end) => { res1 }
+            }
+        }
+        .instrument(tracing::info_span!("call get_balance_history"))
+        .await;
 
-        let daemon_check = tokio::select! {
-            res1 = self.client.get_daemon_online(ctx) => { res1 }
-            res2 = self.client.get_daemon_online(ctx) => { res2 }
-        };
-
-        match daemon_check {
+        match result {
             Ok(result) => {
-                if result.is_object() {
-                    let res_obj = serde_json::to_string_pretty(&result).unwrap();
-                    let msg = format!("GhostVault Not Ready!\n{}", res_obj);
-                    self.display_result(&msg);
-                    return Ok(result);
-                }
+                let result: BalanceHistory = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string(&result).unwrap());
+                Ok(result)
             }
-            Err(e) => return Err(e.into()),
+            Err(e) => Err(e.into()),
         }
+    }
 
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+    pub async fn call_get_roi(
+        &self,
+    ) -> Result<StakingRoi, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx: Context = context::current();
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<StakingRoi>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.get_overview(ctx) => { res1 }
-                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+                res1 = self.client.get_roi(ctx) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call get_overview"))
+        .instrument(tracing::info_span!("call get_roi"))
         .await;
 
         match result {
             Ok(result) => {
-                let staking_data: StakingDataOverview =
-                    serde_json::from_value(result.to_owned()).unwrap();
-                self.display_result(&serde_json::to_string_pretty(&staking_data).unwrap());
+                let result: StakingRoi = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string_pretty(&result).unwrap());
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_get_mnemonic(
+    pub async fn call_get_stake_heatmap_data(
         &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        start: u64,
+        end: u64,
+    ) -> Result<StakeHeatmap, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<StakeHeatmap>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.get_mnemonic(ctx) => { res1 }
-                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+                res1 = self.client.get_stake_heatmap_data(ctx, start, end) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call get_mnemonic"))
+        .instrument(tracing::info_span!("call get_stake_heatmap_data"))
         .await;
 
         match result {
             Ok(result) => {
-                if result.is_string() {
-                    self.display_result(result.as_str().unwrap());
-                } else {
-                    self.display_result("Failed to retrieve mnemonic!");
-                }
+                let result: StakeHeatmap = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string(&result).unwrap());
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_import_wallet(
+    pub async fn call_get_stake_heatmap_data_tz(
         &self,
-        mnemonic: String,
-        wallet_name: String,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        start: u64,
+        end: u64,
+        timezone: String,
+    ) -> Result<StakeHeatmap, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
-        ctx.deadline = SystemTime::now() + time::Duration::from_secs(60 * 120);
-
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        ctx.deadline = SystemTime::now() + self.timeout;
+        let result: Result<GvReply<StakeHeatmap>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.import_wallet(ctx, mnemonic, wallet_name) => { res1 }
-                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+                res1 = self.client.get_stake_heatmap_data_tz(ctx, start, end, timezone) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call import_wallet"))
+        .instrument(tracing::info_span!("call get_stake_heatmap_data_tz"))
         .await;
 
         match result {
             Ok(result) => {
-                self.display_result(result.as_str().unwrap());
+                let result: StakeHeatmap = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string(&result).unwrap());
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_get_earnings_chart_data(
+    pub async fn call_get_stake_barchart_page(
         &self,
         start: u64,
         end: u64,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        division: String,
+        page: ChartPage,
+    ) -> Result<BarChart, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<BarChart>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.get_earnings_chart_data(ctx, start, end) => { res1 }
-                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+                res1 = self.client.get_stake_barchart_page(ctx, start, end, division, page) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call get_earnings_chart_data"))
+        .instrument(tracing::info_span!("call get_stake_barchart_page"))
         .await;
 
         match result {
             Ok(result) => {
-                self.display_result(result.to_string().as_str());
+                let result: BarChart = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string(&result).unwrap());
                 Ok(result)
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn call_get_stake_barchart_data(
+    pub async fn call_get_earnings_chart_page(
         &self,
         start: u64,
         end: u64,
-        division: String,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        page: ChartPage,
+    ) -> Result<AllTimeEarnigns, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let result: Result<Value, client::RpcError> = async move {
-            // Send the request twice, just to be safe! ;)
+        let result: Result<GvReply<AllTimeEarnigns>, client::RpcError> = async move {
             tokio::select! {
-                res1 = self.client.get_stake_barchart_data(ctx, start, end, division) => { res1 }
-                //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
+                res1 = self.client.get_earnings_chart_page(ctx, start, end, page) => { res1 }
             }
         }
-        .instrument(tracing::info_span!("call get_stake_heatmap_data"))
+        .instrument(tracing::info_span!("call get_earnings_chart_page"))
         .await;
 
         match result {
             Ok(result) => {
-                self.display_result(result.to_string().as_str());
+                let result: AllTimeEarnigns = self.unwrap_response(result)?;
+                self.display_result(&serde_json::to_string(&result).unwrap());
                 Ok(result)
             }
             Err(e) => Err(e.into()),
@@ -864,13 +4531,14 @@ impl CLICaller {
 
     pub async fn call_force_resync(
         &self,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        force: bool,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let result: Result<Value, client::RpcError> = async move {
+        let result: Result<GvReply<String>, client::RpcError> = async move {
             // Send the request twice, just to be safe! ;)
             tokio::select! {
-                res1 = self.client.force_resync(ctx) => { res1 }
+                res1 = self.client.force_resync(ctx, force) => { res1 }
                 //res2 = self.client.new_block(context::current(), new_block.clone()) => { res2 }
             }
         }
@@ -879,7 +4547,8 @@ impl CLICaller {
 
         match result {
             Ok(result) => {
-                self.display_result(result.as_str().unwrap());
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
                 Ok(result)
             }
             Err(e) => Err(e.into()),
@@ -889,10 +4558,10 @@ impl CLICaller {
     pub async fn call_set_timezone(
         &self,
         timezone: String,
-    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let mut ctx: Context = context::current();
         ctx.deadline = SystemTime::now() + self.timeout;
-        let result: Result<Value, client::RpcError> = async move {
+        let result: Result<GvReply<String>, client::RpcError> = async move {
             // Send the request twice, just to be safe! ;)
             tokio::select! {
                 res1 = self.client.set_timezone(ctx, timezone) => { res1 }
@@ -904,7 +4573,8 @@ impl CLICaller {
 
         match result {
             Ok(result) => {
-                self.display_result(result.as_str().unwrap());
+                let result: String = self.unwrap_response(result)?;
+                self.display_result(&result);
                 Ok(result)
             }
             Err(e) => Err(e.into()),
@@ -916,39 +4586,220 @@ impl CLICaller {
             println!("{}", result);
         }
     }
+
+    // Runs `method` (status, overview or version) against every vault in the fleet at once,
+    // a vault that can't be reached shows up with its error instead of failing the rest.
+    pub async fn call_fleet(
+        fleet: &FleetConfig,
+        method: &str,
+        json_out: bool,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        if !["status", "overview", "version"].contains(&method) {
+            return Err(format!("Unknown fleet method '{}'", method).into());
+        }
+
+        let calls = fleet.vaults.iter().map(|vault| async move {
+            let connect = tokio::time::timeout(
+                std::time::Duration::from_secs(10),
+                CLICaller::new(&vault.cli_address, true),
+            )
+            .await;
+
+            let caller: CLICaller = match connect {
+                Ok(Ok(caller)) => caller,
+                Ok(Err(err)) => return (vault, Err(err.to_string())),
+                Err(_) => return (vault, Err("Connection timed out".to_string())),
+            };
+
+            // The rows differ per method, so the fleet works on the plain JSON of each.
+            let result: Result<Value, Box<dyn std::error::Error + Send + Sync>> = match method {
+                "status" => caller.call_get_daemon_state().await.map(to_json),
+                "overview" => caller.call_get_overview().await.map(to_json),
+                _ => caller.call_get_version_info().await.map(to_json),
+            };
+
+            (vault, result.map_err(|err| err.to_string()))
+        });
+
+        let results: Vec<(&FleetVault, Result<Value, String>)> = join_all(calls).await;
+
+        if !json_out {
+            display_fleet_table(method, &results);
+        }
+
+        let fleet_res: Vec<Value> = results
+            .into_iter()
+            .map(|(vault, result)| match result {
+                Ok(result) => serde_json::json!({
+                    "name": vault.name,
+                    "cli_address": vault.cli_address,
+                    "result": result,
+                }),
+                Err(err) => serde_json::json!({
+                    "name": vault.name,
+                    "cli_address": vault.cli_address,
+                    "error": err,
+                }),
+            })
+            .collect();
+
+        Ok(Value::Array(fleet_res))
+    }
+}
+
+fn to_json<T: Serialize>(result: T) -> Value {
+    serde_json::to_value(result).unwrap_or_default()
+}
+
+fn fleet_row(method: &str, result: &Value) -> Option<Vec<String>> {
+    match method {
+        "status" => {
+            let status: GVStatus = serde_json::from_value(result.to_owned()).ok()?;
+            Some(vec![
+                status.daemon_synced,
+                status.best_block.to_string(),
+                status.daemon_peers.to_string(),
+                status.active_staking,
+                format!("{:.8}", status.currently_staking),
+                status.stakes_24.to_string(),
+                format!("{:.8}", status.total_24),
+                status.daemon_version,
+            ])
+        }
+        "overview" => {
+            let overview: StakingDataOverview = serde_json::from_value(result.to_owned()).ok()?;
+            Some(vec![
+                format!("{:.8}", overview.total_staking),
+                format!("{:.8}", overview.total_coldstaking),
+                format!("{:.8}", overview.stakes_24h.total),
+                format!("{:.8}", overview.stakes_7d.total),
+                format!("{:.8}", overview.stakes_30d.total),
+                format!("{:.8}", overview.stakes_all.total),
+            ])
+        }
+        _ => {
+            let field = |key: &str| -> String {
+                result
+                    .get(key)
+                    .and_then(|value| value.as_str())
+                    .unwrap_or("N/A")
+                    .to_string()
+            };
+            Some(vec![
+                field("gv_version"),
+                field("git_commit"),
+                field("ghostd_version"),
+                field("latest_release"),
+            ])
+        }
+    }
+}
+
+fn display_fleet_table(method: &str, results: &[(&FleetVault, Result<Value, String>)]) {
+    let headers: Vec<&str> = match method {
+        "status" => vec![
+            "VAULT",
+            "SYNCED",
+            "BLOCK",
+            "PEERS",
+            "STAKING",
+            "WEIGHT",
+            "STAKES 24H",
+            "EARNED 24H",
+            "GHOSTD",
+        ],
+        "overview" => vec![
+            "VAULT",
+            "STAKING",
+            "COLDSTAKING",
+            "24H",
+            "7D",
+            "30D",
+            "ALL TIME",
+        ],
+        _ => vec!["VAULT", "GHOSTVAULT", "COMMIT", "GHOSTD", "LATEST"],
+    };
+
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .map(|(vault, result)| {
+            let mut row: Vec<String> = vec![vault.name.clone()];
+
+            match result {
+                Ok(result) => match fleet_row(method, result) {
+                    Some(cells) => row.extend(cells),
+                    None => row.push("Not ready".to_string()),
+                },
+                Err(err) => row.push(format!("Error: {}", err)),
+            }
+
+            row
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.len()).collect();
+    for row in &rows {
+        // Error rows only have two cells, don't let a long message stretch a column.
+        if row.len() != headers.len() {
+            continue;
+        }
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let format_row = |cells: Vec<String>| -> String {
+        cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| format!("{:<width$}", cell, width = widths.get(i).unwrap_or(&0)))
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!(
+        "{}",
+        format_row(headers.iter().map(|header| header.to_string()).collect()).bold()
+    );
+    for row in rows {
+        let ok: bool = row.len() == headers.len();
+        let line: String = format_row(row);
+        if ok {
+            println!("{}", line);
+        } else {
+            println!("{}", line.red());
+        }
+    }
 }
 
-fn display_stats_page(gv_info: &Value) {
+fn display_stats_page(gv_info: &GVStatus) {
     clear_screen();
     let border = "#".repeat(80).blue();
-    let status: GVStatus = serde_json::from_value(gv_info.to_owned()).unwrap();
+    let status: GVStatus = gv_info.to_owned();
     let privacy_mode = if status.privacy_mode == "ANON" {
         status.privacy_mode.green()
     } else {
         status.privacy_mode.yellow()
     };
 
-    let curr_ver_int = status
-        .daemon_version
-        .replace(".", "")
-        .parse::<u64>()
-        .unwrap();
-
-    let latest_version_int = status
-        .latest_release
-        .replace(".", "")
-        .parse::<u64>()
-        .unwrap();
+    // None when either version can't be read, a release tag ghostd doesn't use shouldn't pass
+    // for up to date or outdated.
+    let node_up_to_date: Option<bool> =
+        gv_methods::compare_versions(&status.daemon_version, &status.latest_release)
+            .map(|ordering| ordering != std::cmp::Ordering::Less);
 
-    let node_up_to_date = curr_ver_int >= latest_version_int;
-
-    let current_ver = if node_up_to_date {
-        status.daemon_version.green()
-    } else {
-        status.daemon_version.red()
+    let current_ver = match node_up_to_date {
+        Some(true) => status.daemon_version.green(),
+        Some(false) => status.daemon_version.red(),
+        None => status.daemon_version.yellow(),
     };
 
-    let up_to_date = bool_to_yn(node_up_to_date);
+    let up_to_date = match node_up_to_date {
+        Some(up_to_date) => color_yn(bool_to_yn(up_to_date)),
+        None => "UNKNOWN".yellow(),
+    };
 
     let peers = if status.daemon_peers <= 2 {
         status.daemon_peers.to_string().red()
@@ -984,28 +4835,43 @@ fn display_stats_page(gv_info: &Value) {
 
     let version = format!("v{}", VERSION);
 
+    let sync_line: String = match &status.sync_progress {
+        Some(progress) => format!(
+            "ghostd sync progress {:>44}\n",
+            format!(
+                "{}/{} ETA {}",
+                progress.blocks, progress.headers, progress.eta
+            )
+            .yellow()
+        ),
+        None => String::new(),
+    };
+
     let formatted_string = format!(
-        "\n{}\nGhostVaultRS {}\nUptime/Load Average {:>45}\nprivacy mode {:>52}\nghostd version {:>50}\nghostd up-to-date {:>47}\nghostd running {:>50}\nghostd uptime {:>51}\nghostd responding (RPC) {:>41}\nghostd peers {:>52}\nghostd blocks synced {:>44}\nlast block (local ghostd) {:>39}\n   (SHELTRPointe network) {:>39}\nghostd is good chain {:>44}\nghostd staking enabled {:>42}\nghostd staking currently? {:>39}\nghostd staking difficulty {:>39}\nghostd network stakeweight {:>38}\ncurrently staking {:>47}\ntotal in coldstaking {:>44}\nstakes/earned last 24h {:>30}/{}\n{}",
+        "\n{}\nGhostVaultRS {}\nUptime/Load Average {:>45}\nprivacy mode {:>52}\nghostd version {:>50}\nghostd up-to-date {:>47}\nghostd running {:>50}\nghostd uptime {:>51}\nghostd responding (RPC) {:>41}\nghostd peers {:>52}\nghostd blocks synced {:>44}\n{}last block (local ghostd) {:>39}\n   (SHELTRPointe network) {:>39}\nghostd is good chain {:>44}\nchain verified by {:>47}\nghostd staking enabled {:>42}\nghostd staking currently? {:>39}\nghostd staking difficulty {:>39}\nghostd network stakeweight {:>38}\ncurrently staking {:>47}\ntotal in coldstaking {:>44}\nexpected time to stake {:>42}\nstakes/earned last 24h {:>30}/{}\n{}",
         border,
         version,
         status.uptime.green(),
         privacy_mode,
         current_ver,
-        color_yn(up_to_date),
+        up_to_date,
         "YES".green(),
         status.daemon_uptime.green(),
         "YES".green(),
         peers,
         color_yn(status.daemon_synced),
+        sync_line,
         status.best_block.to_string().green(),
         status.best_block_extern.to_string().green(),
         color_yn(status.good_chain),
+        status.chain_check.green(),
         color_yn(status.staking_enabled),
         color_yn(status.active_staking),
         status.staking_difficulty.to_string().green(),
         status.network_stake_weight.to_string().green(),
         current_staking,
         total_cold,
+        status.expected_stake_time.green(),
         stakes,
         earned,
         border
@@ -1014,6 +4880,43 @@ fn display_stats_page(gv_info: &Value) {
     println!("{}", formatted_string);
 }
 
+// A 100 character stealth address doesn't fit in a message line, the ends are enough to tell
+// addresses apart.
+pub fn short_address(address: &str) -> String {
+    let chars: Vec<char> = address.chars().collect();
+
+    if chars.len() <= 20 {
+        return address.to_string();
+    }
+
+    format!(
+        "{}...{}",
+        chars[..10].iter().collect::<String>(),
+        chars[chars.len() - 8..].iter().collect::<String>()
+    )
+}
+
+// An address's book label with a shortened address, or the address itself when it has none.
+pub fn labeled_address(address: &str, label: Option<&str>) -> String {
+    match label {
+        Some(label) => format!("{} ({})", label, short_address(address)),
+        None => address.to_string(),
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size: f64 = bytes as f64;
+    let mut unit: usize = 0;
+
+    while size >= 1000.0 && unit < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit += 1;
+    }
+
+    format!("{:.2} {}", size, UNITS[unit])
+}
+
 fn bool_to_yn(bool_val: bool) -> String {
     let new_val: &str = if bool_val { "YES" } else { "NO" };
     new_val.to_string()
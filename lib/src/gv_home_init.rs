@@ -93,14 +93,17 @@ fn create_settings(path: &PathBuf) -> std::io::Result<()> {
         disable_legacy_cron()?;
 
         format!(
-            "{}\nANNOUNCE_ZAPS = true\nANNOUNCE_STAKES = true\nTIMEZONE = \"UTC\"\nANNOUNCE_REWARDS = true\nCLI_ADDRESS = \"127.0.0.1:50051\"\n{}\n{}\n{}\n{}\n{}\nTELOXIDE_TOKEN = \"\"\nTELEGRAM_USER = \"\"\nDAEMON_PATH = \"\"\nDAEMON_HASH = \"\"\nMIN_REWARD_PAYOUT = 10000000\nMNEMONIC = \"\"\nREWARD_INTERVAL = 900\n{}\n",
+            "{}\nRPC_WALLET_HOT = \"\"\nANNOUNCE_ZAPS = true\nANNOUNCE_STAKES = true\nTIMEZONE = \"UTC\"\nANNOUNCE_REWARDS = true\nCLI_ADDRESS = \"127.0.0.1:50051\"\nCLI_TLS_CERT = \"\"\nCLI_TLS_KEY = \"\"\n{}\n{}\n{}\n{}\n{}\nTELOXIDE_TOKEN = \"\"\nTELEGRAM_USER = \"\"\nDAEMON_PATH = \"\"\nDAEMON_HASH = \"\"\nSYSTEM_DAEMON = false\nCONFIRM_DAEMON_UPDATE = false\nUPDATE_CHANNEL = \"STABLE\"\nVAULT_NAME = \"local\"\nBOT_RELAY = false\nTG_VAULTS = []\nTG_CHANNEL = \"\"\nANNOUNCE_WEBHOOK_URL = \"\"\nANNOUNCE_EMAIL = \"\"\nDB_PATH = \"\"\nMIN_REWARD_PAYOUT = 10000000\nMNEMONIC = \"\"\nSEALED_MNEMONIC = \"\"\nREWARD_INTERVAL = 900\nMAINT_WINDOW_START = 2\nMAINT_WINDOW_END = 5\nBACKUP_DIR = \"\"\nBACKUP_INTERVAL = 86400\nBACKUP_RETENTION = 7\nBACKUP_REMOTE_URL = \"\"\nBACKUP_S3_ENDPOINT = \"\"\nBACKUP_S3_REGION = \"\"\nBACKUP_REMOTE_ACCESS_KEY = \"\"\nBACKUP_REMOTE_SECRET_KEY = \"\"\nBACKUP_SFTP_KEY = \"\"\nBACKUP_REMOTE_RETENTION = 30\nCHAIN_SPLIT_TIMEOUT = 1800\nCACHE_TTL_BLOCKCHAININFO = 5\nCACHE_TTL_STAKINGINFO = 10\nCACHE_TTL_COLDSTAKINGINFO = 10\nCACHE_TTL_NETWORKINFO = 30\nEXPLORER_APIS = []\nEXPLORER_TX_URL = \"\"\nEXPLORER_BLOCK_URL = \"\"\nDAEMON_MIRRORS = []\nDAEMON_VERIFY = \"NONE\"\nDAEMON_SIGNING_KEY = \"\"\nDAEMON_PRUNE = 0\nBOOTSTRAP_URL = \"\"\nBOOTSTRAP_SHA256 = \"\"\nBOOTSTRAP_ON_RESYNC = false\nLOCAL_CHAIN_CHECK = false\nCHAIN_PEERS = []\nMISSED_STAKE_FACTOR = 3\nSTAKE_CONFIRMATIONS = 100\nZAP_CONFIRMATIONS = 225\nPAYOUT_SAFE_DEPTH = 20\nBALANCE_SAMPLE_INTERVAL = 3600\nDISK_ALERT_GB = 5\nDOCKER_MAX_RESTARTS = 5\nLOG_LEVEL = \"info\"\nLOG_MODULES = {{}}\nLOG_FILE_SIZE_MB = 10\nLOG_FILE_COUNT = 3\nLOG_JSON = false\nHOOKS = {{}}\nHOOK_TIMEOUT = 30\nFEE_POLICY = \"FIXED\"\nFEE_RATE = 7500\nFEE_CONF_TARGET = 6\nMAX_TX_FEE = 25000000\nSPLIT_UTXOS = false\nSPLIT_THRESHOLD = 200000000000\nSPLIT_COUNT = 4\nSPLIT_INTERVAL = 86400\nANON_FLUSH = true\nANON_FLUSH_THRESHOLD = 10000000\nANON_FLUSH_INTERVAL = 0\nANON_RING_SIZE = 12\nANON_BATCH_SIZE = 0\nWATCH_ONLY = false\nEVENTS_ADDRESS = \"\"\nGRPC_ADDRESS = \"\"\nDIGEST_DAILY = false\nDIGEST_WEEKLY = false\nDIGEST_HOUR = 8\nREWARD_ADDRESS_WHITELIST = []\nREWARD_CHANGE_DELAY = 0\nPAYOUT_LABEL = \"\"\nCONFIG_SALT = \"\"\nCONFIG_CHECK = \"\"\nRPC_AUTH = false\n{}\n",
             wallet, ext_pk, ext_pk_label, reward_addr, anon_mode, anon_mode_reward, internal_anon
         )
     } else {
         info!("Legacy GhostVault install not found...");
         concat!(
             "RPC_WALLET = \"\"\n",
+            "RPC_WALLET_HOT = \"\"\n",
             "CLI_ADDRESS = \"127.0.0.1:50051\"\n",
+            "CLI_TLS_CERT = \"\"\n",
+            "CLI_TLS_KEY = \"\"\n",
             "EXT_PUB_KEY = \"\"\n",
             "EXT_PUB_KEY_LABEL = \"\"\n",
             "REWARD_ADDRESS = \"\"\n",
@@ -110,14 +113,93 @@ fn create_settings(path: &PathBuf) -> std::io::Result<()> {
             "TELEGRAM_USER = \"\"\n",
             "DAEMON_PATH = \"\"\n",
             "DAEMON_HASH = \"\"\n",
+            "SYSTEM_DAEMON = false\n",
+            "CONFIRM_DAEMON_UPDATE = false\n",
+            "UPDATE_CHANNEL = \"STABLE\"\n",
+            "VAULT_NAME = \"local\"\n",
+            "BOT_RELAY = false\n",
+            "TG_VAULTS = []\n",
+            "TG_CHANNEL = \"\"\n",
+            "ANNOUNCE_WEBHOOK_URL = \"\"\n",
+            "ANNOUNCE_EMAIL = \"\"\n",
+            "DB_PATH = \"\"\n",
             "INTERNAL_ANON = \"\"\n",
             "MIN_REWARD_PAYOUT = 10000000\n",
             "MNEMONIC = \"\"\n",
+            "SEALED_MNEMONIC = \"\"\n",
             "REWARD_INTERVAL = 900\n",
             "ANNOUNCE_REWARDS = true\n",
             "ANNOUNCE_STAKES = true\n",
             "ANNOUNCE_ZAPS = true\n",
             "TIMEZONE = \"UTC\"\n",
+            "MAINT_WINDOW_START = 2\n",
+            "MAINT_WINDOW_END = 5\n",
+            "BACKUP_DIR = \"\"\n",
+            "BACKUP_INTERVAL = 86400\n",
+            "BACKUP_RETENTION = 7\n",
+            "BACKUP_REMOTE_URL = \"\"\n",
+            "BACKUP_S3_ENDPOINT = \"\"\n",
+            "BACKUP_S3_REGION = \"\"\n",
+            "BACKUP_REMOTE_ACCESS_KEY = \"\"\n",
+            "BACKUP_REMOTE_SECRET_KEY = \"\"\n",
+            "BACKUP_SFTP_KEY = \"\"\n",
+            "BACKUP_REMOTE_RETENTION = 30\n",
+            "CHAIN_SPLIT_TIMEOUT = 1800\n",
+            "CACHE_TTL_BLOCKCHAININFO = 5\n",
+            "CACHE_TTL_STAKINGINFO = 10\n",
+            "CACHE_TTL_COLDSTAKINGINFO = 10\n",
+            "CACHE_TTL_NETWORKINFO = 30\n",
+            "EXPLORER_APIS = []\n",
+            "EXPLORER_TX_URL = \"\"\n",
+            "EXPLORER_BLOCK_URL = \"\"\n",
+            "DAEMON_MIRRORS = []\n",
+            "DAEMON_VERIFY = \"NONE\"\n",
+            "DAEMON_SIGNING_KEY = \"\"\n",
+            "DAEMON_PRUNE = 0\n",
+            "BOOTSTRAP_URL = \"\"\n",
+            "BOOTSTRAP_SHA256 = \"\"\n",
+            "BOOTSTRAP_ON_RESYNC = false\n",
+            "LOCAL_CHAIN_CHECK = false\n",
+            "CHAIN_PEERS = []\n",
+            "MISSED_STAKE_FACTOR = 3\n",
+            "STAKE_CONFIRMATIONS = 100\n",
+            "ZAP_CONFIRMATIONS = 225\n",
+            "PAYOUT_SAFE_DEPTH = 20\n",
+            "BALANCE_SAMPLE_INTERVAL = 3600\n",
+            "DISK_ALERT_GB = 5\n",
+            "DOCKER_MAX_RESTARTS = 5\n",
+            "LOG_LEVEL = \"info\"\n",
+            "LOG_MODULES = {}\n",
+            "LOG_FILE_SIZE_MB = 10\n",
+            "LOG_FILE_COUNT = 3\n",
+            "LOG_JSON = false\n",
+            "HOOKS = {}\n",
+            "HOOK_TIMEOUT = 30\n",
+            "FEE_POLICY = \"FIXED\"\n",
+            "FEE_RATE = 7500\n",
+            "FEE_CONF_TARGET = 6\n",
+            "MAX_TX_FEE = 25000000\n",
+            "SPLIT_UTXOS = false\n",
+            "SPLIT_THRESHOLD = 200000000000\n",
+            "SPLIT_COUNT = 4\n",
+            "SPLIT_INTERVAL = 86400\n",
+            "ANON_FLUSH = true\n",
+            "ANON_FLUSH_THRESHOLD = 10000000\n",
+            "ANON_FLUSH_INTERVAL = 0\n",
+            "ANON_RING_SIZE = 12\n",
+            "ANON_BATCH_SIZE = 0\n",
+            "WATCH_ONLY = false\n",
+            "EVENTS_ADDRESS = \"\"\n",
+            "GRPC_ADDRESS = \"\"\n",
+            "DIGEST_DAILY = false\n",
+            "DIGEST_WEEKLY = false\n",
+            "DIGEST_HOUR = 8\n",
+            "REWARD_ADDRESS_WHITELIST = []\n",
+            "REWARD_CHANGE_DELAY = 0\n",
+            "PAYOUT_LABEL = \"\"\n",
+            "CONFIG_SALT = \"\"\n",
+            "CONFIG_CHECK = \"\"\n",
+            "RPC_AUTH = false\n",
         )
         .to_string()
     };
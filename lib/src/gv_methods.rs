@@ -1,28 +1,32 @@
 #![allow(dead_code)]
 use crate::{
-    constants::{DAEMON_BASE_URL, LATEST_RELEASE_URL, TMP_PATH},
+    config::{BootstrapSource, DaemonSource, DaemonVerify, UpdateChannel},
+    constants::{GPGV_PATH, LATEST_RELEASE_URL, RELEASES_API_URL, TMP_PATH, VERSION},
     file_ops,
 };
 use data_encoding::HEXLOWER;
 use flate2::read::GzDecoder;
 use indicatif::{ProgressBar, ProgressStyle};
-use log::{error, info};
-use reqwest::{header::CONTENT_LENGTH, Client, Response};
-use serde_json::Value;
+use log::{error, info, warn};
+use minisign_verify::{PublicKey, Signature};
+use reqwest::{
+    header::{CONTENT_LENGTH, RANGE},
+    Client, Response, StatusCode,
+};
 use sha2::{Digest, Sha256};
 use std::{
+    cmp::Ordering,
     env,
-    fs::File,
+    fs::{self, File},
     io::{BufRead, BufReader, Read},
     path::PathBuf,
+    process::Command,
+    time::{Duration, SystemTime},
 };
 use tar::Archive;
 use tokio::io::AsyncWriteExt;
 use walkdir::WalkDir;
 
-use futures::future::select_ok;
-use futures_util::FutureExt;
-
 pub struct PathAndDigest {
     pub daemon_path: PathBuf,
     pub daemon_hash: String,
@@ -32,6 +36,17 @@ pub async fn download_file(
     url: &str,
     file_name: &str,
     with_progress: bool,
+) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    download_file_reporting(url, file_name, with_progress, |_, _| {}).await
+}
+
+// download_file that also hands on_progress the bytes downloaded and the total after every
+// chunk, the total is 0 when the server doesn't send a length.
+pub async fn download_file_reporting(
+    url: &str,
+    file_name: &str,
+    with_progress: bool,
+    on_progress: impl Fn(u64, u64),
 ) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
     // Create a progress bar
     let mut progress_bar: Option<ProgressBar> = if with_progress {
@@ -46,11 +61,25 @@ pub async fn download_file(
         None
     };
 
+    // Downloads land in a .part file first, so an interrupted download can be resumed
+    // and a half written file is never mistaken for a finished one.
+    let part_path: PathBuf = PathBuf::from(format!("{}.part", file_name));
+    let resume_from: u64 = fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
+
     // Create a reqwest client
     let client: Client = Client::new();
 
+    let request = || {
+        let request = client.get(url);
+        if resume_from > 0 {
+            request.header(RANGE, format!("bytes={}-", resume_from))
+        } else {
+            request
+        }
+    };
+
     // Send HTTP GET request
-    let mut response: Result<Response, reqwest::Error> = client.get(url).send().await;
+    let mut response: Result<Response, reqwest::Error> = request().send().await;
 
     // Retry the request if it fails
     let mut retries: u8 = 0;
@@ -61,7 +90,7 @@ pub async fn download_file(
         );
         tokio::time::sleep(std::time::Duration::from_secs(1)).await;
         retries += 1;
-        response = client.get(url).send().await;
+        response = request().send().await;
     }
 
     // Check if the request failed
@@ -73,6 +102,12 @@ pub async fn download_file(
 
     let mut response: Response = response.unwrap();
 
+    // The .part file already holds the whole thing, the caller verifies the checksum.
+    if resume_from > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        fs::rename(&part_path, file_name)?;
+        return Ok(PathBuf::from(&file_name).canonicalize()?);
+    }
+
     // Check if the request was successful
     if !response.status().is_success() {
         return Err(format!(
@@ -82,19 +117,36 @@ pub async fn download_file(
         .into());
     }
 
+    // The server may ignore the range and send the whole file, start over if so.
+    let resumed: bool = resume_from > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+    if resumed {
+        info!(
+            "Resuming download of {} at {} bytes",
+            file_name, resume_from
+        );
+    }
+
     // Get the content length to determine the total file size
-    let total_size: u64 = response
+    let content_length: u64 = response
         .headers()
         .get(CONTENT_LENGTH)
         .and_then(|v: &hyper::header::HeaderValue| v.to_str().ok())
         .and_then(|s: &str| s.parse().ok())
         .unwrap_or(0);
 
+    let mut downloaded_size: u64 = if resumed { resume_from } else { 0 };
+    let total_size: u64 = content_length + downloaded_size;
+
     // Create or open the destination file
-    let mut file: tokio::fs::File = tokio::fs::File::create(file_name).await?;
+    let mut file: tokio::fs::File = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&part_path)
+        .await?;
 
     // Stream the response and write to the file with progress
-    let mut downloaded_size: u64 = 0;
     while let Some(chunk) = response.chunk().await? {
         let chunk_size: u64 = chunk.len() as u64;
         downloaded_size += chunk_size;
@@ -107,19 +159,40 @@ pub async fn download_file(
 
         // Write the chunk to the file
         file.write_all(&chunk).await?;
+
+        on_progress(
+            downloaded_size,
+            if content_length > 0 { total_size } else { 0 },
+        );
     }
 
+    file.flush().await?;
+    drop(file);
+
     if let Some(pb) = &mut progress_bar {
         // Finish the progress bar
         pb.finish_with_message("Download complete");
     }
 
+    fs::rename(&part_path, file_name)?;
+
     let full_path: PathBuf = PathBuf::from(&file_name).canonicalize()?;
 
     Ok(full_path)
 }
 
-pub async fn get_latest_release() -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+// The release the update channel points at, a pinned version is returned as it is.
+pub async fn get_latest_release(
+    channel: &UpdateChannel,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    match channel {
+        UpdateChannel::Stable => get_stable_release().await,
+        UpdateChannel::Testing => get_testing_release().await,
+        UpdateChannel::Pinned(version) => Ok(version.clone()),
+    }
+}
+
+async fn get_stable_release() -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
     let client: Client = Client::new();
     let response: Result<Response, reqwest::Error> = client.get(LATEST_RELEASE_URL).send().await;
 
@@ -141,14 +214,97 @@ pub async fn get_latest_release() -> Result<String, Box<dyn std::error::Error +
     Ok(version)
 }
 
-pub async fn download_daemon() -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
-    let latest_version: String = get_latest_release().await?;
+// The newest release including release candidates, GitHub lists them newest first.
+async fn get_testing_release() -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let client: Client = Client::new();
+    let releases: Vec<serde_json::Value> = client
+        .get(RELEASES_API_URL)
+        .header("User-Agent", format!("GhostVault/{}", VERSION))
+        .send()
+        .await
+        .map_err(|err| format!("Failed to get latest release: {}", err))?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let version: String = releases
+        .iter()
+        .find(|release| !release["draft"].as_bool().unwrap_or(false))
+        .and_then(|release| release["tag_name"].as_str())
+        .map(|tag| tag.trim_start_matches('v').to_string())
+        .ok_or("No ghostd releases found")?;
+
+    Ok(version)
+}
+
+// A ghostd version as its numbers and release candidate, a final release sorts after its
+// candidates. Trailing .0 parts are dropped, ghostd leaves them off.
+pub fn parse_version(version: &str) -> Option<(Vec<u64>, u64)> {
+    let version: &str = version.trim().trim_start_matches('v');
+    let split: usize = version
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(version.len());
+    let (numbers, suffix) = version.split_at(split);
+
+    let mut parts: Vec<u64> = numbers
+        .split('.')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<Vec<u64>>>()?;
+
+    while parts.len() > 1 && parts.last() == Some(&0) {
+        parts.pop();
+    }
+
+    let candidate: u64 = if suffix.is_empty() {
+        u64::MAX
+    } else {
+        suffix
+            .trim_start_matches(|c: char| c.is_ascii_alphabetic())
+            .parse::<u64>()
+            .unwrap_or(0)
+    };
+
+    Some((parts, candidate))
+}
+
+pub fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    Some(parse_version(a)?.cmp(&parse_version(b)?))
+}
+
+// Tries each mirror in turn, the archive is only returned once it matches the release
+// checksums, and those are signed by DAEMON_SIGNING_KEY when DAEMON_VERIFY asks for it.
+pub async fn download_daemon(
+    source: &DaemonSource,
+) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    let latest_version: String = get_latest_release(&source.channel).await?;
+
+    let mut last_err: Box<dyn std::error::Error + Send + Sync> = "No daemon mirrors set".into();
+
+    for mirror in source.mirrors.iter() {
+        match download_from_mirror(mirror, &latest_version, source).await {
+            Ok(download_path) => return Ok(download_path),
+            Err(err) => {
+                warn!("Unable to get ghostd from {}: {}", mirror, err);
+                last_err = err;
+            }
+        }
+    }
+
+    Err(last_err)
+}
+
+async fn download_from_mirror(
+    mirror: &str,
+    latest_version: &str,
+    source: &DaemonSource,
+) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    let release_url: String = format!("{}/v{}", mirror.trim_end_matches('/'), latest_version);
 
     let tripple: String = get_tripple();
 
     let download_url: String = format!(
-        "{}v{}/ghost-{}-{}.tar.gz",
-        DAEMON_BASE_URL, latest_version, latest_version, tripple
+        "{}/ghost-{}-{}.tar.gz",
+        release_url, latest_version, tripple
     );
 
     let file_name: String = format!("ghost-{}-{}.tar.gz", latest_version, tripple);
@@ -156,10 +312,7 @@ pub async fn download_daemon() -> Result<PathBuf, Box<dyn std::error::Error + Se
     // download the hashes.txt file
 
     let file_name_hashes: String = "hashes.txt".to_string();
-    let hashes_url: String = format!(
-        "{}v{}/{}",
-        DAEMON_BASE_URL, latest_version, file_name_hashes
-    );
+    let hashes_url: String = format!("{}/{}", release_url, file_name_hashes);
 
     let tmp_path: PathBuf = PathBuf::from(TMP_PATH);
 
@@ -187,6 +340,23 @@ pub async fn download_daemon() -> Result<PathBuf, Box<dyn std::error::Error + Se
         error!("Failed to download hashes")
     }
 
+    if let Some(sig_ext) = signature_extension(source.verify) {
+        let sig_url: String = format!("{}.{}", hashes_url, sig_ext);
+        let sig_file: String = format!("{}.{}", file_name_hashes_vers, sig_ext);
+
+        let verified = match download_file(&sig_url, &sig_file, false).await {
+            Ok(sig_path) => verify_hashes_signature(&dl_hashes, &sig_path, source),
+            Err(err) => Err(err),
+        };
+
+        // A cached hashes.txt that doesn't check out can't be used with the next mirror either
+        if let Err(err) = verified {
+            let _ = file_ops::rm_file(&dl_hashes);
+            let _ = file_ops::rm_file(&PathBuf::from(&sig_file));
+            return Err(format!("Release checksums failed verification: {}", err).into());
+        }
+    }
+
     let file_path: PathBuf =
         PathBuf::from(format!("{}/{}", tmp_path.to_string_lossy(), &file_name));
 
@@ -206,15 +376,275 @@ pub async fn download_daemon() -> Result<PathBuf, Box<dyn std::error::Error + Se
     )
     .await?;
 
+    // Never hand back an archive that doesn't match the release hashes, drop it so the
+    // next attempt starts clean. Unsigned hashes may be what's wrong, so they go too.
+    if !compare_digest_daemon(&download_path, &dl_hashes)? {
+        file_ops::rm_file(&download_path)?;
+
+        if source.verify == DaemonVerify::None {
+            file_ops::rm_file(&dl_hashes)?;
+        }
+
+        return Err(format!("Checksum mismatch for {}", file_name).into());
+    }
+
     Ok(download_path)
 }
 
+// Downloads the bootstrap into TMP_PATH and checks it against BOOTSTRAP_SHA256, or the digest
+// published next to it. One that doesn't match is deleted so the next try starts over.
+pub async fn download_bootstrap(
+    source: &BootstrapSource,
+    on_progress: impl Fn(u64, u64),
+) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    let tmp_path: PathBuf = PathBuf::from(TMP_PATH);
+
+    if !tmp_path.exists() {
+        file_ops::create_dir(&tmp_path)?;
+    }
+
+    let expected: String = if source.sha256.is_empty() {
+        let sha_file: String = format!("{}/bootstrap.tar.gz.sha256", tmp_path.to_string_lossy());
+        let sha_path: PathBuf =
+            download_file(&format!("{}.sha256", source.url), &sha_file, false).await?;
+        let contents: String = fs::read_to_string(&sha_path)?;
+        file_ops::rm_file(&sha_path)?;
+
+        contents
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_lowercase()
+    } else {
+        source.sha256.clone()
+    };
+
+    if expected.len() != 64 || !expected.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("No valid SHA256 checksum for the bootstrap".into());
+    }
+
+    let file_path: PathBuf = tmp_path.join("bootstrap.tar.gz");
+
+    // Hashing several GB would hold up the runtime
+    let digest = |path: PathBuf| tokio::task::spawn_blocking(move || sha256_digest(&path));
+
+    if file_path.exists() && digest(file_path.clone()).await?? == expected {
+        return Ok(file_path);
+    }
+
+    let download_path: PathBuf = download_file_reporting(
+        &source.url,
+        file_path.as_os_str().to_str().unwrap(),
+        false,
+        on_progress,
+    )
+    .await?;
+
+    if digest(download_path.clone()).await?? != expected {
+        file_ops::rm_file(&download_path)?;
+        return Err("Checksum mismatch for the bootstrap".into());
+    }
+
+    Ok(download_path)
+}
+
+// Moves the bootstrap's blocks/ and chainstate/ into the data dir, ghostd has to be stopped.
+// They may sit under a top level folder in the archive, anything else in it is left out.
+pub fn install_bootstrap(
+    archive_path: &PathBuf,
+    daemon_data_dir: &PathBuf,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    info!("Extracting the blockchain bootstrap...");
+    // Unpacked next to the live dirs, so moving them in is a rename on the same disk
+    let staging_dir: PathBuf = daemon_data_dir.join("bootstrap.tmp");
+
+    if staging_dir.exists() {
+        file_ops::rm_dir(&staging_dir)?;
+    }
+
+    let tar_gz: File = File::open(archive_path)?;
+    let tar: GzDecoder<File> = GzDecoder::new(tar_gz);
+    let mut archive: Archive<GzDecoder<File>> = Archive::new(tar);
+    archive.unpack(&staging_dir)?;
+
+    let chain_dir: PathBuf = WalkDir::new(&staging_dir)
+        .max_depth(3)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .find(|entry| entry.file_type().is_dir() && entry.file_name() == "chainstate")
+        .and_then(|entry| entry.path().parent().map(|dir| dir.to_path_buf()))
+        .filter(|dir| dir.join("blocks").is_dir())
+        .ok_or("The bootstrap has no blocks/ and chainstate/")?;
+
+    for dir in ["blocks", "chainstate"] {
+        let target: PathBuf = daemon_data_dir.join(dir);
+        file_ops::rm_dir(&target)?;
+        fs::rename(chain_dir.join(dir), &target)?;
+    }
+
+    file_ops::rm_dir(&staging_dir)?;
+
+    Ok(())
+}
+
+// Checks an archive that was downloaded by hand, for vaults that can't reach the mirrors.
+// hashes.txt, and its signature when DAEMON_VERIFY needs one, must sit next to the archive.
+pub fn verify_daemon_archive(
+    archive_path: &PathBuf,
+    source: &DaemonSource,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if !archive_path.is_file() {
+        return Err(format!("{} not found", archive_path.display()).into());
+    }
+
+    let release_dir: PathBuf = archive_path
+        .parent()
+        .map(|dir| dir.to_path_buf())
+        .unwrap_or_default();
+    let hashes_path: PathBuf = release_dir.join("hashes.txt");
+
+    if !hashes_path.is_file() {
+        return Err(format!("hashes.txt not found in {}", release_dir.display()).into());
+    }
+
+    match signature_extension(source.verify) {
+        Some(sig_ext) => {
+            let sig_path: PathBuf = release_dir.join(format!("hashes.txt.{}", sig_ext));
+
+            if !sig_path.is_file() {
+                return Err(format!("{} not found", sig_path.display()).into());
+            }
+
+            verify_hashes_signature(&hashes_path, &sig_path, source)?;
+        }
+        None => warn!(
+            "DAEMON_VERIFY is NONE, only checking {} against hashes.txt",
+            archive_path.display()
+        ),
+    }
+
+    if !compare_digest_daemon(archive_path, &hashes_path)? {
+        return Err(format!("Checksum mismatch for {}", archive_path.display()).into());
+    }
+
+    Ok(())
+}
+
+// The ghostd version in a release archive name, ghost-<version>-<triple>.tar.gz
+pub fn archive_version(archive_path: &PathBuf) -> Option<String> {
+    archive_path
+        .file_name()?
+        .to_str()?
+        .strip_prefix("ghost-")?
+        .split('-')
+        .next()
+        .map(|version| version.to_string())
+}
+
+fn signature_extension(verify: DaemonVerify) -> Option<&'static str> {
+    match verify {
+        DaemonVerify::None => None,
+        DaemonVerify::Minisign => Some("minisig"),
+        DaemonVerify::Gpg => Some("asc"),
+    }
+}
+
+fn verify_hashes_signature(
+    hashes_path: &PathBuf,
+    sig_path: &PathBuf,
+    source: &DaemonSource,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if source.signing_key.is_empty() {
+        return Err("DAEMON_SIGNING_KEY is not set".into());
+    }
+
+    match source.verify {
+        DaemonVerify::None => Ok(()),
+        DaemonVerify::Minisign => {
+            let public_key: PublicKey = PublicKey::from_base64(&source.signing_key)
+                .map_err(|err| format!("Invalid DAEMON_SIGNING_KEY: {}", err))?;
+            let signature: Signature = Signature::decode(&fs::read_to_string(sig_path)?)
+                .map_err(|err| format!("Invalid signature file: {}", err))?;
+
+            public_key
+                .verify(&fs::read(hashes_path)?, &signature, false)
+                .map_err(|err| format!("Bad signature: {}", err).into())
+        }
+        DaemonVerify::Gpg => {
+            let output = Command::new(GPGV_PATH)
+                .arg("--keyring")
+                .arg(&source.signing_key)
+                .arg(sig_path)
+                .arg(hashes_path)
+                .output()?;
+
+            if !output.status.success() {
+                return Err(format!(
+                    "Bad signature: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )
+                .into());
+            }
+
+            Ok(())
+        }
+    }
+}
+
+// Removes download cache entries older than `max_age`, a zero age clears the whole cache.
+// Returns how many entries were removed.
+pub fn clean_download_cache(
+    max_age: Duration,
+) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    let tmp_path: PathBuf = PathBuf::from(TMP_PATH);
+
+    if !tmp_path.exists() {
+        return Ok(0);
+    }
+
+    let now: SystemTime = SystemTime::now();
+    let mut removed: usize = 0;
+
+    for entry in fs::read_dir(&tmp_path)? {
+        let entry = entry?;
+        let path: PathBuf = entry.path();
+        let metadata = entry.metadata()?;
+
+        let age: Duration = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .unwrap_or_default();
+
+        if age < max_age {
+            continue;
+        }
+
+        if metadata.is_dir() {
+            file_ops::rm_dir(&path)?;
+        } else {
+            file_ops::rm_file(&path)?;
+        }
+
+        removed += 1;
+    }
+
+    Ok(removed)
+}
+
 pub fn extract_archive(
     archive_path: &PathBuf,
     gv_home_dir: &PathBuf,
 ) -> Result<PathAndDigest, Box<dyn std::error::Error + Send + Sync>> {
     info!("Extracting Ghost daemon...");
-    let daemon_dir: PathBuf = gv_home_dir.join("daemon/");
+    // Each release gets its own daemon/<version>/, so the one running stays put until the new
+    // one is proven to work.
+    let version: String = archive_version(archive_path).unwrap_or("unknown".to_string());
+    let daemon_dir: PathBuf = gv_home_dir.join("daemon").join(version);
+
+    if daemon_dir.exists() {
+        file_ops::rm_dir(&daemon_dir)?;
+    }
 
     let tar_gz: File = File::open(archive_path)?;
     let tar: GzDecoder<File> = GzDecoder::new(tar_gz);
@@ -257,6 +687,100 @@ pub fn extract_archive(
     }
 }
 
+// Removes every install under daemon/ except the ones holding the given ghostd binaries,
+// normally the running release and the one to roll back to.
+pub fn prune_daemon_versions(
+    gv_home_dir: &PathBuf,
+    keep: &[&PathBuf],
+) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    let daemon_dir: PathBuf = gv_home_dir.join("daemon");
+
+    if !daemon_dir.exists() {
+        return Ok(0);
+    }
+
+    let mut removed: usize = 0;
+
+    for entry in fs::read_dir(&daemon_dir)? {
+        let path: PathBuf = entry?.path();
+
+        if keep
+            .iter()
+            .any(|daemon_path| daemon_path.starts_with(&path))
+        {
+            continue;
+        }
+
+        if path.is_dir() {
+            file_ops::rm_dir(&path)?;
+        } else {
+            file_ops::rm_file(&path)?;
+        }
+
+        removed += 1;
+    }
+
+    Ok(removed)
+}
+
+/// searches PATH for a system wide ghostd installation
+pub fn find_system_daemon() -> Option<PathBuf> {
+    let daemon_name: &str = if cfg!(target_os = "windows") {
+        "ghostd.exe"
+    } else {
+        "ghostd"
+    };
+
+    let path_var = env::var_os("PATH")?;
+
+    env::split_paths(&path_var)
+        .map(|dir| dir.join(daemon_name))
+        .find(|daemon_path| daemon_path.is_file())
+}
+
+/// reads the version reported by `ghostd --version`
+pub fn get_binary_version(
+    daemon_path: &PathBuf,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let output = Command::new(daemon_path).arg("--version").output()?;
+
+    if !output.status.success() {
+        let error_string = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Command failed with error:\n{}", error_string).into());
+    }
+
+    let result_string = String::from_utf8_lossy(&output.stdout);
+    let version: &str = result_string
+        .lines()
+        .next()
+        .and_then(|line| line.split(' ').next_back())
+        .and_then(|version| version.strip_prefix("v"))
+        .and_then(|version| version.split('-').next())
+        .ok_or("Unable to parse ghostd version")?;
+
+    Ok(version.strip_suffix(".0").unwrap_or(version).to_string())
+}
+
+/// checks that a system ghostd is usable and warns if it is behind the latest release
+pub async fn check_system_daemon(
+    daemon_path: &PathBuf,
+    channel: &UpdateChannel,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let version: String = get_binary_version(daemon_path)?;
+    parse_version(&version).ok_or("Unable to parse ghostd version")?;
+
+    if let Ok(latest_release) = get_latest_release(channel).await {
+        if compare_versions(&latest_release, &version) == Some(Ordering::Greater) {
+            warn!(
+                "System ghostd v{} is older than the latest release v{}, update it with your package manager.",
+                version, latest_release
+            );
+        }
+    }
+
+    Ok(version)
+}
+
 /// calculates sha256 digest as lowercase hex string
 pub fn sha256_digest(path: &PathBuf) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
     let input: File = File::open(path)?;
@@ -310,8 +834,13 @@ fn compare_digest_daemon(
     for line in data.iter() {
         let split_line: Vec<&str> = line.split_whitespace().collect();
 
-        if split_line.last().unwrap() == &file_path.file_name().unwrap().to_str().unwrap() {
-            if split_line.first().unwrap() == &file_hash.as_str() {
+        if split_line.last()
+            == file_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .as_ref()
+        {
+            if split_line.first() == Some(&file_hash.as_str()) {
                 return Ok(true);
             }
         }
@@ -320,90 +849,6 @@ fn compare_digest_daemon(
     Ok(false)
 }
 
-pub async fn get_remote_best_block() -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
-    let node: Vec<String> = get_remote_nodes();
-
-    let url1: String = format!("{}/getblockcount/", node[0]);
-    let url2: String = format!("{}/getblockcount/", node[1]);
-    let url3: String = format!("{}/getblockcount/", node[2]);
-    let url4: String = format!("{}/getblockcount/", node[3]);
-
-    let result = select_ok(vec![
-        make_get_req(url1).boxed(),
-        make_get_req(url2).boxed(),
-        make_get_req(url3).boxed(),
-        make_get_req(url4).boxed(),
-    ])
-    .await?;
-
-    Ok(result.0)
-}
-
-pub async fn get_remote_block_hash(
-    block_index: u32,
-) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
-    let node: Vec<String> = get_remote_nodes();
-
-    let url1: String = format!("{}/api/block-index/{}/", node[0], block_index);
-    let url2: String = format!("{}/api/block-index/{}/", node[1], block_index);
-    let url3: String = format!("{}/api/block-index/{}/", node[2], block_index);
-    let url4: String = format!("{}/api/block-index/{}/", node[3], block_index);
-
-    let result = select_ok(vec![
-        make_get_req(url1).boxed(),
-        make_get_req(url2).boxed(),
-        make_get_req(url3).boxed(),
-        make_get_req(url4).boxed(),
-    ])
-    .await?;
-
-    Ok(result.0)
-}
-
-pub async fn get_remote_block_chain_info() -> Result<Value, Box<dyn std::error::Error + Send + Sync>>
-{
-    let node: Vec<String> = get_remote_nodes();
-
-    let url1: String = format!("{}/getblockchaininfo/", node[0]);
-    let url2: String = format!("{}/getblockchaininfo/", node[1]);
-    let url3: String = format!("{}/getblockchaininfo/", node[2]);
-    let url4: String = format!("{}/getblockchaininfo/", node[3]);
-
-    let result = select_ok(vec![
-        make_get_req(url1).boxed(),
-        make_get_req(url2).boxed(),
-        make_get_req(url3).boxed(),
-        make_get_req(url4).boxed(),
-    ])
-    .await?;
-
-    Ok(result.0)
-}
-
-async fn make_get_req(url: String) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
-    let res: Response = reqwest::get(url).await?;
-    let json_data: Value = res.json().await?;
-
-    Ok(json_data)
-}
-
-fn get_remote_nodes() -> Vec<String> {
-    let nodes_init: Vec<&str> = vec![
-        "https://api.tuxprint.com",
-        "https://api2.tuxprint.com",
-        "https://socket.tuxprint.com",
-        "https://socket2.tuxprint.com",
-    ];
-
-    let mut nodes: Vec<String> = Vec::new();
-
-    for node in nodes_init {
-        nodes.push(node.to_string())
-    }
-
-    nodes
-}
-
 pub async fn validate_bot_token(
     token: &str,
 ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
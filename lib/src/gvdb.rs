@@ -1,7 +1,20 @@
 extern crate sled;
+use crate::{
+    announce::{self, AnnounceDestination},
+    constants::{
+        EVENT_LOG_RETENTION, JOB_HISTORY_MAX, NETWORK_STATS_RETENTION, RESTART_HISTORY_MAX,
+        SYSTEM_STATS_RETENTION,
+    },
+    event_bus::GvEvent,
+    file_ops,
+    time_ranges::Division,
+};
+use chrono_tz::Tz;
+use log::info;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sled::{Db, Result, Tree};
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 use teloxide::types::MessageId;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -16,6 +29,20 @@ pub struct RewardsDB {
     pub all_time_agvr_reward: u64,
     pub address: String,
     pub is_coldstake: bool,
+    // The cold wallet that staked, empty for stakes from before wallets were tracked.
+    #[serde(default)]
+    pub wallet: String,
+}
+
+impl RewardsDB {
+    // Stakes recorded before wallets were tracked count for the primary wallet.
+    pub fn wallet_or<'a>(&'a self, primary: &'a str) -> &'a str {
+        if self.wallet.is_empty() {
+            primary
+        } else {
+            &self.wallet
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -26,6 +53,14 @@ pub struct Task {
     pub next_run: i64,
     pub min_payout: Option<u64>,
     pub task_running: bool,
+    #[serde(default)]
+    pub last_run: Option<i64>,
+    // "ok", or the error the last run ended with.
+    #[serde(default)]
+    pub last_result: Option<String>,
+    // A paused task isn't run until it's resumed, it can't be run by hand meanwhile either.
+    #[serde(default)]
+    pub paused: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -55,6 +90,9 @@ pub struct NewStakeStatusDB {
     pub confirmations: u32,
     pub timestamp: u64,
     pub tg_msg_id: Option<MessageId>,
+    // The stake's details from its first message, kept for editing it as the stake matures.
+    #[serde(default)]
+    pub summary: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -62,6 +100,386 @@ pub struct ColdRecovery {
     pub seed_words: String,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PayoutDB {
+    pub txid: String,
+    pub timestamp: u64,
+    pub amount: u64,
+    pub fee: u64,
+    pub destination: String,
+    pub mode: String,
+    // Sats per kB, 0 for payouts from before it was recorded.
+    #[serde(default)]
+    pub fee_rate: u64,
+    // The primary cold wallet when it was sent, empty for older payouts.
+    #[serde(default)]
+    pub wallet: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WalletRescanDB {
+    pub start_height: u32,
+    pub status: String,
+    pub requested: u64,
+    pub started: Option<u64>,
+    pub finished: Option<u64>,
+    pub progress: f64,
+    pub error: Option<String>,
+    // A repair clears the wallet's transactions first and rescans them from genesis.
+    #[serde(default)]
+    pub repair: bool,
+}
+
+// The last bootstrap sync, status is downloading, installing, complete or failed. Sizes in bytes,
+// total is 0 when the server didn't say.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BootstrapDB {
+    pub url: String,
+    pub status: String,
+    pub started: u64,
+    pub finished: Option<u64>,
+    pub downloaded: u64,
+    pub total: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StakeStreakDB {
+    pub last_milestone: u32,
+}
+
+// Wallet balances in sats at a point in time, each holds until the next sample.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BalanceSampleDB {
+    pub timestamp: u64,
+    pub currently_staking: u64,
+    pub total_coldstaking: u64,
+    pub anon_balance: u64,
+}
+
+// A delegator sharing the vault. What stakes from their addresses earn is owed to them until
+// the reward payout sends it to their payout address.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TenantDB {
+    pub name: String,
+    // The address or ext pub key they registered with.
+    pub key: String,
+    pub addresses: Vec<String>,
+    pub payout_address: String,
+    pub registered: u64,
+    pub owed: u64,
+    pub earned: u64,
+    pub paid: u64,
+}
+
+// A stake credited to a tenant or a payout sent to them, amounts in sats.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TenantLedgerDB {
+    pub timestamp: u64,
+    pub tenant: String,
+    pub kind: String,
+    pub txid: String,
+    pub amount: u64,
+}
+
+// An output kept out of sweeps and coin selection, locked in the wallet while this is held.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FrozenUtxoDB {
+    pub wallet: String,
+    pub txid: String,
+    pub vout: u32,
+    pub utxo_type: String,
+    pub address: String,
+    pub amount: u64,
+    pub frozen_at: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SharedVaultDB {
+    // Stakes up to here have been credited to tenants.
+    pub credited_until: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MissedStakeDB {
+    // The stake the last alert was counted from, so each drought alerts once.
+    pub alerted_after: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PendingUpdateDB {
+    pub token: String,
+    pub version: String,
+    pub created: u64,
+}
+
+// A split waiting on confirmation, the outputs are (txid, vout, sats) as they were planned.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PendingSplitDB {
+    pub token: String,
+    pub created: u64,
+    pub pieces: u64,
+    pub utxos: Vec<(String, u32, u64)>,
+}
+
+// The Telegram setup conversation, offered until it's been finished or skipped once.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct OnboardingDB {
+    pub completed: Option<u64>,
+    pub skipped: bool,
+}
+
+// A reward mode change waiting out REWARD_CHANGE_DELAY, it can be cancelled until effective.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PendingRewardChangeDB {
+    pub mode: String,
+    pub address: String,
+    pub requested: u64,
+    pub effective: u64,
+}
+
+// Start of the last day and week a digest went out for, so each is only sent once.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DigestDB {
+    pub last_daily: u64,
+    pub last_weekly: u64,
+}
+
+// Disruptive operations waiting for the maintenance window, update is the ghostd version.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MaintenanceDB {
+    pub update: Option<String>,
+    pub resync: bool,
+}
+
+// The last database maintenance pass. Sizes are bytes on disk and trees the entries in each.
+// The repaired counts are entries rewritten from the stakes, unreadable stakes are only counted.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DbMaintenanceDB {
+    pub timestamp: u64,
+    pub duration_ms: u64,
+    pub size_before: u64,
+    pub size_after: u64,
+    pub trees: BTreeMap<String, u64>,
+    pub read_errors: Vec<String>,
+    pub rewards: u64,
+    pub unreadable_rewards: u64,
+    pub rekeyed_rewards: u64,
+    pub totals_repaired: u64,
+    pub rollups_repaired: u64,
+}
+
+impl DbMaintenanceDB {
+    pub fn repaired(&self) -> u64 {
+        self.rekeyed_rewards + self.totals_repaired + self.rollups_repaired
+    }
+}
+
+// Disk and network use in bytes. The net totals are ghostd's own counters, they start over
+// when it restarts, and like the chain size are None while ghostd isn't answering.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SystemSampleDB {
+    pub timestamp: u64,
+    pub daemon_disk_free: u64,
+    pub daemon_disk_total: u64,
+    pub gv_disk_free: u64,
+    pub gv_disk_total: u64,
+    pub chain_size: Option<u64>,
+    pub net_bytes_recv: Option<u64>,
+    pub net_bytes_sent: Option<u64>,
+}
+
+// Staking weights in sats. The network weight is estimated from the difficulty while ghostd
+// reports no netstakeweight.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NetworkSampleDB {
+    pub timestamp: u64,
+    pub height: u32,
+    pub network_weight: u64,
+    pub difficulty: f64,
+    pub our_weight: u64,
+}
+
+// A cold wallet staking in ghostd next to the primary one. Only the primary wallet pays out,
+// its ext pub key and internal anon address are kept here while another wallet is primary.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ColdWalletDB {
+    pub name: String,
+    pub added: u64,
+    pub imported: bool,
+    pub ext_pub_key: Option<String>,
+    pub internal_anon: Option<String>,
+    // MNEMONIC in the config belongs to this wallet, set once the primary wallet is switched.
+    #[serde(default)]
+    pub holds_mnemonic: bool,
+}
+
+// A zap the owner asked for with build_zap_request, matched to the deposit by its stake address.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ZapRequestDB {
+    pub key_index: u32,
+    pub stake_addr: String,
+    pub spend_addr: String,
+    pub amount: u64,
+    pub script_hex: String,
+    pub requested: u64,
+    pub txid: Option<String>,
+}
+
+// A deposit the owner said is coming. A zap of exactly the amount is matched to the oldest open
+// one, missed is set once the owner has been told it didn't arrive by due.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExpectedDepositDB {
+    pub id: u64,
+    pub amount: u64,
+    pub memo: String,
+    pub created: u64,
+    pub due: u64,
+    pub txid: Option<String>,
+    pub missed: bool,
+}
+
+// One run converting public rewards to anon. Kept until all its transactions have matured.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnonConversionDB {
+    pub timestamp: u64,
+    pub txids: Vec<String>,
+    pub amount: u64,
+}
+
+// A bookkeeping label on a payout, zap or any other wallet transaction, keyed by txid.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TxLabelDB {
+    pub txid: String,
+    pub label: String,
+    pub updated: u64,
+}
+
+// A name for a payout or whitelisted address, shown in its place, keyed by address.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AddressBookDB {
+    pub address: String,
+    pub label: String,
+    pub updated: u64,
+}
+
+// A state changing GvCLI call. Passphrases, mnemonics and tokens are left out of params.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuditDB {
+    pub id: u64,
+    pub timestamp: u64,
+    pub method: String,
+    pub origin: String,
+    pub peer: Option<String>,
+    pub params: Value,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+// A restart of ghostd by the Docker supervisor.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RestartDB {
+    pub timestamp: u64,
+    pub attempt: u64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+// A long running job, like a payout or resync. State is running, complete, failed or interrupted,
+// interrupted when ghostvaultd stopped while it ran.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobDB {
+    pub id: u64,
+    pub kind: String,
+    pub state: String,
+    pub started: u64,
+    pub finished: Option<u64>,
+    pub progress: Option<String>,
+    pub outcome: Option<String>,
+    pub error: Option<String>,
+}
+
+// The operator's own text for a message type, header None keeps the built in one.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnnounceTemplateDB {
+    pub header: Option<String>,
+    pub body: String,
+}
+
+// Local hours in TIMEZONE, a window such as 22 to 7 wraps past midnight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHoursDB {
+    pub start: u32,
+    pub end: u32,
+}
+
+// A Telegram chat's own timezone for charts and status, in place of the vault's TIMEZONE.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChatTimezoneDB {
+    pub chat_id: i64,
+    pub timezone: String,
+}
+
+// A notice held back during a destination's quiet hours, sent in one batch once they're over.
+// prefix is the vault prefix of relayed messages.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HeldNoticeDB {
+    pub timestamp: u64,
+    pub prefix: String,
+    pub header: String,
+    pub msg: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DiskAlertDB {
+    // Set once the low disk alert went out, cleared when there's room again.
+    pub alerted: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChainEmergencyDB {
+    pub since: u64,
+    pub active: bool,
+    pub last_alert: u64,
+    pub snoozed_until: Option<u64>,
+}
+
+// Stake totals for one day, week or month, kept alongside the rewards so the overview and
+// charts don't have to walk every stake. Periods start at UTC midnight, weeks on Sunday.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeRollupDB {
+    pub stakes: u32,
+    pub reward: u64,
+    pub agvr_reward: u64,
+}
+
+impl StakeRollupDB {
+    fn add(&mut self, other: &StakeRollupDB) {
+        self.stakes += other.stakes;
+        self.reward += other.reward;
+        self.agvr_reward += other.agvr_reward;
+    }
+
+    fn sub(&mut self, other: &StakeRollupDB) {
+        self.stakes = self.stakes.saturating_sub(other.stakes);
+        self.reward = self.reward.saturating_sub(other.reward);
+        self.agvr_reward = self.agvr_reward.saturating_sub(other.agvr_reward);
+    }
+
+    fn from_reward(reward: &RewardsDB) -> StakeRollupDB {
+        StakeRollupDB {
+            stakes: 1,
+            reward: reward.reward,
+            agvr_reward: reward.agvr_reward,
+        }
+    }
+}
+
+// Rollups are kept in UTC periods and only speed up totals over a range, which come out the
+// same wherever the periods fall. Anything drawn per local day, week or month is summed from
+// the rewards index in the vault's timezone.
+const ROLLUP_TZ: Tz = Tz::UTC;
+
 #[derive(Clone, Debug)]
 pub struct GVDB {
     pub rewards_ts_index: Tree,
@@ -74,6 +492,44 @@ pub struct GVDB {
     pub gvdb: Db,
     pub new_stake_status: Tree,
     pub server_ready_db: Tree,
+    pub payout_history_db: Tree,
+    pub wallet_rescan_db: Tree,
+    pub stake_streak_db: Tree,
+    pub pending_update_db: Tree,
+    pub chain_emergency_db: Tree,
+    pub announce_routes_db: Tree,
+    pub stake_rollup_daily: Tree,
+    pub stake_rollup_weekly: Tree,
+    pub stake_rollup_monthly: Tree,
+    pub balance_history_db: Tree,
+    pub tenants_db: Tree,
+    pub tenant_ledger_db: Tree,
+    pub shared_vault_db: Tree,
+    pub frozen_utxos_db: Tree,
+    pub pending_split_db: Tree,
+    pub event_log_db: Tree,
+    pub digest_db: Tree,
+    pub maintenance_db: Tree,
+    pub system_stats_db: Tree,
+    pub disk_alert_db: Tree,
+    pub bootstrap_db: Tree,
+    pub sync_message_db: Tree,
+    pub restart_history_db: Tree,
+    pub audit_log_db: Tree,
+    pub pending_reward_change_db: Tree,
+    pub onboarding_db: Tree,
+    pub network_stats_db: Tree,
+    pub cold_wallets_db: Tree,
+    pub zap_requests_db: Tree,
+    pub expected_deposits_db: Tree,
+    pub anon_conversions_db: Tree,
+    pub tx_labels_db: Tree,
+    pub address_book_db: Tree,
+    pub jobs_db: Tree,
+    pub quiet_hours_db: Tree,
+    pub held_notices_db: Tree,
+    pub announce_templates_db: Tree,
+    pub chat_timezones_db: Tree,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -86,6 +542,12 @@ pub struct TgBotQueueDB {
     pub msg_type: String,
     pub reward_txid: Option<String>,
     pub msg_to_delete: Option<MessageId>,
+    // Carried by messages that ask for a confirmation, like a pending UTXO split.
+    #[serde(default)]
+    pub action_token: Option<String>,
+    // The stake message this one replaces the text of.
+    #[serde(default)]
+    pub msg_to_edit: Option<MessageId>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -96,11 +558,10 @@ pub struct ServerReadyDB {
 }
 
 impl GVDB {
-    pub async fn new(gv_home: &PathBuf) -> Self {
-        let db_dir: std::path::PathBuf = gv_home.join("gv_database/");
+    pub async fn new(db_dir: &PathBuf) -> Self {
         let db: Db = sled::Config::new()
             .cache_capacity(500000000)
-            .path(&db_dir)
+            .path(db_dir)
             .open()
             .unwrap();
 
@@ -113,6 +574,44 @@ impl GVDB {
         let tg_bot_queue: Tree = db.open_tree(b"tg_bot_queue").unwrap();
         let zap_status_db: Tree = db.open_tree(b"zap_status").unwrap();
         let new_stake_status: Tree = db.open_tree(b"new_stake_status").unwrap();
+        let payout_history_db: Tree = db.open_tree(b"payout_history").unwrap();
+        let wallet_rescan_db: Tree = db.open_tree(b"wallet_rescan").unwrap();
+        let stake_streak_db: Tree = db.open_tree(b"stake_streak").unwrap();
+        let pending_update_db: Tree = db.open_tree(b"pending_update").unwrap();
+        let chain_emergency_db: Tree = db.open_tree(b"chain_emergency").unwrap();
+        let announce_routes_db: Tree = db.open_tree(b"announce_routes").unwrap();
+        let stake_rollup_daily: Tree = db.open_tree(b"stake_rollup_daily").unwrap();
+        let stake_rollup_weekly: Tree = db.open_tree(b"stake_rollup_weekly").unwrap();
+        let stake_rollup_monthly: Tree = db.open_tree(b"stake_rollup_monthly").unwrap();
+        let balance_history_db: Tree = db.open_tree(b"balance_history").unwrap();
+        let tenants_db: Tree = db.open_tree(b"tenants").unwrap();
+        let tenant_ledger_db: Tree = db.open_tree(b"tenant_ledger").unwrap();
+        let shared_vault_db: Tree = db.open_tree(b"shared_vault").unwrap();
+        let frozen_utxos_db: Tree = db.open_tree(b"frozen_utxos").unwrap();
+        let pending_split_db: Tree = db.open_tree(b"pending_split").unwrap();
+        let event_log_db: Tree = db.open_tree(b"event_log").unwrap();
+        let digest_db: Tree = db.open_tree(b"digest").unwrap();
+        let maintenance_db: Tree = db.open_tree(b"maintenance").unwrap();
+        let system_stats_db: Tree = db.open_tree(b"system_stats").unwrap();
+        let disk_alert_db: Tree = db.open_tree(b"disk_alert").unwrap();
+        let bootstrap_db: Tree = db.open_tree(b"bootstrap").unwrap();
+        let sync_message_db: Tree = db.open_tree(b"sync_message").unwrap();
+        let restart_history_db: Tree = db.open_tree(b"restart_history").unwrap();
+        let audit_log_db: Tree = db.open_tree(b"audit_log").unwrap();
+        let pending_reward_change_db: Tree = db.open_tree(b"pending_reward_change").unwrap();
+        let onboarding_db: Tree = db.open_tree(b"onboarding").unwrap();
+        let network_stats_db: Tree = db.open_tree(b"network_stats").unwrap();
+        let cold_wallets_db: Tree = db.open_tree(b"cold_wallets").unwrap();
+        let zap_requests_db: Tree = db.open_tree(b"zap_requests").unwrap();
+        let expected_deposits_db: Tree = db.open_tree(b"expected_deposits").unwrap();
+        let anon_conversions_db: Tree = db.open_tree(b"anon_conversions").unwrap();
+        let tx_labels_db: Tree = db.open_tree(b"tx_labels").unwrap();
+        let address_book_db: Tree = db.open_tree(b"address_book").unwrap();
+        let jobs_db: Tree = db.open_tree(b"jobs").unwrap();
+        let quiet_hours_db: Tree = db.open_tree(b"quiet_hours").unwrap();
+        let held_notices_db: Tree = db.open_tree(b"held_notices").unwrap();
+        let announce_templates_db: Tree = db.open_tree(b"announce_templates").unwrap();
+        let chat_timezones_db: Tree = db.open_tree(b"chat_timezones").unwrap();
 
         GVDB {
             rewards_ts_index,
@@ -125,6 +624,44 @@ impl GVDB {
             gvdb: db,
             new_stake_status,
             server_ready_db,
+            payout_history_db,
+            wallet_rescan_db,
+            stake_streak_db,
+            pending_update_db,
+            chain_emergency_db,
+            announce_routes_db,
+            stake_rollup_daily,
+            stake_rollup_weekly,
+            stake_rollup_monthly,
+            balance_history_db,
+            tenants_db,
+            tenant_ledger_db,
+            shared_vault_db,
+            frozen_utxos_db,
+            pending_split_db,
+            event_log_db,
+            digest_db,
+            maintenance_db,
+            system_stats_db,
+            disk_alert_db,
+            bootstrap_db,
+            sync_message_db,
+            restart_history_db,
+            audit_log_db,
+            pending_reward_change_db,
+            onboarding_db,
+            network_stats_db,
+            cold_wallets_db,
+            zap_requests_db,
+            expected_deposits_db,
+            anon_conversions_db,
+            tx_labels_db,
+            address_book_db,
+            jobs_db,
+            quiet_hours_db,
+            held_notices_db,
+            announce_templates_db,
+            chat_timezones_db,
         }
     }
 
@@ -135,16 +672,40 @@ impl GVDB {
         self.cold_recovery_db.clear().unwrap();
         self.zap_status_db.clear().unwrap();
         self.new_stake_status.clear().unwrap();
+        for period in Division::ALL {
+            self.rollup_tree(period).clear().unwrap();
+        }
+        self.balance_history_db.clear().unwrap();
 
         self.gvdb.flush_async().await.unwrap();
 
         Ok(())
     }
 
+    // Drops the stakes and their rollups for a reindex, what GhostVault knows of the chain and
+    // the wallets is kept.
+    pub async fn clear_stake_history(&self) -> Result<()> {
+        self.rewards_ts_index.clear()?;
+        for period in Division::ALL {
+            self.rollup_tree(period).clear()?;
+        }
+
+        self.gvdb.flush_async().await?;
+
+        Ok(())
+    }
+
     pub async fn set_reward(&self, reward: &RewardsDB) -> Result<()> {
         let key = reward.timestamp.to_be_bytes();
         let value: Vec<u8> = serde_json::to_vec(&reward).unwrap();
-        self.rewards_ts_index.insert(key, value).unwrap();
+        let previous = self.rewards_ts_index.insert(key, value).unwrap();
+
+        // Rewriting a stake replaces its contribution to the rollups rather than adding to it
+        if let Some(previous) = previous {
+            let previous: RewardsDB = serde_json::from_slice(&previous).unwrap();
+            self.update_rollups(&previous, false);
+        }
+        self.update_rollups(reward, true);
         self.gvdb.flush_async().await.unwrap();
 
         Ok(())
@@ -160,119 +721,520 @@ impl GVDB {
     }
 
     pub async fn remove_reward(&self, key: impl AsRef<[u8]>) -> Result<()> {
-        self.rewards_ts_index.remove(key).unwrap();
+        if let Some(removed) = self.rewards_ts_index.remove(key).unwrap() {
+            let removed: RewardsDB = serde_json::from_slice(&removed).unwrap();
+            self.update_rollups(&removed, false);
+        }
         self.gvdb.flush_async().await.unwrap();
         Ok(())
     }
 
-    pub async fn set_task(&self, key: impl AsRef<[u8]>, task: &Task) -> Result<()> {
-        let value: Vec<u8> = serde_json::to_vec(&task).unwrap();
-        self.task_queue.insert(key, value).unwrap();
-        self.gvdb.flush_async().await.unwrap();
-
-        Ok(())
+    pub fn rollup_tree(&self, period: Division) -> &Tree {
+        match period {
+            Division::Day => &self.stake_rollup_daily,
+            Division::Week => &self.stake_rollup_weekly,
+            Division::Month => &self.stake_rollup_monthly,
+        }
     }
 
-    pub fn get_task(&self, key: impl AsRef<[u8]>) -> Option<Task> {
-        if let Some(result) = self.task_queue.get(key).unwrap() {
-            let value: Task = serde_json::from_slice(&result).unwrap();
+    pub fn get_rollup(&self, period: Division, period_start: u64) -> Option<StakeRollupDB> {
+        if let Some(result) = self
+            .rollup_tree(period)
+            .get(period_start.to_be_bytes())
+            .unwrap()
+        {
+            let value: StakeRollupDB = serde_json::from_slice(&result).unwrap();
             Some(value)
         } else {
             None
         }
     }
 
-    pub async fn remove_task(&self, key: impl AsRef<[u8]>) -> Result<()> {
-        self.task_queue.remove(key)?;
-        self.gvdb.flush_async().await.unwrap();
-        Ok(())
-    }
+    fn update_rollups(&self, reward: &RewardsDB, add: bool) {
+        let change: StakeRollupDB = StakeRollupDB::from_reward(reward);
 
-    pub async fn set_tg_bot_queue(&self, key: impl AsRef<[u8]>, task: &TgBotQueueDB) -> Result<()> {
-        let value: Vec<u8> = serde_json::to_vec(&task).unwrap();
-        self.tg_bot_queue.insert(key, value).unwrap();
-        self.gvdb.flush_async().await.unwrap();
+        for period in Division::ALL {
+            let key = period
+                .period_start(&ROLLUP_TZ, reward.timestamp)
+                .to_be_bytes();
 
-        Ok(())
-    }
+            self.rollup_tree(period)
+                .update_and_fetch(key, |current| {
+                    let mut rollup: StakeRollupDB = current
+                        .map(|value| serde_json::from_slice(value).unwrap())
+                        .unwrap_or_default();
 
-    pub fn get_tg_bot_queue(&self, key: impl AsRef<[u8]>) -> Option<TgBotQueueDB> {
-        if let Some(result) = self.tg_bot_queue.get(key).unwrap() {
-            let value: TgBotQueueDB = serde_json::from_slice(&result).unwrap();
-            Some(value)
-        } else {
-            None
+                    if add {
+                        rollup.add(&change);
+                    } else {
+                        rollup.sub(&change);
+                    }
+
+                    if rollup.stakes == 0 {
+                        None
+                    } else {
+                        Some(serde_json::to_vec(&rollup).unwrap())
+                    }
+                })
+                .unwrap();
         }
     }
 
-    pub async fn remove_tg_bot_queue(&self, key: impl AsRef<[u8]>) -> Result<()> {
-        self.tg_bot_queue.remove(key)?;
-        self.gvdb.flush_async().await.unwrap();
+    // Builds the rollups from the rewards index once, databases from before they existed
+    // start out with none. The marker is only written after every period has been stored.
+    pub async fn backfill_stake_rollups(&self) -> Result<()> {
+        if self.gvdb.contains_key(b"stake_rollups_built")? {
+            return Ok(());
+        }
 
-        Ok(())
-    }
+        info!("Building stake rollups from the rewards index...");
 
-    pub async fn set_zap_status(
-        &self,
-        key: impl AsRef<[u8]>,
-        zap_status: &ZapStatusDB,
-    ) -> Result<()> {
-        let value: Vec<u8> = serde_json::to_vec(&zap_status).unwrap();
-        self.zap_status_db.insert(key, value).unwrap();
-        self.gvdb.flush_async().await.unwrap();
+        for period in Division::ALL {
+            let rollups: BTreeMap<u64, StakeRollupDB> = self.rollups_from_rewards(period)?;
+            self.store_rollups(period, rollups)?;
+        }
+
+        self.gvdb.insert(b"stake_rollups_built", &[1])?;
+        self.gvdb.flush_async().await?;
+
+        info!("Stake rollups built!");
 
         Ok(())
     }
 
-    pub fn get_zap_status(&self, key: impl AsRef<[u8]>) -> Option<ZapStatusDB> {
-        if let Some(result) = self.zap_status_db.get(key).unwrap() {
-            let value: ZapStatusDB = serde_json::from_slice(&result).unwrap();
-            Some(value)
-        } else {
-            None
+    // Every period's totals worked out from the rewards index, unreadable stakes are skipped.
+    fn rollups_from_rewards(&self, period: Division) -> Result<BTreeMap<u64, StakeRollupDB>> {
+        let mut rollups: BTreeMap<u64, StakeRollupDB> = BTreeMap::new();
+
+        for result in self.rewards_ts_index.iter() {
+            let (_, value) = result?;
+
+            if let Ok(reward) = serde_json::from_slice::<RewardsDB>(&value) {
+                rollups
+                    .entry(period.period_start(&ROLLUP_TZ, reward.timestamp))
+                    .or_default()
+                    .add(&StakeRollupDB::from_reward(&reward));
+            }
         }
+
+        Ok(rollups)
     }
 
-    pub async fn remove_zap_status(&self, key: impl AsRef<[u8]>) -> Result<()> {
-        self.zap_status_db.remove(key)?;
-        self.gvdb.flush_async().await.unwrap();
+    fn store_rollups(&self, period: Division, rollups: BTreeMap<u64, StakeRollupDB>) -> Result<()> {
+        let tree: &Tree = self.rollup_tree(period);
+        tree.clear()?;
+
+        for (period_start, rollup) in rollups {
+            let value: Vec<u8> = serde_json::to_vec(&rollup).unwrap();
+            tree.insert(period_start.to_be_bytes(), value)?;
+        }
 
         Ok(())
     }
 
-    pub async fn set_cold_recovery(&self, wallet: &str, cold_recover: &ColdRecovery) -> Result<()> {
-        let key = wallet.as_bytes();
-        let value: Vec<u8> = serde_json::to_vec(&cold_recover).unwrap();
-        self.cold_recovery_db.insert(key, value).unwrap();
-        self.gvdb.flush_async().await.unwrap();
+    // Reads every tree and checks the rewards index agrees with itself: each stake under its
+    // own timestamp, the all time totals adding up stake by stake and the rollups matching the
+    // stakes. Whatever can be worked out from the stakes is rewritten. sled reclaims space on
+    // its own, the flush at the end lets it write out what it has freed. A stake landing
+    // mid-pass is put right by the next one.
+    pub async fn run_maintenance(&self) -> Result<DbMaintenanceDB> {
+        let started: std::time::Instant = std::time::Instant::now();
 
-        Ok(())
-    }
+        let mut report: DbMaintenanceDB = DbMaintenanceDB {
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            size_before: self.gvdb.size_on_disk()?,
+            ..Default::default()
+        };
 
-    pub fn get_cold_recovery(&self, key: impl AsRef<[u8]>) -> Option<ColdRecovery> {
-        if let Some(result) = self.cold_recovery_db.get(key).unwrap() {
-            let value: ColdRecovery = serde_json::from_slice(&result).unwrap();
-            Some(value)
-        } else {
-            None
+        for name in self.gvdb.tree_names() {
+            let tree_name: String = String::from_utf8_lossy(&name).to_string();
+            let tree: Tree = self.gvdb.open_tree(&name)?;
+            let mut entries: u64 = 0;
+
+            for result in tree.iter() {
+                match result {
+                    Ok(_) => entries += 1,
+                    Err(err) => {
+                        report.read_errors.push(format!("{}: {}", tree_name, err));
+                        break;
+                    }
+                }
+            }
+
+            report.trees.insert(tree_name, entries);
         }
+
+        self.rekey_rewards(&mut report)?;
+        report.totals_repaired = self.recompute_reward_totals()?;
+        self.repair_stake_rollups(&mut report)?;
+
+        self.gvdb.flush_async().await?;
+
+        report.size_after = self.gvdb.size_on_disk()?;
+        report.duration_ms = started.elapsed().as_millis() as u64;
+
+        Ok(report)
     }
 
-    pub async fn set_daemon_status(&self, status: &DaemonStatusDB) -> Result<()> {
-        let key: &[u8; 13] = b"daemon_status";
-        let value: Vec<u8> = serde_json::to_vec(&status).unwrap();
-        self.daemon_status_db.insert(key, value).unwrap();
-        self.gvdb.flush_async().await.unwrap();
+    // Moves stakes stored under another key to their timestamp, unless a stake is already there.
+    fn rekey_rewards(&self, report: &mut DbMaintenanceDB) -> Result<()> {
+        let mut misplaced: Vec<(sled::IVec, RewardsDB)> = Vec::new();
+
+        for result in self.rewards_ts_index.iter() {
+            let (key, value) = result?;
+            report.rewards += 1;
+
+            match serde_json::from_slice::<RewardsDB>(&value) {
+                Ok(reward) if key.as_ref() != &reward.timestamp.to_be_bytes()[..] => {
+                    misplaced.push((key, reward))
+                }
+                Ok(_) => (),
+                Err(_) => report.unreadable_rewards += 1,
+            }
+        }
+
+        for (key, reward) in misplaced {
+            let value: Vec<u8> = serde_json::to_vec(&reward).unwrap();
+            let moved = self.rewards_ts_index.compare_and_swap(
+                reward.timestamp.to_be_bytes(),
+                None as Option<&[u8]>,
+                Some(value),
+            )?;
+
+            if moved.is_ok() {
+                self.rewards_ts_index.remove(key)?;
+                report.rekeyed_rewards += 1;
+            }
+        }
 
         Ok(())
     }
 
-    pub fn get_daemon_status(&self) -> Option<DaemonStatusDB> {
-        if let Some(result) = self.daemon_status_db.get(b"daemon_status").unwrap() {
-            let value: DaemonStatusDB = serde_json::from_slice(&result).unwrap();
-            Some(value)
-        } else {
-            None
+    // The all time totals only ever grow, each stake's are the ones before it plus its reward.
+    // Returns how many stakes had theirs rewritten.
+    pub fn recompute_reward_totals(&self) -> Result<u64> {
+        let mut repaired: u64 = 0;
+        let mut all_time_reward: u64 = 0;
+        let mut all_time_agvr_reward: u64 = 0;
+
+        for result in self.rewards_ts_index.iter() {
+            let (key, value) = result?;
+
+            let mut reward: RewardsDB = match serde_json::from_slice(&value) {
+                Ok(reward) => reward,
+                Err(_) => continue,
+            };
+
+            all_time_reward += reward.reward;
+            all_time_agvr_reward += reward.agvr_reward;
+
+            if reward.all_time_reward != all_time_reward
+                || reward.all_time_agvr_reward != all_time_agvr_reward
+            {
+                reward.all_time_reward = all_time_reward;
+                reward.all_time_agvr_reward = all_time_agvr_reward;
+
+                let value: Vec<u8> = serde_json::to_vec(&reward).unwrap();
+                self.rewards_ts_index.insert(key, value)?;
+                repaired += 1;
+            }
+        }
+
+        Ok(repaired)
+    }
+
+    // Rebuilds a rollup tree when any of its periods don't match the stakes.
+    fn repair_stake_rollups(&self, report: &mut DbMaintenanceDB) -> Result<()> {
+        for period in Division::ALL {
+            let expected: BTreeMap<u64, StakeRollupDB> = self.rollups_from_rewards(period)?;
+            let mut stored: BTreeMap<u64, StakeRollupDB> = BTreeMap::new();
+            let mut wrong: u64 = 0;
+
+            for result in self.rollup_tree(period).iter() {
+                let (key, value) = result?;
+
+                match (
+                    <[u8; 8]>::try_from(key.as_ref()),
+                    serde_json::from_slice::<StakeRollupDB>(&value),
+                ) {
+                    (Ok(period_start), Ok(rollup)) => {
+                        stored.insert(u64::from_be_bytes(period_start), rollup);
+                    }
+                    _ => wrong += 1,
+                }
+            }
+
+            wrong += expected
+                .iter()
+                .filter(|(period_start, rollup)| stored.get(period_start) != Some(rollup))
+                .count() as u64;
+            wrong += stored
+                .keys()
+                .filter(|period_start| !expected.contains_key(period_start))
+                .count() as u64;
+
+            if wrong > 0 {
+                self.store_rollups(period, expected)?;
+                report.rollups_repaired += wrong;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn sum_rewards(&self, start: u64, end: u64) -> StakeRollupDB {
+        let mut totals: StakeRollupDB = StakeRollupDB::default();
+
+        for result in self
+            .rewards_ts_index
+            .range(start.to_be_bytes()..=end.to_be_bytes())
+        {
+            match result {
+                Ok((_, value)) => {
+                    let reward: RewardsDB = serde_json::from_slice(&value).unwrap();
+                    totals.add(&StakeRollupDB::from_reward(&reward));
+                }
+                Err(err) => {
+                    eprintln!("Error during iteration: {:?}", err);
+                }
+            }
+        }
+
+        totals
+    }
+
+    // Stake totals between two timestamps, both inclusive. Whole months and days come from
+    // the rollups, only the partial days at either end are read from the rewards index.
+    pub fn stake_totals(&self, start: u64, end: u64) -> StakeRollupDB {
+        let mut totals: StakeRollupDB = StakeRollupDB::default();
+        let mut cursor: u64 = start;
+
+        while cursor <= end {
+            let next_month: u64 = Division::Month.next_start(&ROLLUP_TZ, cursor);
+            let next_day: u64 = Division::Day.next_start(&ROLLUP_TZ, cursor);
+
+            let (period, next) = if Division::Month.period_start(&ROLLUP_TZ, cursor) == cursor
+                && next_month <= end.saturating_add(1)
+            {
+                (Some(Division::Month), next_month)
+            } else if Division::Day.period_start(&ROLLUP_TZ, cursor) == cursor
+                && next_day <= end.saturating_add(1)
+            {
+                (Some(Division::Day), next_day)
+            } else {
+                (None, next_day.min(end.saturating_add(1)))
+            };
+
+            match period {
+                Some(period) => {
+                    if let Some(rollup) = self.get_rollup(period, cursor) {
+                        totals.add(&rollup);
+                    }
+                }
+                None => totals.add(&self.sum_rewards(cursor, next - 1)),
+            }
+
+            cursor = next;
+        }
+
+        totals
+    }
+
+    pub async fn set_balance_sample(&self, balance: &BalanceSampleDB) -> Result<()> {
+        let key = balance.timestamp.to_be_bytes();
+        let value: Vec<u8> = serde_json::to_vec(&balance).unwrap();
+        self.balance_history_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn balance_history(&self, start: u64, end: u64) -> Vec<BalanceSampleDB> {
+        self.balance_history_db
+            .range(start.to_be_bytes()..end.saturating_add(1).to_be_bytes())
+            .filter_map(|result| serde_json::from_slice(&result.ok()?.1).ok())
+            .collect()
+    }
+
+    // Time weighted average of the staked balance between start and end, along with when the
+    // samples covering that range begin. None until there's a sample at or before end.
+    pub fn average_staked_balance(&self, start: u64, end: u64) -> Option<(u64, u64)> {
+        let sample = |result: Result<(sled::IVec, sled::IVec)>| -> Option<BalanceSampleDB> {
+            serde_json::from_slice(&result.ok()?.1).ok()
+        };
+
+        // The sample in force at start, or the first one after it
+        let mut current: BalanceSampleDB = match self
+            .balance_history_db
+            .range(..=start.to_be_bytes())
+            .next_back()
+            .and_then(sample)
+        {
+            Some(balance) => balance,
+            None => self
+                .balance_history_db
+                .range(start.to_be_bytes()..=end.to_be_bytes())
+                .next()
+                .and_then(sample)?,
+        };
+
+        let covered_from: u64 = current.timestamp.max(start);
+        let mut cursor: u64 = covered_from;
+        let mut weighted: u128 = 0;
+
+        for next in self
+            .balance_history_db
+            .range(cursor.saturating_add(1).to_be_bytes()..=end.to_be_bytes())
+            .filter_map(sample)
+        {
+            weighted += current.currently_staking as u128 * (next.timestamp - cursor) as u128;
+            cursor = next.timestamp;
+            current = next;
+        }
+        weighted += current.currently_staking as u128 * end.saturating_sub(cursor) as u128;
+
+        let covered_secs: u64 = end.saturating_sub(covered_from);
+        if covered_secs == 0 {
+            return Some((current.currently_staking, covered_from));
+        }
+
+        Some(((weighted / covered_secs as u128) as u64, covered_from))
+    }
+
+    pub async fn set_payout(&self, payout: &PayoutDB) -> Result<()> {
+        // Several payouts can share a timestamp, so the txid is appended to keep keys unique
+        let mut key: Vec<u8> = payout.timestamp.to_be_bytes().to_vec();
+        key.extend_from_slice(payout.txid.as_bytes());
+        let value: Vec<u8> = serde_json::to_vec(&payout).unwrap();
+        self.payout_history_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_payout(&self, key: impl AsRef<[u8]>) -> Option<PayoutDB> {
+        if let Some(result) = self.payout_history_db.get(key).unwrap() {
+            let value: PayoutDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub async fn remove_payout(&self, key: impl AsRef<[u8]>) -> Result<()> {
+        self.payout_history_db.remove(key)?;
+        self.gvdb.flush_async().await.unwrap();
+        Ok(())
+    }
+
+    pub async fn set_task(&self, key: impl AsRef<[u8]>, task: &Task) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&task).unwrap();
+        self.task_queue.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_task(&self, key: impl AsRef<[u8]>) -> Option<Task> {
+        if let Some(result) = self.task_queue.get(key).unwrap() {
+            let value: Task = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub async fn remove_task(&self, key: impl AsRef<[u8]>) -> Result<()> {
+        self.task_queue.remove(key)?;
+        self.gvdb.flush_async().await.unwrap();
+        Ok(())
+    }
+
+    pub async fn set_tg_bot_queue(&self, key: impl AsRef<[u8]>, task: &TgBotQueueDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&task).unwrap();
+        self.tg_bot_queue.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_tg_bot_queue(&self, key: impl AsRef<[u8]>) -> Option<TgBotQueueDB> {
+        if let Some(result) = self.tg_bot_queue.get(key).unwrap() {
+            let value: TgBotQueueDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub async fn remove_tg_bot_queue(&self, key: impl AsRef<[u8]>) -> Result<()> {
+        self.tg_bot_queue.remove(key)?;
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub async fn set_zap_status(
+        &self,
+        key: impl AsRef<[u8]>,
+        zap_status: &ZapStatusDB,
+    ) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&zap_status).unwrap();
+        self.zap_status_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_zap_status(&self, key: impl AsRef<[u8]>) -> Option<ZapStatusDB> {
+        if let Some(result) = self.zap_status_db.get(key).unwrap() {
+            let value: ZapStatusDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub async fn remove_zap_status(&self, key: impl AsRef<[u8]>) -> Result<()> {
+        self.zap_status_db.remove(key)?;
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub async fn set_cold_recovery(&self, wallet: &str, cold_recover: &ColdRecovery) -> Result<()> {
+        let key = wallet.as_bytes();
+        let value: Vec<u8> = serde_json::to_vec(&cold_recover).unwrap();
+        self.cold_recovery_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_cold_recovery(&self, key: impl AsRef<[u8]>) -> Option<ColdRecovery> {
+        if let Some(result) = self.cold_recovery_db.get(key).unwrap() {
+            let value: ColdRecovery = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub async fn set_daemon_status(&self, status: &DaemonStatusDB) -> Result<()> {
+        let key: &[u8; 13] = b"daemon_status";
+        let value: Vec<u8> = serde_json::to_vec(&status).unwrap();
+        self.daemon_status_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_daemon_status(&self) -> Option<DaemonStatusDB> {
+        if let Some(result) = self.daemon_status_db.get(b"daemon_status").unwrap() {
+            let value: DaemonStatusDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
         }
     }
 
@@ -328,4 +1290,964 @@ impl GVDB {
 
         Ok(())
     }
+
+    pub async fn set_wallet_rescan(&self, rescan: &WalletRescanDB) -> Result<()> {
+        let key: &[u8; 13] = b"wallet_rescan";
+        let value: Vec<u8> = serde_json::to_vec(&rescan).unwrap();
+        self.wallet_rescan_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_wallet_rescan(&self) -> Option<WalletRescanDB> {
+        if let Some(result) = self.wallet_rescan_db.get(b"wallet_rescan").unwrap() {
+            let value: WalletRescanDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub async fn set_bootstrap(&self, bootstrap: &BootstrapDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&bootstrap).unwrap();
+        self.bootstrap_db.insert(b"bootstrap", value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_bootstrap(&self) -> Option<BootstrapDB> {
+        let result = self.bootstrap_db.get(b"bootstrap").unwrap()?;
+        serde_json::from_slice(&result).ok()
+    }
+
+    // The Telegram message a vault's sync progress is edited into, keyed by vault name.
+    pub async fn set_sync_message(
+        &self,
+        vault_name: &str,
+        msg_id: Option<MessageId>,
+    ) -> Result<()> {
+        match msg_id {
+            Some(msg_id) => {
+                let value: Vec<u8> = serde_json::to_vec(&msg_id).unwrap();
+                self.sync_message_db.insert(vault_name.as_bytes(), value)?;
+            }
+            None => {
+                self.sync_message_db.remove(vault_name.as_bytes())?;
+            }
+        }
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_sync_message(&self, vault_name: &str) -> Option<MessageId> {
+        let result = self.sync_message_db.get(vault_name.as_bytes()).unwrap()?;
+        serde_json::from_slice(&result).ok()
+    }
+
+    pub async fn remove_wallet_rescan(&self) -> Result<()> {
+        self.wallet_rescan_db.remove(b"wallet_rescan")?;
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub async fn set_stake_streak(&self, streak: &StakeStreakDB) -> Result<()> {
+        let key: &[u8; 12] = b"stake_streak";
+        let value: Vec<u8> = serde_json::to_vec(&streak).unwrap();
+        self.stake_streak_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_stake_streak(&self) -> Option<StakeStreakDB> {
+        if let Some(result) = self.stake_streak_db.get(b"stake_streak").unwrap() {
+            let value: StakeStreakDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub async fn set_missed_stake(&self, missed: &MissedStakeDB) -> Result<()> {
+        let key: &[u8; 12] = b"missed_stake";
+        let value: Vec<u8> = serde_json::to_vec(&missed).unwrap();
+        self.stake_streak_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_missed_stake(&self) -> Option<MissedStakeDB> {
+        if let Some(result) = self.stake_streak_db.get(b"missed_stake").unwrap() {
+            let value: MissedStakeDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub async fn set_pending_update(&self, pending: &PendingUpdateDB) -> Result<()> {
+        let key: &[u8; 14] = b"pending_update";
+        let value: Vec<u8> = serde_json::to_vec(&pending).unwrap();
+        self.pending_update_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_pending_update(&self) -> Option<PendingUpdateDB> {
+        if let Some(result) = self.pending_update_db.get(b"pending_update").unwrap() {
+            let value: PendingUpdateDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub async fn remove_pending_update(&self) -> Result<()> {
+        self.pending_update_db.remove(b"pending_update")?;
+        self.gvdb.flush_async().await.unwrap();
+        Ok(())
+    }
+
+    pub async fn set_chain_emergency(&self, emergency: &ChainEmergencyDB) -> Result<()> {
+        let key: &[u8; 15] = b"chain_emergency";
+        let value: Vec<u8> = serde_json::to_vec(&emergency).unwrap();
+        self.chain_emergency_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_chain_emergency(&self) -> Option<ChainEmergencyDB> {
+        if let Some(result) = self.chain_emergency_db.get(b"chain_emergency").unwrap() {
+            let value: ChainEmergencyDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub async fn remove_chain_emergency(&self) -> Result<()> {
+        self.chain_emergency_db.remove(b"chain_emergency")?;
+        self.gvdb.flush_async().await.unwrap();
+        Ok(())
+    }
+
+    pub async fn set_announce_route(
+        &self,
+        event: &str,
+        destinations: &Vec<AnnounceDestination>,
+    ) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&destinations).unwrap();
+        self.announce_routes_db.insert(event, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_announce_route(&self, event: &str) -> Option<Vec<AnnounceDestination>> {
+        if let Some(result) = self.announce_routes_db.get(event).unwrap() {
+            let value: Vec<AnnounceDestination> = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub async fn set_tenant(&self, tenant: &TenantDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&tenant).unwrap();
+        self.tenants_db.insert(&tenant.name, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_tenant(&self, name: &str) -> Option<TenantDB> {
+        if let Some(result) = self.tenants_db.get(name).unwrap() {
+            let value: TenantDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_tenants(&self) -> Vec<TenantDB> {
+        self.tenants_db
+            .iter()
+            .filter_map(|result| serde_json::from_slice(&result.ok()?.1).ok())
+            .collect()
+    }
+
+    pub async fn remove_tenant(&self, name: &str) -> Result<()> {
+        self.tenants_db.remove(name)?;
+        self.gvdb.flush_async().await.unwrap();
+        Ok(())
+    }
+
+    pub async fn add_tenant_ledger(&self, entry: &TenantLedgerDB) -> Result<()> {
+        // Keyed by time first so a range walks the ledger in order
+        let mut key: Vec<u8> = entry.timestamp.to_be_bytes().to_vec();
+        key.extend_from_slice(entry.tenant.as_bytes());
+        key.extend_from_slice(entry.txid.as_bytes());
+        let value: Vec<u8> = serde_json::to_vec(&entry).unwrap();
+        self.tenant_ledger_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn tenant_ledger(&self, tenant: &str, start: u64, end: u64) -> Vec<TenantLedgerDB> {
+        self.tenant_ledger_db
+            .range(start.to_be_bytes()..end.saturating_add(1).to_be_bytes())
+            .filter_map(|result| serde_json::from_slice(&result.ok()?.1).ok())
+            .filter(|entry: &TenantLedgerDB| entry.tenant == tenant)
+            .collect()
+    }
+
+    pub async fn set_shared_vault(&self, shared_vault: &SharedVaultDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&shared_vault).unwrap();
+        self.shared_vault_db.insert(b"shared_vault", value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_shared_vault(&self) -> Option<SharedVaultDB> {
+        if let Some(result) = self.shared_vault_db.get(b"shared_vault").unwrap() {
+            let value: SharedVaultDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub async fn set_frozen_utxo(&self, utxo: &FrozenUtxoDB) -> Result<()> {
+        let key: String = format!("{}:{}:{}", utxo.wallet, utxo.txid, utxo.vout);
+        let value: Vec<u8> = serde_json::to_vec(&utxo).unwrap();
+        self.frozen_utxos_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_frozen_utxo(&self, wallet: &str, txid: &str, vout: u32) -> Option<FrozenUtxoDB> {
+        let key: String = format!("{}:{}:{}", wallet, txid, vout);
+        if let Some(result) = self.frozen_utxos_db.get(key).unwrap() {
+            let value: FrozenUtxoDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_frozen_utxos(&self, wallet: &str) -> Vec<FrozenUtxoDB> {
+        self.frozen_utxos_db
+            .scan_prefix(format!("{}:", wallet))
+            .filter_map(|result| serde_json::from_slice(&result.ok()?.1).ok())
+            .collect()
+    }
+
+    pub async fn remove_frozen_utxo(&self, wallet: &str, txid: &str, vout: u32) -> Result<()> {
+        let key: String = format!("{}:{}:{}", wallet, txid, vout);
+        self.frozen_utxos_db.remove(key)?;
+        self.gvdb.flush_async().await.unwrap();
+        Ok(())
+    }
+
+    pub async fn set_pending_split(&self, pending: &PendingSplitDB) -> Result<()> {
+        let key: &[u8; 13] = b"pending_split";
+        let value: Vec<u8> = serde_json::to_vec(&pending).unwrap();
+        self.pending_split_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_pending_split(&self) -> Option<PendingSplitDB> {
+        if let Some(result) = self.pending_split_db.get(b"pending_split").unwrap() {
+            let value: PendingSplitDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub async fn remove_pending_split(&self) -> Result<()> {
+        self.pending_split_db.remove(b"pending_split")?;
+        self.gvdb.flush_async().await.unwrap();
+        Ok(())
+    }
+
+    pub async fn set_pending_reward_change(&self, pending: &PendingRewardChangeDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&pending).unwrap();
+        self.pending_reward_change_db
+            .insert(b"pending_reward_change", value)
+            .unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_pending_reward_change(&self) -> Option<PendingRewardChangeDB> {
+        self.pending_reward_change_db
+            .get(b"pending_reward_change")
+            .unwrap()
+            .and_then(|value| serde_json::from_slice(&value).ok())
+    }
+
+    pub async fn remove_pending_reward_change(&self) -> Result<()> {
+        self.pending_reward_change_db
+            .remove(b"pending_reward_change")?;
+        self.gvdb.flush_async().await.unwrap();
+        Ok(())
+    }
+
+    pub async fn set_onboarding(&self, onboarding: &OnboardingDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&onboarding).unwrap();
+        self.onboarding_db.insert(b"onboarding", value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_onboarding(&self) -> OnboardingDB {
+        match self.onboarding_db.get(b"onboarding").unwrap() {
+            Some(result) => serde_json::from_slice(&result).unwrap(),
+            None => OnboardingDB::default(),
+        }
+    }
+
+    pub async fn set_digest(&self, digest: &DigestDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&digest).unwrap();
+        self.digest_db.insert(b"digest", value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_digest(&self) -> DigestDB {
+        match self.digest_db.get(b"digest").unwrap() {
+            Some(result) => serde_json::from_slice(&result).unwrap(),
+            None => DigestDB::default(),
+        }
+    }
+
+    pub async fn set_maintenance(&self, maintenance: &MaintenanceDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&maintenance).unwrap();
+        self.maintenance_db.insert(b"maintenance", value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_maintenance(&self) -> MaintenanceDB {
+        match self.maintenance_db.get(b"maintenance").unwrap() {
+            Some(result) => serde_json::from_slice(&result).unwrap(),
+            None => MaintenanceDB::default(),
+        }
+    }
+
+    pub async fn set_db_maintenance(&self, report: &DbMaintenanceDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&report).unwrap();
+        self.maintenance_db
+            .insert(b"db_maintenance", value)
+            .unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_db_maintenance(&self) -> Option<DbMaintenanceDB> {
+        self.maintenance_db
+            .get(b"db_maintenance")
+            .unwrap()
+            .map(|result| serde_json::from_slice(&result).unwrap())
+    }
+
+    pub async fn set_system_sample(&self, sample: &SystemSampleDB) -> Result<()> {
+        let key = sample.timestamp.to_be_bytes();
+        let value: Vec<u8> = serde_json::to_vec(&sample).unwrap();
+        self.system_stats_db.insert(key, value).unwrap();
+
+        let cutoff: u64 = sample.timestamp.saturating_sub(SYSTEM_STATS_RETENTION);
+        for key in self
+            .system_stats_db
+            .range(..cutoff.to_be_bytes())
+            .keys()
+            .filter_map(|key| key.ok())
+        {
+            self.system_stats_db.remove(key).unwrap();
+        }
+
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub async fn set_network_sample(&self, sample: &NetworkSampleDB) -> Result<()> {
+        let key = sample.timestamp.to_be_bytes();
+        let value: Vec<u8> = serde_json::to_vec(&sample).unwrap();
+        self.network_stats_db.insert(key, value).unwrap();
+
+        let cutoff: u64 = sample.timestamp.saturating_sub(NETWORK_STATS_RETENTION);
+        for key in self
+            .network_stats_db
+            .range(..cutoff.to_be_bytes())
+            .keys()
+            .filter_map(|key| key.ok())
+        {
+            self.network_stats_db.remove(key).unwrap();
+        }
+
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn network_history(&self, start: u64, end: u64) -> Vec<NetworkSampleDB> {
+        self.network_stats_db
+            .range(start.to_be_bytes()..end.saturating_add(1).to_be_bytes())
+            .filter_map(|result| serde_json::from_slice(&result.ok()?.1).ok())
+            .collect()
+    }
+
+    pub async fn set_cold_wallet(&self, wallet: &ColdWalletDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&wallet).unwrap();
+        self.cold_wallets_db.insert(&wallet.name, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_cold_wallet(&self, name: &str) -> Option<ColdWalletDB> {
+        if let Some(result) = self.cold_wallets_db.get(name).unwrap() {
+            let value: ColdWalletDB = serde_json::from_slice(&result).unwrap();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_cold_wallets(&self) -> Vec<ColdWalletDB> {
+        self.cold_wallets_db
+            .iter()
+            .filter_map(|result| serde_json::from_slice(&result.ok()?.1).ok())
+            .collect()
+    }
+
+    // The wallet the config MNEMONIC was created for, None while it is still the primary's.
+    pub fn mnemonic_wallet(&self) -> Option<String> {
+        self.get_cold_wallets()
+            .into_iter()
+            .find(|wallet| wallet.holds_mnemonic)
+            .map(|wallet| wallet.name)
+    }
+
+    pub async fn set_zap_request(&self, request: &ZapRequestDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&request).unwrap();
+        self.zap_requests_db
+            .insert(&request.stake_addr, value)
+            .unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_zap_requests(&self) -> Vec<ZapRequestDB> {
+        self.zap_requests_db
+            .iter()
+            .filter_map(|result| serde_json::from_slice(&result.ok()?.1).ok())
+            .collect()
+    }
+
+    pub async fn remove_zap_request(&self, stake_addr: &str) -> Result<()> {
+        self.zap_requests_db.remove(stake_addr)?;
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn next_expected_deposit_id(&self) -> u64 {
+        self.gvdb.generate_id().unwrap() + 1
+    }
+
+    pub async fn set_expected_deposit(&self, deposit: &ExpectedDepositDB) -> Result<()> {
+        let key = deposit.id.to_be_bytes();
+        let value: Vec<u8> = serde_json::to_vec(&deposit).unwrap();
+        self.expected_deposits_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    // Oldest first.
+    pub fn get_expected_deposits(&self) -> Vec<ExpectedDepositDB> {
+        self.expected_deposits_db
+            .iter()
+            .filter_map(|result| serde_json::from_slice(&result.ok()?.1).ok())
+            .collect()
+    }
+
+    pub async fn remove_expected_deposit(&self, id: u64) -> Result<bool> {
+        let removed: bool = self
+            .expected_deposits_db
+            .remove(id.to_be_bytes())?
+            .is_some();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(removed)
+    }
+
+    pub async fn set_anon_conversion(&self, conversion: &AnonConversionDB) -> Result<()> {
+        let key = conversion.timestamp.to_be_bytes();
+        let value: Vec<u8> = serde_json::to_vec(&conversion).unwrap();
+        self.anon_conversions_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    // Oldest first.
+    pub fn get_anon_conversions(&self) -> Vec<AnonConversionDB> {
+        self.anon_conversions_db
+            .iter()
+            .filter_map(|result| serde_json::from_slice(&result.ok()?.1).ok())
+            .collect()
+    }
+
+    pub async fn remove_anon_conversion(&self, timestamp: u64) -> Result<()> {
+        self.anon_conversions_db.remove(timestamp.to_be_bytes())?;
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub async fn set_tx_label(&self, label: &TxLabelDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&label).unwrap();
+        self.tx_labels_db
+            .insert(label.txid.as_bytes(), value)
+            .unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_tx_label(&self, txid: &str) -> Option<String> {
+        let value = self.tx_labels_db.get(txid.as_bytes()).ok()??;
+        let label: TxLabelDB = serde_json::from_slice(&value).ok()?;
+
+        Some(label.label)
+    }
+
+    pub fn get_tx_labels(&self) -> Vec<TxLabelDB> {
+        self.tx_labels_db
+            .iter()
+            .filter_map(|result| serde_json::from_slice(&result.ok()?.1).ok())
+            .collect()
+    }
+
+    pub async fn remove_tx_label(&self, txid: &str) -> Result<bool> {
+        let removed: bool = self.tx_labels_db.remove(txid.as_bytes())?.is_some();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(removed)
+    }
+
+    pub async fn set_address_label(&self, entry: &AddressBookDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&entry).unwrap();
+        self.address_book_db
+            .insert(entry.address.as_bytes(), value)
+            .unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_address_label(&self, address: &str) -> Option<String> {
+        let value = self.address_book_db.get(address.as_bytes()).ok()??;
+        let entry: AddressBookDB = serde_json::from_slice(&value).ok()?;
+
+        Some(entry.label)
+    }
+
+    // Labels are unique in the book and matched case insensitively.
+    pub fn find_labeled_address(&self, label: &str) -> Option<AddressBookDB> {
+        self.get_address_book()
+            .into_iter()
+            .find(|entry| entry.label.eq_ignore_ascii_case(label.trim()))
+    }
+
+    pub fn get_address_book(&self) -> Vec<AddressBookDB> {
+        self.address_book_db
+            .iter()
+            .filter_map(|result| serde_json::from_slice(&result.ok()?.1).ok())
+            .collect()
+    }
+
+    pub async fn remove_address_label(&self, address: &str) -> Result<bool> {
+        let removed: bool = self.address_book_db.remove(address.as_bytes())?.is_some();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(removed)
+    }
+
+    // Only the last RESTART_HISTORY_MAX restarts are kept.
+    pub async fn set_restart(&self, restart: &RestartDB) -> Result<()> {
+        let key = restart.timestamp.to_be_bytes();
+        let value: Vec<u8> = serde_json::to_vec(&restart).unwrap();
+        self.restart_history_db.insert(key, value).unwrap();
+
+        while self.restart_history_db.len() > RESTART_HISTORY_MAX {
+            self.restart_history_db.pop_min().unwrap();
+        }
+
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    // Newest first.
+    pub fn get_restart_history(&self, limit: usize) -> Vec<RestartDB> {
+        self.restart_history_db
+            .iter()
+            .values()
+            .rev()
+            .filter_map(|value| value.ok())
+            .filter_map(|value| serde_json::from_slice(&value).ok())
+            .take(limit)
+            .collect()
+    }
+
+    pub fn next_audit_id(&self) -> u64 {
+        self.gvdb.generate_id().unwrap() + 1
+    }
+
+    // Nothing is ever removed from the audit log.
+    pub async fn set_audit(&self, entry: &AuditDB) -> Result<()> {
+        let key = entry.id.to_be_bytes();
+        let value: Vec<u8> = serde_json::to_vec(&entry).unwrap();
+        self.audit_log_db.insert(key, value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    // Newest first.
+    pub fn get_audit_log(&self, limit: usize) -> Vec<AuditDB> {
+        self.audit_log_db
+            .iter()
+            .values()
+            .rev()
+            .filter_map(|value| value.ok())
+            .filter_map(|value| serde_json::from_slice(&value).ok())
+            .take(limit)
+            .collect()
+    }
+
+    pub fn next_job_id(&self) -> u64 {
+        self.gvdb.generate_id().unwrap() + 1
+    }
+
+    // Only the last JOB_HISTORY_MAX jobs are kept.
+    pub async fn set_job(&self, job: &JobDB) -> Result<()> {
+        let key = job.id.to_be_bytes();
+        let value: Vec<u8> = serde_json::to_vec(&job).unwrap();
+        self.jobs_db.insert(key, value).unwrap();
+
+        while self.jobs_db.len() > JOB_HISTORY_MAX {
+            self.jobs_db.pop_min().unwrap();
+        }
+
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    // Newest first.
+    pub fn get_jobs(&self, limit: usize) -> Vec<JobDB> {
+        self.jobs_db
+            .iter()
+            .values()
+            .rev()
+            .filter_map(|value| value.ok())
+            .filter_map(|value| serde_json::from_slice(&value).ok())
+            .take(limit)
+            .collect()
+    }
+
+    // Jobs still running when ghostvaultd stopped never finished, called once at startup.
+    pub async fn interrupt_running_jobs(&self) -> Result<Vec<JobDB>> {
+        let current_time: u64 = chrono::Utc::now().timestamp() as u64;
+        let mut interrupted: Vec<JobDB> = Vec::new();
+
+        let running: Vec<JobDB> = self
+            .jobs_db
+            .iter()
+            .values()
+            .filter_map(|value| value.ok())
+            .filter_map(|value| serde_json::from_slice::<JobDB>(&value).ok())
+            .filter(|job| job.state == "running")
+            .collect();
+
+        for mut job in running {
+            job.state = "interrupted".to_string();
+            job.finished = Some(current_time);
+            job.error = Some("ghostvaultd stopped before it finished".to_string());
+            self.set_job(&job).await?;
+            interrupted.push(job);
+        }
+
+        Ok(interrupted)
+    }
+
+    // None turns quiet hours off for the destination.
+    pub async fn set_quiet_hours(
+        &self,
+        destination: AnnounceDestination,
+        hours: Option<QuietHoursDB>,
+    ) -> Result<()> {
+        match hours {
+            Some(hours) => {
+                let value: Vec<u8> = serde_json::to_vec(&hours).unwrap();
+                self.quiet_hours_db.insert(destination.as_str(), value)?;
+            }
+            None => {
+                self.quiet_hours_db.remove(destination.as_str())?;
+            }
+        }
+        self.gvdb.flush_async().await?;
+
+        Ok(())
+    }
+
+    pub fn get_quiet_hours(&self, destination: AnnounceDestination) -> Option<QuietHoursDB> {
+        self.quiet_hours_db
+            .get(destination.as_str())
+            .ok()
+            .flatten()
+            .and_then(|value| serde_json::from_slice(&value).ok())
+    }
+
+    // None goes back to the vault's TIMEZONE.
+    pub async fn set_chat_timezone(&self, chat_id: i64, timezone: Option<&str>) -> Result<()> {
+        match timezone {
+            Some(timezone) => {
+                let entry: ChatTimezoneDB = ChatTimezoneDB {
+                    chat_id,
+                    timezone: timezone.to_string(),
+                };
+                let value: Vec<u8> = serde_json::to_vec(&entry).unwrap();
+                self.chat_timezones_db
+                    .insert(chat_id.to_be_bytes(), value)?;
+            }
+            None => {
+                self.chat_timezones_db.remove(chat_id.to_be_bytes())?;
+            }
+        }
+        self.gvdb.flush_async().await?;
+
+        Ok(())
+    }
+
+    pub fn get_chat_timezone(&self, chat_id: i64) -> Option<String> {
+        self.chat_timezones_db
+            .get(chat_id.to_be_bytes())
+            .ok()
+            .flatten()
+            .and_then(|value| serde_json::from_slice::<ChatTimezoneDB>(&value).ok())
+            .map(|entry| entry.timezone)
+    }
+
+    // None goes back to the built in text.
+    pub async fn set_announce_template(
+        &self,
+        msg_type: &str,
+        template: Option<&AnnounceTemplateDB>,
+    ) -> Result<()> {
+        match template {
+            Some(template) => {
+                let value: Vec<u8> = serde_json::to_vec(template).unwrap();
+                self.announce_templates_db.insert(msg_type, value)?;
+            }
+            None => {
+                self.announce_templates_db.remove(msg_type)?;
+            }
+        }
+        self.gvdb.flush_async().await?;
+
+        Ok(())
+    }
+
+    pub fn get_announce_template(&self, msg_type: &str) -> Option<AnnounceTemplateDB> {
+        self.announce_templates_db
+            .get(msg_type)
+            .ok()
+            .flatten()
+            .and_then(|value| serde_json::from_slice(&value).ok())
+    }
+
+    // Keyed by destination then a generated id, so each destination's notices stay in order.
+    pub async fn hold_notice(
+        &self,
+        destination: AnnounceDestination,
+        notice: &HeldNoticeDB,
+    ) -> Result<()> {
+        let mut key: Vec<u8> = format!("{}:", destination.as_str()).into_bytes();
+        key.extend_from_slice(&self.gvdb.generate_id()?.to_be_bytes());
+
+        let value: Vec<u8> = serde_json::to_vec(&notice).unwrap();
+        self.held_notices_db.insert(key, value)?;
+        self.gvdb.flush_async().await?;
+
+        Ok(())
+    }
+
+    pub fn count_held_notices(&self, destination: AnnounceDestination) -> usize {
+        self.held_notices_db
+            .scan_prefix(format!("{}:", destination.as_str()))
+            .count()
+    }
+
+    // Oldest first, the notices are removed as they're taken.
+    pub async fn take_held_notices(
+        &self,
+        destination: AnnounceDestination,
+    ) -> Result<Vec<HeldNoticeDB>> {
+        let mut notices: Vec<HeldNoticeDB> = Vec::new();
+
+        for result in self
+            .held_notices_db
+            .scan_prefix(format!("{}:", destination.as_str()))
+        {
+            let (key, value) = result?;
+            self.held_notices_db.remove(key)?;
+
+            if let Ok(notice) = serde_json::from_slice(&value) {
+                notices.push(notice);
+            }
+        }
+
+        if !notices.is_empty() {
+            self.gvdb.flush_async().await?;
+        }
+
+        Ok(notices)
+    }
+
+    // The last sample taken at or before timestamp.
+    pub fn system_sample_before(&self, timestamp: u64) -> Option<SystemSampleDB> {
+        let (_, value) = self
+            .system_stats_db
+            .range(..=timestamp.to_be_bytes())
+            .next_back()?
+            .ok()?;
+
+        serde_json::from_slice(&value).ok()
+    }
+
+    pub async fn set_disk_alert(&self, alert: &DiskAlertDB) -> Result<()> {
+        let value: Vec<u8> = serde_json::to_vec(&alert).unwrap();
+        self.disk_alert_db.insert(b"disk_alert", value).unwrap();
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_disk_alert(&self) -> DiskAlertDB {
+        match self.disk_alert_db.get(b"disk_alert").unwrap() {
+            Some(result) => serde_json::from_slice(&result).unwrap(),
+            None => DiskAlertDB::default(),
+        }
+    }
+
+    // Ids only ever go up, even across restarts, so clients can use the last one seen as a
+    // cursor. 0 is left free to mean "from the start".
+    pub fn next_event_id(&self) -> u64 {
+        self.gvdb.generate_id().unwrap() + 1
+    }
+
+    pub async fn set_event(&self, event: &GvEvent) -> Result<()> {
+        let key = event.id.to_be_bytes();
+        let value: Vec<u8> = serde_json::to_vec(&event).unwrap();
+        self.event_log_db.insert(key, value).unwrap();
+
+        // Events are keyed in the order they happened, so the expired ones are all at the front
+        let cutoff: u64 = event.timestamp.saturating_sub(EVENT_LOG_RETENTION);
+        while let Some((key, value)) = self.event_log_db.first().unwrap() {
+            let oldest: GvEvent = serde_json::from_slice(&value).unwrap();
+            if oldest.timestamp >= cutoff {
+                break;
+            }
+            self.event_log_db.remove(key).unwrap();
+        }
+
+        self.gvdb.flush_async().await.unwrap();
+
+        Ok(())
+    }
+
+    pub fn get_events_since(&self, cursor: u64, limit: usize) -> Vec<GvEvent> {
+        self.event_log_db
+            .range(cursor.saturating_add(1).to_be_bytes()..)
+            .take(limit)
+            .filter_map(|result| result.ok())
+            .map(|(_, value)| serde_json::from_slice(&value).unwrap())
+            .collect()
+    }
+
+    // Destinations for a queued message, unrouted events go to the Telegram admin.
+    pub fn announce_destinations(&self, msg_type: &str) -> Vec<AnnounceDestination> {
+        announce::event_for_msg_type(msg_type)
+            .and_then(|event| self.get_announce_route(event))
+            .unwrap_or(vec![AnnounceDestination::TelegramAdmin])
+    }
+}
+
+/// Moves an existing database to a new location, verifying the copy before removing the original.
+pub async fn migrate_db(
+    old_dir: &PathBuf,
+    new_dir: &PathBuf,
+) -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if old_dir == new_dir || !old_dir.exists() {
+        return Ok(());
+    }
+
+    if new_dir.join("db").exists() {
+        return Err(format!(
+            "A database already exists at {}, refusing to overwrite it",
+            new_dir.display()
+        )
+        .into());
+    }
+
+    info!(
+        "Migrating database from {} to {}...",
+        old_dir.display(),
+        new_dir.display()
+    );
+
+    let old_db: Db = sled::Config::new().path(old_dir).open()?;
+    let new_db: Db = sled::Config::new().path(new_dir).open()?;
+
+    new_db.import(old_db.export());
+    new_db.flush_async().await?;
+
+    if old_db.checksum()? != new_db.checksum()? {
+        drop(new_db);
+        file_ops::rm_dir(new_dir)?;
+        return Err("Database checksum mismatch after migration, original left in place".into());
+    }
+
+    drop(old_db);
+    drop(new_db);
+
+    file_ops::rm_dir(old_dir)?;
+    info!("Database migration complete!");
+
+    Ok(())
 }
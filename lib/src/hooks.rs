@@ -0,0 +1,100 @@
+// User scripts run on events, so GhostVault can be tied into anything without a notifier for
+// each. A script gets {"hook": ..., "timestamp": ..., "data": {...}} on stdin and the hook name
+// in GV_HOOK. Hooks run in the background, one that fails or hangs is logged and killed.
+use crate::config::HookSettings;
+use log::{info, warn};
+use serde_json::Value;
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+use tokio::{io::AsyncWriteExt, process::Command};
+
+pub const HOOK_EVENTS: &[&str] = &[
+    "new_stake",
+    "zap",
+    "payout_sent",
+    "daemon_offline",
+    "daemon_online",
+    "sync",
+    "update_complete",
+];
+
+// The hook run for an event stream event.
+pub fn hook_for_event(event: &str) -> Option<&'static str> {
+    match event {
+        "stake" => Some("new_stake"),
+        "zap" => Some("zap"),
+        "payout" => Some("payout_sent"),
+        "offline" => Some("daemon_offline"),
+        "online" => Some("daemon_online"),
+        "sync" => Some("sync"),
+        "update" => Some("update_complete"),
+        _ => None,
+    }
+}
+
+pub fn run_hooks(settings: &HookSettings, hook: &str, timestamp: u64, data: &Value) {
+    let scripts: &[PathBuf] = match settings.scripts.get(hook) {
+        Some(scripts) => scripts,
+        None => return,
+    };
+
+    let payload: String = serde_json::json!({
+        "hook": hook,
+        "timestamp": timestamp,
+        "data": data,
+    })
+    .to_string();
+    let timeout: Duration = Duration::from_secs(settings.timeout);
+
+    for script in scripts {
+        let script: PathBuf = script.clone();
+        let hook: String = hook.to_string();
+        let payload: String = payload.clone();
+
+        tokio::spawn(async move {
+            match tokio::time::timeout(timeout, run_hook(&script, &hook, &payload)).await {
+                Ok(Ok(())) => info!("Ran {} hook {}", hook, script.display()),
+                Ok(Err(err)) => warn!("{} hook {} failed: {}", hook, script.display(), err),
+                Err(_) => warn!(
+                    "{} hook {} killed after {} seconds",
+                    hook,
+                    script.display(),
+                    timeout.as_secs()
+                ),
+            }
+        });
+    }
+}
+
+// kill_on_drop stops the script when the timeout drops this future.
+async fn run_hook(
+    script: &Path,
+    hook: &str,
+    payload: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut child = Command::new(script)
+        .env("GV_HOOK", hook)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    // A script is free to ignore the payload, so a closed stdin isn't an error.
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(payload.as_bytes()).await;
+    }
+
+    let output = child.wait_with_output().await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{}: {}", output.status, stderr.trim()).into());
+    }
+
+    Ok(())
+}
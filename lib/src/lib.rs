@@ -1,22 +1,37 @@
-use serde_json::Value;
-
+pub mod amount;
+pub mod announce;
+pub mod api_schema;
+pub mod backup;
+pub mod cli_tls;
 pub mod config;
+pub mod config_check;
 pub mod constants;
 pub mod daemon_helper;
+pub mod event_bus;
+pub mod explorer;
 pub mod file_ops;
 pub mod gv_client_methods;
 pub mod gv_home_init;
 pub mod gv_methods;
 pub mod gvdb;
+pub mod hooks;
+#[cfg(feature = "parquet")]
+pub mod parquet_export;
+pub mod remote_backup;
+pub mod response;
 pub mod rpc;
+pub mod stats;
 pub mod task_runner;
 pub mod term_link;
+pub mod time_ranges;
 pub mod tg_bot {
     pub mod bot_tasks;
+    pub mod commands;
     pub mod keyboards;
     pub mod tg_bot;
     pub mod dialogs {
         pub mod chart_range_dialog;
+        pub mod onboarding_dialog;
         pub mod reward_interval_dialog;
         pub mod reward_min_dialog;
         pub mod reward_mode_dialog;
@@ -27,37 +42,227 @@ pub mod tg_bot {
     }
 }
 
-use crate::daemon_helper::TxidAndWallet;
+use crate::{
+    api_schema::ApiMethod,
+    backup::{BackupInfo, MigrateInfo},
+    config::ConfigReload,
+    daemon_helper::TxidAndWallet,
+    explorer::ExplorerHealth,
+    gv_client_methods::{
+        AddressBookEntry, AllTimeEarnigns, AnnounceRoute, AnnounceTemplate, AnonymizationStatus,
+        ApiCapabilities, BalanceHistory, BarChart, BlockSummary, ChainEmergency, ChainTip,
+        ChartPage, ColdWalletCreated, ColdWallets, Consolidation, DaemonOnline, DaemonUpdate,
+        Delegations, Digest, DigestSettings, EventPage, ExpectedDeposit, GVStatus,
+        HotWalletCreated, HotWalletInfo, HotWalletScript, MaintenanceWindow, NetworkStats,
+        OperationStatus, ParquetExportSummary, PayoutHistory, PendingRewards, PendingSplit,
+        QuietHours, RecentLogs, RewardOptions, RewardSimulation, ScheduledTask, StakeForecast,
+        StakeHeatmap, StakesByAddress, StakingDataOverview, StakingRoi, SystemStats, Tenant,
+        TenantStatement, TxLabel, TxSummary, Utxo, VersionInfo, ZapRequest,
+    },
+    gvdb::{
+        AddressInfo, AuditDB, BootstrapDB, DbMaintenanceDB, JobDB, PendingRewardChangeDB,
+        RestartDB, TgBotQueueDB, WalletRescanDB,
+    },
+    response::GvReply,
+};
 
+// Every method answers with its data typed, see response.rs for the envelope around it.
 #[tarpc::service]
 pub trait GvCLI {
-    async fn getblockcount() -> Value;
-    async fn shutdown() -> Value;
-    async fn force_resync() -> Value;
-    async fn set_reward_mode(mode: String, addr: Option<String>) -> Value;
-    async fn set_payout_min(min: f64) -> Value;
-    async fn get_ext_pub_key() -> Value;
-    async fn set_reward_interval(interval: String) -> Value;
-    async fn enable_telegram_bot(token: String, user: String) -> Value;
-    async fn disable_telegram_bot() -> Value;
+    async fn getblockcount() -> GvReply<u64>;
+    async fn shutdown() -> GvReply<String>;
+    async fn force_resync(force: bool) -> GvReply<String>;
+    async fn set_reward_mode(mode: String, addr: Option<String>) -> GvReply<String>;
+    async fn set_payout_min(min: f64) -> GvReply<String>;
+    async fn get_ext_pub_key() -> GvReply<String>;
+    async fn set_reward_interval(interval: String) -> GvReply<String>;
+    async fn enable_telegram_bot(token: String, user: String) -> GvReply<String>;
+    async fn disable_telegram_bot() -> GvReply<String>;
     async fn new_block(block_hash: String);
-    async fn get_daemon_state() -> Value;
+    async fn get_daemon_state() -> GvReply<GVStatus>;
+    async fn get_daemon_state_tz(timezone: String) -> GvReply<GVStatus>;
     async fn new_wallet_tx(txid_and_wal: TxidAndWallet);
-    async fn process_daemon_update() -> Value;
+    async fn process_daemon_update(force: bool) -> GvReply<DaemonUpdate>;
     async fn process_payouts();
     async fn start_server_tasks();
-    async fn set_bot_announce(msg_type: String, new_val: bool) -> Value;
-    async fn get_version_info() -> Value;
-    async fn check_chain() -> Value;
-    async fn get_reward_options() -> Value;
-    async fn validate_address(addr: String) -> Value;
-    async fn get_daemon_online() -> Value;
-    async fn get_stake_barchart_data(start: u64, end: u64, division: String) -> Value;
-    async fn get_earnings_chart_data(start: u64, end: u64) -> Value;
-    async fn set_timezone(timezone: String) -> Value;
-    async fn get_pending_rewards() -> Value;
-    async fn get_overview() -> Value;
-    async fn get_mnemonic() -> Value;
-    async fn import_wallet(mnemonic: String, name: String) -> Value;
+    async fn set_bot_announce(msg_type: String, new_val: bool) -> GvReply<String>;
+    async fn get_version_info() -> GvReply<VersionInfo>;
+    async fn check_chain() -> GvReply<bool>;
+    async fn get_reward_options() -> GvReply<RewardOptions>;
+    async fn validate_address(addr: String) -> GvReply<AddressInfo>;
+    async fn get_daemon_online() -> GvReply<DaemonOnline>;
+    async fn get_stake_barchart_data(start: u64, end: u64, division: String) -> GvReply<BarChart>;
+    async fn get_earnings_chart_data(start: u64, end: u64) -> GvReply<AllTimeEarnigns>;
+    async fn get_stake_barchart_data_tz(
+        start: u64,
+        end: u64,
+        division: String,
+        timezone: String,
+    ) -> GvReply<BarChart>;
+    async fn get_earnings_chart_data_tz(
+        start: u64,
+        end: u64,
+        timezone: String,
+    ) -> GvReply<AllTimeEarnigns>;
+    async fn get_stake_barchart_page(
+        start: u64,
+        end: u64,
+        division: String,
+        page: ChartPage,
+    ) -> GvReply<BarChart>;
+    async fn get_earnings_chart_page(
+        start: u64,
+        end: u64,
+        page: ChartPage,
+    ) -> GvReply<AllTimeEarnigns>;
+    async fn set_timezone(timezone: String) -> GvReply<String>;
+    async fn get_pending_rewards() -> GvReply<PendingRewards>;
+    async fn get_overview() -> GvReply<StakingDataOverview>;
+    async fn get_mnemonic(passphrase: String) -> GvReply<Option<String>>;
+    async fn import_wallet(
+        mnemonic: String,
+        name: String,
+        birthday: Option<String>,
+    ) -> GvReply<String>;
     async fn new_remote_block(block_hash: String, height: u32);
+    async fn get_payout_history(start: u64, end: u64) -> GvReply<PayoutHistory>;
+    async fn rescan_wallet(start_height: u32) -> GvReply<String>;
+    async fn get_rescan_status() -> GvReply<WalletRescanDB>;
+    async fn process_wallet_rescan();
+    async fn create_hot_wallet(name: String) -> GvReply<HotWalletCreated>;
+    async fn link_hot_wallet(name: String) -> GvReply<String>;
+    async fn get_hot_wallet_info() -> GvReply<HotWalletInfo>;
+    async fn get_hot_wallet_script() -> GvReply<HotWalletScript>;
+    async fn describe_api() -> GvReply<Vec<ApiMethod>>;
+    async fn get_api_capabilities(client_api_version: u32) -> GvReply<ApiCapabilities>;
+    async fn set_wallet_passphrase(passphrase: String) -> GvReply<String>;
+    async fn unlock_wallet(passphrase: String) -> GvReply<String>;
+    async fn unlock_for_payout(passphrase: String) -> GvReply<String>;
+    async fn get_wallet_lock_status() -> GvReply<String>;
+    async fn export_parquet(out_dir: String) -> GvReply<ParquetExportSummary>;
+    async fn create_backup(path: String) -> GvReply<BackupInfo>;
+    async fn restore_backup(path: String, passphrase: String) -> GvReply<String>;
+    async fn process_auto_backup() -> GvReply<()>;
+    async fn migrate_export(path: String) -> GvReply<MigrateInfo>;
+    async fn migrate_import(path: String, passphrase: String, force: bool) -> GvReply<String>;
+    async fn confirm_update(token: String) -> GvReply<String>;
+    async fn take_bot_messages() -> GvReply<Vec<TgBotQueueDB>>;
+    async fn get_chain_emergency() -> GvReply<ChainEmergency>;
+    async fn chain_emergency_action(action: String) -> GvReply<String>;
+    async fn get_announce_routes() -> GvReply<Vec<AnnounceRoute>>;
+    async fn set_announce_route(event: String, destinations: String) -> GvReply<String>;
+    async fn get_quiet_hours() -> GvReply<Vec<QuietHours>>;
+    async fn set_quiet_hours(destination: String, hours: String) -> GvReply<String>;
+    async fn get_announce_templates() -> GvReply<Vec<AnnounceTemplate>>;
+    async fn set_announce_template(
+        msg_type: String,
+        body: String,
+        header: String,
+    ) -> GvReply<String>;
+    async fn get_explorer_health() -> GvReply<Vec<ExplorerHealth>>;
+    async fn get_chain_tip() -> GvReply<ChainTip>;
+    async fn get_block_hash_at(height: u32) -> GvReply<String>;
+    async fn get_stake_forecast() -> GvReply<StakeForecast>;
+    async fn get_stake_heatmap_data(start: u64, end: u64) -> GvReply<StakeHeatmap>;
+    async fn get_stake_heatmap_data_tz(
+        start: u64,
+        end: u64,
+        timezone: String,
+    ) -> GvReply<StakeHeatmap>;
+    async fn get_roi() -> GvReply<StakingRoi>;
+    async fn process_balance_sample() -> GvReply<()>;
+    async fn get_balance_history(start: u64, end: u64) -> GvReply<BalanceHistory>;
+    async fn get_stakes_by_address(start: u64, end: u64) -> GvReply<StakesByAddress>;
+    async fn get_stakes_by_address_csv(start: u64, end: u64) -> GvReply<String>;
+    async fn register_tenant(name: String, key: String, payout_address: String) -> GvReply<String>;
+    async fn remove_tenant(name: String) -> GvReply<String>;
+    async fn get_tenants() -> GvReply<Vec<Tenant>>;
+    async fn get_tenant_statement(name: String, start: u64, end: u64) -> GvReply<TenantStatement>;
+    async fn set_fee_policy(
+        policy: String,
+        value: Option<f64>,
+        max_fee: Option<f64>,
+    ) -> GvReply<String>;
+    async fn list_utxos(
+        wallet: String,
+        utxo_type: String,
+        min_amount: f64,
+        max_amount: f64,
+        min_confirmations: u64,
+    ) -> GvReply<Vec<Utxo>>;
+    async fn freeze_utxo(wallet: String, txid: String, vout: u32) -> GvReply<String>;
+    async fn unfreeze_utxo(wallet: String, txid: String, vout: u32) -> GvReply<String>;
+    async fn consolidate_utxos(
+        wallet: String,
+        max_amount: f64,
+        max_fee_rate: f64,
+    ) -> GvReply<Consolidation>;
+    async fn split_utxos() -> GvReply<Option<PendingSplit>>;
+    async fn confirm_split(token: String) -> GvReply<Vec<String>>;
+    async fn get_events_since(cursor: u64, wait: u64) -> GvReply<EventPage>;
+    async fn get_digest_settings() -> GvReply<DigestSettings>;
+    async fn set_digest(period: String, enabled: bool) -> GvReply<String>;
+    async fn set_digest_time(hour: u32) -> GvReply<String>;
+    async fn process_digest() -> GvReply<Vec<Digest>>;
+    async fn lookup_tx(txid: String) -> GvReply<TxSummary>;
+    async fn lookup_block(block: String) -> GvReply<BlockSummary>;
+    async fn install_daemon_archive(path: String) -> GvReply<String>;
+    async fn pin_daemon_version(version: String) -> GvReply<String>;
+    async fn get_maintenance_window() -> GvReply<MaintenanceWindow>;
+    async fn get_operation_status(op_id: String) -> GvReply<OperationStatus>;
+    async fn get_jobs(limit: u64) -> GvReply<Vec<JobDB>>;
+    async fn get_scheduled_tasks() -> GvReply<Vec<ScheduledTask>>;
+    async fn run_task_now(name: String) -> GvReply<String>;
+    async fn pause_task(name: String, paused: bool) -> GvReply<String>;
+    async fn set_maintenance_window(start: u32, end: u32) -> GvReply<String>;
+    async fn process_maintenance() -> GvReply<bool>;
+    async fn get_system_stats() -> GvReply<SystemStats>;
+    async fn db_maintenance() -> GvReply<DbMaintenanceDB>;
+    async fn set_daemon_prune(size_mb: u64) -> GvReply<String>;
+    async fn bootstrap_sync() -> GvReply<String>;
+    async fn get_bootstrap_status() -> GvReply<BootstrapDB>;
+    async fn get_restart_history(limit: u64) -> GvReply<Vec<RestartDB>>;
+    async fn set_log_level(level: String, module: String) -> GvReply<String>;
+    async fn get_logs(lines: u64, level_filter: String, daemon: bool) -> GvReply<RecentLogs>;
+    async fn set_origin(origin: String) -> GvReply<String>;
+    async fn get_audit_log(limit: u64) -> GvReply<Vec<AuditDB>>;
+    async fn get_pending_reward_change() -> GvReply<PendingRewardChangeDB>;
+    async fn cancel_reward_change() -> GvReply<String>;
+    async fn seal_mnemonic(passphrase: String) -> GvReply<String>;
+    async fn reload_config() -> GvReply<ConfigReload>;
+    async fn simulate_rewards(mode: Option<String>, days: u64) -> GvReply<RewardSimulation>;
+    async fn get_network_stats(start: u64, end: u64) -> GvReply<NetworkStats>;
+    async fn list_cold_wallets() -> GvReply<ColdWallets>;
+    async fn create_cold_wallet(name: String) -> GvReply<ColdWalletCreated>;
+    async fn import_cold_wallet(name: String, mnemonic: String) -> GvReply<String>;
+    async fn switch_cold_wallet(name: String) -> GvReply<String>;
+    async fn get_cold_wallet_stakes(
+        name: String,
+        start: u64,
+        end: u64,
+        division: String,
+    ) -> GvReply<BarChart>;
+    async fn repair_wallet() -> GvReply<String>;
+    async fn reindex_history() -> GvReply<String>;
+    async fn get_delegations() -> GvReply<Delegations>;
+    async fn build_zap_request(amount: f64) -> GvReply<ZapRequest>;
+    async fn add_expected_deposit(
+        amount: f64,
+        memo: String,
+        hours: Option<u64>,
+    ) -> GvReply<ExpectedDeposit>;
+    async fn get_expected_deposits() -> GvReply<Vec<ExpectedDeposit>>;
+    async fn remove_expected_deposit(id: u64) -> GvReply<String>;
+    async fn get_anonymization_status() -> GvReply<AnonymizationStatus>;
+    async fn set_anon_policy(
+        enabled: bool,
+        threshold: Option<f64>,
+        interval: Option<u64>,
+        ring_size: Option<u32>,
+        batch_size: Option<u64>,
+    ) -> GvReply<String>;
+    async fn set_tx_label(txid: String, label: String) -> GvReply<String>;
+    async fn get_tx_labels() -> GvReply<Vec<TxLabel>>;
+    async fn set_address_label(address: String, label: String) -> GvReply<String>;
+    async fn get_address_book() -> GvReply<Vec<AddressBookEntry>>;
 }
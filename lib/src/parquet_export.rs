@@ -0,0 +1,258 @@
+// Parquet export of rewards, payouts and balance history, partitioned by year-month.
+// The layout is hive style (rewards/year_month=2024-01/rewards.parquet) so the whole
+// directory can be read in one go with pandas or duckdb.
+use crate::{
+    gv_client_methods::ParquetExportSummary,
+    gvdb::{PayoutDB, RewardsDB, GVDB},
+};
+use arrow_array::{ArrayRef, BooleanArray, RecordBatch, StringArray, UInt32Array, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+use chrono::{TimeZone, Utc};
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
+use std::{collections::BTreeMap, error::Error, fs, path::Path, sync::Arc};
+
+struct BalancePoint {
+    timestamp: u64,
+    height: u32,
+    all_time_reward: u64,
+    all_time_agvr_reward: u64,
+    total_paid: u64,
+    total_fees: u64,
+}
+
+fn year_month(timestamp: u64) -> String {
+    match Utc.timestamp_opt(timestamp as i64, 0).single() {
+        Some(date) => date.format("%Y-%m").to_string(),
+        None => "unknown".to_string(),
+    }
+}
+
+fn partition<T>(rows: Vec<T>, timestamp: impl Fn(&T) -> u64) -> BTreeMap<String, Vec<T>> {
+    let mut partitions: BTreeMap<String, Vec<T>> = BTreeMap::new();
+
+    for row in rows {
+        partitions
+            .entry(year_month(timestamp(&row)))
+            .or_default()
+            .push(row);
+    }
+
+    partitions
+}
+
+fn write_batch(
+    out_dir: &Path,
+    table: &str,
+    month: &str,
+    batch: RecordBatch,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let part_dir = out_dir.join(table).join(format!("year_month={}", month));
+    fs::create_dir_all(&part_dir)?;
+
+    // Rewrite the whole partition so re-running an export never duplicates rows.
+    let file = fs::File::create(part_dir.join(format!("{}.parquet", table)))?;
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(props))?;
+    writer.write(&batch)?;
+    writer.close()?;
+
+    Ok(())
+}
+
+fn rewards_batch(rewards: &[RewardsDB]) -> Result<RecordBatch, Box<dyn Error + Send + Sync>> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("timestamp", DataType::UInt64, false),
+        Field::new("height", DataType::UInt32, false),
+        Field::new("block_hash", DataType::Utf8, false),
+        Field::new("txid", DataType::Utf8, false),
+        Field::new("reward_sat", DataType::UInt64, false),
+        Field::new("agvr_reward_sat", DataType::UInt64, false),
+        Field::new("address", DataType::Utf8, false),
+        Field::new("is_coldstake", DataType::Boolean, false),
+    ]));
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from_iter_values(
+            rewards.iter().map(|r| r.timestamp),
+        )),
+        Arc::new(UInt32Array::from_iter_values(
+            rewards.iter().map(|r| r.height),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rewards.iter().map(|r| r.block_hash.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rewards.iter().map(|r| r.txid.as_str()),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            rewards.iter().map(|r| r.reward),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            rewards.iter().map(|r| r.agvr_reward),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rewards.iter().map(|r| r.address.as_str()),
+        )),
+        Arc::new(BooleanArray::from(
+            rewards
+                .iter()
+                .map(|r| r.is_coldstake)
+                .collect::<Vec<bool>>(),
+        )),
+    ];
+
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+fn payouts_batch(
+    db: &GVDB,
+    payouts: &[PayoutDB],
+) -> Result<RecordBatch, Box<dyn Error + Send + Sync>> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("timestamp", DataType::UInt64, false),
+        Field::new("txid", DataType::Utf8, false),
+        Field::new("amount_sat", DataType::UInt64, false),
+        Field::new("fee_sat", DataType::UInt64, false),
+        Field::new("destination", DataType::Utf8, false),
+        Field::new("mode", DataType::Utf8, false),
+        Field::new("fee_rate_sat_per_kb", DataType::UInt64, false),
+        Field::new("label", DataType::Utf8, false),
+    ]));
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from_iter_values(
+            payouts.iter().map(|p| p.timestamp),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            payouts.iter().map(|p| p.txid.as_str()),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            payouts.iter().map(|p| p.amount),
+        )),
+        Arc::new(UInt64Array::from_iter_values(payouts.iter().map(|p| p.fee))),
+        Arc::new(StringArray::from_iter_values(
+            payouts.iter().map(|p| p.destination.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            payouts.iter().map(|p| p.mode.as_str()),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            payouts.iter().map(|p| p.fee_rate),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            payouts
+                .iter()
+                .map(|p| db.get_tx_label(&p.txid).unwrap_or_default()),
+        )),
+    ];
+
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+fn balance_batch(points: &[BalancePoint]) -> Result<RecordBatch, Box<dyn Error + Send + Sync>> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("timestamp", DataType::UInt64, false),
+        Field::new("height", DataType::UInt32, false),
+        Field::new("all_time_reward_sat", DataType::UInt64, false),
+        Field::new("all_time_agvr_reward_sat", DataType::UInt64, false),
+        Field::new("total_paid_sat", DataType::UInt64, false),
+        Field::new("total_fees_sat", DataType::UInt64, false),
+    ]));
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from_iter_values(
+            points.iter().map(|b| b.timestamp),
+        )),
+        Arc::new(UInt32Array::from_iter_values(
+            points.iter().map(|b| b.height),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            points.iter().map(|b| b.all_time_reward),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            points.iter().map(|b| b.all_time_agvr_reward),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            points.iter().map(|b| b.total_paid),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            points.iter().map(|b| b.total_fees),
+        )),
+    ];
+
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+// One point per reward, carrying the running payout totals up to that reward.
+fn balance_history(rewards: &[RewardsDB], payouts: &[PayoutDB]) -> Vec<BalancePoint> {
+    let mut points: Vec<BalancePoint> = Vec::with_capacity(rewards.len());
+    let mut payout_iter = payouts.iter().peekable();
+    let mut total_paid: u64 = 0;
+    let mut total_fees: u64 = 0;
+
+    for reward in rewards {
+        while let Some(payout) = payout_iter.next_if(|p| p.timestamp <= reward.timestamp) {
+            total_paid += payout.amount;
+            total_fees += payout.fee;
+        }
+
+        points.push(BalancePoint {
+            timestamp: reward.timestamp,
+            height: reward.height,
+            all_time_reward: reward.all_time_reward,
+            all_time_agvr_reward: reward.all_time_agvr_reward,
+            total_paid,
+            total_fees,
+        });
+    }
+
+    points
+}
+
+pub fn export_parquet(
+    db: &GVDB,
+    out_dir: &Path,
+) -> Result<ParquetExportSummary, Box<dyn Error + Send + Sync>> {
+    // Both trees are keyed by big endian timestamps, so iteration is already in time order.
+    let mut rewards: Vec<RewardsDB> = Vec::new();
+    for result in db.rewards_ts_index.iter() {
+        let (_, value) = result?;
+        rewards.push(serde_json::from_slice(&value)?);
+    }
+
+    let mut payouts: Vec<PayoutDB> = Vec::new();
+    for result in db.payout_history_db.iter() {
+        let (_, value) = result?;
+        payouts.push(serde_json::from_slice(&value)?);
+    }
+
+    let balance: Vec<BalancePoint> = balance_history(&rewards, &payouts);
+
+    let mut summary = ParquetExportSummary {
+        out_dir: out_dir.display().to_string(),
+        rewards: rewards.len(),
+        payouts: payouts.len(),
+        balance_points: balance.len(),
+        partitions: 0,
+    };
+
+    for (month, rows) in partition(rewards, |r| r.timestamp) {
+        write_batch(out_dir, "rewards", &month, rewards_batch(&rows)?)?;
+        summary.partitions += 1;
+    }
+
+    for (month, rows) in partition(payouts, |p| p.timestamp) {
+        write_batch(out_dir, "payouts", &month, payouts_batch(db, &rows)?)?;
+        summary.partitions += 1;
+    }
+
+    for (month, rows) in partition(balance, |b| b.timestamp) {
+        write_batch(out_dir, "balance", &month, balance_batch(&rows)?)?;
+        summary.partitions += 1;
+    }
+
+    Ok(summary)
+}
@@ -0,0 +1,341 @@
+// Pushes backup archives to S3 compatible object storage or an SFTP server.
+// BACKUP_REMOTE_URL picks the target, s3://bucket/prefix or sftp://user@host:port/path.
+use crate::config::GVConfig;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::{
+    error::Error,
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+use url::Url;
+
+const BACKUP_SUFFIX: &str = ".gvbak";
+
+enum RemoteTarget {
+    S3 {
+        bucket: String,
+        prefix: String,
+    },
+    Sftp {
+        host: String,
+        port: u16,
+        path: String,
+    },
+}
+
+fn remote_target(conf: &GVConfig) -> Result<RemoteTarget, Box<dyn Error + Send + Sync>> {
+    let remote_url: &str = conf
+        .backup_remote_url
+        .as_deref()
+        .ok_or("No remote backup target set")?;
+    let url: Url = Url::parse(remote_url)?;
+
+    let host: String = url
+        .host_str()
+        .ok_or("Remote backup URL is missing a host")?
+        .to_string();
+    let path: String = url.path().trim_matches('/').to_string();
+
+    match url.scheme() {
+        "s3" => Ok(RemoteTarget::S3 {
+            bucket: host,
+            prefix: path,
+        }),
+        "sftp" => {
+            let host: String = if url.username().is_empty() {
+                host
+            } else {
+                format!("{}@{}", url.username(), host)
+            };
+
+            Ok(RemoteTarget::Sftp {
+                host,
+                port: url.port().unwrap_or(22),
+                path: if path.is_empty() {
+                    ".".to_string()
+                } else {
+                    path
+                },
+            })
+        }
+        scheme => Err(format!("Unsupported remote backup target: {}", scheme).into()),
+    }
+}
+
+// BACKUP_REMOTE_URL is usable, for --check-config.
+pub(crate) fn check_target(conf: &GVConfig) -> Result<(), Box<dyn Error + Send + Sync>> {
+    remote_target(conf).map(|_| ())
+}
+
+fn remote_key(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", prefix, name)
+    }
+}
+
+// Uploads the archive and returns where it ended up.
+pub async fn upload_backup(
+    conf: &GVConfig,
+    file: &Path,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let name: String = file
+        .file_name()
+        .ok_or("Invalid backup file")?
+        .to_string_lossy()
+        .to_string();
+
+    match remote_target(conf)? {
+        RemoteTarget::S3 { bucket, prefix } => {
+            let key: String = remote_key(&prefix, &name);
+            let body: Vec<u8> = std::fs::read(file)?;
+            s3_request(conf, "PUT", &bucket, &key, "", body).await?;
+
+            Ok(format!("s3://{}/{}", bucket, key))
+        }
+        RemoteTarget::Sftp { host, port, path } => {
+            let commands: String = format!(
+                "-mkdir \"{}\"\nput \"{}\" \"{}\"\n",
+                path,
+                file.display(),
+                remote_key(&path, &name)
+            );
+            sftp_batch(conf, &host, port, &commands)?;
+
+            Ok(format!(
+                "sftp://{}:{}/{}",
+                host,
+                port,
+                remote_key(&path, &name)
+            ))
+        }
+    }
+}
+
+// Keep the newest BACKUP_REMOTE_RETENTION backups on the remote, returns how many were removed.
+pub async fn prune_remote_backups(conf: &GVConfig) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let keep: usize = conf.backup_remote_retention as usize;
+
+    match remote_target(conf)? {
+        RemoteTarget::S3 { bucket, prefix } => {
+            let query: String = format!("list-type=2&prefix={}", uri_encode(&prefix, true));
+            let listing: String = s3_request(conf, "GET", &bucket, "", &query, Vec::new()).await?;
+
+            let mut keys: Vec<String> = xml_values(&listing, "Key")
+                .into_iter()
+                .filter(|key| key.ends_with(BACKUP_SUFFIX))
+                .collect();
+            keys.sort();
+
+            let remove_count: usize = keys.len().saturating_sub(keep);
+
+            for key in keys.iter().take(remove_count) {
+                s3_request(conf, "DELETE", &bucket, key, "", Vec::new()).await?;
+            }
+
+            Ok(remove_count)
+        }
+        RemoteTarget::Sftp { host, port, path } => {
+            let listing: String = sftp_batch(conf, &host, port, &format!("ls -1 \"{}\"\n", path))?;
+
+            let mut names: Vec<String> = listing
+                .lines()
+                .filter(|line| !line.starts_with("sftp>") && line.ends_with(BACKUP_SUFFIX))
+                .filter_map(|line| Path::new(line.trim()).file_name())
+                .map(|name| name.to_string_lossy().to_string())
+                .collect();
+            names.sort();
+
+            let remove_count: usize = names.len().saturating_sub(keep);
+
+            if remove_count > 0 {
+                let commands: String = names
+                    .iter()
+                    .take(remove_count)
+                    .map(|name| format!("rm \"{}\"\n", remote_key(&path, name)))
+                    .collect();
+                sftp_batch(conf, &host, port, &commands)?;
+            }
+
+            Ok(remove_count)
+        }
+    }
+}
+
+// Runs sftp in batch mode, which needs key based auth since it can never prompt.
+fn sftp_batch(
+    conf: &GVConfig,
+    host: &str,
+    port: u16,
+    commands: &str,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let mut cmd: Command = Command::new("sftp");
+    cmd.args(["-b", "-", "-P", &port.to_string()]).args([
+        "-o",
+        "BatchMode=yes",
+        "-o",
+        "StrictHostKeyChecking=accept-new",
+    ]);
+
+    if let Some(key) = &conf.backup_sftp_key {
+        cmd.args(["-i", key]);
+    }
+
+    let mut child = cmd
+        .arg(host)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    child
+        .stdin
+        .take()
+        .ok_or("Failed to open sftp stdin")?
+        .write_all(commands.as_bytes())?;
+
+    let output = child.wait_with_output()?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "sftp failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded: String = String::new();
+
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    encoded
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn xml_values(xml: &str, tag: &str) -> Vec<String> {
+    let open: String = format!("<{}>", tag);
+    let close: String = format!("</{}>", tag);
+
+    xml.split(&open)
+        .skip(1)
+        .filter_map(|part| part.split(&close).next())
+        .map(|value| value.to_string())
+        .collect()
+}
+
+// Path style request signed with AWS signature v4, which MinIO, B2 and friends accept too.
+async fn s3_request(
+    conf: &GVConfig,
+    method: &str,
+    bucket: &str,
+    key: &str,
+    query: &str,
+    body: Vec<u8>,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let access_key: &str = conf
+        .backup_remote_access_key
+        .as_deref()
+        .ok_or("BACKUP_REMOTE_ACCESS_KEY is not set")?;
+    let secret_key: &str = conf
+        .backup_remote_secret_key
+        .as_deref()
+        .ok_or("BACKUP_REMOTE_SECRET_KEY is not set")?;
+
+    let endpoint: Url = Url::parse(&conf.backup_s3_endpoint)?;
+    let host: String = match endpoint.port() {
+        Some(port) => format!("{}:{}", endpoint.host_str().unwrap_or(""), port),
+        None => endpoint.host_str().unwrap_or("").to_string(),
+    };
+
+    let mut canonical_uri: String = format!("/{}", uri_encode(bucket, true));
+    if !key.is_empty() {
+        canonical_uri.push('/');
+        canonical_uri.push_str(&uri_encode(key, false));
+    }
+
+    let now = chrono::Utc::now();
+    let amz_date: String = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date_stamp: String = now.format("%Y%m%d").to_string();
+    let payload_hash: String = hex_sha256(&body);
+    let region: &str = &conf.backup_s3_region;
+
+    let canonical_request: String = format!(
+        "{}\n{}\n{}\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+        method, canonical_uri, query, host, payload_hash, amz_date, payload_hash
+    );
+
+    let scope: String = format!("{}/{}/s3/aws4_request", date_stamp, region);
+    let string_to_sign: String = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex_sha256(canonical_request.as_bytes())
+    );
+
+    let date_key: Vec<u8> = hmac_sha256(format!("AWS4{}", secret_key).as_bytes(), &date_stamp);
+    let region_key: Vec<u8> = hmac_sha256(&date_key, region);
+    let service_key: Vec<u8> = hmac_sha256(&region_key, "s3");
+    let signing_key: Vec<u8> = hmac_sha256(&service_key, "aws4_request");
+    let signature: String = hex_encode(&hmac_sha256(&signing_key, &string_to_sign));
+
+    let authorization: String = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+        access_key, scope, signature
+    );
+
+    let mut request_url: String = format!("{}://{}{}", endpoint.scheme(), host, canonical_uri);
+    if !query.is_empty() {
+        request_url.push('?');
+        request_url.push_str(query);
+    }
+
+    let response = reqwest::Client::new()
+        .request(reqwest::Method::from_bytes(method.as_bytes())?, request_url)
+        .header("x-amz-content-sha256", payload_hash)
+        .header("x-amz-date", amz_date)
+        .header("authorization", authorization)
+        .body(body)
+        .send()
+        .await?;
+
+    let status = response.status();
+    let text: String = response.text().await?;
+
+    if !status.is_success() {
+        let reason: String = xml_values(&text, "Message")
+            .into_iter()
+            .next()
+            .unwrap_or(status.to_string());
+        return Err(format!("S3 request failed: {}", reason).into());
+    }
+
+    Ok(text)
+}
+
+fn hex_sha256(data: &[u8]) -> String {
+    hex_encode(&Sha256::digest(data))
+}
+
+fn hex_encode(data: &[u8]) -> String {
+    data_encoding::HEXLOWER.encode(data)
+}
@@ -0,0 +1,225 @@
+// Every GvCLI method answers with the same envelope, {ok, code, message, data}.
+// The server builds it with ok/message/error and CLICaller unwraps it, handing callers the
+// data or a GvError they can branch on by code instead of matching on the message text.
+// GvReply is the envelope with its data typed, GvResponse the same envelope read as plain JSON.
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::{error::Error, fmt};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    InvalidArgument,
+    NotFound,
+    AlreadyRunning,
+    InvalidState,
+    Unauthorized,
+    Expired,
+    Disabled,
+    NotConfigured,
+    DaemonError,
+    Unavailable,
+    // The server is too old for the method, nothing was sent to it.
+    Unsupported,
+    Internal,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GvResponse {
+    pub ok: bool,
+    pub code: Option<ErrorCode>,
+    pub message: Option<String>,
+    pub data: Value,
+}
+
+// Serialized it's the same JSON as GvResponse, so clients reading plain envelopes still work.
+#[derive(Clone, Debug, Serialize)]
+pub struct GvReply<T> {
+    pub ok: bool,
+    pub code: Option<ErrorCode>,
+    pub message: Option<String>,
+    pub data: Option<T>,
+}
+
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct GvError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+// What a GvCLI handler produces before it goes on the wire, Ok holds the finished envelope.
+pub type GvResult<T = Value> = Result<GvReply<T>, GvError>;
+
+impl GvError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> GvError {
+        GvError {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+// Tags a failure with an error code so handlers can use `?` on daemon and database calls,
+// the underlying error is kept in the message after `context`.
+pub trait OrCode<T> {
+    fn or_code(self, code: ErrorCode, context: &str) -> Result<T, GvError>;
+}
+
+impl<T, E: fmt::Display> OrCode<T> for Result<T, E> {
+    fn or_code(self, code: ErrorCode, context: &str) -> Result<T, GvError> {
+        self.map_err(|err| GvError::new(code, format!("{}: {}", context, err)))
+    }
+}
+
+impl<T> OrCode<T> for Option<T> {
+    fn or_code(self, code: ErrorCode, context: &str) -> Result<T, GvError> {
+        self.ok_or_else(|| GvError::new(code, context))
+    }
+}
+
+impl GvResponse {
+    // Anything that isn't an envelope is taken as plain data, older servers answer that way.
+    pub fn from_value(value: Value) -> GvResponse {
+        match serde_json::from_value::<GvResponse>(value.clone()) {
+            Ok(response) => response,
+            Err(_) => GvResponse {
+                ok: true,
+                code: None,
+                message: None,
+                data: value,
+            },
+        }
+    }
+
+    pub fn from_error(err: &(dyn Error + 'static)) -> GvResponse {
+        let (code, message) = match err.downcast_ref::<GvError>() {
+            Some(err) => (err.code, err.message.clone()),
+            None => (ErrorCode::Unavailable, err.to_string()),
+        };
+
+        GvResponse {
+            ok: false,
+            code: Some(code),
+            message: Some(message),
+            data: Value::Null,
+        }
+    }
+
+    // The data, or the message when a response carries nothing else.
+    pub fn into_result(self) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        if !self.ok {
+            return Err(Box::new(GvError {
+                code: self.code.unwrap_or(ErrorCode::Internal),
+                message: self.message.unwrap_or_default(),
+            }));
+        }
+
+        match (self.data, self.message) {
+            (Value::Null, Some(message)) => Ok(Value::String(message)),
+            (data, _) => Ok(data),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> GvReply<T> {
+    // Data that doesn't fit T, from a server on another API version, turns into an error
+    // reply rather than failing the whole connection.
+    pub fn from_response(response: GvResponse) -> GvReply<T> {
+        if !response.ok || response.data.is_null() {
+            return GvReply {
+                ok: response.ok,
+                code: response.code,
+                message: response.message,
+                data: None,
+            };
+        }
+
+        match serde_json::from_value::<T>(response.data) {
+            Ok(data) => GvReply {
+                ok: true,
+                code: None,
+                message: response.message,
+                data: Some(data),
+            },
+            Err(err) => error(
+                ErrorCode::Internal,
+                format!("Malformed response from GhostVault: {}", err),
+            ),
+        }
+    }
+
+    // The data, or the message when a reply carries nothing else. Methods that may answer
+    // with only a message take an Option, the message is then None.
+    pub fn into_result(self) -> Result<T, GvError> {
+        if !self.ok {
+            return Err(GvError {
+                code: self.code.unwrap_or(ErrorCode::Internal),
+                message: self.message.unwrap_or_default(),
+            });
+        }
+
+        if let Some(data) = self.data {
+            return Ok(data);
+        }
+
+        let message: Value = self.message.map_or(Value::Null, Value::String);
+
+        serde_json::from_value::<T>(message)
+            .or_else(|_| serde_json::from_value::<T>(Value::Null))
+            .map_err(|err| {
+                GvError::new(
+                    ErrorCode::Internal,
+                    format!("Malformed response from GhostVault: {}", err),
+                )
+            })
+    }
+}
+
+// Read through GvResponse, so servers from before the envelope are understood as well.
+impl<'de, T: DeserializeOwned> Deserialize<'de> for GvReply<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value: Value = Value::deserialize(deserializer)?;
+        Ok(GvReply::from_response(GvResponse::from_value(value)))
+    }
+}
+
+pub fn ok<T>(data: T) -> GvReply<T> {
+    GvReply {
+        ok: true,
+        code: None,
+        message: None,
+        data: Some(data),
+    }
+}
+
+pub fn message<T>(message: impl Into<String>) -> GvReply<T> {
+    GvReply {
+        ok: true,
+        code: None,
+        message: Some(message.into()),
+        data: None,
+    }
+}
+
+// The envelope for a handler's result, errors keep their code.
+pub fn from_result<T>(result: GvResult<T>) -> GvReply<T> {
+    match result {
+        Ok(reply) => reply,
+        Err(err) => error(err.code, err.message),
+    }
+}
+
+pub fn error<T>(code: ErrorCode, message: impl Into<String>) -> GvReply<T> {
+    GvReply {
+        ok: false,
+        code: Some(code),
+        message: Some(message.into()),
+        data: None,
+    }
+}
+
+// Error code of a failed call, None for anything that didn't come back as a GvError.
+pub fn error_code(err: &(dyn Error + 'static)) -> Option<ErrorCode> {
+    err.downcast_ref::<GvError>().map(|err| err.code)
+}
@@ -1,10 +1,14 @@
 // Collection of functions to interface with ghostd.
+use crate::constants::{RPC_BATCH_MAX, RPC_POOL_IDLE_TIMEOUT, RPC_POOL_MAX_IDLE};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::{sync::OnceLock, time::Duration};
 
 use log::{debug, trace};
 use reqwest::Client;
 
+static RPC_CLIENT: OnceLock<Client> = OnceLock::new();
+
 #[derive(Debug, Clone, Default)]
 pub struct RPCURL(String);
 
@@ -36,6 +40,17 @@ impl RPCURL {
         }
         return self;
     }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // The wallet the URL targets, empty for calls that aren't for a wallet.
+    pub(crate) fn wallet(&self) -> &str {
+        self.0
+            .rsplit_once("/wallet/")
+            .map_or("", |(_, wallet)| wallet)
+    }
 }
 
 fn parametrize(args: &str) -> Vec<Value> {
@@ -72,6 +87,21 @@ fn parametrize(args: &str) -> Vec<Value> {
     return params;
 }
 
+// Every DaemonHelper shares one client, so ghostd connections are pooled and kept alive
+// across helpers instead of being opened per call.
+pub(crate) fn pooled_client() -> Client {
+    RPC_CLIENT
+        .get_or_init(|| {
+            Client::builder()
+                .pool_max_idle_per_host(RPC_POOL_MAX_IDLE)
+                .pool_idle_timeout(Duration::from_secs(RPC_POOL_IDLE_TIMEOUT))
+                .tcp_nodelay(true)
+                .build()
+                .unwrap_or_default()
+        })
+        .clone()
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct Post<'r> {
     jsonrpc: &'r str,
@@ -95,7 +125,17 @@ pub(crate) async fn call(
         method,
         params: Value::Array(params),
     };
-    debug!("RPC: {} {} ...", &post.method, &post.params);
+    // Never write wallet passphrases to the log.
+    let is_sensitive: bool = matches!(
+        post.method.as_str(),
+        Some("encryptwallet") | Some("walletpassphrase")
+    );
+
+    if is_sensitive {
+        debug!("RPC: {} [redacted] ...", &post.method);
+    } else {
+        debug!("RPC: {} {} ...", &post.method, &post.params);
+    }
 
     // Use the .post method with async/await
     let response = rpc_client
@@ -114,3 +154,87 @@ pub(crate) async fn call(
 
     Ok(body["result"].clone())
 }
+
+// Sends several calls as JSON-RPC batches, one round trip per RPC_BATCH_MAX calls.
+// Results come back in the order of `calls`, a call the daemon rejected is an Err on its own
+// without failing the rest of the batch.
+pub(crate) async fn call_batch(
+    calls: &[String],
+    rpcurl: &RPCURL,
+    rpc_client: &Client,
+) -> Result<
+    Vec<Result<Value, Box<dyn std::error::Error + Send + Sync>>>,
+    Box<dyn std::error::Error + Send + Sync>,
+> {
+    let mut results: Vec<Result<Value, Box<dyn std::error::Error + Send + Sync>>> =
+        Vec::with_capacity(calls.len());
+
+    for chunk in calls.chunks(RPC_BATCH_MAX) {
+        let ids: Vec<String> = (0..chunk.len()).map(|id| id.to_string()).collect();
+
+        let posts: Vec<Post> = chunk
+            .iter()
+            .zip(ids.iter())
+            .map(|(args, id)| {
+                let mut params = parametrize(args);
+                let method = params.remove(0);
+
+                Post {
+                    jsonrpc: "1.0",
+                    id,
+                    method,
+                    params: Value::Array(params),
+                }
+            })
+            .collect();
+
+        debug!(
+            "RPC batch: {} ...",
+            posts
+                .iter()
+                .map(|post| post.method.as_str().unwrap_or_default())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        );
+
+        let response = rpc_client
+            .post(&rpcurl.0)
+            .header("Content-Type", "application/json")
+            .json(&posts)
+            .send()
+            .await?;
+
+        // ghostd answers a batch with 200 even if single calls in it fail, the errors are
+        // reported per call.
+        let body: Value = response.error_for_status()?.json().await?;
+        let replies: &Vec<Value> = body.as_array().ok_or("Malformed batch response")?;
+
+        let mut chunk_results: Vec<
+            Option<Result<Value, Box<dyn std::error::Error + Send + Sync>>>,
+        > = (0..chunk.len()).map(|_| None).collect();
+
+        for reply in replies {
+            let id: usize = match reply["id"].as_str().and_then(|id| id.parse::<usize>().ok()) {
+                Some(id) if id < chunk.len() => id,
+                _ => continue,
+            };
+
+            chunk_results[id] = Some(match reply.get("error") {
+                Some(error) if !error.is_null() => Err(error["message"]
+                    .as_str()
+                    .unwrap_or("RPC error")
+                    .to_string()
+                    .into()),
+                _ => Ok(reply["result"].clone()),
+            });
+        }
+
+        results.extend(
+            chunk_results
+                .into_iter()
+                .map(|result| result.unwrap_or_else(|| Err("No reply in batch response".into()))),
+        );
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,205 @@
+// Staking odds from our weight against the network's. Each staking sat gets one kernel
+// check per timestamp slot, so stakes arrive as a Poisson process and the expected wait
+// is the block spacing scaled by our share of the network weight.
+use humantime::format_duration;
+use std::time::Duration;
+
+use crate::{
+    amount::{Amount, SATS_PER_GHOST},
+    constants::{
+        BLOCK_TARGET_SPACING, STAKE_TIMESTAMP_GRANULARITY, STAKE_UNDERPERFORM_PROBABILITY,
+    },
+    gv_client_methods::{ModeSimulation, RoiWindow, StakeForecast},
+};
+
+// Network weight in sats. ghostd reports no netstakeweight until it has seen enough
+// stakes, in that case it is estimated from the difficulty.
+pub fn network_weight(net_stake_weight: Amount, difficulty: f64) -> f64 {
+    if net_stake_weight > Amount::ZERO {
+        return net_stake_weight.to_sat() as f64;
+    }
+
+    // A sat hits the target with odds 1/(difficulty * 2^32) per slot, and the network as a
+    // whole finds a block once per target spacing.
+    difficulty * 4294967296.0 * STAKE_TIMESTAMP_GRANULARITY as f64 / BLOCK_TARGET_SPACING as f64
+}
+
+pub fn expected_stake_secs(
+    currently_staking: Amount,
+    net_stake_weight: Amount,
+    difficulty: f64,
+) -> Option<u64> {
+    let our_weight: f64 = currently_staking.to_sat() as f64;
+    let network: f64 = network_weight(net_stake_weight, difficulty);
+
+    if our_weight <= 0.0 || network <= 0.0 {
+        return None;
+    }
+
+    // The network weight is an estimate, it can lag behind a large deposit of ours
+    Some((BLOCK_TARGET_SPACING as f64 * network.max(our_weight) / our_weight).round() as u64)
+}
+
+// P(X <= k) for X ~ Poisson(lambda). Terms are built in log space so a large lambda
+// doesn't underflow the first term to zero.
+pub fn poisson_cdf(k: u32, lambda: f64) -> f64 {
+    if lambda <= 0.0 {
+        return 1.0;
+    }
+
+    let mut log_term: f64 = -lambda;
+    let mut sum: f64 = log_term.exp();
+
+    for i in 1..=k {
+        log_term += (lambda / i as f64).ln();
+        sum += log_term.exp();
+    }
+
+    sum.min(1.0)
+}
+
+// Forecast for the current weight, compared with the stakes actually found over the last
+// window_secs. The comparison assumes the weight was about the same over the window.
+pub fn stake_forecast(
+    currently_staking: Amount,
+    net_stake_weight: Amount,
+    difficulty: f64,
+    actual_stakes: u32,
+    window_secs: u64,
+) -> StakeForecast {
+    let expected_secs: Option<u64> =
+        expected_stake_secs(currently_staking, net_stake_weight, difficulty);
+    let network: f64 = network_weight(net_stake_weight, difficulty);
+
+    let stake_share: f64 = if network > 0.0 {
+        (currently_staking.to_sat() as f64 / network).min(1.0)
+    } else {
+        0.0
+    };
+
+    let (expected_time, stakes_per_day, expected_stakes) = match expected_secs {
+        Some(secs) if secs > 0 => (
+            format_duration(Duration::from_secs(secs)).to_string(),
+            86400.0 / secs as f64,
+            window_secs as f64 / secs as f64,
+        ),
+        _ => ("N/A".to_string(), 0.0, 0.0),
+    };
+
+    let performance: f64 = if expected_stakes > 0.0 {
+        actual_stakes as f64 / expected_stakes
+    } else {
+        0.0
+    };
+
+    let probability: f64 = poisson_cdf(actual_stakes, expected_stakes);
+
+    StakeForecast {
+        currently_staking: currently_staking.to_ghost(),
+        network_stake_weight: network / SATS_PER_GHOST as f64,
+        staking_difficulty: difficulty,
+        stake_share,
+        expected_secs,
+        expected_time,
+        stakes_per_day,
+        window_days: window_secs as f64 / 86400.0,
+        expected_stakes,
+        actual_stakes,
+        performance,
+        probability,
+        underperforming: performance < 1.0 && probability < STAKE_UNDERPERFORM_PROBABILITY,
+    }
+}
+
+// Return on the average staked balance over covered_secs, all three as percentages. APR scales
+// the period's return to a year, APY also compounds it as if rewards were restaked.
+pub fn roi_window(average_staked: Amount, rewards: Amount, covered_secs: u64) -> RoiWindow {
+    let roi: f64 = if average_staked > Amount::ZERO {
+        rewards.to_sat() as f64 / average_staked.to_sat() as f64
+    } else {
+        0.0
+    };
+
+    let periods_per_year: f64 = if covered_secs > 0 {
+        (365 * 86400) as f64 / covered_secs as f64
+    } else {
+        0.0
+    };
+
+    RoiWindow {
+        covered_days: covered_secs as f64 / 86400.0,
+        average_staked: average_staked.to_ghost(),
+        rewards: rewards.to_ghost(),
+        roi: roi * 100.0,
+        apr: roi * periods_per_year * 100.0,
+        apy: ((1.0 + roi).powf(periods_per_year) - 1.0) * 100.0,
+    }
+}
+
+// What the stakes between start and end would have paid under each reward mode. DEFAULT keeps
+// rewards staking and STANDARD pays each one out in its coinstake, neither costs a fee. ANON
+// collects rewards on the internal anon address and sends them every reward interval once they
+// reach the payout minimum, each send costing payout_fee.
+pub fn simulate_reward_modes(
+    stakes: &[(u64, Amount)],
+    start: u64,
+    end: u64,
+    reward_interval: u64,
+    min_payout: Amount,
+    payout_fee: Amount,
+) -> Vec<ModeSimulation> {
+    let rewards: Amount = stakes.iter().map(|(_, reward)| *reward).sum();
+
+    let mut received: Amount = Amount::ZERO;
+    let mut fees: Amount = Amount::ZERO;
+    let mut payouts: u32 = 0;
+    let mut pending: Amount = Amount::ZERO;
+
+    let mut stakes_iter = stakes.iter().peekable();
+    let interval: u64 = reward_interval.max(1);
+    let mut check: u64 = start.saturating_add(interval);
+
+    while check <= end {
+        while let Some((_, reward)) = stakes_iter.next_if(|(timestamp, _)| *timestamp <= check) {
+            pending += *reward;
+        }
+
+        if pending >= min_payout && pending > payout_fee {
+            received += pending - payout_fee;
+            fees += payout_fee;
+            payouts += 1;
+            pending = Amount::ZERO;
+        }
+
+        check = check.saturating_add(interval);
+    }
+
+    let unpaid: Amount = pending + stakes_iter.map(|(_, reward)| *reward).sum::<Amount>();
+
+    vec![
+        ModeSimulation {
+            mode: "DEFAULT".to_string(),
+            received: rewards,
+            fees: Amount::ZERO,
+            payouts: 0,
+            unpaid: Amount::ZERO,
+            compounding: true,
+        },
+        ModeSimulation {
+            mode: "STANDARD".to_string(),
+            received: rewards,
+            fees: Amount::ZERO,
+            payouts: stakes.len() as u32,
+            unpaid: Amount::ZERO,
+            compounding: false,
+        },
+        ModeSimulation {
+            mode: "ANON".to_string(),
+            received,
+            fees,
+            payouts,
+            unpaid,
+            compounding: false,
+        },
+    ]
+}
@@ -1,16 +1,22 @@
 use crate::{
     config::GVConfig,
-    constants::{DEFAULT_DEAMON_UPDATE, DEFAULT_MIN_PAYOUT, DEFAULT_SELF_UPDATE},
+    constants::{
+        DB_MAINTENANCE_INTERVAL, DB_MAINTENANCE_RETRY, DEFAULT_BACKUP_INTERVAL,
+        DEFAULT_BALANCE_SAMPLE_INTERVAL, DEFAULT_DEAMON_UPDATE, DEFAULT_MIN_PAYOUT,
+        DEFAULT_SELF_UPDATE, DEFAULT_SPLIT_INTERVAL, DIGEST_CHECK_INTERVAL, SCHEDULED_TASKS,
+    },
     gv_client_methods::CLICaller,
-    gvdb::{ServerReadyDB, Task, GVDB},
+    gvdb::{MaintenanceDB, ServerReadyDB, Task, WalletRescanDB, GVDB},
 };
-use log::info;
+use chrono::Timelike;
+use chrono_tz::Tz;
+use log::{error, info};
 use std::sync::Arc;
 use tokio::sync::RwLock as async_RwLock;
 
 pub async fn task_runner(db: &Arc<GVDB>, gv_config: &Arc<async_RwLock<GVConfig>>) {
     info!("Starting the task service...");
-    let tasks_to_complete: Vec<&str> = vec!["daemon_update", "self_update", "process_rewards"];
+    let tasks_to_complete: Vec<&str> = SCHEDULED_TASKS.to_vec();
     let current_time: i64 = get_current_time();
     let cloned_tasks: Vec<&str> = tasks_to_complete.clone();
     let runner_tasks: Vec<&str> = tasks_to_complete.clone();
@@ -25,6 +31,11 @@ pub async fn task_runner(db: &Arc<GVDB>, gv_config: &Arc<async_RwLock<GVConfig>>
                 "daemon_update" => DEFAULT_DEAMON_UPDATE,
                 "self_update" => DEFAULT_SELF_UPDATE,
                 "process_rewards" => conf.reward_interval,
+                "auto_backup" => backup_run_interval(conf.backup_interval),
+                "balance_sample" => balance_sample_interval(conf.balance_sample_interval),
+                "utxo_split" => utxo_split_interval(conf.split_interval),
+                "digest" => DIGEST_CHECK_INTERVAL as u64,
+                "db_maintenance" => DB_MAINTENANCE_INTERVAL as u64,
 
                 _ => continue,
             } as i64;
@@ -43,12 +54,18 @@ pub async fn task_runner(db: &Arc<GVDB>, gv_config: &Arc<async_RwLock<GVConfig>>
                 next_run,
                 min_payout,
                 task_running: false,
+                last_run: None,
+                last_result: None,
+                paused: false,
             };
 
             db.set_task(task.as_bytes(), &task_entry).await.unwrap();
         }
     }
 
+    // Pick up any interval change made to the config file while stopped.
+    sync_task_intervals(db, &conf).await;
+
     let wait_rpc_db = Arc::clone(&db);
     let wait_rpc_config = Arc::clone(&gv_config);
 
@@ -81,7 +98,7 @@ pub async fn task_runner(db: &Arc<GVDB>, gv_config: &Arc<async_RwLock<GVConfig>>
                 task_details.unwrap()
             };
 
-            if task_details.task_running {
+            if task_details.task_running || task_details.paused {
                 continue;
             }
 
@@ -108,11 +125,61 @@ pub async fn task_runner(db: &Arc<GVDB>, gv_config: &Arc<async_RwLock<GVConfig>>
                             process_rewards_callback(&db_clone, &conf_clone).await;
                         });
                     }
+                    &"auto_backup" => {
+                        tokio::spawn(async move {
+                            auto_backup_callback(&db_clone, &conf_clone).await;
+                        });
+                    }
+                    &"balance_sample" => {
+                        tokio::spawn(async move {
+                            balance_sample_callback(&db_clone, &conf_clone).await;
+                        });
+                    }
+                    &"utxo_split" => {
+                        tokio::spawn(async move {
+                            utxo_split_callback(&db_clone, &conf_clone).await;
+                        });
+                    }
+                    &"digest" => {
+                        tokio::spawn(async move {
+                            digest_callback(&db_clone, &conf_clone).await;
+                        });
+                    }
+                    &"db_maintenance" => {
+                        tokio::spawn(async move {
+                            db_maintenance_callback(&db_clone, &conf_clone).await;
+                        });
+                    }
                     _ => (),
                 }
             }
         }
 
+        // Scheduled wallet rescans are held until the maintenance window opens.
+        let rescan: Option<WalletRescanDB> = db.get_wallet_rescan();
+
+        if rescan.is_some_and(|rescan| rescan.status == "scheduled")
+            && in_maint_window(gv_config).await
+        {
+            let conf_clone = Arc::clone(gv_config);
+
+            tokio::spawn(async move {
+                wallet_rescan_callback(&conf_clone).await;
+            });
+        }
+
+        // So are deferred ghostd updates and resyncs.
+        let maintenance: MaintenanceDB = db.get_maintenance();
+
+        if (maintenance.update.is_some() || maintenance.resync) && in_maint_window(gv_config).await
+        {
+            let conf_clone = Arc::clone(gv_config);
+
+            tokio::spawn(async move {
+                maintenance_callback(&conf_clone).await;
+            });
+        }
+
         tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
     }
 }
@@ -134,11 +201,18 @@ async fn daemon_update_callback(db: &Arc<GVDB>, gv_config: &Arc<async_RwLock<GVC
 
     let conf = gv_config.read().await;
 
-    let cli_caller: CLICaller = CLICaller::new(&conf.cli_address, true).await.unwrap();
+    let cli_caller: CLICaller = CLICaller::new(&conf.cli_address, true)
+        .await
+        .unwrap()
+        .with_origin("internal")
+        .await;
     drop(conf);
-    cli_caller.call_process_daemon_update().await.unwrap();
+    let result: Result<(), String> = task_result(
+        "Error checking for a ghostd update",
+        cli_caller.call_process_daemon_update(false).await,
+    );
 
-    schedule_next(db, task, &mut task_details).await;
+    schedule_next(db, task, &mut task_details, result).await;
 }
 
 async fn self_update_callback(db: &Arc<GVDB>, _gv_config: &Arc<async_RwLock<GVConfig>>) {
@@ -147,7 +221,7 @@ async fn self_update_callback(db: &Arc<GVDB>, _gv_config: &Arc<async_RwLock<GVCo
     let mut task_details: Task = db.get_task(task.as_bytes()).unwrap();
     toggle_running(db, task, &mut task_details).await;
 
-    schedule_next(db, task, &mut task_details).await;
+    schedule_next(db, task, &mut task_details, Ok(())).await;
 }
 
 async fn process_rewards_callback(db: &Arc<GVDB>, gv_config: &Arc<async_RwLock<GVConfig>>) {
@@ -160,15 +234,212 @@ async fn process_rewards_callback(db: &Arc<GVDB>, gv_config: &Arc<async_RwLock<G
 
     let cli_caller: CLICaller = CLICaller::new(&conf.cli_address, true).await.unwrap();
     drop(conf);
-    cli_caller.call_process_reward_payout().await.unwrap();
+    let result: Result<(), String> = task_result(
+        "Error processing reward payout",
+        cli_caller.call_process_reward_payout().await,
+    );
+
+    schedule_next(db, task, &mut task_details, result).await;
+}
+
+async fn auto_backup_callback(db: &Arc<GVDB>, gv_config: &Arc<async_RwLock<GVConfig>>) {
+    let task: &str = "auto_backup";
+    info!("Running task: {}", task);
+    let mut task_details: Task = db.get_task(task.as_bytes()).unwrap();
+    toggle_running(db, task, &mut task_details).await;
+
+    let conf = gv_config.read().await;
+
+    let cli_caller: CLICaller = CLICaller::new(&conf.cli_address, true).await.unwrap();
+    drop(conf);
+    let result: Result<(), String> = task_result(
+        "Error running automatic backup",
+        cli_caller.call_process_auto_backup().await,
+    );
 
-    schedule_next(db, task, &mut task_details).await;
+    schedule_next(db, task, &mut task_details, result).await;
 }
 
-async fn schedule_next(db: &Arc<GVDB>, task: &str, task_details: &mut Task) {
+// A zero interval disables backups, the task still ticks daily and the server skips it.
+fn backup_run_interval(backup_interval: u64) -> u64 {
+    if backup_interval == 0 {
+        DEFAULT_BACKUP_INTERVAL as u64
+    } else {
+        backup_interval
+    }
+}
+
+async fn balance_sample_callback(db: &Arc<GVDB>, gv_config: &Arc<async_RwLock<GVConfig>>) {
+    let task: &str = "balance_sample";
+    info!("Running task: {}", task);
+    let mut task_details: Task = db.get_task(task.as_bytes()).unwrap();
+    toggle_running(db, task, &mut task_details).await;
+
+    let conf = gv_config.read().await;
+
+    let cli_caller: CLICaller = CLICaller::new(&conf.cli_address, true).await.unwrap();
+    drop(conf);
+
+    // A missed sample only leaves a longer step in the history, try again next interval
+    let result: Result<(), String> = task_result(
+        "Error sampling balances",
+        cli_caller.call_process_balance_sample().await,
+    );
+
+    schedule_next(db, task, &mut task_details, result).await;
+}
+
+fn balance_sample_interval(balance_sample_interval: u64) -> u64 {
+    if balance_sample_interval == 0 {
+        DEFAULT_BALANCE_SAMPLE_INTERVAL as u64
+    } else {
+        balance_sample_interval
+    }
+}
+
+// Only plans the split, nothing is spent until it's confirmed from Telegram or gv-cli.
+async fn utxo_split_callback(db: &Arc<GVDB>, gv_config: &Arc<async_RwLock<GVConfig>>) {
+    let task: &str = "utxo_split";
+    info!("Running task: {}", task);
+    let mut task_details: Task = db.get_task(task.as_bytes()).unwrap();
+    toggle_running(db, task, &mut task_details).await;
+
+    let conf = gv_config.read().await;
+    let split_utxos: bool = conf.split_utxos && !conf.watch_only;
+
+    let cli_caller: CLICaller = CLICaller::new(&conf.cli_address, true)
+        .await
+        .unwrap()
+        .with_origin("internal")
+        .await;
+    drop(conf);
+
+    let result: Result<(), String> = if split_utxos {
+        task_result(
+            "Error planning UTXO split",
+            cli_caller.call_split_utxos().await,
+        )
+    } else {
+        Ok(())
+    };
+
+    schedule_next(db, task, &mut task_details, result).await;
+}
+
+fn utxo_split_interval(split_interval: u64) -> u64 {
+    if split_interval == 0 {
+        DEFAULT_SPLIT_INTERVAL as u64
+    } else {
+        split_interval
+    }
+}
+
+// Checks often and lets the server decide, a digest goes out once DIGEST_HOUR has passed.
+async fn digest_callback(db: &Arc<GVDB>, gv_config: &Arc<async_RwLock<GVConfig>>) {
+    let task: &str = "digest";
+    info!("Running task: {}", task);
+    let mut task_details: Task = db.get_task(task.as_bytes()).unwrap();
+    toggle_running(db, task, &mut task_details).await;
+
+    let conf = gv_config.read().await;
+    let digest_on: bool = conf.digest_daily || conf.digest_weekly;
+
+    let cli_caller: CLICaller = CLICaller::new(&conf.cli_address, true).await.unwrap();
+    drop(conf);
+
+    let result: Result<(), String> = if digest_on {
+        task_result(
+            "Error sending digest",
+            cli_caller.call_process_digest().await,
+        )
+    } else {
+        Ok(())
+    };
+
+    schedule_next(db, task, &mut task_details, result).await;
+}
+
+// Reads every tree, so like updates and resyncs it waits for the maintenance window.
+async fn db_maintenance_callback(db: &Arc<GVDB>, gv_config: &Arc<async_RwLock<GVConfig>>) {
+    let task: &str = "db_maintenance";
+    info!("Running task: {}", task);
+    let mut task_details: Task = db.get_task(task.as_bytes()).unwrap();
+    toggle_running(db, task, &mut task_details).await;
+
+    if !in_maint_window(gv_config).await {
+        task_details.next_run = get_current_time() + DB_MAINTENANCE_RETRY;
+        toggle_running(db, task, &mut task_details).await;
+        return;
+    }
+
+    let conf = gv_config.read().await;
+
+    let cli_caller: CLICaller = CLICaller::new(&conf.cli_address, true)
+        .await
+        .unwrap()
+        .with_origin("internal")
+        .await;
+    drop(conf);
+
+    let result: Result<(), String> = task_result(
+        "Error running database maintenance",
+        cli_caller.call_db_maintenance().await,
+    );
+
+    schedule_next(db, task, &mut task_details, result).await;
+}
+
+async fn wallet_rescan_callback(gv_config: &Arc<async_RwLock<GVConfig>>) {
+    info!("Running task: wallet_rescan");
+    let conf = gv_config.read().await;
+
+    let cli_caller: CLICaller = CLICaller::new(&conf.cli_address, true).await.unwrap();
+    drop(conf);
+    cli_caller.call_process_wallet_rescan().await.unwrap();
+}
+
+async fn maintenance_callback(gv_config: &Arc<async_RwLock<GVConfig>>) {
+    info!("Running task: maintenance");
+    let conf = gv_config.read().await;
+
+    let cli_caller: CLICaller = CLICaller::new(&conf.cli_address, true).await.unwrap();
+    drop(conf);
+    cli_caller.call_process_maintenance().await.unwrap();
+}
+
+// Errors are logged here, the message is kept as the task's last result.
+fn task_result<T>(
+    context: &str,
+    res: Result<T, Box<dyn std::error::Error + Send + Sync>>,
+) -> Result<(), String> {
+    match res {
+        Ok(_) => Ok(()),
+        Err(err) => {
+            error!("{}: {}", context, err);
+            Err(err.to_string())
+        }
+    }
+}
+
+async fn schedule_next(
+    db: &Arc<GVDB>,
+    task: &str,
+    task_details: &mut Task,
+    result: Result<(), String>,
+) {
     let current_time: i64 = get_current_time();
     let next_time: i64 = task_details.run_interval + current_time;
     task_details.next_run = next_time;
+    task_details.last_run = Some(current_time);
+    task_details.last_result = Some(match result {
+        Ok(_) => "ok".to_string(),
+        Err(err) => err,
+    });
+
+    // The task may have been paused while it ran.
+    if let Some(stored) = db.get_task(task.as_bytes()) {
+        task_details.paused = stored.paused;
+    }
 
     db.set_task(task.as_bytes(), task_details).await.unwrap();
     toggle_running(db, task, task_details).await;
@@ -180,6 +451,24 @@ async fn toggle_running(db: &Arc<GVDB>, task: &str, task_details: &mut Task) {
     db.set_task(task.as_bytes(), &task_details).await.unwrap();
 }
 
+// BACKUP_INTERVAL, BALANCE_SAMPLE_INTERVAL and SPLIT_INTERVAL are only read from the config
+// file, so they're copied to their tasks at startup and on reload_config.
+pub async fn sync_task_intervals(db: &Arc<GVDB>, conf: &GVConfig) {
+    for (task, run_interval) in [
+        ("auto_backup", backup_run_interval(conf.backup_interval)),
+        (
+            "balance_sample",
+            balance_sample_interval(conf.balance_sample_interval),
+        ),
+        ("utxo_split", utxo_split_interval(conf.split_interval)),
+    ] {
+        if let Some(mut task_details) = db.get_task(task.as_bytes()) {
+            task_details.run_interval = run_interval as i64;
+            db.set_task(task.as_bytes(), &task_details).await.unwrap();
+        }
+    }
+}
+
 pub async fn update_payout_interval(db: &Arc<GVDB>, new_interval: i64) -> std::io::Result<()> {
     let task: &str = "process_rewards";
     let mut task_details: Task = db.get_task(task.as_bytes()).unwrap();
@@ -208,6 +497,51 @@ pub async fn update_payout_min(db: &Arc<GVDB>, new_min_payout: u64) -> std::io::
     Ok(())
 }
 
+pub fn get_scheduled_tasks(db: &Arc<GVDB>) -> Vec<Task> {
+    SCHEDULED_TASKS
+        .iter()
+        .filter_map(|task| db.get_task(task.as_bytes()))
+        .collect()
+}
+
+// The runner picks the task up on its next pass, within a few seconds.
+pub async fn run_task_now(db: &Arc<GVDB>, task: &str) -> std::io::Result<()> {
+    let mut task_details: Task = db.get_task(task.as_bytes()).unwrap();
+    task_details.next_run = get_current_time();
+
+    db.set_task(task.as_bytes(), &task_details).await.unwrap();
+
+    Ok(())
+}
+
+pub async fn pause_task(db: &Arc<GVDB>, task: &str, paused: bool) -> std::io::Result<()> {
+    let mut task_details: Task = db.get_task(task.as_bytes()).unwrap();
+    task_details.paused = paused;
+
+    db.set_task(task.as_bytes(), &task_details).await.unwrap();
+
+    Ok(())
+}
+
+pub async fn in_maint_window(gv_config: &Arc<async_RwLock<GVConfig>>) -> bool {
+    let conf = gv_config.read().await;
+    let window_start: u32 = conf.maint_window_start;
+    let window_end: u32 = conf.maint_window_end;
+    let tz: Tz = Tz::from_str_insensitive(&conf.timezone).unwrap_or(Tz::UTC);
+    drop(conf);
+
+    let hour: u32 = chrono::Utc::now().with_timezone(&tz).hour();
+
+    // A window such as 22 to 4 wraps past midnight, equal hours means any time.
+    if window_start == window_end {
+        true
+    } else if window_start < window_end {
+        (window_start..window_end).contains(&hour)
+    } else {
+        hour >= window_start || hour < window_end
+    }
+}
+
 fn get_current_time() -> i64 {
     let current_time = chrono::Utc::now();
     let timestamp: i64 = current_time.timestamp();
@@ -1,13 +1,21 @@
 use crate::{
+    announce::{self, announce_enabled, AnnounceDestination},
     config::GVConfig,
-    gvdb::{NewStakeStatusDB, TgBotQueueDB, GVDB},
-    tg_bot::keyboards::make_link_button,
+    gv_client_methods::{CLICaller, FleetVault},
+    gvdb::{HeldNoticeDB, NewStakeStatusDB, TgBotQueueDB, GVDB},
+    tg_bot::{
+        keyboards::{
+            make_inline_chain_emergency_menu, make_inline_reward_change_menu,
+            make_inline_split_menu, make_link_button,
+        },
+        tg_bot::vault_prefix,
+    },
 };
 use log::{info, warn};
 use std::sync::Arc;
 use teloxide::{
     adaptors::DefaultParseMode, payloads::SendMessageSetters, prelude::*, types::MessageId,
-    utils::markdown::escape,
+    utils::markdown::escape, RequestError,
 };
 use tokio::sync::RwLock as async_RwLock;
 
@@ -47,6 +55,271 @@ impl BotRunner {
         }
     }
 
+    // Action menus are only attached for the admin, vault_index is None for the channel.
+    async fn send_queued_message(
+        &self,
+        chat_id: &str,
+        msg_details: &TgBotQueueDB,
+        prefix: &str,
+        vault_index: Option<usize>,
+    ) -> Result<Message, RequestError> {
+        let message: String = queued_message_text(msg_details, prefix);
+
+        match (vault_index, &msg_details.url) {
+            (Some(vault_index), _) if msg_details.msg_type == "emergency" => {
+                self.bot
+                    .send_message(chat_id.to_string(), message)
+                    .reply_markup(make_inline_chain_emergency_menu(vault_index))
+                    .await
+            }
+            (Some(vault_index), _) if msg_details.msg_type == "split" => {
+                let token: &str = msg_details.action_token.as_deref().unwrap_or("");
+
+                self.bot
+                    .send_message(chat_id.to_string(), message)
+                    .reply_markup(make_inline_split_menu(vault_index, token))
+                    .await
+            }
+            (Some(vault_index), _) if msg_details.msg_type == "reward_change" => {
+                self.bot
+                    .send_message(chat_id.to_string(), message)
+                    .reply_markup(make_inline_reward_change_menu(vault_index))
+                    .await
+            }
+            (_, Some(links)) => {
+                let keyboard = make_link_button(links, "View on explorer");
+
+                self.bot
+                    .send_message(chat_id.to_string(), message)
+                    .reply_markup(keyboard)
+                    .await
+            }
+            _ => self.bot.send_message(chat_id.to_string(), message).await,
+        }
+    }
+
+    // A vault's sync progress goes into one message that's edited as it moves along, instead of
+    // a new one every few minutes. "synced" edits it one last time and lets it go. A wallet import
+    // gets its own message the same way.
+    async fn edit_sync_message(
+        &self,
+        msg_details: &TgBotQueueDB,
+        prefix: &str,
+        vault_name: &str,
+    ) -> Result<(), RequestError> {
+        let message: String = queued_message_text(msg_details, prefix);
+        let done: bool = matches!(msg_details.msg_type.as_str(), "synced" | "imported");
+        let import_key: String = format!("{}:import", vault_name);
+        let vault_name: &str = match msg_details.msg_type.as_str() {
+            "import" | "imported" => &import_key,
+            _ => vault_name,
+        };
+
+        let edited: bool = match self.db.get_sync_message(vault_name) {
+            Some(msg_id) => self
+                .bot
+                .edit_message_text(self.tg_user.clone(), msg_id, message.clone())
+                .await
+                .is_ok(),
+            None => false,
+        };
+
+        // The old message was deleted or is too old to edit, start a new one
+        let msg_id: Option<MessageId> = if edited {
+            self.db.get_sync_message(vault_name)
+        } else {
+            Some(
+                self.bot
+                    .send_message(self.tg_user.clone(), message)
+                    .await?
+                    .id,
+            )
+        };
+
+        let _ = self
+            .db
+            .set_sync_message(vault_name, msg_id.filter(|_| !done))
+            .await;
+
+        Ok(())
+    }
+
+    // A stake's confirmations, maturing or orphaning replace the text of its first message so
+    // each stake stays one message. Only the admin's copy is edited, the channel's is left as
+    // it was sent. Returns false when there's no message left to edit.
+    async fn edit_stake_message(&self, msg_details: &TgBotQueueDB, prefix: &str) -> bool {
+        let msg_id: MessageId = match msg_details.msg_to_edit {
+            Some(msg_id) => msg_id,
+            None => return false,
+        };
+
+        let message: String = queued_message_text(msg_details, prefix);
+        let edit = self
+            .bot
+            .edit_message_text(self.tg_user.clone(), msg_id, message);
+
+        let edited = match &msg_details.url {
+            Some(links) => {
+                edit.reply_markup(make_link_button(links, "View on explorer"))
+                    .await
+            }
+            None => edit.await,
+        };
+
+        match edited {
+            Ok(_) => true,
+            Err(err) => {
+                warn!("Error editing stake message: {:?}", err);
+                false
+            }
+        }
+    }
+
+    // Sends to the Telegram destinations routed for the message. The admin's copy is returned
+    // so stake messages can be edited later. Destinations in quiet hours hold it instead.
+    async fn route_queued_message(
+        &self,
+        msg_details: &TgBotQueueDB,
+        prefix: &str,
+        vault_index: usize,
+        tg_channel: &Option<String>,
+        quiet: &[AnnounceDestination],
+    ) -> Result<Option<Message>, RequestError> {
+        let mut destinations: Vec<AnnounceDestination> =
+            self.db.announce_destinations(&msg_details.msg_type);
+
+        if !announce::bypasses_quiet_hours(&msg_details.msg_type) {
+            for destination in quiet {
+                if destinations.contains(destination) {
+                    let _ = self
+                        .db
+                        .hold_notice(*destination, &announce::held_notice(msg_details, prefix))
+                        .await;
+                }
+            }
+
+            destinations.retain(|destination| !quiet.contains(destination));
+        }
+
+        if let Some(channel) = tg_channel
+            .as_deref()
+            .filter(|_| destinations.contains(&AnnounceDestination::TelegramChannel))
+        {
+            if let Err(err) = self
+                .send_queued_message(channel, msg_details, prefix, None)
+                .await
+            {
+                warn!("Error sending message to channel: {:?}", err);
+            }
+        }
+
+        if destinations.contains(&AnnounceDestination::TelegramAdmin) {
+            self.send_queued_message(&self.tg_user, msg_details, prefix, Some(vault_index))
+                .await
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    // The Telegram destinations in their quiet hours right now.
+    fn quiet_destinations(&self, conf: &GVConfig) -> Vec<AnnounceDestination> {
+        [
+            AnnounceDestination::TelegramAdmin,
+            AnnounceDestination::TelegramChannel,
+        ]
+        .into_iter()
+        .filter(|destination| announce::in_quiet_hours(&self.db, conf, *destination))
+        .collect()
+    }
+
+    // Once a destination's quiet hours are over, what was held for it goes out as one message.
+    async fn send_held_notices(&self, quiet: &[AnnounceDestination], tg_channel: &Option<String>) {
+        for (destination, chat_id) in [
+            (
+                AnnounceDestination::TelegramAdmin,
+                Some(self.tg_user.clone()),
+            ),
+            (AnnounceDestination::TelegramChannel, tg_channel.clone()),
+        ] {
+            if quiet.contains(&destination) || self.db.count_held_notices(destination) == 0 {
+                continue;
+            }
+
+            let notices: Vec<HeldNoticeDB> = match self.db.take_held_notices(destination).await {
+                Ok(notices) => notices,
+                Err(err) => {
+                    warn!("Error reading held notices: {}", err);
+                    continue;
+                }
+            };
+
+            // The channel was unset while notices were held for it
+            let chat_id: String = match chat_id {
+                Some(chat_id) => chat_id,
+                None => continue,
+            };
+
+            let summary: TgBotQueueDB = announce::quiet_hours_summary(&notices);
+
+            if let Err(err) = self.send_queued_message(&chat_id, &summary, "", None).await {
+                warn!("Error sending quiet hours summary: {:?}", err);
+            }
+        }
+    }
+
+    // Pull the queued messages of the other vaults in TG_VAULTS, they need BOT_RELAY turned on.
+    async fn relay_vault_messages(&self, vaults: &[FleetVault]) {
+        for (index, vault) in vaults.iter().enumerate() {
+            let connect = tokio::time::timeout(
+                tokio::time::Duration::from_secs(5),
+                CLICaller::new(&vault.cli_address, true),
+            )
+            .await;
+
+            let cli_caller: CLICaller = match connect {
+                Ok(Ok(cli_caller)) => cli_caller,
+                _ => continue,
+            };
+
+            let messages: Vec<TgBotQueueDB> = match cli_caller.call_take_bot_messages().await {
+                Ok(messages) => messages,
+                Err(err) => {
+                    warn!("Error relaying messages from {}: {}", vault.name, err);
+                    continue;
+                }
+            };
+
+            let conf = self.gv_config.read().await;
+            let prefix: String = vault_prefix(&conf, &vault.name);
+            let tg_channel: Option<String> = conf.tg_channel.clone();
+            let quiet: Vec<AnnounceDestination> = self.quiet_destinations(&conf);
+            let messages: Vec<TgBotQueueDB> = messages
+                .into_iter()
+                .filter(|msg_details| announce_enabled(&conf, &msg_details.msg_type))
+                .collect();
+            drop(conf);
+
+            for msg_details in messages {
+                let sent = if matches!(
+                    msg_details.msg_type.as_str(),
+                    "sync" | "synced" | "import" | "imported"
+                ) {
+                    self.edit_sync_message(&msg_details, &prefix, &vault.name)
+                        .await
+                } else {
+                    self.route_queued_message(&msg_details, &prefix, index + 1, &tg_channel, &quiet)
+                        .await
+                        .map(|_| ())
+                };
+
+                if let Err(err) = sent {
+                    warn!("Error sending message: {:?}", err);
+                }
+            }
+        }
+    }
+
     pub async fn background_task(&self) {
         loop {
             let current_time = chrono::Utc::now();
@@ -54,6 +327,12 @@ impl BotRunner {
             let five_minutes: u64 = 300;
 
             let conf = self.gv_config.read().await;
+            let prefix: String = vault_prefix(&conf, &conf.vault_name);
+            let tg_vaults: Vec<FleetVault> = conf.tg_vaults.clone();
+            let tg_channel: Option<String> = conf.tg_channel.clone();
+            let quiet: Vec<AnnounceDestination> = self.quiet_destinations(&conf);
+
+            self.send_held_notices(&quiet, &tg_channel).await;
 
             for result in self.db.tg_bot_queue.iter() {
                 match result {
@@ -69,36 +348,50 @@ impl BotRunner {
                         }
 
                         match msg_details.msg_type.as_str() {
-                            "rewards" => {
-                                if !conf.announce_rewards {
+                            "stake_removal" | "stake_confirmations" | "stake_matured" => {
+                                if !announce_enabled(&conf, &msg_details.msg_type) {
                                     self.db.remove_tg_bot_queue(key).await.unwrap();
                                     continue;
                                 }
-                            }
-                            "stake" => {
-                                if !conf.announce_stakes {
+
+                                // Queued before stake messages were edited in place
+                                if let Some(msg_id) = msg_details.msg_to_delete {
+                                    let _ =
+                                        self.bot.delete_message(self.tg_user.clone(), msg_id).await;
                                     self.db.remove_tg_bot_queue(key).await.unwrap();
                                     continue;
                                 }
-                            }
-                            "zap" => {
-                                if !conf.announce_zaps {
+
+                                // A stake whose message is gone still gets told it matured,
+                                // progress and orphans only make sense on the original.
+                                if self.edit_stake_message(&msg_details, &prefix).await
+                                    || msg_details.msg_type != "stake_matured"
+                                {
                                     self.db.remove_tg_bot_queue(key).await.unwrap();
                                     continue;
                                 }
                             }
-                            "offline" | "online" => {
-                                // Do nothing
-                            }
-                            "stake_removal" => {
-                                if msg_details.msg_to_delete.is_some() {
-                                    let msg_id: MessageId = msg_details.msg_to_delete.unwrap();
-                                    let _ =
-                                        self.bot.delete_message(self.tg_user.clone(), msg_id).await;
+                            "sync" | "synced" | "import" | "imported" => {
+                                if let Err(err) = self
+                                    .edit_sync_message(&msg_details, &prefix, &conf.vault_name)
+                                    .await
+                                {
+                                    warn!("Error sending sync message: {:?}", err);
+                                    continue;
                                 }
                                 self.db.remove_tg_bot_queue(key).await.unwrap();
                                 continue;
                             }
+                            "rewards" | "stake" | "streak" | "zap" | "offline" | "online"
+                            | "update" | "bad_chain" | "drought" | "incident" | "disk"
+                            | "backup" | "emergency" | "split" | "digest" | "reward_change"
+                            | "reward_changed" | "mnemonic_access" | "deposit_missed"
+                            | "payout_deferred" | "reindex" => {
+                                if !announce_enabled(&conf, &msg_details.msg_type) {
+                                    self.db.remove_tg_bot_queue(key).await.unwrap();
+                                    continue;
+                                }
+                            }
                             _ => {
                                 info!("Unknown message type: {}", msg_details.msg_type);
                                 self.db.tg_bot_queue.remove(key).unwrap();
@@ -106,47 +399,23 @@ impl BotRunner {
                             }
                         }
 
-                        let mut message = String::from(
-                            escape(format!("{}\n\n", msg_details.header).as_str()).as_str(),
-                        );
-
-                        if msg_details.code_block.is_some() {
-                            message.push_str(
-                                format!("```\n{}\n```\n", msg_details.code_block.unwrap()).as_str(),
-                            );
-                        }
-
-                        if msg_details.msg.is_some() {
-                            message.push_str(
-                                escape(format!("{}\n", msg_details.msg.unwrap()).as_str()).as_str(),
-                            );
-                        }
-
-                        let sent_msg_res = if msg_details.url.is_some() {
-                            let links = msg_details.url.unwrap();
-                            let keyboard = make_link_button(&links, "View on Ghostscan");
+                        let msg_type: String = msg_details.msg_type.clone();
+                        let reward_txid: Option<String> = msg_details.reward_txid.clone();
+                        let sent_msg_res = self
+                            .route_queued_message(&msg_details, &prefix, 0, &tg_channel, &quiet)
+                            .await;
 
-                            self.bot
-                                .send_message(self.tg_user.clone(), message)
-                                .reply_markup(keyboard)
-                                .await
-                        } else {
-                            self.bot.send_message(self.tg_user.clone(), message).await
-                        };
-
-                        let sent_msg = if sent_msg_res.is_err() {
-                            let err_msg = sent_msg_res.err().unwrap();
-                            warn!("Error sending message: {:?}", err_msg);
-                            continue;
-                        } else {
-                            sent_msg_res.unwrap()
+                        let sent_msg = match sent_msg_res {
+                            Ok(sent_msg) => sent_msg,
+                            Err(err_msg) => {
+                                warn!("Error sending message: {:?}", err_msg);
+                                continue;
+                            }
                         };
 
-                        if msg_details.msg_type.as_str() == "stake"
-                            && msg_details.reward_txid.is_some()
+                        if let (Some(reward_txid), Some(sent_msg)) =
+                            (reward_txid.filter(|_| msg_type == "stake"), sent_msg)
                         {
-                            let reward_txid = msg_details.reward_txid.unwrap();
-
                             let stake_status: Option<NewStakeStatusDB> =
                                 self.db.get_new_stake_status(reward_txid.as_bytes());
 
@@ -171,7 +440,24 @@ impl BotRunner {
 
             drop(conf);
 
+            self.relay_vault_messages(&tg_vaults).await;
+
             tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
         }
     }
 }
+
+fn queued_message_text(msg_details: &TgBotQueueDB, prefix: &str) -> String {
+    let mut message =
+        String::from(escape(format!("{}{}\n\n", prefix, msg_details.header).as_str()).as_str());
+
+    if let Some(code_block) = &msg_details.code_block {
+        message.push_str(format!("```\n{}\n```\n", code_block).as_str());
+    }
+
+    if let Some(msg) = &msg_details.msg {
+        message.push_str(escape(format!("{}\n", msg).as_str()).as_str());
+    }
+
+    message
+}
@@ -1,14 +1,36 @@
-use crate::gv_client_methods::{AllTimeEarnigns, BarChart};
-use chrono::DateTime;
+use crate::gv_client_methods::{
+    AllTimeEarnigns, BalanceHistory, BarChart, NetworkStats, StakeHeatmap,
+};
+use chrono::{DateTime, Datelike, Days, NaiveDate};
 use plotters::prelude::*;
-use serde_json::Value;
+use qrcode::{Color as QrColor, QrCode};
 
-pub fn make_barchart(data_value: &Value) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let bc_data: BarChart = serde_json::from_value(data_value.to_owned())?;
+// Charts are drawn into an RGB buffer and returned as PNG bytes, nothing touches the disk so
+// any number of chart requests can render at once.
+const CHART_SIZE: (u32, u32) = (640, 480);
+
+fn encode_png(rgb: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut png_bytes: Vec<u8> = Vec::new();
+
+    let mut encoder = png::Encoder::new(&mut png_bytes, CHART_SIZE.0, CHART_SIZE.1);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(rgb)?;
+    writer.finish()?;
+
+    Ok(png_bytes)
+}
+
+pub fn make_barchart(
+    bc_data: BarChart,
+) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
     let data = bc_data.data;
     let division = bc_data.division.as_str();
 
-    let root = BitMapBackend::new("/tmp/barchart.png", (640, 480)).into_drawing_area();
+    let mut rgb: Vec<u8> = vec![0; (CHART_SIZE.0 * CHART_SIZE.1 * 3) as usize];
+    let root = BitMapBackend::with_buffer(&mut rgb, CHART_SIZE).into_drawing_area();
 
     root.fill(&RGBColor(23, 26, 26))?;
 
@@ -162,12 +184,16 @@ pub fn make_barchart(data_value: &Value) -> Result<(), Box<dyn std::error::Error
     )?;
 
     root.present()?;
+    // The backend borrows the buffer until it's dropped
+    drop(chart);
+    drop(root);
 
-    Ok(())
+    encode_png(&rgb)
 }
 
-pub fn make_area_chart(data_value: &Value) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let chart_data: AllTimeEarnigns = serde_json::from_value(data_value.to_owned())?;
+pub fn make_area_chart(
+    chart_data: AllTimeEarnigns,
+) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
     let data = chart_data.data;
 
     if data.is_empty() {
@@ -183,7 +209,8 @@ pub fn make_area_chart(data_value: &Value) -> Result<(), Box<dyn std::error::Err
     let x_range = 0..(enum_data.last().unwrap().0 + 1);
     let y_range = (enum_data.first().unwrap().1)..(enum_data.last().unwrap().1 + 1.0);
 
-    let root = BitMapBackend::new("/tmp/total_earnings_chart.png", (640, 480)).into_drawing_area();
+    let mut rgb: Vec<u8> = vec![0; (CHART_SIZE.0 * CHART_SIZE.1 * 3) as usize];
+    let root = BitMapBackend::with_buffer(&mut rgb, CHART_SIZE).into_drawing_area();
 
     root.fill(&RGBColor(23, 26, 26))?;
 
@@ -238,8 +265,11 @@ pub fn make_area_chart(data_value: &Value) -> Result<(), Box<dyn std::error::Err
     ))?;
 
     root.present()?;
+    // The backend borrows the buffer until it's dropped
+    drop(chart);
+    drop(root);
 
-    Ok(())
+    encode_png(&rgb)
 }
 
 fn get_ts_from_index(index: &u64, data: Vec<(u64, f64, u64)>) -> i64 {
@@ -259,3 +289,347 @@ fn get_ts_from_week(week: &u64, data: Vec<(u64, u64, u64)>) -> i64 {
     }
     0
 }
+
+pub fn make_heatmap(
+    heatmap: StakeHeatmap,
+) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    if heatmap.data.is_empty() {
+        return Err("No Data".into());
+    }
+
+    let days: Vec<(NaiveDate, u64)> = heatmap
+        .data
+        .iter()
+        .map(|day| {
+            Ok((
+                NaiveDate::parse_from_str(&day.date, "%Y-%m-%d")?,
+                day.stakes,
+            ))
+        })
+        .collect::<Result<Vec<(NaiveDate, u64)>, chrono::ParseError>>()?;
+
+    // Columns are weeks from Sunday to Saturday, the first one starts on the Sunday on or
+    // before the first day. Rows count up from the bottom, so Sunday is row 6. Each cell is
+    // 10 units wide so the key points can sit in the middle of it.
+    let first_day: NaiveDate = days[0].0;
+    let grid_start: NaiveDate =
+        first_day - Days::new(first_day.weekday().num_days_from_sunday() as u64);
+    let cell = |date: NaiveDate| -> (i32, i32) {
+        let offset: i32 = (date - grid_start).num_days() as i32;
+        (offset / 7, 6 - offset % 7)
+    };
+    let weeks: i32 = cell(days[days.len() - 1].0).0 + 1;
+
+    // Label the first column and each column where a month starts, thinned out for long
+    // ranges so the labels don't overlap.
+    let mut month_columns: Vec<i32> = vec![0];
+    for (date, _) in &days {
+        let (week, _) = cell(*date);
+        if date.day() == 1 && week > month_columns[month_columns.len() - 1] {
+            month_columns.push(week);
+        }
+    }
+    let label_step: usize = month_columns.len().div_ceil(24);
+    let x_points: Vec<i32> = month_columns
+        .iter()
+        .step_by(label_step)
+        .map(|week| week * 10 + 5)
+        .collect();
+    let y_points: Vec<i32> = (0..7).map(|row| row * 10 + 5).collect();
+
+    let mut rgb: Vec<u8> = vec![0; (CHART_SIZE.0 * CHART_SIZE.1 * 3) as usize];
+    let root = BitMapBackend::with_buffer(&mut rgb, CHART_SIZE).into_drawing_area();
+
+    root.fill(&RGBColor(23, 26, 26))?;
+
+    let date_range = format!("{} - {}", heatmap.start, heatmap.end);
+
+    let mut chart = ChartBuilder::on(&root)
+        .x_label_area_size(60)
+        .y_label_area_size(45)
+        .margin(5)
+        .caption(date_range, ("sans-serif", 24.0).with_color(WHITE))
+        .build_cartesian_2d(
+            (0..weeks * 10).with_key_points(x_points),
+            (0..70).with_key_points(y_points),
+        )?;
+
+    let weekdays: [&str; 7] = ["Sat", "Fri", "Thu", "Wed", "Tue", "Mon", "Sun"];
+
+    chart
+        .configure_mesh()
+        .disable_mesh()
+        .x_desc(format!(
+            "{} stakes, at most {} in a day",
+            heatmap.total_stakes, heatmap.max_stakes
+        ))
+        .axis_desc_style(("sans-serif", 15).into_font().color(&WHITE))
+        .y_label_style(("sans-serif", 15).into_font().color(&WHITE))
+        .x_label_style(("sans-serif", 15).into_font().color(&WHITE))
+        .y_label_formatter(&|y| weekdays.get(*y as usize / 10).unwrap_or(&"").to_string())
+        .x_label_formatter(&|x| {
+            let column_start: NaiveDate = grid_start + Days::new(*x as u64 / 10 * 7);
+            let column_end: NaiveDate = column_start + Days::new(6);
+
+            // A column holding the 1st is labelled with the month it starts
+            let label_day: NaiveDate = if column_end.day() <= 7 {
+                column_end
+            } else {
+                column_start.max(first_day)
+            };
+            label_day.format("%m/%y").to_string()
+        })
+        .draw()?;
+
+    let max_stakes: f64 = heatmap.max_stakes.max(1) as f64;
+
+    chart.draw_series(days.iter().map(|(date, stakes)| {
+        let (week, row) = cell(*date);
+
+        // Empty days stay grey, the rest fade in with the number of stakes
+        let color: RGBAColor = if *stakes == 0 {
+            RGBColor(45, 50, 50).mix(1.0)
+        } else {
+            RGBColor(174, 255, 0).mix(0.25 + 0.75 * (*stakes as f64 / max_stakes))
+        };
+
+        Rectangle::new(
+            [(week * 10 + 1, row * 10 + 1), (week * 10 + 9, row * 10 + 9)],
+            color.filled(),
+        )
+    }))?;
+
+    root.present()?;
+    // The backend borrows the buffer until it's dropped
+    drop(chart);
+    drop(root);
+
+    encode_png(&rgb)
+}
+
+pub fn make_balance_chart(
+    history: BalanceHistory,
+) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    let data = history.data;
+
+    if data.len() < 2 {
+        return Err("No Data".into());
+    }
+
+    let x_range = (data[0][0] as i64)..(data[data.len() - 1][0] as i64 + 1);
+    let max_balance: f64 = data
+        .iter()
+        .flat_map(|sample| sample[1..].iter().copied())
+        .fold(0.0, f64::max);
+    let y_range = 0.0..(max_balance * 1.1).max(1.0);
+    let y_chars = (max_balance as u64).to_string().len() as u32;
+
+    let mut rgb: Vec<u8> = vec![0; (CHART_SIZE.0 * CHART_SIZE.1 * 3) as usize];
+    let root = BitMapBackend::with_buffer(&mut rgb, CHART_SIZE).into_drawing_area();
+
+    root.fill(&RGBColor(23, 26, 26))?;
+
+    let date_range = format!("{} - {}", history.start, history.end);
+
+    let mut chart = ChartBuilder::on(&root)
+        .x_label_area_size(75)
+        .y_label_area_size(30 + y_chars * 10)
+        .margin(5)
+        .caption(date_range, ("sans-serif", 24.0).with_color(WHITE))
+        .build_cartesian_2d(x_range, y_range)?;
+
+    chart
+        .configure_mesh()
+        .disable_mesh()
+        .y_desc("GHOST")
+        .x_desc("Date")
+        .axis_desc_style(("sans-serif", 15).into_font().color(&WHITE))
+        .y_label_style(("sans-serif", 15).into_font().color(&WHITE))
+        .x_label_style(
+            ("sans-serif", 15)
+                .into_font()
+                .color(&WHITE)
+                .transform(FontTransform::Rotate270),
+        )
+        .y_label_formatter(&|y| format!("{}", *y as u64))
+        .x_label_formatter(&|x| {
+            let date = DateTime::from_timestamp(*x, 0).unwrap_or_default();
+            date.format("%d/%m/%y           ").to_string()
+        })
+        .x_labels(20)
+        .draw()?;
+
+    // Column 1 is currently staking, 2 the total in cold staking and 3 the anon balance
+    let series: [(usize, &str, RGBColor); 3] = [
+        (1, "Staking", RGBColor(174, 255, 0)),
+        (2, "Cold staking", RGBColor(0, 190, 255)),
+        (3, "Anon", RGBColor(255, 170, 0)),
+    ];
+
+    for (column, label, color) in series {
+        chart
+            .draw_series(LineSeries::new(
+                data.iter().map(|sample| (sample[0] as i64, sample[column])),
+                color.stroke_width(2),
+            ))?
+            .label(label)
+            .legend(move |(x, y)| {
+                PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2))
+            });
+    }
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::LowerRight)
+        .background_style(RGBColor(23, 26, 26).mix(0.8))
+        .border_style(WHITE.mix(0.3))
+        .label_font(("sans-serif", 15).into_font().color(&WHITE))
+        .draw()?;
+
+    root.present()?;
+    // The backend borrows the buffer until it's dropped
+    drop(chart);
+    drop(root);
+
+    encode_png(&rgb)
+}
+
+// The network weight is far larger than the vault's, so each gets its own axis, the network on
+// the left and the vault on the right.
+pub fn make_network_chart(
+    stats: NetworkStats,
+) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    let data = stats.data;
+
+    if data.len() < 2 {
+        return Err("No Data".into());
+    }
+
+    let x_range = (data[0][0] as i64)..(data[data.len() - 1][0] as i64 + 1);
+    let max_network: f64 = data.iter().map(|sample| sample[1]).fold(0.0, f64::max);
+    let max_ours: f64 = data.iter().map(|sample| sample[2]).fold(0.0, f64::max);
+    let y_chars = (max_network as u64).to_string().len() as u32;
+    let y2_chars = (max_ours as u64).to_string().len() as u32;
+
+    let mut rgb: Vec<u8> = vec![0; (CHART_SIZE.0 * CHART_SIZE.1 * 3) as usize];
+    let root = BitMapBackend::with_buffer(&mut rgb, CHART_SIZE).into_drawing_area();
+
+    root.fill(&RGBColor(23, 26, 26))?;
+
+    let date_range = format!("{} - {}", stats.start, stats.end);
+
+    let mut chart = ChartBuilder::on(&root)
+        .x_label_area_size(75)
+        .y_label_area_size(30 + y_chars * 10)
+        .right_y_label_area_size(30 + y2_chars * 10)
+        .margin(5)
+        .caption(date_range, ("sans-serif", 24.0).with_color(WHITE))
+        .build_cartesian_2d(x_range.clone(), 0.0..(max_network * 1.1).max(1.0))?
+        .set_secondary_coord(x_range, 0.0..(max_ours * 1.1).max(1.0));
+
+    chart
+        .configure_mesh()
+        .disable_mesh()
+        .y_desc("Network GHOST")
+        .x_desc("Date")
+        .axis_desc_style(("sans-serif", 15).into_font().color(&WHITE))
+        .y_label_style(("sans-serif", 15).into_font().color(&WHITE))
+        .x_label_style(
+            ("sans-serif", 15)
+                .into_font()
+                .color(&WHITE)
+                .transform(FontTransform::Rotate270),
+        )
+        .y_label_formatter(&|y| format!("{}", *y as u64))
+        .x_label_formatter(&|x| {
+            let date = DateTime::from_timestamp(*x, 0).unwrap_or_default();
+            date.format("%d/%m/%y           ").to_string()
+        })
+        .x_labels(20)
+        .draw()?;
+
+    chart
+        .configure_secondary_axes()
+        .y_desc("Vault GHOST")
+        .axis_desc_style(("sans-serif", 15).into_font().color(&WHITE))
+        .label_style(("sans-serif", 15).into_font().color(&WHITE))
+        .y_label_formatter(&|y| format!("{}", *y as u64))
+        .draw()?;
+
+    let network_color: RGBColor = RGBColor(0, 190, 255);
+    let ours_color: RGBColor = RGBColor(174, 255, 0);
+
+    chart
+        .draw_series(LineSeries::new(
+            data.iter().map(|sample| (sample[0] as i64, sample[1])),
+            network_color.stroke_width(2),
+        ))?
+        .label("Network weight")
+        .legend(move |(x, y)| {
+            PathElement::new(vec![(x, y), (x + 20, y)], network_color.stroke_width(2))
+        });
+
+    chart
+        .draw_secondary_series(LineSeries::new(
+            data.iter().map(|sample| (sample[0] as i64, sample[2])),
+            ours_color.stroke_width(2),
+        ))?
+        .label("My weight")
+        .legend(move |(x, y)| {
+            PathElement::new(vec![(x, y), (x + 20, y)], ours_color.stroke_width(2))
+        });
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::LowerRight)
+        .background_style(RGBColor(23, 26, 26).mix(0.8))
+        .border_style(WHITE.mix(0.3))
+        .label_font(("sans-serif", 15).into_font().color(&WHITE))
+        .draw()?;
+
+    root.present()?;
+    // The backend borrows the buffer until it's dropped
+    drop(chart);
+    drop(root);
+
+    encode_png(&rgb)
+}
+
+// Each QR module is drawn as a square of QR_MODULE_PX pixels, with the quiet zone the spec asks
+// for around the code.
+const QR_MODULE_PX: usize = 8;
+const QR_QUIET_ZONE: usize = 4;
+
+pub fn make_qr_code(data: &str) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    let code: QrCode = QrCode::new(data.as_bytes())?;
+    let colors: Vec<QrColor> = code.to_colors();
+    let width: usize = code.width();
+    let size: usize = (width + QR_QUIET_ZONE * 2) * QR_MODULE_PX;
+
+    let mut gray: Vec<u8> = vec![255; size * size];
+
+    for (index, color) in colors.iter().enumerate() {
+        if *color != QrColor::Dark {
+            continue;
+        }
+
+        let x: usize = (index % width + QR_QUIET_ZONE) * QR_MODULE_PX;
+        let y: usize = (index / width + QR_QUIET_ZONE) * QR_MODULE_PX;
+
+        for row in y..y + QR_MODULE_PX {
+            gray[row * size + x..row * size + x + QR_MODULE_PX].fill(0);
+        }
+    }
+
+    let mut png_bytes: Vec<u8> = Vec::new();
+
+    let mut encoder = png::Encoder::new(&mut png_bytes, size as u32, size as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&gray)?;
+    writer.finish()?;
+
+    Ok(png_bytes)
+}
@@ -0,0 +1,500 @@
+// Everything the bot answers to, in one place. The slash commands registered with Telegram, the
+// keyboard buttons and /help are all built from BOT_COMMANDS, so a new entry shows up in each.
+use teloxide::types::{BotCommand, KeyboardButton};
+
+pub struct BotCommandSpec {
+    pub id: &'static str,
+    // Without the slash, also offered in Telegram's command menu.
+    pub command: Option<&'static str>,
+    pub args: &'static str,
+    // The keyboard label, matched case insensitively like the slash command.
+    pub button: Option<&'static str>,
+    pub section: &'static str,
+    pub description: &'static str,
+}
+
+// Order of the sections in /help.
+pub const HELP_SECTIONS: [&str; 6] = [
+    "Main",
+    "Commands",
+    "Stats",
+    "Bot Settings",
+    "GhostVault Options",
+    "Reward Options",
+];
+
+const fn spec(
+    id: &'static str,
+    command: Option<&'static str>,
+    args: &'static str,
+    button: Option<&'static str>,
+    section: &'static str,
+    description: &'static str,
+) -> BotCommandSpec {
+    BotCommandSpec {
+        id,
+        command,
+        args,
+        button,
+        section,
+        description,
+    }
+}
+
+pub const BOT_COMMANDS: &[BotCommandSpec] = &[
+    spec(
+        "status",
+        Some("status"),
+        "",
+        Some("\u{2139}\u{FE0F} Status"),
+        "Main",
+        "Full status of the selected vault",
+    ),
+    spec(
+        "stats",
+        Some("stats"),
+        "",
+        Some("\u{1F4CA} Stats"),
+        "Main",
+        "Stakes, rewards and charts",
+    ),
+    spec(
+        "bot_settings",
+        Some("settings"),
+        "",
+        Some("\u{2699}\u{FE0F} Bot Settings"),
+        "Main",
+        "Announcements, routes, digests and quiet hours",
+    ),
+    spec(
+        "gv_options",
+        None,
+        "",
+        Some("\u{2699}\u{FE0F} GhostVault Options"),
+        "Main",
+        "Wallets, updates, resyncs and maintenance",
+    ),
+    spec(
+        "ghost_links",
+        None,
+        "",
+        Some("\u{1F47B} Ghost Links"),
+        "Main",
+        "Links to Ghost sites and explorers",
+    ),
+    spec(
+        "vaults",
+        Some("vaults"),
+        "",
+        Some("\u{1F5C4}\u{FE0F} Vaults"),
+        "Main",
+        "Pick the vault the bot works with",
+    ),
+    spec(
+        "help",
+        Some("help"),
+        "",
+        Some("\u{2753} Help"),
+        "Main",
+        "This list",
+    ),
+    spec(
+        "home",
+        None,
+        "",
+        Some("\u{1F3E0} Home"),
+        "Main",
+        "Back to the main keyboard",
+    ),
+    spec(
+        "start",
+        Some("start"),
+        "",
+        None,
+        "Commands",
+        "Open the main keyboard",
+    ),
+    spec(
+        "setup",
+        Some("setup"),
+        "",
+        None,
+        "Commands",
+        "Walk through setting up this vault",
+    ),
+    spec(
+        "quick",
+        Some("quick"),
+        "",
+        None,
+        "Commands",
+        "One line status, also works in group chats",
+    ),
+    spec(
+        "tx",
+        Some("tx"),
+        "<txid>",
+        None,
+        "Commands",
+        "Look up a transaction",
+    ),
+    spec(
+        "block",
+        Some("block"),
+        "<height or hash>",
+        None,
+        "Commands",
+        "Look up a block",
+    ),
+    spec(
+        "chat_timezone",
+        Some("timezone"),
+        "[Area/City or off]",
+        None,
+        "Commands",
+        "This chat's own timezone for charts and status, also works in group chats",
+    ),
+    spec(
+        "label",
+        Some("label"),
+        "<address> [name]",
+        None,
+        "Commands",
+        "Name an address in the address book, no name removes it",
+    ),
+    spec(
+        "operations",
+        Some("operations"),
+        "",
+        None,
+        "Commands",
+        "Long running operations in progress",
+    ),
+    spec(
+        "jobs",
+        Some("jobs"),
+        "",
+        None,
+        "Commands",
+        "Recent payouts, resyncs, backups and other jobs",
+    ),
+    spec(
+        "expected",
+        Some("expected"),
+        "",
+        None,
+        "Commands",
+        "Deposits waiting to arrive",
+    ),
+    spec(
+        "expect",
+        Some("expect"),
+        "<amount> [memo]",
+        None,
+        "Commands",
+        "Watch for a deposit",
+    ),
+    spec(
+        "zaprequest",
+        Some("zaprequest"),
+        "<amount>",
+        None,
+        "Commands",
+        "Make a deposit request to zap",
+    ),
+    spec(
+        "overview",
+        None,
+        "",
+        Some("\u{1F4CB} Overview"),
+        "Stats",
+        "Staking overview",
+    ),
+    spec(
+        "pending_rewards",
+        None,
+        "",
+        Some("\u{1F4B0} Pending Rewards"),
+        "Stats",
+        "Rewards not paid out yet",
+    ),
+    spec(
+        "charts",
+        Some("charts"),
+        "",
+        Some("\u{1F4CA} Charts"),
+        "Stats",
+        "Stake and reward charts",
+    ),
+    spec(
+        "payout_history",
+        None,
+        "",
+        Some("\u{1F4DC} Payout History"),
+        "Stats",
+        "Past reward payouts",
+    ),
+    spec(
+        "roi",
+        None,
+        "",
+        Some("\u{1F4C8} ROI"),
+        "Stats",
+        "Return on the staked amount",
+    ),
+    spec(
+        "delegations",
+        None,
+        "",
+        Some("\u{1F91D} Delegations"),
+        "Stats",
+        "Cold staking delegations",
+    ),
+    spec(
+        "toggle_stake",
+        None,
+        "",
+        Some("\u{1F4B8} Toggle Stake"),
+        "Bot Settings",
+        "Turn stake announcements on or off",
+    ),
+    spec(
+        "toggle_reward",
+        None,
+        "",
+        Some("\u{1F4B0} Toggle Reward"),
+        "Bot Settings",
+        "Turn payout announcements on or off",
+    ),
+    spec(
+        "toggle_zap",
+        None,
+        "",
+        Some("\u{26A1} Toggle Zap"),
+        "Bot Settings",
+        "Turn zap announcements on or off",
+    ),
+    spec(
+        "set_timezone",
+        None,
+        "",
+        Some("\u{1F55B} Set Timezone"),
+        "Bot Settings",
+        "Timezone for charts, digests and quiet hours",
+    ),
+    spec(
+        "announce_routes",
+        None,
+        "",
+        Some("\u{1F9ED} Announce Routes"),
+        "Bot Settings",
+        "Where each announcement is sent",
+    ),
+    spec(
+        "digests",
+        None,
+        "",
+        Some("\u{1F4F0} Digests"),
+        "Bot Settings",
+        "Daily and weekly summaries",
+    ),
+    spec(
+        "scheduled_tasks",
+        None,
+        "",
+        Some("\u{23F1}\u{FE0F} Scheduled Tasks"),
+        "Bot Settings",
+        "Run or pause scheduled tasks",
+    ),
+    spec(
+        "quiet_hours",
+        None,
+        "",
+        Some("\u{1F319} Quiet Hours"),
+        "Bot Settings",
+        "Hold notices overnight",
+    ),
+    spec(
+        "cs_key",
+        None,
+        "",
+        Some("\u{2744}\u{FE0F} CS Key"),
+        "GhostVault Options",
+        "The cold staking key to delegate to",
+    ),
+    spec(
+        "reward_options",
+        Some("rewards"),
+        "",
+        Some("\u{1F4B8} Reward Options"),
+        "GhostVault Options",
+        "Reward mode, interval and payout minimum",
+    ),
+    spec(
+        "cold_wallets",
+        None,
+        "",
+        Some("\u{1F45B} Cold Wallets"),
+        "GhostVault Options",
+        "Cold wallets watched by this vault",
+    ),
+    spec(
+        "version",
+        None,
+        "",
+        Some("\u{1F4CA} Version"),
+        "GhostVault Options",
+        "GhostVault and ghostd versions",
+    ),
+    spec(
+        "update_ghostd",
+        None,
+        "",
+        Some("\u{1F6E0}\u{FE0F} Update ghostd"),
+        "GhostVault Options",
+        "Update ghostd to the latest release",
+    ),
+    spec(
+        "resync",
+        None,
+        "",
+        Some("\u{1F501} Resync"),
+        "GhostVault Options",
+        "Resync the chain",
+    ),
+    spec(
+        "check_chain",
+        None,
+        "",
+        Some("\u{1F517} Check Chain"),
+        "GhostVault Options",
+        "Check ghostd is on the right chain",
+    ),
+    spec(
+        "recovery",
+        None,
+        "",
+        Some("\u{1F4E5} Recovery"),
+        "GhostVault Options",
+        "Recover a wallet",
+    ),
+    spec(
+        "unlock_wallet",
+        None,
+        "",
+        Some("\u{1F513} Unlock Wallet"),
+        "GhostVault Options",
+        "Unlock an encrypted wallet",
+    ),
+    spec(
+        "maintenance",
+        None,
+        "",
+        Some("\u{1F527} Maintenance"),
+        "GhostVault Options",
+        "The maintenance window",
+    ),
+    spec(
+        "recent_logs",
+        None,
+        "",
+        Some("\u{1F4DC} Recent Logs"),
+        "GhostVault Options",
+        "The last lines of the log",
+    ),
+    spec(
+        "set_reward_mode",
+        None,
+        "",
+        Some("\u{1F4B8} Set Reward Mode & Address"),
+        "Reward Options",
+        "Where rewards are paid",
+    ),
+    spec(
+        "set_payout_min",
+        None,
+        "",
+        Some("\u{1F4B0} Set Payout Min"),
+        "Reward Options",
+        "Smallest payout sent",
+    ),
+    spec(
+        "set_reward_interval",
+        None,
+        "",
+        Some("\u{1F4CA} Set Reward Interval"),
+        "Reward Options",
+        "How often rewards are paid",
+    ),
+    spec(
+        "simulate_rewards",
+        None,
+        "",
+        Some("\u{1F9EE} Simulate Rewards"),
+        "Reward Options",
+        "Estimate rewards for an amount",
+    ),
+    spec(
+        "address_book",
+        Some("addressbook"),
+        "",
+        Some("\u{1F4D2} Address Book"),
+        "Reward Options",
+        "Names for payout and whitelisted addresses",
+    ),
+];
+
+fn find(id: &str) -> &'static BotCommandSpec {
+    BOT_COMMANDS
+        .iter()
+        .find(|spec| spec.id == id)
+        .unwrap_or_else(|| panic!("Unknown bot command: {}", id))
+}
+
+pub fn command_button(id: &str) -> KeyboardButton {
+    KeyboardButton::new(find(id).button.unwrap_or(id).to_string())
+}
+
+// Whether the already lowercased text is the command's slash command or button.
+pub fn matches_command(id: &str, text: &str) -> bool {
+    let spec: &BotCommandSpec = find(id);
+
+    spec.command
+        .is_some_and(|command| text.starts_with(&format!("/{}", command)))
+        || spec
+            .button
+            .is_some_and(|button| text.starts_with(&button.to_lowercase()))
+}
+
+// For setMyCommands.
+pub fn menu_commands() -> Vec<BotCommand> {
+    BOT_COMMANDS
+        .iter()
+        .filter_map(|spec| {
+            spec.command
+                .map(|command| BotCommand::new(command, spec.description))
+        })
+        .collect()
+}
+
+pub fn help_text() -> String {
+    let mut help: String = "👻 GhostVault Help 👻\n".to_string();
+
+    for section in HELP_SECTIONS {
+        help.push_str(&format!("\n{}\n", section));
+
+        for spec in BOT_COMMANDS.iter().filter(|spec| spec.section == section) {
+            let name: String = match (spec.command, spec.button) {
+                (Some(command), Some(button)) => format!("{} /{}", button, command),
+                (Some(command), None) if spec.args.is_empty() => format!("/{}", command),
+                (Some(command), None) => format!("/{} {}", command, spec.args),
+                (None, Some(button)) => button.to_string(),
+                (None, None) => continue,
+            };
+
+            help.push_str(&format!("{} - {}\n", name, spec.description));
+        }
+    }
+
+    help
+}
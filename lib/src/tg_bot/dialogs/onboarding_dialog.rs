@@ -0,0 +1,474 @@
+use crate::{
+    config::GVConfig,
+    gv_client_methods::CLICaller,
+    gvdb::{AddressInfo, OnboardingDB, ServerReadyDB, GVDB},
+    tg_bot::{
+        dialogs::utils::{HandlerResult, OnboardingDialog, OnboardingState},
+        keyboards::{
+            make_inline_cancel_button, make_keyboard_main, make_onboarding_keyboard,
+            make_timezone_region_keyboard,
+        },
+        tg_bot::server_unready_message,
+    },
+};
+use log::{info, warn};
+use std::{
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use teloxide::{
+    adaptors::DefaultParseMode,
+    dispatching::dialogue::InMemStorage,
+    payloads::SendMessageSetters,
+    prelude::*,
+    types::{InlineKeyboardMarkup, KeyboardMarkup, MessageId},
+    utils::markdown::escape,
+};
+use tokio::sync::RwLock as async_RwLock;
+
+const ONBOARDING_READY_CHECK: u64 = 30; // seconds between checks for a ready vault
+
+// A vault still on the DEFAULT reward mode that never went through setup. One set up from
+// gv-cli first is left alone.
+pub fn needs_onboarding(conf: &GVConfig, db: &GVDB) -> bool {
+    db.get_onboarding().completed.is_none()
+        && conf.reward_address.is_none()
+        && !conf.anon_mode
+        && db.get_pending_reward_change().is_none()
+}
+
+// Started with the bot. Setting a reward mode needs a ready vault, so this waits for one before
+// the owner is asked anything.
+pub async fn offer_onboarding(
+    bot: DefaultParseMode<Bot>,
+    gv_config: Arc<async_RwLock<GVConfig>>,
+    db: Arc<GVDB>,
+    onboarding_mem: Arc<InMemStorage<OnboardingState>>,
+    last_dialog_id: Arc<AtomicI32>,
+) {
+    loop {
+        let ready: bool = db
+            .get_server_ready()
+            .is_some_and(|server_ready| server_ready.ready && server_ready.daemon_ready);
+
+        if ready {
+            break;
+        }
+
+        tokio::time::sleep(Duration::from_secs(ONBOARDING_READY_CHECK)).await;
+    }
+
+    let conf = gv_config.read().await;
+    let needed: bool = needs_onboarding(&conf, &db);
+    let tg_user: Option<String> = conf.tg_user.clone();
+    drop(conf);
+
+    if !needed {
+        // Set up some other way, it's not offered again if the mode goes back to DEFAULT.
+        if db.get_onboarding().completed.is_none() {
+            let onboarding: OnboardingDB = OnboardingDB {
+                completed: Some(chrono::Utc::now().timestamp() as u64),
+                skipped: false,
+            };
+
+            if let Err(err) = db.set_onboarding(&onboarding).await {
+                warn!("Unable to save the Telegram setup state: {}", err);
+            }
+        }
+
+        return;
+    }
+
+    // Another dialog already running means the owner found their way around.
+    if last_dialog_id.load(Ordering::Relaxed) != 0 {
+        return;
+    }
+
+    let chat_id: ChatId = match tg_user.and_then(|user| user.parse::<i64>().ok()) {
+        Some(user) => ChatId(user),
+        None => return,
+    };
+
+    info!("Reward mode was never set, starting Telegram setup.");
+
+    let dialogue: OnboardingDialog = OnboardingDialog::new(onboarding_mem, chat_id);
+
+    if let Err(err) = start_onboarding(bot, dialogue, chat_id, last_dialog_id).await {
+        warn!("Unable to start Telegram setup: {}", err);
+    }
+}
+
+pub async fn onboarding_dialogue_handler(
+    bot: DefaultParseMode<Bot>,
+    msg: Message,
+    last_dialog_id: Arc<AtomicI32>,
+    onboarding_dialogue: OnboardingDialog,
+    cli_caller: &CLICaller,
+    db: &Arc<GVDB>,
+) -> ResponseResult<()> {
+    let server_ready: ServerReadyDB = db.get_server_ready().unwrap();
+
+    if !server_ready.daemon_ready || !server_ready.ready {
+        let reason: String = server_unready_message(&server_ready);
+
+        let message: String =
+            escape("Ghost daemon unavailable, send /setup to carry on later.\nReason:");
+        let reasoned_message: String = format!("{}{}", message, reason);
+
+        bot.send_message(msg.chat.id, reasoned_message)
+            .reply_markup(make_keyboard_main())
+            .await?;
+        onboarding_dialogue.exit().await.unwrap();
+
+        let last_id = last_dialog_id.load(Ordering::Relaxed);
+
+        if last_id != 0 {
+            bot.delete_message(msg.chat.id, MessageId(last_id)).await?;
+            last_dialog_id.store(0, Ordering::Relaxed);
+        }
+
+        return Ok(());
+    }
+
+    let text: String = match msg.text() {
+        Some(text) => text.trim().to_string(),
+        None => {
+            bot.send_message(msg.chat.id, escape("Send me plain text."))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let onboarding_state = onboarding_dialogue.get().await.unwrap();
+
+    let step_res: HandlerResult = match onboarding_state {
+        Some(OnboardingState::ReceiveRewardMode) => {
+            receive_reward_mode(&bot, &onboarding_dialogue, msg.chat.id, &text, cli_caller).await
+        }
+        Some(OnboardingState::ReceiveAddress { reward_mode }) => {
+            receive_address(
+                &bot,
+                &onboarding_dialogue,
+                msg.chat.id,
+                &reward_mode,
+                &text,
+                cli_caller,
+            )
+            .await
+        }
+        Some(OnboardingState::ReceiveInterval) => {
+            receive_interval(&bot, &onboarding_dialogue, msg.chat.id, &text, cli_caller).await
+        }
+        Some(OnboardingState::ReceiveMinimum) => {
+            receive_minimum(
+                &bot,
+                &onboarding_dialogue,
+                msg.chat.id,
+                &text,
+                cli_caller,
+                &last_dialog_id,
+                db,
+            )
+            .await
+        }
+        _ => Ok(()),
+    };
+
+    if let Err(err) = step_res {
+        warn!("Telegram setup step failed: {}", err);
+    }
+
+    Ok(())
+}
+
+async fn ask(
+    bot: &DefaultParseMode<Bot>,
+    chat_id: ChatId,
+    message: &str,
+    options: &[&str],
+) -> HandlerResult {
+    let keyboard: KeyboardMarkup = make_onboarding_keyboard(options);
+
+    bot.send_message(chat_id, escape(message))
+        .reply_markup(keyboard)
+        .await?;
+
+    Ok(())
+}
+
+async fn ask_reward_mode(bot: &DefaultParseMode<Bot>, chat_id: ChatId) -> HandlerResult {
+    let message: &str = concat!(
+        "Step 1 of 5: Reward mode\n\n",
+        "DEFAULT:\nRewards go back to the staking address and are auto zapped.\n\n",
+        "STANDARD:\nRewards are sent to an address of your choosing and are NOT auto zapped.\n\n",
+        "ANON:\nRewards are sent to an address of your choosing through GhostVault's internal anon address, ",
+        "and auto zapped if it's a 256bit address (one that starts with a 2).\n\n",
+        "Please choose a reward mode."
+    );
+
+    ask(bot, chat_id, message, &["DEFAULT", "STANDARD", "ANON"]).await
+}
+
+async fn ask_interval(bot: &DefaultParseMode<Bot>, chat_id: ChatId) -> HandlerResult {
+    let message: &str = concat!(
+        "Step 3 of 5: Reward interval\n\n",
+        "How often GhostVault checks if rewards can be sent, 15 minutes at the least. ",
+        "Only applies to ANON mode.\n",
+        "Please choose an interval or send one such as 30m, 12h or 2d."
+    );
+
+    ask(bot, chat_id, message, &["15m", "1h", "6h", "1d"]).await
+}
+
+async fn ask_minimum(bot: &DefaultParseMode<Bot>, chat_id: ChatId) -> HandlerResult {
+    let message: &str = concat!(
+        "Step 4 of 5: Payout minimum\n\n",
+        "The least amount of rewards, in GHOST, that are sent at once. The lowest is 0.1 GHOST. ",
+        "Only applies to ANON mode.\n",
+        "Please choose a minimum or send another amount."
+    );
+
+    ask(bot, chat_id, message, &["0.1", "1", "10", "100"]).await
+}
+
+pub async fn start_onboarding(
+    bot: DefaultParseMode<Bot>,
+    dialogue: OnboardingDialog,
+    chat_id: ChatId,
+    last_dialog_id: Arc<AtomicI32>,
+) -> HandlerResult {
+    let skip_markup: InlineKeyboardMarkup = make_inline_cancel_button("cancel_onboarding");
+
+    let message: String = escape(concat!(
+        "👻 Welcome to GhostVault! 👻\n\n",
+        "Let's set up how your staking rewards are handled. Any step can be skipped and ",
+        "everything can be changed later under GhostVault Options."
+    ));
+    let new_msg = bot
+        .send_message(chat_id, message)
+        .reply_markup(skip_markup)
+        .await?;
+
+    let new_id: i32 = new_msg.id.to_string().parse::<i32>().unwrap();
+    last_dialog_id.store(new_id, Ordering::Relaxed);
+
+    ask_reward_mode(&bot, chat_id).await?;
+
+    dialogue.update(OnboardingState::ReceiveRewardMode).await?;
+
+    Ok(())
+}
+
+async fn receive_reward_mode(
+    bot: &DefaultParseMode<Bot>,
+    dialogue: &OnboardingDialog,
+    chat_id: ChatId,
+    text: &str,
+    cli_caller: &CLICaller,
+) -> HandlerResult {
+    match text.to_uppercase().as_str() {
+        "SKIP" => (),
+        "DEFAULT" => match cli_caller
+            .call_set_reward_mode("DEFAULT".to_string(), None)
+            .await
+        {
+            Ok(_) => {
+                bot.send_message(chat_id, escape("Reward mode set to DEFAULT."))
+                    .await?;
+            }
+            Err(e) => {
+                let message: String = escape(format!("Error: {}", e).as_str());
+                bot.send_message(chat_id, message).await?;
+                return ask_reward_mode(bot, chat_id).await;
+            }
+        },
+        mode @ ("STANDARD" | "ANON") => {
+            ask(
+                bot,
+                chat_id,
+                "Step 2 of 5: Reward address\n\nPlease send your reward address.",
+                &[],
+            )
+            .await?;
+
+            dialogue
+                .update(OnboardingState::ReceiveAddress {
+                    reward_mode: mode.to_string(),
+                })
+                .await?;
+
+            return Ok(());
+        }
+        _ => {
+            bot.send_message(chat_id, escape("Invalid reward mode."))
+                .await?;
+            return ask_reward_mode(bot, chat_id).await;
+        }
+    }
+
+    ask_interval(bot, chat_id).await?;
+    dialogue.update(OnboardingState::ReceiveInterval).await?;
+
+    Ok(())
+}
+
+async fn receive_address(
+    bot: &DefaultParseMode<Bot>,
+    dialogue: &OnboardingDialog,
+    chat_id: ChatId,
+    reward_mode: &str,
+    text: &str,
+    cli_caller: &CLICaller,
+) -> HandlerResult {
+    if !text.eq_ignore_ascii_case("SKIP") {
+        let addr_info: Option<AddressInfo> = cli_caller
+            .call_validate_address(text.to_string())
+            .await
+            .ok();
+
+        let problem: Option<&str> = match &addr_info {
+            Some(addr_info) if !addr_info.is_valid => Some("Invalid address."),
+            Some(addr_info) if addr_info.is_mine => Some("Address belongs to GhostVault!"),
+            Some(_) => None,
+            None => Some("Unable to check the address."),
+        };
+
+        if let Some(problem) = problem {
+            let message: String = format!("{} Please send a different address.", problem);
+            return ask(bot, chat_id, &message, &[]).await;
+        }
+
+        match cli_caller
+            .call_set_reward_mode(reward_mode.to_string(), Some(text.to_string()))
+            .await
+        {
+            Ok(resp) => {
+                let auto_zap: &str = match &addr_info {
+                    Some(addr_info) if reward_mode != "STANDARD" && addr_info.is_256bit => {
+                        "\n256bit address detected. Rewards will be auto zapped."
+                    }
+                    _ => "",
+                };
+                let message: String = if resp.is_empty() {
+                    format!("Reward mode set to {}.{}", reward_mode, auto_zap)
+                } else {
+                    format!("{}{}", resp, auto_zap)
+                };
+
+                bot.send_message(chat_id, escape(&message)).await?;
+            }
+            Err(e) => {
+                let message: String = format!("Error: {}\nPlease send a different address.", e);
+                return ask(bot, chat_id, &message, &[]).await;
+            }
+        }
+    }
+
+    ask_interval(bot, chat_id).await?;
+    dialogue.update(OnboardingState::ReceiveInterval).await?;
+
+    Ok(())
+}
+
+async fn receive_interval(
+    bot: &DefaultParseMode<Bot>,
+    dialogue: &OnboardingDialog,
+    chat_id: ChatId,
+    text: &str,
+    cli_caller: &CLICaller,
+) -> HandlerResult {
+    if !text.eq_ignore_ascii_case("SKIP") {
+        // The server checks the format and the 15 minute minimum.
+        if let Err(e) = cli_caller.call_set_reward_interval(text.to_string()).await {
+            bot.send_message(chat_id, escape(format!("Error: {}", e).as_str()))
+                .await?;
+            return ask_interval(bot, chat_id).await;
+        }
+
+        let message: String = format!("Reward interval set to {}.", text);
+        bot.send_message(chat_id, escape(&message)).await?;
+    }
+
+    ask_minimum(bot, chat_id).await?;
+    dialogue.update(OnboardingState::ReceiveMinimum).await?;
+
+    Ok(())
+}
+
+async fn receive_minimum(
+    bot: &DefaultParseMode<Bot>,
+    dialogue: &OnboardingDialog,
+    chat_id: ChatId,
+    text: &str,
+    cli_caller: &CLICaller,
+    last_dialog_id: &Arc<AtomicI32>,
+    db: &Arc<GVDB>,
+) -> HandlerResult {
+    if !text.eq_ignore_ascii_case("SKIP") {
+        let min: f64 = match text.parse::<f64>() {
+            Ok(min) if min >= 0.1 => min,
+            _ => {
+                bot.send_message(
+                    chat_id,
+                    escape("Please send a number of at least 0.1 GHOST."),
+                )
+                .await?;
+                return ask_minimum(bot, chat_id).await;
+            }
+        };
+
+        if let Err(e) = cli_caller.call_set_payout_min(min).await {
+            bot.send_message(chat_id, escape(format!("Error: {}", e).as_str()))
+                .await?;
+            return ask_minimum(bot, chat_id).await;
+        }
+
+        let message: String = format!("Payout minimum set to {} GHOST.", min);
+        bot.send_message(chat_id, escape(&message)).await?;
+    }
+
+    db.set_onboarding(&OnboardingDB {
+        completed: Some(chrono::Utc::now().timestamp() as u64),
+        skipped: false,
+    })
+    .await?;
+    dialogue.exit().await?;
+
+    let last_id = last_dialog_id.swap(0, Ordering::Relaxed);
+    if last_id != 0 {
+        let _ = bot.delete_message(chat_id, MessageId(last_id)).await;
+    }
+
+    // The timezone keyboard is the one the Set Timezone button uses, it finishes on its own.
+    bot.send_message(
+        chat_id,
+        escape(
+            "👻 Rewards are set up 👻\n\nYou can change them any time under GhostVault Options.",
+        ),
+    )
+    .reply_markup(make_keyboard_main())
+    .await?;
+
+    bot.send_message(
+        chat_id,
+        escape("Step 5 of 5: Timezone\n\nReports use UTC until you pick a timezone.\nPlease select your region."),
+    )
+    .reply_markup(make_timezone_region_keyboard())
+    .await?;
+
+    Ok(())
+}
+
+pub async fn skip_onboarding(db: &Arc<GVDB>) {
+    let onboarding: OnboardingDB = OnboardingDB {
+        completed: Some(chrono::Utc::now().timestamp() as u64),
+        skipped: true,
+    };
+
+    if let Err(err) = db.set_onboarding(&onboarding).await {
+        warn!("Unable to save the Telegram setup state: {}", err);
+    }
+}
@@ -261,9 +261,9 @@ pub async fn receive_interval(
 
     let interval = format!("{}{}", msg.text().unwrap(), interval_multiplier);
 
-    let cli_res = cli_caller.call_set_reward_interval(interval).await.unwrap();
+    let cli_res = cli_caller.call_set_reward_interval(interval).await;
 
-    if cli_res.as_str().unwrap() == "Reward interval updated!" {
+    if cli_res.is_ok() {
         let keyboard = make_keyboard_gv_options();
         let message = escape("Reward interval updated.");
         bot.send_message(msg.chat.id, message)
@@ -7,7 +7,6 @@ use crate::{
         tg_bot::server_unready_message,
     },
 };
-use serde_json::Value;
 use std::sync::{
     atomic::{AtomicI32, Ordering},
     Arc,
@@ -155,11 +154,8 @@ pub async fn receive_minimum(
         return Ok(());
     }
 
-    let cli_res: Value = cli_caller.call_set_payout_min(min).await.unwrap();
-    let res_str: &str = cli_res.as_str().unwrap();
-
-    if res_str != "Minimum payout updated!" {
-        let message = escape(format!("Error updating minimum reward: {}", res_str).as_str());
+    if let Err(e) = cli_caller.call_set_payout_min(min).await {
+        let message = escape(format!("Error updating minimum reward: {}", e).as_str());
         let _new_msg = bot
             .send_message(msg.chat.id, message)
             .reply_markup(confirm_markup)
@@ -1,5 +1,5 @@
 use crate::{
-    gv_client_methods::CLICaller,
+    gv_client_methods::{labeled_address, AddressBookEntry, CLICaller},
     gvdb::{AddressInfo, ServerReadyDB, GVDB},
     tg_bot::{
         dialogs::utils::{HandlerResult, UpdateRewardModeDialog, UpdateRewardModeState},
@@ -149,10 +149,9 @@ pub async fn receive_reward_mode(
             "DEFAULT" => {
                 let cli_res = cli_caller
                     .call_set_reward_mode("DEFAULT".to_string(), None)
-                    .await
-                    .unwrap();
+                    .await;
 
-                if cli_res.as_str().unwrap() == "Reward mode updated!" {
+                if cli_res.is_ok() {
                     let keyboard = make_keyboard_gv_options();
                     let message = escape("Reward mode updated to DEFAULT.");
                     bot.send_message(msg.chat.id, message)
@@ -174,7 +173,21 @@ pub async fn receive_reward_mode(
             }
             "STANDARD" | "ANON" => {
                 let cancel_markup = make_inline_cancel_button("cancel_update_reward_mode");
-                let message = escape("Please provide your reward address.");
+
+                let book: Vec<AddressBookEntry> =
+                    cli_caller.call_get_address_book().await.unwrap_or_default();
+                let message: String = if book.is_empty() {
+                    escape("Please provide your reward address.")
+                } else {
+                    let names: Vec<String> = book.into_iter().map(|entry| entry.label).collect();
+                    escape(
+                        format!(
+                            "Please provide your reward address, or one of these names from the address book:\n{}",
+                            names.join("\n")
+                        )
+                        .as_str(),
+                    )
+                };
                 let new_msg = bot
                     .send_message(msg.chat.id, message)
                     .reply_markup(cancel_markup)
@@ -229,15 +242,20 @@ pub async fn receive_address(
         .reply_markup(empty_keyboard)
         .await;
 
-    let addr = msg.text().unwrap();
+    let text: &str = msg.text().unwrap_or_default().trim();
+
+    // A name from the address book stands in for its address
+    let book: Vec<AddressBookEntry> = cli_caller.call_get_address_book().await.unwrap_or_default();
+    let labeled: Option<&AddressBookEntry> = book
+        .iter()
+        .find(|entry| entry.label.eq_ignore_ascii_case(text));
+    let addr: String = labeled.map_or_else(|| text.to_string(), |entry| entry.address.clone());
 
-    let cli_res = cli_caller
-        .call_validate_address(addr.to_string())
+    let addr_info: AddressInfo = cli_caller
+        .call_validate_address(addr.clone())
         .await
         .unwrap();
 
-    let addr_info: AddressInfo = serde_json::from_value(cli_res).unwrap();
-
     if !addr_info.is_valid {
         let cancel_markup = make_inline_cancel_button("cancel_update_reward_mode");
         let message = escape("Invalid address. Please provide a valid address.");
@@ -261,11 +279,10 @@ pub async fn receive_address(
     } else {
         let new_mode = reward_mode.clone().to_uppercase();
         let cli_res = cli_caller
-            .call_set_reward_mode(new_mode, Some(addr.to_string()))
-            .await
-            .unwrap();
+            .call_set_reward_mode(new_mode, Some(addr.clone()))
+            .await;
 
-        if cli_res.as_str().unwrap() == "Reward mode updated!" {
+        if cli_res.is_ok() {
             let keyboard = make_keyboard_gv_options();
             let new_mode = reward_mode.clone().to_uppercase();
 
@@ -275,8 +292,15 @@ pub async fn receive_address(
                 ""
             };
 
-            let message =
-                escape(format!("Reward mode updated to {}.{}", new_mode, auto_zap).as_str());
+            let message = escape(
+                format!(
+                    "Reward mode updated to {}, rewards go to {}.{}",
+                    new_mode,
+                    labeled_address(&addr, labeled.map(|entry| entry.label.as_str())),
+                    auto_zap
+                )
+                .as_str(),
+            );
             bot.send_message(msg.chat.id, message)
                 .reply_markup(keyboard)
                 .await?;
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Datelike, Days, Months, NaiveDate, TimeZone, Timelike, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Timelike, Utc};
 use chrono_tz::Tz;
 use serde_json::Value;
 use teloxide::{dispatching::dialogue::InMemStorage, prelude::*};
@@ -30,6 +30,19 @@ pub enum UpdateRewardMinState {
     ReceiveMinimum,
 }
 
+// The setup conversation for a new vault, each step can be skipped to keep the current value.
+#[derive(Clone, Default, Debug)]
+pub enum OnboardingState {
+    #[default]
+    Start,
+    ReceiveRewardMode,
+    ReceiveAddress {
+        reward_mode: String,
+    },
+    ReceiveInterval,
+    ReceiveMinimum,
+}
+
 #[derive(Clone, Default, Debug)]
 pub enum GetDateRangeState {
     #[default]
@@ -52,6 +65,7 @@ pub type UpdateRewardModeDialog =
 pub type UpdateRewardIntervalDialog =
     Dialogue<UpdateRewardIntervalState, InMemStorage<UpdateRewardIntervalState>>;
 pub type UpdateRewardMinDialog = Dialogue<UpdateRewardMinState, InMemStorage<UpdateRewardMinState>>;
+pub type OnboardingDialog = Dialogue<OnboardingState, InMemStorage<OnboardingState>>;
 pub type GetDateRangeDialog = Dialogue<GetDateRangeState, InMemStorage<GetDateRangeState>>;
 pub type HandlerResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
 
@@ -102,45 +116,6 @@ pub fn get_current_month_year_day(time_zone: &str) -> (i32, u32, u32) {
     (year, month, day)
 }
 
-pub fn parse_chart_range(range: &str, time_zone: &str) -> (u64, u64) {
-    let dt = Utc::now();
-    let n_time = NaiveDate::from_ymd_opt(dt.year(), dt.month(), dt.day())
-        .unwrap()
-        .and_hms_opt(dt.hour(), dt.minute(), dt.second())
-        .unwrap();
-
-    let tz: Tz = Tz::from_str_insensitive(time_zone).unwrap();
-    let now: DateTime<Tz> = Tz::from_utc_datetime(&tz, &n_time);
-
-    let end = now.timestamp() as u64;
-
-    let start = match range {
-        "last_two_weeks" => now.checked_sub_days(Days::new(14)).unwrap().timestamp() as u64,
-        "last_month" => now.checked_sub_months(Months::new(1)).unwrap().timestamp() as u64,
-        "last_three_months" => now.checked_sub_months(Months::new(3)).unwrap().timestamp() as u64,
-        "last_six_months" => now.checked_sub_months(Months::new(6)).unwrap().timestamp() as u64,
-        "last_year" => now.checked_sub_months(Months::new(12)).unwrap().timestamp() as u64,
-        "year_to_date" => {
-            let jan_one = now
-                .with_month(1)
-                .unwrap()
-                .with_day(1)
-                .unwrap()
-                .with_hour(0)
-                .unwrap()
-                .with_minute(0)
-                .unwrap()
-                .with_second(0)
-                .unwrap();
-            jan_one.timestamp() as u64
-        }
-        "max" => 0,
-        _ => now.checked_sub_days(Days::new(14)).unwrap().timestamp() as u64,
-    };
-
-    (start, end)
-}
-
 pub fn get_timezone_opts() -> Value {
     let json_dada = r#"
     {
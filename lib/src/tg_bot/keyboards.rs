@@ -1,21 +1,30 @@
-use crate::tg_bot::dialogs::utils;
+use crate::{
+    announce::AnnounceDestination,
+    constants::QUIET_HOURS_PRESETS,
+    gv_client_methods::{
+        AddressBookEntry, AnnounceRoute, ColdWallets, DigestSettings, MaintenanceWindow,
+        QuietHours, ScheduledTask,
+    },
+    tg_bot::{commands::command_button, dialogs::utils},
+};
 use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, KeyboardButton, KeyboardMarkup};
 use url::Url;
 
 pub fn make_keyboard_main() -> KeyboardMarkup {
-    let status_button = KeyboardButton::new("\u{2139}\u{FE0F} Status".to_string());
-    let stats_button = KeyboardButton::new("\u{1F4CA} Stats".to_string());
-    let bot_settings_button = KeyboardButton::new("\u{2699}\u{FE0F} Bot Settings".to_string());
-    let gv_settings_button = KeyboardButton::new("\u{2699}\u{FE0F} GhostVault Options".to_string());
+    let status_button = command_button("status");
+    let stats_button = command_button("stats");
+    let bot_settings_button = command_button("bot_settings");
+    let gv_settings_button = command_button("gv_options");
 
-    let ghost_links_button = KeyboardButton::new("\u{1F47B} Ghost Links".to_string());
-    let gv_help_button = KeyboardButton::new("\u{2753} Help".to_string());
+    let ghost_links_button = command_button("ghost_links");
+    let gv_help_button = command_button("help");
+    let vaults_button = command_button("vaults");
 
     // Create keyboard markup
     let keys = KeyboardMarkup::new(vec![
         vec![status_button, stats_button],
         vec![bot_settings_button, gv_settings_button],
-        vec![ghost_links_button, gv_help_button],
+        vec![ghost_links_button, vaults_button, gv_help_button],
     ]);
 
     let keyboard = KeyboardMarkup::persistent(keys);
@@ -26,17 +35,23 @@ pub fn make_keyboard_main() -> KeyboardMarkup {
 }
 
 pub fn make_keyboard_bot_settings() -> KeyboardMarkup {
-    let stake_ann_button = KeyboardButton::new("\u{1F4B8} Toggle Stake".to_string());
-    let reward_ann_button = KeyboardButton::new("\u{1F4B0} Toggle Reward".to_string());
-    let zap_ann_button = KeyboardButton::new("\u{26A1} Toggle Zap".to_string());
-    let timezone_button = KeyboardButton::new("\u{1F55B} Set Timezone".to_string());
+    let stake_ann_button = command_button("toggle_stake");
+    let reward_ann_button = command_button("toggle_reward");
+    let zap_ann_button = command_button("toggle_zap");
+    let timezone_button = command_button("set_timezone");
+    let routes_button = command_button("announce_routes");
+    let digest_button = command_button("digests");
+    let tasks_button = command_button("scheduled_tasks");
+    let quiet_button = command_button("quiet_hours");
 
-    let home_button = KeyboardButton::new("\u{1F3E0} Home".to_string());
+    let home_button = command_button("home");
 
     // Create keyboard markup
     let keys = KeyboardMarkup::new(vec![
         vec![stake_ann_button, reward_ann_button],
         vec![zap_ann_button, timezone_button],
+        vec![routes_button, digest_button],
+        vec![tasks_button, quiet_button],
         vec![home_button],
     ]);
 
@@ -48,21 +63,26 @@ pub fn make_keyboard_bot_settings() -> KeyboardMarkup {
 }
 
 pub fn make_keyboard_gv_options() -> KeyboardMarkup {
-    let ext_pubk_button = KeyboardButton::new("\u{2744}\u{FE0F} CS Key".to_string());
-    let reward_button = KeyboardButton::new("\u{1F4B8} Reward Options".to_string());
-    let version_button = KeyboardButton::new("\u{1F4CA} Version".to_string());
-    let daemon_update_button = KeyboardButton::new("\u{1F6E0}\u{FE0F} Update ghostd".to_string());
-    let resync_button = KeyboardButton::new("\u{1F501} Resync".to_string());
-    let check_chain_button = KeyboardButton::new("\u{1F517} Check Chain".to_string());
-    let recovery_button = KeyboardButton::new("\u{1F4E5} Recovery".to_string());
-
-    let home_button = KeyboardButton::new("\u{1F3E0} Home".to_string());
+    let ext_pubk_button = command_button("cs_key");
+    let reward_button = command_button("reward_options");
+    let cold_wallets_button = command_button("cold_wallets");
+    let version_button = command_button("version");
+    let daemon_update_button = command_button("update_ghostd");
+    let resync_button = command_button("resync");
+    let check_chain_button = command_button("check_chain");
+    let recovery_button = command_button("recovery");
+    let unlock_button = command_button("unlock_wallet");
+    let maintenance_button = command_button("maintenance");
+    let logs_button = command_button("recent_logs");
+
+    let home_button = command_button("home");
 
     // Create keyboard markup
     let keys = KeyboardMarkup::new(vec![
-        vec![ext_pubk_button, reward_button],
+        vec![ext_pubk_button, reward_button, cold_wallets_button],
         vec![version_button, daemon_update_button],
         vec![resync_button, check_chain_button, recovery_button],
+        vec![unlock_button, maintenance_button, logs_button],
         vec![home_button],
     ]);
 
@@ -74,18 +94,21 @@ pub fn make_keyboard_gv_options() -> KeyboardMarkup {
 }
 
 pub fn make_keyboard_reward_options() -> KeyboardMarkup {
-    let reward_mode_button = KeyboardButton::new("\u{1F4B8} Set Reward Mode & Address".to_string());
-    let payout_min_button = KeyboardButton::new("\u{1F4B0} Set Payout Min".to_string());
-    let reward_interval_button = KeyboardButton::new("\u{1F4CA} Set Reward Interval".to_string());
+    let reward_mode_button = command_button("set_reward_mode");
+    let payout_min_button = command_button("set_payout_min");
+    let reward_interval_button = command_button("set_reward_interval");
+    let simulate_button = command_button("simulate_rewards");
+    let address_book_button = command_button("address_book");
 
-    let gv_options_button = KeyboardButton::new("\u{2699}\u{FE0F} GhostVault Options".to_string());
+    let gv_options_button = command_button("gv_options");
 
-    let home_button = KeyboardButton::new("\u{1F3E0} Home".to_string());
+    let home_button = command_button("home");
 
     // Create keyboard markup
     let keys = KeyboardMarkup::new(vec![
         vec![reward_mode_button],
         vec![reward_interval_button, payout_min_button],
+        vec![simulate_button, address_book_button],
         vec![gv_options_button, home_button],
     ]);
 
@@ -135,6 +158,23 @@ pub fn make_reward_interval_keyboard() -> KeyboardMarkup {
     keyboard
 }
 
+// A setup step's choices, with SKIP to keep the current value. Anything else can be typed too.
+pub fn make_onboarding_keyboard(options: &[&str]) -> KeyboardMarkup {
+    let option_buttons: Vec<KeyboardButton> = options
+        .iter()
+        .map(|option| KeyboardButton::new(option.to_string()))
+        .collect();
+    let skip_button = KeyboardButton::new("SKIP".to_string());
+
+    let keys = KeyboardMarkup::new(vec![option_buttons, vec![skip_button]]);
+
+    let keyboard = KeyboardMarkup::persistent(keys);
+    let mut keyboard = keyboard.input_field_placeholder("Please choose an option".to_string());
+    keyboard.resize_keyboard = Some(true);
+
+    keyboard
+}
+
 pub fn make_timezone_region_keyboard() -> InlineKeyboardMarkup {
     let json_data = utils::get_timezone_opts();
 
@@ -232,16 +272,20 @@ pub fn make_timezone_option_keyboard(key: &str, page: u8) -> Option<InlineKeyboa
 }
 
 pub fn make_stats_info_keyboard() -> KeyboardMarkup {
-    let overview_button = KeyboardButton::new("\u{1F4CB} Overview");
-    let pending_rewards_button = KeyboardButton::new("\u{1F4B0} Pending Rewards");
-    let charts_button = KeyboardButton::new("\u{1F4CA} Charts");
+    let overview_button = command_button("overview");
+    let pending_rewards_button = command_button("pending_rewards");
+    let charts_button = command_button("charts");
+    let payout_history_button = command_button("payout_history");
+    let roi_button = command_button("roi");
+    let delegations_button = command_button("delegations");
 
-    let home_button = KeyboardButton::new("\u{1F3E0} Home");
+    let home_button = command_button("home");
 
     // Create keyboard markup
     let keys = KeyboardMarkup::new(vec![
         vec![overview_button, pending_rewards_button],
-        vec![charts_button],
+        vec![charts_button, payout_history_button],
+        vec![roi_button, delegations_button],
         vec![home_button],
     ]);
 
@@ -306,7 +350,9 @@ pub fn make_inline_stake_chart_range_menu(chart_type: String) -> InlineKeyboardM
     );
 
     let back_button = match chart_type.as_str() {
-        "earnings_chart" => InlineKeyboardButton::callback("Back", "back_to_stake_chart"),
+        "earnings_chart" | "heatmap_chart" | "balance_chart" | "network_chart" => {
+            InlineKeyboardButton::callback("Back", "back_to_stake_chart")
+        }
         _ => InlineKeyboardButton::callback("Back", "stake_chart"),
     };
     let cancel_button = InlineKeyboardButton::callback("Cancel", "cancel_select_chart");
@@ -337,14 +383,70 @@ pub fn make_inline_chart_menu() -> InlineKeyboardMarkup {
 
     let stakes_button = InlineKeyboardButton::callback("Stakes Over Time", "stake_chart");
     let earnings_button = InlineKeyboardButton::callback("Total Earnings", "earnings_chart");
+    let heatmap_button = InlineKeyboardButton::callback("Stake Calendar", "heatmap_chart");
+    let balance_button = InlineKeyboardButton::callback("Balance History", "balance_chart");
+    let network_button = InlineKeyboardButton::callback("My Weight vs Network", "network_chart");
 
     let cancel_button = InlineKeyboardButton::callback("Cancel", "cancel_select_chart");
 
     let row1 = vec![stakes_button, earnings_button];
-    let row2 = vec![cancel_button];
+    let row2 = vec![heatmap_button, balance_button];
+    let row3 = vec![network_button];
+    let row4 = vec![cancel_button];
+
+    keyboard.push(row1);
+    keyboard.push(row2);
+    keyboard.push(row3);
+    keyboard.push(row4);
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+pub fn make_inline_simulate_rewards_menu() -> InlineKeyboardMarkup {
+    let week_button = InlineKeyboardButton::callback("7 Days", "simulate_rewards,7");
+    let month_button = InlineKeyboardButton::callback("30 Days", "simulate_rewards,30");
+    let quarter_button = InlineKeyboardButton::callback("90 Days", "simulate_rewards,90");
+    let year_button = InlineKeyboardButton::callback("1 Year", "simulate_rewards,365");
+
+    let cancel_button = InlineKeyboardButton::callback("Cancel", "cancel_simulate_rewards");
+
+    InlineKeyboardMarkup::new(vec![
+        vec![week_button, month_button],
+        vec![quarter_button, year_button],
+        vec![cancel_button],
+    ])
+}
+
+pub fn make_inline_payout_history_menu() -> InlineKeyboardMarkup {
+    let mut keyboard: Vec<Vec<InlineKeyboardButton>> = Vec::new();
+
+    let last_two_weeks_button =
+        InlineKeyboardButton::callback("Last 2 Weeks", "payout_history_selection,last_two_weeks");
+    let last_month_button =
+        InlineKeyboardButton::callback("Last Month", "payout_history_selection,last_month");
+    let last_three_months_button = InlineKeyboardButton::callback(
+        "Last 3 Months",
+        "payout_history_selection,last_three_months",
+    );
+    let year_to_date_button =
+        InlineKeyboardButton::callback("Year to Date", "payout_history_selection,year_to_date");
+    let last_year_button =
+        InlineKeyboardButton::callback("Last Year", "payout_history_selection,last_year");
+    let max_button = InlineKeyboardButton::callback("Max", "payout_history_selection,max");
+
+    let cancel_button = InlineKeyboardButton::callback("Cancel", "cancel_select_chart");
+
+    let row1 = vec![
+        last_two_weeks_button,
+        last_month_button,
+        last_three_months_button,
+    ];
+    let row2 = vec![year_to_date_button, last_year_button, max_button];
+    let row3 = vec![cancel_button];
 
     keyboard.push(row1);
     keyboard.push(row2);
+    keyboard.push(row3);
 
     InlineKeyboardMarkup::new(keyboard)
 }
@@ -431,6 +533,315 @@ pub fn make_inline_ghost_links_menu() -> InlineKeyboardMarkup {
     InlineKeyboardMarkup::new(keyboard)
 }
 
+// One button per vault, the index is sent back since names could overflow the callback data.
+pub fn make_inline_vault_menu(vault_names: &[String], selected: usize) -> InlineKeyboardMarkup {
+    let keyboard: Vec<Vec<InlineKeyboardButton>> = vault_names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let label: String = if index == selected {
+                format!("\u{2705} {}", name)
+            } else {
+                name.to_string()
+            };
+
+            vec![InlineKeyboardButton::callback(
+                label,
+                format!("select_vault:{}", index),
+            )]
+        })
+        .collect();
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+// Indexes stand in for wallet names here too, the primary wallet is ticked.
+pub fn make_inline_cold_wallet_menu(wallets: &ColdWallets) -> InlineKeyboardMarkup {
+    let keyboard: Vec<Vec<InlineKeyboardButton>> = wallets
+        .wallets
+        .iter()
+        .enumerate()
+        .map(|(index, wallet)| {
+            let label: String = if wallet.primary {
+                format!("\u{2705} {}", wallet.name)
+            } else {
+                wallet.name.to_string()
+            };
+
+            vec![InlineKeyboardButton::callback(
+                label,
+                format!("cold_wallet:{}", index),
+            )]
+        })
+        .collect();
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+pub fn make_inline_cold_wallet_options(index: usize, primary: bool) -> InlineKeyboardMarkup {
+    let mut keyboard: Vec<Vec<InlineKeyboardButton>> = Vec::new();
+
+    if !primary {
+        keyboard.push(vec![InlineKeyboardButton::callback(
+            "Make Primary",
+            format!("cold_wallet_primary:{}", index),
+        )]);
+    }
+
+    keyboard.push(vec![InlineKeyboardButton::callback("Back", "cold_wallets")]);
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+pub fn make_inline_address_book_menu(entries: &[AddressBookEntry]) -> InlineKeyboardMarkup {
+    let keyboard: Vec<Vec<InlineKeyboardButton>> = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            vec![InlineKeyboardButton::callback(
+                entry.label.clone(),
+                format!("address_entry:{}", index),
+            )]
+        })
+        .collect();
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+pub fn make_inline_address_entry_options(index: usize) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![
+        vec![InlineKeyboardButton::callback(
+            "Remove",
+            format!("address_remove:{}", index),
+        )],
+        vec![InlineKeyboardButton::callback("Back", "address_book")],
+    ])
+}
+
+// One button per announcement event, showing where it goes now.
+pub fn make_inline_routes_menu(routes: &[AnnounceRoute]) -> InlineKeyboardMarkup {
+    let keyboard: Vec<Vec<InlineKeyboardButton>> = routes
+        .iter()
+        .map(|route| {
+            let destinations: Vec<&str> = route
+                .destinations
+                .iter()
+                .map(|destination| destination.as_str())
+                .collect();
+
+            let label: String = if destinations.is_empty() {
+                format!("{}: none", route.event)
+            } else {
+                format!("{}: {}", route.event, destinations.join(", "))
+            };
+
+            vec![InlineKeyboardButton::callback(
+                label,
+                format!("route_event:{}", route.event),
+            )]
+        })
+        .collect();
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+// Toggles for each destination of a single event.
+pub fn make_inline_route_menu(route: &AnnounceRoute) -> InlineKeyboardMarkup {
+    let mut keyboard: Vec<Vec<InlineKeyboardButton>> = AnnounceDestination::ALL
+        .iter()
+        .map(|destination| {
+            let label: String = if route.destinations.contains(destination) {
+                format!("\u{2705} {}", destination.as_str())
+            } else {
+                format!("\u{274C} {}", destination.as_str())
+            };
+
+            vec![InlineKeyboardButton::callback(
+                label,
+                format!("route_toggle:{}:{}", route.event, destination.as_str()),
+            )]
+        })
+        .collect();
+
+    keyboard.push(vec![InlineKeyboardButton::callback(
+        "\u{2B05}\u{FE0F} Back",
+        "route_menu",
+    )]);
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+// A row per destination with its quiet hours.
+pub fn make_inline_quiet_hours_menu(quiet_hours: &[QuietHours]) -> InlineKeyboardMarkup {
+    let keyboard: Vec<Vec<InlineKeyboardButton>> = quiet_hours
+        .iter()
+        .map(|quiet| {
+            vec![InlineKeyboardButton::callback(
+                format!("{}: {}", quiet.destination.as_str(), quiet.window()),
+                format!("quiet_dest:{}", quiet.destination.as_str()),
+            )]
+        })
+        .collect();
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+// Preset windows for one destination, custom hours are set with GvCLI.
+pub fn make_inline_quiet_hours_presets(destination: &str) -> InlineKeyboardMarkup {
+    let mut keyboard: Vec<Vec<InlineKeyboardButton>> = QUIET_HOURS_PRESETS
+        .chunks(2)
+        .map(|presets| {
+            presets
+                .iter()
+                .map(|hours| {
+                    InlineKeyboardButton::callback(
+                        hours.to_string(),
+                        format!("quiet_set:{}:{}", destination, hours),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+
+    keyboard.push(vec![
+        InlineKeyboardButton::callback("\u{1F514} Off", format!("quiet_set:{}:off", destination)),
+        InlineKeyboardButton::callback("\u{2B05}\u{FE0F} Back", "quiet_menu"),
+    ]);
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+// Actions for a chain split emergency, carries the vault index so relayed alerts act on their vault.
+pub fn make_inline_chain_emergency_menu(vault_index: usize) -> InlineKeyboardMarkup {
+    let keyboard: Vec<Vec<InlineKeyboardButton>> = vec![
+        vec![InlineKeyboardButton::callback(
+            "\u{1F501} Resync",
+            format!("chain_emergency:resync:{}", vault_index),
+        )],
+        vec![InlineKeyboardButton::callback(
+            "\u{1F6AB} Ban Peers",
+            format!("chain_emergency:ban_peers:{}", vault_index),
+        )],
+        vec![InlineKeyboardButton::callback(
+            "\u{23F3} Wait",
+            format!("chain_emergency:wait:{}", vault_index),
+        )],
+    ];
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+// Toggles for the daily and weekly digest, with the hour they go out stepped an hour at a time.
+pub fn make_inline_digest_menu(settings: &DigestSettings) -> InlineKeyboardMarkup {
+    let toggle = |enabled: bool, name: &str| -> String {
+        if enabled {
+            format!("\u{2705} {} digest", name)
+        } else {
+            format!("\u{274C} {} digest", name)
+        }
+    };
+
+    let keyboard: Vec<Vec<InlineKeyboardButton>> = vec![
+        vec![InlineKeyboardButton::callback(
+            toggle(settings.daily, "Daily"),
+            "digest_toggle:daily",
+        )],
+        vec![InlineKeyboardButton::callback(
+            toggle(settings.weekly, "Weekly"),
+            "digest_toggle:weekly",
+        )],
+        vec![
+            InlineKeyboardButton::callback(
+                "\u{2B05}\u{FE0F} Earlier",
+                format!("digest_hour:{}", (settings.hour + 23) % 24),
+            ),
+            InlineKeyboardButton::callback(format!("{:02}:00", settings.hour), " ".to_string()),
+            InlineKeyboardButton::callback(
+                "Later \u{27A1}\u{FE0F}",
+                format!("digest_hour:{}", (settings.hour + 1) % 24),
+            ),
+        ],
+    ];
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+// The hours ghostd updates and resyncs wait for, each end stepped an hour at a time.
+pub fn make_inline_maintenance_menu(window: &MaintenanceWindow) -> InlineKeyboardMarkup {
+    let hour_row = |label: &str, key: &str, hour: u32| -> Vec<InlineKeyboardButton> {
+        vec![
+            InlineKeyboardButton::callback(
+                "\u{2B05}\u{FE0F}",
+                format!("maint_{}:{}", key, (hour + 23) % 24),
+            ),
+            InlineKeyboardButton::callback(format!("{} {:02}:00", label, hour), " ".to_string()),
+            InlineKeyboardButton::callback(
+                "\u{27A1}\u{FE0F}",
+                format!("maint_{}:{}", key, (hour + 1) % 24),
+            ),
+        ]
+    };
+
+    let keyboard: Vec<Vec<InlineKeyboardButton>> = vec![
+        hour_row("Start", "start", window.start),
+        hour_row("End", "end", window.end),
+    ];
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+// A row per task, run it now or pause and resume it.
+pub fn make_inline_tasks_menu(tasks: &[ScheduledTask]) -> InlineKeyboardMarkup {
+    let keyboard: Vec<Vec<InlineKeyboardButton>> = tasks
+        .iter()
+        .map(|task| {
+            let pause_button: InlineKeyboardButton = if task.paused {
+                InlineKeyboardButton::callback(
+                    "\u{25B6}\u{FE0F} Resume",
+                    format!("sched_resume:{}", task.name),
+                )
+            } else {
+                InlineKeyboardButton::callback(
+                    "\u{23F8}\u{FE0F} Pause",
+                    format!("sched_pause:{}", task.name),
+                )
+            };
+
+            vec![
+                InlineKeyboardButton::callback(task.name.clone(), " ".to_string()),
+                InlineKeyboardButton::callback(
+                    "\u{23E9} Run now",
+                    format!("sched_run:{}", task.name),
+                ),
+                pause_button,
+            ]
+        })
+        .collect();
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+pub fn make_inline_split_menu(vault_index: usize, token: &str) -> InlineKeyboardMarkup {
+    let keyboard: Vec<Vec<InlineKeyboardButton>> = vec![vec![
+        InlineKeyboardButton::callback(
+            "\u{2702} Split",
+            format!("confirm_split:{}:{}", vault_index, token),
+        ),
+        InlineKeyboardButton::callback("Cancel", "cancel_resync"),
+    ]];
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
+pub fn make_inline_reward_change_menu(vault_index: usize) -> InlineKeyboardMarkup {
+    let keyboard: Vec<Vec<InlineKeyboardButton>> = vec![vec![InlineKeyboardButton::callback(
+        "\u{274C} Cancel Change",
+        format!("cancel_reward_change:{}", vault_index),
+    )]];
+
+    InlineKeyboardMarkup::new(keyboard)
+}
+
 pub fn make_link_button(links: &Vec<String>, msg: &str) -> InlineKeyboardMarkup {
     let mut keyboard: Vec<Vec<InlineKeyboardButton>> = Vec::new();
 
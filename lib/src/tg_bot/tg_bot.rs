@@ -1,44 +1,66 @@
 use crate::{
+    amount::Amount,
+    announce::AnnounceDestination,
     config::GVConfig,
-    file_ops,
-    gv_client_methods::{BarChart, CLICaller, GVStatus, PendingRewards, StakingDataOverview},
-    gvdb::{ServerReadyDB, GVDB},
+    constants::{COLD_WALLET_CHART_DAYS, OPERATIONS, TG_LOG_LINES},
+    gv_client_methods::{
+        labeled_address, short_address, AddressBookEntry, AnnounceRoute, AnonymizationStatus,
+        BarChart, BlockSummary, CLICaller, ColdWallets, DaemonUpdate, Delegations, DigestSettings,
+        ExpectedDeposit, FleetVault, GVStatus, MaintenanceWindow, NetworkStats, OperationState,
+        OperationStatus, PayoutHistory, PendingRewards, QuietHours, RecentLogs, RewardSimulation,
+        ScheduledTask, StakingDataOverview, StakingRoi, TxSummary, VersionInfo, ZapRequest,
+    },
+    gvdb::{JobDB, ServerReadyDB, GVDB},
+    response::{self, ErrorCode},
     tg_bot::{
         bot_tasks::BotRunner,
-        charts::charts::{make_area_chart, make_barchart},
+        charts::charts::{
+            make_area_chart, make_balance_chart, make_barchart, make_heatmap, make_network_chart,
+            make_qr_code,
+        },
+        commands::{help_text, matches_command, menu_commands},
         dialogs::{
             chart_range_dialog::{receive_first_date, start_chart_range_dialogue},
+            onboarding_dialog::{
+                offer_onboarding, onboarding_dialogue_handler, skip_onboarding, start_onboarding,
+            },
             reward_interval_dialog::{
                 reward_interval_dialogue_handler, start_update_reward_interval,
             },
             reward_min_dialog::{reward_min_dialogue_handler, start_update_reward_min},
             reward_mode_dialog::{reward_mode_dialogue_handler, start_update_reward_mode},
             utils::{
-                get_current_month_year_day, parse_chart_range, GetDateRangeDialog,
-                GetDateRangeState, UpdateRewardIntervalDialog, UpdateRewardIntervalState,
-                UpdateRewardMinDialog, UpdateRewardMinState, UpdateRewardModeDialog,
-                UpdateRewardModeState,
+                get_current_month_year_day, GetDateRangeDialog, GetDateRangeState,
+                OnboardingDialog, OnboardingState, UpdateRewardIntervalDialog,
+                UpdateRewardIntervalState, UpdateRewardMinDialog, UpdateRewardMinState,
+                UpdateRewardModeDialog, UpdateRewardModeState,
             },
         },
         keyboards::{
-            make_inline_calander, make_inline_chart_menu, make_inline_ghost_links_menu,
-            make_inline_stake_chart_range_menu, make_inline_stakes_chart_menu,
+            make_inline_address_book_menu, make_inline_address_entry_options, make_inline_calander,
+            make_inline_chart_menu, make_inline_cold_wallet_menu, make_inline_cold_wallet_options,
+            make_inline_digest_menu, make_inline_ghost_links_menu, make_inline_maintenance_menu,
+            make_inline_payout_history_menu, make_inline_quiet_hours_menu,
+            make_inline_quiet_hours_presets, make_inline_route_menu, make_inline_routes_menu,
+            make_inline_simulate_rewards_menu, make_inline_stake_chart_range_menu,
+            make_inline_stakes_chart_menu, make_inline_tasks_menu, make_inline_vault_menu,
             make_keyboard_bot_settings, make_keyboard_gv_options, make_keyboard_main,
-            make_keyboard_reward_options, make_reward_interval_keyboard, make_reward_mode_keyboard,
-            make_stats_info_keyboard, make_timezone_option_keyboard, make_timezone_region_keyboard,
+            make_keyboard_reward_options, make_link_button, make_reward_interval_keyboard,
+            make_reward_mode_keyboard, make_stats_info_keyboard, make_timezone_option_keyboard,
+            make_timezone_region_keyboard,
         },
     },
+    time_ranges,
 };
-use chrono::{NaiveDate, TimeZone};
+use chrono::NaiveDate;
 use chrono_tz::Tz;
-use log::info;
+use log::{info, warn};
 use serde_json::Value;
 use std::{
     env,
-    path::PathBuf,
     sync::{
         atomic::{AtomicI32, Ordering},
-        Arc,
+        Arc, RwLock,
     },
     vec,
 };
@@ -47,33 +69,113 @@ use teloxide::{
     dispatching::dialogue::InMemStorage,
     payloads::SendMessageSetters,
     prelude::*,
-    types::{InlineKeyboardButton, InlineKeyboardMarkup, InputFile, MessageId, ParseMode},
+    types::{
+        ForceReply, InlineKeyboardButton, InlineKeyboardMarkup, InlineQueryResult,
+        InlineQueryResultArticle, InputFile, InputMessageContent, InputMessageContentText,
+        MessageId, ParseMode,
+    },
     utils::markdown::escape,
 };
 use tokio::sync::RwLock as async_RwLock;
 use url::Url;
 
+const UNLOCK_WALLET_PROMPT: &str = "Reply to this message with your wallet passphrase.";
+// Log text per message, escaping can double it and Telegram stops at 4096.
+const TG_LOG_MAX_CHARS: usize = 1900;
+// Largest delegations shown, the rest are summed up so the message stays under the limit.
+const TG_DELEGATIONS_MAX: usize = 15;
+
+// Vault the bot is pointed at, an index into vault_names() where 0 is this GhostVault.
+static SELECTED_VAULT: RwLock<usize> = RwLock::new(0);
+
+// Marks the bot's calls as coming from Telegram in the vault's audit log.
+async fn bot_caller(
+    cli_address: &str,
+) -> Result<CLICaller, Box<dyn std::error::Error + Send + Sync>> {
+    let cli_caller: CLICaller = CLICaller::new(cli_address, true).await?;
+    Ok(cli_caller.with_origin("telegram").await)
+}
+
+fn vault_names(conf: &GVConfig) -> Vec<String> {
+    std::iter::once(conf.vault_name.clone())
+        .chain(conf.tg_vaults.iter().map(|vault| vault.name.clone()))
+        .collect()
+}
+
+fn selected_vault_index() -> usize {
+    *SELECTED_VAULT.read().unwrap()
+}
+
+fn selected_vault(conf: &GVConfig) -> FleetVault {
+    vault_at(conf, selected_vault_index())
+}
+
+fn vault_at(conf: &GVConfig, index: usize) -> FleetVault {
+    match index
+        .checked_sub(1)
+        .and_then(|index| conf.tg_vaults.get(index))
+    {
+        Some(vault) => vault.clone(),
+        None => FleetVault {
+            name: conf.vault_name.clone(),
+            cli_address: conf.cli_address.clone(),
+            tls_cert: None,
+        },
+    }
+}
+
+// Tags replies and announcements with the vault they came from, only once there is more than one.
+pub fn vault_prefix(conf: &GVConfig, vault_name: &str) -> String {
+    if conf.tg_vaults.is_empty() {
+        String::new()
+    } else {
+        format!("[{}] ", vault_name)
+    }
+}
+
+// The dialogue storages the message and callback handlers share, passed to dptree as one
+// dependency.
+#[derive(Clone)]
+struct DialogStorage {
+    reward_mode: Arc<InMemStorage<UpdateRewardModeState>>,
+    reward_interval: Arc<InMemStorage<UpdateRewardIntervalState>>,
+    reward_min: Arc<InMemStorage<UpdateRewardMinState>>,
+    chart_range: Arc<InMemStorage<GetDateRangeState>>,
+    onboarding: Arc<InMemStorage<OnboardingState>>,
+}
+
 async fn command_handler(
     bot: DefaultParseMode<Bot>,
     msg: Message,
     gv_config: Arc<async_RwLock<GVConfig>>,
     db: Arc<GVDB>,
-    reward_mode_mem: Arc<InMemStorage<UpdateRewardModeState>>,
     last_dialog_id: Arc<AtomicI32>,
-    reward_interval_mem: Arc<InMemStorage<UpdateRewardIntervalState>>,
-    reward_min_mem: Arc<InMemStorage<UpdateRewardMinState>>,
-    chart_range_mem: Arc<InMemStorage<GetDateRangeState>>,
+    dialogs: DialogStorage,
 ) -> ResponseResult<()> {
     let conf = gv_config.read().await;
     let auth_user = conf.to_owned().tg_user.unwrap();
-    let cli_address = conf.to_owned().cli_address;
+    let local_address = conf.to_owned().cli_address;
+    let vault: FleetVault = selected_vault(&conf);
+    let prefix: String = vault_prefix(&conf, &vault.name);
     drop(conf);
 
     if msg.chat.id.to_string() != auth_user {
+        // /quick and /timezone also answer the admin in group chats, nothing else does.
+        let from_admin: bool = msg
+            .from()
+            .is_some_and(|user| user.id.to_string() == auth_user);
+        let text: String = msg.text().unwrap_or_default().to_lowercase();
+
+        if from_admin && matches_command("quick", &text) {
+            reply_quick_status(&bot, &msg, &gv_config, &db).await?;
+        } else if from_admin && matches_command("chat_timezone", &text) {
+            reply_chat_timezone(&bot, &msg, &gv_config, &db).await?;
+        }
+
         return Ok(());
     }
 
-    let cli_caller_res = CLICaller::new(&cli_address, true).await;
+    let cli_caller_res = bot_caller(&vault.cli_address).await;
 
     let cli_caller = match cli_caller_res {
         Ok(cli) => cli,
@@ -92,10 +194,61 @@ async fn command_handler(
         message_option.unwrap()
     };
 
+    let is_unlock_reply: bool = msg
+        .reply_to_message()
+        .and_then(|reply| reply.text())
+        .is_some_and(|text| text.starts_with(UNLOCK_WALLET_PROMPT));
+
+    if is_unlock_reply {
+        // Don't leave the passphrase sitting in the chat history.
+        let _ = bot.delete_message(msg.chat.id, msg.id).await;
+
+        let message = match cli_caller
+            .call_unlock_wallet(user_message.to_string())
+            .await
+        {
+            Ok(resp) => escape(&resp),
+            Err(e) => escape(format!("Error: {}", e).as_str()),
+        };
+
+        bot.send_message(msg.chat.id, message)
+            .reply_markup(make_keyboard_gv_options())
+            .await?;
+
+        return Ok(());
+    }
+
+    // Setup always configures this GhostVault, whichever vault is selected.
+    let onboarding_dialogue: OnboardingDialog =
+        OnboardingDialog::new(dialogs.onboarding.clone(), msg.chat.id);
+
+    if let Ok(Some(_)) = onboarding_dialogue.get().await {
+        let local_caller: CLICaller = match bot_caller(&local_address).await {
+            Ok(cli) => cli,
+            Err(e) => {
+                let message = escape(format!("Error: {}", e).as_str());
+                bot.send_message(msg.chat.id, message).await?;
+                return Ok(());
+            }
+        };
+
+        onboarding_dialogue_handler(
+            bot.clone(),
+            msg.clone(),
+            last_dialog_id.clone(),
+            onboarding_dialogue,
+            &local_caller,
+            &db,
+        )
+        .await?;
+
+        return Ok(());
+    }
+
     let reward_update_dialogue: Dialogue<
         UpdateRewardModeState,
         InMemStorage<UpdateRewardModeState>,
-    > = UpdateRewardModeDialog::new(reward_mode_mem.clone(), msg.chat.id);
+    > = UpdateRewardModeDialog::new(dialogs.reward_mode.clone(), msg.chat.id);
 
     let reward_update_state = reward_update_dialogue.get().await;
 
@@ -103,7 +256,7 @@ async fn command_handler(
         reward_mode_dialogue_handler(
             bot.clone(),
             msg.clone(),
-            reward_mode_mem.clone(),
+            dialogs.reward_mode.clone(),
             last_dialog_id.clone(),
             reward_update_dialogue.clone(),
             &cli_caller,
@@ -117,7 +270,7 @@ async fn command_handler(
     let reward_interval_dialogue: Dialogue<
         UpdateRewardIntervalState,
         InMemStorage<UpdateRewardIntervalState>,
-    > = UpdateRewardIntervalDialog::new(reward_interval_mem.clone(), msg.chat.id);
+    > = UpdateRewardIntervalDialog::new(dialogs.reward_interval.clone(), msg.chat.id);
 
     let reward_interval_state = reward_interval_dialogue.get().await;
 
@@ -125,7 +278,7 @@ async fn command_handler(
         reward_interval_dialogue_handler(
             bot.clone(),
             msg.clone(),
-            reward_interval_mem.clone(),
+            dialogs.reward_interval.clone(),
             last_dialog_id.clone(),
             reward_interval_dialogue.clone(),
             &cli_caller,
@@ -137,7 +290,7 @@ async fn command_handler(
     }
 
     let reward_min_dialogue: Dialogue<UpdateRewardMinState, InMemStorage<UpdateRewardMinState>> =
-        UpdateRewardMinDialog::new(reward_min_mem.clone(), msg.chat.id);
+        UpdateRewardMinDialog::new(dialogs.reward_min.clone(), msg.chat.id);
 
     let reward_min_state = reward_min_dialogue.get().await;
 
@@ -145,7 +298,7 @@ async fn command_handler(
         reward_min_dialogue_handler(
             bot.clone(),
             msg.clone(),
-            reward_min_mem.clone(),
+            dialogs.reward_min.clone(),
             last_dialog_id.clone(),
             reward_min_dialogue.clone(),
             &cli_caller,
@@ -157,7 +310,7 @@ async fn command_handler(
     }
 
     let chart_range_dialogue: Dialogue<GetDateRangeState, InMemStorage<GetDateRangeState>> =
-        GetDateRangeDialog::new(chart_range_mem.clone(), msg.chat.id);
+        GetDateRangeDialog::new(dialogs.chart_range.clone(), msg.chat.id);
 
     let chart_range_state = chart_range_dialogue.get().await;
 
@@ -165,7 +318,7 @@ async fn command_handler(
         reward_min_dialogue_handler(
             bot.clone(),
             msg.clone(),
-            reward_min_mem.clone(),
+            dialogs.reward_min.clone(),
             last_dialog_id.clone(),
             reward_min_dialogue.clone(),
             &cli_caller,
@@ -176,11 +329,22 @@ async fn command_handler(
         return Ok(());
     }
 
-    let server_ready: ServerReadyDB = db.get_server_ready().unwrap();
+    let server_ready: ServerReadyDB = if selected_vault_index() == 0 {
+        db.get_server_ready().unwrap()
+    } else {
+        match cli_caller.call_get_server_ready().await {
+            Ok(server_ready) => server_ready,
+            Err(e) => {
+                let message = escape(format!("{}Error: {}", prefix, e).as_str());
+                bot.send_message(msg.chat.id, message).await?;
+                return Ok(());
+            }
+        }
+    };
 
     match user_message.to_lowercase().as_str() {
-        cmd if cmd.starts_with("\u{2753} help") => {
-            let reply = escape("👻 GhostVault Help 👻\n\n");
+        cmd if matches_command("help", cmd) => {
+            let reply = escape(&help_text());
 
             let help_link_button =
                 InlineKeyboardMarkup::default().append_row(vec![InlineKeyboardButton::url(
@@ -192,7 +356,7 @@ async fn command_handler(
                 .reply_markup(help_link_button)
                 .await?
         }
-        cmd if cmd.starts_with("/start") => {
+        cmd if matches_command("start", cmd) => {
             let keyboard = make_keyboard_main();
 
             let welcome_message =
@@ -202,7 +366,35 @@ async fn command_handler(
                 .reply_markup(keyboard)
                 .await?
         }
-        cmd if cmd.starts_with("/status") || cmd.starts_with("\u{2139}\u{FE0F} status") => {
+        cmd if matches_command("setup", cmd) => {
+            let local_ready: ServerReadyDB = db.get_server_ready().unwrap();
+
+            if !local_ready.daemon_ready || !local_ready.ready {
+                let reason = server_unready_message(&local_ready);
+
+                let message = escape("Ghost daemon unavailable.\nReason:");
+                let reasoned_message = format!("{}{}", message, reason);
+
+                bot.send_message(msg.chat.id, reasoned_message).await?;
+                return Ok(());
+            }
+
+            if last_dialog_id.load(Ordering::Relaxed) != 0 {
+                return Ok(());
+            }
+
+            start_onboarding(
+                bot.clone(),
+                onboarding_dialogue,
+                msg.chat.id,
+                last_dialog_id.clone(),
+            )
+            .await
+            .unwrap();
+
+            return Ok(());
+        }
+        cmd if matches_command("status", cmd) => {
             if !server_ready.daemon_ready || !server_ready.ready {
                 let reason = server_unready_message(&server_ready);
 
@@ -212,508 +404,734 @@ async fn command_handler(
 
                 bot.send_message(msg.chat.id, reasoned_message).await?
             } else {
-                reply_status(&bot, &msg, &gv_config).await?
+                reply_status(&bot, &msg, &gv_config, &db).await?
             }
         }
-        cmd if cmd.starts_with("/stats") || cmd.starts_with("\u{1F4CA} stats") => {
-            let keyboard = make_stats_info_keyboard();
-
-            let stats_message = escape("👻 Stats 👻\n Please choose an option");
-
-            bot.send_message(msg.chat.id, stats_message)
-                .reply_markup(keyboard)
-                .await?
+        cmd if matches_command("quick", cmd) => {
+            reply_quick_status(&bot, &msg, &gv_config, &db).await?
         }
-        cmd if cmd.starts_with("/bot_settings")
-            || cmd.starts_with("\u{2699}\u{FE0F} bot settings") =>
-        {
-            let reply = get_bot_settings(&gv_config).await;
-            let keyboard = make_keyboard_bot_settings();
-
-            bot.send_message(msg.chat.id, reply)
-                .reply_markup(keyboard)
-                .await?
+        cmd if matches_command("chat_timezone", cmd) => {
+            reply_chat_timezone(&bot, &msg, &gv_config, &db).await?
         }
-        cmd if cmd.starts_with("\u{1F47B} ghost links") => {
-            let keyboard = make_inline_ghost_links_menu();
+        cmd if matches_command("tx", cmd) => {
+            let txid: String = command_arg(user_message);
 
-            let ghost_links_message = escape("👻 Ghost Links 👻");
+            if txid.is_empty() {
+                let message = escape("Usage: /tx <txid>");
+                bot.send_message(msg.chat.id, message).await?;
+                return Ok(());
+            }
 
-            bot.send_message(msg.chat.id, ghost_links_message)
-                .reply_markup(keyboard)
-                .await?
-        }
-        cmd if cmd.starts_with("\u{1F3E0} home")
-            || vec!["home", "/home", "keyboard", "/keyboard"].contains(&cmd) =>
-        {
-            let keyboard = make_keyboard_main();
+            let tx: TxSummary = match cli_caller.call_lookup_tx(txid).await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    let message = escape(format!("{}Error: {}", prefix, e).as_str());
+                    bot.send_message(msg.chat.id, message).await?;
+                    return Ok(());
+                }
+            };
 
-            let home_message = escape("\u{1F3E0} Home");
+            let header: String = escape(format!("{}👻 Transaction 👻", prefix).as_str());
+            let details: String = escape(format_tx_summary(&tx).as_str());
+            let reply: String = format!("{}\n\n```\n{}\n```", header, details);
 
-            bot.send_message(msg.chat.id, home_message)
-                .reply_markup(keyboard)
+            bot.send_message(msg.chat.id, reply)
+                .reply_markup(make_link_button(&vec![tx.url], "View on explorer"))
                 .await?
         }
-        cmd if cmd.starts_with("\u{1F4B8} toggle stake") => {
-            let conf = gv_config.read().await;
-            let toggle = !conf.announce_stakes;
-            drop(conf);
+        cmd if matches_command("block", cmd) => {
+            let block: String = command_arg(user_message);
 
-            let cli_res = cli_caller
-                .call_set_bot_announce("stake".to_string(), toggle)
-                .await;
-
-            match cli_res {
-                Ok(_) => {
-                    let reply = get_bot_settings(&gv_config).await;
+            if block.is_empty() {
+                let message = escape("Usage: /block <height or hash>");
+                bot.send_message(msg.chat.id, message).await?;
+                return Ok(());
+            }
 
-                    bot.send_message(msg.chat.id, reply).await?
-                }
+            let block: BlockSummary = match cli_caller.call_lookup_block(block).await {
+                Ok(resp) => resp,
                 Err(e) => {
-                    let message = escape(format!("Error: {}", e).as_str());
-                    bot.send_message(msg.chat.id, message).await?
+                    let message = escape(format!("{}Error: {}", prefix, e).as_str());
+                    bot.send_message(msg.chat.id, message).await?;
+                    return Ok(());
                 }
-            }
-        }
-        cmd if cmd.starts_with("\u{1F4B0} toggle reward") => {
-            let conf = gv_config.read().await;
-            let toggle = !conf.announce_rewards;
-            drop(conf);
+            };
 
-            let cli_res = cli_caller
-                .call_set_bot_announce("reward".to_string(), toggle)
-                .await;
+            let header: String = escape(format!("{}👻 Block 👻", prefix).as_str());
+            let details: String = escape(format_block_summary(&block).as_str());
+            let reply: String = format!("{}\n\n```\n{}\n```", header, details);
 
-            match cli_res {
-                Ok(_) => {
-                    let reply = get_bot_settings(&gv_config).await;
+            bot.send_message(msg.chat.id, reply)
+                .reply_markup(make_link_button(&vec![block.url], "View on explorer"))
+                .await?
+        }
+        cmd if matches_command("operations", cmd) => {
+            let mut statuses: Vec<OperationStatus> = Vec::new();
 
-                    bot.send_message(msg.chat.id, reply).await?
-                }
-                Err(e) => {
-                    let message = escape(format!("Error: {}", e).as_str());
-                    bot.send_message(msg.chat.id, message).await?
+            for op_id in OPERATIONS {
+                match cli_caller
+                    .call_get_operation_status(op_id.to_string())
+                    .await
+                {
+                    Ok(status) => statuses.push(status),
+                    Err(e) => {
+                        let message = escape(format!("{}Error: {}", prefix, e).as_str());
+                        bot.send_message(msg.chat.id, message).await?;
+                        return Ok(());
+                    }
                 }
             }
-        }
-        cmd if cmd.starts_with("\u{26A1} toggle zap") => {
-            let conf = gv_config.read().await;
-            let toggle = !conf.announce_zaps;
-            drop(conf);
 
-            let cli_res = cli_caller
-                .call_set_bot_announce("zap".to_string(), toggle)
-                .await;
-
-            match cli_res {
-                Ok(_) => {
-                    let reply = get_bot_settings(&gv_config).await;
-
-                    bot.send_message(msg.chat.id, reply).await?
-                }
+            let message: String =
+                escape(format!("{}{}", prefix, format_operations(&statuses)).as_str());
+            bot.send_message(msg.chat.id, message).await?
+        }
+        cmd if matches_command("jobs", cmd) => {
+            let jobs: Vec<JobDB> = match cli_caller.call_get_jobs(10).await {
+                Ok(resp) => resp,
                 Err(e) => {
-                    let message = escape(format!("Error: {}", e).as_str());
-                    bot.send_message(msg.chat.id, message).await?
+                    let message = escape(format!("{}Error: {}", prefix, e).as_str());
+                    bot.send_message(msg.chat.id, message).await?;
+                    return Ok(());
                 }
-            }
-        }
-        cmd if cmd.starts_with("\u{2699}\u{FE0F} ghostvault options") => {
-            let keyboard = make_keyboard_gv_options();
-
-            let gv_options_message = escape("👻 GhostVault Options 👻\n Please choose an option");
+            };
 
-            bot.send_message(msg.chat.id, gv_options_message)
-                .reply_markup(keyboard)
-                .await?
+            let message: String = escape(format!("{}{}", prefix, format_jobs(&jobs)).as_str());
+            bot.send_message(msg.chat.id, message).await?
         }
-        cmd if cmd.starts_with("\u{2744}\u{FE0F} cs key") => {
-            let cli_res = cli_caller.call_get_ext_pub_key().await;
-
-            let ext_pub_key = match cli_res {
-                Ok(resp) => resp.as_str().unwrap().to_string(),
+        // Before /expect, which it starts with
+        cmd if matches_command("expected", cmd) => {
+            let deposits: Vec<ExpectedDeposit> = match cli_caller.call_get_expected_deposits().await
+            {
+                Ok(resp) => resp,
                 Err(e) => {
-                    let message = escape(format!("Error: {}", e).as_str());
+                    let message = escape(format!("{}Error: {}", prefix, e).as_str());
                     bot.send_message(msg.chat.id, message).await?;
                     return Ok(());
                 }
             };
 
-            let key_mono: String = format!("`{}`", ext_pub_key);
-
-            let header = escape("👻 CS Key 👻\n\n");
-            let message = escape(format!("Your GhostVault CS Key is\n\n").as_str());
-
-            let reply: String = format!("{}{}{}", header, message, key_mono);
-
-            bot.send_message(msg.chat.id, reply).await?
+            let message: String =
+                escape(format!("{}{}", prefix, format_expected_deposits(&deposits)).as_str());
+            bot.send_message(msg.chat.id, message).await?
         }
-        cmd if cmd.starts_with("\u{1F4CA} version") => {
-            let cli_res = cli_caller.call_get_version_info().await;
+        cmd if matches_command("expect", cmd) => {
+            let mut args = user_message.split_whitespace().skip(1);
+
+            let amount: f64 = match args.next().map(|amount| amount.parse::<f64>()) {
+                Some(Ok(amount)) => amount,
+                _ => {
+                    let message = escape("Usage: /expect <amount> [memo]");
+                    bot.send_message(msg.chat.id, message).await?;
+                    return Ok(());
+                }
+            };
+            let memo: String = args.collect::<Vec<&str>>().join(" ");
 
-            let cli_resp: Value = match cli_res {
+            let deposit: ExpectedDeposit = match cli_caller
+                .call_add_expected_deposit(amount, memo, None)
+                .await
+            {
                 Ok(resp) => resp,
                 Err(e) => {
-                    let message = escape(format!("Error: {}", e).as_str());
+                    let message = escape(format!("{}Error: {}", prefix, e).as_str());
                     bot.send_message(msg.chat.id, message).await?;
                     return Ok(());
                 }
             };
 
-            let gv_version: &str = cli_resp["gv_version"].as_str().unwrap();
-            let ghostd_version: &str = cli_resp["ghostd_version"].as_str().unwrap();
-            let latest_release: &str = cli_resp["latest_release"].as_str().unwrap();
-
-            let header: String = escape("👻 GhostVault Version Info 👻\n\n");
             let message: String = escape(
                 format!(
-                    "GhostVault: {}\nGhostd: {}\nLatest Ghostd Release: {}",
-                    gv_version, ghostd_version, latest_release
+                    "{}Expecting {} GHOST by {}. You'll be told if it doesn't arrive.",
+                    prefix,
+                    deposit.amount,
+                    format_utc(deposit.due)
                 )
                 .as_str(),
             );
-
-            let reply: String = format!("{}{}", header, message);
-
-            bot.send_message(msg.chat.id, reply).await?
+            bot.send_message(msg.chat.id, message).await?
         }
-        cmd if cmd.starts_with("\u{1F501} resync") => {
-            let good_chain_res = cli_caller.call_check_chain().await;
+        cmd if matches_command("label", cmd) => {
+            let mut args = user_message.split_whitespace().skip(1);
 
-            let good_chain = match good_chain_res {
-                Ok(resp) => resp.as_bool().unwrap(),
-                Err(e) => {
-                    let message = escape(format!("Error: {}", e).as_str());
+            let address: String = match args.next() {
+                Some(address) => address.to_string(),
+                None => {
+                    let message = escape("Usage: /label <address> [name]");
                     bot.send_message(msg.chat.id, message).await?;
                     return Ok(());
                 }
             };
+            // No name takes the address out of the book
+            let label: String = args.collect::<Vec<&str>>().join(" ");
 
-            let confirm_markup = InlineKeyboardMarkup::default().append_row(vec![
-                InlineKeyboardButton::callback("Confirm", "confirm_resync"),
-                InlineKeyboardButton::callback("Cancel", "cancel_resync"),
-            ]);
-
-            let message = if good_chain {
-                escape(
-                    "Your GhostVault is properly synced!\n\nAre you sure you want to start the resync operation?\nThis will take a long time and staking will be unavailable for the duration.",
-                )
-            } else {
-                escape(
-                    "WARNING: your GhostVault has a bad sync!\n\nAre you sure you want to start the resync operation?\nThis will take a long time and staking will be unavailable for the duration.",
-                )
+            let message: String = match cli_caller.call_set_address_label(address, label).await {
+                Ok(resp) => resp,
+                Err(e) => format!("Error: {}", e),
             };
 
-            let sent_message = bot
-                .send_message(msg.chat.id, &message)
-                .reply_markup(confirm_markup)
-                .await?;
-            sent_message
+            bot.send_message(
+                msg.chat.id,
+                escape(format!("{}{}", prefix, message).as_str()),
+            )
+            .await?
         }
-        cmd if cmd.starts_with("\u{1F517} check chain") => {
-            let cli_res = cli_caller.call_check_chain().await;
-
-            let good_chain = match cli_res {
-                Ok(resp) => resp.as_bool().unwrap(),
+        cmd if matches_command("address_book", cmd) => {
+            match cli_caller.call_get_address_book().await {
+                Ok(entries) => {
+                    let message = escape(format_address_book(&entries).as_str());
+
+                    bot.send_message(msg.chat.id, message)
+                        .reply_markup(make_inline_address_book_menu(&entries))
+                        .await?
+                }
                 Err(e) => {
-                    let message = escape(format!("Error: {}", e).as_str());
+                    let message = escape(format!("{}Error: {}", prefix, e).as_str());
+                    bot.send_message(msg.chat.id, message).await?
+                }
+            }
+        }
+        cmd if matches_command("zaprequest", cmd) => {
+            let amount: f64 = match command_arg(user_message).parse::<f64>() {
+                Ok(amount) => amount,
+                Err(_) => {
+                    let message = escape("Usage: /zaprequest <amount>");
                     bot.send_message(msg.chat.id, message).await?;
                     return Ok(());
                 }
             };
 
-            let message = if good_chain {
-                escape("Your GhostVault is properly synced!")
-            } else {
-                escape("WARNING: your GhostVault has a bad sync!")
-            };
-
-            bot.send_message(msg.chat.id, message).await?
-        }
-        cmd if cmd.starts_with("\u{1F6E0}\u{FE0F} update ghostd") => {
-            let cli_res = cli_caller.call_process_daemon_update().await;
-
-            let cli_resp = match cli_res {
+            let request: ZapRequest = match cli_caller.call_build_zap_request(amount).await {
                 Ok(resp) => resp,
                 Err(e) => {
-                    let message = escape(format!("Error: {}", e).as_str());
+                    let message = escape(format!("{}Error: {}", prefix, e).as_str());
                     bot.send_message(msg.chat.id, message).await?;
                     return Ok(());
                 }
             };
 
-            let header = escape("👻 Ghostd Update 👻\n\n");
+            let caption: String = escape(
+                format!(
+                    "{}👻 Zap Request 👻\n\nSend {} GHOST to cold stake with the vault.\n\nStake address: {}\nSpend address: {}\n\nExpires {}",
+                    prefix,
+                    request.amount,
+                    request.stake_addr,
+                    request.spend_addr,
+                    format_utc(request.expires)
+                )
+                .as_str(),
+            );
 
-            let sent_message = if cli_resp.is_string() {
-                let new_version = cli_resp.as_str().unwrap();
+            let uri: String = request.uri.clone();
+            match tokio::task::spawn_blocking(move || make_qr_code(&uri)).await {
+                Ok(Ok(png_bytes)) => {
+                    let qr_file = InputFile::memory(png_bytes).file_name("zap_request.png");
 
-                if new_version.contains("Failed to check for updates!") {
-                    let message = escape(
-                        format!(
-                            "{}Failed to check for updates!\nPlease try again later.",
-                            header
-                        )
-                        .as_str(),
-                    );
-                    bot.send_message(msg.chat.id, message).await?
-                } else {
-                    let message = escape(
-                        format!(
-                            "{}New update found!\nUpdating ghostd to version: {}",
-                            header, new_version
-                        )
-                        .as_str(),
-                    );
-                    bot.send_message(msg.chat.id, message).await?
+                    bot.send_photo(msg.chat.id, qr_file)
+                        .caption(caption)
+                        .await?
                 }
-            } else {
-                let message = escape(format!("{}Ghostd is already up to date", header).as_str());
-                bot.send_message(msg.chat.id, message).await?
-            };
-
-            sent_message
+                _ => {
+                    let script: String = format!("```\n{}\n```", request.script_hex);
+                    bot.send_message(msg.chat.id, format!("{}\n\n{}", caption, script))
+                        .await?
+                }
+            }
         }
-        cmd if cmd.starts_with("\u{1F4B8} reward options") => {
-            let keyboard = make_keyboard_reward_options();
-
-            let cli_res = cli_caller.call_get_reward_options().await;
+        cmd if matches_command("vaults", cmd) => {
+            let conf = gv_config.read().await;
+            let names: Vec<String> = vault_names(&conf);
+            drop(conf);
 
-            let cli_value = match cli_res {
-                Ok(resp) => resp,
-                Err(e) => {
-                    let message = escape(format!("Error: {}", e).as_str());
-                    bot.send_message(msg.chat.id, message).await?;
-                    return Ok(());
-                }
-            };
+            if names.len() < 2 {
+                let message = escape(
+                    format!(
+                        "👻 Vaults 👻\n\nOnly {} is configured.\nAdd vaults to TG_VAULTS in gv_settings.toml to manage them from this bot.",
+                        vault.name
+                    )
+                    .as_str(),
+                );
 
-            let reward_options: String = serde_json::to_string_pretty(&cli_value).unwrap();
-            let code_block: String = format!("\n```\n{}\n```\n", reward_options);
-            let header: String = escape("👻 Reward Options 👻\n\n");
-            let choose_opt: String = escape("\nPlease choose an option");
+                bot.send_message(msg.chat.id, message).await?
+            } else {
+                let kb = make_inline_vault_menu(&names, selected_vault_index());
+                let message = escape(
+                    format!("👻 Vaults 👻\n\nManaging: {}\nSelect a vault", vault.name).as_str(),
+                );
 
-            let explainer = escape(concat!(
-                "reward_address: The address that rewards are sent to. This will be blank in DEFAULT mode.\n",
-                "reward_interval: This is how often GhostVault will check if rewards can be sent. Only applies to ANON mode.\n",
-                "reward_min: Available rewards must be this much before they are sent. Only applies to ANON mode.\n",
-                "reward_mode: This is the reward mode that GhostVault is in.\n\n",
-            ));
+                bot.send_message(msg.chat.id, message)
+                    .reply_markup(kb)
+                    .await?
+            }
+        }
+        cmd if matches_command("stats", cmd) => {
+            let keyboard = make_stats_info_keyboard();
 
-            let send_message: String =
-                format!("{}{}{}{}", header, explainer, code_block, choose_opt);
+            let stats_message = escape("👻 Stats 👻\n Please choose an option");
 
-            bot.send_message(msg.chat.id, send_message)
+            bot.send_message(msg.chat.id, stats_message)
                 .reply_markup(keyboard)
                 .await?
         }
-        cmd if cmd.starts_with("\u{1F4B8} set reward mode & address") => {
-            if server_ready.daemon_ready && server_ready.ready {
-                let keyboard = make_reward_mode_keyboard();
+        // /bot_settings from before the command menu
+        cmd if matches_command("bot_settings", cmd) || cmd.starts_with("/bot_settings") => {
+            let reply = get_bot_settings(&gv_config).await;
+            let keyboard = make_keyboard_bot_settings();
 
-                if last_dialog_id.load(Ordering::Relaxed) != 0 {
-                    return Ok(());
-                }
+            bot.send_message(msg.chat.id, reply)
+                .reply_markup(keyboard)
+                .await?
+        }
+        cmd if matches_command("announce_routes", cmd) => {
+            // Routes are a setting of this bot, not of the selected vault.
+            let routes_res = match bot_caller(&local_address).await {
+                Ok(local_caller) => get_announce_routes(&local_caller).await,
+                Err(e) => Err(e),
+            };
 
-                let new_msg = bot
-                    .send_message(msg.chat.id, "👻 Reward Mode Updater 👻")
-                    .reply_markup(keyboard)
-                    .await?;
+            match routes_res {
+                Ok(routes) => {
+                    let message =
+                        escape("Announcement Routes\n\nPick an event to change where it's sent.");
 
-                let new_id: i32 = new_msg.id.to_string().parse::<i32>().unwrap();
-                last_dialog_id.store(new_id, Ordering::Relaxed);
+                    bot.send_message(msg.chat.id, message)
+                        .reply_markup(make_inline_routes_menu(&routes))
+                        .await?
+                }
+                Err(e) => {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(msg.chat.id, message).await?
+                }
+            }
+        }
+        cmd if matches_command("digests", cmd) => {
+            // Digests are a setting of this bot, not of the selected vault.
+            let settings_res = match bot_caller(&local_address).await {
+                Ok(local_caller) => get_digest_settings(&local_caller).await,
+                Err(e) => Err(e),
+            };
 
-                reward_update_dialogue
-                    .update(UpdateRewardModeState::Start)
-                    .await
-                    .unwrap();
+            match settings_res {
+                Ok(settings) => {
+                    let message = escape(
+                        format!(
+                            "Digests\n\nSummaries of the last day or week, sent at the hour below ({}).",
+                            settings.timezone
+                        )
+                        .as_str(),
+                    );
 
-                start_update_reward_mode(
-                    bot.clone(),
-                    reward_update_dialogue.clone(),
-                    msg.clone(),
-                    last_dialog_id.clone(),
-                )
-                .await
-                .unwrap();
-            } else {
-                let reason = server_unready_message(&server_ready);
+                    bot.send_message(msg.chat.id, message)
+                        .reply_markup(make_inline_digest_menu(&settings))
+                        .await?
+                }
+                Err(e) => {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(msg.chat.id, message).await?
+                }
+            }
+        }
+        cmd if matches_command("quiet_hours", cmd) => {
+            // Quiet hours are a setting of this bot, not of the selected vault.
+            let quiet_res = match bot_caller(&local_address).await {
+                Ok(local_caller) => local_caller.call_get_quiet_hours().await,
+                Err(e) => Err(e),
+            };
 
-                let message = escape("Ghost daemon unavailable.\nReason:");
-                let reasoned_message = format!("{}{}", message, reason);
+            match quiet_res {
+                Ok(quiet_hours) => {
+                    let message = escape(format_quiet_hours(&quiet_hours).as_str());
 
-                bot.send_message(msg.chat.id, reasoned_message).await?;
+                    bot.send_message(msg.chat.id, message)
+                        .reply_markup(make_inline_quiet_hours_menu(&quiet_hours))
+                        .await?
+                }
+                Err(e) => {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(msg.chat.id, message).await?
+                }
             }
-
-            return Ok(());
         }
+        cmd if matches_command("scheduled_tasks", cmd) => {
+            match cli_caller.call_get_scheduled_tasks().await {
+                Ok(tasks) => {
+                    let message = escape(format_scheduled_tasks(&tasks).as_str());
+
+                    bot.send_message(msg.chat.id, message)
+                        .reply_markup(make_inline_tasks_menu(&tasks))
+                        .await?
+                }
+                Err(e) => {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(msg.chat.id, message).await?
+                }
+            }
+        }
+        cmd if matches_command("maintenance", cmd) => {
+            let window_res = get_maintenance_window(&cli_caller).await;
 
-        cmd if cmd.starts_with("\u{1F4CA} set reward interval") => {
-            if server_ready.daemon_ready && server_ready.ready {
-                let keyboard = make_reward_interval_keyboard();
+            match window_res {
+                Ok(window) => {
+                    let message = escape(format_maintenance_window(&window).as_str());
 
-                if last_dialog_id.load(Ordering::Relaxed) != 0 {
-                    return Ok(());
+                    bot.send_message(msg.chat.id, message)
+                        .reply_markup(make_inline_maintenance_menu(&window))
+                        .await?
+                }
+                Err(e) => {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(msg.chat.id, message).await?
                 }
+            }
+        }
+        cmd if matches_command("cold_wallets", cmd) => match get_cold_wallets(&cli_caller).await {
+            Ok(wallets) => {
+                let message = escape(format_cold_wallets(&wallets).as_str());
 
-                let new_msg = bot
-                    .send_message(msg.chat.id, "👻 Reward Interval Updater 👻")
-                    .reply_markup(keyboard)
-                    .await?;
+                bot.send_message(msg.chat.id, message)
+                    .reply_markup(make_inline_cold_wallet_menu(&wallets))
+                    .await?
+            }
+            Err(e) => {
+                let message = escape(format!("Error: {}", e).as_str());
+                bot.send_message(msg.chat.id, message).await?
+            }
+        },
+        cmd if matches_command("recent_logs", cmd) => {
+            let logs_res = get_recent_logs(&cli_caller).await;
 
-                let new_id: i32 = new_msg.id.to_string().parse::<i32>().unwrap();
-                last_dialog_id.store(new_id, Ordering::Relaxed);
+            match logs_res {
+                Ok(logs) => {
+                    let header: String =
+                        escape(format!("{}\u{1F4DC} Recent Logs", prefix).as_str());
+                    let lines: String = escape(format_recent_logs(&logs).as_str());
+                    let reply: String = format!("{}\n\n```\n{}\n```", header, lines);
 
-                reward_interval_dialogue
-                    .update(UpdateRewardIntervalState::Start)
-                    .await
-                    .unwrap();
+                    bot.send_message(msg.chat.id, reply).await?
+                }
+                Err(e) => {
+                    let message = escape(format!("{}Error: {}", prefix, e).as_str());
+                    bot.send_message(msg.chat.id, message).await?
+                }
+            }
+        }
+        cmd if matches_command("ghost_links", cmd) => {
+            let keyboard = make_inline_ghost_links_menu();
 
-                start_update_reward_interval(
-                    bot.clone(),
-                    reward_interval_dialogue.clone(),
-                    msg.clone(),
-                    last_dialog_id.clone(),
-                )
-                .await
-                .unwrap();
-            } else {
-                let reason = server_unready_message(&server_ready);
+            let ghost_links_message = escape("👻 Ghost Links 👻");
 
-                let message = escape("Ghost daemon unavailable.\nReason:");
-                let reasoned_message = format!("{}{}", message, reason);
+            bot.send_message(msg.chat.id, ghost_links_message)
+                .reply_markup(keyboard)
+                .await?
+        }
+        cmd if matches_command("home", cmd)
+            || vec!["home", "/home", "keyboard", "/keyboard"].contains(&cmd) =>
+        {
+            let keyboard = make_keyboard_main();
 
-                bot.send_message(msg.chat.id, reasoned_message).await?;
-            }
+            let home_message = escape("\u{1F3E0} Home");
 
-            return Ok(());
+            bot.send_message(msg.chat.id, home_message)
+                .reply_markup(keyboard)
+                .await?
         }
+        cmd if matches_command("toggle_stake", cmd) => {
+            let conf = gv_config.read().await;
+            let toggle = !conf.announce_stakes;
+            drop(conf);
 
-        cmd if cmd.starts_with("\u{1F4B0} set payout min") => {
-            if server_ready.daemon_ready && server_ready.ready {
-                if last_dialog_id.load(Ordering::Relaxed) != 0 {
-                    return Ok(());
+            // Announcements are a setting of this bot, not of the selected vault.
+            let cli_res = match bot_caller(&local_address).await {
+                Ok(local_caller) => {
+                    local_caller
+                        .call_set_bot_announce("stake".to_string(), toggle)
+                        .await
                 }
+                Err(e) => Err(e),
+            };
 
-                let new_msg = bot
-                    .send_message(msg.chat.id, "👻 Payout Min Updater 👻")
-                    .await?;
-
-                let new_id: i32 = new_msg.id.to_string().parse::<i32>().unwrap();
-                last_dialog_id.store(new_id, Ordering::Relaxed);
+            match cli_res {
+                Ok(_) => {
+                    let reply = get_bot_settings(&gv_config).await;
 
-                reward_min_dialogue
-                    .update(UpdateRewardMinState::Start)
-                    .await
-                    .unwrap();
+                    bot.send_message(msg.chat.id, reply).await?
+                }
+                Err(e) => {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(msg.chat.id, message).await?
+                }
+            }
+        }
+        cmd if matches_command("toggle_reward", cmd) => {
+            let conf = gv_config.read().await;
+            let toggle = !conf.announce_rewards;
+            drop(conf);
 
-                start_update_reward_min(
-                    bot.clone(),
-                    reward_min_dialogue.clone(),
-                    msg.clone(),
-                    last_dialog_id.clone(),
-                )
-                .await
-                .unwrap();
-            } else {
-                let reason = server_unready_message(&server_ready);
+            // Announcements are a setting of this bot, not of the selected vault.
+            let cli_res = match bot_caller(&local_address).await {
+                Ok(local_caller) => {
+                    local_caller
+                        .call_set_bot_announce("reward".to_string(), toggle)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
 
-                let message = escape("Ghost daemon unavailable.\nReason:");
-                let reasoned_message = format!("{}{}", message, reason);
+            match cli_res {
+                Ok(_) => {
+                    let reply = get_bot_settings(&gv_config).await;
 
-                bot.send_message(msg.chat.id, reasoned_message).await?;
+                    bot.send_message(msg.chat.id, reply).await?
+                }
+                Err(e) => {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(msg.chat.id, message).await?
+                }
             }
-            return Ok(());
         }
+        cmd if matches_command("toggle_zap", cmd) => {
+            let conf = gv_config.read().await;
+            let toggle = !conf.announce_zaps;
+            drop(conf);
 
-        cmd if cmd.starts_with("\u{1F55B} set timezone") => {
-            let message = escape("👻 Timezone Updater 👻\n\nPlease select your region.");
+            // Announcements are a setting of this bot, not of the selected vault.
+            let cli_res = match bot_caller(&local_address).await {
+                Ok(local_caller) => {
+                    local_caller
+                        .call_set_bot_announce("zap".to_string(), toggle)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
 
-            let kb = make_timezone_region_keyboard();
+            match cli_res {
+                Ok(_) => {
+                    let reply = get_bot_settings(&gv_config).await;
 
-            bot.send_message(msg.chat.id, message)
-                .reply_markup(kb)
-                .await?
+                    bot.send_message(msg.chat.id, reply).await?
+                }
+                Err(e) => {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(msg.chat.id, message).await?
+                }
+            }
         }
+        cmd if matches_command("gv_options", cmd) => {
+            let keyboard = make_keyboard_gv_options();
 
-        cmd if cmd.starts_with("\u{1F4CA} charts") => {
-            let kb = make_inline_chart_menu();
-
-            let message = escape("👻 Charts 👻\n\nPlease select a chart type");
+            let gv_options_message = escape("👻 GhostVault Options 👻\n Please choose an option");
 
-            bot.send_message(msg.chat.id, message)
-                .reply_markup(kb)
+            bot.send_message(msg.chat.id, gv_options_message)
+                .reply_markup(keyboard)
                 .await?
         }
+        cmd if matches_command("cs_key", cmd) => {
+            let cli_res = cli_caller.call_get_ext_pub_key().await;
 
-        cmd if cmd.starts_with("\u{1F4CB} overview") => {
-            if server_ready.daemon_ready && server_ready.ready {
-                let cli_res = cli_caller.call_get_overview().await;
+            let ext_pub_key = match cli_res {
+                Ok(resp) => resp,
+                Err(e) => {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(msg.chat.id, message).await?;
+                    return Ok(());
+                }
+            };
 
-                let cli_value = match cli_res {
-                    Ok(resp) => resp,
-                    Err(e) => {
-                        let message = escape(format!("Error: {}", e).as_str());
-                        bot.send_message(msg.chat.id, message).await?;
-                        return Ok(());
-                    }
-                };
+            let key_mono: String = format!("`{}`", ext_pub_key);
 
-                let header: String = escape("👻 Overview 👻\n\n");
-                let staking_data: StakingDataOverview = serde_json::from_value(cli_value).unwrap();
+            let header = escape("👻 CS Key 👻\n\n");
+            let message = escape(format!("Your GhostVault CS Key is\n\n").as_str());
 
-                let overview: String = serde_json::to_string_pretty(&staking_data).unwrap();
-                let code_block: String = format!("```\n{}\n```\n", overview);
+            let reply: String = format!("{}{}{}", header, message, key_mono);
 
-                let message: String = format!("{}{}", header, code_block);
+            bot.send_message(msg.chat.id, reply).await?
+        }
+        cmd if matches_command("version", cmd) => {
+            let cli_res = cli_caller.call_get_version_info().await;
 
-                bot.send_message(msg.chat.id, message).await?
-            } else {
-                let reason = server_unready_message(&server_ready);
+            let version_info: VersionInfo = match cli_res {
+                Ok(resp) => resp,
+                Err(e) => {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(msg.chat.id, message).await?;
+                    return Ok(());
+                }
+            };
 
-                let message = escape("Ghost daemon unavailable.\nReason:");
+            let or_unknown = |value: &str| -> String {
+                if value.is_empty() {
+                    "unknown".to_string()
+                } else {
+                    value.to_string()
+                }
+            };
+            let gv_version: &str = &version_info.gv_version;
+            let git_commit: String = or_unknown(&version_info.git_commit);
+            let build_date: String = or_unknown(&version_info.build_date);
+            let ghostd_version: &str = &version_info.ghostd_version;
+            let latest_release: &str = &version_info.latest_release;
 
-                let reasoned_message = format!("{}{}", message, reason);
+            let header: String = escape("👻 GhostVault Version Info 👻\n\n");
+            let message: String = escape(
+                format!(
+                    "GhostVault: {} ({})\nBuilt: {}\nGhostd: {}\nLatest Ghostd Release: {}",
+                    gv_version, git_commit, build_date, ghostd_version, latest_release
+                )
+                .as_str(),
+            );
 
-                bot.send_message(msg.chat.id, reasoned_message).await?
-            }
+            let reply: String = format!("{}{}", header, message);
+
+            bot.send_message(msg.chat.id, reply).await?
         }
+        cmd if matches_command("resync", cmd) => {
+            let good_chain_res = cli_caller.call_check_chain().await;
 
-        cmd if cmd.starts_with("\u{1F4B0} pending rewards") => {
-            if !server_ready.daemon_ready || !server_ready.ready {
-                let reason = server_unready_message(&server_ready);
+            let good_chain = match good_chain_res {
+                Ok(resp) => resp,
+                Err(e) => {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(msg.chat.id, message).await?;
+                    return Ok(());
+                }
+            };
 
-                let message = escape("Ghost daemon unavailable.\nReason:");
+            let confirm_markup = InlineKeyboardMarkup::default().append_row(vec![
+                InlineKeyboardButton::callback("Confirm", "confirm_resync"),
+                InlineKeyboardButton::callback("Resync now", "confirm_resync:now"),
+                InlineKeyboardButton::callback("Cancel", "cancel_resync"),
+            ]);
 
-                let reasoned_message = format!("{}{}", message, reason);
+            let message = if good_chain {
+                escape(
+                    "Your GhostVault is properly synced!\n\nAre you sure you want to start the resync operation?\nThis will take a long time and staking will be unavailable for the duration.\nConfirm waits for the maintenance window, Resync now starts right away.",
+                )
+            } else {
+                escape(
+                    "WARNING: your GhostVault has a bad sync!\n\nAre you sure you want to start the resync operation?\nThis will take a long time and staking will be unavailable for the duration.\nConfirm waits for the maintenance window, Resync now starts right away.",
+                )
+            };
 
-                bot.send_message(msg.chat.id, reasoned_message).await?
+            let sent_message = bot
+                .send_message(msg.chat.id, &message)
+                .reply_markup(confirm_markup)
+                .await?;
+            sent_message
+        }
+        cmd if matches_command("check_chain", cmd) => {
+            let cli_res = cli_caller.call_check_chain().await;
+
+            let good_chain = match cli_res {
+                Ok(resp) => resp,
+                Err(e) => {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(msg.chat.id, message).await?;
+                    return Ok(());
+                }
+            };
+
+            let message = if good_chain {
+                escape("Your GhostVault is properly synced!")
             } else {
-                let cli_res = cli_caller.call_get_pending_rewards().await;
+                escape("WARNING: your GhostVault has a bad sync!")
+            };
 
-                let cli_value = match cli_res {
-                    Ok(resp) => resp,
-                    Err(e) => {
-                        let message = escape(format!("Error: {}", e).as_str());
-                        bot.send_message(msg.chat.id, message).await?;
-                        return Ok(());
-                    }
-                };
+            bot.send_message(msg.chat.id, message).await?
+        }
+        cmd if matches_command("update_ghostd", cmd) => {
+            let cli_res = cli_caller.call_process_daemon_update(false).await;
+            let header = escape("👻 Ghostd Update 👻\n\n");
 
-                let header = escape("👻 Pending Rewards 👻\n\n");
+            let update: DaemonUpdate = match cli_res {
+                Ok(resp) => resp,
+                Err(e) => {
+                    let message = match response::error_code(e.as_ref()) {
+                        Some(ErrorCode::Unavailable) => escape(
+                            format!(
+                                "{}Failed to check for updates!\nPlease try again later.",
+                                header
+                            )
+                            .as_str(),
+                        ),
+                        Some(ErrorCode::Disabled) => escape(
+                            format!(
+                                "{}GhostVault is using a system ghostd install.\nPlease update it with your package manager.",
+                                header
+                            )
+                            .as_str(),
+                        ),
+                        _ => escape(format!("Error: {}", e).as_str()),
+                    };
+                    bot.send_message(msg.chat.id, message).await?;
+                    return Ok(());
+                }
+            };
 
-                let pending_rewards: PendingRewards = serde_json::from_value(cli_value).unwrap();
+            let sent_message = match update {
+                DaemonUpdate::Deferred(window) => {
+                    let update_markup = InlineKeyboardMarkup::default().append_row(vec![
+                        InlineKeyboardButton::callback("Update now", "force_update"),
+                        InlineKeyboardButton::callback("Cancel", "cancel_resync"),
+                    ]);
 
-                let pending_rewards: String =
-                    serde_json::to_string_pretty(&pending_rewards).unwrap();
-                let code_block: String = format!("\n```\n{}\n```\n", pending_rewards);
+                    let message = escape(
+                    format!(
+                        "{}New update found!\nGhostd will be updated to version {} in the maintenance window, {:02}:00 to {:02}:00 {}.",
+                        header,
+                        window.deferred_update.unwrap_or_default(),
+                        window.start,
+                        window.end,
+                        window.timezone
+                    )
+                    .as_str(),
+                );
+                    bot.send_message(msg.chat.id, message)
+                        .reply_markup(update_markup)
+                        .await?
+                }
+                DaemonUpdate::Pending(pending) => {
+                    let confirm_markup = InlineKeyboardMarkup::default().append_row(vec![
+                        InlineKeyboardButton::callback(
+                            "Confirm",
+                            format!("confirm_update:{}", pending.token),
+                        ),
+                        InlineKeyboardButton::callback("Cancel", "cancel_resync"),
+                    ]);
 
-                let message = format!("{}{}", header, code_block);
+                    let message = escape(
+                        format!(
+                            "{}New update found!\nConfirm to update ghostd to version: {}",
+                            header, pending.version
+                        )
+                        .as_str(),
+                    );
+                    bot.send_message(msg.chat.id, message)
+                        .reply_markup(confirm_markup)
+                        .await?
+                }
+                DaemonUpdate::Updating(new_version) => {
+                    let message = escape(
+                        format!(
+                            "{}New update found!\nUpdating ghostd to version: {}",
+                            header, new_version
+                        )
+                        .as_str(),
+                    );
+                    bot.send_message(msg.chat.id, message).await?
+                }
+                DaemonUpdate::UpToDate(_) => {
+                    let message =
+                        escape(format!("{}Ghostd is already up to date", header).as_str());
+                    bot.send_message(msg.chat.id, message).await?
+                }
+            };
 
-                bot.send_message(msg.chat.id, message).await?
-            }
+            sent_message
         }
+        cmd if matches_command("reward_options", cmd) => {
+            let keyboard = make_keyboard_reward_options();
 
-        cmd if cmd.starts_with("\u{1F4E5} recovery") => {
-            let cli_res = cli_caller.call_get_mnemonic().await;
+            let cli_res = cli_caller.call_get_reward_options().await;
 
             let cli_value = match cli_res {
                 Ok(resp) => resp,
@@ -724,734 +1142,2971 @@ async fn command_handler(
                 }
             };
 
-            let cold_recovery = if cli_value.is_string() {
-                Some(cli_value.as_str().unwrap().to_string())
-            } else {
-                None
-            };
+            let reward_options: String = serde_json::to_string_pretty(&cli_value).unwrap();
+            let code_block: String = format!("\n```\n{}\n```\n", reward_options);
+            let header: String = escape("👻 Reward Options 👻\n\n");
+            let choose_opt: String = escape("\nPlease choose an option");
 
-            let mnemonic = cold_recovery.unwrap_or("Mnemonic not found.".to_string());
+            let explainer = escape(concat!(
+                "reward_address: The address that rewards are sent to. This will be blank in DEFAULT mode.\n",
+                "reward_interval: This is how often GhostVault will check if rewards can be sent. Only applies to ANON mode.\n",
+                "reward_min: Available rewards must be this much before they are sent. Only applies to ANON mode.\n",
+                "reward_mode: This is the reward mode that GhostVault is in.\n\n",
+            ));
 
-            let header = escape("👻 Recovery Mnemonic 👻\n");
-            let message = escape(format!("Your recovery mnemonic is\n\n").as_str());
-            let code_block = format!("`{}`", mnemonic);
-            let reply = format!("{}{}{}", header, message, code_block);
+            let send_message: String =
+                format!("{}{}{}{}", header, explainer, code_block, choose_opt);
 
-            bot.send_message(msg.chat.id, reply).await?
-        }
-        _ => {
-            return Ok(());
+            bot.send_message(msg.chat.id, send_message)
+                .reply_markup(keyboard)
+                .await?
         }
-    };
-
-    Ok(())
-}
+        cmd if matches_command("set_reward_mode", cmd) => {
+            if server_ready.daemon_ready && server_ready.ready {
+                let keyboard = make_reward_mode_keyboard();
 
-async fn callback_handler(
-    bot: DefaultParseMode<Bot>,
-    q: CallbackQuery,
-    gv_config: Arc<async_RwLock<GVConfig>>,
-    _db: Arc<GVDB>,
-    reward_mode_mem: Arc<InMemStorage<UpdateRewardModeState>>,
-    last_dialog_id: Arc<AtomicI32>,
-    reward_interval_mem: Arc<InMemStorage<UpdateRewardIntervalState>>,
-    reward_min_mem: Arc<InMemStorage<UpdateRewardMinState>>,
-    chart_range_mem: Arc<InMemStorage<GetDateRangeState>>,
-) -> ResponseResult<()> {
-    if let Some(data) = q.clone().data {
-        match data.as_str() {
-            "confirm_resync" => {
-                let conf = gv_config.read().await;
-                let cli_address = conf.to_owned().cli_address;
-                let user = conf.to_owned().tg_user.unwrap();
-                drop(conf);
+                if last_dialog_id.load(Ordering::Relaxed) != 0 {
+                    return Ok(());
+                }
 
-                let cli_caller_res = CLICaller::new(&cli_address, true).await;
+                let new_msg = bot
+                    .send_message(msg.chat.id, "👻 Reward Mode Updater 👻")
+                    .reply_markup(keyboard)
+                    .await?;
 
-                let cli_caller = match cli_caller_res {
-                    Ok(cli) => cli,
-                    Err(e) => {
-                        let message = escape(format!("Error: {}", e).as_str());
-                        bot.send_message(user, message).await?;
-                        return Ok(());
-                    }
-                };
+                let new_id: i32 = new_msg.id.to_string().parse::<i32>().unwrap();
+                last_dialog_id.store(new_id, Ordering::Relaxed);
 
-                let cli_res = cli_caller.call_force_resync().await;
+                reward_update_dialogue
+                    .update(UpdateRewardModeState::Start)
+                    .await
+                    .unwrap();
 
-                match cli_res {
-                    Ok(_) => {
-                        bot.answer_callback_query(q.id).await?;
-                        bot.delete_message(user.clone(), q.message.unwrap().id)
-                            .await?;
+                start_update_reward_mode(
+                    bot.clone(),
+                    reward_update_dialogue.clone(),
+                    msg.clone(),
+                    last_dialog_id.clone(),
+                )
+                .await
+                .unwrap();
+            } else {
+                let reason = server_unready_message(&server_ready);
 
-                        let message = escape("Resync operation started\nThis will take a while.");
+                let message = escape("Ghost daemon unavailable.\nReason:");
+                let reasoned_message = format!("{}{}", message, reason);
 
-                        bot.send_message(user.clone(), message).await?;
-                    }
-                    Err(e) => {
-                        let message = escape(format!("Error: {}", e).as_str());
-                        bot.send_message(user, message).await?;
-                    }
-                }
-            }
-            "cancel_resync" => {
-                let conf = gv_config.read().await;
-                let user = conf.to_owned().tg_user.unwrap();
-                drop(conf);
-                bot.answer_callback_query(q.id).await?;
-                bot.delete_message(user, q.message.unwrap().id).await?;
+                bot.send_message(msg.chat.id, reasoned_message).await?;
             }
-            "cancel_update_reward_mode" => {
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
-                let dialogue = UpdateRewardModeDialog::new(reward_mode_mem, chat_id);
-                let current_dialog = dialogue.get().await.unwrap();
 
-                bot.answer_callback_query(q.id).await?;
+            return Ok(());
+        }
 
-                let keyboard = make_keyboard_gv_options();
+        cmd if matches_command("set_reward_interval", cmd) => {
+            if server_ready.daemon_ready && server_ready.ready {
+                let keyboard = make_reward_interval_keyboard();
 
-                if !current_dialog.is_none() {
-                    dialogue.exit().await.unwrap();
+                if last_dialog_id.load(Ordering::Relaxed) != 0 {
+                    return Ok(());
                 }
 
-                bot.send_message(chat_id, "Cancelled")
+                let new_msg = bot
+                    .send_message(msg.chat.id, "👻 Reward Interval Updater 👻")
                     .reply_markup(keyboard)
                     .await?;
-                last_dialog_id.store(0, Ordering::Relaxed);
-                bot.delete_message(chat_id, msg_id).await?;
-            }
 
-            "cancel_update_reward_interval" => {
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
-                let dialogue = UpdateRewardIntervalDialog::new(reward_interval_mem, chat_id);
-                let current_dialog = dialogue.get().await.unwrap();
+                let new_id: i32 = new_msg.id.to_string().parse::<i32>().unwrap();
+                last_dialog_id.store(new_id, Ordering::Relaxed);
 
-                bot.answer_callback_query(q.id).await?;
+                reward_interval_dialogue
+                    .update(UpdateRewardIntervalState::Start)
+                    .await
+                    .unwrap();
 
-                let keyboard = make_keyboard_gv_options();
+                start_update_reward_interval(
+                    bot.clone(),
+                    reward_interval_dialogue.clone(),
+                    msg.clone(),
+                    last_dialog_id.clone(),
+                )
+                .await
+                .unwrap();
+            } else {
+                let reason = server_unready_message(&server_ready);
 
-                if !current_dialog.is_none() {
-                    dialogue.exit().await.unwrap();
-                }
-                bot.send_message(chat_id, "Cancelled")
-                    .reply_markup(keyboard)
-                    .await?;
-                last_dialog_id.store(0, Ordering::Relaxed);
-                bot.delete_message(chat_id, msg_id).await?;
-            }
-            "cancel_update_reward_min" => {
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
-                let dialogue = UpdateRewardMinDialog::new(reward_min_mem, chat_id);
-                let current_dialog = dialogue.get().await.unwrap();
+                let message = escape("Ghost daemon unavailable.\nReason:");
+                let reasoned_message = format!("{}{}", message, reason);
 
-                bot.answer_callback_query(q.id).await?;
+                bot.send_message(msg.chat.id, reasoned_message).await?;
+            }
 
-                let keyboard = make_keyboard_gv_options();
+            return Ok(());
+        }
 
-                if !current_dialog.is_none() {
-                    dialogue.exit().await.unwrap();
+        cmd if matches_command("set_payout_min", cmd) => {
+            if server_ready.daemon_ready && server_ready.ready {
+                if last_dialog_id.load(Ordering::Relaxed) != 0 {
+                    return Ok(());
                 }
-                bot.send_message(chat_id, "Cancelled")
-                    .reply_markup(keyboard)
+
+                let new_msg = bot
+                    .send_message(msg.chat.id, "👻 Payout Min Updater 👻")
                     .await?;
-                last_dialog_id.store(0, Ordering::Relaxed);
-                bot.delete_message(chat_id, msg_id).await?;
-            }
-            btn_press if btn_press.starts_with("next_month") => {
-                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
-                let month: u32 = split_msg[1].parse::<u32>().unwrap();
-                let year: i32 = split_msg[2].parse::<i32>().unwrap();
 
-                let year_month: (i32, u32) = if month == 12 {
-                    (year + 1, 1)
-                } else {
-                    (year, month + 1)
-                };
+                let new_id: i32 = new_msg.id.to_string().parse::<i32>().unwrap();
+                last_dialog_id.store(new_id, Ordering::Relaxed);
 
-                let conf = gv_config.read().await;
-                let timezone = conf.to_owned().timezone;
+                reward_min_dialogue
+                    .update(UpdateRewardMinState::Start)
+                    .await
+                    .unwrap();
 
-                let kb = make_inline_calander(year_month.0, year_month.1, &timezone);
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+                start_update_reward_min(
+                    bot.clone(),
+                    reward_min_dialogue.clone(),
+                    msg.clone(),
+                    last_dialog_id.clone(),
+                )
+                .await
+                .unwrap();
+            } else {
+                let reason = server_unready_message(&server_ready);
 
-                let _ = bot
-                    .edit_message_reply_markup(chat_id, msg_id)
-                    .reply_markup(kb)
-                    .await;
+                let message = escape("Ghost daemon unavailable.\nReason:");
+                let reasoned_message = format!("{}{}", message, reason);
+
+                bot.send_message(msg.chat.id, reasoned_message).await?;
             }
-            btn_press if btn_press.starts_with("prev_month") => {
-                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
-                let month: u32 = split_msg[1].parse::<u32>().unwrap();
-                let year: i32 = split_msg[2].parse::<i32>().unwrap();
+            return Ok(());
+        }
 
-                let year_month: (i32, u32) = if month == 1 {
-                    (year - 1, 12)
-                } else {
-                    (year, month - 1)
-                };
+        cmd if matches_command("set_timezone", cmd) => {
+            let message = escape("👻 Timezone Updater 👻\n\nPlease select your region.");
 
-                let conf = gv_config.read().await;
-                let timezone = conf.to_owned().timezone;
+            let kb = make_timezone_region_keyboard();
 
-                let kb = make_inline_calander(year_month.0, year_month.1, &timezone);
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+            bot.send_message(msg.chat.id, message)
+                .reply_markup(kb)
+                .await?
+        }
 
-                let _ = bot
-                    .edit_message_reply_markup(chat_id, msg_id)
-                    .reply_markup(kb)
-                    .await;
-            }
-            btn_press if btn_press.starts_with("date_selection") => {
-                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
-                let day: u32 = split_msg[1].parse::<u32>().unwrap();
-                let month: u32 = split_msg[2].parse::<u32>().unwrap();
-                let year: i32 = split_msg[3].parse::<i32>().unwrap();
-                let q_clone = q.clone();
+        cmd if matches_command("charts", cmd) => {
+            let kb = make_inline_chart_menu();
 
-                bot.answer_callback_query(q.id).await?;
+            let message = escape("👻 Charts 👻\n\nPlease select a chart type");
 
-                let chart_range_dialogue: Dialogue<
-                    GetDateRangeState,
-                    InMemStorage<GetDateRangeState>,
-                > = GetDateRangeDialog::new(
-                    chart_range_mem.clone(),
-                    q.message.as_ref().unwrap().chat.id,
-                );
+            bot.send_message(msg.chat.id, message)
+                .reply_markup(kb)
+                .await?
+        }
 
-                let chart_range_state = chart_range_dialogue.get().await.unwrap();
+        cmd if matches_command("roi", cmd) => {
+            if server_ready.daemon_ready && server_ready.ready {
+                let cli_res = cli_caller.call_get_roi().await;
 
-                let conf = gv_config.read().await;
-                let time_zone = conf.to_owned().timezone;
-                let tz = Tz::from_str_insensitive(&time_zone).unwrap();
+                let roi: StakingRoi = match cli_res {
+                    Ok(resp) => resp,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(msg.chat.id, message).await?;
+                        return Ok(());
+                    }
+                };
 
-                drop(conf);
+                let header: String = escape(format!("{}👻 Staking ROI 👻\n\n", prefix).as_str());
 
-                let timestamp: u64 = tz
-                    .with_ymd_and_hms(year, month, day, 0, 0, 0)
-                    .unwrap()
-                    .timestamp() as u64;
+                let roi_json: String = serde_json::to_string_pretty(&roi).unwrap();
+                let code_block: String = format!("```\n{}\n```\n", roi_json);
 
-                if chart_range_state.is_some() {
-                    match chart_range_state {
-                        Some(GetDateRangeState::ReceiveFirstDate {
-                            division,
-                            time_zone,
-                            chart_type,
-                        }) => {
-                            receive_first_date(
-                                bot.clone(),
-                                chart_range_dialogue.clone(),
-                                &q_clone,
-                                timestamp,
-                                division,
-                                &time_zone,
-                                chart_type,
-                            )
-                            .await
-                            .unwrap();
-                        }
-                        Some(GetDateRangeState::ReceiveSecondDate {
-                            first_date,
-                            division,
-                            time_zone: _,
-                            chart_type,
-                        }) => {
-                            let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                            let msg_id = q.message.as_ref().unwrap().id;
-                            let end_time = NaiveDate::from_ymd_opt(year, month, day)
-                                .unwrap()
-                                .and_hms_opt(23, 59, 59)
-                                .unwrap()
-                                .and_local_timezone(tz)
-                                .unwrap()
-                                .timestamp() as u64;
-                            if end_time <= first_date {
-                                let message = escape("End date must be after start date");
-                                bot.edit_message_text(chat_id, msg_id, message).await?;
-                                chart_range_dialogue.exit().await.unwrap();
-                                return Ok(());
-                            }
+                let message: String = format!("{}{}", header, code_block);
 
-                            let chart_range = (first_date, end_time);
-                            chart_range_dialogue.exit().await.unwrap();
+                bot.send_message(msg.chat.id, message).await?
+            } else {
+                let reason = server_unready_message(&server_ready);
 
-                            let _ = bot.delete_message(chat_id, msg_id).await;
+                let message = escape("Ghost daemon unavailable.\nReason:");
 
-                            if chart_type == "earnings_chart" {
-                                send_earnings_chart(chart_range, &bot, &q_clone, gv_config).await?;
-                            } else {
-                                send_barchart(chart_range, &bot, &q_clone, gv_config, &division)
-                                    .await?;
-                            }
-                        }
-                        _ => {}
+                let reasoned_message = format!("{}{}", message, reason);
+
+                bot.send_message(msg.chat.id, reasoned_message).await?
+            }
+        }
+
+        cmd if matches_command("delegations", cmd) => {
+            if server_ready.daemon_ready && server_ready.ready {
+                match get_delegations(&cli_caller).await {
+                    Ok(delegations) => {
+                        let message = escape(
+                            format!("{}{}", prefix, format_delegations(&delegations)).as_str(),
+                        );
+                        bot.send_message(msg.chat.id, message).await?
+                    }
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(msg.chat.id, message).await?
                     }
                 }
-            }
-            btn_press if btn_press.starts_with("current_date") => {
-                let conf = gv_config.read().await;
-                let timezone = conf.to_owned().timezone;
-                let current_ymd = get_current_month_year_day(&timezone);
-                let kb = make_inline_calander(current_ymd.0, current_ymd.1, &timezone);
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+            } else {
+                let reason = server_unready_message(&server_ready);
 
-                let _ = bot
-                    .edit_message_reply_markup(chat_id, msg_id)
-                    .reply_markup(kb)
-                    .await;
-            }
-            btn_press if btn_press.starts_with("tz_region_selection") => {
-                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
-                let region: &str = split_msg[1];
+                let message = escape("Ghost daemon unavailable.\nReason:");
 
-                if region == "UTC" {
-                    let conf = gv_config.read().await;
-                    let cli_address = conf.to_owned().cli_address;
-                    drop(conf);
+                let reasoned_message = format!("{}{}", message, reason);
 
-                    let cli_caller_res = CLICaller::new(&cli_address, true).await;
+                bot.send_message(msg.chat.id, reasoned_message).await?
+            }
+        }
 
-                    let cli_caller = match cli_caller_res {
-                        Ok(cli) => cli,
-                        Err(e) => {
-                            let message = escape(format!("Error: {}", e).as_str());
-                            bot.send_message(q.message.as_ref().unwrap().chat.id, message)
-                                .await?;
-                            return Ok(());
-                        }
-                    };
+        cmd if matches_command("simulate_rewards", cmd) => {
+            let kb = make_inline_simulate_rewards_menu();
 
-                    let cli_res = cli_caller.call_set_timezone("UTC".to_string()).await;
+            let message = escape(concat!(
+                "👻 Reward Simulation 👻\n\n",
+                "See what your past stakes would have paid under each reward mode.\n",
+                "Please select a range"
+            ));
 
-                    match cli_res {
-                        Ok(_) => {}
-                        Err(e) => {
-                            let message = escape(format!("Error: {}", e).as_str());
-                            bot.send_message(q.message.as_ref().unwrap().chat.id, message)
-                                .await?;
-                        }
+            bot.send_message(msg.chat.id, message)
+                .reply_markup(kb)
+                .await?
+        }
+
+        cmd if matches_command("payout_history", cmd) => {
+            let kb = make_inline_payout_history_menu();
+
+            let message = escape("👻 Payout History 👻\n\nPlease select a range");
+
+            bot.send_message(msg.chat.id, message)
+                .reply_markup(kb)
+                .await?
+        }
+
+        cmd if matches_command("overview", cmd) => {
+            if server_ready.daemon_ready && server_ready.ready {
+                let cli_res = cli_caller.call_get_overview().await;
+
+                let staking_data: StakingDataOverview = match cli_res {
+                    Ok(resp) => resp,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(msg.chat.id, message).await?;
+                        return Ok(());
                     }
+                };
 
-                    let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                    let msg_id = q.message.as_ref().unwrap().id;
+                let header: String = escape(format!("{}👻 Overview 👻\n\n", prefix).as_str());
 
-                    let kb = make_keyboard_main();
+                let overview: String = serde_json::to_string_pretty(&staking_data).unwrap();
+                let code_block: String = format!("```\n{}\n```\n", overview);
 
-                    bot.send_message(chat_id, "Timezone set to UTC")
-                        .reply_markup(kb)
-                        .await?;
+                let message: String = format!("{}{}", header, code_block);
 
-                    last_dialog_id.store(0, Ordering::Relaxed);
-                    bot.delete_message(chat_id, msg_id).await?;
-                } else {
-                    let kb = make_timezone_option_keyboard(region, 1).unwrap();
+                bot.send_message(msg.chat.id, message).await?
+            } else {
+                let reason = server_unready_message(&server_ready);
 
-                    let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                    let msg_id = q.message.as_ref().unwrap().id;
+                let message = escape("Ghost daemon unavailable.\nReason:");
 
-                    let message = escape(
-                        format!(
-                            "👻 Timezone Updater 👻\n\nSelected region: {}\nPlease select a City.",
-                            region
-                        )
-                        .as_str(),
-                    );
+                let reasoned_message = format!("{}{}", message, reason);
 
-                    bot.edit_message_text(chat_id, msg_id, message)
-                        .reply_markup(kb)
-                        .await?;
-                }
+                bot.send_message(msg.chat.id, reasoned_message).await?
             }
-            btn_press if btn_press.starts_with("tz_selection") => {
-                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
-                let region: &str = split_msg[1];
-                let city = split_msg[2];
+        }
 
-                let tz = format!("{}/{}", region, city);
+        cmd if matches_command("pending_rewards", cmd) => {
+            if !server_ready.daemon_ready || !server_ready.ready {
+                let reason = server_unready_message(&server_ready);
+
+                let message = escape("Ghost daemon unavailable.\nReason:");
+
+                let reasoned_message = format!("{}{}", message, reason);
+
+                bot.send_message(msg.chat.id, reasoned_message).await?
+            } else {
+                let cli_res = cli_caller.call_get_pending_rewards().await;
+
+                let pending_rewards: PendingRewards = match cli_res {
+                    Ok(resp) => resp,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(msg.chat.id, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                let header = escape(format!("{}👻 Pending Rewards 👻\n\n", prefix).as_str());
+
+                let pending_rewards: String =
+                    serde_json::to_string_pretty(&pending_rewards).unwrap();
+                let code_block: String = format!("\n```\n{}\n```\n", pending_rewards);
+
+                // The pending rewards are still worth showing if this fails
+                let anon_block: String = match cli_caller.call_get_anonymization_status().await {
+                    Ok(status) => format!(
+                        "{}\n```\n{}\n```\n",
+                        escape("\n👻 Anonymization 👻\n"),
+                        format_anonymization_status(&status)
+                    ),
+                    Err(_) => String::new(),
+                };
+
+                let message = format!("{}{}{}", header, code_block, anon_block);
+
+                bot.send_message(msg.chat.id, message).await?
+            }
+        }
+
+        cmd if matches_command("unlock_wallet", cmd) => {
+            let message = escape(UNLOCK_WALLET_PROMPT);
+
+            bot.send_message(msg.chat.id, message)
+                .reply_markup(ForceReply::new())
+                .await?
+        }
+
+        cmd if matches_command("recovery", cmd) => {
+            let cli_res = cli_caller.call_get_mnemonic(String::new()).await;
+
+            let cli_value = match cli_res {
+                Ok(resp) => resp,
+                Err(e) => {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(msg.chat.id, message).await?;
+                    return Ok(());
+                }
+            };
+
+            let mnemonic = cli_value.unwrap_or("Mnemonic not found.".to_string());
+
+            let header = escape("👻 Recovery Mnemonic 👻\n");
+            let message = escape(format!("Your recovery mnemonic is\n\n").as_str());
+            let code_block = format!("`{}`", mnemonic);
+            let reply = format!("{}{}{}", header, message, code_block);
+
+            bot.send_message(msg.chat.id, reply).await?
+        }
+        _ => {
+            return Ok(());
+        }
+    };
+
+    Ok(())
+}
 
+async fn callback_handler(
+    bot: DefaultParseMode<Bot>,
+    q: CallbackQuery,
+    gv_config: Arc<async_RwLock<GVConfig>>,
+    db: Arc<GVDB>,
+    last_dialog_id: Arc<AtomicI32>,
+    dialogs: DialogStorage,
+) -> ResponseResult<()> {
+    if let Some(data) = q.clone().data {
+        match data.as_str() {
+            btn_press if btn_press.starts_with("confirm_resync") => {
                 let conf = gv_config.read().await;
-                let cli_address = conf.to_owned().cli_address;
+                let cli_address = selected_vault(&conf).cli_address;
+                let user = conf.to_owned().tg_user.unwrap();
                 drop(conf);
 
-                let cli_caller_res = CLICaller::new(&cli_address, true).await;
+                let cli_caller_res = bot_caller(&cli_address).await;
 
                 let cli_caller = match cli_caller_res {
                     Ok(cli) => cli,
                     Err(e) => {
                         let message = escape(format!("Error: {}", e).as_str());
-                        bot.send_message(q.message.as_ref().unwrap().chat.id, message)
-                            .await?;
+                        bot.send_message(user, message).await?;
                         return Ok(());
                     }
                 };
 
-                let cli_res = cli_caller.call_set_timezone(tz.clone()).await;
+                let force: bool = btn_press == "confirm_resync:now";
+                let cli_res = cli_caller.call_force_resync(force).await;
 
                 match cli_res {
-                    Ok(_) => {}
+                    Ok(resp) => {
+                        bot.answer_callback_query(q.id).await?;
+                        bot.delete_message(user.clone(), q.message.unwrap().id)
+                            .await?;
+
+                        let message = if force {
+                            escape("Resync operation started\nThis will take a while.")
+                        } else {
+                            escape(if resp.is_empty() {
+                                "Resync scheduled."
+                            } else {
+                                resp.as_str()
+                            })
+                        };
+
+                        bot.send_message(user.clone(), message).await?;
+                    }
                     Err(e) => {
                         let message = escape(format!("Error: {}", e).as_str());
-                        bot.send_message(q.message.as_ref().unwrap().chat.id, message)
-                            .await?;
+                        bot.send_message(user, message).await?;
                     }
                 }
+            }
+            "force_update" => {
+                let conf = gv_config.read().await;
+                let cli_address = selected_vault(&conf).cli_address;
+                let user = conf.to_owned().tg_user.unwrap();
+                drop(conf);
 
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+                let cli_caller = match bot_caller(&cli_address).await {
+                    Ok(cli) => cli,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
 
-                let kb = make_keyboard_main();
+                bot.answer_callback_query(q.id).await?;
+                bot.delete_message(user.clone(), q.message.unwrap().id)
+                    .await?;
 
-                let message = escape(format!("Timezone set to {}", tz).as_str());
+                let header = escape("👻 Ghostd Update 👻\n\n");
 
-                bot.send_message(chat_id, message).reply_markup(kb).await?;
+                let message = match cli_caller.call_process_daemon_update(true).await {
+                    Ok(DaemonUpdate::Updating(new_version)) => escape(
+                        format!("{}Updating ghostd to version: {}", header, new_version).as_str(),
+                    ),
+                    Ok(_) => escape(format!("{}Ghostd is already up to date", header).as_str()),
+                    Err(e) => escape(format!("Error: {}", e).as_str()),
+                };
 
-                last_dialog_id.store(0, Ordering::Relaxed);
-                bot.delete_message(chat_id, msg_id).await?;
+                bot.send_message(user, message).await?;
             }
-            btn_press if btn_press.starts_with("tz_page_back") => {
-                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
-                let region: &str = split_msg[1];
-
-                let page = split_msg[2].parse::<u8>().unwrap();
+            btn_press if btn_press.starts_with("maint_") => {
+                let conf = gv_config.read().await;
+                let cli_address = selected_vault(&conf).cli_address;
+                let user = conf.to_owned().tg_user.unwrap();
+                drop(conf);
 
-                let kb = if page == 1 {
-                    make_timezone_region_keyboard()
-                } else {
-                    make_timezone_option_keyboard(region, page - 1).unwrap()
+                let cli_caller = match bot_caller(&cli_address).await {
+                    Ok(cli) => cli,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
                 };
 
+                bot.answer_callback_query(q.id).await?;
+
                 let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id: MessageId = q.message.as_ref().unwrap().id;
+                let msg_id = q.message.as_ref().unwrap().id;
 
-                let message = escape(
-                    format!(
-                        "👻 Timezone Updater 👻\n\nSelected region: {}\nPlease select a City.",
-                        region
-                    )
-                    .as_str(),
-                );
+                let window: MaintenanceWindow = match get_maintenance_window(&cli_caller).await {
+                    Ok(window) => window,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                // maint_start:HOUR and maint_end:HOUR move one end of the window.
+                let (start, end) = match btn_press.split_once(':') {
+                    Some(("maint_start", hour)) => match hour.parse::<u32>() {
+                        Ok(hour) => (hour, window.end),
+                        Err(_) => return Ok(()),
+                    },
+                    Some(("maint_end", hour)) => match hour.parse::<u32>() {
+                        Ok(hour) => (window.start, hour),
+                        Err(_) => return Ok(()),
+                    },
+                    _ => return Ok(()),
+                };
+
+                if let Err(e) = cli_caller.call_set_maintenance_window(start, end).await {
+                    let message = escape(format!("Error: {}", e).as_str());
+                    bot.send_message(user, message).await?;
+                    return Ok(());
+                }
+
+                let window: MaintenanceWindow =
+                    get_maintenance_window(&cli_caller).await.unwrap_or(window);
+
+                let message = escape(format_maintenance_window(&window).as_str());
 
                 bot.edit_message_text(chat_id, msg_id, message)
-                    .reply_markup(kb)
+                    .reply_markup(make_inline_maintenance_menu(&window))
                     .await?;
             }
-            btn_press if btn_press.starts_with("tz_page_next") => {
-                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
-                let region: &str = split_msg[1];
+            btn_press if btn_press.starts_with("sched_") => {
+                let conf = gv_config.read().await;
+                let cli_address = selected_vault(&conf).cli_address;
+                let user = conf.to_owned().tg_user.unwrap();
+                drop(conf);
 
-                let page = split_msg[2].parse::<u8>().unwrap();
+                let cli_caller = match bot_caller(&cli_address).await {
+                    Ok(cli) => cli,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
 
-                let kb = make_timezone_option_keyboard(region, page + 1).unwrap();
+                bot.answer_callback_query(q.id).await?;
 
                 let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
                 let msg_id = q.message.as_ref().unwrap().id;
 
-                let message = escape(
-                    format!(
-                        "👻 Timezone Updater 👻\n\nSelected region: {}\nPlease select a City.",
-                        region
-                    )
-                    .as_str(),
-                );
+                // sched_run:TASK runs a task now, sched_pause:TASK and sched_resume:TASK hold it.
+                let update_res = match btn_press.split_once(':') {
+                    Some(("sched_run", task)) => {
+                        cli_caller.call_run_task_now(task.to_string()).await
+                    }
+                    Some(("sched_pause", task)) => {
+                        cli_caller.call_pause_task(task.to_string(), true).await
+                    }
+                    Some(("sched_resume", task)) => {
+                        cli_caller.call_pause_task(task.to_string(), false).await
+                    }
+                    _ => return Ok(()),
+                };
+
+                let header: String = match update_res {
+                    Ok(resp) => resp,
+                    Err(e) => format!("Error: {}", e),
+                };
+
+                let tasks: Vec<ScheduledTask> = match cli_caller.call_get_scheduled_tasks().await {
+                    Ok(tasks) => tasks,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                let message =
+                    escape(format!("{}\n\n{}", header, format_scheduled_tasks(&tasks)).as_str());
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(make_inline_tasks_menu(&tasks))
+                    .await?;
+            }
+            btn_press if btn_press.starts_with("quiet_") => {
+                let conf = gv_config.read().await;
+                let local_address: String = conf.cli_address.clone();
+                let user = conf.to_owned().tg_user.unwrap();
+                drop(conf);
+
+                let local_caller = match bot_caller(&local_address).await {
+                    Ok(cli) => cli,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                bot.answer_callback_query(q.id).await?;
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                // quiet_dest:DEST opens the presets, quiet_set:DEST:HOURS sets them.
+                if let Some(destination) = btn_press.strip_prefix("quiet_dest:") {
+                    let message = escape(
+                        format!(
+                            "Quiet hours for {}\n\nPick a window in local time, or use gv-cli for others.",
+                            destination
+                        )
+                        .as_str(),
+                    );
+
+                    bot.edit_message_text(chat_id, msg_id, message)
+                        .reply_markup(make_inline_quiet_hours_presets(destination))
+                        .await?;
+                    return Ok(());
+                }
+
+                let header: String = match btn_press
+                    .strip_prefix("quiet_set:")
+                    .and_then(|rest| rest.split_once(':'))
+                {
+                    Some((destination, hours)) => match local_caller
+                        .call_set_quiet_hours(destination.to_string(), hours.to_string())
+                        .await
+                    {
+                        Ok(resp) => format!("{}\n\n", resp),
+                        Err(e) => format!("Error: {}\n\n", e),
+                    },
+                    None => String::new(),
+                };
+
+                let quiet_hours: Vec<QuietHours> = match local_caller.call_get_quiet_hours().await {
+                    Ok(quiet_hours) => quiet_hours,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                let message =
+                    escape(format!("{}{}", header, format_quiet_hours(&quiet_hours)).as_str());
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(make_inline_quiet_hours_menu(&quiet_hours))
+                    .await?;
+            }
+            btn_press if btn_press.starts_with("confirm_update:") => {
+                let conf = gv_config.read().await;
+                let cli_address = selected_vault(&conf).cli_address;
+                let user = conf.to_owned().tg_user.unwrap();
+                drop(conf);
+
+                let token: String = btn_press
+                    .strip_prefix("confirm_update:")
+                    .unwrap_or("")
+                    .to_string();
+
+                let cli_caller = match bot_caller(&cli_address).await {
+                    Ok(cli) => cli,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                bot.answer_callback_query(q.id).await?;
+                bot.delete_message(user.clone(), q.message.unwrap().id)
+                    .await?;
+
+                let message = match cli_caller.call_confirm_update(token).await {
+                    Ok(resp) => escape(format!("Updating ghostd to version: {}", resp).as_str()),
+                    Err(e) => escape(format!("Error: {}", e).as_str()),
+                };
+
+                bot.send_message(user, message).await?;
+            }
+            btn_press if btn_press.starts_with("confirm_split:") => {
+                let (index, token) = btn_press
+                    .strip_prefix("confirm_split:")
+                    .and_then(|rest| rest.split_once(':'))
+                    .unwrap_or(("0", ""));
+                let index: usize = index.parse::<usize>().unwrap_or(0);
+
+                let conf = gv_config.read().await;
+                let vault: FleetVault = vault_at(&conf, index);
+                let prefix: String = vault_prefix(&conf, &vault.name);
+                let user = conf.to_owned().tg_user.unwrap();
+                drop(conf);
+
+                let cli_caller = match bot_caller(&vault.cli_address).await {
+                    Ok(cli) => cli,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                bot.answer_callback_query(q.id).await?;
+                bot.delete_message(user.clone(), q.message.unwrap().id)
+                    .await?;
+
+                let message = match cli_caller.call_confirm_split(token.to_string()).await {
+                    Ok(txids) => escape(
+                        format!(
+                            "{}Split {} output(s):\n{}",
+                            prefix,
+                            txids.len(),
+                            txids.join("\n")
+                        )
+                        .as_str(),
+                    ),
+                    Err(e) => escape(format!("{}Error: {}", prefix, e).as_str()),
+                };
+
+                bot.send_message(user, message).await?;
+            }
+            btn_press if btn_press.starts_with("cancel_reward_change:") => {
+                let index: usize = btn_press
+                    .strip_prefix("cancel_reward_change:")
+                    .and_then(|index| index.parse::<usize>().ok())
+                    .unwrap_or(0);
+
+                let conf = gv_config.read().await;
+                let vault: FleetVault = vault_at(&conf, index);
+                let prefix: String = vault_prefix(&conf, &vault.name);
+                let user = conf.to_owned().tg_user.unwrap();
+                drop(conf);
+
+                let cli_caller = match bot_caller(&vault.cli_address).await {
+                    Ok(cli) => cli,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                bot.answer_callback_query(q.id).await?;
+                bot.delete_message(user.clone(), q.message.unwrap().id)
+                    .await?;
+
+                let message = match cli_caller.call_cancel_reward_change().await {
+                    Ok(resp) => escape(format!("{}{}", prefix, resp).as_str()),
+                    Err(e) => escape(format!("{}Error: {}", prefix, e).as_str()),
+                };
+
+                bot.send_message(user, message).await?;
+            }
+            btn_press if btn_press.starts_with("chain_emergency:") => {
+                let (action, index) = btn_press
+                    .strip_prefix("chain_emergency:")
+                    .and_then(|rest| rest.split_once(':'))
+                    .unwrap_or(("", "0"));
+                let index: usize = index.parse::<usize>().unwrap_or(0);
+
+                let conf = gv_config.read().await;
+                let vault: FleetVault = vault_at(&conf, index);
+                let prefix: String = vault_prefix(&conf, &vault.name);
+                let user = conf.to_owned().tg_user.unwrap();
+                drop(conf);
+
+                let cli_caller = match bot_caller(&vault.cli_address).await {
+                    Ok(cli) => cli,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                bot.answer_callback_query(q.id).await?;
+
+                let message = match cli_caller
+                    .call_chain_emergency_action(action.to_string())
+                    .await
+                {
+                    Ok(resp) => escape(format!("{}{}", prefix, resp).as_str()),
+                    Err(e) => escape(format!("Error: {}", e).as_str()),
+                };
+
+                bot.send_message(user, message).await?;
+            }
+            btn_press if btn_press.starts_with("digest_") => {
+                let conf = gv_config.read().await;
+                let local_address: String = conf.cli_address.clone();
+                let user = conf.to_owned().tg_user.unwrap();
+                drop(conf);
+
+                let local_caller = match bot_caller(&local_address).await {
+                    Ok(cli) => cli,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                bot.answer_callback_query(q.id).await?;
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let settings: DigestSettings = match get_digest_settings(&local_caller).await {
+                    Ok(settings) => settings,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                // digest_toggle:PERIOD flips a digest, digest_hour:HOUR moves when they're sent.
+                let update_res = match btn_press.split_once(':') {
+                    Some(("digest_toggle", "daily")) => {
+                        local_caller
+                            .call_set_digest("daily".to_string(), !settings.daily)
+                            .await
+                    }
+                    Some(("digest_toggle", "weekly")) => {
+                        local_caller
+                            .call_set_digest("weekly".to_string(), !settings.weekly)
+                            .await
+                    }
+                    Some(("digest_hour", hour)) => match hour.parse::<u32>() {
+                        Ok(hour) => local_caller.call_set_digest_time(hour).await,
+                        Err(_) => return Ok(()),
+                    },
+                    _ => return Ok(()),
+                };
+
+                let header: String = match update_res {
+                    Ok(resp) => resp,
+                    Err(e) => format!("Error: {}", e),
+                };
+
+                let settings: DigestSettings =
+                    get_digest_settings(&local_caller).await.unwrap_or(settings);
+
+                let message = escape(
+                    format!(
+                        "{}\n\nSummaries of the last day or week, sent at the hour below ({}).",
+                        header, settings.timezone
+                    )
+                    .as_str(),
+                );
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(make_inline_digest_menu(&settings))
+                    .await?;
+            }
+            btn_press if btn_press.starts_with("route_") => {
+                let conf = gv_config.read().await;
+                let local_address: String = conf.cli_address.clone();
+                let user = conf.to_owned().tg_user.unwrap();
+                drop(conf);
+
+                let local_caller = match bot_caller(&local_address).await {
+                    Ok(cli) => cli,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                bot.answer_callback_query(q.id).await?;
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                // route_event:EVENT opens an event, route_toggle:EVENT:DEST flips a destination.
+                let (event, toggle) = match btn_press.split_once(':') {
+                    Some((_, rest)) => match rest.split_once(':') {
+                        Some((event, dest)) => (Some(event.to_string()), Some(dest.to_string())),
+                        None => (Some(rest.to_string()), None),
+                    },
+                    None => (None, None),
+                };
+
+                let mut header: String = String::new();
+
+                if let (Some(event), Some(toggle)) = (&event, &toggle) {
+                    let current: Vec<AnnounceDestination> = get_announce_routes(&local_caller)
+                        .await
+                        .unwrap_or_default()
+                        .into_iter()
+                        .find(|route| &route.event == event)
+                        .map(|route| route.destinations)
+                        .unwrap_or_default();
+
+                    let updated: Vec<&str> = AnnounceDestination::ALL
+                        .iter()
+                        .filter(|destination| {
+                            current.contains(destination) != (destination.as_str() == toggle)
+                        })
+                        .map(|destination| destination.as_str())
+                        .collect();
+
+                    let destinations: String = if updated.is_empty() {
+                        "none".to_string()
+                    } else {
+                        updated.join(",")
+                    };
+
+                    header = match local_caller
+                        .call_set_announce_route(event.to_string(), destinations)
+                        .await
+                    {
+                        Ok(resp) => format!("{}\n\n", resp),
+                        Err(e) => format!("Error: {}\n\n", e),
+                    };
+                }
+
+                let routes: Vec<AnnounceRoute> = match get_announce_routes(&local_caller).await {
+                    Ok(routes) => routes,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(user, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                match event.and_then(|event| routes.iter().find(|route| route.event == event)) {
+                    Some(route) => {
+                        let message =
+                            escape(format!("{}Routes for {}", header, route.event).as_str());
+
+                        bot.edit_message_text(chat_id, msg_id, message)
+                            .reply_markup(make_inline_route_menu(route))
+                            .await?;
+                    }
+                    None => {
+                        let message = escape(
+                            "Announcement Routes\n\nPick an event to change where it's sent.",
+                        );
+
+                        bot.edit_message_text(chat_id, msg_id, message)
+                            .reply_markup(make_inline_routes_menu(&routes))
+                            .await?;
+                    }
+                }
+            }
+            btn_press if btn_press.starts_with("select_vault:") => {
+                let conf = gv_config.read().await;
+                let names: Vec<String> = vault_names(&conf);
+                drop(conf);
+
+                let index: usize = btn_press
+                    .strip_prefix("select_vault:")
+                    .and_then(|index| index.parse::<usize>().ok())
+                    .filter(|index| *index < names.len())
+                    .unwrap_or(0);
+
+                *SELECTED_VAULT.write().unwrap() = index;
+
+                bot.answer_callback_query(q.id).await?;
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+                let message = escape(format!("Now managing {}", names[index]).as_str());
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(make_inline_vault_menu(&names, index))
+                    .await?;
+            }
+            "cold_wallets" => {
+                let conf = gv_config.read().await;
+                let cli_address = selected_vault(&conf).cli_address;
+                drop(conf);
+
+                bot.answer_callback_query(q.id.clone()).await?;
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                // The wallet's chart is a photo, so the list goes out as a new message
+                let _ = bot.delete_message(chat_id, msg_id).await;
+
+                let wallets_res = match bot_caller(&cli_address).await {
+                    Ok(cli_caller) => get_cold_wallets(&cli_caller).await,
+                    Err(e) => Err(e),
+                };
+
+                match wallets_res {
+                    Ok(wallets) => {
+                        let message = escape(format_cold_wallets(&wallets).as_str());
+                        bot.send_message(chat_id, message)
+                            .reply_markup(make_inline_cold_wallet_menu(&wallets))
+                            .await?;
+                    }
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(chat_id, message).await?;
+                    }
+                }
+            }
+            "address_book" => {
+                let conf = gv_config.read().await;
+                let cli_address = selected_vault(&conf).cli_address;
+                drop(conf);
+
+                bot.answer_callback_query(q.id.clone()).await?;
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let entries_res = match bot_caller(&cli_address).await {
+                    Ok(cli_caller) => cli_caller.call_get_address_book().await,
+                    Err(e) => Err(e),
+                };
+
+                match entries_res {
+                    Ok(entries) => {
+                        let message = escape(format_address_book(&entries).as_str());
+                        bot.edit_message_text(chat_id, msg_id, message)
+                            .reply_markup(make_inline_address_book_menu(&entries))
+                            .await?;
+                    }
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(chat_id, message).await?;
+                    }
+                }
+            }
+            btn_press if btn_press.starts_with("address_entry:") => {
+                let conf = gv_config.read().await;
+                let cli_address = selected_vault(&conf).cli_address;
+                drop(conf);
+
+                let index: usize = match btn_press
+                    .strip_prefix("address_entry:")
+                    .and_then(|index| index.parse::<usize>().ok())
+                {
+                    Some(index) => index,
+                    None => return Ok(()),
+                };
+
+                bot.answer_callback_query(q.id.clone()).await?;
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let entries_res = match bot_caller(&cli_address).await {
+                    Ok(cli_caller) => cli_caller.call_get_address_book().await,
+                    Err(e) => Err(e),
+                };
+
+                let entry: AddressBookEntry = match entries_res {
+                    Ok(entries) => match entries.into_iter().nth(index) {
+                        Some(entry) => entry,
+                        None => return Ok(()),
+                    },
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(chat_id, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                let message: String = format!(
+                    "{}```\n{}\n```",
+                    escape(
+                        format!(
+                            "📒 {} 📒\n\n{}Saved {}\n\n",
+                            entry.label,
+                            if entry.whitelisted {
+                                "In REWARD_ADDRESS_WHITELIST\n"
+                            } else {
+                                ""
+                            },
+                            format_utc(entry.updated)
+                        )
+                        .as_str()
+                    ),
+                    entry.address
+                );
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(make_inline_address_entry_options(index))
+                    .await?;
+            }
+            btn_press if btn_press.starts_with("address_remove:") => {
+                let conf = gv_config.read().await;
+                let cli_address = selected_vault(&conf).cli_address;
+                drop(conf);
+
+                let index: usize = match btn_press
+                    .strip_prefix("address_remove:")
+                    .and_then(|index| index.parse::<usize>().ok())
+                {
+                    Some(index) => index,
+                    None => return Ok(()),
+                };
+
+                bot.answer_callback_query(q.id.clone()).await?;
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let cli_caller = match bot_caller(&cli_address).await {
+                    Ok(cli) => cli,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(chat_id, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                // Looked up again, the book could have changed since the menu was sent
+                let address: String = match cli_caller.call_get_address_book().await {
+                    Ok(entries) => match entries.into_iter().nth(index) {
+                        Some(entry) => entry.address,
+                        None => return Ok(()),
+                    },
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(chat_id, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                let message: String = match cli_caller
+                    .call_set_address_label(address, String::new())
+                    .await
+                {
+                    Ok(resp) => resp,
+                    Err(e) => format!("Error: {}", e),
+                };
+
+                let entries: Vec<AddressBookEntry> =
+                    cli_caller.call_get_address_book().await.unwrap_or_default();
+
+                bot.edit_message_text(
+                    chat_id,
+                    msg_id,
+                    escape(format!("{}\n\n{}", message, format_address_book(&entries)).as_str()),
+                )
+                .reply_markup(make_inline_address_book_menu(&entries))
+                .await?;
+            }
+            btn_press if btn_press.starts_with("cold_wallet:") => {
+                let index: usize = match btn_press
+                    .strip_prefix("cold_wallet:")
+                    .and_then(|index| index.parse::<usize>().ok())
+                {
+                    Some(index) => index,
+                    None => return Ok(()),
+                };
+
+                bot.answer_callback_query(q.id.clone()).await?;
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let _ = bot.delete_message(chat_id, msg_id).await?;
+
+                send_cold_wallet_chart(index, &bot, &q, gv_config).await?;
+            }
+            btn_press if btn_press.starts_with("cold_wallet_primary:") => {
+                let conf = gv_config.read().await;
+                let cli_address = selected_vault(&conf).cli_address;
+                drop(conf);
+
+                let index: usize = match btn_press
+                    .strip_prefix("cold_wallet_primary:")
+                    .and_then(|index| index.parse::<usize>().ok())
+                {
+                    Some(index) => index,
+                    None => return Ok(()),
+                };
+
+                bot.answer_callback_query(q.id.clone()).await?;
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+
+                let cli_caller = match bot_caller(&cli_address).await {
+                    Ok(cli) => cli,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(chat_id, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                // Looked up again, the list could have changed since the menu was sent
+                let name: String = match get_cold_wallets(&cli_caller).await {
+                    Ok(wallets) => match wallets.wallets.get(index) {
+                        Some(wallet) => wallet.name.clone(),
+                        None => return Ok(()),
+                    },
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(chat_id, message).await?;
+                        return Ok(());
+                    }
+                };
+
+                let message: String = match cli_caller.call_switch_cold_wallet(name).await {
+                    Ok(resp) => resp,
+                    Err(e) => format!("Error: {}", e),
+                };
+
+                bot.send_message(chat_id, escape(&message))
+                    .reply_markup(make_keyboard_gv_options())
+                    .await?;
+            }
+            "cancel_resync" | "cancel_simulate_rewards" => {
+                let conf = gv_config.read().await;
+                let user = conf.to_owned().tg_user.unwrap();
+                drop(conf);
+                bot.answer_callback_query(q.id).await?;
+                bot.delete_message(user, q.message.unwrap().id).await?;
+            }
+            "cancel_onboarding" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+                let dialogue = OnboardingDialog::new(dialogs.onboarding, chat_id);
+                let current_dialog = dialogue.get().await.unwrap();
+
+                bot.answer_callback_query(q.id).await?;
+
+                if !current_dialog.is_none() {
+                    dialogue.exit().await.unwrap();
+                }
+
+                skip_onboarding(&db).await;
+
+                let message = escape("Setup skipped. Send /setup to run it again any time.");
+
+                bot.send_message(chat_id, message)
+                    .reply_markup(make_keyboard_main())
+                    .await?;
+                last_dialog_id.store(0, Ordering::Relaxed);
+                bot.delete_message(chat_id, msg_id).await?;
+            }
+            "cancel_update_reward_mode" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+                let dialogue = UpdateRewardModeDialog::new(dialogs.reward_mode, chat_id);
+                let current_dialog = dialogue.get().await.unwrap();
+
+                bot.answer_callback_query(q.id).await?;
+
+                let keyboard = make_keyboard_gv_options();
+
+                if !current_dialog.is_none() {
+                    dialogue.exit().await.unwrap();
+                }
+
+                bot.send_message(chat_id, "Cancelled")
+                    .reply_markup(keyboard)
+                    .await?;
+                last_dialog_id.store(0, Ordering::Relaxed);
+                bot.delete_message(chat_id, msg_id).await?;
+            }
+
+            "cancel_update_reward_interval" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+                let dialogue = UpdateRewardIntervalDialog::new(dialogs.reward_interval, chat_id);
+                let current_dialog = dialogue.get().await.unwrap();
+
+                bot.answer_callback_query(q.id).await?;
+
+                let keyboard = make_keyboard_gv_options();
+
+                if !current_dialog.is_none() {
+                    dialogue.exit().await.unwrap();
+                }
+                bot.send_message(chat_id, "Cancelled")
+                    .reply_markup(keyboard)
+                    .await?;
+                last_dialog_id.store(0, Ordering::Relaxed);
+                bot.delete_message(chat_id, msg_id).await?;
+            }
+            "cancel_update_reward_min" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+                let dialogue = UpdateRewardMinDialog::new(dialogs.reward_min, chat_id);
+                let current_dialog = dialogue.get().await.unwrap();
+
+                bot.answer_callback_query(q.id).await?;
+
+                let keyboard = make_keyboard_gv_options();
+
+                if !current_dialog.is_none() {
+                    dialogue.exit().await.unwrap();
+                }
+                bot.send_message(chat_id, "Cancelled")
+                    .reply_markup(keyboard)
+                    .await?;
+                last_dialog_id.store(0, Ordering::Relaxed);
+                bot.delete_message(chat_id, msg_id).await?;
+            }
+            btn_press if btn_press.starts_with("next_month") => {
+                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
+                let month: u32 = split_msg[1].parse::<u32>().unwrap();
+                let year: i32 = split_msg[2].parse::<i32>().unwrap();
+
+                let year_month: (i32, u32) = if month == 12 {
+                    (year + 1, 1)
+                } else {
+                    (year, month + 1)
+                };
+
+                let conf = gv_config.read().await;
+                let timezone = chat_timezone(&db, &conf, q.message.as_ref().unwrap().chat.id);
+
+                let kb = make_inline_calander(year_month.0, year_month.1, &timezone);
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let _ = bot
+                    .edit_message_reply_markup(chat_id, msg_id)
+                    .reply_markup(kb)
+                    .await;
+            }
+            btn_press if btn_press.starts_with("prev_month") => {
+                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
+                let month: u32 = split_msg[1].parse::<u32>().unwrap();
+                let year: i32 = split_msg[2].parse::<i32>().unwrap();
+
+                let year_month: (i32, u32) = if month == 1 {
+                    (year - 1, 12)
+                } else {
+                    (year, month - 1)
+                };
+
+                let conf = gv_config.read().await;
+                let timezone = chat_timezone(&db, &conf, q.message.as_ref().unwrap().chat.id);
+
+                let kb = make_inline_calander(year_month.0, year_month.1, &timezone);
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let _ = bot
+                    .edit_message_reply_markup(chat_id, msg_id)
+                    .reply_markup(kb)
+                    .await;
+            }
+            btn_press if btn_press.starts_with("date_selection") => {
+                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
+                let day: u32 = split_msg[1].parse::<u32>().unwrap();
+                let month: u32 = split_msg[2].parse::<u32>().unwrap();
+                let year: i32 = split_msg[3].parse::<i32>().unwrap();
+                let q_clone = q.clone();
+
+                bot.answer_callback_query(q.id).await?;
+
+                let chart_range_dialogue: Dialogue<
+                    GetDateRangeState,
+                    InMemStorage<GetDateRangeState>,
+                > = GetDateRangeDialog::new(
+                    dialogs.chart_range.clone(),
+                    q.message.as_ref().unwrap().chat.id,
+                );
+
+                let chart_range_state = chart_range_dialogue.get().await.unwrap();
+
+                let conf = gv_config.read().await;
+                let time_zone = chat_timezone(&db, &conf, q.message.as_ref().unwrap().chat.id);
+                let tz = Tz::from_str_insensitive(&time_zone).unwrap();
+
+                drop(conf);
+
+                let date: NaiveDate = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+                let timestamp: u64 = time_ranges::local_day_start(&tz, date);
+
+                if chart_range_state.is_some() {
+                    match chart_range_state {
+                        Some(GetDateRangeState::ReceiveFirstDate {
+                            division,
+                            time_zone,
+                            chart_type,
+                        }) => {
+                            receive_first_date(
+                                bot.clone(),
+                                chart_range_dialogue.clone(),
+                                &q_clone,
+                                timestamp,
+                                division,
+                                &time_zone,
+                                chart_type,
+                            )
+                            .await
+                            .unwrap();
+                        }
+                        Some(GetDateRangeState::ReceiveSecondDate {
+                            first_date,
+                            division,
+                            time_zone: _,
+                            chart_type,
+                        }) => {
+                            let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                            let msg_id = q.message.as_ref().unwrap().id;
+                            let end_time: u64 = time_ranges::local_day_end(&tz, date);
+                            if end_time <= first_date {
+                                let message = escape("End date must be after start date");
+                                bot.edit_message_text(chat_id, msg_id, message).await?;
+                                chart_range_dialogue.exit().await.unwrap();
+                                return Ok(());
+                            }
+
+                            let chart_range = (first_date, end_time);
+                            chart_range_dialogue.exit().await.unwrap();
+
+                            let _ = bot.delete_message(chat_id, msg_id).await;
+
+                            let chat_tz: Option<String> = db.get_chat_timezone(chat_id.0);
+
+                            if chart_type == "earnings_chart" {
+                                send_earnings_chart(
+                                    chart_range,
+                                    &bot,
+                                    &q_clone,
+                                    gv_config,
+                                    chat_tz,
+                                )
+                                .await?;
+                            } else if chart_type == "heatmap_chart" {
+                                send_heatmap(chart_range, &bot, &q_clone, gv_config, chat_tz)
+                                    .await?;
+                            } else if chart_type == "balance_chart" {
+                                send_balance_chart(chart_range, &bot, &q_clone, gv_config).await?;
+                            } else if chart_type == "network_chart" {
+                                send_network_chart(chart_range, &bot, &q_clone, gv_config).await?;
+                            } else {
+                                send_barchart(
+                                    chart_range,
+                                    &bot,
+                                    &q_clone,
+                                    gv_config,
+                                    &division,
+                                    chat_tz,
+                                )
+                                .await?;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            btn_press if btn_press.starts_with("current_date") => {
+                let conf = gv_config.read().await;
+                let timezone = chat_timezone(&db, &conf, q.message.as_ref().unwrap().chat.id);
+                let current_ymd = get_current_month_year_day(&timezone);
+                let kb = make_inline_calander(current_ymd.0, current_ymd.1, &timezone);
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let _ = bot
+                    .edit_message_reply_markup(chat_id, msg_id)
+                    .reply_markup(kb)
+                    .await;
+            }
+            btn_press if btn_press.starts_with("tz_region_selection") => {
+                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
+                let region: &str = split_msg[1];
+
+                if region == "UTC" {
+                    let conf = gv_config.read().await;
+                    let cli_address = conf.to_owned().cli_address;
+                    drop(conf);
+
+                    let cli_caller_res = bot_caller(&cli_address).await;
+
+                    let cli_caller = match cli_caller_res {
+                        Ok(cli) => cli,
+                        Err(e) => {
+                            let message = escape(format!("Error: {}", e).as_str());
+                            bot.send_message(q.message.as_ref().unwrap().chat.id, message)
+                                .await?;
+                            return Ok(());
+                        }
+                    };
+
+                    let cli_res = cli_caller.call_set_timezone("UTC".to_string()).await;
+
+                    match cli_res {
+                        Ok(_) => {}
+                        Err(e) => {
+                            let message = escape(format!("Error: {}", e).as_str());
+                            bot.send_message(q.message.as_ref().unwrap().chat.id, message)
+                                .await?;
+                        }
+                    }
+
+                    let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                    let msg_id = q.message.as_ref().unwrap().id;
+
+                    let kb = make_keyboard_main();
+
+                    bot.send_message(chat_id, "Timezone set to UTC")
+                        .reply_markup(kb)
+                        .await?;
+
+                    last_dialog_id.store(0, Ordering::Relaxed);
+                    bot.delete_message(chat_id, msg_id).await?;
+                } else {
+                    let kb = make_timezone_option_keyboard(region, 1).unwrap();
+
+                    let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                    let msg_id = q.message.as_ref().unwrap().id;
+
+                    let message = escape(
+                        format!(
+                            "👻 Timezone Updater 👻\n\nSelected region: {}\nPlease select a City.",
+                            region
+                        )
+                        .as_str(),
+                    );
+
+                    bot.edit_message_text(chat_id, msg_id, message)
+                        .reply_markup(kb)
+                        .await?;
+                }
+            }
+            btn_press if btn_press.starts_with("tz_selection") => {
+                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
+                let region: &str = split_msg[1];
+                let city = split_msg[2];
+
+                let tz = format!("{}/{}", region, city);
+
+                let conf = gv_config.read().await;
+                let cli_address = conf.to_owned().cli_address;
+                drop(conf);
+
+                let cli_caller_res = bot_caller(&cli_address).await;
+
+                let cli_caller = match cli_caller_res {
+                    Ok(cli) => cli,
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(q.message.as_ref().unwrap().chat.id, message)
+                            .await?;
+                        return Ok(());
+                    }
+                };
+
+                let cli_res = cli_caller.call_set_timezone(tz.clone()).await;
+
+                match cli_res {
+                    Ok(_) => {}
+                    Err(e) => {
+                        let message = escape(format!("Error: {}", e).as_str());
+                        bot.send_message(q.message.as_ref().unwrap().chat.id, message)
+                            .await?;
+                    }
+                }
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let kb = make_keyboard_main();
+
+                let message = escape(format!("Timezone set to {}", tz).as_str());
+
+                bot.send_message(chat_id, message).reply_markup(kb).await?;
+
+                last_dialog_id.store(0, Ordering::Relaxed);
+                bot.delete_message(chat_id, msg_id).await?;
+            }
+            btn_press if btn_press.starts_with("tz_page_back") => {
+                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
+                let region: &str = split_msg[1];
+
+                let page = split_msg[2].parse::<u8>().unwrap();
+
+                let kb = if page == 1 {
+                    make_timezone_region_keyboard()
+                } else {
+                    make_timezone_option_keyboard(region, page - 1).unwrap()
+                };
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id: MessageId = q.message.as_ref().unwrap().id;
+
+                let message = escape(
+                    format!(
+                        "👻 Timezone Updater 👻\n\nSelected region: {}\nPlease select a City.",
+                        region
+                    )
+                    .as_str(),
+                );
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(kb)
+                    .await?;
+            }
+            btn_press if btn_press.starts_with("tz_page_next") => {
+                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
+                let region: &str = split_msg[1];
+
+                let page = split_msg[2].parse::<u8>().unwrap();
+
+                let kb = make_timezone_option_keyboard(region, page + 1).unwrap();
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let message = escape(
+                    format!(
+                        "👻 Timezone Updater 👻\n\nSelected region: {}\nPlease select a City.",
+                        region
+                    )
+                    .as_str(),
+                );
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(kb)
+                    .await?;
+            }
+
+            btn_press if btn_press.starts_with("stake_chart_selection") => {
+                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
+                let chart_type: &str = split_msg[1];
+                let chart_range = split_msg[2];
+                let conf = gv_config.read().await;
+                let q_ctx = q.clone();
+
+                let division = match chart_type {
+                    "stakes_day_chart" => "day",
+                    "stakes_week_chart" => "week",
+                    "stakes_month_chart" => "month",
+                    "earnings_chart" => "earnings",
+                    "heatmap_chart" => "heatmap",
+                    "balance_chart" => "balance",
+                    "network_chart" => "network",
+                    _ => "day",
+                };
+
+                let time_zone = chat_timezone(&db, &conf, q.message.as_ref().unwrap().chat.id);
+                drop(conf);
+
+                if chart_range == "custom_range" {
+                    let chart_range_dialog = GetDateRangeDialog::new(
+                        dialogs.chart_range.clone(),
+                        q_ctx.message.as_ref().unwrap().chat.id,
+                    );
+
+                    start_chart_range_dialogue(
+                        bot.clone(),
+                        chart_range_dialog,
+                        &q,
+                        &time_zone,
+                        division.to_string(),
+                        chart_type.to_string(),
+                    )
+                    .await
+                    .unwrap();
+                    return Ok(());
+                }
+
+                let tz: Tz = Tz::from_str_insensitive(&time_zone).unwrap_or(Tz::UTC);
+                let start_end = time_ranges::named_range(chart_range, &tz);
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let _ = bot.delete_message(chat_id, msg_id).await?;
+
+                let chat_tz: Option<String> = db.get_chat_timezone(chat_id.0);
+
+                if chart_type == "earnings_chart" {
+                    send_earnings_chart(start_end, &bot, &q, gv_config, chat_tz).await?;
+                } else if chart_type == "heatmap_chart" {
+                    send_heatmap(start_end, &bot, &q, gv_config, chat_tz).await?;
+                } else if chart_type == "balance_chart" {
+                    send_balance_chart(start_end, &bot, &q, gv_config).await?;
+                } else if chart_type == "network_chart" {
+                    send_network_chart(start_end, &bot, &q, gv_config).await?;
+                } else {
+                    send_barchart(start_end, &bot, &q, gv_config, division, chat_tz).await?;
+                }
+            }
+
+            btn_press if btn_press.starts_with("payout_history_selection") => {
+                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
+                let history_range = split_msg[1];
+
+                let conf = gv_config.read().await;
+                let time_zone = chat_timezone(&db, &conf, q.message.as_ref().unwrap().chat.id);
+                drop(conf);
+
+                let tz: Tz = Tz::from_str_insensitive(&time_zone).unwrap_or(Tz::UTC);
+                let start_end = time_ranges::named_range(history_range, &tz);
+
+                bot.answer_callback_query(q.id.clone()).await?;
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let _ = bot.delete_message(chat_id, msg_id).await?;
+
+                send_payout_history(start_end, &bot, &q, gv_config).await?;
+            }
+
+            btn_press if btn_press.starts_with("simulate_rewards,") => {
+                let days: u64 = btn_press
+                    .strip_prefix("simulate_rewards,")
+                    .and_then(|days| days.parse::<u64>().ok())
+                    .unwrap_or_default();
+
+                bot.answer_callback_query(q.id.clone()).await?;
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let _ = bot.delete_message(chat_id, msg_id).await?;
+
+                send_reward_simulation(days, &bot, &q, gv_config).await?;
+            }
+
+            "tz_back" => {
+                let kb = make_timezone_region_keyboard();
+
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let message = escape("👻 Timezone Updater 👻\n\nPlease select your region.");
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(kb)
+                    .await?;
+            }
+
+            "back_to_stake_chart" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let kb = make_inline_chart_menu();
+
+                let message = escape("👻 Charts 👻\n\nPlease select a chart type");
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(kb)
+                    .await?;
+            }
+
+            "stake_chart" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let kb = make_inline_stakes_chart_menu();
+
+                let message = escape("👻 Stake Charts 👻\n\nPlease select a divisor");
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(kb)
+                    .await?;
+            }
+
+            "earnings_chart" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let kb = make_inline_stake_chart_range_menu("earnings_chart".to_string());
+
+                let message = escape("👻 Earnings Charts 👻\n\nPlease select a range");
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(kb)
+                    .await?;
+            }
+
+            "heatmap_chart" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let kb = make_inline_stake_chart_range_menu("heatmap_chart".to_string());
+
+                let message = escape("👻 Stake Calendar 👻\n\nPlease select a range");
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(kb)
+                    .await?;
+            }
+
+            "balance_chart" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let kb = make_inline_stake_chart_range_menu("balance_chart".to_string());
+
+                let message = escape("👻 Balance History 👻\n\nPlease select a range");
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(kb)
+                    .await?;
+            }
+
+            "network_chart" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let kb = make_inline_stake_chart_range_menu("network_chart".to_string());
+
+                let message = escape("👻 My Weight vs Network 👻\n\nPlease select a range");
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(kb)
+                    .await?;
+            }
+
+            "stakes_day_chart" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let kb = make_inline_stake_chart_range_menu("stakes_day_chart".to_string());
+
+                let message = escape("👻 Range Selection 👻\n\nPlease select a range");
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(kb)
+                    .await?;
+            }
+            "stakes_month_chart" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let kb = make_inline_stake_chart_range_menu("stakes_month_chart".to_string());
+
+                let message = escape("👻 Range Selection 👻\n\nPlease select a range");
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(kb)
+                    .await?;
+            }
+            "stakes_week_chart" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let kb = make_inline_stake_chart_range_menu("stakes_week_chart".to_string());
+
+                let message = escape("👻 Range Selection 👻\n\nPlease select a range");
+
+                bot.edit_message_text(chat_id, msg_id, message)
+                    .reply_markup(kb)
+                    .await?;
+            }
+
+            "cancel_select_tz" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let kb = make_keyboard_bot_settings();
+
+                bot.send_message(chat_id, "Cancelled")
+                    .reply_markup(kb)
+                    .await?;
+
+                last_dialog_id.store(0, Ordering::Relaxed);
+                bot.delete_message(chat_id, msg_id).await?;
+            }
+
+            "cancel_select_chart" => {
+                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+                let msg_id = q.message.as_ref().unwrap().id;
+
+                let kb = make_stats_info_keyboard();
+
+                bot.send_message(chat_id, "Cancelled")
+                    .reply_markup(kb)
+                    .await?;
+
+                last_dialog_id.store(0, Ordering::Relaxed);
+                bot.delete_message(chat_id, msg_id).await?;
+
+                let chart_range_dialog =
+                    GetDateRangeDialog::new(dialogs.chart_range.clone(), chat_id);
+
+                if chart_range_dialog.get().await.unwrap().is_some() {
+                    chart_range_dialog.exit().await.unwrap();
+                }
+            }
+
+            " " | "\t\t" => {
+                bot.answer_callback_query(q.id).await?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+async fn get_announce_routes(
+    cli_caller: &CLICaller,
+) -> Result<Vec<AnnounceRoute>, Box<dyn std::error::Error + Send + Sync>> {
+    cli_caller.call_get_announce_routes().await
+}
+
+async fn get_recent_logs(
+    cli_caller: &CLICaller,
+) -> Result<RecentLogs, Box<dyn std::error::Error + Send + Sync>> {
+    cli_caller
+        .call_get_logs(TG_LOG_LINES, String::new(), false)
+        .await
+}
+
+// Telegram caps a message at 4096 characters, the oldest lines are dropped to fit.
+fn format_recent_logs(logs: &RecentLogs) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut len: usize = 0;
+
+    for line in logs.ghostvault.iter().rev() {
+        len += line.chars().count() + 1;
+        if len > TG_LOG_MAX_CHARS {
+            break;
+        }
+        lines.push(line);
+    }
+
+    if lines.is_empty() {
+        return "The log is empty.".to_string();
+    }
+
+    lines.reverse();
+    lines.join("\n")
+}
+
+async fn get_cold_wallets(
+    cli_caller: &CLICaller,
+) -> Result<ColdWallets, Box<dyn std::error::Error + Send + Sync>> {
+    cli_caller.call_list_cold_wallets().await
+}
+
+fn format_cold_wallets(wallets: &ColdWallets) -> String {
+    let mut message: String = "👛 Cold Wallets 👛\n".to_string();
+
+    for wallet in &wallets.wallets {
+        message.push_str(&format!(
+            "\n{}{}{}\nStakes: {}, {} GHOST\nPayouts: {}, {} GHOST\n",
+            wallet.name,
+            if wallet.primary { " (primary)" } else { "" },
+            if wallet.loaded { "" } else { " - not loaded" },
+            wallet.stakes,
+            wallet.rewards,
+            wallet.payouts,
+            wallet.paid
+        ));
+    }
+
+    message.push_str("\nThe primary wallet pays out and takes zaps. Select a wallet");
+    message
+}
+
+fn format_address_book(entries: &[AddressBookEntry]) -> String {
+    if entries.is_empty() {
+        return "📒 Address Book 📒\n\nNo addresses saved yet. Name one with /label <address> <name>, the name can then be given instead of the address when setting the reward mode.".to_string();
+    }
+
+    let mut message: String = "📒 Address Book 📒\n".to_string();
+
+    for entry in entries {
+        message.push_str(&format!(
+            "\n{}{}\n{}\n",
+            entry.label,
+            if entry.whitelisted {
+                " (whitelisted)"
+            } else {
+                ""
+            },
+            short_address(&entry.address)
+        ));
+    }
+
+    message.push_str("\nSelect an address");
+    message
+}
+
+async fn get_delegations(
+    cli_caller: &CLICaller,
+) -> Result<Delegations, Box<dyn std::error::Error + Send + Sync>> {
+    cli_caller.call_get_delegations().await
+}
+
+fn format_delegations(delegations: &Delegations) -> String {
+    if delegations.delegations.is_empty() {
+        return "🤝 Delegations 🤝\n\nNo one is cold staking to this vault.".to_string();
+    }
+
+    let mut message: String = format!(
+        "🤝 Delegations 🤝\n\n{} GHOST from {} addresses in {} UTXOs\n",
+        delegations.total,
+        delegations.delegations.len(),
+        delegations.utxos
+    );
+
+    for delegation in delegations.delegations.iter().take(TG_DELEGATIONS_MAX) {
+        let last_stake: String = match delegation.last_stake {
+            Some(timestamp) => format_utc(timestamp),
+            None => "Never".to_string(),
+        };
+
+        message.push_str(&format!(
+            "\n{}\n{} GHOST ({:.2}%) in {} UTXOs\nStakes: {}, last: {}\n",
+            delegation.spend_address,
+            delegation.amount,
+            delegation.share,
+            delegation.utxos,
+            delegation.stakes,
+            last_stake
+        ));
+    }
+
+    if delegations.delegations.len() > TG_DELEGATIONS_MAX {
+        let rest: Amount = delegations
+            .delegations
+            .iter()
+            .skip(TG_DELEGATIONS_MAX)
+            .map(|delegation| delegation.amount)
+            .sum();
+
+        message.push_str(&format!(
+            "\n...and {} more addresses with {} GHOST, see gv-cli delegations.",
+            delegations.delegations.len() - TG_DELEGATIONS_MAX,
+            rest
+        ));
+    }
+
+    message
+}
+
+fn format_operations(statuses: &[OperationStatus]) -> String {
+    let mut message: String = "👻 Operations 👻\n".to_string();
+
+    for status in statuses {
+        let state: String = match status.state {
+            OperationState::Idle => match status.finished {
+                Some(finished) => format!("done {}", format_utc(finished)),
+                None => "idle".to_string(),
+            },
+            OperationState::Running => format!(
+                "running{}",
+                status
+                    .progress
+                    .as_ref()
+                    .map(|progress| format!(", {}", progress))
+                    .unwrap_or_default()
+            ),
+            OperationState::Failed => format!(
+                "failed, {}",
+                status.error.as_deref().unwrap_or("unknown error")
+            ),
+        };
+
+        message.push_str(&format!("\n{}: {}", status.op_id, state));
+    }
+
+    message
+}
+
+fn format_quick_status(status: &GVStatus) -> String {
+    let mark = |value: &str| if value == "YES" { "✅" } else { "❌" };
+
+    format!(
+        "Synced {} | Staking {} | 24h: {} stakes, {} GHOST",
+        mark(&status.daemon_synced),
+        mark(&status.active_staking),
+        status.stakes_24,
+        status.total_24_amount
+    )
+}
+
+fn format_status_card(prefix: &str, status: &GVStatus) -> String {
+    [
+        format!("{}👻 GhostVault Status 👻\n", prefix),
+        format!(
+            "Synced: {} (block {}, {} peers)",
+            status.daemon_synced, status.best_block, status.daemon_peers
+        ),
+        format!("Good chain: {}", status.good_chain),
+        format!(
+            "Staking: {}, {} GHOST",
+            status.active_staking, status.currently_staking_amount
+        ),
+        format!("Last stake: {}", status.last_stake),
+        format!(
+            "24h: {} stakes, {} GHOST",
+            status.stakes_24, status.total_24_amount
+        ),
+    ]
+    .join("\n")
+}
+
+fn format_quiet_hours(quiet_hours: &[QuietHours]) -> String {
+    let mut message: String = "🌙 Quiet Hours 🌙\n\n".to_string();
+    message.push_str("Notices are held during a destination's quiet hours and sent as one ");
+    message.push_str("summary after. Offline and chain alerts always go out.\n");
+
+    for quiet in quiet_hours {
+        let state: String = if quiet.quiet_now {
+            format!(", quiet now with {} held", quiet.held)
+        } else {
+            String::new()
+        };
+
+        message.push_str(&format!(
+            "\n{}: {}{}",
+            quiet.destination.as_str(),
+            quiet.window(),
+            state
+        ));
+    }
+
+    message
+}
+
+fn format_scheduled_tasks(tasks: &[ScheduledTask]) -> String {
+    let interval = |secs: u64| -> String {
+        if secs.is_multiple_of(86400) {
+            format!("{}d", secs / 86400)
+        } else if secs.is_multiple_of(3600) {
+            format!("{}h", secs / 3600)
+        } else {
+            format!("{}m", secs / 60)
+        }
+    };
+
+    let mut message: String = "👻 Scheduled Tasks 👻\n".to_string();
+
+    for task in tasks {
+        let state: &str = if task.running {
+            "running"
+        } else if task.paused {
+            "paused"
+        } else {
+            "scheduled"
+        };
+
+        let last: String = match (task.last_run, &task.last_result) {
+            (Some(last_run), Some(result)) => format!("{}, {}", format_utc(last_run), result),
+            (Some(last_run), None) => format_utc(last_run),
+            _ => "never".to_string(),
+        };
+
+        message.push_str(&format!(
+            "\n{} ({}, every {})\nLast: {}\nNext: {}\n",
+            task.name,
+            state,
+            interval(task.interval),
+            last,
+            format_utc(task.next_run)
+        ));
+    }
+
+    message
+}
+
+fn format_jobs(jobs: &[JobDB]) -> String {
+    if jobs.is_empty() {
+        return "No jobs have run yet.".to_string();
+    }
+
+    let mut message: String = "👻 Recent Jobs 👻\n".to_string();
+
+    for job in jobs {
+        let detail: Option<&String> = match job.state.as_str() {
+            "running" => job.progress.as_ref(),
+            "complete" => job.outcome.as_ref(),
+            _ => job.error.as_ref(),
+        };
+
+        message.push_str(&format!(
+            "\n{} {}: {}",
+            format_utc(job.started),
+            job.kind,
+            job.state
+        ));
+
+        if let Some(detail) = detail {
+            message.push_str(&format!(", {}", detail));
+        }
+    }
+
+    message
+}
+
+fn format_expected_deposits(deposits: &[ExpectedDeposit]) -> String {
+    if deposits.is_empty() {
+        return "No deposits are expected, add one with /expect <amount> [memo]".to_string();
+    }
+
+    let mut message: String = "👻 Expected Deposits 👻\n".to_string();
+
+    for deposit in deposits {
+        let memo: String = if deposit.memo.is_empty() {
+            String::new()
+        } else {
+            format!(" - {}", deposit.memo)
+        };
+
+        message.push_str(&format!(
+            "\n#{} {} GHOST{}\n{}, due {}\n",
+            deposit.id,
+            deposit.amount,
+            memo,
+            deposit.status,
+            format_utc(deposit.due)
+        ));
+    }
+
+    message
+}
+
+fn format_anonymization_status(status: &AnonymizationStatus) -> String {
+    let mut message: String = format!(
+        "Public trusted: {}\nPublic pending: {}\nConverting: {}\nAnon immature: {}\nAnon trusted: {}",
+        status.public_trusted,
+        status.public_pending,
+        status.converting,
+        status.anon_immature,
+        status.anon_trusted
+    );
+
+    for conversion in &status.in_flight {
+        message.push_str(&format!(
+            "\n\n{} GHOST, {} confirmations\nMatures about {}",
+            conversion.amount,
+            conversion.confirmations,
+            format_utc(conversion.matures)
+        ));
+
+        for txid in &conversion.txids {
+            message.push_str(&format!("\n{}", txid));
+        }
+    }
+
+    message
+}
+
+async fn get_maintenance_window(
+    cli_caller: &CLICaller,
+) -> Result<MaintenanceWindow, Box<dyn std::error::Error + Send + Sync>> {
+    cli_caller.call_get_maintenance_window().await
+}
+
+fn format_maintenance_window(window: &MaintenanceWindow) -> String {
+    let hours: String = if window.start == window.end {
+        "Any time".to_string()
+    } else {
+        format!(
+            "{:02}:00 to {:02}:00 ({})",
+            window.start, window.end, window.timezone
+        )
+    };
+
+    let mut waiting: Vec<String> = Vec::new();
+
+    if let Some(version) = &window.deferred_update {
+        waiting.push(format!("Ghostd update to {}", version));
+    }
+
+    if window.deferred_resync {
+        waiting.push("Resync".to_string());
+    }
+
+    let waiting: String = if waiting.is_empty() {
+        "Nothing".to_string()
+    } else {
+        waiting.join(", ")
+    };
+
+    format!(
+        "Maintenance Window\n\nGhostd updates and resyncs wait for these hours.\n\nHours: {}\nOpen now: {}\nWaiting: {}",
+        hours,
+        if window.open { "Yes" } else { "No" },
+        waiting
+    )
+}
+
+async fn get_digest_settings(
+    cli_caller: &CLICaller,
+) -> Result<DigestSettings, Box<dyn std::error::Error + Send + Sync>> {
+    cli_caller.call_get_digest_settings().await
+}
+
+async fn get_bot_settings(gv_config: &Arc<async_RwLock<GVConfig>>) -> String {
+    let conf = gv_config.read().await;
+    let stake_announce = if conf.announce_stakes {
+        "Stake announcments: ✅\n"
+    } else {
+        "Stake announcments: ❌\n"
+    };
+
+    let reward_announce = if conf.announce_rewards {
+        "Reward announcments: ✅\n"
+    } else {
+        "Reward announcments: ❌\n"
+    };
+
+    let zap_announce = if conf.announce_zaps {
+        "Zap announcments: ✅\n"
+    } else {
+        "Zap announcments: ❌\n"
+    };
+
+    let daily_digest = if conf.digest_daily {
+        "Daily digest: ✅\n"
+    } else {
+        "Daily digest: ❌\n"
+    };
+
+    let weekly_digest = if conf.digest_weekly {
+        "Weekly digest: ✅\n"
+    } else {
+        "Weekly digest: ❌\n"
+    };
+
+    let timezone = conf.timezone.clone().to_uppercase();
+
+    let reply = escape(
+        format!(
+            "Bot Settings\n\n{}{}{}{}{}\nTimezone: {}",
+            stake_announce, reward_announce, zap_announce, daily_digest, weekly_digest, timezone
+        )
+        .as_str(),
+    );
+
+    reply
+}
+
+pub fn server_unready_message(server_ready: &ServerReadyDB) -> String {
+    let reason = server_ready.reason.clone().unwrap_or("".to_string());
+    let res = match reason.as_str() {
+        "Daemon offline" => escape("Ghostd is offline. Please wait for it to start."),
+        "Daemon update in progress" => {
+            escape("Ghostd update in progress. Please wait for it to finish before making changes.")
+        }
+        "Importing Wallet" => escape("Importing wallet. Please wait for it to finish."),
+        "Forcing resync" => {
+            escape("Forcing resync. Please wait for it to finish before making changes.")
+        }
+        _ => escape("Unknown error! Please try again later."),
+    };
+
+    res
+}
+
+// What follows the command, keeping its case since txids and hashes are passed on as is.
+fn command_arg(user_message: &str) -> String {
+    user_message
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn format_utc(timestamp: u64) -> String {
+    chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|datetime| datetime.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_default()
+}
+
+fn format_tx_summary(tx: &TxSummary) -> String {
+    let block: String = match (tx.block_height, &tx.block_hash) {
+        (Some(height), _) => height.to_string(),
+        (None, Some(hash)) => hash.clone(),
+        (None, None) => "unconfirmed".to_string(),
+    };
+
+    format!(
+        "Txid: {}\nWallet: {}\nType: {}\nAmount: {:.8}\nFee: {:.8}\nConfirmations: {}\nBlock: {}\nTime: {}",
+        tx.txid,
+        tx.wallet,
+        tx.category,
+        tx.amount.to_ghost(),
+        tx.fee.to_ghost(),
+        tx.confirmations,
+        block,
+        format_utc(tx.time)
+    )
+}
+
+fn format_block_summary(block: &BlockSummary) -> String {
+    format!(
+        "Height: {}\nHash: {}\nConfirmations: {}\nTransactions: {}\nSize: {} bytes\nTime: {}\nPrevious: {}",
+        block.height,
+        block.hash,
+        block.confirmations,
+        block.tx_count,
+        block.size,
+        format_utc(block.time),
+        block.previous_hash.as_deref().unwrap_or("none")
+    )
+}
+
+async fn reply_status(
+    bot: &DefaultParseMode<Bot>,
+    msg: &Message,
+    gv_config: &Arc<async_RwLock<GVConfig>>,
+    db: &GVDB,
+) -> ResponseResult<Message> {
+    let conf = gv_config.read().await;
+    let vault: FleetVault = selected_vault(&conf);
+    let prefix: String = vault_prefix(&conf, &vault.name);
+    drop(conf);
+
+    let cli_caller_res = bot_caller(&vault.cli_address).await;
+
+    let cli_caller = match cli_caller_res {
+        Ok(cli) => cli,
+        Err(e) => {
+            let message = escape(format!("Error: {}", e).as_str());
+            bot.send_message(msg.chat.id, message).await?;
+            return Ok(msg.clone());
+        }
+    };
+
+    let cli_res = match db.get_chat_timezone(msg.chat.id.0) {
+        Some(timezone) => cli_caller.call_get_daemon_state_tz(timezone).await,
+        None => cli_caller.call_get_daemon_state().await,
+    };
+    let status: GVStatus = match cli_res {
+        Ok(resp) => resp,
+        Err(e) => {
+            let message = escape(format!("Error: {}", e).as_str());
+            bot.send_message(msg.chat.id, message).await?;
+            return Ok(msg.clone());
+        }
+    };
+    let pretty_string = serde_json::to_string_pretty(&status).unwrap();
+    let reply_escaped = escape(format!("{}", pretty_string).as_str());
+    let header: String = escape(format!("{}👻 GhostVault Status 👻", prefix).as_str());
+    let reply: String = format!("{}\n\n```\n{}\n```", header, reply_escaped);
+    let msg: Message = bot.send_message(msg.chat.id, reply).await?;
+
+    Ok(msg)
+}
+
+// The selected vault's status with its prefix, for /quick and inline queries. With a timezone
+// its times are in that rather than the vault's TIMEZONE.
+async fn fetch_status(
+    gv_config: &Arc<async_RwLock<GVConfig>>,
+    timezone: Option<String>,
+) -> Result<(String, GVStatus), Box<dyn std::error::Error + Send + Sync>> {
+    let conf = gv_config.read().await;
+    let vault: FleetVault = selected_vault(&conf);
+    let prefix: String = vault_prefix(&conf, &vault.name);
+    drop(conf);
+
+    let cli_caller: CLICaller = bot_caller(&vault.cli_address).await?;
+    let status: GVStatus = match timezone {
+        Some(timezone) => cli_caller.call_get_daemon_state_tz(timezone).await?,
+        None => cli_caller.call_get_daemon_state().await?,
+    };
 
-                bot.edit_message_text(chat_id, msg_id, message)
-                    .reply_markup(kb)
-                    .await?;
-            }
+    Ok((prefix, status))
+}
 
-            btn_press if btn_press.starts_with("stake_chart_selection") => {
-                let split_msg = btn_press.split(",").collect::<Vec<&str>>();
-                let chart_type: &str = split_msg[1];
-                let chart_range = split_msg[2];
-                let conf = gv_config.read().await;
-                let q_ctx = q.clone();
+async fn reply_quick_status(
+    bot: &DefaultParseMode<Bot>,
+    msg: &Message,
+    gv_config: &Arc<async_RwLock<GVConfig>>,
+    db: &GVDB,
+) -> ResponseResult<Message> {
+    let chat_tz: Option<String> = db.get_chat_timezone(msg.chat.id.0);
 
-                let division = match chart_type {
-                    "stakes_day_chart" => "day",
-                    "stakes_week_chart" => "week",
-                    "stakes_month_chart" => "month",
-                    "earnings_chart" => "earnings",
-                    _ => "day",
-                };
+    let message: String = match fetch_status(gv_config, chat_tz).await {
+        Ok((prefix, status)) => format!("{}{}", prefix, format_quick_status(&status)),
+        Err(e) => format!("Error: {}", e),
+    };
 
-                let time_zone = conf.to_owned().timezone;
-                drop(conf);
+    bot.send_message(msg.chat.id, escape(&message)).await
+}
 
-                if chart_range == "custom_range" {
-                    let chart_range_dialog = GetDateRangeDialog::new(
-                        chart_range_mem.clone(),
-                        q_ctx.message.as_ref().unwrap().chat.id,
-                    );
+// The chat's own timezone if one was set with /timezone, otherwise the vault's TIMEZONE.
+fn chat_timezone(db: &GVDB, conf: &GVConfig, chat_id: ChatId) -> String {
+    db.get_chat_timezone(chat_id.0)
+        .unwrap_or_else(|| conf.timezone.clone())
+}
 
-                    start_chart_range_dialogue(
-                        bot.clone(),
-                        chart_range_dialog,
-                        &q,
-                        &time_zone,
-                        division.to_string(),
-                        chart_type.to_string(),
-                    )
-                    .await
-                    .unwrap();
-                    return Ok(());
-                }
+// /timezone shows the chat's timezone, /timezone Area/City sets it and /timezone off goes back to
+// the vault's. Kept in the bot's database so each chat can differ.
+async fn reply_chat_timezone(
+    bot: &DefaultParseMode<Bot>,
+    msg: &Message,
+    gv_config: &Arc<async_RwLock<GVConfig>>,
+    db: &GVDB,
+) -> ResponseResult<Message> {
+    let chat_id: ChatId = msg.chat.id;
+    let arg: String = msg.text().map(command_arg).unwrap_or_default();
+    let vault_tz: String = gv_config.read().await.timezone.clone();
+
+    let message: String = if arg.is_empty() {
+        match db.get_chat_timezone(chat_id.0) {
+            Some(timezone) => format!(
+                "This chat uses {}, the vault uses {}.\nSend /timezone off to use the vault's.",
+                timezone, vault_tz
+            ),
+            None => format!(
+                "This chat uses the vault's timezone, {}.\nSend /timezone Area/City to change it.",
+                vault_tz
+            ),
+        }
+    } else if arg.eq_ignore_ascii_case("off") {
+        match db.set_chat_timezone(chat_id.0, None).await {
+            Ok(_) => format!("This chat is back to the vault's timezone, {}.", vault_tz),
+            Err(e) => format!("Error: {}", e),
+        }
+    } else {
+        match Tz::from_str_insensitive(&arg) {
+            Ok(tz) => match db.set_chat_timezone(chat_id.0, Some(tz.name())).await {
+                Ok(_) => format!("This chat now uses {}.", tz.name()),
+                Err(e) => format!("Error: {}", e),
+            },
+            Err(_) => format!("Invalid timezone: {}", arg),
+        }
+    };
 
-                let start_end = parse_chart_range(chart_range, &time_zone);
+    bot.send_message(chat_id, escape(&message)).await
+}
 
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+// Answers "@botname status" in any chat, for the admin only so the bot stays private. Inline mode
+// has to be turned on for the bot with BotFather's /setinline.
+async fn inline_query_handler(
+    bot: DefaultParseMode<Bot>,
+    q: InlineQuery,
+    gv_config: Arc<async_RwLock<GVConfig>>,
+) -> ResponseResult<()> {
+    let auth_user: String = gv_config.read().await.tg_user.clone().unwrap_or_default();
+    let query: String = q.query.trim().to_lowercase();
+
+    let mut results: Vec<InlineQueryResult> = Vec::new();
+
+    if q.from.id.to_string() == auth_user && "status".starts_with(query.as_str()) {
+        let (title, description, card) = match fetch_status(&gv_config, None).await {
+            Ok((prefix, status)) => (
+                format!("{}GhostVault Status", prefix),
+                format_quick_status(&status),
+                format_status_card(&prefix, &status),
+            ),
+            Err(e) => (
+                "GhostVault Status".to_string(),
+                "Status unavailable".to_string(),
+                format!("Error: {}", e),
+            ),
+        };
+
+        let content: InputMessageContent = InputMessageContent::Text(
+            InputMessageContentText::new(escape(&card)).parse_mode(ParseMode::MarkdownV2),
+        );
+
+        results.push(InlineQueryResult::Article(
+            InlineQueryResultArticle::new("status", title, content).description(description),
+        ));
+    }
 
-                let _ = bot.delete_message(chat_id, msg_id).await?;
+    // Not cached, so the card is current and nobody else is shown the admin's answer.
+    bot.answer_inline_query(q.id, results)
+        .cache_time(0)
+        .is_personal(true)
+        .await?;
 
-                if chart_type == "earnings_chart" {
-                    send_earnings_chart(start_end, &bot, &q, gv_config).await?;
-                } else {
-                    send_barchart(start_end, &bot, &q, gv_config, division).await?;
-                }
-            }
+    Ok(())
+}
 
-            "tz_back" => {
-                let kb = make_timezone_region_keyboard();
+async fn send_barchart(
+    start_end: (u64, u64),
+    bot: &DefaultParseMode<Bot>,
+    q: &CallbackQuery,
+    gv_config: Arc<async_RwLock<GVConfig>>,
+    division: &str,
+    chat_tz: Option<String>,
+) -> ResponseResult<()> {
+    let kb = InlineKeyboardMarkup::default();
 
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+    let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+    let conf = gv_config.read().await;
 
-                let message = escape("👻 Timezone Updater 👻\n\nPlease select your region.");
+    let cli_caller_res = bot_caller(&selected_vault(&conf).cli_address).await;
 
-                bot.edit_message_text(chat_id, msg_id, message)
-                    .reply_markup(kb)
-                    .await?;
-            }
+    let cli_caller = match cli_caller_res {
+        Ok(cli) => cli,
+        Err(e) => {
+            let message = escape(format!("Error: {}", e).as_str());
+            bot.send_message(chat_id, message).await?;
+            return Ok(());
+        }
+    };
 
-            "back_to_stake_chart" => {
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+    let cli_res = match chat_tz {
+        Some(timezone) => {
+            cli_caller
+                .call_get_stake_barchart_data_tz(
+                    start_end.0,
+                    start_end.1,
+                    division.to_string(),
+                    timezone,
+                )
+                .await
+        }
+        None => {
+            cli_caller
+                .call_get_stake_barchart_data(start_end.0, start_end.1, division.to_string())
+                .await
+        }
+    };
 
-                let kb = make_inline_chart_menu();
+    let bc_data: BarChart = match cli_res {
+        Ok(resp) => resp,
+        Err(e) => {
+            let message = escape(format!("Error: {}", e).as_str());
+            bot.send_message(chat_id, message).await?;
+            return Ok(());
+        }
+    };
 
-                let message = escape("👻 Charts 👻\n\nPlease select a chart type");
+    if bc_data.data.is_empty() {
+        let message = escape("No data available for the selected range");
+        let kb = make_stats_info_keyboard();
 
-                bot.edit_message_text(chat_id, msg_id, message)
-                    .reply_markup(kb)
-                    .await?;
-            }
+        bot.send_message(chat_id, message).reply_markup(kb).await?;
+        return Ok(());
+    }
 
-            "stake_chart" => {
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+    // Rendering is CPU bound, keep it off the bot's async workers
+    let mk_chart = tokio::task::spawn_blocking(move || make_barchart(bc_data)).await;
 
-                let kb = make_inline_stakes_chart_menu();
+    match mk_chart {
+        Ok(Ok(png_bytes)) => {
+            let chart_file = InputFile::memory(png_bytes).file_name("barchart.png");
 
-                let message = escape("👻 Stake Charts 👻\n\nPlease select a divisor");
+            let message = escape("👻 Stake Chart 👻");
 
-                bot.edit_message_text(chat_id, msg_id, message)
-                    .reply_markup(kb)
-                    .await?;
-            }
+            bot.send_photo(chat_id, chart_file)
+                .caption(message)
+                .reply_markup(kb)
+                .await?;
+        }
+        Ok(Err(_)) => {
+            let message = escape("No data available for the selected range");
 
-            "earnings_chart" => {
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+            bot.send_message(chat_id, message).await?;
+        }
+        Err(_) => {
+            let message = escape("Error generating chart. Please try again later.");
 
-                let kb = make_inline_stake_chart_range_menu("earnings_chart".to_string());
+            bot.send_message(chat_id, message).await?;
+        }
+    }
 
-                let message = escape("👻 Earnings Charts 👻\n\nPlease select a range");
+    Ok(())
+}
 
-                bot.edit_message_text(chat_id, msg_id, message)
-                    .reply_markup(kb)
-                    .await?;
-            }
+async fn send_reward_simulation(
+    days: u64,
+    bot: &DefaultParseMode<Bot>,
+    q: &CallbackQuery,
+    gv_config: Arc<async_RwLock<GVConfig>>,
+) -> ResponseResult<()> {
+    let conf = gv_config.read().await;
+    let cli_address = selected_vault(&conf).cli_address;
+    drop(conf);
 
-            "stakes_day_chart" => {
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+    let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
 
-                let kb = make_inline_stake_chart_range_menu("stakes_day_chart".to_string());
+    let cli_caller = match bot_caller(&cli_address).await {
+        Ok(cli) => cli,
+        Err(e) => {
+            let message = escape(format!("Error: {}", e).as_str());
+            bot.send_message(chat_id, message).await?;
+            return Ok(());
+        }
+    };
 
-                let message = escape("👻 Range Selection 👻\n\nPlease select a range");
+    let simulation: RewardSimulation = match cli_caller.call_simulate_rewards(None, days).await {
+        Ok(resp) => resp,
+        Err(e) => {
+            let message = escape(format!("Error: {}", e).as_str());
+            bot.send_message(chat_id, message).await?;
+            return Ok(());
+        }
+    };
 
-                bot.edit_message_text(chat_id, msg_id, message)
-                    .reply_markup(kb)
-                    .await?;
-            }
-            "stakes_month_chart" => {
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+    let mut message: String = format!(
+        "👻 Reward Simulation 👻\n\nLast {} days: {} stakes, {} GHOST\nCurrent mode: {}\n",
+        simulation.days, simulation.stakes, simulation.rewards, simulation.current_mode
+    );
 
-                let kb = make_inline_stake_chart_range_menu("stakes_month_chart".to_string());
+    for mode in &simulation.modes {
+        message.push_str(&format!(
+            "\n{}\nReceived: {} GHOST{}\nPayouts: {}\nFees: {} GHOST\n",
+            mode.mode,
+            mode.received,
+            if mode.compounding {
+                ", kept staking"
+            } else {
+                ""
+            },
+            mode.payouts,
+            mode.fees
+        ));
 
-                let message = escape("👻 Range Selection 👻\n\nPlease select a range");
+        if mode.unpaid > Amount::ZERO {
+            message.push_str(&format!(
+                "Below the payout minimum: {} GHOST\n",
+                mode.unpaid
+            ));
+        }
+    }
 
-                bot.edit_message_text(chat_id, msg_id, message)
-                    .reply_markup(kb)
-                    .await?;
-            }
-            "stakes_week_chart" => {
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+    let fee_source: &str = if simulation.payout_fee_estimated {
+        "estimated from the fee rate"
+    } else {
+        "the average of your past payouts"
+    };
+    message.push_str(&format!(
+        "\nANON payouts cost {} GHOST each, {}. Uses your current reward interval and payout minimum.",
+        simulation.payout_fee, fee_source
+    ));
 
-                let kb = make_inline_stake_chart_range_menu("stakes_week_chart".to_string());
+    bot.send_message(chat_id, escape(&message))
+        .reply_markup(make_keyboard_reward_options())
+        .await?;
 
-                let message = escape("👻 Range Selection 👻\n\nPlease select a range");
+    Ok(())
+}
 
-                bot.edit_message_text(chat_id, msg_id, message)
-                    .reply_markup(kb)
-                    .await?;
-            }
+async fn send_cold_wallet_chart(
+    index: usize,
+    bot: &DefaultParseMode<Bot>,
+    q: &CallbackQuery,
+    gv_config: Arc<async_RwLock<GVConfig>>,
+) -> ResponseResult<()> {
+    let conf = gv_config.read().await;
+    let cli_address = selected_vault(&conf).cli_address;
+    drop(conf);
 
-            "cancel_select_tz" => {
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+    let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
 
-                let kb = make_keyboard_bot_settings();
+    let cli_caller = match bot_caller(&cli_address).await {
+        Ok(cli) => cli,
+        Err(e) => {
+            let message = escape(format!("Error: {}", e).as_str());
+            bot.send_message(chat_id, message).await?;
+            return Ok(());
+        }
+    };
 
-                bot.send_message(chat_id, "Cancelled")
-                    .reply_markup(kb)
-                    .await?;
+    let wallets: ColdWallets = match get_cold_wallets(&cli_caller).await {
+        Ok(wallets) => wallets,
+        Err(e) => {
+            let message = escape(format!("Error: {}", e).as_str());
+            bot.send_message(chat_id, message).await?;
+            return Ok(());
+        }
+    };
 
-                last_dialog_id.store(0, Ordering::Relaxed);
-                bot.delete_message(chat_id, msg_id).await?;
-            }
+    let wallet = match wallets.wallets.get(index) {
+        Some(wallet) => wallet.clone(),
+        None => return Ok(()),
+    };
 
-            "cancel_select_chart" => {
-                let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
-                let msg_id = q.message.as_ref().unwrap().id;
+    let end: u64 = chrono::Utc::now().timestamp() as u64;
+    let start: u64 = end.saturating_sub(COLD_WALLET_CHART_DAYS * 86400);
 
-                let kb = make_stats_info_keyboard();
+    let cli_value = match cli_caller
+        .call_get_cold_wallet_stakes(wallet.name.clone(), start, end, "day".to_string())
+        .await
+    {
+        Ok(resp) => resp,
+        Err(e) => {
+            let message = escape(format!("Error: {}", e).as_str());
+            bot.send_message(chat_id, message).await?;
+            return Ok(());
+        }
+    };
 
-                bot.send_message(chat_id, "Cancelled")
-                    .reply_markup(kb)
-                    .await?;
+    let caption: String = format!(
+        "👛 {}{} 👛
+
+Stakes per day, last {} days
+All time: {} stakes, {} GHOST
+Payouts: {}, {} GHOST",
+        wallet.name,
+        if wallet.primary { " (primary)" } else { "" },
+        COLD_WALLET_CHART_DAYS,
+        wallet.stakes,
+        wallet.rewards,
+        wallet.payouts,
+        wallet.paid
+    );
+    let kb = make_inline_cold_wallet_options(index, wallet.primary);
 
-                last_dialog_id.store(0, Ordering::Relaxed);
-                bot.delete_message(chat_id, msg_id).await?;
+    // Rendering is CPU bound, keep it off the bot's async workers
+    let mk_chart = tokio::task::spawn_blocking(move || make_barchart(cli_value)).await;
 
-                let chart_range_dialog = GetDateRangeDialog::new(chart_range_mem.clone(), chat_id);
+    match mk_chart {
+        Ok(Ok(png_bytes)) => {
+            let chart_file = InputFile::memory(png_bytes).file_name("cold_wallet_stakes.png");
 
-                if chart_range_dialog.get().await.unwrap().is_some() {
-                    chart_range_dialog.exit().await.unwrap();
-                }
-            }
+            bot.send_photo(chat_id, chart_file)
+                .caption(escape(&caption))
+                .reply_markup(kb)
+                .await?;
+        }
+        Ok(Err(_)) => {
+            let message = escape(&format!(
+                "{}\n\nNo stakes in the last {} days",
+                caption, COLD_WALLET_CHART_DAYS
+            ));
 
-            " " | "\t\t" => {
-                bot.answer_callback_query(q.id).await?;
-            }
-            _ => {}
+            bot.send_message(chat_id, message).reply_markup(kb).await?;
+        }
+        Err(_) => {
+            let message = escape("Error generating chart. Please try again later.");
+
+            bot.send_message(chat_id, message).await?;
         }
     }
 
     Ok(())
 }
 
-async fn get_bot_settings(gv_config: &Arc<async_RwLock<GVConfig>>) -> String {
+async fn send_payout_history(
+    start_end: (u64, u64),
+    bot: &DefaultParseMode<Bot>,
+    q: &CallbackQuery,
+    gv_config: Arc<async_RwLock<GVConfig>>,
+) -> ResponseResult<()> {
     let conf = gv_config.read().await;
-    let stake_announce = if conf.announce_stakes {
-        "Stake announcments: ✅\n"
-    } else {
-        "Stake announcments: ❌\n"
-    };
+    let cli_address = selected_vault(&conf).cli_address;
+    drop(conf);
 
-    let reward_announce = if conf.announce_rewards {
-        "Reward announcments: ✅\n"
-    } else {
-        "Reward announcments: ❌\n"
-    };
+    let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
 
-    let zap_announce = if conf.announce_zaps {
-        "Zap announcments: ✅\n"
-    } else {
-        "Zap announcments: ❌\n"
+    let cli_caller_res = bot_caller(&cli_address).await;
+
+    let cli_caller = match cli_caller_res {
+        Ok(cli) => cli,
+        Err(e) => {
+            let message = escape(format!("Error: {}", e).as_str());
+            bot.send_message(chat_id, message).await?;
+            return Ok(());
+        }
     };
 
-    let timezone = conf.timezone.clone().to_uppercase();
+    let cli_res = cli_caller
+        .call_get_payout_history(start_end.0, start_end.1)
+        .await;
 
-    let reply = escape(
+    let history: PayoutHistory = match cli_res {
+        Ok(resp) => resp,
+        Err(e) => {
+            let message = escape(format!("Error: {}", e).as_str());
+            bot.send_message(chat_id, message).await?;
+            return Ok(());
+        }
+    };
+
+    let header: String = escape(
         format!(
-            "Bot Settings\n\n{}{}{}\nTimezone: {}",
-            stake_announce, reward_announce, zap_announce, timezone
+            "👻 Payout History 👻\n\n{} - {}\nPayouts: {}\nTotal Paid: {} GHOST\nTotal Fees: {} GHOST\n\n",
+            history.start, history.end, history.payout_count, history.total_paid, history.total_fees
         )
         .as_str(),
     );
 
-    reply
-}
-
-pub fn server_unready_message(server_ready: &ServerReadyDB) -> String {
-    let reason = server_ready.reason.clone().unwrap_or("".to_string());
-    let res = match reason.as_str() {
-        "Daemon offline" => escape("Ghostd is offline. Please wait for it to start."),
-        "Daemon update in progress" => {
-            escape("Ghostd update in progress. Please wait for it to finish before making changes.")
+    // Keep the reply under the Telegram message size limit
+    let mut recent: Vec<Value> = Vec::new();
+    for payout in history.payouts.iter().rev().take(10) {
+        let mut entry: Value = serde_json::json!({
+            "date": payout.date,
+            "txid": payout.txid,
+            "amount": payout.amount,
+            "fee": payout.fee,
+            "mode": payout.mode,
+        });
+
+        if !payout.label.is_empty() {
+            entry["label"] = Value::String(payout.label.clone());
         }
-        "Importing Wallet" => escape("Importing wallet. Please wait for it to finish."),
-        "Forcing resync" => {
-            escape("Forcing resync. Please wait for it to finish before making changes.")
+
+        if payout.destination_label.is_some() {
+            entry["to"] = Value::String(labeled_address(
+                &payout.destination,
+                payout.destination_label.as_deref(),
+            ));
         }
-        _ => escape("Unknown error! Please try again later."),
+
+        recent.push(entry);
+    }
+
+    let message: String = if recent.is_empty() {
+        format!("{}{}", header, escape("No payouts found for this range."))
+    } else {
+        let payouts: String = serde_json::to_string_pretty(&recent).unwrap();
+        let code_block: String = format!("```\n{}\n```\n", payouts);
+        format!(
+            "{}{}{}",
+            header,
+            escape("Most recent payouts:\n"),
+            code_block
+        )
     };
 
-    res
+    let kb = make_stats_info_keyboard();
+
+    bot.send_message(chat_id, message).reply_markup(kb).await?;
+
+    Ok(())
 }
 
-async fn reply_status(
+async fn send_earnings_chart(
+    start_end: (u64, u64),
     bot: &DefaultParseMode<Bot>,
-    msg: &Message,
-    gv_config: &Arc<async_RwLock<GVConfig>>,
-) -> ResponseResult<Message> {
+    q: &CallbackQuery,
+    gv_config: Arc<async_RwLock<GVConfig>>,
+    chat_tz: Option<String>,
+) -> ResponseResult<()> {
+    let kb = InlineKeyboardMarkup::default();
+
+    let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
     let conf = gv_config.read().await;
-    let cli_address = conf.to_owned().cli_address;
-    drop(conf);
 
-    let cli_caller_res = CLICaller::new(&cli_address, true).await;
+    let cli_caller_res = bot_caller(&selected_vault(&conf).cli_address).await;
 
     let cli_caller = match cli_caller_res {
         Ok(cli) => cli,
         Err(e) => {
             let message = escape(format!("Error: {}", e).as_str());
-            bot.send_message(msg.chat.id, message).await?;
-            return Ok(msg.clone());
+            bot.send_message(chat_id, message).await?;
+            return Ok(());
         }
     };
 
-    let cli_res = cli_caller.call_get_daemon_state().await;
-    let cli_value = match cli_res {
+    let chart_data_res = match chat_tz {
+        Some(timezone) => {
+            cli_caller
+                .call_get_earnings_chart_data_tz(start_end.0, start_end.1, timezone)
+                .await
+        }
+        None => {
+            cli_caller
+                .call_get_earnings_chart_data(start_end.0, start_end.1)
+                .await
+        }
+    };
+
+    let chart_data = match chart_data_res {
         Ok(resp) => resp,
         Err(e) => {
             let message = escape(format!("Error: {}", e).as_str());
-            bot.send_message(msg.chat.id, message).await?;
-            return Ok(msg.clone());
+            bot.send_message(chat_id, message).await?;
+            return Ok(());
         }
     };
-    let status: GVStatus = serde_json::from_value(cli_value.clone()).unwrap();
-    let pretty_string = serde_json::to_string_pretty(&status).unwrap();
-    let reply_escaped = escape(format!("{}", pretty_string).as_str());
-    let header: String = escape(format!("👻 GhostVault Status 👻").as_str());
-    let reply: String = format!("{}\n\n```\n{}\n```", header, reply_escaped);
-    let msg: Message = bot.send_message(msg.chat.id, reply).await?;
 
-    Ok(msg)
+    // Rendering is CPU bound, keep it off the bot's async workers
+    let mk_chart = tokio::task::spawn_blocking(move || make_area_chart(chart_data)).await;
+
+    match mk_chart {
+        Ok(Ok(png_bytes)) => {
+            let chart_file = InputFile::memory(png_bytes).file_name("total_earnings_chart.png");
+
+            let message = escape("👻 Earnings Chart 👻");
+
+            bot.send_photo(chat_id, chart_file)
+                .caption(message)
+                .reply_markup(kb)
+                .await?;
+        }
+        Ok(Err(_)) => {
+            let message = escape("No data available for the selected range");
+
+            bot.send_message(chat_id, message).await?;
+        }
+        Err(_) => {
+            let message = escape("Error generating chart. Please try again later.");
+
+            bot.send_message(chat_id, message).await?;
+        }
+    }
+
+    Ok(())
 }
 
-async fn send_barchart(
+async fn send_heatmap(
     start_end: (u64, u64),
     bot: &DefaultParseMode<Bot>,
     q: &CallbackQuery,
     gv_config: Arc<async_RwLock<GVConfig>>,
-    division: &str,
+    chat_tz: Option<String>,
 ) -> ResponseResult<()> {
-    let kb = InlineKeyboardMarkup::default();
+    let kb = make_stats_info_keyboard();
 
     let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
     let conf = gv_config.read().await;
+    let cli_address = selected_vault(&conf).cli_address;
+    drop(conf);
 
-    let cli_caller_res = CLICaller::new(&conf.cli_address, true).await;
+    let cli_caller_res = bot_caller(&cli_address).await;
 
     let cli_caller = match cli_caller_res {
         Ok(cli) => cli,
@@ -1462,9 +4117,18 @@ async fn send_barchart(
         }
     };
 
-    let cli_res = cli_caller
-        .call_get_stake_barchart_data(start_end.0, start_end.1, division.to_string())
-        .await;
+    let cli_res = match chat_tz {
+        Some(timezone) => {
+            cli_caller
+                .call_get_stake_heatmap_data_tz(start_end.0, start_end.1, timezone)
+                .await
+        }
+        None => {
+            cli_caller
+                .call_get_stake_heatmap_data(start_end.0, start_end.1)
+                .await
+        }
+    };
 
     let cli_value = match cli_res {
         Ok(resp) => resp,
@@ -1475,61 +4139,115 @@ async fn send_barchart(
         }
     };
 
-    let bc_data: BarChart = serde_json::from_value(cli_value.to_owned()).unwrap();
-    let data = bc_data.data;
+    // Rendering is CPU bound, keep it off the bot's async workers
+    let mk_chart = tokio::task::spawn_blocking(move || make_heatmap(cli_value)).await;
 
-    if data.is_empty() {
-        let message = escape("No data available for the selected range");
-        let kb = make_stats_info_keyboard();
+    match mk_chart {
+        Ok(Ok(png_bytes)) => {
+            let chart_file = InputFile::memory(png_bytes).file_name("stake_heatmap.png");
 
-        bot.send_message(chat_id, message).reply_markup(kb).await?;
-        return Ok(());
+            let message = escape("👻 Stake Calendar 👻");
+
+            bot.send_photo(chat_id, chart_file)
+                .caption(message)
+                .reply_markup(kb)
+                .await?;
+        }
+        Ok(Err(_)) => {
+            let message = escape("No data available for the selected range");
+
+            bot.send_message(chat_id, message).reply_markup(kb).await?;
+        }
+        Err(_) => {
+            let message = escape("Error generating chart. Please try again later.");
+
+            bot.send_message(chat_id, message).await?;
+        }
     }
 
-    let mk_chart = make_barchart(&cli_value);
+    Ok(())
+}
 
-    if mk_chart.is_err() {
-        let message = escape("No data available for the selected range");
+async fn send_balance_chart(
+    start_end: (u64, u64),
+    bot: &DefaultParseMode<Bot>,
+    q: &CallbackQuery,
+    gv_config: Arc<async_RwLock<GVConfig>>,
+) -> ResponseResult<()> {
+    let kb = make_stats_info_keyboard();
 
-        bot.send_message(chat_id, message).await?;
-    } else {
-        let _ = mk_chart.unwrap();
+    let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
+    let conf = gv_config.read().await;
+    let cli_address = selected_vault(&conf).cli_address;
+    drop(conf);
 
-        let chart_path = PathBuf::from("/tmp/barchart.png");
+    let cli_caller_res = bot_caller(&cli_address).await;
 
-        if !chart_path.exists() {
-            let message = escape("Error generating chart. Please try again later.");
+    let cli_caller = match cli_caller_res {
+        Ok(cli) => cli,
+        Err(e) => {
+            let message = escape(format!("Error: {}", e).as_str());
+            bot.send_message(chat_id, message).await?;
+            return Ok(());
+        }
+    };
+
+    let cli_res = cli_caller
+        .call_get_balance_history(start_end.0, start_end.1)
+        .await;
 
+    let cli_value = match cli_res {
+        Ok(resp) => resp,
+        Err(e) => {
+            let message = escape(format!("Error: {}", e).as_str());
             bot.send_message(chat_id, message).await?;
-        } else {
-            let chart_file = InputFile::file(chart_path.clone());
+            return Ok(());
+        }
+    };
 
-            let message = escape("👻 Stake Chart 👻");
+    // Rendering is CPU bound, keep it off the bot's async workers
+    let mk_chart = tokio::task::spawn_blocking(move || make_balance_chart(cli_value)).await;
+
+    match mk_chart {
+        Ok(Ok(png_bytes)) => {
+            let chart_file = InputFile::memory(png_bytes).file_name("balance_history.png");
+
+            let message = escape("👻 Balance History 👻");
 
             bot.send_photo(chat_id, chart_file)
                 .caption(message)
                 .reply_markup(kb)
                 .await?;
+        }
+        Ok(Err(_)) => {
+            let message = escape("No data available for the selected range");
+
+            bot.send_message(chat_id, message).reply_markup(kb).await?;
+        }
+        Err(_) => {
+            let message = escape("Error generating chart. Please try again later.");
 
-            file_ops::rm_file(&chart_path).unwrap();
+            bot.send_message(chat_id, message).await?;
         }
     }
 
     Ok(())
 }
 
-async fn send_earnings_chart(
+async fn send_network_chart(
     start_end: (u64, u64),
     bot: &DefaultParseMode<Bot>,
     q: &CallbackQuery,
     gv_config: Arc<async_RwLock<GVConfig>>,
 ) -> ResponseResult<()> {
-    let kb = InlineKeyboardMarkup::default();
+    let kb = make_stats_info_keyboard();
 
     let chat_id: ChatId = q.message.as_ref().unwrap().chat.id;
     let conf = gv_config.read().await;
+    let cli_address = selected_vault(&conf).cli_address;
+    drop(conf);
 
-    let cli_caller_res = CLICaller::new(&conf.cli_address, true).await;
+    let cli_caller_res = bot_caller(&cli_address).await;
 
     let cli_caller = match cli_caller_res {
         Ok(cli) => cli,
@@ -1540,11 +4258,11 @@ async fn send_earnings_chart(
         }
     };
 
-    let chart_data_res = cli_caller
-        .call_get_earnings_chart_data(start_end.0, start_end.1)
+    let cli_res = cli_caller
+        .call_get_network_stats(start_end.0, start_end.1)
         .await;
 
-    let chart_data = match chart_data_res {
+    let stats: NetworkStats = match cli_res {
         Ok(resp) => resp,
         Err(e) => {
             let message = escape(format!("Error: {}", e).as_str());
@@ -1553,32 +4271,40 @@ async fn send_earnings_chart(
         }
     };
 
-    let mk_chart = make_area_chart(&chart_data);
-
-    if mk_chart.is_err() {
-        let message = escape("No data available for the selected range");
-
-        bot.send_message(chat_id, message).await?;
-    } else {
-        let _ = mk_chart.unwrap();
-
-        let chart_path = PathBuf::from("/tmp/total_earnings_chart.png");
+    let change = |change: Option<f64>| -> String {
+        change.map_or("N/A".to_string(), |change| format!("{:+.2}%", change))
+    };
+    let caption: String = format!(
+        "👻 My Weight vs Network 👻\n\nShare of network weight: {:.4}%\nAverage share: {:.4}%\nNetwork weight change: {}\nDifficulty change: {}",
+        stats.stake_share * 100.0,
+        stats.average_share * 100.0,
+        change(stats.network_weight_change),
+        change(stats.difficulty_change)
+    );
 
-        if !chart_path.exists() {
-            let message = escape("Error generating chart. Please try again later.");
+    // Rendering is CPU bound, keep it off the bot's async workers
+    let mk_chart = tokio::task::spawn_blocking(move || make_network_chart(stats)).await;
 
-            bot.send_message(chat_id, message).await?;
-        } else {
-            let chart_file = InputFile::file(chart_path.clone());
+    match mk_chart {
+        Ok(Ok(png_bytes)) => {
+            let chart_file = InputFile::memory(png_bytes).file_name("network_weight.png");
 
-            let message = escape("👻 Earnings Chart 👻");
+            let message = escape(&caption);
 
             bot.send_photo(chat_id, chart_file)
                 .caption(message)
                 .reply_markup(kb)
                 .await?;
+        }
+        Ok(Err(_)) => {
+            let message = escape("No data available for the selected range");
+
+            bot.send_message(chat_id, message).reply_markup(kb).await?;
+        }
+        Err(_) => {
+            let message = escape("Error generating chart. Please try again later.");
 
-            file_ops::rm_file(&chart_path).unwrap();
+            bot.send_message(chat_id, message).await?;
         }
     }
 
@@ -1594,6 +4320,11 @@ pub async fn run_tg_bot(config_clone_tg_bot: Arc<async_RwLock<GVConfig>>, db: Ar
     info!("Starting Telegram bot...");
     let bot: DefaultParseMode<Bot> = Bot::from_env().parse_mode(ParseMode::MarkdownV2);
 
+    // The menu next to the message box, failing to set it only costs the menu.
+    if let Err(err) = bot.set_my_commands(menu_commands()).await {
+        warn!("Unable to register the bot's commands: {:?}", err);
+    }
+
     let commands_db: Arc<GVDB> = Arc::clone(&db);
     let bot_runner_db: Arc<GVDB> = Arc::clone(&db);
 
@@ -1616,22 +4347,16 @@ pub async fn run_tg_bot(config_clone_tg_bot: Arc<async_RwLock<GVConfig>>, db: Ar
              db: Arc<GVDB>,
              msg: Message,
              last_dialog_id: Arc<AtomicI32>,
-             reward_mode_mem: Arc<InMemStorage<UpdateRewardModeState>>,
-             reward_interval_mem: Arc<InMemStorage<UpdateRewardIntervalState>>,
-             reward_min_mem: Arc<InMemStorage<UpdateRewardMinState>>,
-             chart_range_mem: Arc<InMemStorage<GetDateRangeState>>| async move {
-                command_handler(
-                    bot,
-                    msg,
-                    gv_config,
-                    db,
-                    reward_mode_mem,
-                    last_dialog_id,
-                    reward_interval_mem,
-                    reward_min_mem,
-                    chart_range_mem,
-                )
-                .await?;
+             dialogs: DialogStorage| async move {
+                command_handler(bot, msg, gv_config, db, last_dialog_id, dialogs).await?;
+                respond(())
+            },
+        ))
+        .branch(Update::filter_inline_query().endpoint(
+            |bot: DefaultParseMode<Bot>,
+             gv_config: Arc<async_RwLock<GVConfig>>,
+             inline_query: InlineQuery| async move {
+                inline_query_handler(bot, inline_query, gv_config).await?;
                 respond(())
             },
         ))
@@ -1641,45 +4366,37 @@ pub async fn run_tg_bot(config_clone_tg_bot: Arc<async_RwLock<GVConfig>>, db: Ar
              db: Arc<GVDB>,
              callback_query: CallbackQuery,
              last_dialog_id: Arc<AtomicI32>,
-             reward_mode_mem: Arc<InMemStorage<UpdateRewardModeState>>,
-             reward_interval_mem: Arc<InMemStorage<UpdateRewardIntervalState>>,
-             reward_min_mem: Arc<InMemStorage<UpdateRewardMinState>>,
-             chart_range_mem: Arc<InMemStorage<GetDateRangeState>>| async move {
-                callback_handler(
-                    bot,
-                    callback_query,
-                    gv_config,
-                    db,
-                    reward_mode_mem,
-                    last_dialog_id,
-                    reward_interval_mem,
-                    reward_min_mem,
-                    chart_range_mem,
-                )
-                .await?;
+             dialogs: DialogStorage| async move {
+                callback_handler(bot, callback_query, gv_config, db, last_dialog_id, dialogs)
+                    .await?;
                 respond(())
             },
         ));
 
-    let reward_mode_mem: Arc<InMemStorage<UpdateRewardModeState>> =
-        InMemStorage::<UpdateRewardModeState>::new();
-    let reward_interval_mem: Arc<InMemStorage<UpdateRewardIntervalState>> =
-        InMemStorage::<UpdateRewardIntervalState>::new();
-    let reward_min_mem: Arc<InMemStorage<UpdateRewardMinState>> =
-        InMemStorage::<UpdateRewardMinState>::new();
-    let chart_range_mem: Arc<InMemStorage<GetDateRangeState>> =
-        InMemStorage::<GetDateRangeState>::new();
+    let dialogs = DialogStorage {
+        reward_mode: InMemStorage::<UpdateRewardModeState>::new(),
+        reward_interval: InMemStorage::<UpdateRewardIntervalState>::new(),
+        reward_min: InMemStorage::<UpdateRewardMinState>::new(),
+        chart_range: InMemStorage::<GetDateRangeState>::new(),
+        onboarding: InMemStorage::<OnboardingState>::new(),
+    };
+
+    // Offers setup to a vault whose reward mode was never chosen.
+    tokio::spawn(offer_onboarding(
+        bot.clone(),
+        Arc::clone(&bot_conf),
+        Arc::clone(&db),
+        Arc::clone(&dialogs.onboarding),
+        Arc::clone(&last_dialog_id),
+    ));
 
     Dispatcher::builder(bot.clone(), handler)
         // Pass the shared state to the handler as a dependency.
         .dependencies(dptree::deps![
             bot_conf,
             commands_db,
-            reward_mode_mem,
             last_dialog_id,
-            reward_interval_mem,
-            reward_min_mem,
-            chart_range_mem
+            dialogs
         ])
         .enable_ctrlc_handler()
         .build()
@@ -0,0 +1,340 @@
+// Date ranges and chart buckets in the vault's timezone, shared by ghostvaultd, the bot and
+// gv-cli so every frontend draws the same bars. Periods are stepped on the local calendar and
+// never by adding seconds, so a bucket stays one whole local day, week or month when DST
+// starts or ends inside it. Weeks start on Sunday.
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Division {
+    Day,
+    Week,
+    Month,
+}
+
+impl Division {
+    pub const ALL: [Division; 3] = [Division::Day, Division::Week, Division::Month];
+
+    pub fn from_name(name: &str) -> Option<Division> {
+        match name.to_lowercase().as_str() {
+            "day" => Some(Division::Day),
+            "week" => Some(Division::Week),
+            "month" => Some(Division::Month),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Division::Day => "day",
+            Division::Week => "week",
+            Division::Month => "month",
+        }
+    }
+
+    // First local date of the period the date falls in.
+    pub fn first_date(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Division::Day => date,
+            Division::Week => date - Days::new(date.weekday().num_days_from_sunday() as u64),
+            Division::Month => date.with_day(1).unwrap(),
+        }
+    }
+
+    // First local date of the period after the one the date falls in.
+    pub fn next_date(&self, date: NaiveDate) -> NaiveDate {
+        let first: NaiveDate = self.first_date(date);
+
+        match self {
+            Division::Day => first + Days::new(1),
+            Division::Week => first + Days::new(7),
+            Division::Month => first + Months::new(1),
+        }
+    }
+
+    // Start of the local period the timestamp falls in.
+    pub fn period_start(&self, tz: &Tz, timestamp: u64) -> u64 {
+        local_day_start(tz, self.first_date(local_date(tz, timestamp)))
+    }
+
+    // Start of the local period after the one the timestamp falls in.
+    pub fn next_start(&self, tz: &Tz, timestamp: u64) -> u64 {
+        local_day_start(tz, self.next_date(local_date(tz, timestamp)))
+    }
+}
+
+pub fn local_date(tz: &Tz, timestamp: u64) -> NaiveDate {
+    DateTime::from_timestamp(timestamp as i64, 0)
+        .unwrap_or_default()
+        .with_timezone(tz)
+        .date_naive()
+}
+
+// The instant a local wall-clock time names. A time DST skips resolves to the moment the clocks
+// jump, and a time that happens twice to the first one.
+pub fn local_instant(tz: &Tz, local: NaiveDateTime) -> u64 {
+    if let Some(instant) = tz.from_local_datetime(&local).earliest() {
+        return instant.timestamp() as u64;
+    }
+
+    // Clocks only ever jump on a whole minute, and never by more than a few hours.
+    let minute: NaiveDateTime = local.with_second(0).unwrap_or(local);
+
+    (1..=180)
+        .find_map(|minutes| {
+            tz.from_local_datetime(&(minute + chrono::Duration::minutes(minutes)))
+                .earliest()
+        })
+        .map_or_else(
+            || tz.from_utc_datetime(&local).timestamp() as u64,
+            |instant| instant.timestamp() as u64,
+        )
+}
+
+// Start of a local calendar day. Where DST skips midnight the day starts when the clocks do,
+// and where midnight happens twice it's the first one.
+pub fn local_day_start(tz: &Tz, date: NaiveDate) -> u64 {
+    local_instant(tz, date.and_hms_opt(0, 0, 0).unwrap())
+}
+
+// Last second of a local calendar day.
+pub fn local_day_end(tz: &Tz, date: NaiveDate) -> u64 {
+    let next: NaiveDate = date.succ_opt().unwrap_or(date);
+    local_day_start(tz, next).saturating_sub(1)
+}
+
+// Start of the local year the timestamp falls in.
+pub fn year_start(tz: &Tz, timestamp: u64) -> u64 {
+    let date: NaiveDate = local_date(tz, timestamp);
+    local_day_start(tz, date.with_ordinal(1).unwrap())
+}
+
+// The [start, next start) of every period from the one holding start to the one holding end.
+pub fn buckets(division: Division, tz: &Tz, start: u64, end: u64) -> Vec<(u64, u64)> {
+    let mut buckets: Vec<(u64, u64)> = Vec::new();
+
+    if start > end {
+        return buckets;
+    }
+
+    let last: NaiveDate = division.first_date(local_date(tz, end));
+    let mut date: NaiveDate = division.first_date(local_date(tz, start));
+
+    while date <= last {
+        let next: NaiveDate = division.next_date(date);
+        buckets.push((local_day_start(tz, date), local_day_start(tz, next)));
+        date = next;
+    }
+
+    buckets
+}
+
+// How many of the timestamps fall in each period, keyed by the period start. Periods without
+// any are left out.
+pub fn bucket_counts(
+    division: Division,
+    tz: &Tz,
+    timestamps: impl IntoIterator<Item = u64>,
+) -> BTreeMap<u64, u64> {
+    let mut counts: BTreeMap<u64, u64> = BTreeMap::new();
+
+    for timestamp in timestamps {
+        *counts
+            .entry(division.period_start(tz, timestamp))
+            .or_insert(0) += 1;
+    }
+
+    counts
+}
+
+// The ranges offered in the bot's chart menus, ending now. Unknown names are the last two weeks.
+pub fn named_range(range: &str, tz: &Tz) -> (u64, u64) {
+    range_ending(range, tz, Utc::now())
+}
+
+// Months and days are taken off the local wall clock, so a month back from 9am is 9am even
+// across a DST change. Where that time doesn't exist the range starts when the clocks jump.
+fn range_ending(range: &str, tz: &Tz, now: DateTime<Utc>) -> (u64, u64) {
+    let local: NaiveDateTime = now.with_timezone(tz).naive_local();
+    let end: u64 = now.timestamp() as u64;
+
+    let months_back = |months: u32| -> u64 {
+        local
+            .checked_sub_months(Months::new(months))
+            .map_or(0, |start| local_instant(tz, start))
+    };
+
+    let start: u64 = match range {
+        "last_month" => months_back(1),
+        "last_three_months" => months_back(3),
+        "last_six_months" => months_back(6),
+        "last_year" => months_back(12),
+        "year_to_date" => year_start(tz, end),
+        "max" => 0,
+        _ => local
+            .checked_sub_days(Days::new(14))
+            .map_or(0, |start| local_instant(tz, start)),
+    };
+
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> u64 {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+            .timestamp() as u64
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    const HOUR: u64 = 3600;
+
+    #[test]
+    fn day_buckets_across_dst() {
+        let tz: Tz = chrono_tz::America::New_York;
+
+        let spring = buckets(
+            Division::Day,
+            &tz,
+            utc(2024, 3, 9, 12, 0),
+            utc(2024, 3, 11, 12, 0),
+        );
+        assert_eq!(spring.len(), 3);
+        assert_eq!(spring[0], (utc(2024, 3, 9, 5, 0), utc(2024, 3, 10, 5, 0)));
+        assert_eq!(spring[1], (utc(2024, 3, 10, 5, 0), utc(2024, 3, 11, 4, 0)));
+        assert_eq!(spring[1].1 - spring[1].0, 23 * HOUR);
+
+        let fall = buckets(
+            Division::Day,
+            &tz,
+            utc(2024, 11, 3, 12, 0),
+            utc(2024, 11, 3, 12, 0),
+        );
+        assert_eq!(fall, vec![(utc(2024, 11, 3, 4, 0), utc(2024, 11, 4, 5, 0))]);
+        assert_eq!(fall[0].1 - fall[0].0, 25 * HOUR);
+    }
+
+    #[test]
+    fn week_buckets_across_dst() {
+        let tz: Tz = chrono_tz::America::New_York;
+
+        // Both changes fall on a Sunday, so each starts a week.
+        let spring = buckets(
+            Division::Week,
+            &tz,
+            utc(2024, 3, 12, 12, 0),
+            utc(2024, 3, 12, 12, 0),
+        );
+        assert_eq!(
+            spring,
+            vec![(utc(2024, 3, 10, 5, 0), utc(2024, 3, 17, 4, 0))]
+        );
+        assert_eq!(spring[0].1 - spring[0].0, (7 * 24 - 1) * HOUR);
+
+        let fall = buckets(
+            Division::Week,
+            &tz,
+            utc(2024, 11, 1, 12, 0),
+            utc(2024, 11, 5, 12, 0),
+        );
+        assert_eq!(fall.len(), 2);
+        assert_eq!(fall[0], (utc(2024, 10, 27, 4, 0), utc(2024, 11, 3, 4, 0)));
+        assert_eq!(fall[1], (utc(2024, 11, 3, 4, 0), utc(2024, 11, 10, 5, 0)));
+        assert_eq!(fall[1].1 - fall[1].0, (7 * 24 + 1) * HOUR);
+    }
+
+    #[test]
+    fn month_buckets_across_dst() {
+        let tz: Tz = chrono_tz::America::New_York;
+
+        let months = buckets(
+            Division::Month,
+            &tz,
+            utc(2024, 3, 15, 0, 0),
+            utc(2024, 11, 15, 0, 0),
+        );
+        assert_eq!(months.len(), 9);
+        assert_eq!(months[0], (utc(2024, 3, 1, 5, 0), utc(2024, 4, 1, 4, 0)));
+        assert_eq!(months[0].1 - months[0].0, (31 * 24 - 1) * HOUR);
+        assert_eq!(months[8], (utc(2024, 11, 1, 4, 0), utc(2024, 12, 1, 5, 0)));
+        assert_eq!(months[8].1 - months[8].0, (30 * 24 + 1) * HOUR);
+
+        let counts = bucket_counts(
+            Division::Month,
+            &tz,
+            [
+                utc(2024, 3, 1, 4, 59),
+                utc(2024, 3, 1, 5, 0),
+                utc(2024, 4, 1, 3, 59),
+            ],
+        );
+        assert_eq!(counts.get(&utc(2024, 2, 1, 5, 0)), Some(&1));
+        assert_eq!(counts.get(&utc(2024, 3, 1, 5, 0)), Some(&2));
+    }
+
+    #[test]
+    fn day_start_when_dst_skips_or_repeats_midnight() {
+        // Cuba moves its clocks at midnight, skipping it in March and repeating it in November.
+        let tz: Tz = chrono_tz::America::Havana;
+
+        assert_eq!(
+            local_day_start(&tz, date(2024, 3, 10)),
+            utc(2024, 3, 10, 5, 0)
+        );
+        assert_eq!(
+            local_day_end(&tz, date(2024, 3, 9)),
+            utc(2024, 3, 10, 5, 0) - 1
+        );
+        assert_eq!(
+            local_day_start(&tz, date(2024, 11, 3)),
+            utc(2024, 11, 3, 4, 0)
+        );
+
+        let days = buckets(
+            Division::Day,
+            &tz,
+            utc(2024, 3, 10, 12, 0),
+            utc(2024, 3, 10, 12, 0),
+        );
+        assert_eq!(days, vec![(utc(2024, 3, 10, 5, 0), utc(2024, 3, 11, 4, 0))]);
+    }
+
+    #[test]
+    fn named_range_at_dst_edge() {
+        let tz: Tz = chrono_tz::America::New_York;
+
+        // A month before 02:30 on April 10th is inside the hour skipped on March 10th.
+        let now = Utc.with_ymd_and_hms(2024, 4, 10, 6, 30, 0).unwrap();
+        assert_eq!(
+            range_ending("last_month", &tz, now),
+            (utc(2024, 3, 10, 7, 0), utc(2024, 4, 10, 6, 30))
+        );
+
+        let now = Utc.with_ymd_and_hms(2024, 3, 24, 6, 30, 0).unwrap();
+        assert_eq!(
+            range_ending("last_two_weeks", &tz, now).0,
+            utc(2024, 3, 10, 7, 0)
+        );
+
+        // A month before 01:30 on December 3rd happened twice on November 3rd, the first counts.
+        let now = Utc.with_ymd_and_hms(2024, 12, 3, 6, 30, 0).unwrap();
+        assert_eq!(
+            range_ending("last_month", &tz, now).0,
+            utc(2024, 11, 3, 5, 30)
+        );
+
+        let now = Utc.with_ymd_and_hms(2024, 3, 10, 7, 30, 0).unwrap();
+        assert_eq!(
+            range_ending("year_to_date", &tz, now).0,
+            utc(2024, 1, 1, 5, 0)
+        );
+        assert_eq!(range_ending("max", &tz, now).0, 0);
+    }
+}